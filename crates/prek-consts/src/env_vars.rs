@@ -71,12 +71,17 @@ impl EnvVarsRead for EnvVarsMap<'_> {
 impl EnvVars {
     pub const PATH: &'static str = "PATH";
     pub const HOME: &'static str = "HOME";
+    pub const USERPROFILE: &'static str = "USERPROFILE";
+    pub const SYSTEMDRIVE: &'static str = "SystemDrive";
     pub const CI: &'static str = "CI";
     pub const LC_ALL: &'static str = "LC_ALL";
+    pub const PAGER: &'static str = "PAGER";
+    pub const LESS: &'static str = "LESS";
 
     // Git related
     pub const GIT_DIR: &'static str = "GIT_DIR";
     pub const GIT_WORK_TREE: &'static str = "GIT_WORK_TREE";
+    pub const GIT_ALTERNATE_OBJECT_DIRECTORIES: &'static str = "GIT_ALTERNATE_OBJECT_DIRECTORIES";
     pub const GIT_TERMINAL_PROMPT: &'static str = "GIT_TERMINAL_PROMPT";
 
     pub const SKIP: &'static str = "SKIP";
@@ -89,16 +94,27 @@ impl EnvVars {
     pub const PREK_NO_CONCURRENCY: &'static str = "PREK_NO_CONCURRENCY";
     pub const PREK_CONCURRENT_HOOKS: &'static str = "PREK_CONCURRENT_HOOKS";
     pub const PREK_CONCURRENT_BATCHES: &'static str = "PREK_CONCURRENT_BATCHES";
+    pub const PREK_HOOK_SCHEDULE: &'static str = "PREK_HOOK_SCHEDULE";
     pub const PREK_MAX_CONCURRENCY: &'static str = "PREK_MAX_CONCURRENCY";
     pub const PREK_NO_FAST_PATH: &'static str = "PREK_NO_FAST_PATH";
+    pub const PREK_NO_PTY: &'static str = "PREK_NO_PTY";
+    pub const PREK_NO_JJ: &'static str = "PREK_NO_JJ";
+    pub const PREK_LOG: &'static str = "PREK_LOG";
     pub const PREK_UV_SOURCE: &'static str = "PREK_UV_SOURCE";
     pub const PREK_NATIVE_TLS: &'static str = "PREK_NATIVE_TLS";
     pub const PREK_DOWNLOAD_CHECKSUM_POLICY: &'static str = "PREK_DOWNLOAD_CHECKSUM_POLICY";
+    pub const PREK_CONNECT_TIMEOUT: &'static str = "PREK_CONNECT_TIMEOUT";
+    pub const PREK_DOWNLOAD_TIMEOUT: &'static str = "PREK_DOWNLOAD_TIMEOUT";
+    pub const PREK_DOWNLOAD_RATE_LIMIT: &'static str = "PREK_DOWNLOAD_RATE_LIMIT";
+    pub const PREK_REMOTE_CACHE_TOKEN: &'static str = "PREK_REMOTE_CACHE_TOKEN";
+    pub const PREK_PREFER_SYSTEM_TOOLCHAINS: &'static str = "PREK_PREFER_SYSTEM_TOOLCHAINS";
     pub const SSL_CERT_FILE: &'static str = "SSL_CERT_FILE";
     pub const SSL_CERT_DIR: &'static str = "SSL_CERT_DIR";
     pub const PREK_CONTAINER_RUNTIME: &'static str = "PREK_CONTAINER_RUNTIME";
     pub const PREK_DOCKER_NO_INIT: &'static str = "PREK_DOCKER_NO_INIT";
+    pub const PREK_DOCKER_USER: &'static str = "PREK_DOCKER_USER";
     pub const PREK_QUIET: &'static str = "PREK_QUIET";
+    pub const PREK_REQUIRE_TRUST: &'static str = "PREK_REQUIRE_TRUST";
 
     // PREK internal environment variables
     pub const PREK_INTERNAL__TEST_DIR: &'static str = "PREK_INTERNAL__TEST_DIR";
@@ -120,6 +136,7 @@ impl EnvVars {
     // Python & uv related
     pub const VIRTUAL_ENV: &'static str = "VIRTUAL_ENV";
     pub const PYTHONHOME: &'static str = "PYTHONHOME";
+    pub const PYTHONIOENCODING: &'static str = "PYTHONIOENCODING";
     pub const UV_PYTHON: &'static str = "UV_PYTHON";
     pub const UV_PYTHON_PREFERENCE: &'static str = "UV_PYTHON_PREFERENCE";
     pub const UV_SYSTEM_PYTHON: &'static str = "UV_SYSTEM_PYTHON";
@@ -141,6 +158,9 @@ impl EnvVars {
     // GitHub API authentication (to avoid rate limits)
     pub const GITHUB_TOKEN: &'static str = "GITHUB_TOKEN";
 
+    // GitHub Actions job summary file
+    pub const GITHUB_STEP_SUMMARY: &'static str = "GITHUB_STEP_SUMMARY";
+
     // Go related
     pub const GOTOOLCHAIN: &'static str = "GOTOOLCHAIN";
     pub const GOROOT: &'static str = "GOROOT";
@@ -175,6 +195,8 @@ impl EnvVars {
 
     // Ruby related
     pub const PREK_RUBY_MIRROR: &'static str = "PREK_RUBY_MIRROR";
+    pub const PREK_RUBYINSTALLER_MIRROR: &'static str = "PREK_RUBYINSTALLER_MIRROR";
+    pub const SCOOP: &'static str = "SCOOP";
     pub const GEM_HOME: &'static str = "GEM_HOME";
     pub const GEM_PATH: &'static str = "GEM_PATH";
     pub const BUNDLE_IGNORE_CONFIG: &'static str = "BUNDLE_IGNORE_CONFIG";
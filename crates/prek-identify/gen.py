@@ -11,11 +11,18 @@ from identify.interpreters import INTERPRETERS
 from identify.extensions import EXTENSIONS, EXTENSIONS_NEED_BINARY_CHECK, NAMES
 
 
+# Tags assigned by prek itself that `identify` does not produce.
+EXTRA_TAGS = [
+    # Gitlink entries, i.e. submodule pointers recorded in the superproject.
+    "submodule",
+]
+
 TAG_ID_CONSTS = [
     ("TAG_FILE", "file"),
     ("TAG_DIRECTORY", "directory"),
     ("TAG_SYMLINK", "symlink"),
     ("TAG_SOCKET", "socket"),
+    ("TAG_SUBMODULE", "submodule"),
     ("TAG_EXECUTABLE", "executable"),
     ("TAG_NON_EXECUTABLE", "non-executable"),
     ("TAG_TEXT", "text"),
@@ -27,11 +34,13 @@ TAG_SET_CONSTS = [
     ("TAG_SET_DIRECTORY", ["directory"]),
     ("TAG_SET_SYMLINK", ["symlink"]),
     ("TAG_SET_SOCKET", ["socket"]),
+    ("TAG_SET_SUBMODULE", ["directory", "submodule"]),
     ("TAG_SET_TEXT", ["text"]),
     ("TAG_SET_TEXT_OR_BINARY", ["text", "binary"]),
     ("TAG_SET_EXECUTABLE_TEXT", ["executable", "text"]),
     ("TAG_SET_JSON", ["json"]),
     ("TAG_SET_JSON5", ["json5"]),
+    ("TAG_SET_PYTHON", ["python"]),
     ("TAG_SET_TOML", ["toml"]),
     ("TAG_SET_XML", ["xml"]),
     ("TAG_SET_YAML", ["yaml"]),
@@ -45,6 +54,14 @@ EXTENSION_OVERRIDES = {
     "mts": {"text", "ts"},
 }
 
+# Multi-dot suffixes that carry more meaning than their last extension alone,
+# matched against the longest suffix of the filename and added to the tags of
+# its last extension in `EXTENSIONS`.
+COMPOSITE_EXTENSIONS = {
+    "tar.bz2": {"binary", "bzip2", "tar"},
+    "tar.gz": {"binary", "gzip", "tar"},
+}
+
 SELF_DIR = Path(__file__).parent
 TAGS_FILE = SELF_DIR / "src/tags.rs"
 
@@ -53,7 +70,7 @@ def gen():
     with open(TAGS_FILE, "w", newline="\n") as f:
         f.write("// This file is auto-generated by gen.py. DO NOT EDIT MANUALLY.\n\n")
         f.write("use crate::TagSet;\n\n")
-        tags = sorted(set(ALL_TAGS))
+        tags = sorted(set(ALL_TAGS) | set(EXTRA_TAGS))
         tag_to_id = {tag: idx for idx, tag in enumerate(tags)}
 
         def tagset_expr(tag_set):
@@ -94,6 +111,16 @@ def gen():
             f.write(f'    "{ext}" => {tagset_expr(EXTENSIONS[ext])},\n')
         f.write("};\n\n")
 
+        f.write(
+            "pub const COMPOSITE_EXTENSIONS: phf::Map<&str, TagSet> = phf::phf_map! {\n"
+        )
+        for ext in sorted(COMPOSITE_EXTENSIONS):
+            tag_names = sorted(COMPOSITE_EXTENSIONS[ext])
+            tag_names_str = ", ".join(f'"{tag}"' for tag in tag_names)
+            f.write(f"    // [{tag_names_str}]\n")
+            f.write(f'    "{ext}" => {tagset_expr(COMPOSITE_EXTENSIONS[ext])},\n')
+        f.write("};\n\n")
+
         f.write("pub const NAMES: phf::Map<&str, TagSet> = phf::phf_map! {\n")
         for name in sorted(NAMES):
             tag_names = sorted(NAMES[name])
@@ -18,9 +18,12 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 // THE SOFTWARE.
 
+use std::collections::HashMap;
 use std::io::{BufRead, Read};
 use std::ops::BitOrAssign;
 use std::path::Path;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg(feature = "serde")]
 use serde::de::{Error as DeError, SeqAccess, Visitor};
@@ -35,7 +38,10 @@ const TAG_WORDS: usize = tags::ALL_TAGS.len().div_ceil(64);
 ///
 /// Each bit corresponds to an index in [`tags::ALL_TAGS`].
 /// This keeps membership / set operations fast and allocation-free.
-#[derive(Clone, Copy, Default)]
+///
+/// Since [`tags::ALL_TAGS`] is sorted, iterating and serializing a set always yields its
+/// tags in lexicographic order, regardless of the order they were inserted in.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct TagSet {
     bits: [u64; TAG_WORDS],
 }
@@ -138,6 +144,11 @@ impl TagSet {
         true
     }
 
+    /// Returns `true` if the two sets share at least one tag.
+    pub fn intersects(&self, other: &TagSet) -> bool {
+        !self.is_disjoint(other)
+    }
+
     /// Returns `true` if all tags in `self` are also present in `other`.
     pub fn is_subset(&self, other: &TagSet) -> bool {
         for idx in 0..TAG_WORDS {
@@ -148,7 +159,7 @@ impl TagSet {
         true
     }
 
-    /// Iterates tags in deterministic id order.
+    /// Iterates tags in lexicographic order.
     pub fn iter(&self) -> TagSetIter<'_> {
         TagSetIter {
             bits: &self.bits,
@@ -171,6 +182,16 @@ impl BitOrAssign<&TagSet> for TagSet {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for TagSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for TagSet {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -240,6 +261,78 @@ pub enum Error {
     Shebang(#[from] ShebangError),
 }
 
+/// User-defined tags for file names and extensions.
+///
+/// A custom match replaces the tags the built-in tables would give the file name, so custom
+/// entries can both add new file types and reassign known ones.
+#[derive(Debug, Default, Clone)]
+pub struct CustomTags {
+    filenames: HashMap<String, TagSet>,
+    extensions: HashMap<String, TagSet>,
+}
+
+impl CustomTags {
+    /// Tag files with exactly this name, e.g. `Snakefile`.
+    pub fn add_filename(&mut self, filename: impl Into<String>, tags: TagSet) {
+        self.filenames.insert(filename.into(), tags);
+    }
+
+    /// Tag files with this extension, e.g. `snake` or `tar.zst`, ignoring case.
+    pub fn add_extension(&mut self, extension: &str, tags: TagSet) {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        self.extensions.insert(extension.to_ascii_lowercase(), tags);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filenames.is_empty() && self.extensions.is_empty()
+    }
+
+    /// Look up a file name, preferring exact names, then the longest matching extension.
+    fn get(&self, filename: &str) -> Option<TagSet> {
+        if let Some(tags) = self.filenames.get(filename) {
+            return Some(*tags);
+        }
+        if self.extensions.is_empty() {
+            return None;
+        }
+
+        let filename = filename.to_ascii_lowercase();
+        // Skip a leading dot so dotfiles are not treated as all-extension names.
+        filename
+            .match_indices('.')
+            .filter(|(pos, _)| *pos > 0)
+            .find_map(|(pos, _)| self.extensions.get(&filename[pos + 1..]))
+            .copied()
+    }
+}
+
+static CUSTOM_TAGS: OnceLock<CustomTags> = OnceLock::new();
+
+/// Register custom tags to consult before the built-in tables.
+///
+/// Custom tags can only be registered once per process; returns `false` if they already were.
+pub fn set_custom_tags(custom: CustomTags) -> bool {
+    CUSTOM_TAGS.set(custom).is_ok()
+}
+
+static DETECT_CONTENT: AtomicBool = AtomicBool::new(false);
+
+/// Tag extensionless text files of otherwise unknown type as JSON, YAML, TOML or XML based on
+/// their contents.
+///
+/// This is off by default, as it reads the first KB of every such file.
+pub fn set_content_detection(enabled: bool) {
+    DETECT_CONTENT.store(enabled, Ordering::Relaxed);
+}
+
+/// The tags of a text file whose type is not known from its name or shebang.
+const TAG_SET_UNKNOWN_TEXT: TagSet = TagSet::new(&[
+    tags::TAG_FILE,
+    tags::TAG_EXECUTABLE,
+    tags::TAG_NON_EXECUTABLE,
+    tags::TAG_TEXT,
+]);
+
 /// Identify tags for a file at the given path.
 pub fn tags_from_path(path: &Path) -> Result<TagSet, Error> {
     let metadata = fs_err::symlink_metadata(path)?;
@@ -296,10 +389,27 @@ pub fn tags_from_path(path: &Path) -> Result<TagSet, Error> {
         }
     }
 
+    if tags.is_subset(&TAG_SET_UNKNOWN_TEXT)
+        && tags.intersects(&tags::TAG_SET_TEXT)
+        && path.extension().is_none()
+        && DETECT_CONTENT.load(Ordering::Relaxed)
+    {
+        tags |= &tags_from_content(path);
+    }
+
     Ok(tags)
 }
 
 fn tags_from_filename(filename: &Path) -> TagSet {
+    if let Some(tags) = CUSTOM_TAGS.get().and_then(|custom| {
+        filename
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| custom.get(name))
+    }) {
+        return tags;
+    }
+
     let extension_tags = tags_from_extension(filename);
     let Some(filename) = filename.file_name().and_then(|name| name.to_str()) else {
         return extension_tags;
@@ -326,6 +436,18 @@ fn tags_from_filename(filename: &Path) -> TagSet {
 }
 
 fn tags_from_extension(filename: &Path) -> TagSet {
+    let mut tags = tags_from_last_extension(filename);
+    if let Some(composite) = filename
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(tags_from_composite_extension)
+    {
+        tags |= &composite;
+    }
+    tags
+}
+
+fn tags_from_last_extension(filename: &Path) -> TagSet {
     let Some(ext) = filename.extension().and_then(|ext| ext.to_str()) else {
         return TagSet::default();
     };
@@ -344,6 +466,24 @@ fn tags_from_extension(filename: &Path) -> TagSet {
     TagSet::default()
 }
 
+/// Match multi-dot suffixes like "tar.gz", preferring the longest one.
+fn tags_from_composite_extension(filename: &str) -> Option<TagSet> {
+    let lower;
+    let filename = if filename.as_bytes().iter().any(u8::is_ascii_uppercase) {
+        lower = filename.to_ascii_lowercase();
+        lower.as_str()
+    } else {
+        filename
+    };
+
+    // Skip a leading dot so dotfiles are not treated as all-extension names.
+    filename
+        .match_indices('.')
+        .filter(|(pos, _)| *pos > 0)
+        .find_map(|(pos, _)| tags::COMPOSITE_EXTENSIONS.get(&filename[pos + 1..]))
+        .copied()
+}
+
 fn tags_from_interpreter(interpreter: &str) -> TagSet {
     let mut name = interpreter
         .rfind('/')
@@ -384,9 +524,42 @@ fn starts_with(slice: &[String], prefix: &[&str]) -> bool {
     slice.len() >= prefix.len() && slice.iter().zip(prefix.iter()).all(|(s, p)| s == p)
 }
 
+/// Reject control characters other than whitespace, but allow non-ASCII UTF-8
+/// so interpreter paths such as `/opt/café/bin/python` still work.
 fn is_printable_shebang(line: &str) -> bool {
-    line.bytes()
-        .all(|b| (0x20..=0x7E).contains(&b) || (0x09..=0x0D).contains(&b))
+    line.chars()
+        .all(|c| !c.is_control() || ('\u{09}'..='\u{0D}').contains(&c))
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Read the next line and return its content after the `#!` marker.
+///
+/// Returns `Ok(None)` at EOF or if the line is not a shebang line, and
+/// `Err(ShebangError::NonPrintableChars)` if it is not valid printable UTF-8.
+fn read_shebang_line<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    strip_bom: bool,
+) -> Result<Option<String>, ShebangError> {
+    buf.clear();
+    if reader.read_until(b'\n', buf)? == 0 {
+        return Ok(None);
+    }
+
+    let mut line = buf.as_slice();
+    if strip_bom {
+        line = line.strip_prefix(UTF8_BOM).unwrap_or(line);
+    }
+    let Some(line) = line.strip_prefix(b"#!") else {
+        return Ok(None);
+    };
+    let line = std::str::from_utf8(line).map_err(|_| ShebangError::NonPrintableChars)?;
+    if !is_printable_shebang(line) {
+        return Err(ShebangError::NonPrintableChars);
+    }
+
+    Ok(Some(line.to_string()))
 }
 
 fn nix_shebang_interpreter(tokens: &[String]) -> Option<&str> {
@@ -404,26 +577,13 @@ fn nix_shebang_interpreter(tokens: &[String]) -> Option<&str> {
 /// Example:
 /// `#!nix-shell -i python3 -p python3` would return `["python3"]`
 fn parse_nix_shebang<R: BufRead>(reader: &mut R, mut cmd: Vec<String>) -> Vec<String> {
-    let mut next_line = String::new();
+    let mut buf = Vec::new();
     loop {
-        next_line.clear();
-        match reader.read_line(&mut next_line) {
-            Ok(0) => break,
-            Ok(_) => {}
-            Err(err) => {
-                if err.kind() == std::io::ErrorKind::InvalidData {
-                    return cmd;
-                }
-                break;
-            }
-        }
-
-        let Some(line) = next_line.strip_prefix("#!") else {
-            break;
+        let line = match read_shebang_line(reader, &mut buf, false) {
+            Ok(Some(line)) => line,
+            Ok(None) | Err(ShebangError::IoError(_)) => break,
+            Err(_) => return cmd,
         };
-        if !is_printable_shebang(line) {
-            return cmd;
-        }
 
         let Some(line_tokens) = shlex::split(line.trim()) else {
             continue;
@@ -440,18 +600,12 @@ fn parse_nix_shebang<R: BufRead>(reader: &mut R, mut cmd: Vec<String>) -> Vec<St
 pub fn parse_shebang(path: &Path) -> Result<Vec<String>, ShebangError> {
     let file = fs_err::File::open(path)?;
     let mut reader = std::io::BufReader::new(file);
-    let mut line = String::new();
-    reader.read_line(&mut line)?;
-    if !line.starts_with("#!") {
-        return Err(ShebangError::NoShebang);
-    }
+    // Scripts authored on Windows may start with a UTF-8 BOM and use CRLF line endings,
+    // the trailing `\r` is removed by `trim` below.
+    let line =
+        read_shebang_line(&mut reader, &mut Vec::new(), true)?.ok_or(ShebangError::NoShebang)?;
 
-    // Require only printable ASCII
-    if !is_printable_shebang(&line) {
-        return Err(ShebangError::NonPrintableChars);
-    }
-
-    let mut tokens = shlex::split(line[2..].trim()).ok_or(ShebangError::ParseFailed)?;
+    let mut tokens = shlex::split(line.trim()).ok_or(ShebangError::ParseFailed)?;
     let mut cmd =
         if starts_with(&tokens, &["/usr/bin/env", "-S"]) || starts_with(&tokens, &["env", "-S"]) {
             tokens.drain(0..2);
@@ -516,9 +670,294 @@ fn is_text_file(path: &Path) -> bool {
     buffer[..bytes_read].iter().all(|&b| is_text_char(b))
 }
 
+/// Guess the format of a text file from its first KB.
+fn tags_from_content(path: &Path) -> TagSet {
+    let mut buffer = [0; 1024];
+    let Ok(mut file) = fs_err::File::open(path) else {
+        return TagSet::default();
+    };
+    let Ok(bytes_read) = file.read(&mut buffer) else {
+        return TagSet::default();
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..bytes_read]);
+    // Ignore the last line if the file is longer, as it may be cut off.
+    let head = if bytes_read == buffer.len() {
+        head.rsplit_once('\n').map_or("", |(head, _)| head)
+    } else {
+        &head
+    };
+    sniff_format(head)
+}
+
+fn sniff_format(head: &str) -> TagSet {
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with("<?xml") {
+        return tags::TAG_SET_XML;
+    }
+    if head.starts_with("%YAML") {
+        return tags::TAG_SET_YAML;
+    }
+
+    if let Some(rest) = head.strip_prefix('{') {
+        if rest.trim_start().starts_with(['"', '}']) {
+            return tags::TAG_SET_JSON;
+        }
+        return TagSet::default();
+    }
+
+    // TOML table headers are checked before JSON arrays, which they look like.
+    let starts_with_table = head
+        .lines()
+        .next()
+        .is_some_and(|line| is_toml_table_header(strip_toml_comment(line.trim())));
+    if !starts_with_table && let Some(rest) = head.strip_prefix('[') {
+        let rest = rest.trim_start();
+        if rest.starts_with(['{', '[', '"', ']', '-'])
+            || rest.starts_with(|c: char| c.is_ascii_digit())
+        {
+            return tags::TAG_SET_JSON;
+        }
+        return TagSet::default();
+    }
+
+    // Plain text easily starts like a key, so every line has to fit.
+    if is_toml(head) {
+        return tags::TAG_SET_TOML;
+    }
+    if is_yaml(head) {
+        return tags::TAG_SET_YAML;
+    }
+
+    TagSet::default()
+}
+
+/// Whether every line of `head` is a TOML comment, table header or key with a valid value, and
+/// there is at least one header or key.
+fn is_toml(head: &str) -> bool {
+    let mut value = TomlValue::default();
+    let mut found = false;
+    for line in head.lines() {
+        // Lines of arrays, inline tables and strings that span lines.
+        if value.is_open() {
+            value.scan(line);
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if is_toml_table_header(strip_toml_comment(line)) {
+            found = true;
+            continue;
+        }
+        let Some((key, rest)) = line.split_once('=') else {
+            return false;
+        };
+        let rest = rest.trim_start();
+        if !is_dotted_bare_key(key.trim_end()) || !is_toml_value(rest) {
+            return false;
+        }
+        value.scan(rest);
+        found = true;
+    }
+    found
+}
+
+/// Strip a trailing comment from a line without strings, such as a table header.
+fn strip_toml_comment(line: &str) -> &str {
+    line.split_once('#')
+        .map_or(line, |(line, _)| line)
+        .trim_end()
+}
+
+/// Whether `value` starts like a TOML string, number, boolean, date, array or inline table,
+/// unlike the unquoted strings of `.env` and INI files.
+fn is_toml_value(value: &str) -> bool {
+    if value.starts_with(['"', '\'', '[', '{']) {
+        return true;
+    }
+    let token = value
+        .split(|c: char| c.is_whitespace() || matches!(c, '#' | ','))
+        .next()
+        .unwrap_or_default();
+    if matches!(
+        token,
+        "true" | "false" | "inf" | "+inf" | "-inf" | "nan" | "+nan" | "-nan"
+    ) {
+        return true;
+    }
+    token
+        .trim_start_matches(['+', '-'])
+        .starts_with(|c: char| c.is_ascii_digit())
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-' | '.' | ':'))
+}
+
+/// The brackets and multi-line strings still open in a TOML value that spans lines.
+#[derive(Default)]
+struct TomlValue {
+    depth: usize,
+    multiline: Option<&'static str>,
+}
+
+impl TomlValue {
+    fn is_open(&self) -> bool {
+        self.depth > 0 || self.multiline.is_some()
+    }
+
+    fn scan(&mut self, mut text: &str) {
+        loop {
+            if let Some(delimiter) = self.multiline {
+                let Some(end) = text.find(delimiter) else {
+                    return;
+                };
+                text = &text[end + delimiter.len()..];
+                self.multiline = None;
+                continue;
+            }
+
+            let Some(idx) = text.find(['"', '\'', '[', ']', '{', '}', '#']) else {
+                return;
+            };
+            let rest = &text[idx + 1..];
+            text = match text.as_bytes()[idx] {
+                b'#' => return,
+                b'[' | b'{' => {
+                    self.depth += 1;
+                    rest
+                }
+                b']' | b'}' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    rest
+                }
+                quote => {
+                    let delimiter = if quote == b'"' { "\"\"\"" } else { "\'\'\'" };
+                    if text[idx..].starts_with(delimiter) {
+                        self.multiline = Some(delimiter);
+                        &text[idx + delimiter.len()..]
+                    } else {
+                        skip_toml_string(rest, quote)
+                    }
+                }
+            };
+        }
+    }
+}
+
+/// The rest of `text` after the closing `quote` of a one-line string.
+fn skip_toml_string(text: &str, quote: u8) -> &str {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' if quote == b'"' => idx += 2,
+            byte if byte == quote => return &text[idx + 1..],
+            _ => idx += 1,
+        }
+    }
+    ""
+}
+
+/// Whether every line of `head` is a YAML comment, document marker, mapping key or sequence item,
+/// or continues a value, and there are at least two markers, keys or items.
+fn is_yaml(head: &str) -> bool {
+    let mut found = 0;
+    // Lines indented deeper than a key continue its value.
+    let mut key_indent = None;
+    let mut block_scalar_indent = None;
+    for line in head.lines() {
+        let content = line.trim_start();
+        let indent = line.len() - content.len();
+        let content = content.trim_end();
+        if block_scalar_indent.is_some_and(|parent| content.is_empty() || indent > parent) {
+            continue;
+        }
+        block_scalar_indent = None;
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        if matches!(content, "---" | "...") || content.starts_with("--- ") {
+            found += 1;
+            key_indent = None;
+            continue;
+        }
+
+        // Sequence items can hold a mapping key, as in `- id: check`.
+        let (content, is_item) = match content.strip_prefix('-') {
+            Some(rest) if rest.is_empty() || rest.starts_with([' ', '\t']) => {
+                (rest.trim_start(), true)
+            }
+            _ => (content, false),
+        };
+        if let Some((key, value)) = content.split_once(':')
+            && (value.is_empty() || value.starts_with([' ', '\t']))
+            && is_yaml_key(key)
+        {
+            found += 1;
+            key_indent = Some(indent);
+            if is_block_scalar_indicator(value.trim()) {
+                block_scalar_indent = Some(indent);
+            }
+        } else if is_item {
+            found += 1;
+        } else if key_indent.is_none_or(|parent| indent <= parent) {
+            return false;
+        }
+    }
+    found >= 2
+}
+
+/// Whether `key` is a bare or quoted YAML mapping key.
+fn is_yaml_key(key: &str) -> bool {
+    is_dotted_bare_key(key)
+        || (key.len() >= 2
+            && ((key.starts_with('"') && key.ends_with('"'))
+                || (key.starts_with('\'') && key.ends_with('\''))))
+}
+
+/// Whether `value` starts a literal or folded block scalar, such as `|`, `>-` or `|2 # comment`.
+fn is_block_scalar_indicator(value: &str) -> bool {
+    value.strip_prefix(['|', '>']).is_some_and(|rest| {
+        strip_toml_comment(rest)
+            .chars()
+            .all(|c| matches!(c, '+' | '-' | '0'..='9'))
+    })
+}
+
+/// Whether `line` is a TOML `[table]` or `[[array.of.tables]]` header with bare keys.
+fn is_toml_table_header(line: &str) -> bool {
+    let inner = line
+        .strip_prefix("[[")
+        .and_then(|line| line.strip_suffix("]]"))
+        .or_else(|| {
+            line.strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+        });
+    inner.is_some_and(|inner| {
+        // Tables start with a letter, unlike one-line JSON arrays such as `[1]`.
+        inner.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && is_dotted_bare_key(inner.trim())
+    })
+}
+
+/// Whether `key` is made of bare keys, such as `tool.prek` or `default_stages`.
+fn is_dotted_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('.').all(|part| {
+            let part = part.trim();
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{TagSet, tags};
+    use super::{CustomTags, TagSet, tags};
     use std::io::Write;
     use std::path::Path;
 
@@ -564,6 +1003,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sniff_format() {
+        let cases: &[(&str, &[&str])] = &[
+            ("{\n  \"name\": \"prek\"\n}\n", &["json"]),
+            ("[{\"id\": 1}]", &["json"]),
+            ("[\n  1,\n  2\n]", &["json"]),
+            ("{}", &["json"]),
+            ("\u{feff}<?xml version=\"1.0\"?>\n<a/>", &["xml"]),
+            ("---\nkey: value\n", &["yaml"]),
+            ("# comment\nrepos:\n  - repo: local\n", &["yaml"]),
+            ("- one\n- two\n", &["yaml"]),
+            ("name = \"prek\"\n", &["toml"]),
+            ("# comment\n[tool.prek]\nkey = 1\n", &["toml"]),
+            ("[[repos]]\nrepo = \"local\"\n", &["toml"]),
+            ("tool.prek.key = true\n", &["toml"]),
+            ("Hello, world!\n", &[]),
+            ("{{ template }}\n", &[]),
+            ("[1]\n", &["json"]),
+            ("see http://example.com\n", &[]),
+            ("", &[]),
+            ("TODO: x\n", &[]),
+            ("TODO: x\nthen ship it\n", &[]),
+            ("FOO=bar\n", &[]),
+            ("[section]\nkey = value\n", &[]),
+            ("---\ntitle: Notes\n---\n# Notes\nSome prose.\n", &[]),
+            ("name: prek\nversion: 1\n", &["yaml"]),
+            (
+                "script: |\n  echo one\n\n  echo two\nname: build\n",
+                &["yaml"],
+            ),
+            (
+                "deps = [\n  \"a\",\n  \"b\",\n]\nname = 'x' # comment\n",
+                &["toml"],
+            ),
+            ("text = \"\"\"\nfree = form\n\"\"\"\n", &["toml"]),
+        ];
+        for (head, expected) in cases {
+            assert_tagset(&super::sniff_format(head), expected);
+        }
+    }
+
+    #[test]
+    fn custom_tags() {
+        let mut custom = CustomTags::default();
+        custom.add_extension("snake", TagSet::from_tags(["python", "text"]));
+        custom.add_extension(".tar.zst", TagSet::from_tags(["binary", "tar"]));
+        custom.add_extension("json", TagSet::from_tags(["json5", "text"]));
+        custom.add_filename("Snakefile", TagSet::from_tags(["python", "text"]));
+
+        assert_tagset(&custom.get("rules.snake").unwrap(), &["python", "text"]);
+        assert_tagset(&custom.get("RULES.SNAKE").unwrap(), &["python", "text"]);
+        assert_tagset(&custom.get("data.tar.zst").unwrap(), &["binary", "tar"]);
+        assert_tagset(&custom.get("data.json").unwrap(), &["json5", "text"]);
+        assert_tagset(&custom.get("Snakefile").unwrap(), &["python", "text"]);
+        assert!(custom.get("snakefile").is_none());
+        assert!(custom.get(".snake").is_none());
+        assert!(custom.get("main.rs").is_none());
+    }
+
     #[test]
     fn tags_from_filename() {
         let tags = super::tags_from_filename(Path::new("test.py"));
@@ -618,6 +1116,23 @@ mod tests {
         assert_tagset(&tags, &["text", "tiltfile"]);
     }
 
+    #[test]
+    fn tags_from_filename_composite_extensions() {
+        let tags = super::tags_from_filename(Path::new("release.tar.gz"));
+        assert_tagset(&tags, &["binary", "gzip", "tar"]);
+
+        let tags = super::tags_from_filename(Path::new("release-1.2.TAR.BZ2"));
+        assert_tagset(&tags, &["binary", "bzip2", "tar"]);
+
+        // A bare composite suffix is a dotfile, not an extension.
+        let tags = super::tags_from_filename(Path::new(".tar.gz"));
+        assert_tagset(&tags, &["binary", "gzip"]);
+
+        // Unknown composites fall back to the last extension.
+        let tags = super::tags_from_filename(Path::new("backup.sql.gz"));
+        assert_tagset(&tags, &["binary", "gzip"]);
+    }
+
     #[test]
     fn tags_from_filename_prefers_known_extension_over_name_prefix() {
         let tags = super::tags_from_filename(Path::new("makefile.png"));
@@ -695,6 +1210,8 @@ mod tests {
         assert!(!a.is_subset(&b));
         assert!(!a.is_disjoint(&b));
         assert!(a.is_disjoint(&c));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
 
         let mut merged = b;
         merged |= &c;
@@ -716,6 +1233,30 @@ mod tests {
         assert_tagset(&parsed, &["python", "text"]);
     }
 
+    #[test]
+    fn tagset_iterates_in_sorted_order() {
+        let set = TagSet::from_tags(["yaml", "text", "file", "non-executable"]);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec!["file", "non-executable", "text", "yaml"]
+        );
+        assert_eq!(
+            set,
+            TagSet::from_tags(["text", "yaml", "non-executable", "file"])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tagset_serialize_round_trip() {
+        let set = TagSet::from_tags(["text", "python"]);
+        let json = serde_json::to_string(&set).expect("should serialize tags");
+        assert_eq!(json, r#"["python","text"]"#);
+
+        let parsed: TagSet = serde_json::from_str(&json).expect("should parse tags");
+        assert_eq!(parsed, set);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn tagset_deserialize_unknown_tag_errors() {
@@ -727,6 +1268,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_shebang_strips_bom_and_crlf() -> anyhow::Result<()> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"\xEF\xBB\xBF#!/usr/bin/env python3\r\nprint('hi')\r\n")?;
+        file.flush()?;
+
+        let cmd = super::parse_shebang(file.path())?;
+        assert_eq!(cmd, vec!["python3"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_shebang_allows_utf8_interpreter_path() -> anyhow::Result<()> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all("#!/opt/café/bin/python3 -u\n".as_bytes())?;
+        file.flush()?;
+
+        let cmd = super::parse_shebang(file.path())?;
+        assert_eq!(cmd, vec!["/opt/café/bin/python3", "-u"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_shebang_rejects_non_utf8_and_control_chars() -> anyhow::Result<()> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"#!/usr/bin/\xFFpython\n")?;
+        file.flush()?;
+        assert!(matches!(
+            super::parse_shebang(file.path()),
+            Err(super::ShebangError::NonPrintableChars)
+        ));
+
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"#!/usr/bin/python\x1b\n")?;
+        file.flush()?;
+        assert!(matches!(
+            super::parse_shebang(file.path()),
+            Err(super::ShebangError::NonPrintableChars)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn tags_from_path_windows_authored_script() -> anyhow::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let script = dir.path().join("script");
+        fs_err::write(&script, b"\xEF\xBB\xBF#!/usr/bin/env bash\r\necho hi\r\n")?;
+        fs_err::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+        let tags = super::tags_from_path(&script)?;
+        assert_tagset(&tags, &["bash", "executable", "file", "shell", "text"]);
+
+        Ok(())
+    }
+
     #[test]
     fn parse_shebang_nix_shell_interpreter() -> anyhow::Result<()> {
         let mut file = tempfile::NamedTempFile::new()?;
@@ -2,7 +2,7 @@
 
 use crate::TagSet;
 
-pub const ALL_TAGS: [&str; 311] = [
+pub const ALL_TAGS: [&str; 312] = [
     "adobe-illustrator",
     "alpm",
     "apinotes",
@@ -246,6 +246,7 @@ pub const ALL_TAGS: [&str; 311] = [
     "spec",
     "sql",
     "stylus",
+    "submodule",
     "svelte",
     "svg",
     "swf",
@@ -318,25 +319,28 @@ pub const ALL_TAGS: [&str; 311] = [
 
 pub const TAG_FILE: u16 = 78;
 pub const TAG_DIRECTORY: u16 = 58;
-pub const TAG_SYMLINK: u16 = 248;
+pub const TAG_SYMLINK: u16 = 249;
 pub const TAG_SOCKET: u16 = 238;
+pub const TAG_SUBMODULE: u16 = 243;
 pub const TAG_EXECUTABLE: u16 = 74;
 pub const TAG_NON_EXECUTABLE: u16 = 176;
-pub const TAG_TEXT: u16 = 255;
+pub const TAG_TEXT: u16 = 256;
 pub const TAG_BINARY: u16 = 21;
 
 pub const TAG_SET_FILE: TagSet = TagSet::new(&[78]);
 pub const TAG_SET_DIRECTORY: TagSet = TagSet::new(&[58]);
-pub const TAG_SET_SYMLINK: TagSet = TagSet::new(&[248]);
+pub const TAG_SET_SYMLINK: TagSet = TagSet::new(&[249]);
 pub const TAG_SET_SOCKET: TagSet = TagSet::new(&[238]);
-pub const TAG_SET_TEXT: TagSet = TagSet::new(&[255]);
-pub const TAG_SET_TEXT_OR_BINARY: TagSet = TagSet::new(&[21, 255]);
-pub const TAG_SET_EXECUTABLE_TEXT: TagSet = TagSet::new(&[74, 255]);
+pub const TAG_SET_SUBMODULE: TagSet = TagSet::new(&[58, 243]);
+pub const TAG_SET_TEXT: TagSet = TagSet::new(&[256]);
+pub const TAG_SET_TEXT_OR_BINARY: TagSet = TagSet::new(&[21, 256]);
+pub const TAG_SET_EXECUTABLE_TEXT: TagSet = TagSet::new(&[74, 256]);
 pub const TAG_SET_JSON: TagSet = TagSet::new(&[135]);
 pub const TAG_SET_JSON5: TagSet = TagSet::new(&[136]);
-pub const TAG_SET_TOML: TagSet = TagSet::new(&[260]);
-pub const TAG_SET_XML: TagSet = TagSet::new(&[297]);
-pub const TAG_SET_YAML: TagSet = TagSet::new(&[301]);
+pub const TAG_SET_PYTHON: TagSet = TagSet::new(&[213]);
+pub const TAG_SET_TOML: TagSet = TagSet::new(&[261]);
+pub const TAG_SET_XML: TagSet = TagSet::new(&[298]);
+pub const TAG_SET_YAML: TagSet = TagSet::new(&[302]);
 
 pub const INTERPRETERS: phf::Map<&str, TagSet> = phf::phf_map! {
     // ["ash", "shell"]
@@ -382,50 +386,50 @@ pub const INTERPRETERS: phf::Map<&str, TagSet> = phf::phf_map! {
     // ["sh", "shell"]
     "sh" => TagSet::new(&[234, 235]),
     // ["shell", "tcsh"]
-    "tcsh" => TagSet::new(&[235, 251]),
+    "tcsh" => TagSet::new(&[235, 252]),
     // ["shell", "zsh"]
-    "zsh" => TagSet::new(&[235, 310]),
+    "zsh" => TagSet::new(&[235, 311]),
 };
 
 pub const EXTENSIONS: phf::Map<&str, TagSet> = phf::phf_map! {
     // ["asciidoc", "text"]
-    "adoc" => TagSet::new(&[4, 255]),
+    "adoc" => TagSet::new(&[4, 256]),
     // ["adobe-illustrator", "binary"]
     "ai" => TagSet::new(&[0, 21]),
     // ["aspectj", "text"]
-    "aj" => TagSet::new(&[7, 255]),
+    "aj" => TagSet::new(&[7, 256]),
     // ["apinotes", "text"]
-    "apinotes" => TagSet::new(&[2, 255]),
+    "apinotes" => TagSet::new(&[2, 256]),
     // ["asar", "binary"]
     "asar" => TagSet::new(&[3, 21]),
     // ["asciidoc", "text"]
-    "asciidoc" => TagSet::new(&[4, 255]),
+    "asciidoc" => TagSet::new(&[4, 256]),
     // ["asm", "text"]
-    "asm" => TagSet::new(&[6, 255]),
+    "asm" => TagSet::new(&[6, 256]),
     // ["astro", "text"]
-    "astro" => TagSet::new(&[8, 255]),
+    "astro" => TagSet::new(&[8, 256]),
     // ["avif", "binary", "image"]
     "avif" => TagSet::new(&[10, 21, 117]),
     // ["avro-schema", "text"]
-    "avsc" => TagSet::new(&[11, 255]),
+    "avsc" => TagSet::new(&[11, 256]),
     // ["bash", "shell", "text"]
-    "bash" => TagSet::new(&[14, 235, 255]),
+    "bash" => TagSet::new(&[14, 235, 256]),
     // ["batch", "text"]
-    "bat" => TagSet::new(&[15, 255]),
+    "bat" => TagSet::new(&[15, 256]),
     // ["bash", "bats", "shell", "text"]
-    "bats" => TagSet::new(&[14, 16, 235, 255]),
+    "bats" => TagSet::new(&[14, 16, 235, 256]),
     // ["bazel", "text"]
-    "bazel" => TagSet::new(&[17, 255]),
+    "bazel" => TagSet::new(&[17, 256]),
     // ["bitbake", "text"]
-    "bb" => TagSet::new(&[22, 255]),
+    "bb" => TagSet::new(&[22, 256]),
     // ["bitbake", "text"]
-    "bbappend" => TagSet::new(&[22, 255]),
+    "bbappend" => TagSet::new(&[22, 256]),
     // ["bitbake", "text"]
-    "bbclass" => TagSet::new(&[22, 255]),
+    "bbclass" => TagSet::new(&[22, 256]),
     // ["beancount", "text"]
-    "beancount" => TagSet::new(&[19, 255]),
+    "beancount" => TagSet::new(&[19, 256]),
     // ["bib", "text"]
-    "bib" => TagSet::new(&[20, 255]),
+    "bib" => TagSet::new(&[20, 256]),
     // ["binary", "bitmap", "image"]
     "bmp" => TagSet::new(&[21, 23, 117]),
     // ["binary", "bzip2"]
@@ -433,836 +437,843 @@ pub const EXTENSIONS: phf::Map<&str, TagSet> = phf::phf_map! {
     // ["binary", "bzip3"]
     "bz3" => TagSet::new(&[21, 27]),
     // ["bazel", "text"]
-    "bzl" => TagSet::new(&[17, 255]),
+    "bzl" => TagSet::new(&[17, 256]),
     // ["c", "text"]
-    "c" => TagSet::new(&[28, 255]),
+    "c" => TagSet::new(&[28, 256]),
     // ["c++", "text"]
-    "c++" => TagSet::new(&[31, 255]),
+    "c++" => TagSet::new(&[31, 256]),
     // ["c++", "text"]
-    "c++m" => TagSet::new(&[31, 255]),
+    "c++m" => TagSet::new(&[31, 256]),
     // ["c++", "text"]
-    "cc" => TagSet::new(&[31, 255]),
+    "cc" => TagSet::new(&[31, 256]),
     // ["c++", "text"]
-    "ccm" => TagSet::new(&[31, 255]),
+    "ccm" => TagSet::new(&[31, 256]),
     // ["text"]
-    "cfg" => TagSet::new(&[255]),
+    "cfg" => TagSet::new(&[256]),
     // ["c2hs", "text"]
-    "chs" => TagSet::new(&[32, 255]),
+    "chs" => TagSet::new(&[32, 256]),
     // ["javascript", "text"]
-    "cjs" => TagSet::new(&[129, 255]),
+    "cjs" => TagSet::new(&[129, 256]),
     // ["clojure", "text"]
-    "clj" => TagSet::new(&[36, 255]),
+    "clj" => TagSet::new(&[36, 256]),
     // ["clojure", "text"]
-    "cljc" => TagSet::new(&[36, 255]),
+    "cljc" => TagSet::new(&[36, 256]),
     // ["clojure", "clojurescript", "text"]
-    "cljs" => TagSet::new(&[36, 37, 255]),
+    "cljs" => TagSet::new(&[36, 37, 256]),
     // ["cmake", "text"]
-    "cmake" => TagSet::new(&[38, 255]),
+    "cmake" => TagSet::new(&[38, 256]),
     // ["batch", "text"]
-    "cmd" => TagSet::new(&[15, 255]),
+    "cmd" => TagSet::new(&[15, 256]),
     // ["text"]
-    "cnf" => TagSet::new(&[255]),
+    "cnf" => TagSet::new(&[256]),
     // ["coffee", "text"]
-    "coffee" => TagSet::new(&[40, 255]),
+    "coffee" => TagSet::new(&[40, 256]),
     // ["text"]
-    "conf" => TagSet::new(&[255]),
+    "conf" => TagSet::new(&[256]),
     // ["c++", "text"]
-    "cpp" => TagSet::new(&[31, 255]),
+    "cpp" => TagSet::new(&[31, 256]),
     // ["c++", "text"]
-    "cppm" => TagSet::new(&[31, 255]),
+    "cppm" => TagSet::new(&[31, 256]),
     // ["crystal", "text"]
-    "cr" => TagSet::new(&[42, 255]),
+    "cr" => TagSet::new(&[42, 256]),
     // ["pem", "text"]
-    "crt" => TagSet::new(&[187, 255]),
+    "crt" => TagSet::new(&[187, 256]),
     // ["c#", "text"]
-    "cs" => TagSet::new(&[29, 255]),
+    "cs" => TagSet::new(&[29, 256]),
     // ["csh", "shell", "text"]
-    "csh" => TagSet::new(&[43, 235, 255]),
+    "csh" => TagSet::new(&[43, 235, 256]),
     // ["cson", "text"]
-    "cson" => TagSet::new(&[44, 255]),
+    "cson" => TagSet::new(&[44, 256]),
     // ["csproj", "msbuild", "text", "xml"]
-    "csproj" => TagSet::new(&[45, 168, 255, 297]),
+    "csproj" => TagSet::new(&[45, 168, 256, 298]),
     // ["css", "text"]
-    "css" => TagSet::new(&[46, 255]),
+    "css" => TagSet::new(&[46, 256]),
     // ["csv", "text"]
-    "csv" => TagSet::new(&[48, 255]),
+    "csv" => TagSet::new(&[48, 256]),
     // ["c#", "c#script", "text"]
-    "csx" => TagSet::new(&[29, 30, 255]),
+    "csx" => TagSet::new(&[29, 30, 256]),
     // ["text", "ts"]
-    "cts" => TagSet::new(&[255, 261]),
+    "cts" => TagSet::new(&[256, 262]),
     // ["cuda", "text"]
-    "cu" => TagSet::new(&[49, 255]),
+    "cu" => TagSet::new(&[49, 256]),
     // ["cue", "text"]
-    "cue" => TagSet::new(&[50, 255]),
+    "cue" => TagSet::new(&[50, 256]),
     // ["cuda", "text"]
-    "cuh" => TagSet::new(&[49, 255]),
+    "cuh" => TagSet::new(&[49, 256]),
     // ["c++", "text"]
-    "cxx" => TagSet::new(&[31, 255]),
+    "cxx" => TagSet::new(&[31, 256]),
     // ["c++", "text"]
-    "cxxm" => TagSet::new(&[31, 255]),
+    "cxxm" => TagSet::new(&[31, 256]),
     // ["cylc", "text"]
-    "cylc" => TagSet::new(&[51, 255]),
+    "cylc" => TagSet::new(&[51, 256]),
     // ["dart", "text"]
-    "dart" => TagSet::new(&[53, 255]),
+    "dart" => TagSet::new(&[53, 256]),
     // ["dbc", "text"]
-    "dbc" => TagSet::new(&[55, 255]),
+    "dbc" => TagSet::new(&[55, 256]),
     // ["def", "text"]
-    "def" => TagSet::new(&[56, 255]),
+    "def" => TagSet::new(&[56, 256]),
     // ["diff", "text"]
-    "diff" => TagSet::new(&[57, 255]),
+    "diff" => TagSet::new(&[57, 256]),
     // ["binary"]
     "dll" => TagSet::new(&[21]),
     // ["dtd", "text"]
-    "dtd" => TagSet::new(&[62, 255]),
+    "dtd" => TagSet::new(&[62, 256]),
     // ["binary", "jar", "zip"]
-    "ear" => TagSet::new(&[21, 126, 308]),
+    "ear" => TagSet::new(&[21, 126, 309]),
     // ["clojure", "edn", "text"]
-    "edn" => TagSet::new(&[36, 64, 255]),
+    "edn" => TagSet::new(&[36, 64, 256]),
     // ["ejs", "text"]
-    "ejs" => TagSet::new(&[65, 255]),
+    "ejs" => TagSet::new(&[65, 256]),
     // ["ejson", "json", "text"]
-    "ejson" => TagSet::new(&[66, 135, 255]),
+    "ejson" => TagSet::new(&[66, 135, 256]),
     // ["elm", "text"]
-    "elm" => TagSet::new(&[68, 255]),
+    "elm" => TagSet::new(&[68, 256]),
     // ["entitlements", "plist"]
     "entitlements" => TagSet::new(&[69, 196]),
     // ["dotenv", "text"]
-    "env" => TagSet::new(&[61, 255]),
+    "env" => TagSet::new(&[61, 256]),
     // ["binary", "eot"]
     "eot" => TagSet::new(&[21, 70]),
     // ["binary", "eps"]
     "eps" => TagSet::new(&[21, 71]),
     // ["erb", "text"]
-    "erb" => TagSet::new(&[72, 255]),
+    "erb" => TagSet::new(&[72, 256]),
     // ["erlang", "text"]
-    "erl" => TagSet::new(&[73, 255]),
+    "erl" => TagSet::new(&[73, 256]),
     // ["erlang", "text"]
-    "escript" => TagSet::new(&[73, 255]),
+    "escript" => TagSet::new(&[73, 256]),
     // ["elixir", "text"]
-    "ex" => TagSet::new(&[67, 255]),
+    "ex" => TagSet::new(&[67, 256]),
     // ["binary"]
     "exe" => TagSet::new(&[21]),
     // ["elixir", "text"]
-    "exs" => TagSet::new(&[67, 255]),
+    "exs" => TagSet::new(&[67, 256]),
     // ["text", "yaml"]
-    "eyaml" => TagSet::new(&[255, 301]),
+    "eyaml" => TagSet::new(&[256, 302]),
     // ["fortran", "text"]
-    "f03" => TagSet::new(&[82, 255]),
+    "f03" => TagSet::new(&[82, 256]),
     // ["fortran", "text"]
-    "f08" => TagSet::new(&[82, 255]),
+    "f08" => TagSet::new(&[82, 256]),
     // ["fortran", "text"]
-    "f90" => TagSet::new(&[82, 255]),
+    "f90" => TagSet::new(&[82, 256]),
     // ["fortran", "text"]
-    "f95" => TagSet::new(&[82, 255]),
+    "f95" => TagSet::new(&[82, 256]),
     // ["gherkin", "text"]
-    "feature" => TagSet::new(&[87, 255]),
+    "feature" => TagSet::new(&[87, 256]),
     // ["fish", "text"]
-    "fish" => TagSet::new(&[79, 255]),
+    "fish" => TagSet::new(&[79, 256]),
     // ["binary", "fits"]
     "fits" => TagSet::new(&[21, 80]),
     // ["f#", "text"]
-    "fs" => TagSet::new(&[76, 255]),
+    "fs" => TagSet::new(&[76, 256]),
     // ["fsproj", "msbuild", "text", "xml"]
-    "fsproj" => TagSet::new(&[83, 168, 255, 297]),
+    "fsproj" => TagSet::new(&[83, 168, 256, 298]),
     // ["f#", "f#script", "text"]
-    "fsx" => TagSet::new(&[76, 77, 255]),
+    "fsx" => TagSet::new(&[76, 77, 256]),
     // ["gdscript", "text"]
-    "gd" => TagSet::new(&[84, 255]),
+    "gd" => TagSet::new(&[84, 256]),
     // ["ruby", "text"]
-    "gemspec" => TagSet::new(&[223, 255]),
+    "gemspec" => TagSet::new(&[223, 256]),
     // ["geojson", "json", "text"]
-    "geojson" => TagSet::new(&[85, 135, 255]),
+    "geojson" => TagSet::new(&[85, 135, 256]),
     // ["binary", "ggb", "zip"]
-    "ggb" => TagSet::new(&[21, 86, 308]),
+    "ggb" => TagSet::new(&[21, 86, 309]),
     // ["binary", "gif", "image"]
     "gif" => TagSet::new(&[21, 88, 117]),
     // ["gleam", "text"]
-    "gleam" => TagSet::new(&[94, 255]),
+    "gleam" => TagSet::new(&[94, 256]),
     // ["go", "text"]
-    "go" => TagSet::new(&[95, 255]),
+    "go" => TagSet::new(&[95, 256]),
     // ["gotmpl", "text"]
-    "gotmpl" => TagSet::new(&[98, 255]),
+    "gotmpl" => TagSet::new(&[98, 256]),
     // ["gpx", "text", "xml"]
-    "gpx" => TagSet::new(&[99, 255, 297]),
+    "gpx" => TagSet::new(&[99, 256, 298]),
     // ["groovy", "text"]
-    "gradle" => TagSet::new(&[101, 255]),
+    "gradle" => TagSet::new(&[101, 256]),
     // ["graphql", "text"]
-    "graphql" => TagSet::new(&[100, 255]),
+    "graphql" => TagSet::new(&[100, 256]),
     // ["groovy", "text"]
-    "groovy" => TagSet::new(&[101, 255]),
+    "groovy" => TagSet::new(&[101, 256]),
     // ["gyb", "text"]
-    "gyb" => TagSet::new(&[102, 255]),
+    "gyb" => TagSet::new(&[102, 256]),
     // ["gyp", "python", "text"]
-    "gyp" => TagSet::new(&[103, 213, 255]),
+    "gyp" => TagSet::new(&[103, 213, 256]),
     // ["gyp", "python", "text"]
-    "gypi" => TagSet::new(&[103, 213, 255]),
+    "gypi" => TagSet::new(&[103, 213, 256]),
     // ["binary", "gzip"]
     "gz" => TagSet::new(&[21, 104]),
     // ["c", "c++", "header", "text"]
-    "h" => TagSet::new(&[28, 31, 108, 255]),
+    "h" => TagSet::new(&[28, 31, 108, 256]),
     // ["handlebars", "text"]
-    "hbs" => TagSet::new(&[105, 255]),
+    "hbs" => TagSet::new(&[105, 256]),
     // ["hcl", "text"]
-    "hcl" => TagSet::new(&[107, 255]),
+    "hcl" => TagSet::new(&[107, 256]),
     // ["c++", "header", "text"]
-    "hh" => TagSet::new(&[31, 108, 255]),
+    "hh" => TagSet::new(&[31, 108, 256]),
     // ["hlsl", "text"]
-    "hlsl" => TagSet::new(&[110, 255]),
+    "hlsl" => TagSet::new(&[110, 256]),
     // ["hlsl", "text"]
-    "hlsli" => TagSet::new(&[110, 255]),
+    "hlsli" => TagSet::new(&[110, 256]),
     // ["c++", "header", "text"]
-    "hpp" => TagSet::new(&[31, 108, 255]),
+    "hpp" => TagSet::new(&[31, 108, 256]),
     // ["erlang", "text"]
-    "hrl" => TagSet::new(&[73, 255]),
+    "hrl" => TagSet::new(&[73, 256]),
     // ["haskell", "text"]
-    "hs" => TagSet::new(&[106, 255]),
+    "hs" => TagSet::new(&[106, 256]),
     // ["html", "text"]
-    "htm" => TagSet::new(&[111, 255]),
+    "htm" => TagSet::new(&[111, 256]),
     // ["html", "text"]
-    "html" => TagSet::new(&[111, 255]),
+    "html" => TagSet::new(&[111, 256]),
     // ["c++", "header", "text"]
-    "hxx" => TagSet::new(&[31, 108, 255]),
+    "hxx" => TagSet::new(&[31, 108, 256]),
     // ["binary", "icns"]
     "icns" => TagSet::new(&[21, 113]),
     // ["binary", "icon"]
     "ico" => TagSet::new(&[21, 114]),
     // ["icalendar", "text"]
-    "ics" => TagSet::new(&[112, 255]),
+    "ics" => TagSet::new(&[112, 256]),
     // ["idl", "text"]
-    "idl" => TagSet::new(&[115, 255]),
+    "idl" => TagSet::new(&[115, 256]),
     // ["idris", "text"]
-    "idr" => TagSet::new(&[116, 255]),
+    "idr" => TagSet::new(&[116, 256]),
     // ["inc", "text"]
-    "inc" => TagSet::new(&[118, 255]),
+    "inc" => TagSet::new(&[118, 256]),
     // ["ini", "text"]
-    "ini" => TagSet::new(&[119, 255]),
+    "ini" => TagSet::new(&[119, 256]),
     // ["c++", "inl", "text"]
-    "inl" => TagSet::new(&[31, 120, 255]),
+    "inl" => TagSet::new(&[31, 120, 256]),
     // ["c++", "ino", "text"]
-    "ino" => TagSet::new(&[31, 121, 255]),
+    "ino" => TagSet::new(&[31, 121, 256]),
     // ["inx", "text", "xml"]
-    "inx" => TagSet::new(&[122, 255, 297]),
+    "inx" => TagSet::new(&[122, 256, 298]),
     // ["c++", "text"]
-    "ipp" => TagSet::new(&[31, 255]),
+    "ipp" => TagSet::new(&[31, 256]),
     // ["ipxe", "text"]
-    "ipxe" => TagSet::new(&[123, 255]),
+    "ipxe" => TagSet::new(&[123, 256]),
     // ["json", "jupyter", "text"]
-    "ipynb" => TagSet::new(&[135, 141, 255]),
+    "ipynb" => TagSet::new(&[135, 141, 256]),
     // ["c++", "text"]
-    "ixx" => TagSet::new(&[31, 255]),
+    "ixx" => TagSet::new(&[31, 256]),
     // ["jinja", "text"]
-    "j2" => TagSet::new(&[132, 255]),
+    "j2" => TagSet::new(&[132, 256]),
     // ["jade", "text"]
-    "jade" => TagSet::new(&[125, 255]),
+    "jade" => TagSet::new(&[125, 256]),
     // ["binary", "jar", "zip"]
-    "jar" => TagSet::new(&[21, 126, 308]),
+    "jar" => TagSet::new(&[21, 126, 309]),
     // ["java", "text"]
-    "java" => TagSet::new(&[127, 255]),
+    "java" => TagSet::new(&[127, 256]),
     // ["jbuilder", "ruby", "text"]
-    "jbuilder" => TagSet::new(&[130, 223, 255]),
+    "jbuilder" => TagSet::new(&[130, 223, 256]),
     // ["groovy", "jenkins", "text"]
-    "jenkins" => TagSet::new(&[101, 131, 255]),
+    "jenkins" => TagSet::new(&[101, 131, 256]),
     // ["groovy", "jenkins", "text"]
-    "jenkinsfile" => TagSet::new(&[101, 131, 255]),
+    "jenkinsfile" => TagSet::new(&[101, 131, 256]),
     // ["jinja", "text"]
-    "jinja" => TagSet::new(&[132, 255]),
+    "jinja" => TagSet::new(&[132, 256]),
     // ["jinja", "text"]
-    "jinja2" => TagSet::new(&[132, 255]),
+    "jinja2" => TagSet::new(&[132, 256]),
     // ["julia", "text"]
-    "jl" => TagSet::new(&[140, 255]),
+    "jl" => TagSet::new(&[140, 256]),
     // ["binary", "image", "jpeg"]
     "jpeg" => TagSet::new(&[21, 117, 133]),
     // ["binary", "image", "jpeg"]
     "jpg" => TagSet::new(&[21, 117, 133]),
     // ["javascript", "text"]
-    "js" => TagSet::new(&[129, 255]),
+    "js" => TagSet::new(&[129, 256]),
     // ["json", "text"]
-    "json" => TagSet::new(&[135, 255]),
+    "json" => TagSet::new(&[135, 256]),
     // ["json5", "text"]
-    "json5" => TagSet::new(&[136, 255]),
+    "json5" => TagSet::new(&[136, 256]),
     // ["json", "jsonld", "text"]
-    "jsonld" => TagSet::new(&[135, 137, 255]),
+    "jsonld" => TagSet::new(&[135, 137, 256]),
     // ["jsonnet", "text"]
-    "jsonnet" => TagSet::new(&[138, 255]),
+    "jsonnet" => TagSet::new(&[138, 256]),
     // ["jsx", "text"]
-    "jsx" => TagSet::new(&[139, 255]),
+    "jsx" => TagSet::new(&[139, 256]),
     // ["pem", "text"]
-    "key" => TagSet::new(&[187, 255]),
+    "key" => TagSet::new(&[187, 256]),
     // ["kml", "text", "xml"]
-    "kml" => TagSet::new(&[142, 255, 297]),
+    "kml" => TagSet::new(&[142, 256, 298]),
     // ["kotlin", "text"]
-    "kt" => TagSet::new(&[143, 255]),
+    "kt" => TagSet::new(&[143, 256]),
     // ["kotlin", "text"]
-    "kts" => TagSet::new(&[143, 255]),
+    "kts" => TagSet::new(&[143, 256]),
     // ["lean", "text"]
-    "lean" => TagSet::new(&[147, 255]),
+    "lean" => TagSet::new(&[147, 256]),
     // ["ini", "lektorproject", "text"]
-    "lektorproject" => TagSet::new(&[119, 149, 255]),
+    "lektorproject" => TagSet::new(&[119, 149, 256]),
     // ["less", "text"]
-    "less" => TagSet::new(&[150, 255]),
+    "less" => TagSet::new(&[150, 256]),
     // ["lazarus", "lazarus-form", "text"]
-    "lfm" => TagSet::new(&[145, 146, 255]),
+    "lfm" => TagSet::new(&[145, 146, 256]),
     // ["literate-haskell", "text"]
-    "lhs" => TagSet::new(&[152, 255]),
+    "lhs" => TagSet::new(&[152, 256]),
     // ["jsonnet", "text"]
-    "libsonnet" => TagSet::new(&[138, 255]),
+    "libsonnet" => TagSet::new(&[138, 256]),
     // ["idris", "text"]
-    "lidr" => TagSet::new(&[116, 255]),
+    "lidr" => TagSet::new(&[116, 256]),
     // ["liquid", "text"]
-    "liquid" => TagSet::new(&[151, 255]),
+    "liquid" => TagSet::new(&[151, 256]),
     // ["lazarus", "text", "xml"]
-    "lpi" => TagSet::new(&[145, 255, 297]),
+    "lpi" => TagSet::new(&[145, 256, 298]),
     // ["lazarus", "pascal", "text"]
-    "lpr" => TagSet::new(&[145, 184, 255]),
+    "lpr" => TagSet::new(&[145, 184, 256]),
     // ["lektor", "text"]
-    "lr" => TagSet::new(&[148, 255]),
+    "lr" => TagSet::new(&[148, 256]),
     // ["lua", "text"]
-    "lua" => TagSet::new(&[153, 255]),
+    "lua" => TagSet::new(&[153, 256]),
     // ["objective-c", "text"]
-    "m" => TagSet::new(&[179, 255]),
+    "m" => TagSet::new(&[179, 256]),
     // ["m4", "text"]
-    "m4" => TagSet::new(&[154, 255]),
+    "m4" => TagSet::new(&[154, 256]),
     // ["magik", "text"]
-    "magik" => TagSet::new(&[155, 255]),
+    "magik" => TagSet::new(&[155, 256]),
     // ["makefile", "text"]
-    "make" => TagSet::new(&[157, 255]),
+    "make" => TagSet::new(&[157, 256]),
     // ["manifest", "text"]
-    "manifest" => TagSet::new(&[158, 255]),
+    "manifest" => TagSet::new(&[158, 256]),
     // ["map", "text"]
-    "map" => TagSet::new(&[159, 255]),
+    "map" => TagSet::new(&[159, 256]),
     // ["markdown", "text"]
-    "markdown" => TagSet::new(&[160, 255]),
+    "markdown" => TagSet::new(&[160, 256]),
     // ["markdown", "text"]
-    "md" => TagSet::new(&[160, 255]),
+    "md" => TagSet::new(&[160, 256]),
     // ["mdx", "text"]
-    "mdx" => TagSet::new(&[161, 255]),
+    "mdx" => TagSet::new(&[161, 256]),
     // ["meson", "text"]
-    "meson" => TagSet::new(&[163, 255]),
+    "meson" => TagSet::new(&[163, 256]),
     // ["metal", "text"]
-    "metal" => TagSet::new(&[165, 255]),
+    "metal" => TagSet::new(&[165, 256]),
     // ["mib", "text"]
-    "mib" => TagSet::new(&[166, 255]),
+    "mib" => TagSet::new(&[166, 256]),
     // ["javascript", "text"]
-    "mjs" => TagSet::new(&[129, 255]),
+    "mjs" => TagSet::new(&[129, 256]),
     // ["makefile", "text"]
-    "mk" => TagSet::new(&[157, 255]),
+    "mk" => TagSet::new(&[157, 256]),
     // ["ocaml", "text"]
-    "ml" => TagSet::new(&[181, 255]),
+    "ml" => TagSet::new(&[181, 256]),
     // ["ocaml", "text"]
-    "mli" => TagSet::new(&[181, 255]),
+    "mli" => TagSet::new(&[181, 256]),
     // ["c++", "objective-c++", "text"]
-    "mm" => TagSet::new(&[31, 180, 255]),
+    "mm" => TagSet::new(&[31, 180, 256]),
     // ["modulemap", "text"]
-    "modulemap" => TagSet::new(&[167, 255]),
+    "modulemap" => TagSet::new(&[167, 256]),
     // ["musescore", "text", "xml"]
-    "mscx" => TagSet::new(&[169, 255, 297]),
+    "mscx" => TagSet::new(&[169, 256, 298]),
     // ["binary", "musescore", "zip"]
-    "mscz" => TagSet::new(&[21, 169, 308]),
+    "mscz" => TagSet::new(&[21, 169, 309]),
     // ["text", "ts"]
-    "mts" => TagSet::new(&[255, 261]),
+    "mts" => TagSet::new(&[256, 262]),
     // ["mustache", "text"]
-    "mustache" => TagSet::new(&[170, 255]),
+    "mustache" => TagSet::new(&[170, 256]),
     // ["myst", "text"]
-    "myst" => TagSet::new(&[171, 255]),
+    "myst" => TagSet::new(&[171, 256]),
     // ["ngdoc", "text"]
-    "ngdoc" => TagSet::new(&[172, 255]),
+    "ngdoc" => TagSet::new(&[172, 256]),
     // ["nim", "text"]
-    "nim" => TagSet::new(&[173, 255]),
+    "nim" => TagSet::new(&[173, 256]),
     // ["nimble", "text"]
-    "nimble" => TagSet::new(&[174, 255]),
+    "nimble" => TagSet::new(&[174, 256]),
     // ["nim", "text"]
-    "nims" => TagSet::new(&[173, 255]),
+    "nims" => TagSet::new(&[173, 256]),
     // ["nix", "text"]
-    "nix" => TagSet::new(&[175, 255]),
+    "nix" => TagSet::new(&[175, 256]),
     // ["nunjucks", "text"]
-    "njk" => TagSet::new(&[178, 255]),
+    "njk" => TagSet::new(&[178, 256]),
     // ["binary", "otf"]
     "otf" => TagSet::new(&[21, 182]),
     // ["binary", "p12"]
     "p12" => TagSet::new(&[21, 183]),
     // ["pascal", "text"]
-    "pas" => TagSet::new(&[184, 255]),
+    "pas" => TagSet::new(&[184, 256]),
     // ["diff", "text"]
-    "patch" => TagSet::new(&[57, 255]),
+    "patch" => TagSet::new(&[57, 256]),
     // ["binary", "pdf"]
     "pdf" => TagSet::new(&[21, 186]),
     // ["pem", "text"]
-    "pem" => TagSet::new(&[187, 255]),
+    "pem" => TagSet::new(&[187, 256]),
     // ["php", "text"]
-    "php" => TagSet::new(&[189, 255]),
+    "php" => TagSet::new(&[189, 256]),
     // ["php", "text"]
-    "php4" => TagSet::new(&[189, 255]),
+    "php4" => TagSet::new(&[189, 256]),
     // ["php", "text"]
-    "php5" => TagSet::new(&[189, 255]),
+    "php5" => TagSet::new(&[189, 256]),
     // ["php", "text"]
-    "phtml" => TagSet::new(&[189, 255]),
+    "phtml" => TagSet::new(&[189, 256]),
     // ["json", "piskel", "text"]
-    "piskel" => TagSet::new(&[135, 192, 255]),
+    "piskel" => TagSet::new(&[135, 192, 256]),
     // ["perl", "text"]
-    "pl" => TagSet::new(&[188, 255]),
+    "pl" => TagSet::new(&[188, 256]),
     // ["plantuml", "text"]
-    "plantuml" => TagSet::new(&[195, 255]),
+    "plantuml" => TagSet::new(&[195, 256]),
     // ["plist"]
     "plist" => TagSet::new(&[196]),
     // ["perl", "text"]
-    "pm" => TagSet::new(&[188, 255]),
+    "pm" => TagSet::new(&[188, 256]),
     // ["binary", "image", "png"]
     "png" => TagSet::new(&[21, 117, 197]),
     // ["pofile", "text"]
-    "po" => TagSet::new(&[198, 255]),
+    "po" => TagSet::new(&[198, 256]),
     // ["pom", "text", "xml"]
-    "pom" => TagSet::new(&[199, 255, 297]),
+    "pom" => TagSet::new(&[199, 256, 298]),
     // ["puppet", "text"]
-    "pp" => TagSet::new(&[206, 255]),
+    "pp" => TagSet::new(&[206, 256]),
     // ["image", "ppm"]
     "ppm" => TagSet::new(&[117, 201]),
     // ["prisma", "text"]
-    "prisma" => TagSet::new(&[203, 255]),
+    "prisma" => TagSet::new(&[203, 256]),
     // ["java-properties", "text"]
-    "properties" => TagSet::new(&[128, 255]),
+    "properties" => TagSet::new(&[128, 256]),
     // ["msbuild", "text", "xml"]
-    "props" => TagSet::new(&[168, 255, 297]),
+    "props" => TagSet::new(&[168, 256, 298]),
     // ["proto", "text"]
-    "proto" => TagSet::new(&[204, 255]),
+    "proto" => TagSet::new(&[204, 256]),
     // ["powershell", "text"]
-    "ps1" => TagSet::new(&[200, 255]),
+    "ps1" => TagSet::new(&[200, 256]),
     // ["powershell", "text"]
-    "psd1" => TagSet::new(&[200, 255]),
+    "psd1" => TagSet::new(&[200, 256]),
     // ["powershell", "text"]
-    "psm1" => TagSet::new(&[200, 255]),
+    "psm1" => TagSet::new(&[200, 256]),
     // ["pug", "text"]
-    "pug" => TagSet::new(&[205, 255]),
+    "pug" => TagSet::new(&[205, 256]),
     // ["plantuml", "text"]
-    "puml" => TagSet::new(&[195, 255]),
+    "puml" => TagSet::new(&[195, 256]),
     // ["purescript", "text"]
-    "purs" => TagSet::new(&[207, 255]),
+    "purs" => TagSet::new(&[207, 256]),
     // ["cython", "text"]
-    "pxd" => TagSet::new(&[52, 255]),
+    "pxd" => TagSet::new(&[52, 256]),
     // ["cython", "text"]
-    "pxi" => TagSet::new(&[52, 255]),
+    "pxi" => TagSet::new(&[52, 256]),
     // ["python", "text"]
-    "py" => TagSet::new(&[213, 255]),
+    "py" => TagSet::new(&[213, 256]),
     // ["pyi", "text"]
-    "pyi" => TagSet::new(&[208, 255]),
+    "pyi" => TagSet::new(&[208, 256]),
     // ["msbuild", "pyproj", "text", "xml"]
-    "pyproj" => TagSet::new(&[168, 211, 255, 297]),
+    "pyproj" => TagSet::new(&[168, 211, 256, 298]),
     // ["python", "text"]
-    "pyt" => TagSet::new(&[213, 255]),
+    "pyt" => TagSet::new(&[213, 256]),
     // ["python", "text"]
-    "pyw" => TagSet::new(&[213, 255]),
+    "pyw" => TagSet::new(&[213, 256]),
     // ["cython", "text"]
-    "pyx" => TagSet::new(&[52, 255]),
+    "pyx" => TagSet::new(&[52, 256]),
     // ["binary", "pyz"]
     "pyz" => TagSet::new(&[21, 216]),
     // ["binary", "pyz"]
     "pyzw" => TagSet::new(&[21, 216]),
     // ["qml", "text"]
-    "qml" => TagSet::new(&[217, 255]),
+    "qml" => TagSet::new(&[217, 256]),
     // ["r", "text"]
-    "r" => TagSet::new(&[218, 255]),
+    "r" => TagSet::new(&[218, 256]),
     // ["ruby", "text"]
-    "rake" => TagSet::new(&[223, 255]),
+    "rake" => TagSet::new(&[223, 256]),
     // ["ruby", "text"]
-    "rb" => TagSet::new(&[223, 255]),
+    "rb" => TagSet::new(&[223, 256]),
     // ["resx", "text", "xml"]
-    "resx" => TagSet::new(&[220, 255, 297]),
+    "resx" => TagSet::new(&[220, 256, 298]),
     // ["relax-ng", "text", "xml"]
-    "rng" => TagSet::new(&[219, 255, 297]),
+    "rng" => TagSet::new(&[219, 256, 298]),
     // ["robot", "text"]
-    "robot" => TagSet::new(&[221, 255]),
+    "robot" => TagSet::new(&[221, 256]),
     // ["rust", "text"]
-    "rs" => TagSet::new(&[224, 255]),
+    "rs" => TagSet::new(&[224, 256]),
     // ["rst", "text"]
-    "rst" => TagSet::new(&[222, 255]),
+    "rst" => TagSet::new(&[222, 256]),
     // ["asm", "text"]
-    "s" => TagSet::new(&[6, 255]),
+    "s" => TagSet::new(&[6, 256]),
     // ["sas", "text"]
-    "sas" => TagSet::new(&[227, 255]),
+    "sas" => TagSet::new(&[227, 256]),
     // ["sass", "text"]
-    "sass" => TagSet::new(&[228, 255]),
+    "sass" => TagSet::new(&[228, 256]),
     // ["sbt", "scala", "text"]
-    "sbt" => TagSet::new(&[229, 230, 255]),
+    "sbt" => TagSet::new(&[229, 230, 256]),
     // ["scala", "text"]
-    "sc" => TagSet::new(&[230, 255]),
+    "sc" => TagSet::new(&[230, 256]),
     // ["scala", "text"]
-    "scala" => TagSet::new(&[230, 255]),
+    "scala" => TagSet::new(&[230, 256]),
     // ["scheme", "text"]
-    "scm" => TagSet::new(&[231, 255]),
+    "scm" => TagSet::new(&[231, 256]),
     // ["scss", "text"]
-    "scss" => TagSet::new(&[233, 255]),
+    "scss" => TagSet::new(&[233, 256]),
     // ["shell", "text"]
-    "sh" => TagSet::new(&[235, 255]),
+    "sh" => TagSet::new(&[235, 256]),
     // ["sln", "text"]
-    "sln" => TagSet::new(&[236, 255]),
+    "sln" => TagSet::new(&[236, 256]),
     // ["msbuild", "slnx", "text", "xml"]
-    "slnx" => TagSet::new(&[168, 237, 255, 297]),
+    "slnx" => TagSet::new(&[168, 237, 256, 298]),
     // ["salt", "text"]
-    "sls" => TagSet::new(&[225, 255]),
+    "sls" => TagSet::new(&[225, 256]),
     // ["binary"]
     "so" => TagSet::new(&[21]),
     // ["solidity", "text"]
-    "sol" => TagSet::new(&[239, 255]),
+    "sol" => TagSet::new(&[239, 256]),
     // ["spec", "text"]
-    "spec" => TagSet::new(&[240, 255]),
+    "spec" => TagSet::new(&[240, 256]),
     // ["sql", "text"]
-    "sql" => TagSet::new(&[241, 255]),
+    "sql" => TagSet::new(&[241, 256]),
     // ["scheme", "text"]
-    "ss" => TagSet::new(&[231, 255]),
+    "ss" => TagSet::new(&[231, 256]),
     // ["tex", "text"]
-    "sty" => TagSet::new(&[254, 255]),
+    "sty" => TagSet::new(&[255, 256]),
     // ["stylus", "text"]
-    "styl" => TagSet::new(&[242, 255]),
+    "styl" => TagSet::new(&[242, 256]),
     // ["system-verilog", "text"]
-    "sv" => TagSet::new(&[249, 255]),
+    "sv" => TagSet::new(&[250, 256]),
     // ["svelte", "text"]
-    "svelte" => TagSet::new(&[243, 255]),
+    "svelte" => TagSet::new(&[244, 256]),
     // ["image", "svg", "text", "xml"]
-    "svg" => TagSet::new(&[117, 244, 255, 297]),
+    "svg" => TagSet::new(&[117, 245, 256, 298]),
     // ["system-verilog", "text"]
-    "svh" => TagSet::new(&[249, 255]),
+    "svh" => TagSet::new(&[250, 256]),
     // ["binary", "swf"]
-    "swf" => TagSet::new(&[21, 245]),
+    "swf" => TagSet::new(&[21, 246]),
     // ["swift", "text"]
-    "swift" => TagSet::new(&[246, 255]),
+    "swift" => TagSet::new(&[247, 256]),
     // ["swiftdeps", "text"]
-    "swiftdeps" => TagSet::new(&[247, 255]),
+    "swiftdeps" => TagSet::new(&[248, 256]),
     // ["python", "text", "twisted"]
-    "tac" => TagSet::new(&[213, 255, 266]),
+    "tac" => TagSet::new(&[213, 256, 267]),
     // ["binary", "tar"]
-    "tar" => TagSet::new(&[21, 250]),
+    "tar" => TagSet::new(&[21, 251]),
     // ["msbuild", "text", "xml"]
-    "targets" => TagSet::new(&[168, 255, 297]),
+    "targets" => TagSet::new(&[168, 256, 298]),
     // ["templ", "text"]
-    "templ" => TagSet::new(&[252, 255]),
+    "templ" => TagSet::new(&[253, 256]),
     // ["tex", "text"]
-    "tex" => TagSet::new(&[254, 255]),
+    "tex" => TagSet::new(&[255, 256]),
     // ["text", "textproto"]
-    "textproto" => TagSet::new(&[255, 256]),
+    "textproto" => TagSet::new(&[256, 257]),
     // ["terraform", "text"]
-    "tf" => TagSet::new(&[253, 255]),
+    "tf" => TagSet::new(&[254, 256]),
     // ["terraform", "text"]
-    "tfvars" => TagSet::new(&[253, 255]),
+    "tfvars" => TagSet::new(&[254, 256]),
     // ["binary", "gzip"]
     "tgz" => TagSet::new(&[21, 104]),
     // ["text", "thrift"]
-    "thrift" => TagSet::new(&[255, 257]),
+    "thrift" => TagSet::new(&[256, 258]),
     // ["binary", "image", "tiff"]
-    "tif" => TagSet::new(&[21, 117, 258]),
+    "tif" => TagSet::new(&[21, 117, 259]),
     // ["binary", "image", "tiff"]
-    "tiff" => TagSet::new(&[21, 117, 258]),
+    "tiff" => TagSet::new(&[21, 117, 259]),
     // ["text", "toml"]
-    "toml" => TagSet::new(&[255, 260]),
+    "toml" => TagSet::new(&[256, 261]),
     // ["c++", "text"]
-    "tpp" => TagSet::new(&[31, 255]),
+    "tpp" => TagSet::new(&[31, 256]),
     // ["text", "ts"]
-    "ts" => TagSet::new(&[255, 261]),
+    "ts" => TagSet::new(&[256, 262]),
     // ["text", "tsv"]
-    "tsv" => TagSet::new(&[255, 262]),
+    "tsv" => TagSet::new(&[256, 263]),
     // ["text", "tsx"]
-    "tsx" => TagSet::new(&[255, 263]),
+    "tsx" => TagSet::new(&[256, 264]),
     // ["binary", "ttf"]
-    "ttf" => TagSet::new(&[21, 264]),
+    "ttf" => TagSet::new(&[21, 265]),
     // ["text", "twig"]
-    "twig" => TagSet::new(&[255, 265]),
+    "twig" => TagSet::new(&[256, 266]),
     // ["ini", "text", "txsprofile"]
-    "txsprofile" => TagSet::new(&[119, 255, 267]),
+    "txsprofile" => TagSet::new(&[119, 256, 268]),
     // ["plain-text", "text"]
-    "txt" => TagSet::new(&[194, 255]),
+    "txt" => TagSet::new(&[194, 256]),
     // ["text", "textproto"]
-    "txtpb" => TagSet::new(&[255, 256]),
+    "txtpb" => TagSet::new(&[256, 257]),
     // ["text", "urdf", "xml"]
-    "urdf" => TagSet::new(&[255, 268, 297]),
+    "urdf" => TagSet::new(&[256, 269, 298]),
     // ["text", "verilog"]
-    "v" => TagSet::new(&[255, 273]),
+    "v" => TagSet::new(&[256, 274]),
     // ["text", "vb"]
-    "vb" => TagSet::new(&[255, 269]),
+    "vb" => TagSet::new(&[256, 270]),
     // ["msbuild", "text", "vbproj", "xml"]
-    "vbproj" => TagSet::new(&[168, 255, 270, 297]),
+    "vbproj" => TagSet::new(&[168, 256, 271, 298]),
     // ["msbuild", "text", "vcxproj", "xml"]
-    "vcxproj" => TagSet::new(&[168, 255, 271, 297]),
+    "vcxproj" => TagSet::new(&[168, 256, 272, 298]),
     // ["text", "vdx"]
-    "vdx" => TagSet::new(&[255, 272]),
+    "vdx" => TagSet::new(&[256, 273]),
     // ["text", "verilog"]
-    "vh" => TagSet::new(&[255, 273]),
+    "vh" => TagSet::new(&[256, 274]),
     // ["text", "vhdl"]
-    "vhd" => TagSet::new(&[255, 274]),
+    "vhd" => TagSet::new(&[256, 275]),
     // ["text", "vim"]
-    "vim" => TagSet::new(&[255, 275]),
+    "vim" => TagSet::new(&[256, 276]),
     // ["text", "vtl"]
-    "vtl" => TagSet::new(&[255, 276]),
+    "vtl" => TagSet::new(&[256, 277]),
     // ["text", "vue"]
-    "vue" => TagSet::new(&[255, 277]),
+    "vue" => TagSet::new(&[256, 278]),
     // ["binary", "jar", "zip"]
-    "war" => TagSet::new(&[21, 126, 308]),
+    "war" => TagSet::new(&[21, 126, 309]),
     // ["audio", "binary", "wav"]
-    "wav" => TagSet::new(&[9, 21, 278]),
+    "wav" => TagSet::new(&[9, 21, 279]),
     // ["binary", "image", "webp"]
-    "webp" => TagSet::new(&[21, 117, 279]),
+    "webp" => TagSet::new(&[21, 117, 280]),
     // ["binary", "wheel", "zip"]
-    "whl" => TagSet::new(&[21, 280, 308]),
+    "whl" => TagSet::new(&[21, 281, 309]),
     // ["text", "wkt"]
-    "wkt" => TagSet::new(&[255, 281]),
+    "wkt" => TagSet::new(&[256, 282]),
     // ["binary", "woff"]
-    "woff" => TagSet::new(&[21, 282]),
+    "woff" => TagSet::new(&[21, 283]),
     // ["binary", "woff2"]
-    "woff2" => TagSet::new(&[21, 283]),
+    "woff2" => TagSet::new(&[21, 284]),
     // ["text", "wsdl", "xml"]
-    "wsdl" => TagSet::new(&[255, 284, 297]),
+    "wsdl" => TagSet::new(&[256, 285, 298]),
     // ["python", "text", "wsgi"]
-    "wsgi" => TagSet::new(&[213, 255, 285]),
+    "wsgi" => TagSet::new(&[213, 256, 286]),
     // ["text", "urdf", "xacro", "xml"]
-    "xacro" => TagSet::new(&[255, 268, 286, 297]),
+    "xacro" => TagSet::new(&[256, 269, 287, 298]),
     // ["text", "xaml", "xml"]
-    "xaml" => TagSet::new(&[255, 287, 297]),
+    "xaml" => TagSet::new(&[256, 288, 298]),
     // ["text", "xcconfig", "xcodebuild"]
-    "xcconfig" => TagSet::new(&[255, 288, 289]),
+    "xcconfig" => TagSet::new(&[256, 289, 290]),
     // ["plist", "xcodebuild", "xcprivacy"]
-    "xcprivacy" => TagSet::new(&[196, 289, 290]),
+    "xcprivacy" => TagSet::new(&[196, 290, 291]),
     // ["text", "xcodebuild", "xcscheme", "xml"]
-    "xcscheme" => TagSet::new(&[255, 289, 291, 297]),
+    "xcscheme" => TagSet::new(&[256, 290, 292, 298]),
     // ["plist", "xcodebuild", "xcsettings"]
-    "xcsettings" => TagSet::new(&[196, 289, 292]),
+    "xcsettings" => TagSet::new(&[196, 290, 293]),
     // ["json", "text", "xcodebuild", "xctestplan"]
-    "xctestplan" => TagSet::new(&[135, 255, 289, 293]),
+    "xctestplan" => TagSet::new(&[135, 256, 290, 294]),
     // ["text", "xcodebuild", "xcworkspacedata", "xml"]
-    "xcworkspacedata" => TagSet::new(&[255, 289, 294, 297]),
+    "xcworkspacedata" => TagSet::new(&[256, 290, 295, 298]),
     // ["html", "text", "xhtml", "xml"]
-    "xhtml" => TagSet::new(&[111, 255, 295, 297]),
+    "xhtml" => TagSet::new(&[111, 256, 296, 298]),
     // ["text", "xliff", "xml"]
-    "xlf" => TagSet::new(&[255, 296, 297]),
+    "xlf" => TagSet::new(&[256, 297, 298]),
     // ["text", "xliff", "xml"]
-    "xliff" => TagSet::new(&[255, 296, 297]),
+    "xliff" => TagSet::new(&[256, 297, 298]),
     // ["text", "xml"]
-    "xml" => TagSet::new(&[255, 297]),
+    "xml" => TagSet::new(&[256, 298]),
     // ["text", "xquery"]
-    "xq" => TagSet::new(&[255, 298]),
+    "xq" => TagSet::new(&[256, 299]),
     // ["text", "xquery"]
-    "xql" => TagSet::new(&[255, 298]),
+    "xql" => TagSet::new(&[256, 299]),
     // ["text", "xquery"]
-    "xqm" => TagSet::new(&[255, 298]),
+    "xqm" => TagSet::new(&[256, 299]),
     // ["text", "xquery"]
-    "xqu" => TagSet::new(&[255, 298]),
+    "xqu" => TagSet::new(&[256, 299]),
     // ["text", "xquery"]
-    "xquery" => TagSet::new(&[255, 298]),
+    "xquery" => TagSet::new(&[256, 299]),
     // ["text", "xquery"]
-    "xqy" => TagSet::new(&[255, 298]),
+    "xqy" => TagSet::new(&[256, 299]),
     // ["text", "xml", "xsd"]
-    "xsd" => TagSet::new(&[255, 297, 299]),
+    "xsd" => TagSet::new(&[256, 298, 300]),
     // ["text", "xml", "xsl"]
-    "xsl" => TagSet::new(&[255, 297, 300]),
+    "xsl" => TagSet::new(&[256, 298, 301]),
     // ["text", "xml", "xsl"]
-    "xslt" => TagSet::new(&[255, 297, 300]),
+    "xslt" => TagSet::new(&[256, 298, 301]),
     // ["text", "yaml"]
-    "yaml" => TagSet::new(&[255, 301]),
+    "yaml" => TagSet::new(&[256, 302]),
     // ["text", "yaml", "yamlld"]
-    "yamlld" => TagSet::new(&[255, 301, 302]),
+    "yamlld" => TagSet::new(&[256, 302, 303]),
     // ["text", "yang"]
-    "yang" => TagSet::new(&[255, 304]),
+    "yang" => TagSet::new(&[256, 305]),
     // ["text", "xml", "yin"]
-    "yin" => TagSet::new(&[255, 297, 305]),
+    "yin" => TagSet::new(&[256, 298, 306]),
     // ["text", "yaml"]
-    "yml" => TagSet::new(&[255, 301]),
+    "yml" => TagSet::new(&[256, 302]),
     // ["text", "xml", "zcml"]
-    "zcml" => TagSet::new(&[255, 297, 306]),
+    "zcml" => TagSet::new(&[256, 298, 307]),
     // ["text", "zig"]
-    "zig" => TagSet::new(&[255, 307]),
+    "zig" => TagSet::new(&[256, 308]),
     // ["binary", "zip"]
-    "zip" => TagSet::new(&[21, 308]),
+    "zip" => TagSet::new(&[21, 309]),
     // ["text", "zpt"]
-    "zpt" => TagSet::new(&[255, 309]),
+    "zpt" => TagSet::new(&[256, 310]),
     // ["shell", "text", "zsh"]
-    "zsh" => TagSet::new(&[235, 255, 310]),
+    "zsh" => TagSet::new(&[235, 256, 311]),
+};
+
+pub const COMPOSITE_EXTENSIONS: phf::Map<&str, TagSet> = phf::phf_map! {
+    // ["binary", "bzip2", "tar"]
+    "tar.bz2" => TagSet::new(&[21, 26, 251]),
+    // ["binary", "gzip", "tar"]
+    "tar.gz" => TagSet::new(&[21, 104, 251]),
 };
 
 pub const NAMES: phf::Map<&str, TagSet> = phf::phf_map! {
     // ["text", "yaml"]
-    ".ansible-lint" => TagSet::new(&[255, 301]),
+    ".ansible-lint" => TagSet::new(&[256, 302]),
     // ["babelrc", "json", "text"]
-    ".babelrc" => TagSet::new(&[13, 135, 255]),
+    ".babelrc" => TagSet::new(&[13, 135, 256]),
     // ["bash", "shell", "text"]
-    ".bash_aliases" => TagSet::new(&[14, 235, 255]),
+    ".bash_aliases" => TagSet::new(&[14, 235, 256]),
     // ["bash", "shell", "text"]
-    ".bash_profile" => TagSet::new(&[14, 235, 255]),
+    ".bash_profile" => TagSet::new(&[14, 235, 256]),
     // ["bash", "shell", "text"]
-    ".bashrc" => TagSet::new(&[14, 235, 255]),
+    ".bashrc" => TagSet::new(&[14, 235, 256]),
     // ["bazelrc", "text"]
-    ".bazelrc" => TagSet::new(&[18, 255]),
+    ".bazelrc" => TagSet::new(&[18, 256]),
     // ["bowerrc", "json", "text"]
-    ".bowerrc" => TagSet::new(&[24, 135, 255]),
+    ".bowerrc" => TagSet::new(&[24, 135, 256]),
     // ["browserslistrc", "text"]
-    ".browserslistrc" => TagSet::new(&[25, 255]),
+    ".browserslistrc" => TagSet::new(&[25, 256]),
     // ["text", "yaml"]
-    ".clang-format" => TagSet::new(&[255, 301]),
+    ".clang-format" => TagSet::new(&[256, 302]),
     // ["text", "yaml"]
-    ".clang-tidy" => TagSet::new(&[255, 301]),
+    ".clang-tidy" => TagSet::new(&[256, 302]),
     // ["codespellrc", "ini", "text"]
-    ".codespellrc" => TagSet::new(&[39, 119, 255]),
+    ".codespellrc" => TagSet::new(&[39, 119, 256]),
     // ["coveragerc", "ini", "text"]
-    ".coveragerc" => TagSet::new(&[41, 119, 255]),
+    ".coveragerc" => TagSet::new(&[41, 119, 256]),
     // ["csh", "shell", "text"]
-    ".cshrc" => TagSet::new(&[43, 235, 255]),
+    ".cshrc" => TagSet::new(&[43, 235, 256]),
     // ["csslintrc", "json", "text"]
-    ".csslintrc" => TagSet::new(&[47, 135, 255]),
+    ".csslintrc" => TagSet::new(&[47, 135, 256]),
     // ["dockerignore", "text"]
-    ".dockerignore" => TagSet::new(&[60, 255]),
+    ".dockerignore" => TagSet::new(&[60, 256]),
     // ["editorconfig", "text"]
-    ".editorconfig" => TagSet::new(&[63, 255]),
+    ".editorconfig" => TagSet::new(&[63, 256]),
     // ["bash", "shell", "text"]
-    ".envrc" => TagSet::new(&[14, 235, 255]),
+    ".envrc" => TagSet::new(&[14, 235, 256]),
     // ["flake8", "ini", "text"]
-    ".flake8" => TagSet::new(&[81, 119, 255]),
+    ".flake8" => TagSet::new(&[81, 119, 256]),
     // ["gitattributes", "text"]
-    ".gitattributes" => TagSet::new(&[89, 255]),
+    ".gitattributes" => TagSet::new(&[89, 256]),
     // ["gitconfig", "ini", "text"]
-    ".gitconfig" => TagSet::new(&[90, 119, 255]),
+    ".gitconfig" => TagSet::new(&[90, 119, 256]),
     // ["gitignore", "text"]
-    ".gitignore" => TagSet::new(&[91, 255]),
+    ".gitignore" => TagSet::new(&[91, 256]),
     // ["gitlint", "ini", "text"]
-    ".gitlint" => TagSet::new(&[92, 119, 255]),
+    ".gitlint" => TagSet::new(&[92, 119, 256]),
     // ["gitmodules", "text"]
-    ".gitmodules" => TagSet::new(&[93, 255]),
+    ".gitmodules" => TagSet::new(&[93, 256]),
     // ["hgrc", "ini", "text"]
-    ".hgrc" => TagSet::new(&[109, 119, 255]),
+    ".hgrc" => TagSet::new(&[109, 119, 256]),
     // ["ini", "isort", "text"]
-    ".isort.cfg" => TagSet::new(&[119, 124, 255]),
+    ".isort.cfg" => TagSet::new(&[119, 124, 256]),
     // ["jshintrc", "json", "text"]
-    ".jshintrc" => TagSet::new(&[134, 135, 255]),
+    ".jshintrc" => TagSet::new(&[134, 135, 256]),
     // ["mailmap", "text"]
-    ".mailmap" => TagSet::new(&[156, 255]),
+    ".mailmap" => TagSet::new(&[156, 256]),
     // ["json", "mention-bot", "text"]
-    ".mention-bot" => TagSet::new(&[135, 162, 255]),
+    ".mention-bot" => TagSet::new(&[135, 162, 256]),
     // ["npmignore", "text"]
-    ".npmignore" => TagSet::new(&[177, 255]),
+    ".npmignore" => TagSet::new(&[177, 256]),
     // ["pdbrc", "python", "text"]
-    ".pdbrc" => TagSet::new(&[185, 213, 255]),
+    ".pdbrc" => TagSet::new(&[185, 213, 256]),
     // ["gitignore", "prettierignore", "text"]
-    ".prettierignore" => TagSet::new(&[91, 202, 255]),
+    ".prettierignore" => TagSet::new(&[91, 202, 256]),
     // ["ini", "pypirc", "text"]
-    ".pypirc" => TagSet::new(&[119, 210, 255]),
+    ".pypirc" => TagSet::new(&[119, 210, 256]),
     // ["ini", "text"]
-    ".rstcheck.cfg" => TagSet::new(&[119, 255]),
+    ".rstcheck.cfg" => TagSet::new(&[119, 256]),
     // ["salt-lint", "text", "yaml"]
-    ".salt-lint" => TagSet::new(&[226, 255, 301]),
+    ".salt-lint" => TagSet::new(&[226, 256, 302]),
     // ["ini", "text"]
-    ".sqlfluff" => TagSet::new(&[119, 255]),
+    ".sqlfluff" => TagSet::new(&[119, 256]),
     // ["text", "yaml", "yamllint"]
-    ".yamllint" => TagSet::new(&[255, 301, 303]),
+    ".yamllint" => TagSet::new(&[256, 302, 304]),
     // ["shell", "text", "zsh"]
-    ".zlogin" => TagSet::new(&[235, 255, 310]),
+    ".zlogin" => TagSet::new(&[235, 256, 311]),
     // ["shell", "text", "zsh"]
-    ".zlogout" => TagSet::new(&[235, 255, 310]),
+    ".zlogout" => TagSet::new(&[235, 256, 311]),
     // ["shell", "text", "zsh"]
-    ".zprofile" => TagSet::new(&[235, 255, 310]),
+    ".zprofile" => TagSet::new(&[235, 256, 311]),
     // ["shell", "text", "zsh"]
-    ".zshenv" => TagSet::new(&[235, 255, 310]),
+    ".zshenv" => TagSet::new(&[235, 256, 311]),
     // ["shell", "text", "zsh"]
-    ".zshrc" => TagSet::new(&[235, 255, 310]),
+    ".zshrc" => TagSet::new(&[235, 256, 311]),
     // ["plain-text", "text"]
-    "AUTHORS" => TagSet::new(&[194, 255]),
+    "AUTHORS" => TagSet::new(&[194, 256]),
     // ["bazel", "text"]
-    "BUILD" => TagSet::new(&[17, 255]),
+    "BUILD" => TagSet::new(&[17, 256]),
     // ["ruby", "text"]
-    "Brewfile" => TagSet::new(&[223, 255]),
+    "Brewfile" => TagSet::new(&[223, 256]),
     // ["plain-text", "text"]
-    "CHANGELOG" => TagSet::new(&[194, 255]),
+    "CHANGELOG" => TagSet::new(&[194, 256]),
     // ["cmake", "text"]
-    "CMakeLists.txt" => TagSet::new(&[38, 255]),
+    "CMakeLists.txt" => TagSet::new(&[38, 256]),
     // ["plain-text", "text"]
-    "CONTRIBUTING" => TagSet::new(&[194, 255]),
+    "CONTRIBUTING" => TagSet::new(&[194, 256]),
     // ["plain-text", "text"]
-    "COPYING" => TagSet::new(&[194, 255]),
+    "COPYING" => TagSet::new(&[194, 256]),
     // ["cargo-lock", "text", "toml"]
-    "Cargo.lock" => TagSet::new(&[34, 255, 260]),
+    "Cargo.lock" => TagSet::new(&[34, 256, 261]),
     // ["cargo", "text", "toml"]
-    "Cargo.toml" => TagSet::new(&[33, 255, 260]),
+    "Cargo.toml" => TagSet::new(&[33, 256, 261]),
     // ["dockerfile", "text"]
-    "Containerfile" => TagSet::new(&[59, 255]),
+    "Containerfile" => TagSet::new(&[59, 256]),
     // ["dockerfile", "text"]
-    "Dockerfile" => TagSet::new(&[59, 255]),
+    "Dockerfile" => TagSet::new(&[59, 256]),
     // ["ruby", "text"]
-    "Fastfile" => TagSet::new(&[223, 255]),
+    "Fastfile" => TagSet::new(&[223, 256]),
     // ["makefile", "text"]
-    "GNUmakefile" => TagSet::new(&[157, 255]),
+    "GNUmakefile" => TagSet::new(&[157, 256]),
     // ["ruby", "text"]
-    "Gemfile" => TagSet::new(&[223, 255]),
+    "Gemfile" => TagSet::new(&[223, 256]),
     // ["text"]
-    "Gemfile.lock" => TagSet::new(&[255]),
+    "Gemfile.lock" => TagSet::new(&[256]),
     // ["groovy", "jenkins", "text"]
-    "Jenkinsfile" => TagSet::new(&[101, 131, 255]),
+    "Jenkinsfile" => TagSet::new(&[101, 131, 256]),
     // ["plain-text", "text"]
-    "LICENSE" => TagSet::new(&[194, 255]),
+    "LICENSE" => TagSet::new(&[194, 256]),
     // ["plain-text", "text"]
-    "MAINTAINERS" => TagSet::new(&[194, 255]),
+    "MAINTAINERS" => TagSet::new(&[194, 256]),
     // ["makefile", "text"]
-    "Makefile" => TagSet::new(&[157, 255]),
+    "Makefile" => TagSet::new(&[157, 256]),
     // ["plain-text", "text"]
-    "NEWS" => TagSet::new(&[194, 255]),
+    "NEWS" => TagSet::new(&[194, 256]),
     // ["plain-text", "text"]
-    "NOTICE" => TagSet::new(&[194, 255]),
+    "NOTICE" => TagSet::new(&[194, 256]),
     // ["plain-text", "text"]
-    "PATENTS" => TagSet::new(&[194, 255]),
+    "PATENTS" => TagSet::new(&[194, 256]),
     // ["alpm", "bash", "pkgbuild", "shell", "text"]
-    "PKGBUILD" => TagSet::new(&[1, 14, 193, 235, 255]),
+    "PKGBUILD" => TagSet::new(&[1, 14, 193, 235, 256]),
     // ["text", "toml"]
-    "Pipfile" => TagSet::new(&[255, 260]),
+    "Pipfile" => TagSet::new(&[256, 261]),
     // ["json", "text"]
-    "Pipfile.lock" => TagSet::new(&[135, 255]),
+    "Pipfile.lock" => TagSet::new(&[135, 256]),
     // ["plain-text", "text"]
-    "README" => TagSet::new(&[194, 255]),
+    "README" => TagSet::new(&[194, 256]),
     // ["ruby", "text"]
-    "Rakefile" => TagSet::new(&[223, 255]),
+    "Rakefile" => TagSet::new(&[223, 256]),
     // ["scons", "text"]
-    "SConscript" => TagSet::new(&[232, 255]),
+    "SConscript" => TagSet::new(&[232, 256]),
     // ["scons", "text"]
-    "SConstruct" => TagSet::new(&[232, 255]),
+    "SConstruct" => TagSet::new(&[232, 256]),
     // ["scons", "text"]
-    "SCsub" => TagSet::new(&[232, 255]),
+    "SCsub" => TagSet::new(&[232, 256]),
     // ["text", "tiltfile"]
-    "Tiltfile" => TagSet::new(&[255, 259]),
+    "Tiltfile" => TagSet::new(&[256, 260]),
     // ["ruby", "text"]
-    "Vagrantfile" => TagSet::new(&[223, 255]),
+    "Vagrantfile" => TagSet::new(&[223, 256]),
     // ["bazel", "text"]
-    "WORKSPACE" => TagSet::new(&[17, 255]),
+    "WORKSPACE" => TagSet::new(&[17, 256]),
     // ["bitbake", "text"]
-    "bblayers.conf" => TagSet::new(&[22, 255]),
+    "bblayers.conf" => TagSet::new(&[22, 256]),
     // ["bitbake", "text"]
-    "bitbake.conf" => TagSet::new(&[22, 255]),
+    "bitbake.conf" => TagSet::new(&[22, 256]),
     // ["ruby", "text"]
-    "config.ru" => TagSet::new(&[223, 255]),
+    "config.ru" => TagSet::new(&[223, 256]),
     // ["bazel", "text"]
-    "copy.bara.sky" => TagSet::new(&[17, 255]),
+    "copy.bara.sky" => TagSet::new(&[17, 256]),
     // ["bash", "shell", "text"]
-    "direnvrc" => TagSet::new(&[14, 235, 255]),
+    "direnvrc" => TagSet::new(&[14, 235, 256]),
     // ["go-mod", "text"]
-    "go.mod" => TagSet::new(&[96, 255]),
+    "go.mod" => TagSet::new(&[96, 256]),
     // ["go-sum", "text"]
-    "go.sum" => TagSet::new(&[97, 255]),
+    "go.sum" => TagSet::new(&[97, 256]),
     // ["makefile", "text"]
-    "makefile" => TagSet::new(&[157, 255]),
+    "makefile" => TagSet::new(&[157, 256]),
     // ["meson", "text"]
-    "meson.build" => TagSet::new(&[163, 255]),
+    "meson.build" => TagSet::new(&[163, 256]),
     // ["meson", "meson-options", "text"]
-    "meson.options" => TagSet::new(&[163, 164, 255]),
+    "meson.options" => TagSet::new(&[163, 164, 256]),
     // ["meson", "meson-options", "text"]
-    "meson_options.txt" => TagSet::new(&[163, 164, 255]),
+    "meson_options.txt" => TagSet::new(&[163, 164, 256]),
     // ["text", "toml"]
-    "poetry.lock" => TagSet::new(&[255, 260]),
+    "poetry.lock" => TagSet::new(&[256, 261]),
     // ["pom", "text", "xml"]
-    "pom.xml" => TagSet::new(&[199, 255, 297]),
+    "pom.xml" => TagSet::new(&[199, 256, 298]),
     // ["ini", "pylintrc", "text"]
-    "pylintrc" => TagSet::new(&[119, 209, 255]),
+    "pylintrc" => TagSet::new(&[119, 209, 256]),
     // ["pyproject", "text", "toml"]
-    "pyproject.toml" => TagSet::new(&[212, 255, 260]),
+    "pyproject.toml" => TagSet::new(&[212, 256, 261]),
     // ["erlang", "text"]
-    "rebar.config" => TagSet::new(&[73, 255]),
+    "rebar.config" => TagSet::new(&[73, 256]),
     // ["ini", "text"]
-    "setup.cfg" => TagSet::new(&[119, 255]),
+    "setup.cfg" => TagSet::new(&[119, 256]),
     // ["erlang", "text"]
-    "sys.config" => TagSet::new(&[73, 255]),
+    "sys.config" => TagSet::new(&[73, 256]),
     // ["erlang", "text"]
-    "sys.config.src" => TagSet::new(&[73, 255]),
+    "sys.config.src" => TagSet::new(&[73, 256]),
     // ["text", "toml"]
-    "uv.lock" => TagSet::new(&[255, 260]),
+    "uv.lock" => TagSet::new(&[256, 261]),
     // ["python", "text"]
-    "wscript" => TagSet::new(&[213, 255]),
+    "wscript" => TagSet::new(&[213, 256]),
 };
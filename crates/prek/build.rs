@@ -23,6 +23,7 @@ SOFTWARE.
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use fs_err as fs;
 
@@ -38,6 +39,44 @@ fn main() {
         .to_path_buf();
 
     commit_info(&workspace_root);
+    build_info();
+}
+
+fn build_info() {
+    #[allow(clippy::disallowed_methods)]
+    let target = std::env::var("TARGET").unwrap();
+    println!("cargo:rustc-env=PREK_BUILD_TARGET={target}");
+
+    // Honor `SOURCE_DATE_EPOCH` so reproducible builds embed a stable date.
+    // https://reproducible-builds.org/specs/source-date-epoch/
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    #[allow(clippy::disallowed_methods)]
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs())
+        });
+    let (year, month, day) = civil_from_days(timestamp / 86_400);
+    println!("cargo:rustc-env=PREK_BUILD_DATE={year:04}-{month:02}-{day:02}");
+}
+
+/// Convert days since the Unix epoch to a `(year, month, day)` date.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
 }
 
 fn commit_info(workspace_root: &Path) {
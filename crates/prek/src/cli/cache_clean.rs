@@ -18,6 +18,7 @@ pub(crate) fn cache_clean(store: &Store, printer: Printer) -> Result<ExitStatus>
         writeln!(printer.stdout(), "{}", "Nothing to clean".bold())?;
         return Ok(ExitStatus::Success);
     }
+    store.ensure_marked()?;
 
     let num_paths = walkdir::WalkDir::new(store.path()).into_iter().count();
     let reporter = CleaningReporter::new(printer, num_paths);
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::ops::AddAssign;
@@ -29,6 +30,8 @@ enum RemovalKind {
     Tools,
     CacheEntries,
     PatchFiles,
+    PartialInstalls,
+    ScratchEntries,
 }
 
 impl RemovalKind {
@@ -40,6 +43,8 @@ impl RemovalKind {
                 RemovalKind::Tools => "tools",
                 RemovalKind::CacheEntries => "cache entries",
                 RemovalKind::PatchFiles => "patch files",
+                RemovalKind::PartialInstalls => "partial installs",
+                RemovalKind::ScratchEntries => "scratch entries",
             }
         } else {
             match self {
@@ -48,6 +53,8 @@ impl RemovalKind {
                 RemovalKind::Tools => "tool",
                 RemovalKind::CacheEntries => "cache entry",
                 RemovalKind::PatchFiles => "patch file",
+                RemovalKind::PartialInstalls => "partial install",
+                RemovalKind::ScratchEntries => "scratch entry",
             }
         }
     }
@@ -55,6 +62,10 @@ impl RemovalKind {
 
 const STALE_PATCH_RETENTION: Duration = Duration::from_hours(30 * 24);
 
+/// Prefixes of the temporary directories tool installers download and extract into
+/// before moving the result into place.
+const PARTIAL_INSTALL_PREFIXES: &[&str] = &[".install-", ".tmp"];
+
 #[derive(Debug, Clone)]
 struct RemovalItem {
     label: String,
@@ -77,6 +88,8 @@ struct Removal {
     kind: RemovalKind,
     count: usize,
     bytes: u64,
+    /// Reclaimed bytes keyed by store bucket, e.g. `tools/node` or `cache/go`.
+    buckets: BTreeMap<String, u64>,
     items: Vec<RemovalItem>,
 }
 
@@ -86,9 +99,20 @@ impl Removal {
             kind,
             count: 0,
             bytes: 0,
+            buckets: BTreeMap::new(),
             items: Vec::new(),
         }
     }
+
+    fn record(&mut self, bucket: String, bytes: u64, item: Option<RemovalItem>) {
+        self.count += 1;
+        self.bytes = self.bytes.saturating_add(bytes);
+        let bucket_bytes = self.buckets.entry(bucket).or_default();
+        *bucket_bytes = bucket_bytes.saturating_add(bytes);
+        if let Some(item) = item {
+            self.items.push(item);
+        }
+    }
 }
 
 impl Display for Removal {
@@ -108,6 +132,10 @@ impl AddAssign for Removal {
 
         self.count += rhs.count;
         self.bytes = self.bytes.saturating_add(rhs.bytes);
+        for (bucket, bytes) in rhs.buckets {
+            let bucket_bytes = self.buckets.entry(bucket).or_default();
+            *bucket_bytes = bucket_bytes.saturating_add(bytes);
+        }
         self.items.extend(rhs.items);
     }
 }
@@ -117,6 +145,7 @@ struct RemovalSummary {
     parts: Vec<String>,
     count: usize,
     bytes: u64,
+    buckets: BTreeMap<String, u64>,
 }
 
 impl RemovalSummary {
@@ -140,6 +169,10 @@ impl AddAssign<&Removal> for RemovalSummary {
         }
         self.count += rhs.count;
         self.bytes = self.bytes.saturating_add(rhs.bytes);
+        for (bucket, bytes) in &rhs.buckets {
+            let bucket_bytes = self.buckets.entry(bucket.clone()).or_default();
+            *bucket_bytes = bucket_bytes.saturating_add(*bytes);
+        }
     }
 }
 
@@ -149,6 +182,7 @@ pub(crate) async fn cache_gc(
     verbose: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    store.ensure_marked()?;
     let _lock = store.lock_async().await?;
 
     let tracked_configs = store.tracked_configs()?;
@@ -247,6 +281,7 @@ pub(crate) async fn cache_gc(
         RemovalKind::Repos,
         &store.repos_dir(),
         &used_repo_keys,
+        |_| "repos".to_string(),
         dry_run,
         verbose,
     )?;
@@ -256,6 +291,7 @@ pub(crate) async fn cache_gc(
         RemovalKind::HookEnvs,
         &store.hooks_dir(),
         &used_hook_env_dirs,
+        |_| "hooks".to_string(),
         dry_run,
         verbose,
     )?;
@@ -267,6 +303,7 @@ pub(crate) async fn cache_gc(
         RemovalKind::Tools,
         &tools_root,
         &used_tool_names,
+        |name| format!("tools/{name}"),
         dry_run,
         verbose,
     )?;
@@ -284,14 +321,18 @@ pub(crate) async fn cache_gc(
         RemovalKind::CacheEntries,
         &cache_root,
         &used_cache_names,
+        |name| format!("cache/{name}"),
         dry_run,
         verbose,
     )?;
 
-    // Seep scratch/, as it is only temporary data.
-    if !dry_run {
-        let _ = fs_err::remove_dir_all(store.scratch_path());
-    }
+    // Sweep tools/<bucket>/.install-* of the remaining buckets, left behind by interrupted
+    // downloads. Holding the store lock means no install is in progress.
+    let removed_partials = sweep_partial_installs(store, &used_tools, dry_run, verbose)?;
+
+    // Sweep scratch/, as it is only temporary data.
+    let removed_scratch = sweep_scratch(&store.scratch_path(), dry_run, verbose)?;
+
     // Keep recent recovery patches, but clear out stale ones that are unlikely to be useful.
     let removed_patches = sweep_stale_patch_files(&store.patches_dir(), dry_run, verbose)?;
 
@@ -300,6 +341,8 @@ pub(crate) async fn cache_gc(
     removed += &removed_hooks;
     removed += &removed_tools;
     removed += &removed_cache;
+    removed += &removed_partials;
+    removed += &removed_scratch;
     removed += &removed_patches;
 
     let removed_total_bytes = removed.total_bytes();
@@ -317,10 +360,13 @@ pub(crate) async fn cache_gc(
         )?;
 
         if verbose {
+            print_bucket_sizes(printer, &removed)?;
             print_removed_details(printer, verb, &removed_repos)?;
             print_removed_details(printer, verb, &removed_hooks)?;
             print_removed_details(printer, verb, &removed_tools)?;
             print_removed_details(printer, verb, &removed_cache)?;
+            print_removed_details(printer, verb, &removed_partials)?;
+            print_removed_details(printer, verb, &removed_scratch)?;
             print_removed_details(printer, verb, &removed_patches)?;
         }
     }
@@ -328,6 +374,25 @@ pub(crate) async fn cache_gc(
     Ok(ExitStatus::Success)
 }
 
+fn print_bucket_sizes(printer: Printer, removed: &RemovalSummary) -> Result<()> {
+    writeln!(
+        printer.stdout(),
+        "\n{}:",
+        "Reclaimed space by bucket".bold()
+    )?;
+    for (bucket, bytes) in &removed.buckets {
+        let (bytes, unit) = human_readable_bytes(*bytes);
+        writeln!(
+            printer.stdout(),
+            "{} {}: {}",
+            "-".dimmed(),
+            bucket.bold(),
+            format!("{bytes:.1}{unit}").cyan()
+        )?;
+    }
+    Ok(())
+}
+
 fn print_removed_details(printer: Printer, verb: &str, removal: &Removal) -> Result<()> {
     if removal.count == 0 {
         return Ok(());
@@ -422,7 +487,7 @@ fn hook_env_requirements_from_config(
     requirements
 }
 
-fn mark_tool_versions_from_install_info(
+pub(crate) fn mark_tool_versions_from_install_info(
     store: &Store,
     info: &InstallInfo,
     used_tool_versions: &mut FxHashMap<ToolBucket, FxHashSet<String>>,
@@ -523,22 +588,14 @@ fn sweep_tool_bucket_versions(
         };
 
         if dry_run {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if let Some(item) = item {
-                removal.items.push(item);
-            }
+            removal.record(format!("tools/{bucket}"), entry_bytes, item);
             continue;
         }
 
         if let Err(err) = fs_err::remove_dir_all(&path) {
             warn!(%err, path = %path.display(), "Failed to remove unused tool version");
         } else {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if let Some(item) = item {
-                removal.items.push(item);
-            }
+            removal.record(format!("tools/{bucket}"), entry_bytes, item);
         }
     }
 
@@ -549,6 +606,7 @@ fn sweep_dir_by_name(
     kind: RemovalKind,
     root: &Path,
     keep_names: &FxHashSet<String>,
+    bucket_of: impl Fn(&str) -> String,
     dry_run: bool,
     collect_names: bool,
 ) -> Result<Removal> {
@@ -606,11 +664,7 @@ fn sweep_dir_by_name(
         };
 
         if dry_run {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if collect_names && let Some(item) = item {
-                removal.items.push(item);
-            }
+            removal.record(bucket_of(name), entry_bytes, item);
             continue;
         }
 
@@ -618,13 +672,7 @@ fn sweep_dir_by_name(
         if let Err(err) = fs_err::remove_dir_all(&path) {
             warn!(%err, path = %path.display(), "Failed to remove unused cache entry");
         } else {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if collect_names {
-                if let Some(item) = item {
-                    removal.items.push(item);
-                }
-            }
+            removal.record(bucket_of(name), entry_bytes, item);
         }
     }
 
@@ -685,21 +733,68 @@ fn sweep_stale_patch_files(root: &Path, dry_run: bool, collect_names: bool) -> R
             .then(|| RemovalItem::new(name.to_string(), path.to_string_lossy().into_owned()));
 
         if dry_run {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if let Some(item) = item {
-                removal.items.push(item);
-            }
+            removal.record("patches".to_string(), entry_bytes, item);
             continue;
         }
 
         if let Err(err) = fs_err::remove_file(&path) {
             warn!(%err, path = %path.display(), "Failed to remove old patch file");
         } else {
-            removal.count += 1;
-            removal.bytes = removal.bytes.saturating_add(entry_bytes);
-            if let Some(item) = item {
-                removal.items.push(item);
+            removal.record("patches".to_string(), entry_bytes, item);
+        }
+    }
+
+    Ok(removal)
+}
+
+fn sweep_partial_installs(
+    store: &Store,
+    used_tools: &FxHashSet<ToolBucket>,
+    dry_run: bool,
+    collect_names: bool,
+) -> Result<Removal> {
+    let mut removal = Removal::new(RemovalKind::PartialInstalls);
+
+    for &bucket in used_tools {
+        let bucket_root = store.tools_path(bucket);
+        let entries = match fs_err::read_dir(&bucket_root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !PARTIAL_INSTALL_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                continue;
+            }
+
+            let entry_bytes = dir_size_bytes(&path);
+            let item = collect_names.then(|| {
+                RemovalItem::new(
+                    format!("{bucket}/{name}"),
+                    path.to_string_lossy().into_owned(),
+                )
+            });
+
+            if dry_run {
+                removal.record(format!("tools/{bucket}"), entry_bytes, item);
+                continue;
+            }
+
+            if let Err(err) = fs_err::remove_dir_all(&path) {
+                warn!(%err, path = %path.display(), "Failed to remove partial install");
+            } else {
+                removal.record(format!("tools/{bucket}"), entry_bytes, item);
             }
         }
     }
@@ -707,6 +802,46 @@ fn sweep_stale_patch_files(root: &Path, dry_run: bool, collect_names: bool) -> R
     Ok(removal)
 }
 
+fn sweep_scratch(root: &Path, dry_run: bool, collect_names: bool) -> Result<Removal> {
+    let mut removal = Removal::new(RemovalKind::ScratchEntries);
+    let entries = match fs_err::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Removal::new(RemovalKind::ScratchEntries));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let entry_bytes = dir_size_bytes(&path);
+        let item = collect_names
+            .then(|| RemovalItem::new(name.to_string(), path.to_string_lossy().into_owned()));
+
+        if dry_run {
+            removal.record("scratch".to_string(), entry_bytes, item);
+            continue;
+        }
+
+        let result = if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            fs_err::remove_dir_all(&path)
+        } else {
+            fs_err::remove_file(&path)
+        };
+        if let Err(err) = result {
+            warn!(%err, path = %path.display(), "Failed to remove scratch entry");
+        } else {
+            removal.record("scratch".to_string(), entry_bytes, item);
+        }
+    }
+
+    Ok(removal)
+}
+
 fn label_for_entry(
     kind: RemovalKind,
     repo_marker: Option<&RepoMarker>,
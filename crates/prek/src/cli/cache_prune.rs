@@ -0,0 +1,180 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use strum::IntoEnumIterator;
+use tracing::warn;
+
+use crate::cli::ExitStatus;
+use crate::cli::cache_gc::mark_tool_versions_from_install_info;
+use crate::cli::cache_size::{dir_size_bytes, human_readable_bytes};
+use crate::cli::run::InstallCache;
+use crate::printer::Printer;
+use crate::store::{Store, ToolBucket};
+
+/// A store entry selected for removal.
+struct StaleEntry {
+    label: String,
+    path: PathBuf,
+    bytes: u64,
+}
+
+/// Remove hook environments not used within `older_than`, and toolchains that no remaining
+/// environment uses and that were installed before the same cutoff.
+pub(crate) async fn cache_prune(
+    store: &Store,
+    older_than: Duration,
+    dry_run: bool,
+    verbose: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    store.ensure_marked()?;
+    let _lock = store.lock_async().await?;
+
+    let cutoff = SystemTime::now()
+        .checked_sub(older_than)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let install_cache = InstallCache::new();
+    let mut stale_envs = Vec::new();
+    let mut used_tool_versions: FxHashMap<ToolBucket, FxHashSet<String>> = FxHashMap::default();
+    for installed in install_cache.installed_hooks(store).await {
+        let info = installed.info_ref();
+        if info.last_used().is_some_and(|last_used| last_used < cutoff) {
+            let name = info
+                .env_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            stale_envs.push(StaleEntry {
+                label: format!("{name} ({})", info.language),
+                path: info.env_path.clone(),
+                bytes: dir_size_bytes(&info.env_path),
+            });
+        } else {
+            mark_tool_versions_from_install_info(store, info, &mut used_tool_versions);
+        }
+    }
+
+    let mut stale_tools = Vec::new();
+    for bucket in ToolBucket::iter() {
+        stale_tools.extend(stale_tool_versions(
+            bucket,
+            &store.tools_path(bucket),
+            used_tool_versions.get(&bucket),
+            cutoff,
+        )?);
+    }
+
+    let removed_envs = remove_entries(stale_envs, dry_run);
+    let removed_tools = remove_entries(stale_tools, dry_run);
+
+    if removed_envs.is_empty() && removed_tools.is_empty() {
+        writeln!(printer.stdout(), "{}", "Nothing to prune".bold())?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let mut parts = Vec::new();
+    for (entries, singular, plural) in [
+        (&removed_envs, "hook env", "hook envs"),
+        (&removed_tools, "tool", "tools"),
+    ] {
+        if !entries.is_empty() {
+            let noun = if entries.len() == 1 { singular } else { plural };
+            parts.push(format!("{} {noun}", entries.len().cyan().bold()));
+        }
+    }
+    let total_bytes = removed_envs
+        .iter()
+        .chain(&removed_tools)
+        .map(|entry| entry.bytes)
+        .sum::<u64>();
+    let (bytes, unit) = human_readable_bytes(total_bytes);
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    writeln!(
+        printer.stdout(),
+        "{verb} {} ({})",
+        parts.join(", "),
+        format!("{bytes:.1}{unit}").cyan().bold(),
+    )?;
+
+    if verbose {
+        for entry in removed_envs.iter().chain(&removed_tools) {
+            writeln!(printer.stdout(), "{} {}", "-".dimmed(), entry.label.bold())?;
+            writeln!(
+                printer.stdout(),
+                "  {}: {}",
+                "path".bold().dimmed(),
+                entry.path.display()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Find the version directories of a tool bucket that are unused and older than `cutoff`.
+fn stale_tool_versions(
+    bucket: ToolBucket,
+    bucket_root: &Path,
+    used_versions: Option<&FxHashSet<String>>,
+    cutoff: SystemTime,
+) -> Result<Vec<StaleEntry>> {
+    let entries = match fs_err::read_dir(bucket_root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut stale = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Tools such as uv are single files inside `tools/`, not versions.
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(version) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // Skip hidden dirs, such as partial installs.
+        if version.starts_with('.') || used_versions.is_some_and(|used| used.contains(version)) {
+            continue;
+        }
+        let installed = entry.metadata().and_then(|metadata| metadata.modified());
+        if !installed.is_ok_and(|installed| installed < cutoff) {
+            continue;
+        }
+
+        stale.push(StaleEntry {
+            label: format!("{bucket}/{version}"),
+            bytes: dir_size_bytes(&path),
+            path,
+        });
+    }
+
+    Ok(stale)
+}
+
+/// Remove `entries` unless `dry_run` is set, returning the entries that are (or would be) gone.
+fn remove_entries(entries: Vec<StaleEntry>, dry_run: bool) -> Vec<StaleEntry> {
+    let mut entries = entries
+        .into_iter()
+        .filter(|entry| {
+            if dry_run {
+                return true;
+            }
+            match fs_err::remove_dir_all(&entry.path) {
+                Ok(()) => true,
+                Err(err) => {
+                    warn!(%err, path = %entry.path.display(), "Failed to remove stale cache entry");
+                    false
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by(|a, b| a.label.cmp(&b.label));
+    entries
+}
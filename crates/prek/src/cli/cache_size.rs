@@ -7,19 +7,45 @@ use crate::cli::ExitStatus;
 use crate::printer::Printer;
 use crate::store::Store;
 
-/// Display the total size of the cache.
+/// Display the total size of the cache, optionally broken down by bucket.
 pub(crate) fn cache_size(
     store: &Store,
     human_readable: bool,
+    buckets: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    let format_size = |bytes: u64| {
+        if human_readable {
+            let (bytes, unit) = human_readable_bytes(bytes);
+            format!("{bytes:.1}{unit}")
+        } else {
+            bytes.to_string()
+        }
+    };
+
     // Walk the entire cache root
     let total_bytes = dir_size_bytes(store.path());
-    if human_readable {
-        let (bytes, unit) = human_readable_bytes(total_bytes);
-        writeln!(printer.stdout_important(), "{bytes:.1}{unit}")?;
-    } else {
-        writeln!(printer.stdout_important(), "{total_bytes}")?;
+    if !buckets {
+        writeln!(printer.stdout_important(), "{}", format_size(total_bytes))?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let mut bucket_bytes = vec![
+        ("repos", dir_size_bytes(&store.repos_dir())),
+        ("hooks", dir_size_bytes(&store.hooks_dir())),
+        ("tools", dir_size_bytes(&store.tools_dir())),
+        ("cache", dir_size_bytes(&store.cache_dir())),
+    ];
+    let known_bytes = bucket_bytes.iter().map(|(_, bytes)| bytes).sum::<u64>();
+    bucket_bytes.push(("other", total_bytes.saturating_sub(known_bytes)));
+    bucket_bytes.push(("total", total_bytes));
+
+    for (bucket, bytes) in bucket_bytes {
+        writeln!(
+            printer.stdout_important(),
+            "{bucket:<5} {}",
+            format_size(bytes)
+        )?;
     }
 
     Ok(ExitStatus::Success)
@@ -0,0 +1,592 @@
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fmt::{Display, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use prek_consts::PRE_COMMIT_CONFIG_YAML;
+use tempfile::TempDir;
+
+use crate::cli::ExitStatus;
+use crate::git::{self, GIT_ROOT, GitCommandExt};
+use crate::printer::Printer;
+use crate::process::Cmd;
+use crate::store::Store;
+
+const STATUSES: &[&str] = &["Passed", "Failed", "Skipped", "Dry Run"];
+const NO_FILES: &str = "(no files to check)";
+/// The summary line prek prints before a group of hooks that modified files together.
+const MODIFIED_GROUP_HEADER: &str = "Files were modified by following hooks";
+
+/// The outcome of a single hook, parsed from the status lines of `run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HookOutcome {
+    name: String,
+    /// The hook id, printed by both tools in verbose mode.
+    id: Option<String>,
+    status: String,
+    exit_code: Option<i32>,
+}
+
+impl Display for HookOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.status)?;
+        if let Some(code) = self.exit_code {
+            write!(f, " (exit code {code})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything observable about one `run` invocation.
+#[derive(Debug, Default)]
+struct RunSummary {
+    exit_code: Option<i32>,
+    hooks: Vec<HookOutcome>,
+    modified_files: BTreeSet<String>,
+    output: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Difference {
+    OnlyPrek(String),
+    OnlyPreCommit(String),
+    Outcome {
+        name: String,
+        prek: String,
+        pre_commit: String,
+    },
+    ModifiedOnlyByPrek(String),
+    ModifiedOnlyByPreCommit(String),
+    HookModifiedOnlyByPrek {
+        hook: String,
+        file: String,
+    },
+    HookModifiedOnlyByPreCommit {
+        hook: String,
+        file: String,
+    },
+    ExitCode {
+        prek: Option<i32>,
+        pre_commit: Option<i32>,
+    },
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn code(code: Option<i32>) -> String {
+            code.map_or_else(|| "none".to_string(), |code| code.to_string())
+        }
+
+        match self {
+            Self::OnlyPrek(name) => write!(f, "Hook `{name}` was only run by prek"),
+            Self::OnlyPreCommit(name) => write!(f, "Hook `{name}` was only run by pre-commit"),
+            Self::Outcome {
+                name,
+                prek,
+                pre_commit,
+            } => write!(
+                f,
+                "Hook `{name}`: prek reported `{prek}`, pre-commit reported `{pre_commit}`"
+            ),
+            Self::ModifiedOnlyByPrek(file) => write!(f, "File `{file}` was only modified by prek"),
+            Self::ModifiedOnlyByPreCommit(file) => {
+                write!(f, "File `{file}` was only modified by pre-commit")
+            }
+            Self::HookModifiedOnlyByPrek { hook, file } => {
+                write!(f, "Hook `{hook}`: file `{file}` was only modified by prek")
+            }
+            Self::HookModifiedOnlyByPreCommit { hook, file } => write!(
+                f,
+                "Hook `{hook}`: file `{file}` was only modified by pre-commit"
+            ),
+            Self::ExitCode { prek, pre_commit } => write!(
+                f,
+                "Exit code: prek exited with {}, pre-commit exited with {}",
+                code(*prek),
+                code(*pre_commit)
+            ),
+        }
+    }
+}
+
+/// Parse a status line like `Trim trailing whitespace.....(no files to check)Skipped`
+/// into the hook name and its status.
+fn parse_status_line(line: &str) -> Option<(&str, String)> {
+    let (rest, status) = STATUSES
+        .iter()
+        .find_map(|status| line.strip_suffix(status).map(|rest| (rest, *status)))?;
+    let (rest, status) = match rest.strip_suffix(NO_FILES) {
+        Some(rest) => (rest, format!("{status} {NO_FILES}")),
+        None => (rest, status.to_string()),
+    };
+    let name = rest.strip_suffix('.')?.trim_end_matches('.');
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, status))
+}
+
+/// Extract the hooks and their outcomes from the (uncolored) output of `run`.
+fn parse_hook_outcomes(output: &str) -> Vec<HookOutcome> {
+    let mut hooks: Vec<HookOutcome> = Vec::new();
+
+    for line in output.lines() {
+        // prek indents hooks of workspace projects and draws a box around grouped hooks.
+        let line = line.trim_start_matches([' ', '┌', '│', '└']).trim_end();
+
+        if let Some(code) = line.strip_prefix("- exit code: ") {
+            if let Some(hook) = hooks.last_mut() {
+                hook.exit_code = code.trim().parse().ok();
+            }
+        } else if let Some(id) = line.strip_prefix("- hook id: ") {
+            if let Some(hook) = hooks.last_mut() {
+                hook.id = Some(id.trim().to_string());
+            }
+        } else if let Some((name, status)) = parse_status_line(line) {
+            if name == MODIFIED_GROUP_HEADER {
+                continue;
+            }
+            hooks.push(HookOutcome {
+                name: name.to_string(),
+                id: None,
+                status,
+                exit_code: None,
+            });
+        }
+    }
+
+    hooks
+}
+
+fn compare_runs(prek: &RunSummary, pre_commit: &RunSummary) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    // Pair hooks by name, in order, so hooks with the same name are matched one by one.
+    let mut unmatched: Vec<Option<&HookOutcome>> = pre_commit.hooks.iter().map(Some).collect();
+    for hook in &prek.hooks {
+        let matched = unmatched
+            .iter_mut()
+            .find(|other| other.is_some_and(|other| other.name == hook.name))
+            .and_then(Option::take);
+        match matched {
+            Some(other) if (&other.status, other.exit_code) == (&hook.status, hook.exit_code) => {}
+            Some(other) => differences.push(Difference::Outcome {
+                name: hook.name.clone(),
+                prek: hook.to_string(),
+                pre_commit: other.to_string(),
+            }),
+            None => differences.push(Difference::OnlyPrek(hook.name.clone())),
+        }
+    }
+    differences.extend(
+        unmatched
+            .into_iter()
+            .flatten()
+            .map(|hook| Difference::OnlyPreCommit(hook.name.clone())),
+    );
+
+    differences.extend(
+        prek.modified_files
+            .difference(&pre_commit.modified_files)
+            .map(|file| Difference::ModifiedOnlyByPrek(file.clone())),
+    );
+    differences.extend(
+        pre_commit
+            .modified_files
+            .difference(&prek.modified_files)
+            .map(|file| Difference::ModifiedOnlyByPreCommit(file.clone())),
+    );
+
+    if prek.exit_code != pre_commit.exit_code {
+        differences.push(Difference::ExitCode {
+            prek: prek.exit_code,
+            pre_commit: pre_commit.exit_code,
+        });
+    }
+
+    differences
+}
+
+/// Compare the files `hook` modified when run on its own by each tool.
+fn compare_hook_files(
+    hook: &str,
+    prek: &BTreeSet<String>,
+    pre_commit: &BTreeSet<String>,
+) -> Vec<Difference> {
+    prek.difference(pre_commit)
+        .map(|file| Difference::HookModifiedOnlyByPrek {
+            hook: hook.to_string(),
+            file: file.clone(),
+        })
+        .chain(
+            pre_commit
+                .difference(prek)
+                .map(|file| Difference::HookModifiedOnlyByPreCommit {
+                    hook: hook.to_string(),
+                    file: file.clone(),
+                }),
+        )
+        .collect()
+}
+
+/// Write the currently staged state of the repository as a tree object.
+async fn write_index_tree(root: &Path) -> Result<String> {
+    let output = git::git_cmd()?
+        .arg("write-tree")
+        .current_dir(root)
+        .output()
+        .await
+        .context("Failed to snapshot the index, are there unresolved merge conflicts?")?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Clone the repository into `dest`, with the index and the work tree set to `tree`.
+///
+/// Unstaged changes are left out, just like `run` stashes them before running hooks.
+async fn clone_with_tree(root: &Path, tree: &str, dest: &Path) -> Result<()> {
+    git::git_cmd()?
+        .isolate_from_git_env()
+        .arg("clone")
+        .arg("--quiet")
+        // Share the object database so the staged blobs are reachable from the clone.
+        .arg("--shared")
+        .arg("--no-checkout")
+        .arg(root)
+        .arg(dest)
+        .output()
+        .await?;
+    git::git_cmd()?
+        .isolate_from_git_env()
+        .arg("read-tree")
+        .arg("--reset")
+        .arg("-u")
+        .arg(tree)
+        .current_dir(dest)
+        .output()
+        .await?;
+    Ok(())
+}
+
+/// Files in the work tree that differ from the index, including new untracked files.
+async fn modified_files(repo: &Path) -> Result<BTreeSet<String>> {
+    let output = git::git_cmd()?
+        .isolate_from_git_env()
+        .arg("status")
+        .arg("--porcelain")
+        .arg("--untracked-files=all")
+        .arg("-z")
+        .current_dir(repo)
+        .output()
+        .await?;
+
+    Ok(parse_porcelain(&output.stdout))
+}
+
+/// Parse the files changed in the work tree from `git status --porcelain -z`.
+fn parse_porcelain(stdout: &[u8]) -> BTreeSet<String> {
+    let mut files = BTreeSet::new();
+    let mut entries = stdout
+        .split(|&b| b == b'\0')
+        .filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        // `XY path`, where `Y` is the work tree status.
+        if entry.len() <= 3 {
+            continue;
+        }
+        let (index, work_tree) = (entry[0], entry[1]);
+        // A rename or copy is followed by its source path as a separate entry.
+        let source = if matches!(index, b'R' | b'C') || matches!(work_tree, b'R' | b'C') {
+            entries.next()
+        } else {
+            None
+        };
+        if work_tree == b' ' {
+            continue;
+        }
+        files.insert(String::from_utf8_lossy(&entry[3..]).into_owned());
+        if work_tree == b'R'
+            && let Some(source) = source
+        {
+            files.insert(String::from_utf8_lossy(source).into_owned());
+        }
+    }
+    files
+}
+
+async fn run_in(mut cmd: Cmd, repo: &Path) -> Result<RunSummary> {
+    let output = cmd
+        .isolate_from_git_env()
+        .current_dir(repo)
+        .stdin(Stdio::null())
+        .check(false)
+        .output()
+        .await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    Ok(RunSummary {
+        exit_code: output.status.code(),
+        hooks: parse_hook_outcomes(&stdout),
+        modified_files: modified_files(repo).await?,
+        output: stdout,
+    })
+}
+
+fn run_cmd(program: &Path, hook: Option<&str>, run_args: &[OsString]) -> Cmd {
+    let mut cmd = Cmd::new(program);
+    // Verbose mode makes both tools print the id of every hook.
+    cmd.arg("run").arg("--color").arg("never").arg("--verbose");
+    if let Some(hook) = hook {
+        cmd.arg(hook);
+    }
+    cmd.args(run_args);
+    cmd
+}
+
+fn write_output(printer: Printer, tool: &str, summary: &RunSummary) -> Result<()> {
+    writeln!(
+        printer.stdout(),
+        "{}",
+        format!("Output of `{tool} run`:").cyan().bold()
+    )?;
+    writeln!(printer.stdout(), "{}", summary.output.trim_end().dimmed())?;
+    Ok(())
+}
+
+pub(crate) async fn compare(
+    store: &Store,
+    pre_commit: Option<PathBuf>,
+    run_args: Vec<OsString>,
+    per_hook: bool,
+    verbose: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let pre_commit = match pre_commit {
+        Some(pre_commit) => pre_commit,
+        None => which::which("pre-commit").context(
+            "Failed to find `pre-commit` in PATH, install it or pass its path with `--pre-commit`",
+        )?,
+    };
+
+    let root = GIT_ROOT.as_ref()?;
+    if !root.join(PRE_COMMIT_CONFIG_YAML).is_file() {
+        anyhow::bail!(
+            "`pre-commit` only reads `{PRE_COMMIT_CONFIG_YAML}`, which does not exist in `{}`",
+            root.display()
+        );
+    }
+
+    let version = Cmd::new(&pre_commit)
+        .arg("--version")
+        .output()
+        .await
+        .context("Failed to get the version of `pre-commit`")?;
+    let version = String::from_utf8_lossy(&version.stdout).trim().to_string();
+
+    writeln!(
+        printer.stdout(),
+        "Comparing `prek run` with `{}` ({version})",
+        "pre-commit run".cyan()
+    )?;
+
+    let tmp_dir = TempDir::with_prefix_in("compare-", store.scratch_path())?;
+    let tree = write_index_tree(root).await?;
+    let prek_repo = tmp_dir.path().join("prek");
+    let pre_commit_repo = tmp_dir.path().join("pre-commit");
+    clone_with_tree(root, &tree, &prek_repo).await?;
+    clone_with_tree(root, &tree, &pre_commit_repo).await?;
+
+    // Run one after the other, so both tools get the machine to themselves.
+    let prek_exe = std::env::current_exe()?;
+    let prek = run_in(run_cmd(&prek_exe, None, &run_args), &prek_repo).await?;
+    let pre_commit_summary =
+        run_in(run_cmd(&pre_commit, None, &run_args), &pre_commit_repo).await?;
+
+    if verbose {
+        write_output(printer, "prek", &prek)?;
+        write_output(printer, "pre-commit", &pre_commit_summary)?;
+    }
+
+    let mut differences = compare_runs(&prek, &pre_commit_summary);
+
+    if per_hook {
+        // Run every hook on its own, so the files each hook modified can be told apart.
+        let ids = prek
+            .hooks
+            .iter()
+            .chain(&pre_commit_summary.hooks)
+            .filter_map(|hook| hook.id.as_deref())
+            .collect::<BTreeSet<_>>();
+        for (i, id) in ids.into_iter().enumerate() {
+            let prek_repo = tmp_dir.path().join(format!("prek-{i}"));
+            let pre_commit_repo = tmp_dir.path().join(format!("pre-commit-{i}"));
+            clone_with_tree(root, &tree, &prek_repo).await?;
+            clone_with_tree(root, &tree, &pre_commit_repo).await?;
+
+            let prek_run = run_in(run_cmd(&prek_exe, Some(id), &run_args), &prek_repo).await?;
+            let pre_commit_run =
+                run_in(run_cmd(&pre_commit, Some(id), &run_args), &pre_commit_repo).await?;
+            differences.extend(compare_hook_files(
+                id,
+                &prek_run.modified_files,
+                &pre_commit_run.modified_files,
+            ));
+        }
+    }
+
+    if differences.is_empty() {
+        let count = prek.hooks.len();
+        writeln!(
+            printer.stdout(),
+            "{} across {count} hook{}",
+            "No differences found".green().bold(),
+            if count == 1 { "" } else { "s" }
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer.stdout(),
+        "{}",
+        format!(
+            "Found {} difference{}:",
+            differences.len(),
+            if differences.len() == 1 { "" } else { "s" }
+        )
+        .red()
+        .bold()
+    )?;
+    for difference in &differences {
+        writeln!(printer.stdout(), "  - {difference}")?;
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_outcomes() {
+        let output = indoc::indoc! {"
+            trailing-whitespace......................................................Passed
+            check yaml...........................................(no files to check)Skipped
+            ruff.....................................................................Failed
+            - hook id: ruff
+            - exit code: 1
+
+            x.py:1:1: F401 `os` imported but unused
+            Files were modified by following hooks...................................Failed
+              ┌ fmt-a................................................................Passed
+              └ fmt-b................................................................Passed
+        "};
+
+        let hooks = parse_hook_outcomes(output);
+        let hooks: Vec<_> = hooks
+            .iter()
+            .map(|hook| (hook.name.as_str(), hook.to_string()))
+            .collect();
+        assert_eq!(
+            hooks,
+            [
+                ("trailing-whitespace", "Passed".to_string()),
+                ("check yaml", "Skipped (no files to check)".to_string()),
+                ("ruff", "Failed (exit code 1)".to_string()),
+                ("fmt-a", "Passed".to_string()),
+                ("fmt-b", "Passed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn compare_summaries() {
+        let hook = |name: &str, status: &str| HookOutcome {
+            name: name.to_string(),
+            id: None,
+            status: status.to_string(),
+            exit_code: None,
+        };
+        let prek = RunSummary {
+            exit_code: Some(1),
+            hooks: vec![
+                hook("a", "Passed"),
+                hook("b", "Failed"),
+                hook("c", "Passed"),
+            ],
+            modified_files: BTreeSet::from(["x.txt".to_string()]),
+            output: String::new(),
+        };
+        let pre_commit = RunSummary {
+            exit_code: Some(1),
+            hooks: vec![
+                hook("a", "Passed"),
+                hook("b", "Passed"),
+                hook("d", "Passed"),
+            ],
+            modified_files: BTreeSet::from(["x.txt".to_string(), "y.txt".to_string()]),
+            output: String::new(),
+        };
+
+        let differences = compare_runs(&prek, &pre_commit);
+        assert_eq!(
+            differences,
+            [
+                Difference::Outcome {
+                    name: "b".to_string(),
+                    prek: "Failed".to_string(),
+                    pre_commit: "Passed".to_string(),
+                },
+                Difference::OnlyPrek("c".to_string()),
+                Difference::OnlyPreCommit("d".to_string()),
+                Difference::ModifiedOnlyByPreCommit("y.txt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_outcome_ids() {
+        let output = indoc::indoc! {"
+            ruff.....................................................................Failed
+            - hook id: ruff
+            - exit code: 1
+        "};
+        let hooks = parse_hook_outcomes(output);
+        assert_eq!(hooks[0].id.as_deref(), Some("ruff"));
+    }
+
+    #[test]
+    fn parse_porcelain_renames() {
+        let stdout = b"R  new.txt\0old.txt\0 M changed.txt\0?? added.txt\0 R moved.txt\0orig.txt\0M  staged.txt\0";
+        assert_eq!(
+            parse_porcelain(stdout),
+            BTreeSet::from([
+                "added.txt".to_string(),
+                "changed.txt".to_string(),
+                "moved.txt".to_string(),
+                "orig.txt".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn compare_hook_modified_files() {
+        let prek = BTreeSet::from(["a.txt".to_string(), "b.txt".to_string()]);
+        let pre_commit = BTreeSet::from(["b.txt".to_string(), "c.txt".to_string()]);
+        assert_eq!(
+            compare_hook_files("fmt", &prek, &pre_commit),
+            [
+                Difference::HookModifiedOnlyByPrek {
+                    hook: "fmt".to_string(),
+                    file: "a.txt".to_string(),
+                },
+                Difference::HookModifiedOnlyByPreCommit {
+                    hook: "fmt".to_string(),
+                    file: "c.txt".to_string(),
+                },
+            ]
+        );
+    }
+}
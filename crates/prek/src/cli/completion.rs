@@ -8,7 +8,7 @@ use clap_complete::CompletionCandidate;
 use crate::config;
 use crate::fs::{CWD, PathClean};
 use crate::store::Store;
-use crate::workspace::{Project, Workspace};
+use crate::workspace::{Project, Refresh, Workspace};
 
 pub(crate) fn selector_completer(current: &OsStr) -> Vec<CompletionCandidate> {
     let Some(current) = current.to_str() else {
@@ -55,7 +55,7 @@ impl SelectorCompleter {
     fn load() -> Option<Self> {
         let store = Store::from_settings().ok()?;
         let root = Workspace::find_root(None, &CWD).ok()?;
-        let workspace = Workspace::discover(&store, root, None, None, false).ok()?;
+        let workspace = Workspace::discover(&store, root, None, None, &Refresh::None).ok()?;
 
         Some(Self { workspace })
     }
@@ -0,0 +1,729 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use regex::Regex;
+use serde_json::Value;
+use toml_edit::{Array, DocumentMut, Item, Key, TableLike};
+use tracing::debug;
+
+use crate::cli::ExitStatus;
+use crate::cli::run::{CollectOptions, FileTagCache, collect_run_input};
+use crate::config::{Language, Stage, is_mutable_rev, load_config};
+use crate::fs::{CWD, Simplified};
+use crate::hooks::{config_dirs, find_duplicate_hooks, hooks_without_files};
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::warn_user;
+use crate::workspace::{HookInitFilters, Project, Workspace};
+
+/// Stage names accepted for compatibility with older `pre-commit` configs.
+const DEPRECATED_STAGES: &[(&str, Stage)] = &[
+    ("commit", Stage::PreCommit),
+    ("merge-commit", Stage::PreMergeCommit),
+    ("push", Stage::PrePush),
+];
+
+static YAML_AUTO_UPDATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^auto_update(\s*:)").expect("regex must be valid"));
+
+static STAGES_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:-\s+)?(?:default_)?stages\s*:").expect("regex must be valid")
+});
+
+static FLOW_STAGES_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"([{,]\s*(?:default_)?stages\s*:\s*)\[([^\]#]*)\]").expect("regex must be valid")
+});
+
+static YAML_SEQUENCE_ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*-\s+['"]?[\w-]+['"]?\s*(?:#.*)?$"#).expect("regex must be valid")
+});
+
+static YAML_BLOCK_SCALAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*(?:-\s+)*)[\w.-]+\s*:\s*[|>][1-9+-]*\s*(?:#.*)?$")
+        .expect("regex must be valid")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+enum Rule {
+    InvalidConfig,
+    UnpinnedRev,
+    Deprecated,
+    UnknownLanguage,
+    DuplicateHook,
+    NoMatchingFiles,
+}
+
+struct Finding {
+    rule: Rule,
+    message: String,
+    fixable: bool,
+}
+
+impl Finding {
+    fn new(rule: Rule, message: String) -> Self {
+        Self {
+            rule,
+            message,
+            fixable: false,
+        }
+    }
+
+    fn fixable(rule: Rule, message: String) -> Self {
+        Self {
+            rule,
+            message,
+            fixable: true,
+        }
+    }
+}
+
+/// A member config file and its findings.
+struct LintedConfig {
+    path: PathBuf,
+    /// The path shown in the report, relative to the workspace root.
+    display: String,
+    findings: Vec<Finding>,
+}
+
+/// Lint every member config of the workspace for style and consistency issues.
+pub(crate) async fn config_lint(
+    store: &Store,
+    config: Option<PathBuf>,
+    fix: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let input = collect_run_input(&workspace_root, CollectOptions::all_files())
+        .await?
+        .into_files();
+
+    let config_files = if let Some(config) = config {
+        vec![(PathBuf::new(), config)]
+    } else {
+        config_dirs(&input)
+            .into_iter()
+            .filter_map(|dir| {
+                Project::find_config(&workspace_root.join(dir))
+                    .map(|config| (dir.to_path_buf(), config))
+            })
+            .collect()
+    };
+
+    let mut fixed = 0;
+    let mut linted = Vec::with_capacity(config_files.len());
+    let mut projects = Vec::new();
+    for (relative_path, path) in config_files {
+        let display = path.strip_prefix(&workspace_root).map_or_else(
+            |_| path.user_display().to_string(),
+            |p| p.display().to_string(),
+        );
+
+        let content = fs_err::read_to_string(&path)?;
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+
+        let mut findings = lint_document(&content, is_toml);
+        if fix && findings.iter().any(|finding| finding.fixable) {
+            match fix_content(&content, is_toml) {
+                Ok(new_content) if new_content != content => {
+                    fs_err::write(&path, &new_content)?;
+                    let remaining = lint_document(&new_content, is_toml);
+                    fixed += findings.len().saturating_sub(remaining.len());
+                    findings = remaining;
+                }
+                Ok(_) => {}
+                Err(err) => warn_user!("Skipping fixes for `{display}`: {err}"),
+            }
+        }
+
+        match load_config(&path) {
+            Ok(config) => {
+                let mut project = Project::from_config(path.clone(), config, None);
+                project.with_relative_path(relative_path);
+                projects.push(project);
+            }
+            // A finding from the raw document already explains why the config does not load.
+            Err(_)
+                if findings.iter().any(|finding| {
+                    matches!(finding.rule, Rule::InvalidConfig | Rule::UnknownLanguage)
+                }) => {}
+            Err(err) => {
+                findings.push(Finding::new(Rule::InvalidConfig, err.to_string()));
+            }
+        }
+
+        linted.push(LintedConfig {
+            path,
+            display,
+            findings,
+        });
+    }
+
+    for duplicate in find_duplicate_hooks(&projects) {
+        if let Some(config) = linted
+            .iter_mut()
+            .find(|config| config.path == duplicate.project.config_file())
+        {
+            config
+                .findings
+                .push(Finding::new(Rule::DuplicateHook, duplicate.to_string()));
+        }
+    }
+
+    // Only repos already in the store are checked for matching files, so linting never clones.
+    let tag_cache = FileTagCache::from_paths(&input);
+    for project in projects {
+        let config_file = project.config_file().to_path_buf();
+        let (project_hooks, missing_repos) = project
+            .init_cached_hooks(store, HookInitFilters::none())
+            .await
+            .context("Failed to init hooks")?;
+        for repo in missing_repos {
+            debug!(
+                "Skipping hooks of `{repo}` in `{}`, the repo is not cloned yet",
+                config_file.user_display()
+            );
+        }
+        let Some(config) = linted.iter_mut().find(|config| config.path == config_file) else {
+            continue;
+        };
+        for hook in hooks_without_files(&project_hooks, &input, &tag_cache) {
+            config.findings.push(Finding::new(
+                Rule::NoMatchingFiles,
+                format!("hook `{}` does not match any file", hook.id),
+            ));
+        }
+    }
+
+    let mut remaining = 0;
+    let mut fixable = 0;
+    for config in &linted {
+        for finding in &config.findings {
+            remaining += 1;
+            if finding.fixable {
+                fixable += 1;
+            }
+            writeln!(
+                printer.stdout(),
+                "{}: {} {}",
+                config.display.cyan(),
+                format!("[{}]", finding.rule).yellow(),
+                finding.message
+            )?;
+        }
+    }
+
+    if fixed > 0 {
+        writeln!(
+            printer.stderr(),
+            "Fixed {fixed} {}",
+            if fixed == 1 { "issue" } else { "issues" }
+        )?;
+    }
+
+    if remaining == 0 {
+        writeln!(
+            printer.stderr(),
+            "{}: No issues found",
+            "success".green().bold()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    write!(
+        printer.stderr(),
+        "Found {remaining} {}",
+        if remaining == 1 { "issue" } else { "issues" }
+    )?;
+    if fixable > 0 {
+        write!(
+            printer.stderr(),
+            ", {fixable} fixable with `prek config lint --fix`"
+        )?;
+    }
+    writeln!(printer.stderr())?;
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Lint a config file by its raw document, so configs that fail to load are still checked.
+fn lint_document(content: &str, is_toml: bool) -> Vec<Finding> {
+    let document: Result<Value, String> = if is_toml {
+        toml::from_str(content).map_err(|err| err.to_string())
+    } else {
+        serde_saphyr::from_str(content).map_err(|err| err.to_string())
+    };
+    let document = match document {
+        Ok(document) => document,
+        Err(err) => return vec![Finding::new(Rule::InvalidConfig, err)],
+    };
+
+    let mut findings = Vec::new();
+
+    if document.get("auto_update").is_some() {
+        findings.push(Finding::fixable(
+            Rule::Deprecated,
+            "`auto_update` is deprecated, use `update` instead".to_string(),
+        ));
+    }
+    lint_stages(
+        document.get("default_stages"),
+        "`default_stages`",
+        &mut findings,
+    );
+    if let Some(languages) = document
+        .get("default_language_version")
+        .and_then(Value::as_object)
+    {
+        for language in languages.keys() {
+            lint_language(language, "`default_language_version`", &mut findings);
+        }
+    }
+
+    let repos = document
+        .get("repos")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for repo in repos {
+        let url = repo.get("repo").and_then(Value::as_str).unwrap_or_default();
+        if !matches!(url, "local" | "meta" | "builtin")
+            && let Some(rev) = repo.get("rev").and_then(Value::as_str)
+            && is_mutable_rev(rev)
+        {
+            findings.push(Finding::new(
+                Rule::UnpinnedRev,
+                format!("`{url}` uses the mutable rev `{rev}`, pin it to a tag or a commit SHA"),
+            ));
+        }
+
+        let hooks = repo
+            .get("hooks")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for hook in hooks {
+            let id = hook.get("id").and_then(Value::as_str).unwrap_or_default();
+            let location = format!("hook `{id}`");
+            lint_stages(hook.get("stages"), &location, &mut findings);
+            if let Some(language) = hook.get("language").and_then(Value::as_str) {
+                lint_language(language, &location, &mut findings);
+            }
+        }
+    }
+
+    findings
+}
+
+fn lint_stages(stages: Option<&Value>, location: &str, findings: &mut Vec<Finding>) {
+    let Some(stages) = stages.and_then(Value::as_array) else {
+        return;
+    };
+    for stage in stages.iter().filter_map(Value::as_str) {
+        if let Some((_, replacement)) = DEPRECATED_STAGES.iter().find(|(name, _)| *name == stage) {
+            findings.push(Finding::fixable(
+                Rule::Deprecated,
+                format!("{location}: stage `{stage}` is deprecated, use `{replacement}` instead"),
+            ));
+        }
+    }
+}
+
+fn lint_language(language: &str, location: &str, findings: &mut Vec<Finding>) {
+    if serde_json::from_value::<Language>(Value::String(language.to_string())).is_err() {
+        findings.push(Finding::new(
+            Rule::UnknownLanguage,
+            format!("{location}: unknown language `{language}`"),
+        ));
+    }
+}
+
+/// Apply the safe rewrites, preserving comments and formatting.
+fn fix_content(content: &str, is_toml: bool) -> Result<String> {
+    if is_toml {
+        fix_toml(content)
+    } else {
+        fix_yaml(content)
+    }
+}
+
+/// Rewrite a TOML config through `toml_edit`, so only the deprecated keys and stages change.
+fn fix_toml(content: &str) -> Result<String> {
+    let mut document = content.parse::<DocumentMut>()?;
+
+    if document.contains_key("auto_update") && !document.contains_key("update") {
+        let order = document
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>();
+        if let Some((key, item)) = document.remove_entry("auto_update") {
+            let mut update = Key::new("update");
+            *update.leaf_decor_mut() = key.leaf_decor().clone();
+            document.insert_formatted(&update, item);
+        }
+        // Keep `update` where `auto_update` was.
+        let position = |key: &Key| {
+            let name = if key.get() == "update" {
+                "auto_update"
+            } else {
+                key.get()
+            };
+            order.iter().position(|entry| entry == name)
+        };
+        document.sort_values_by(|a, _, b, _| position(a).cmp(&position(b)));
+    }
+
+    if let Some(stages) = document
+        .get_mut("default_stages")
+        .and_then(Item::as_array_mut)
+    {
+        rename_toml_stages(stages);
+    }
+    for_each_toml_table(document.get_mut("repos"), |repo| {
+        for_each_toml_table(repo.get_mut("hooks"), |hook| {
+            if let Some(stages) = hook.get_mut("stages").and_then(Item::as_array_mut) {
+                rename_toml_stages(stages);
+            }
+        });
+    });
+
+    Ok(document.to_string())
+}
+
+/// Call `f` with every table of an array of tables, or of an array of inline tables.
+fn for_each_toml_table(item: Option<&mut Item>, mut f: impl FnMut(&mut dyn TableLike)) {
+    match item {
+        Some(Item::ArrayOfTables(tables)) => {
+            for table in tables.iter_mut() {
+                f(table);
+            }
+        }
+        Some(Item::Value(toml_edit::Value::Array(array))) => {
+            for table in array
+                .iter_mut()
+                .filter_map(toml_edit::Value::as_inline_table_mut)
+            {
+                f(table);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rename_toml_stages(stages: &mut Array) {
+    for stage in stages.iter_mut() {
+        let Some(replacement) = stage.as_str().and_then(deprecated_stage) else {
+            continue;
+        };
+        let decor = stage.decor().clone();
+        *stage = toml_edit::Value::from(replacement);
+        *stage.decor_mut() = decor;
+    }
+}
+
+/// Apply the safe rewrites to a YAML config line by line, so comments and formatting are
+/// preserved.
+///
+/// Only mapping keys are rewritten, never the content of block scalars or quoted strings, and
+/// the result must parse to the same document as the fixed original.
+fn fix_yaml(content: &str) -> Result<String> {
+    let mut output = String::with_capacity(content.len());
+    // Whether the following lines continue a stages list opened on an earlier line.
+    let mut in_block_sequence = false;
+    let mut in_flow_sequence = false;
+    // The indentation of the key of a block scalar whose content follows.
+    let mut block_scalar_indent = None;
+
+    for line in content.split_inclusive('\n') {
+        if let Some(indent) = block_scalar_indent {
+            if line.trim().is_empty() || indentation(line) > indent {
+                output.push_str(line);
+                continue;
+            }
+            block_scalar_indent = None;
+        }
+        if in_flow_sequence {
+            in_flow_sequence = !line.contains(']');
+            output.push_str(&rename_stages(line));
+            continue;
+        }
+        if in_block_sequence {
+            let trimmed = line.trim();
+            if YAML_SEQUENCE_ITEM_RE.is_match(line.trim_end()) {
+                output.push_str(&rename_stages(line));
+                continue;
+            }
+            in_block_sequence = trimmed.is_empty() || trimmed.starts_with('#');
+        }
+
+        if let Some(key) = STAGES_KEY_RE.find(line) {
+            let (key, value) = line.split_at(key.end());
+            let value_without_comment = value.split('#').next().unwrap_or_default();
+            if value_without_comment.trim().is_empty() {
+                in_block_sequence = true;
+            } else if value_without_comment.contains('[') && !value_without_comment.contains(']') {
+                in_flow_sequence = true;
+            }
+            output.push_str(key);
+            output.push_str(&rename_stages(value));
+            continue;
+        }
+
+        if let Some(caps) = YAML_BLOCK_SCALAR_RE.captures(line.trim_end()) {
+            block_scalar_indent = Some(caps[1].len());
+            output.push_str(line);
+            continue;
+        }
+
+        // Stages lists inside flow mappings.
+        let line = FLOW_STAGES_RE.replace_all(line, |caps: &regex::Captures| {
+            let matched = caps.get(0).expect("capture group 0 must exist");
+            if is_yaml_code(&line[..matched.start()]) {
+                format!("{}[{}]", &caps[1], rename_stages(&caps[2]))
+            } else {
+                matched.as_str().to_string()
+            }
+        });
+        let line = YAML_AUTO_UPDATE_RE.replace(&line, "update${1}");
+        output.push_str(&line);
+    }
+
+    let mut expected = serde_saphyr::from_str::<Value>(content)?;
+    fix_document(&mut expected);
+    let fixed = serde_saphyr::from_str::<Value>(&output)?;
+    anyhow::ensure!(
+        fixed == expected,
+        "the rewritten config would change more than the deprecated names"
+    );
+
+    Ok(output)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Whether the end of `prefix` is outside of quoted strings and comments.
+fn is_yaml_code(prefix: &str) -> bool {
+    let mut quote = None;
+    let mut previous = ' ';
+    for ch in prefix.chars() {
+        match quote {
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '#' && previous.is_whitespace() => return false,
+            Some(open) if ch == open => quote = None,
+            _ => {}
+        }
+        previous = ch;
+    }
+    quote.is_none()
+}
+
+/// Apply the safe rewrites to a parsed document.
+fn fix_document(document: &mut Value) {
+    if let Some(object) = document.as_object_mut()
+        && !object.contains_key("update")
+        && let Some(auto_update) = object.remove("auto_update")
+    {
+        object.insert("update".to_string(), auto_update);
+    }
+
+    rename_document_stages(document.get_mut("default_stages"));
+    let hooks = document
+        .get_mut("repos")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(|repo| repo.get_mut("hooks").and_then(Value::as_array_mut))
+        .flatten();
+    for hook in hooks {
+        rename_document_stages(hook.get_mut("stages"));
+    }
+}
+
+fn rename_document_stages(stages: Option<&mut Value>) {
+    for stage in stages.and_then(Value::as_array_mut).into_iter().flatten() {
+        if let Some(replacement) = stage.as_str().and_then(deprecated_stage) {
+            *stage = Value::String(replacement.to_string());
+        }
+    }
+}
+
+/// Rename the deprecated stage names in a (part of a) line, leaving any comment untouched.
+fn rename_stages(text: &str) -> String {
+    let (value, comment) = text.split_at(text.find('#').unwrap_or(text.len()));
+
+    let mut output = String::with_capacity(text.len());
+    let mut token_start = None;
+    for (idx, ch) in value.char_indices() {
+        let is_token_char = ch.is_alphanumeric() || ch == '-' || ch == '_';
+        match (token_start, is_token_char) {
+            (None, true) => token_start = Some(idx),
+            (Some(start), false) => {
+                output.push_str(renamed_stage(&value[start..idx]));
+                output.push(ch);
+                token_start = None;
+            }
+            (None, false) => output.push(ch),
+            (Some(_), true) => {}
+        }
+    }
+    if let Some(start) = token_start {
+        output.push_str(renamed_stage(&value[start..]));
+    }
+
+    output.push_str(comment);
+    output
+}
+
+fn renamed_stage(token: &str) -> &str {
+    deprecated_stage(token).unwrap_or(token)
+}
+
+/// The replacement of a deprecated stage name.
+fn deprecated_stage(name: &str) -> Option<&'static str> {
+    DEPRECATED_STAGES
+        .iter()
+        .find(|(deprecated, _)| *deprecated == name)
+        .map(|(_, stage)| stage.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fix_content;
+
+    #[test]
+    fn fix_yaml_config() {
+        let config = indoc::indoc! {r"
+            auto_update:
+              cooldown_days: 7
+            default_stages: [commit, push]  # legacy names
+            repos:
+              - repo: local
+                hooks:
+                  - id: pre-commit-check
+                    stages:
+                      - commit
+                      - 'merge-commit'
+                    language: system
+                    entry: echo commit
+        "};
+
+        insta::assert_snapshot!(fix_content(config, false).unwrap(), @r"
+        update:
+          cooldown_days: 7
+        default_stages: [pre-commit, pre-push]  # legacy names
+        repos:
+          - repo: local
+            hooks:
+              - id: pre-commit-check
+                stages:
+                  - pre-commit
+                  - 'pre-merge-commit'
+                language: system
+                entry: echo commit
+        ");
+    }
+
+    #[test]
+    fn fix_yaml_config_leaves_values_untouched() {
+        let config = indoc::indoc! {r#"
+            repos:
+              - repo: local
+                hooks:
+                  - { id: inline, stages: [push], language: system, entry: "echo {a, stages: [commit]}" }
+                  - id: script
+                    name: script
+                    language: system
+                    entry: |
+                      stages:
+                        - commit
+                    stages: [commit]
+        "#};
+
+        insta::assert_snapshot!(fix_content(config, false).unwrap(), @r#"
+        repos:
+          - repo: local
+            hooks:
+              - { id: inline, stages: [pre-push], language: system, entry: "echo {a, stages: [commit]}" }
+              - id: script
+                name: script
+                language: system
+                entry: |
+                  stages:
+                    - commit
+                stages: [pre-commit]
+        "#);
+    }
+
+    #[test]
+    fn fix_toml_config() {
+        let config = indoc::indoc! {r#"
+            default_stages = [
+              "commit",
+              "pre-push",
+            ]
+
+            [[repos]]
+            repo = "local"
+            hooks = [{ id = "check", stages = ["push"], language = "system", entry = "echo commit" }]
+
+            [auto_update]
+            cooldown_days = 7
+        "#};
+
+        insta::assert_snapshot!(fix_content(config, true).unwrap(), @r#"
+        default_stages = [
+          "pre-commit",
+          "pre-push",
+        ]
+
+        [[repos]]
+        repo = "local"
+        hooks = [{ id = "check", stages = ["pre-push"], language = "system", entry = "echo commit" }]
+
+        [update]
+        cooldown_days = 7
+        "#);
+    }
+
+    #[test]
+    fn fix_toml_config_leaves_values_untouched() {
+        let config = indoc::indoc! {r#"
+            auto_update = { cooldown_days = 7 }
+            fail_fast = true
+
+            [[repos]]
+            repo = "local"
+
+            [[repos.hooks]]
+            id = "check"
+            entry = """
+            stages = ["commit"]
+            """
+            language = "system"
+            stages = ["commit"] # legacy name
+        "#};
+
+        insta::assert_snapshot!(fix_content(config, true).unwrap(), @r#"
+        update = { cooldown_days = 7 }
+        fail_fast = true
+
+        [[repos]]
+        repo = "local"
+
+        [[repos.hooks]]
+        id = "check"
+        entry = """
+        stages = ["commit"]
+        """
+        language = "system"
+        stages = ["pre-commit"] # legacy name
+        "#);
+    }
+}
@@ -0,0 +1,15 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::cli::ExitStatus;
+use crate::printer::Printer;
+
+/// The JSON Schema of the configuration file, kept in sync with the configuration types by the
+/// schema generation test.
+static CONFIG_SCHEMA: &str = include_str!("../../../../prek.schema.json");
+
+pub(crate) fn config_schema(printer: Printer) -> Result<ExitStatus> {
+    write!(printer.stdout_important(), "{CONFIG_SCHEMA}")?;
+    Ok(ExitStatus::Success)
+}
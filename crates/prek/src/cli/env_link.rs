@@ -0,0 +1,119 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use owo_colors::OwoColorize;
+
+use crate::cli::ExitStatus;
+use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
+use crate::cli::run::{EnvLink, EnvLinks, InstallCache, Selectors, install_hooks, link_dir};
+use crate::fs::{CWD, PathClean, Simplified};
+use crate::git::{self, GIT_ROOT};
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::workspace::{HookInitFilters, Refresh, Workspace};
+
+/// Link the environment of a hook into the project.
+pub(crate) async fn env_link(
+    store: &Store,
+    config: Option<PathBuf>,
+    hook: String,
+    path: Option<PathBuf>,
+    refresh: Refresh,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let selectors = Selectors::load(std::slice::from_ref(&hook), &[], &workspace_root)?;
+    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), &refresh)?;
+
+    let reporter = HookInitReporter::new(printer);
+    let _lock = store.lock_async().await?;
+    let hooks = workspace
+        .init_hooks(
+            store,
+            HookInitFilters::new(Some(&selectors), None),
+            Some(&reporter),
+        )
+        .await
+        .context("Failed to init hooks")?;
+    let mut hooks: Vec<_> = hooks
+        .into_iter()
+        .filter(|h| selectors.matches_hook(h))
+        .collect();
+
+    let hook = match hooks.len() {
+        0 => anyhow::bail!("No hook found for `{}`", hook.cyan()),
+        1 => hooks.remove(0),
+        _ => anyhow::bail!(
+            "`{}` matches {} hooks, use `{}` to select one:\n{}",
+            hook.cyan(),
+            hooks.len(),
+            "<project>:<hook-id>".cyan(),
+            hooks
+                .iter()
+                .map(|h| format!("  - {}", h.full_id()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    };
+    if !hook.needs_install_env() {
+        anyhow::bail!(
+            "Hook `{}` does not use an environment, there is nothing to link",
+            hook.id.cyan()
+        );
+    }
+
+    let link = match path {
+        Some(path) => CWD.join(path).clean(),
+        None => hook
+            .project()
+            .path()
+            .join(".prek")
+            .join("envs")
+            .join(&hook.id),
+    };
+    let git_root = GIT_ROOT.as_ref()?;
+    let Ok(relative) = link.strip_prefix(git_root) else {
+        anyhow::bail!(
+            "Link path `{}` must be inside the repository",
+            link.user_display()
+        );
+    };
+    let relative = relative.to_path_buf();
+
+    let mut links = EnvLinks::load(git_root)?;
+    links.insert(EnvLink::new(relative.clone(), &hook));
+
+    let reporter = HookInstallReporter::new(printer);
+    let mut install_cache = InstallCache::new();
+    let installed = install_hooks(vec![Arc::new(hook)], store, &reporter, &mut install_cache)
+        .await?
+        .remove(0);
+    reporter.on_complete();
+
+    let Some(env_path) = installed.env_path() else {
+        unreachable!("hooks that need an environment are installed into one");
+    };
+    link_dir(env_path, &link)?;
+    links.save(git_root)?;
+
+    writeln!(
+        printer.stdout(),
+        "Linked `{}` to the environment of hook `{}`",
+        link.user_display().cyan(),
+        installed.id.cyan(),
+    )?;
+
+    if !git::is_ignored(git_root, &relative).await? {
+        writeln!(
+            printer.stderr(),
+            "{} `{}` is not ignored by git, consider adding it to `{}`",
+            "hint:".yellow().bold(),
+            relative.display().cyan(),
+            ".gitignore".cyan(),
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use encoding_rs::Encoding;
+use itertools::Itertools;
+use prek_identify::TagSet;
+use serde::Serialize;
+
+use crate::cli::ExitStatus;
+use crate::cli::reporter::HookInitReporter;
+use crate::config::{CiOptions, FilePattern, Isolation, Language, PassFilenames, Shell, Stage};
+use crate::fs::CWD;
+use crate::git;
+use crate::hook::{Hook, Repo};
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::workspace::{HookInitFilters, Refresh, Workspace};
+
+/// A project configuration that loads back as a `.pre-commit-config.yaml`.
+#[derive(Serialize)]
+struct ExportedProject<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_prek_version: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orphan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<&'a FilePattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude: Option<&'a FilePattern>,
+    fail_fast: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ci: Option<&'a CiOptions>,
+    repos: Vec<ExportedRepo<'a>>,
+}
+
+#[derive(Serialize)]
+struct ExportedRepo<'a> {
+    repo: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<&'a FilePattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude: Option<&'a FilePattern>,
+    hooks: Vec<ExportedHook<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ExportedPassFilenames {
+    Bool(bool),
+    Limit(usize),
+}
+
+impl From<PassFilenames> for ExportedPassFilenames {
+    fn from(value: PassFilenames) -> Self {
+        match value {
+            PassFilenames::All => Self::Bool(true),
+            PassFilenames::None => Self::Bool(false),
+            PassFilenames::Limited(n) => Self::Limit(n.get()),
+        }
+    }
+}
+
+/// Every option of a resolved hook. Repo-level `files` and `exclude` go on the [`ExportedRepo`].
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Serialize)]
+struct ExportedHook<'a> {
+    id: &'a str,
+    name: &'a str,
+    /// Meta and builtin hooks do not accept an `entry`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shell: Option<Shell>,
+    language: Language,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    language_version: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    alias: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<&'a FilePattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude: Option<&'a FilePattern>,
+    types: TagSet,
+    types_or: TagSet,
+    exclude_types: TagSet,
+    additional_dependencies: &'a [String],
+    setup: &'a [String],
+    args: &'a [String],
+    env: BTreeMap<&'a str, &'a str>,
+    stages: Vec<Stage>,
+    groups: &'a BTreeSet<String>,
+    always_run: bool,
+    pass_filenames: ExportedPassFilenames,
+    files_from_repo_root: bool,
+    require_serial: bool,
+    require_serial_project: bool,
+    fail_fast: bool,
+    advisory: bool,
+    verbose: bool,
+    stream_output: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern_hint: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_encoding: Option<&'static str>,
+    success_exit_codes: &'a [i32],
+    warning_exit_codes: &'a [i32],
+    use_project_node_modules: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    isolation: Option<Isolation>,
+    priority: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_prek_version: Option<&'a str>,
+}
+
+impl<'a> From<&'a Hook> for ExportedHook<'a> {
+    fn from(hook: &'a Hook) -> Self {
+        Self {
+            id: &hook.id,
+            name: &hook.name,
+            entry: matches!(hook.repo(), Repo::Local { .. } | Repo::Remote { .. })
+                .then(|| hook.entry.raw()),
+            shell: hook.entry.shell(),
+            language: hook.language,
+            language_version: &hook.language_version,
+            alias: &hook.alias,
+            description: hook.description.as_deref(),
+            files: hook.files.as_ref(),
+            exclude: hook.exclude.as_ref(),
+            types: hook.types,
+            types_or: hook.types_or,
+            exclude_types: hook.exclude_types,
+            additional_dependencies: &hook.additional_dependencies,
+            setup: &hook.setup,
+            args: &hook.args,
+            env: hook
+                .env
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect(),
+            stages: hook.stages.iter().collect(),
+            groups: &hook.groups,
+            always_run: hook.always_run,
+            pass_filenames: hook.pass_filenames.into(),
+            files_from_repo_root: hook.files_from_repo_root,
+            require_serial: hook.require_serial,
+            require_serial_project: hook.require_serial_project,
+            fail_fast: hook.fail_fast,
+            advisory: hook.advisory,
+            verbose: hook.verbose,
+            stream_output: hook.stream_output,
+            log_file: hook.log_file.as_deref(),
+            pattern_hint: hook.pattern_hint.as_deref(),
+            output_encoding: hook.output_encoding.map(Encoding::name),
+            success_exit_codes: &hook.success_exit_codes,
+            warning_exit_codes: &hook.warning_exit_codes,
+            use_project_node_modules: hook.use_project_node_modules,
+            isolation: hook.isolation,
+            priority: hook.priority,
+            minimum_prek_version: hook.minimum_prek_version.as_deref(),
+        }
+    }
+}
+
+/// Print the configuration of every project, with defaults applied, as a loadable configuration.
+///
+/// Each project is a separate YAML document, headed by a comment with its path relative to the
+/// workspace root.
+pub(crate) async fn export_config(
+    store: &Store,
+    config: Option<PathBuf>,
+    resolved: bool,
+    refresh: Refresh,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let workspace = Workspace::discover(store, workspace_root, config, None, &refresh)?;
+
+    let reporter = HookInitReporter::new(printer);
+    let lock = store.lock_async().await?;
+    let hooks = workspace
+        .init_hooks(store, HookInitFilters::new(None, None), Some(&reporter))
+        .await
+        .context("Failed to init hooks")?;
+    drop(lock);
+
+    let mut projects = BTreeMap::new();
+    for project in workspace.projects() {
+        let config = project.config();
+        let mut repos = Vec::new();
+
+        // Hooks are ordered by project and then by repo, so consecutive hooks sharing a repo
+        // came from the same `repos` entry.
+        let repo_groups: Vec<Vec<&Hook>> = hooks
+            .iter()
+            .filter(|hook| hook.project().idx() == project.idx())
+            .chunk_by(|hook| std::ptr::from_ref(hook.repo()))
+            .into_iter()
+            .map(|(_, group)| group.collect())
+            .collect();
+        for repo_hooks in repo_groups {
+            let first = repo_hooks[0];
+
+            let (name, rev) = match first.repo() {
+                Repo::Remote { path, url, rev, .. } => {
+                    let rev = if resolved {
+                        git::get_head_commit(path).await.with_context(|| {
+                            format!("Failed to resolve the commit of `{url}@{rev}`")
+                        })?
+                    } else {
+                        rev.clone()
+                    };
+                    (url.as_str(), Some(rev))
+                }
+                Repo::Local { .. } => ("local", None),
+                Repo::Meta { .. } => ("meta", None),
+                Repo::Builtin { .. } => ("builtin", None),
+            };
+
+            repos.push(ExportedRepo {
+                repo: name,
+                rev,
+                files: first.repo_files.as_ref(),
+                exclude: first.repo_exclude.as_ref(),
+                hooks: repo_hooks.into_iter().map(ExportedHook::from).collect(),
+            });
+        }
+
+        let key = if project.is_root() {
+            ".".to_string()
+        } else {
+            project.relative_path().display().to_string()
+        };
+        projects.insert(
+            key,
+            ExportedProject {
+                minimum_prek_version: config.minimum_prek_version.as_deref(),
+                orphan: config.orphan,
+                files: config.files.as_ref(),
+                exclude: config.exclude.as_ref(),
+                fail_fast: config.fail_fast.unwrap_or(false),
+                ci: config.ci.as_ref(),
+                repos,
+            },
+        );
+    }
+
+    let mut stdout = printer.stdout_important();
+    for (i, (path, project)) in projects.iter().enumerate() {
+        if i > 0 {
+            writeln!(stdout, "---")?;
+        }
+        writeln!(stdout, "# Project: {path}")?;
+        write!(stdout, "{}", serde_saphyr::to_string(project)?)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,586 @@
+//! Long-form help: extended topic pages and man pages, shown by `prek help`.
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Write};
+use std::process::Stdio;
+
+use anyhow::Result;
+use clap::{CommandFactory, ValueEnum};
+use owo_colors::OwoColorize;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+
+use crate::cli::{Cli, ExitStatus};
+use crate::config::Language;
+use crate::printer::Printer;
+use crate::version;
+
+/// Text wrapping width for topic pages in the terminal.
+const WIDTH: usize = 80;
+
+/// A block of a help page, rendered for the terminal or as roff.
+enum Block {
+    Heading(&'static str),
+    Paragraph(Cow<'static, str>),
+    /// A term, such as an option or environment variable, followed by its description.
+    Item(Cow<'static, str>, Cow<'static, str>),
+    /// Preformatted lines, such as examples and tables.
+    Code(Cow<'static, str>),
+}
+
+struct Topic {
+    name: &'static str,
+    summary: &'static str,
+    blocks: fn() -> Vec<Block>,
+}
+
+static TOPICS: &[Topic] = &[
+    Topic {
+        name: "selectors",
+        summary: "Select or skip hooks and projects",
+        blocks: selectors,
+    },
+    Topic {
+        name: "workspace",
+        summary: "Run the hooks of nested projects in one repository",
+        blocks: workspace,
+    },
+    Topic {
+        name: "environment",
+        summary: "Environment variables read by prek",
+        blocks: environment,
+    },
+    Topic {
+        name: "languages",
+        summary: "Supported hook languages and what they support",
+        blocks: languages,
+    },
+];
+
+fn selectors() -> Vec<Block> {
+    vec![
+        Block::Paragraph(
+            "Commands such as `prek run`, `prek install` and `prek list` accept selectors to \
+             pick hooks and projects, and `--skip` accepts the same syntax to leave them out."
+                .into(),
+        ),
+        Block::Heading("Syntax"),
+        Block::Item(
+            "<hook-id>".into(),
+            "All hooks with the given id, in every project.".into(),
+        ),
+        Block::Item(
+            "<project-path>/".into(),
+            "All hooks of the project and its subprojects. The trailing slash is required: a \
+             selector without a slash is a hook id."
+                .into(),
+        ),
+        Block::Item(
+            "<project-path>:<hook-id>".into(),
+            "Only the given hook of the given project.".into(),
+        ),
+        Block::Item(
+            ":<hook-id>".into(),
+            "A hook whose id contains `:`, such as `:lint:ruff` for the id `lint:ruff`.".into(),
+        ),
+        Block::Paragraph(
+            "Project paths are relative to the current directory. Selectors can be repeated, \
+             and `PREK_SKIP` or `SKIP` hold comma-separated selectors to skip."
+                .into(),
+        ),
+        Block::Heading("Examples"),
+        Block::Code(
+            "prek run black                    # every `black` hook\n\
+             prek run frontend/                # every hook of `frontend`\n\
+             prek run frontend:lint            # `lint` of `frontend` only\n\
+             prek run --skip src/backend/      # everything but `src/backend`"
+                .into(),
+        ),
+    ]
+}
+
+fn workspace() -> Vec<Block> {
+    vec![
+        Block::Paragraph(
+            "A workspace is a directory tree with a config file at its root and any number of \
+             nested config files. Each directory with a config file is a project."
+                .into(),
+        ),
+        Block::Heading("Discovery"),
+        Block::Paragraph(
+            "Without `--config`, prek walks up from the current directory to the first config \
+             file, which becomes the workspace root, then searches below it for the other \
+             projects, up to the Git repository root. Directories starting with a dot, and \
+             paths ignored by `.gitignore` or `.prekignore`, are not searched. Run with \
+             `--refresh` after changing them."
+                .into(),
+        ),
+        Block::Heading("Execution"),
+        Block::Paragraph(
+            "Each project only sees the files under its own directory, and its hooks run from \
+             that directory. Projects run from the deepest to the shallowest, and the \
+             workspace root runs last. Projects at the same depth may run concurrently."
+                .into(),
+        ),
+        Block::Paragraph(
+            "Use selectors to run the hooks of some projects only, see `prek help selectors`. \
+             Pass `--config` to run a single config file without discovering a workspace."
+                .into(),
+        ),
+    ]
+}
+
+fn environment() -> Vec<Block> {
+    [
+        (
+            EnvVars::PREK_HOME,
+            "Directory for caches, toolchains and hook environments.",
+        ),
+        (
+            EnvVars::PREK_COLOR,
+            "Colored output: `auto` (default), `always` or `never`.",
+        ),
+        (
+            EnvVars::PREK_QUIET,
+            "`1` for quiet output, like `-q`, `2` for silent output.",
+        ),
+        (
+            EnvVars::PREK_SKIP,
+            "Comma-separated selectors of hooks or projects to skip.",
+        ),
+        (
+            EnvVars::PREK_ALLOW_NO_CONFIG,
+            "Exit successfully when no config file is found.",
+        ),
+        (
+            EnvVars::PREK_NO_CONCURRENCY,
+            "Run one hook and one batch at a time.",
+        ),
+        (
+            EnvVars::PREK_CONCURRENT_HOOKS,
+            "Maximum number of hooks running at once.",
+        ),
+        (
+            EnvVars::PREK_CONCURRENT_BATCHES,
+            "Maximum number of batches of one hook running at once.",
+        ),
+        (
+            EnvVars::PREK_HOOK_SCHEDULE,
+            "Which ready hooks start first: `config-order` or `slowest-first`.",
+        ),
+        (
+            EnvVars::PREK_NO_FAST_PATH,
+            "Use the original implementation of built-in hooks.",
+        ),
+        (
+            EnvVars::PREK_LOG,
+            "Filter for the log file, with the `RUST_LOG` syntax.",
+        ),
+        (
+            EnvVars::PREK_NO_PTY,
+            "Run hooks with pipes instead of a pseudo-terminal.",
+        ),
+        (
+            EnvVars::PREK_NO_JJ,
+            "Collect files with Git even in a colocated Jujutsu repo.",
+        ),
+        (
+            EnvVars::PREK_UV_SOURCE,
+            "Where to install uv from, e.g. `github`, `pypi` or a mirror URL.",
+        ),
+        (
+            EnvVars::PREK_NATIVE_TLS,
+            "Use the system certificate store.",
+        ),
+        (
+            EnvVars::PREK_DOWNLOAD_CHECKSUM_POLICY,
+            "Checksum verification of toolchain downloads.",
+        ),
+        (
+            EnvVars::PREK_CONNECT_TIMEOUT,
+            "Seconds to wait for a connection, `10` by default.",
+        ),
+        (
+            EnvVars::PREK_DOWNLOAD_TIMEOUT,
+            "Seconds to wait for more data from a download, `30` by default.",
+        ),
+        (
+            EnvVars::PREK_DOWNLOAD_RATE_LIMIT,
+            "Combined download speed limit, such as `500K` or `2M`.",
+        ),
+        (
+            EnvVars::PREK_REMOTE_CACHE_TOKEN,
+            "Bearer token sent to the remote cache of hook environments.",
+        ),
+        (
+            EnvVars::PREK_REQUIRE_TRUST,
+            "Ask before cloning a remote hook repository for the first time.",
+        ),
+        (
+            EnvVars::PREK_PREFER_SYSTEM_TOOLCHAINS,
+            "Use toolchains installed by mise or asdf before downloading.",
+        ),
+        (
+            EnvVars::PREK_CONTAINER_RUNTIME,
+            "Container runtime: `auto`, `docker`, `podman` or `container`.",
+        ),
+        (
+            EnvVars::PREK_DOCKER_NO_INIT,
+            "Do not pass `--init` to the container runtime.",
+        ),
+        (
+            EnvVars::PREK_DOCKER_USER,
+            "User that container hooks run as: `auto`, `none` or a `--user` value.",
+        ),
+        (
+            EnvVars::PREK_RUBY_MIRROR,
+            "Base URL for Ruby toolchain downloads.",
+        ),
+        (
+            EnvVars::PREK_RUBYINSTALLER_MIRROR,
+            "Base URL for RubyInstaller2 downloads on Windows.",
+        ),
+        (
+            EnvVars::PREK_RUST_PROFILE,
+            "`rustup` profile for managed Rust toolchains.",
+        ),
+        (EnvVars::SKIP, "Fallback for `PREK_SKIP`."),
+    ]
+    .into_iter()
+    .map(|(name, description)| Block::Item(name.into(), description.into()))
+    .chain([Block::Paragraph(
+        "`PRE_COMMIT_*` variables are read as fallbacks for their `PREK_*` counterparts. See \
+         https://prek.j178.dev/reference/environment-variables/ for details."
+            .into(),
+    )])
+    .collect()
+}
+
+fn languages() -> Vec<Block> {
+    let mark = |supported: bool| if supported { "yes" } else { "-" };
+    let mut table = format!(
+        "{:<14}{:<14}{:<18}{}",
+        "language", "environment", "language_version", "additional_dependencies"
+    );
+    for language in Language::value_variants() {
+        let _ = write!(
+            table,
+            "\n{:<14}{:<14}{:<18}{}",
+            language.to_string(),
+            mark(language.supports_install_env()),
+            mark(language.supports_language_version()),
+            mark(language.supports_dependency()),
+        );
+    }
+
+    vec![
+        Block::Paragraph(
+            "The `language` of a hook tells prek how to install and run it. Languages with an \
+             environment get an isolated one per hook repository and dependency set, with \
+             toolchains downloaded when no suitable one is installed."
+                .into(),
+        ),
+        Block::Code(table.into()),
+        Block::Paragraph(
+            "See https://prek.j178.dev/languages/ for the details of each language.".into(),
+        ),
+    ]
+}
+
+/// Show the long-form help for a topic or a command.
+pub(crate) fn help(topic: &[String], man: bool, printer: Printer) -> Result<ExitStatus> {
+    let mut cli = Cli::command();
+    cli.build();
+
+    let output = if let [name] = topic
+        && let Some(topic) = TOPICS.iter().find(|topic| topic.name == name.as_str())
+    {
+        let name = format!("prek-{}", topic.name);
+        let blocks = (topic.blocks)();
+        if man {
+            render_man(&name, 7, topic.summary, &blocks)
+        } else {
+            render_terminal(&name, topic.summary, &blocks)
+        }
+    } else if topic.is_empty() && !man {
+        topic_list()
+    } else {
+        let mut command = &cli;
+        for name in topic {
+            command = command
+                .get_subcommands()
+                .find(|sub| {
+                    sub.get_name() == name.as_str()
+                        || sub.get_all_aliases().any(|alias| alias == name.as_str())
+                })
+                .filter(|sub| !sub.is_hide_set())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown help topic or command `{}`; run `prek help` for the list of topics",
+                        topic.join(" ")
+                    )
+                })?;
+        }
+        if man {
+            let name = std::iter::once("prek")
+                .chain(topic.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("-");
+            let summary = command
+                .get_about()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            render_man(&name, 1, &summary, &command_blocks(command))
+        } else {
+            command.clone().render_long_help().ansi().to_string()
+        }
+    };
+
+    if man || !std::io::stdout().is_terminal() || !page(&output)? {
+        write!(printer.stdout(), "{output}")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn topic_list() -> String {
+    let mut output = format!("{}\n\n", "Help topics:".bold());
+    for topic in TOPICS {
+        let _ = writeln!(output, "  {:<14}{}", topic.name.cyan(), topic.summary);
+    }
+    let _ = write!(
+        output,
+        "\nUse `prek help <TOPIC>` to read a topic, or `prek help <COMMAND>` for the full help of a \
+         command.\nAdd `--man` to print a man page instead, e.g. `prek help --man run > prek-run.1`.\n"
+    );
+    output
+}
+
+/// The man page sections of a command.
+fn command_blocks(command: &clap::Command) -> Vec<Block> {
+    let mut blocks = vec![
+        Block::Heading("Synopsis"),
+        Block::Code(
+            command
+                .clone()
+                .render_usage()
+                .to_string()
+                .trim_start_matches("Usage: ")
+                .to_string()
+                .into(),
+        ),
+    ];
+    if let Some(about) = command.get_long_about().or_else(|| command.get_about()) {
+        blocks.push(Block::Heading("Description"));
+        blocks.extend(
+            about
+                .to_string()
+                .split("\n\n")
+                .map(|paragraph| Block::Paragraph(paragraph.to_string().into())),
+        );
+    }
+
+    let subcommands: Vec<_> = command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .collect();
+    if !subcommands.is_empty() {
+        blocks.push(Block::Heading("Commands"));
+        for sub in subcommands {
+            let about = sub.get_about().map(ToString::to_string).unwrap_or_default();
+            blocks.push(Block::Item(sub.get_name().to_string().into(), about.into()));
+        }
+    }
+
+    let args: Vec<_> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .collect();
+    if !args.is_empty() {
+        blocks.push(Block::Heading("Options"));
+        for arg in args {
+            let mut term = match (arg.get_short(), arg.get_long()) {
+                (Some(short), Some(long)) => format!("-{short}, --{long}"),
+                (None, Some(long)) => format!("--{long}"),
+                (Some(short), None) => format!("-{short}"),
+                (None, None) => arg.get_id().to_string().to_uppercase(),
+            };
+            if arg.get_long().is_some()
+                && arg.get_action().takes_values()
+                && let Some(names) = arg.get_value_names()
+            {
+                let _ = write!(term, " <{}>", names.join("> <"));
+            }
+            let help = arg
+                .get_long_help()
+                .or_else(|| arg.get_help())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            blocks.push(Block::Item(term.into(), help.into()));
+        }
+    }
+
+    blocks
+}
+
+/// Render a page for the terminal.
+fn render_terminal(name: &str, summary: &str, blocks: &[Block]) -> String {
+    let mut output = format!("{} - {summary}\n", name.bold());
+    for block in blocks {
+        match block {
+            Block::Heading(heading) => {
+                let _ = write!(output, "\n{}\n", heading.bold());
+            }
+            Block::Paragraph(text) => {
+                output.push('\n');
+                output.push_str(&wrap(text, "    "));
+            }
+            Block::Item(term, description) => {
+                let _ = writeln!(output, "\n    {}", term.cyan());
+                output.push_str(&wrap(description, "        "));
+            }
+            Block::Code(code) => {
+                output.push('\n');
+                for line in code.lines() {
+                    let _ = writeln!(output, "        {line}");
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Wrap `text` to [`WIDTH`] columns, indenting every line.
+fn wrap(text: &str, indent: &str) -> String {
+    let mut output = String::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && indent.len() + line.len() + 1 + word.len() > WIDTH {
+            let _ = writeln!(output, "{indent}{line}");
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        let _ = writeln!(output, "{indent}{line}");
+    }
+    output
+}
+
+/// Render a man page in roff format.
+fn render_man(name: &str, section: u8, summary: &str, blocks: &[Block]) -> String {
+    let mut output = format!(
+        ".TH \"{}\" \"{section}\" \"\" \"prek {}\" \"prek manual\"\n.SH NAME\n{} \\- {}\n",
+        name.to_uppercase(),
+        version::version(),
+        roff_escape(name),
+        roff_escape(summary),
+    );
+    for block in blocks {
+        match block {
+            Block::Heading(heading) => {
+                let _ = writeln!(output, ".SH {}", heading.to_uppercase());
+            }
+            Block::Paragraph(text) => {
+                let _ = writeln!(output, ".PP\n{}", roff_lines(text));
+            }
+            Block::Item(term, description) => {
+                let _ = writeln!(
+                    output,
+                    ".TP\n\\fB{}\\fR\n{}",
+                    roff_escape(term),
+                    roff_lines(description)
+                );
+            }
+            Block::Code(code) => {
+                let _ = writeln!(output, ".PP\n.nf\n.RS 4\n{}\n.RE\n.fi", roff_lines(code));
+            }
+        }
+    }
+    output
+}
+
+/// Escape text for roff, line by line, dropping blank lines that roff would print.
+fn roff_lines(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(roff_escape)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with(['.', '\'']) {
+        format!("\\&{escaped}")
+    } else {
+        escaped
+    }
+}
+
+/// Show `output` in `$PAGER`, or `less`. Returns `false` if no pager could be started.
+fn page(output: &str) -> Result<bool> {
+    let pager = EnvVars
+        .var(EnvVars::PAGER)
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let Some(argv) = shlex::split(&pager).filter(|argv| !argv.is_empty()) else {
+        return Ok(false);
+    };
+
+    let mut cmd = std::process::Command::new(&argv[0]);
+    cmd.args(&argv[1..]).stdin(Stdio::piped());
+    if EnvVars.var_os(EnvVars::LESS).is_none() {
+        // Keep colors, and exit right away when the page fits on the screen.
+        cmd.env(EnvVars::LESS, "FRX");
+    }
+    let Ok(mut child) = cmd.spawn() else {
+        return Ok(false);
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything.
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    child.wait()?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_render_as_man_pages() {
+        let blocks = selectors();
+        let page = render_man(
+            "prek-selectors",
+            7,
+            "Select or skip hooks and projects",
+            &blocks,
+        );
+        assert!(
+            page.contains(".SH NAME\nprek\\-selectors \\- Select or skip hooks and projects\n")
+        );
+        assert!(page.contains(".TP\n\\fB<project\\-path>/\\fR\n"));
+    }
+
+    #[test]
+    fn wrap_text() {
+        let text = "word ".repeat(30);
+        let wrapped = wrap(&text, "    ");
+        assert!(wrapped.lines().all(|line| line.len() <= WIDTH));
+        assert!(wrapped.lines().all(|line| line.starts_with("    word")));
+        assert_eq!(wrapped.split_whitespace().count(), 30);
+    }
+
+    #[test]
+    fn roff_escapes_control_lines() {
+        assert_eq!(roff_escape(".hidden"), "\\&.hidden");
+        assert_eq!(roff_escape("a\\b-c"), "a\\eb\\-c");
+    }
+}
@@ -8,10 +8,12 @@ use anstream::eprintln;
 use anyhow::Result;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use prek_consts::PRE_COMMIT_CONFIG_YAML;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::debug;
 
-use crate::cli::{self, ExitStatus, RunOptions, flag};
+use crate::cli::{self, ExitStatus, RunOptions, RunSettings};
 use crate::config::HookType;
 use crate::fs::CWD;
 use crate::git::GIT_ROOT;
@@ -47,6 +49,20 @@ pub(crate) async fn hook_impl(
         );
     }
 
+    // Scripts generated by `pre-commit install` always pass `--config=.pre-commit-config.yaml`.
+    // When they invoke prek in place of `pre-commit`, discover the config like our own shims do,
+    // so that `prek.toml` and workspaces work without reinstalling the shims.
+    let config = if script_version.is_none()
+        && config.as_deref() == Some(Path::new(PRE_COMMIT_CONFIG_YAML))
+        && hook_dir.as_deref().is_some_and(|hook_dir| {
+            cli::install::is_pre_commit_script(&hook_dir.join(hook_type.as_ref()))
+        }) {
+        debug!("Invoked by a `pre-commit` generated Git hook, discovering the config");
+        None
+    } else {
+        config
+    };
+
     let allow_missing_config =
         skip_on_missing_config || EnvVars.is_set(EnvVars::PREK_ALLOW_NO_CONFIG);
     let warn_for_no_config = || {
@@ -118,23 +134,16 @@ pub(crate) async fn hook_impl(
     let Some(run_args) = to_run_args(hook_type, &args, &stdin).await? else {
         return Ok(legacy_code.into());
     };
-    let file_selection = run_args.file_selection.into();
 
     let status = cli::run(
         store,
-        config,
-        includes,
-        skips,
-        vec![],
-        vec![],
-        Some(hook_type.into()),
-        file_selection,
-        false,
-        flag(run_args.fail_fast, run_args.no_fail_fast),
-        false,
-        false,
-        run_args.extra,
-        false,
+        RunSettings {
+            config,
+            includes,
+            skips,
+            hook_stage: Some(hook_type.into()),
+            ..RunSettings::from(run_args)
+        },
         printer,
     )
     .await?;
@@ -155,6 +164,7 @@ fn hook_num_args(hook_type: HookType) -> RangeInclusive<usize> {
         HookType::PreMergeCommit => 0..=0,
         HookType::PostMerge => 1..=1,
         HookType::PostRewrite => 1..=1,
+        HookType::PreAutoGc => 0..=0,
         HookType::PrePush => 2..=2,
         HookType::PreRebase => 1..=2,
         HookType::PrepareCommitMsg => 1..=3,
@@ -288,7 +298,10 @@ async fn to_run_args(
                 run_args.extra.pre_rebase_branch = Some(args[1].to_string_lossy().into_owned());
             }
         }
-        HookType::PostCommit | HookType::PreMergeCommit | HookType::PreCommit => {}
+        HookType::PostCommit
+        | HookType::PreAutoGc
+        | HookType::PreMergeCommit
+        | HookType::PreCommit => {}
     }
 
     Ok(Some(run_args))
@@ -3,23 +3,40 @@ use std::path::PathBuf;
 
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use prek_identify::tags_from_path;
+use prek_identify::{TagSet, tags_from_path};
 use serde::Serialize;
 
 use crate::cli::{ExitStatus, IdentifyOutputFormat};
+use crate::config::load_config;
+use crate::fs::CWD;
 use crate::printer::Printer;
+use crate::workspace::{Project, Workspace};
 
 #[derive(Serialize)]
 struct IdentifyEntry {
     path: String,
-    tags: Vec<String>,
+    tags: TagSet,
 }
 
 pub(crate) fn identify(
+    config: Option<PathBuf>,
     paths: &[PathBuf],
     output_format: IdentifyOutputFormat,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
+    // Apply the `identify` mappings of the current workspace, if there is one.
+    let config = config.or_else(|| {
+        Workspace::find_root(None, &CWD)
+            .ok()
+            .and_then(|root| Project::find_config(&root))
+    });
+    if let Some(identify) = config
+        .and_then(|config| load_config(&config).ok())
+        .and_then(|config| config.identify)
+    {
+        identify.register();
+    }
+
     let mut status = ExitStatus::Success;
     let mut outputs = Vec::new();
 
@@ -37,7 +54,7 @@ pub(crate) fn identify(
                 IdentifyOutputFormat::Json => {
                     outputs.push(IdentifyEntry {
                         path: path.display().to_string(),
-                        tags: tags.iter().map(ToString::to_string).collect(),
+                        tags,
                     });
                 }
             },
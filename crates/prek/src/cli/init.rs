@@ -0,0 +1,542 @@
+use std::fmt::Write as _;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use futures_util::future::join_all;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use prek_consts::{CONFIG_FILENAMES, PRE_COMMIT_CONFIG_YAML, PREK_TOML};
+use prek_identify::tags_from_path;
+use rustc_hash::FxHashSet;
+use semver::Version;
+use tracing::debug;
+
+use crate::cli::run::FileSelection;
+use crate::cli::{ExitStatus, RunSettings, SampleConfigFormat};
+use crate::fs::Simplified;
+use crate::git;
+use crate::git::GIT_ROOT;
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::warn_user;
+use crate::workspace::Refresh;
+
+/// A remote hook repository suggested when files of a matching type are found.
+struct RemoteSuggestion {
+    repo: &'static str,
+    hooks: &'static [&'static str],
+    /// The file type tags that trigger this suggestion.
+    tags: &'static [&'static str],
+}
+
+static REMOTE_SUGGESTIONS: &[RemoteSuggestion] = &[
+    RemoteSuggestion {
+        repo: "https://github.com/astral-sh/ruff-pre-commit",
+        hooks: &["ruff", "ruff-format"],
+        tags: &["python", "pyi"],
+    },
+    RemoteSuggestion {
+        repo: "https://github.com/golangci/golangci-lint",
+        hooks: &["golangci-lint"],
+        tags: &["go"],
+    },
+    RemoteSuggestion {
+        repo: "https://github.com/shellcheck-py/shellcheck-py",
+        hooks: &["shellcheck"],
+        tags: &["shell"],
+    },
+];
+
+/// A `repo: local` hook that runs a tool already installed on the system.
+struct LocalHook {
+    id: &'static str,
+    name: &'static str,
+    entry: &'static str,
+    /// The file type tag that triggers this suggestion, also the hook's `types`.
+    tag: &'static str,
+    pass_filenames: bool,
+}
+
+static LOCAL_HOOKS: &[LocalHook] = &[
+    LocalHook {
+        id: "cargo-fmt",
+        name: "cargo fmt",
+        entry: "cargo fmt --",
+        tag: "rust",
+        pass_filenames: true,
+    },
+    LocalHook {
+        id: "cargo-clippy",
+        name: "cargo clippy",
+        entry: "cargo clippy --all-targets -- -D warnings",
+        tag: "rust",
+        pass_filenames: false,
+    },
+];
+
+/// Builtin hooks that are suggested for every repository.
+static BUILTIN_HOOKS: &[&str] = &[
+    "trailing-whitespace",
+    "end-of-file-fixer",
+    "check-added-large-files",
+];
+
+/// Builtin hooks that are suggested when files of a matching type are found.
+static BUILTIN_TYPED_HOOKS: &[(&str, &str)] = &[
+    ("yaml", "check-yaml"),
+    ("json", "check-json"),
+    ("toml", "check-toml"),
+];
+
+/// One hook of a repo entry in the generated config.
+enum HookEntry {
+    /// A hook defined by its repo, referenced by id.
+    Id(&'static str),
+    Local(&'static LocalHook),
+}
+
+/// One repo entry of the generated config.
+struct RepoEntry {
+    repo: &'static str,
+    rev: Option<String>,
+    hooks: Vec<HookEntry>,
+}
+
+/// Generate a config for the current repository, install the Git shims, and optionally run all hooks.
+#[expect(clippy::fn_params_excessive_bools)]
+pub(crate) async fn init(
+    store: &Store,
+    config: Option<PathBuf>,
+    format: Option<SampleConfigFormat>,
+    yes: bool,
+    no_install: bool,
+    run_all: bool,
+    refresh: Refresh,
+    verbose: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let git_root = GIT_ROOT.as_ref()?;
+
+    if let Some(existing) = CONFIG_FILENAMES
+        .iter()
+        .map(|filename| git_root.join(filename))
+        .find(|path| path.is_file())
+    {
+        anyhow::bail!(
+            "Found existing config `{}`, use `prek update` to update it",
+            existing.user_display().cyan()
+        );
+    }
+
+    if !yes && !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "`prek init` needs an interactive terminal, use `--yes` to accept all suggestions"
+        );
+    }
+
+    let (path, format) = match (config, format) {
+        (Some(path), Some(format)) => (path, format),
+        (Some(path), None) => {
+            let format = match path.extension() {
+                Some(ext) if ext.eq_ignore_ascii_case("toml") => SampleConfigFormat::Toml,
+                _ => SampleConfigFormat::Yaml,
+            };
+            (path, format)
+        }
+        (None, Some(SampleConfigFormat::Toml)) => {
+            (git_root.join(PREK_TOML), SampleConfigFormat::Toml)
+        }
+        (None, Some(SampleConfigFormat::Yaml) | None) => (
+            git_root.join(PRE_COMMIT_CONFIG_YAML),
+            SampleConfigFormat::Yaml,
+        ),
+    };
+    if path.exists() {
+        anyhow::bail!("File `{}` already exists", path.user_display().cyan());
+    }
+
+    let tags = detect_tags(git_root).await?;
+    debug!("Detected file types: {:?}", tags);
+
+    let mut builtin_hooks = BUILTIN_HOOKS.to_vec();
+    builtin_hooks.extend(
+        BUILTIN_TYPED_HOOKS
+            .iter()
+            .filter(|(tag, _)| tags.contains(*tag))
+            .map(|(_, hook)| *hook),
+    );
+    let mut entries = vec![RepoEntry {
+        repo: "builtin",
+        rev: None,
+        hooks: builtin_hooks.into_iter().map(HookEntry::Id).collect(),
+    }];
+
+    for suggestion in REMOTE_SUGGESTIONS {
+        let Some(tag) = suggestion.tags.iter().find(|tag| tags.contains(**tag)) else {
+            continue;
+        };
+        let accepted = yes
+            || confirm(
+                &format!(
+                    "Found {} files, add {} from {}?",
+                    tag.cyan(),
+                    suggestion
+                        .hooks
+                        .iter()
+                        .map(|hook| format!("`{hook}`"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    suggestion.repo.cyan()
+                ),
+                true,
+            )?;
+        if accepted {
+            entries.push(RepoEntry {
+                repo: suggestion.repo,
+                rev: None,
+                hooks: suggestion
+                    .hooks
+                    .iter()
+                    .copied()
+                    .map(HookEntry::Id)
+                    .collect(),
+            });
+        }
+    }
+
+    let mut local = Vec::new();
+    let local_hooks = LOCAL_HOOKS
+        .iter()
+        .filter(|hook| tags.contains(hook.tag))
+        .collect::<Vec<_>>();
+    for tag in local_hooks.iter().map(|hook| hook.tag).unique() {
+        let hooks = local_hooks
+            .iter()
+            .filter(|hook| hook.tag == tag)
+            .copied()
+            .collect::<Vec<_>>();
+        let accepted = yes
+            || confirm(
+                &format!(
+                    "Found {} files, add {} as local hooks?",
+                    tag.cyan(),
+                    hooks
+                        .iter()
+                        .map(|hook| format!("`{}`", hook.id))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                true,
+            )?;
+        if accepted {
+            local.extend(hooks.into_iter().map(HookEntry::Local));
+        }
+    }
+    if !local.is_empty() {
+        entries.push(RepoEntry {
+            repo: "local",
+            rev: None,
+            hooks: local,
+        });
+    }
+
+    // Pin every remote repo to its latest tag. Hard-coded revisions would go stale, and a repo
+    // without a usable `rev` cannot be cloned, so repos that cannot be reached are left out.
+    let remote = |entry: &RepoEntry| !matches!(entry.repo, "builtin" | "local");
+    let revs = join_all(
+        entries
+            .iter()
+            .filter(|entry| remote(entry))
+            .map(|entry| latest_tag(entry.repo)),
+    )
+    .await;
+    let mut revs = revs.into_iter();
+    let mut unresolved = Vec::new();
+    entries.retain_mut(|entry| {
+        if !remote(entry) {
+            return true;
+        }
+        match revs.next().flatten() {
+            Some(rev) => {
+                entry.rev = Some(rev);
+                true
+            }
+            None => {
+                unresolved.push(entry.repo);
+                false
+            }
+        }
+    });
+
+    let content = match format {
+        SampleConfigFormat::Yaml => render_yaml(&entries),
+        SampleConfigFormat::Toml => render_toml(&entries),
+    };
+    fs_err::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+    fs_err::write(&path, content)?;
+    writeln!(
+        printer.stdout(),
+        "Written to `{}`",
+        path.user_display().cyan()
+    )?;
+    if !unresolved.is_empty() {
+        warn_user!(
+            "Failed to fetch the latest tag of {}, add {} to the config with a `rev` yourself",
+            unresolved
+                .iter()
+                .map(|repo| format!("`{}`", repo.cyan()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            if unresolved.len() == 1 { "it" } else { "them" },
+        );
+    }
+
+    // Only pass the config through when it is not discovered automatically.
+    let discovered = std::path::absolute(&path).is_ok_and(|path| {
+        CONFIG_FILENAMES
+            .iter()
+            .any(|name| path == git_root.join(name))
+    });
+    let config = (!discovered).then_some(path);
+
+    if !no_install {
+        let status = crate::cli::install(
+            store,
+            config.clone(),
+            vec![],
+            vec![],
+            vec![],
+            false,
+            false,
+            false,
+            refresh.clone(),
+            printer,
+            None,
+        )
+        .await?;
+        if !matches!(status, ExitStatus::Success) {
+            return Ok(status);
+        }
+    }
+
+    let run_all = run_all || (!yes && confirm("Run all hooks against all files now?", false)?);
+    if run_all {
+        return crate::cli::run(
+            store,
+            RunSettings {
+                config,
+                use_jj: true,
+                selection: FileSelection::All {
+                    from_ref: None,
+                    to_ref: None,
+                },
+                refresh,
+                verbose,
+                ..RunSettings::default()
+            },
+            printer,
+        )
+        .await;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Collect the file type tags of all tracked files in the repository.
+async fn detect_tags(git_root: &Path) -> Result<FxHashSet<&'static str>> {
+    let files = git::ls_files(git_root, ["."]).await?;
+    let mut tags = FxHashSet::default();
+    for file in files {
+        if let Ok(file_tags) = tags_from_path(&git_root.join(&file)) {
+            tags.extend(file_tags.iter());
+        }
+    }
+    Ok(tags)
+}
+
+/// Return the highest semver tag of a remote repository, if it can be fetched.
+async fn latest_tag(repo: &str) -> Option<String> {
+    let tags = match git::ls_remote_tags(repo).await {
+        Ok(tags) => tags,
+        Err(err) => {
+            debug!("Failed to list tags of `{repo}`: {err}");
+            return None;
+        }
+    };
+    tags.into_iter()
+        .filter_map(|tag| {
+            let version = Version::parse(tag.strip_prefix('v').unwrap_or(&tag)).ok()?;
+            version.pre.is_empty().then_some((version, tag))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag)
+}
+
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    let mut stderr = anstream::stderr();
+    write!(stderr, "{question} {} ", hint.dimmed())?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
+fn render_yaml(entries: &[RepoEntry]) -> String {
+    let mut out = String::from("# See https://prek.j178.dev for more information.\nrepos:\n");
+    for entry in entries {
+        let _ = writeln!(out, "  - repo: {}", entry.repo);
+        if let Some(rev) = &entry.rev {
+            let _ = writeln!(out, "    rev: {rev}");
+        }
+        out.push_str("    hooks:\n");
+        for hook in &entry.hooks {
+            match hook {
+                HookEntry::Id(id) => {
+                    let _ = writeln!(out, "      - id: {id}");
+                }
+                HookEntry::Local(hook) => {
+                    let _ = writeln!(out, "      - id: {}", hook.id);
+                    let _ = writeln!(out, "        name: {}", hook.name);
+                    let _ = writeln!(out, "        entry: {}", hook.entry);
+                    out.push_str("        language: system\n");
+                    let _ = writeln!(out, "        types: [{}]", hook.tag);
+                    if !hook.pass_filenames {
+                        out.push_str("        pass_filenames: false\n");
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn render_toml(entries: &[RepoEntry]) -> String {
+    let mut out = String::from(indoc::indoc! {"
+        # Configuration file for `prek`, a git hook framework written in Rust.
+        # See https://prek.j178.dev for more information.
+        #:schema https://www.schemastore.org/prek.json
+    "});
+    for entry in entries {
+        let _ = writeln!(out, "\n[[repos]]\nrepo = \"{}\"", entry.repo);
+        if let Some(rev) = &entry.rev {
+            let _ = writeln!(out, "rev = \"{rev}\"");
+        }
+        out.push_str("hooks = [\n");
+        for hook in &entry.hooks {
+            match hook {
+                HookEntry::Id(id) => {
+                    let _ = writeln!(out, "    {{ id = \"{id}\" }},");
+                }
+                HookEntry::Local(hook) => {
+                    let _ = write!(
+                        out,
+                        "    {{ id = \"{}\", name = \"{}\", entry = \"{}\", language = \"system\", types = [\"{}\"]",
+                        hook.id, hook.name, hook.entry, hook.tag
+                    );
+                    if !hook.pass_filenames {
+                        out.push_str(", pass_filenames = false");
+                    }
+                    out.push_str(" },\n");
+                }
+            }
+        }
+        out.push_str("]\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<RepoEntry> {
+        vec![
+            RepoEntry {
+                repo: "builtin",
+                rev: None,
+                hooks: vec![
+                    HookEntry::Id("trailing-whitespace"),
+                    HookEntry::Id("check-yaml"),
+                ],
+            },
+            RepoEntry {
+                repo: "https://github.com/astral-sh/ruff-pre-commit",
+                rev: Some("v0.6.9".to_string()),
+                hooks: vec![HookEntry::Id("ruff"), HookEntry::Id("ruff-format")],
+            },
+            RepoEntry {
+                repo: "local",
+                rev: None,
+                hooks: LOCAL_HOOKS.iter().map(HookEntry::Local).collect(),
+            },
+        ]
+    }
+
+    #[test]
+    fn render_yaml_config() {
+        insta::assert_snapshot!(render_yaml(&entries()), @r"
+        # See https://prek.j178.dev for more information.
+        repos:
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+              - id: check-yaml
+          - repo: https://github.com/astral-sh/ruff-pre-commit
+            rev: v0.6.9
+            hooks:
+              - id: ruff
+              - id: ruff-format
+          - repo: local
+            hooks:
+              - id: cargo-fmt
+                name: cargo fmt
+                entry: cargo fmt --
+                language: system
+                types: [rust]
+              - id: cargo-clippy
+                name: cargo clippy
+                entry: cargo clippy --all-targets -- -D warnings
+                language: system
+                types: [rust]
+                pass_filenames: false
+        ");
+    }
+
+    #[test]
+    fn render_toml_config() {
+        insta::assert_snapshot!(render_toml(&entries()), @r#"
+        # Configuration file for `prek`, a git hook framework written in Rust.
+        # See https://prek.j178.dev for more information.
+        #:schema https://www.schemastore.org/prek.json
+
+        [[repos]]
+        repo = "builtin"
+        hooks = [
+            { id = "trailing-whitespace" },
+            { id = "check-yaml" },
+        ]
+
+        [[repos]]
+        repo = "https://github.com/astral-sh/ruff-pre-commit"
+        rev = "v0.6.9"
+        hooks = [
+            { id = "ruff" },
+            { id = "ruff-format" },
+        ]
+
+        [[repos]]
+        repo = "local"
+        hooks = [
+            { id = "cargo-fmt", name = "cargo fmt", entry = "cargo fmt --", language = "system", types = ["rust"] },
+            { id = "cargo-clippy", name = "cargo clippy", entry = "cargo clippy --all-targets -- -D warnings", language = "system", types = ["rust"], pass_filenames = false },
+        ]
+        "#);
+    }
+}
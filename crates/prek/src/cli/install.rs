@@ -9,18 +9,22 @@ use clap::ValueEnum;
 use owo_colors::OwoColorize;
 use prek_consts::CONFIG_FILENAMES;
 use same_file::is_same_file;
+use serde::Serialize;
+use tracing::debug;
 
+use crate::cli::cache_size::human_readable_bytes;
 use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
 use crate::cli::run;
-use crate::cli::run::InstallCache;
+use crate::cli::run::{EnvLinks, InstallCache};
 use crate::cli::run::{SelectorSource, Selectors};
-use crate::cli::{ExitStatus, HookType};
-use crate::config::load_config;
+use crate::cli::{ExitStatus, HookType, PrepareHooksOutputFormat};
+use crate::config::{Language, load_config};
 use crate::fs::{CWD, Simplified};
 use crate::git::{GIT_ROOT, git_cmd};
+use crate::languages::ToolchainDownload;
 use crate::printer::Printer;
 use crate::store::Store;
-use crate::workspace::{Error as WorkspaceError, HookInitFilters, Project, Workspace};
+use crate::workspace::{Error as WorkspaceError, HookInitFilters, Project, Refresh, Workspace};
 use crate::{git, warn_user};
 
 #[allow(clippy::fn_params_excessive_bools)]
@@ -33,7 +37,7 @@ pub(crate) async fn install(
     prepare_hooks: bool,
     overwrite: bool,
     allow_missing_config: bool,
-    refresh: bool,
+    refresh: Refresh,
     printer: Printer,
     git_dir: Option<&Path>,
 ) -> Result<ExitStatus> {
@@ -120,7 +124,17 @@ pub(crate) async fn install(
     }
 
     if prepare_hooks {
-        self::prepare_hooks(store, config, includes, skips, refresh, printer).await?;
+        self::prepare_hooks(
+            store,
+            config,
+            includes,
+            skips,
+            PrepareHooksOutputFormat::Text,
+            false,
+            refresh,
+            printer,
+        )
+        .await?;
     }
 
     Ok(ExitStatus::Success)
@@ -131,12 +145,26 @@ pub(crate) async fn prepare_hooks(
     config: Option<PathBuf>,
     includes: Vec<String>,
     skips: Vec<String>,
-    refresh: bool,
+    output_format: PrepareHooksOutputFormat,
+    dry_run: bool,
+    refresh: Refresh,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
     let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), refresh)?;
+    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), &refresh)?;
+
+    if dry_run {
+        let plan = PrepareHooksPlan::new(store, &workspace, &selectors).await?;
+        match output_format {
+            PrepareHooksOutputFormat::Text => plan.write_text(printer)?,
+            PrepareHooksOutputFormat::Json => {
+                let json_output = serde_json::to_string_pretty(&plan)?;
+                writeln!(printer.stdout(), "{json_output}")?;
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
 
     let reporter = HookInitReporter::new(printer);
     let _lock = store.lock_async().await?;
@@ -160,9 +188,176 @@ pub(crate) async fn prepare_hooks(
     run::install_hooks(filtered_hooks, store, &reporter, &mut install_cache).await?;
     reporter.on_complete();
 
+    let stats = install_cache.stats();
+    debug!("Hook environments: {stats}");
+    if matches!(output_format, PrepareHooksOutputFormat::Json) {
+        let json_output = serde_json::to_string_pretty(&stats)?;
+        writeln!(printer.stdout(), "{json_output}")?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// What `prek prepare-hooks` would do, computed without cloning or installing anything.
+#[derive(Debug, Serialize)]
+struct PrepareHooksPlan {
+    /// Remote repos that are not in the store yet.
+    clone: Vec<PlannedClone>,
+    /// Toolchains that no installed or system toolchain satisfies.
+    download: Vec<PlannedDownload>,
+    /// New environments, each shared by the listed hooks.
+    install: Vec<PlannedEnv>,
+    /// Hooks that would reuse an environment already in the store.
+    reused: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedClone {
+    repo: String,
+    rev: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedDownload {
+    language: Language,
+    #[serde(flatten)]
+    toolchain: ToolchainDownload,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedEnv {
+    language: Language,
+    language_version: String,
+    dependencies: Vec<String>,
+    hooks: Vec<String>,
+}
+
+impl PrepareHooksPlan {
+    async fn new(store: &Store, workspace: &Workspace, selectors: &Selectors) -> Result<Self> {
+        let (hooks, missing_repos) = workspace
+            .init_cached_hooks(store, HookInitFilters::new(Some(selectors), None))
+            .await
+            .context("Failed to init hooks")?;
+        let hooks: Vec<_> = hooks
+            .into_iter()
+            .filter(|h| selectors.matches_hook(h))
+            .map(Arc::new)
+            .collect();
+
+        let install_cache = InstallCache::new();
+        let groups = run::plan_new_envs(&hooks, store, &install_cache).await;
+        let needs_env = hooks.iter().filter(|hook| hook.needs_install_env()).count();
+        let installed = groups.iter().map(Vec::len).sum::<usize>();
+
+        let mut download: Vec<PlannedDownload> = Vec::new();
+        for group in &groups {
+            let language = group[0].language;
+            let Some(toolchain) = language.toolchain_download(store, &group[0]).await? else {
+                continue;
+            };
+            if !download
+                .iter()
+                .any(|planned| planned.language == language && planned.toolchain == toolchain)
+            {
+                download.push(PlannedDownload {
+                    language,
+                    toolchain,
+                });
+            }
+        }
+
+        Ok(Self {
+            clone: missing_repos
+                .into_iter()
+                .map(|repo| PlannedClone {
+                    repo: repo.repo().to_string(),
+                    rev: repo.rev.clone(),
+                })
+                .collect(),
+            download,
+            install: groups
+                .into_iter()
+                .map(|group| PlannedEnv {
+                    language: group[0].language,
+                    language_version: if group[0].language_version.is_empty() {
+                        "default".to_string()
+                    } else {
+                        group[0].language_version.clone()
+                    },
+                    dependencies: group[0].env_dependencies().to_vec(),
+                    hooks: group.iter().map(|hook| format!("{hook:#}")).collect(),
+                })
+                .collect(),
+            reused: needs_env - installed,
+        })
+    }
+
+    fn write_text(&self, printer: Printer) -> Result<()> {
+        let mut stdout = printer.stdout();
+
+        if !self.clone.is_empty() {
+            writeln!(stdout, "{}", "Repositories to clone:".bold())?;
+            for repo in &self.clone {
+                writeln!(stdout, "  {}@{}", repo.repo.cyan(), repo.rev)?;
+            }
+        }
+
+        if !self.download.is_empty() {
+            writeln!(stdout, "{}", "Toolchains to download:".bold())?;
+            for planned in &self.download {
+                let toolchain = &planned.toolchain;
+                write!(stdout, "  {}", planned.language.as_ref().cyan())?;
+                if let Some(version) = &toolchain.version {
+                    write!(stdout, " {version}")?;
+                }
+                if let Some(size) = toolchain.size {
+                    let (size, unit) = human_readable_bytes(size);
+                    write!(stdout, " ({size:.1}{unit})")?;
+                }
+                match &toolchain.url {
+                    Some(url) => writeln!(stdout, " from {url}")?,
+                    None => writeln!(stdout, " (version could not be resolved)")?,
+                }
+            }
+        }
+
+        if !self.install.is_empty() {
+            writeln!(stdout, "{}", "Environments to install:".bold())?;
+            for env in &self.install {
+                write!(
+                    stdout,
+                    "  {} (language_version: {})",
+                    env.language.as_ref().cyan(),
+                    env.language_version
+                )?;
+                if !env.dependencies.is_empty() {
+                    write!(stdout, " with {}", env.dependencies.join(", "))?;
+                }
+                writeln!(stdout, " for {}", env.hooks.join(", "))?;
+            }
+        }
+
+        if self.reused > 0 {
+            writeln!(
+                stdout,
+                "{} hook(s) would reuse an installed environment",
+                self.reused
+            )?;
+        }
+
+        if self.clone.is_empty() && self.install.is_empty() {
+            writeln!(stdout, "Nothing to prepare")?;
+        } else if !self.clone.is_empty() {
+            writeln!(
+                stdout,
+                "Environments for hooks from repositories that are not cloned yet are not listed"
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 fn get_hook_types(
     mut hook_types: Vec<HookType>,
     project: Option<&Project>,
@@ -224,16 +419,30 @@ fn install_hook_script(
                 "Overwriting existing hook at `{}`",
                 hook_path.user_display().cyan()
             )?;
-        } else {
-            if !is_our_script(&hook_path)? {
-                fs_err::rename(&hook_path, &legacy_path)?;
-                writeln!(
-                    printer.stdout(),
-                    "Hook already exists at `{}`, moved it to `{}`",
+        } else if is_pre_commit_script(&hook_path) {
+            // Running it as a legacy hook would run the same hooks twice.
+            writeln!(
+                printer.stdout(),
+                "Replacing `pre-commit` hook at `{}`",
+                hook_path.user_display().cyan()
+            )?;
+        } else if !is_our_script(&hook_path)? {
+            // Moving the hook aside would clobber a backup made by an earlier install.
+            if legacy_path.try_exists()? && !is_our_script(&legacy_path)? {
+                anyhow::bail!(
+                    "Refusing to overwrite `{}`, which is not managed by prek, because `{}` already exists.\n\n{} Move one of them out of the way, or use `--overwrite` to replace both.",
                     hook_path.user_display().cyan(),
-                    legacy_path.user_display().yellow()
-                )?;
+                    legacy_path.user_display().cyan(),
+                    "hint:".yellow().bold(),
+                );
             }
+            fs_err::rename(&hook_path, &legacy_path)?;
+            writeln!(
+                printer.stdout(),
+                "Hook already exists at `{}`, moved it to `{}`",
+                hook_path.user_display().cyan(),
+                legacy_path.user_display().yellow()
+            )?;
         }
     }
 
@@ -374,6 +583,22 @@ static PRIOR_HASHES: &[&str] = &[];
 // Use a different hash from `pre-commit` since our script is different.
 static CURRENT_HASH: &str = "182c10f181da4464a3eec51b83331688";
 
+// Hashes of the scripts generated by upstream `pre-commit install`, current and prior.
+static PRE_COMMIT_HASHES: &[&str] = &[
+    "138fd403232d2ddd5efb44317e38bf03",
+    "4d9958c90bc262f47553e2c073f14cfe",
+    "d8ee923c46731b42cd95cc869add4062",
+    "49fd668cb42069aa1b6048464be5d395",
+    "79f09a650522a87b0da915d0d983b2de",
+    "e358c9dae00eac5d06b38dfdb1e33a8c",
+];
+
+/// Checks if the script was generated by upstream `pre-commit`.
+pub(crate) fn is_pre_commit_script(hook_path: &Path) -> bool {
+    fs_err::read_to_string(hook_path)
+        .is_ok_and(|content| PRE_COMMIT_HASHES.iter().any(|hash| content.contains(hash)))
+}
+
 /// Checks if the script contains any of the hashes that `prek` has used in the past.
 fn is_our_script(hook_path: &Path) -> std::io::Result<bool> {
     let content = fs_err::read_to_string(hook_path)?;
@@ -449,6 +674,17 @@ pub(crate) async fn uninstall(
                 continue;
             }
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                // The shim was removed by hand, still put back the hook it replaced.
+                if legacy_path.try_exists()? {
+                    fs_err::rename(&legacy_path, &hook_path)?;
+                    writeln!(
+                        printer.stdout(),
+                        "Restored `{}` to `{}`",
+                        legacy_path.user_display().cyan(),
+                        hook_path.user_display().cyan()
+                    )?;
+                    continue;
+                }
                 if !all {
                     writeln!(
                         printer.stderr(),
@@ -479,16 +715,42 @@ pub(crate) async fn uninstall(
         }
     }
 
+    if git_dir.is_none() {
+        remove_env_links(printer)?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Remove the hook environment links created by `prek env link`.
+fn remove_env_links(printer: Printer) -> Result<()> {
+    let Ok(git_root) = GIT_ROOT.as_ref() else {
+        return Ok(());
+    };
+    let mut links = EnvLinks::load(git_root)?;
+    for link in links.take() {
+        let path = git_root.join(&link.path);
+        // Leave the path alone if it was replaced with something that is not a link.
+        if fs_err::read_link(&path).is_err() {
+            continue;
+        }
+        run::remove_link(&path)?;
+        writeln!(
+            printer.stdout(),
+            "Removed env link `{}`",
+            path.user_display().cyan()
+        )?;
+    }
+    links.save(git_root)
+}
+
 pub(crate) async fn init_template_dir(
     store: &Store,
     directory: PathBuf,
     config: Option<PathBuf>,
     hook_types: Vec<HookType>,
     requires_config: bool,
-    refresh: bool,
+    refresh: Refresh,
     printer: Printer,
 ) -> Result<ExitStatus> {
     install(
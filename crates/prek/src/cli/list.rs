@@ -6,23 +6,35 @@ use owo_colors::OwoColorize;
 use serde::Serialize;
 
 use crate::cli::reporter::HookInitReporter;
-use crate::cli::run::{GroupFilters, Selectors};
-use crate::cli::{ExitStatus, ListOutputFormat};
-use crate::config::{Language, Stage};
+use crate::cli::run::{GroupFilters, InstallCache, Selectors};
+use crate::cli::{ExitStatus, ListFilter, ListOutputFormat};
+use crate::config::{FilePattern, Language, Stage};
 use crate::fs::CWD;
+use crate::hook::{Hook, Repo};
 use crate::printer::Printer;
 use crate::store::Store;
-use crate::workspace::{HookInitFilters, Workspace};
+use crate::workspace::{HookInitFilters, Refresh, Workspace};
 
 #[derive(Serialize)]
 struct SerializableHook {
     id: String,
     full_id: String,
+    /// The project directory relative to the workspace root, `.` for the root project.
+    project: String,
     name: String,
     alias: String,
     language: Language,
     description: Option<String>,
+    /// Hooks run in ascending priority order, hooks sharing a priority run concurrently.
+    priority: u32,
     stages: Vec<Stage>,
+    files: Option<FilePattern>,
+    exclude: Option<FilePattern>,
+    /// Whether the hook environment is installed, `null` for hooks that need no environment.
+    installed: Option<bool>,
+    /// Whether the project's `ci.skip` lists this hook.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    ci_skip: bool,
 }
 
 pub(crate) async fn list(
@@ -34,15 +46,17 @@ pub(crate) async fn list(
     no_groups: Vec<String>,
     hook_stage: Option<Stage>,
     language: Option<Language>,
+    filters: Vec<ListFilter>,
+    tree: bool,
     output_format: ListOutputFormat,
-    refresh: bool,
+    refresh: Refresh,
     verbose: bool,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
     let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
     let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
     let group_filters = GroupFilters::parse(&groups, &no_groups)?;
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), refresh)?;
+    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), &refresh)?;
 
     let reporter = HookInitReporter::new(printer);
     let lock = store.lock_async().await?;
@@ -55,22 +69,37 @@ pub(crate) async fn list(
         .await
         .context("Failed to init hooks")?;
 
-    drop(lock);
-
     let filtered_hooks: Vec<_> = hooks
         .into_iter()
         .filter(|h| selectors.matches_hook(h))
         .filter(|h| group_filters.matches_hook(h))
         .filter(|h| hook_stage.is_none_or(|hook_stage| h.stages.contains(hook_stage)))
         .filter(|h| language.is_none_or(|lang| h.language == lang))
+        .filter(|h| filters.iter().all(|filter| matches_filter(h, filter)))
         .collect();
 
+    // Install states are read from the store, so resolve them while still holding the lock.
+    let install_states = if tree || matches!(output_format, ListOutputFormat::Json) {
+        let install_cache = InstallCache::new();
+        let mut states = Vec::with_capacity(filtered_hooks.len());
+        for hook in &filtered_hooks {
+            states.push(install_state(&install_cache, store, hook).await);
+        }
+        states
+    } else {
+        Vec::new()
+    };
+
+    drop(lock);
+
     selectors.report_unused();
     group_filters.report_unused();
 
     match output_format {
         ListOutputFormat::Text => {
-            if verbose {
+            if tree {
+                write_tree(&filtered_hooks, &install_states, printer)?;
+            } else if verbose {
                 // TODO: show repo path and environment path (if installed)
                 for hook in &filtered_hooks {
                     writeln!(printer.stdout(), "{}", hook.full_id().bold())?;
@@ -110,6 +139,12 @@ pub(crate) async fn list(
                         "Stages:".bold().cyan(),
                         hook.stages
                     )?;
+                    writeln!(
+                        printer.stdout(),
+                        "  {} {}",
+                        "Priority:".bold().cyan(),
+                        hook.priority
+                    )?;
                     writeln!(printer.stdout())?;
                 }
             } else {
@@ -121,18 +156,37 @@ pub(crate) async fn list(
         ListOutputFormat::Json => {
             let serializable_hooks: Vec<_> = filtered_hooks
                 .into_iter()
-                .map(|h| {
+                .zip(install_states)
+                .map(|(h, state)| {
                     let id = h.id.clone();
                     let full_id = h.full_id();
                     let stages = h.stages.iter().collect();
+                    let ci_skip = h
+                        .project()
+                        .config()
+                        .ci
+                        .as_ref()
+                        .is_some_and(|ci| ci.skip.contains(&h.id));
+                    let project = h.project().to_string();
+                    let installed = match state {
+                        InstallState::NoEnv => None,
+                        InstallState::Installed => Some(true),
+                        InstallState::NotInstalled => Some(false),
+                    };
                     SerializableHook {
                         id,
                         full_id,
+                        project,
                         name: h.name,
                         alias: h.alias,
                         language: h.language,
                         description: h.description,
+                        priority: h.priority,
                         stages,
+                        files: h.files,
+                        exclude: h.exclude,
+                        installed,
+                        ci_skip,
                     }
                 })
                 .collect();
@@ -144,3 +198,104 @@ pub(crate) async fn list(
 
     Ok(ExitStatus::Success)
 }
+
+fn matches_filter(hook: &Hook, filter: &ListFilter) -> bool {
+    match filter {
+        ListFilter::Language(language) => hook.language == *language,
+        ListFilter::Stage(stage) => hook.stages.contains(*stage),
+        ListFilter::Repo(repo) => match hook.repo() {
+            Repo::Remote { url, .. } => url == repo,
+            Repo::Local { .. } => repo == "local",
+            Repo::Meta { .. } => repo == "meta",
+            Repo::Builtin { .. } => repo == "builtin",
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum InstallState {
+    /// The hook does not need an environment.
+    NoEnv,
+    Installed,
+    NotInstalled,
+}
+
+async fn install_state(install_cache: &InstallCache, store: &Store, hook: &Hook) -> InstallState {
+    if hook.environment_requirement().is_none() {
+        InstallState::NoEnv
+    } else if install_cache.installed_info(store, hook).await.is_some() {
+        InstallState::Installed
+    } else {
+        InstallState::NotInstalled
+    }
+}
+
+/// Render hooks as a `project -> repo -> hook` tree.
+fn write_tree(hooks: &[Hook], states: &[InstallState], printer: Printer) -> anyhow::Result<()> {
+    // Group by project in workspace order, then by repo in config order.
+    let mut projects: Vec<(&Hook, Vec<(String, Vec<(&Hook, InstallState)>)>)> = Vec::new();
+    for (hook, state) in hooks.iter().zip(states.iter().copied()) {
+        let project_idx = hook.project().idx();
+        let repos = match projects
+            .iter_mut()
+            .find(|(first, _)| first.project().idx() == project_idx)
+        {
+            Some((_, repos)) => repos,
+            None => {
+                projects.push((hook, Vec::new()));
+                &mut projects.last_mut().expect("just pushed").1
+            }
+        };
+
+        let repo = hook.repo().to_string();
+        match repos.iter_mut().find(|(name, _)| *name == repo) {
+            Some((_, hooks)) => hooks.push((hook, state)),
+            None => repos.push((repo, vec![(hook, state)])),
+        }
+    }
+    projects.sort_by_key(|(first, _)| first.project().idx());
+
+    for (first, repos) in &projects {
+        writeln!(printer.stdout(), "{}", first.project().to_string().bold())?;
+        for (repo_pos, (repo, hooks)) in repos.iter().enumerate() {
+            let last_repo = repo_pos + 1 == repos.len();
+            let (branch, indent) = if last_repo {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            writeln!(printer.stdout(), "{branch}{}", repo.cyan())?;
+
+            for (hook_pos, (hook, state)) in hooks.iter().enumerate() {
+                let branch = if hook_pos + 1 == hooks.len() {
+                    "└── "
+                } else {
+                    "├── "
+                };
+                let icon = match state {
+                    InstallState::Installed => "✔".green().to_string(),
+                    InstallState::NotInstalled => "○".yellow().to_string(),
+                    InstallState::NoEnv => "·".dimmed().to_string(),
+                };
+                writeln!(
+                    printer.stdout(),
+                    "{indent}{branch}{icon} {} {}",
+                    hook.id,
+                    format!("({})", hook.language.as_ref()).dimmed()
+                )?;
+            }
+        }
+    }
+
+    if !projects.is_empty() {
+        writeln!(
+            printer.stdout(),
+            "\n{} installed  {} not installed  {} no environment needed",
+            "✔".green(),
+            "○".yellow(),
+            "·".dimmed()
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::time::Duration;
 
 use clap::builder::styling::{AnsiColor, Effects};
 use clap::builder::{ArgPredicate, PathBufValueParser, Styles, TypedValueParser};
@@ -15,18 +16,30 @@ use crate::fs::expand_tilde;
 
 mod cache_clean;
 mod cache_gc;
+mod cache_prune;
 mod cache_size;
+mod compare;
 mod completion;
+mod config_lint;
+mod config_schema;
+mod env_link;
+mod export_config;
+mod help;
 mod hook_impl;
 mod identify;
+mod init;
 mod install;
 mod list;
 mod list_builtins;
+mod plan;
+mod render_hook;
 pub mod reporter;
 pub mod run;
 mod sample_config;
+mod sbom;
 #[cfg(feature = "self-update")]
 mod self_update;
+mod server_hook;
 mod try_repo;
 mod update;
 mod validate;
@@ -34,17 +47,29 @@ mod yaml_to_toml;
 
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_gc::cache_gc;
+pub(crate) use cache_prune::cache_prune;
 pub(crate) use cache_size::cache_size;
+pub(crate) use compare::compare;
 use completion::selector_completer;
+pub(crate) use config_lint::config_lint;
+pub(crate) use config_schema::config_schema;
+pub(crate) use env_link::env_link;
+pub(crate) use export_config::export_config;
+pub(crate) use help::help;
 pub(crate) use hook_impl::hook_impl;
 pub(crate) use identify::identify;
+pub(crate) use init::init;
 pub(crate) use install::{init_template_dir, install, prepare_hooks, uninstall};
 pub(crate) use list::list;
 pub(crate) use list_builtins::list_builtins;
-pub(crate) use run::run;
+pub(crate) use plan::plan;
+pub(crate) use render_hook::render_hook;
+pub(crate) use run::{RunSettings, run};
 pub(crate) use sample_config::sample_config;
+pub(crate) use sbom::sbom;
 #[cfg(feature = "self-update")]
 pub(crate) use self_update::self_update;
+pub(crate) use server_hook::server_hook;
 pub(crate) use try_repo::try_repo;
 pub(crate) use update::update;
 pub(crate) use validate::{validate_configs, validate_manifest};
@@ -130,11 +155,9 @@ const STYLES: Styles = Styles::styled()
 #[derive(Parser)]
 #[command(
     name = "prek",
-    long_version = crate::version::version(),
     about = "A fast Git hook manager written in Rust, designed as a drop-in alternative to pre-commit, reimagined."
 )]
 #[command(
-    propagate_version = true,
     disable_help_flag = true,
     disable_help_subcommand = true,
     disable_version_flag = true
@@ -150,6 +173,10 @@ pub(crate) struct Cli {
 
     #[command(flatten)]
     pub(crate) globals: GlobalArgs,
+
+    /// The format to use for `--version` output.
+    #[arg(long = "output-format", value_enum, requires = "version", hide = true)]
+    pub(crate) version_output_format: Option<VersionOutputFormat>,
 }
 
 #[derive(Debug, Args)]
@@ -187,8 +214,19 @@ pub(crate) struct GlobalArgs {
     pub(crate) color: ColorChoice,
 
     /// Refresh all cached data.
-    #[arg(global = true, long)]
-    pub(crate) refresh: bool,
+    ///
+    /// Pass project paths, e.g. `--refresh=packages/app`, to only rediscover the workspace
+    /// projects at or below them and keep the cached state of the other projects. Can be
+    /// specified multiple times.
+    #[arg(
+        global = true,
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        require_equals = true,
+        value_hint = ValueHint::DirPath,
+    )]
+    pub(crate) refresh: Option<Vec<PathBuf>>,
 
     /// Display the concise help for this command.
     #[arg(global = true, short, long, action = ArgAction::HelpShort)]
@@ -211,8 +249,8 @@ pub(crate) struct GlobalArgs {
     #[arg(global = true, short, long, action = ArgAction::Count)]
     pub(crate) verbose: u8,
 
-    /// Write trace logs to the specified file.
-    /// If not specified, trace logs will be written to `$PREK_HOME/prek.log`.
+    /// Write debug logs to the specified file, as JSON lines.
+    /// If not specified, logs will be written to `$PREK_HOME/log/prek.log`.
     #[arg(
         global = true,
         long,
@@ -222,13 +260,16 @@ pub(crate) struct GlobalArgs {
     )]
     pub(crate) log_file: Option<PathBuf>,
 
-    /// Do not write trace logs to a log file.
+    /// Do not write logs to a log file.
     #[arg(global = true, long, overrides_with = "log_file", hide = true)]
     pub(crate) no_log_file: bool,
 
     /// Display the prek version.
-    #[arg(global = true, short = 'V', long, action = ArgAction::Version)]
-    version: (),
+    ///
+    /// Use `--version --output-format json` to include build metadata, such as the commit,
+    /// target triple, build date, and enabled features.
+    #[arg(global = true, short = 'V', long)]
+    pub(crate) version: bool,
 
     /// Show the resolved settings for the current command.
     ///
@@ -267,13 +308,23 @@ pub(crate) enum Command {
     ValidateConfig(ValidateConfigArgs),
     /// Validate `.pre-commit-hooks.yaml` files.
     ValidateManifest(ValidateManifestArgs),
+    /// Lint configuration files and print their JSON Schema.
+    Config(ConfigNamespace),
     /// Produce a sample configuration file (prek.toml or .pre-commit-config.yaml).
     SampleConfig(SampleConfigArgs),
+    /// Interactively create a configuration file for the current repository.
+    ///
+    /// Detects the file types used in the repository, suggests hooks for them
+    /// (pinned to the latest released tags), writes the configuration file at
+    /// the repository root, and installs the Git shims.
+    Init(InitArgs),
     /// Update the `rev` field of repositories in the config file to the latest version.
     #[command(name = "update", aliases = ["auto-update", "autoupdate"])]
     Update(UpdateArgs),
     /// Manage the prek cache.
     Cache(CacheNamespace),
+    /// Manage hook environments.
+    Env(EnvNamespace),
     /// Clean unused cached repos.
     #[command(hide = true)]
     GC(CacheGcArgs),
@@ -285,6 +336,51 @@ pub(crate) enum Command {
     InitTemplateDir(InitTemplateDirArgs),
     /// Try the pre-commit hooks in the current repo.
     TryRepo(Box<TryRepoArgs>),
+    /// Run both `prek run` and Python `pre-commit run` and report where they differ.
+    ///
+    /// Both tools run with the same arguments on separate temporary clones of the
+    /// repository, holding the currently staged changes. The hooks that ran, their
+    /// outcomes, the files they modified, and the exit codes are compared. With
+    /// `--per-hook`, the files modified by each hook are compared as well.
+    ///
+    /// Exits with a non-zero status if any difference is found.
+    Compare(CompareArgs),
+    /// Print the command lines a hook would be run with, without running it.
+    ///
+    /// Shows the working directory, the environment variables set by the hook's `env`,
+    /// and the argv of every batch the given files would be split into.
+    RenderHook(RenderHookArgs),
+    /// Print the configuration of every project as a loadable config, one YAML document per project.
+    ///
+    /// Project defaults such as `default_stages`, `default_language_version` and
+    /// `default_env` are applied to each hook, and remote hooks are merged with
+    /// their manifest, so the output describes exactly which hooks run and how.
+    ExportConfig(ExportConfigArgs),
+    /// Print the order hooks would run in, without running them.
+    ///
+    /// Hooks of each project are grouped by `priority`; hooks in the same group run
+    /// concurrently, and groups run in ascending priority order. Projects are listed in the
+    /// order they run, from the deepest to the workspace root.
+    Plan(PlanArgs),
+    /// Print a software bill of materials of everything prek installed for the workspace.
+    ///
+    /// Lists the cloned hook repositories with their revisions, the language toolchains used
+    /// by hook environments, and the Python, npm, Cargo and RubyGems packages installed into
+    /// each environment. Only repositories and environments that already exist are included, so
+    /// nothing is cloned or installed; run `prek prepare-hooks` first to include every hook.
+    Sbom(SbomArgs),
+    /// Run hooks against the refs pushed to a server-side repository.
+    ///
+    /// Intended to be called from a `pre-receive` hook on a self-hosted Git server. Reads the
+    /// ref updates from stdin, checks out each pushed commit into a temporary work tree and
+    /// runs the hooks with the changes of the push as input.
+    ///
+    /// The configuration is read from `--config` if given, otherwise from the default branch of
+    /// the receiving repository. The configuration in the pushed commits is never used, as it
+    /// could run arbitrary commands on the server.
+    ///
+    /// Exits with a non-zero status if the hooks fail for any ref, which rejects the push.
+    ServerHook(ServerHookArgs),
     /// The implementation of the prek Git shim that is installed in Git's effective hooks directory.
     #[command(hide = true)]
     HookImpl(HookImplArgs),
@@ -293,6 +389,13 @@ pub(crate) enum Command {
     /// `prek` self management.
     #[command(name = "self")]
     Self_(SelfNamespace),
+    /// Show long-form help for a command or a topic.
+    ///
+    /// Topics cover subjects that span several commands, such as the selector syntax or
+    /// workspace mode. Run `prek help` without arguments to list them.
+    ///
+    /// The help is shown in a pager (`$PAGER`, or `less`) when stdout is a terminal.
+    Help(HelpArgs),
 }
 
 #[derive(Debug, Args)]
@@ -329,8 +432,8 @@ pub(crate) struct InstallArgs {
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
 
-    /// Overwrite existing Git shims.
-    #[arg(short = 'f', long)]
+    /// Overwrite existing Git shims, including hooks not managed by prek.
+    #[arg(short = 'f', long, alias = "force")]
     pub(crate) overwrite: bool,
 
     /// Also prepare environments for all hooks used in the config file.
@@ -402,6 +505,24 @@ pub(crate) struct PrepareHooksArgs {
     /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
     #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
     pub(crate) skips: Vec<String>,
+
+    /// The output format.
+    ///
+    /// The `json` format reports how many hooks reused an existing environment, needed a new one,
+    /// or had an unhealthy environment rebuilt.
+    #[arg(long, value_enum, default_value_t = PrepareHooksOutputFormat::Text)]
+    pub(crate) output_format: PrepareHooksOutputFormat,
+
+    /// List the repositories that would be cloned, the toolchains that would be downloaded and the
+    /// environments that would be installed, without cloning or installing anything.
+    ///
+    /// Toolchain versions and download sizes are looked up from the download hosts, and left out
+    /// when they cannot be reached.
+    ///
+    /// Environments of hooks from repositories that are not cloned yet cannot be listed, since
+    /// their hook definitions are only known after cloning.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
 }
 
 #[derive(Debug, Args)]
@@ -470,7 +591,7 @@ pub(crate) struct RunExtraArgs {
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct FileSelectionArgs {
     /// Run hooks on all tracked files in the repository.
-    #[arg(short, long, conflicts_with_all = ["files", "glob", "from_ref", "to_ref"])]
+    #[arg(short, long, conflicts_with_all = ["files", "files_from", "glob", "from_ref", "to_ref"])]
     pub(crate) all_files: bool,
 
     /// Run hooks on the specified file paths.
@@ -486,6 +607,20 @@ pub(crate) struct FileSelectionArgs {
     ]
     pub(crate) files: Vec<String>,
 
+    /// Run hooks on the file paths listed in the specified file, or on stdin with `-`.
+    ///
+    /// Paths are separated by NUL characters if the list contains any, such as the output of
+    /// `git ls-files -z`, and by newlines otherwise. They are resolved like `--files` paths, without
+    /// the command line length limit, and can be combined with `--files`, `--glob` and
+    /// `--directory`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["all_files", "from_ref", "to_ref"],
+        value_hint = ValueHint::FilePath
+    )]
+    pub(crate) files_from: Option<PathBuf>,
+
     /// Run hooks on tracked files matching the specified glob pattern.
     ///
     /// Patterns are matched against paths relative to the current working directory after applying
@@ -513,6 +648,9 @@ pub(crate) struct FileSelectionArgs {
 
     /// The original ref in a `<from_ref>...<to_ref>` diff expression.
     /// Files changed in this diff will be run through the hooks.
+    ///
+    /// Accepts any revision Git understands, such as `origin/main` or `@{upstream}`, or a whole
+    /// `<from_ref>...<to_ref>` range like `main...HEAD`.
     #[arg(short = 's', long, alias = "source", value_hint = ValueHint::Other)]
     pub(crate) from_ref: Option<String>,
 
@@ -529,8 +667,21 @@ pub(crate) struct FileSelectionArgs {
     pub(crate) to_ref: Option<String>,
 
     /// Run hooks against the last commit. Equivalent to `--from-ref HEAD~1 --to-ref HEAD`.
-    #[arg(long, conflicts_with_all = ["all_files", "files", "glob", "directory", "from_ref", "to_ref"])]
+    #[arg(long, conflicts_with_all = ["all_files", "files", "files_from", "glob", "directory", "from_ref", "to_ref"])]
     pub(crate) last_commit: bool,
+
+    /// Run hooks on files changed since the specified revision, including uncommitted changes.
+    ///
+    /// Files are compared between the working tree and the merge base of the revision and `HEAD`,
+    /// so `--since main` on a feature branch selects the files the branch changed, whether they
+    /// are committed, staged, unstaged, or untracked. Unstaged changes are not stashed.
+    #[arg(
+        long,
+        value_name = "REV",
+        conflicts_with_all = ["all_files", "files", "files_from", "glob", "directory", "from_ref", "to_ref", "last_commit"],
+        value_hint = ValueHint::Other
+    )]
+    pub(crate) since: Option<String>,
 }
 
 impl From<FileSelectionArgs> for run::FileSelection {
@@ -538,13 +689,19 @@ impl From<FileSelectionArgs> for run::FileSelection {
         let FileSelectionArgs {
             all_files,
             files,
+            files_from,
             glob,
             directory,
             from_ref,
             to_ref,
             last_commit,
+            since,
         } = args;
 
+        if let Some(rev) = since {
+            return Self::Since { rev };
+        }
+
         if last_commit {
             return Self::Diff {
                 from_ref: "HEAD~1".to_string(),
@@ -557,9 +714,10 @@ impl From<FileSelectionArgs> for run::FileSelection {
             refs => refs,
         };
 
-        if !files.is_empty() || !glob.is_empty() || !directory.is_empty() {
+        if !files.is_empty() || files_from.is_some() || !glob.is_empty() || !directory.is_empty() {
             return Self::Explicit {
                 files,
+                files_from,
                 globs: glob,
                 directories: directory,
             };
@@ -623,14 +781,76 @@ pub(crate) struct RunOptions {
     #[arg(long, hide = true, overrides_with = "fail_fast")]
     pub(crate) no_fail_fast: bool,
 
+    /// Report hook failures as usual, but always exit with status code 0.
+    ///
+    /// Useful for introducing new hooks without blocking commits. To do this for some hooks only,
+    /// set `advisory: true` on them instead.
+    #[arg(long)]
+    pub(crate) exit_zero: bool,
+
     /// Do not run the hooks, but print the hooks that would have been run.
     #[arg(long)]
     pub(crate) dry_run: bool,
 
+    /// Write a GitHub Actions job summary and fold hook output into log groups.
+    ///
+    /// Appends a markdown table of every hook, with durations and failure output, to the file
+    /// named by `GITHUB_STEP_SUMMARY`, and wraps each hook's output in `::group::` markers.
+    #[arg(long)]
+    pub(crate) github_summary: bool,
+
+    /// Write a machine-readable report of the run to a file, as `<format>=<path>`.
+    ///
+    /// The report lists every hook with its project, status, duration, exit code, number of
+    /// files, and whether it modified files. Supported formats are `json`, and `junit` for
+    /// JUnit XML with one test case per hook that CI systems such as GitLab and Jenkins render
+    /// as test results.
+    ///
+    /// Can be specified multiple times.
+    #[arg(long = "report", value_name = "FORMAT=PATH", value_parser = parse_run_report)]
+    pub(crate) reports: Vec<RunReport>,
+
     #[command(flatten)]
     pub(crate) extra: RunExtraArgs,
 }
 
+/// The format of a `prek run --report` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunReportFormat {
+    Json,
+    Junit,
+}
+
+/// A report requested with `prek run --report <format>=<path>`.
+#[derive(Debug, Clone)]
+pub(crate) struct RunReport {
+    pub(crate) format: RunReportFormat,
+    pub(crate) path: PathBuf,
+}
+
+fn parse_run_report(value: &str) -> Result<RunReport, String> {
+    let Some((format, path)) = value.split_once('=') else {
+        return Err("expected `<format>=<path>`".to_string());
+    };
+    if path.is_empty() {
+        return Err("path must not be empty".to_string());
+    }
+
+    let format = match format {
+        "json" => RunReportFormat::Json,
+        "junit" => RunReportFormat::Junit,
+        _ => {
+            return Err(format!(
+                "unknown report format `{format}`, expected `json` or `junit`"
+            ));
+        }
+    };
+    Ok(RunReport {
+        format,
+        path: expand_tilde(PathBuf::from(path)),
+    })
+}
+
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct RunArgs {
     #[command(flatten)]
@@ -660,6 +880,55 @@ pub(crate) struct RunArgs {
     /// Can be specified multiple times. Exclusion wins over inclusion.
     #[arg(long = "no-group", value_name = "GROUP")]
     pub(crate) no_groups: Vec<String>,
+
+    /// Run only hooks that come from the specified repository.
+    ///
+    /// Accepts the full repo URL, or its trailing path such as `pre-commit-hooks` or
+    /// `pre-commit/pre-commit-hooks`. Use `local`, `meta` or `builtin` to select hooks
+    /// from those repos. Remote repos that are not selected are not cloned.
+    ///
+    /// Can be specified multiple times.
+    #[arg(long = "repo", value_name = "REPO")]
+    pub(crate) repos: Vec<String>,
+
+    /// Treat the whole workspace as a single project.
+    ///
+    /// Hooks configured in the workspace root run once over the matching files of every project,
+    /// including orphan projects, and the same hooks of nested projects are not run. Useful for
+    /// workspace-wide tools like `typos`, e.g. `prek run typos --all-files --flatten`.
+    #[arg(long)]
+    pub(crate) flatten: bool,
+
+    /// Run identical remote hooks of several projects only once.
+    ///
+    /// Hooks from the same repository and rev, with the same id, arguments and environment, run
+    /// once from the repository root over the files of all their projects. Each project reports
+    /// the output lines that name its files.
+    #[arg(long)]
+    pub(crate) dedup_hooks: bool,
+
+    /// With `--all-files`, only check the files changed since each hook last passed.
+    ///
+    /// A hook that passed an earlier `--all-files --incremental` run is only given the files
+    /// changed since. It checks every file again when its config, environment, `args`, common
+    /// tool config files or the files named in its `args` change, or when prek is upgraded.
+    ///
+    /// Cannot be combined with `--record` or `--replay`, which need every hook command to run.
+    #[arg(long, conflicts_with_all = ["record", "replay"])]
+    pub(crate) incremental: bool,
+
+    /// Record the commands hooks run into a directory, with their output and exit code.
+    ///
+    /// A recording can be replayed with `--replay` to reproduce the run without executing
+    /// any hook, for example to share a bug reproduction.
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath, conflicts_with = "replay")]
+    pub(crate) record: Option<PathBuf>,
+
+    /// Answer hook commands from a recording made with `--record` instead of running them.
+    ///
+    /// Hook environments are still installed, and replayed hooks do not modify any file.
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub(crate) replay: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Default, Args)]
@@ -685,6 +954,149 @@ pub(crate) struct TryRepoArgs {
     pub(crate) stage: Option<Stage>,
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct CompareArgs {
+    /// Path to the `pre-commit` executable, otherwise it is looked up in `PATH`.
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    pub(crate) pre_commit: Option<PathBuf>,
+
+    /// Also run every hook on its own and compare the files each hook modified.
+    ///
+    /// Each hook runs on fresh clones of the repository, so this takes as long as running
+    /// every hook separately with both tools.
+    #[arg(long)]
+    pub(crate) per_hook: bool,
+
+    /// Arguments passed to `run` of both tools, e.g. `prek compare -- --all-files`.
+    #[arg(last = true, value_name = "RUN_ARGS")]
+    pub(crate) run_args: Vec<OsString>,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct ExportConfigArgs {
+    /// Freeze the `rev` of every remote repository to the commit SHA it resolves to.
+    ///
+    /// Useful for archiving exactly which hooks were enforced for a given release.
+    #[arg(long)]
+    pub(crate) resolved: bool,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct SbomArgs {
+    /// The document format.
+    #[arg(long, value_enum, default_value_t = SbomFormat::Cyclonedx)]
+    pub(crate) format: SbomFormat,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct HelpArgs {
+    /// The topic or command to show help for, e.g. `selectors` or `cache clean`.
+    #[arg(value_name = "TOPIC|COMMAND")]
+    pub(crate) topic: Vec<String>,
+
+    /// Print a man page in roff format instead.
+    ///
+    /// For example, `prek help --man run > prek-run.1`, or `prek help --man` for the `prek`
+    /// command itself.
+    #[arg(long)]
+    pub(crate) man: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ServerHookArgs {
+    /// The stage to run hooks for.
+    #[arg(long, value_enum, default_value_t = ServerHookStage::PrePush)]
+    pub(crate) stage: ServerHookStage,
+
+    /// The output format.
+    ///
+    /// `json` prints the outcome of every ref, including the output of its hooks.
+    #[arg(long, value_enum, default_value_t = ServerHookOutputFormat::Text)]
+    pub(crate) output_format: ServerHookOutputFormat,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct RenderHookArgs {
+    /// Include the specified hooks or projects.
+    ///
+    /// Supports flexible selector syntax:
+    ///
+    /// - `hook-id`: Run all hooks with the specified ID across all projects
+    ///
+    /// - `project-path/`: Run all hooks from the specified project
+    ///
+    /// - `project-path:hook-id`: Run only the specified hook from the specified project
+    ///
+    /// Can be specified multiple times to select multiple hooks/projects.
+    #[arg(
+        value_name = "HOOK|PROJECT",
+        required = true,
+        value_hint = ValueHint::Other,
+        add = ArgValueCompleter::new(selector_completer)
+    )]
+    pub(crate) includes: Vec<String>,
+
+    /// Skip the specified hooks or projects.
+    ///
+    /// Supports flexible selector syntax:
+    ///
+    /// - `hook-id`: Skip all hooks with the specified ID across all projects
+    ///
+    /// - `project-path/`: Skip all hooks from the specified project
+    ///
+    /// - `project-path:hook-id`: Skip only the specified hook from the specified project
+    ///
+    /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
+    pub(crate) skips: Vec<String>,
+
+    /// Sample files to render the hook with.
+    ///
+    /// Files are filtered with the hook's `files`, `exclude`, and `types` settings
+    /// before being split into batches.
+    #[arg(long, num_args = 1.., value_hint = ValueHint::AnyPath)]
+    pub(crate) files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct PlanArgs {
+    /// Include the specified hooks or projects.
+    ///
+    /// Supports flexible selector syntax:
+    ///
+    /// - `hook-id`: Run all hooks with the specified ID across all projects
+    ///
+    /// - `project-path/`: Run all hooks from the specified project
+    ///
+    /// - `project-path:hook-id`: Run only the specified hook from the specified project
+    ///
+    /// Can be specified multiple times to select multiple hooks/projects.
+    #[arg(
+        value_name = "HOOK|PROJECT",
+        value_hint = ValueHint::Other,
+        add = ArgValueCompleter::new(selector_completer)
+    )]
+    pub(crate) includes: Vec<String>,
+
+    /// Skip the specified hooks or projects.
+    ///
+    /// Supports flexible selector syntax:
+    ///
+    /// - `hook-id`: Skip all hooks with the specified ID across all projects
+    ///
+    /// - `project-path/`: Skip all hooks from the specified project
+    ///
+    /// - `project-path:hook-id`: Skip only the specified hook from the specified project
+    ///
+    /// Can be specified multiple times. Also accepts `PREK_SKIP` or `SKIP` environment variables (comma-delimited).
+    #[arg(long = "skip", value_name = "HOOK|PROJECT", add = ArgValueCompleter::new(selector_completer))]
+    pub(crate) skips: Vec<String>,
+
+    /// The stage to plan the run for.
+    #[arg(long, value_enum, alias = "hook-stage", default_value_t = Stage::PreCommit)]
+    pub(crate) stage: Stage,
+}
+
 #[derive(Debug, Clone, Copy, clap::ValueEnum, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub(crate) enum ListOutputFormat {
@@ -701,6 +1113,64 @@ pub(crate) enum IdentifyOutputFormat {
     Json,
 }
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum VersionOutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum PrepareHooksOutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default)]
+pub(crate) enum SbomFormat {
+    /// CycloneDX 1.5 JSON.
+    #[default]
+    Cyclonedx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default)]
+pub(crate) enum ServerHookStage {
+    #[default]
+    PrePush,
+    Manual,
+}
+
+impl From<ServerHookStage> for Stage {
+    fn from(stage: ServerHookStage) -> Self {
+        match stage {
+            ServerHookStage::PrePush => Stage::PrePush,
+            ServerHookStage::Manual => Stage::Manual,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ServerHookOutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum UpdateOutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct ListBuiltinsArgs {
     /// The output format.
@@ -760,11 +1230,47 @@ pub(crate) struct ListArgs {
     /// Show only hooks that are implemented in the specified language.
     #[arg(long, value_enum)]
     pub(crate) language: Option<Language>,
+    /// Show only hooks matching a `<key>=<value>` filter. This option may be specified multiple times.
+    ///
+    /// Supported keys are `language`, `stage`, and `repo` (a repository URL, `local`, `meta`, or `builtin`).
+    /// For example, use `--filter language=python` to only show Python hooks.
+    #[arg(long = "filter", value_name = "KEY=VALUE", value_parser = parse_list_filter)]
+    pub(crate) filters: Vec<ListFilter>,
+    /// Show hooks as a tree grouped by project and repository, with their install state.
+    #[arg(long)]
+    pub(crate) tree: bool,
     /// The output format.
     #[arg(long, value_enum, default_value_t = ListOutputFormat::Text)]
     pub(crate) output_format: ListOutputFormat,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) enum ListFilter {
+    Language(Language),
+    Stage(Stage),
+    Repo(String),
+}
+
+fn parse_list_filter(value: &str) -> Result<ListFilter, String> {
+    let Some((key, value)) = value.split_once('=') else {
+        return Err("expected `<key>=<value>`".to_string());
+    };
+    if value.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+
+    match key {
+        "language" => {
+            <Language as clap::ValueEnum>::from_str(value, true).map(ListFilter::Language)
+        }
+        "stage" => <Stage as clap::ValueEnum>::from_str(value, true).map(ListFilter::Stage),
+        "repo" => Ok(ListFilter::Repo(value.to_string())),
+        _ => Err(format!(
+            "unknown filter key `{key}`, expected one of `language`, `stage`, `repo`"
+        )),
+    }
+}
+
 #[derive(Debug, Clone, Default, Args)]
 pub(crate) struct IdentifyArgs {
     /// The path(s) to the file(s) to identify.
@@ -778,10 +1284,44 @@ pub(crate) struct IdentifyArgs {
 #[derive(Debug, Args)]
 pub(crate) struct ValidateConfigArgs {
     /// The path to the configuration file.
+    ///
+    /// Defaults to the configuration files of all projects in the workspace.
     #[arg(value_name = "CONFIG")]
     pub(crate) configs: Vec<PathBuf>,
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct ConfigNamespace {
+    #[command(subcommand)]
+    pub(crate) command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum ConfigCommand {
+    /// Lint the configuration files of every project in the workspace.
+    ///
+    /// Goes beyond `validate-config` by reporting repositories with mutable `rev` values,
+    /// deprecated keys and stage names, unknown languages, hooks configured in both a project
+    /// and one of its parents, and hooks that match no file in the repository. Hooks of remote
+    /// repositories are only checked for matching files once they are cloned, so linting never
+    /// needs the network.
+    ///
+    /// Exits with a non-zero status if any issue remains.
+    Lint(ConfigLintArgs),
+    /// Print the JSON Schema of the configuration file.
+    ///
+    /// The schema matches the configuration types of this prek build, so it includes every
+    /// prek-specific key. Point your editor at it for completion and validation.
+    Schema,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct ConfigLintArgs {
+    /// Apply safe rewrites, such as renaming deprecated keys and stage names, in place.
+    #[arg(long)]
+    pub(crate) fix: bool,
+}
+
 #[derive(Debug, Args)]
 pub(crate) struct ValidateManifestArgs {
     /// The path to the manifest file.
@@ -809,6 +1349,30 @@ pub(crate) struct SampleConfigArgs {
     pub(crate) format: Option<SampleConfigFormat>,
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct InitArgs {
+    /// Accept all suggested hooks without prompting.
+    #[arg(short, long)]
+    pub(crate) yes: bool,
+
+    /// Select the configuration file format.
+    ///
+    /// Defaults to `.pre-commit-config.yaml` unless `--format toml` is set,
+    /// which uses `prek.toml`.
+    #[arg(long, value_enum)]
+    pub(crate) format: Option<SampleConfigFormat>,
+
+    /// Do not install the Git shims after writing the configuration file.
+    #[arg(long)]
+    pub(crate) no_install: bool,
+
+    /// Run all hooks against all files after writing the configuration file.
+    ///
+    /// Without `--yes`, prek asks whether to run them.
+    #[arg(long)]
+    pub(crate) run: bool,
+}
+
 #[derive(Debug, Copy, Clone, clap::ValueEnum)]
 pub(crate) enum SampleConfigFormat {
     Yaml,
@@ -909,6 +1473,12 @@ pub(crate) struct UpdateArgs {
     /// Valid values are `0` through `255`; `0` disables this check.
     #[arg(long, value_name = "DAYS", conflicts_with = "bleeding_edge")]
     pub(crate) cooldown_days: Option<u8>,
+    /// The output format.
+    ///
+    /// The `json` and `yaml` formats report the current rev, the latest rev, and the kind of change
+    /// (`major`, `minor`, `patch`, `unknown`, or `none`) for each repository, and imply `--dry-run`.
+    #[arg(long, value_enum, default_value_t = UpdateOutputFormat::Text)]
+    pub(crate) output_format: UpdateOutputFormat,
 }
 
 #[derive(Clone, Debug)]
@@ -983,6 +1553,41 @@ pub(crate) struct CacheNamespace {
     pub(crate) command: CacheCommand,
 }
 
+#[derive(Debug, Args)]
+pub(crate) struct EnvNamespace {
+    #[command(subcommand)]
+    pub(crate) command: EnvCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum EnvCommand {
+    /// Link the environment of a hook into the project.
+    ///
+    /// Creates a symlink (a junction on Windows if symlinks are not permitted) to the hook's
+    /// environment, so IDEs can use the same interpreter and tools as the hook. The environment
+    /// is installed first if needed, and the link is updated whenever the environment is rebuilt.
+    ///
+    /// Links are removed by `prek uninstall`.
+    Link(EnvLinkArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct EnvLinkArgs {
+    /// The hook whose environment to link, as `hook-id` or `project-path:hook-id`.
+    #[arg(
+        value_name = "HOOK",
+        value_hint = ValueHint::Other,
+        add = ArgValueCompleter::new(selector_completer)
+    )]
+    pub(crate) hook: String,
+
+    /// Where to create the link.
+    ///
+    /// Defaults to `.prek/envs/<hook-id>` in the hook's project.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::DirPath)]
+    pub(crate) path: Option<PathBuf>,
+}
+
 #[derive(Debug, Args)]
 pub(crate) struct UtilNamespace {
     #[command(subcommand)]
@@ -1032,6 +1637,8 @@ pub(crate) enum CacheCommand {
     Clean,
     /// Show the size of the prek cache.
     Size(SizeArgs),
+    /// Remove hook environments and toolchains that have not been used recently.
+    Prune(CachePruneArgs),
 }
 
 #[derive(Args, Debug)]
@@ -1039,6 +1646,51 @@ pub struct SizeArgs {
     /// Display the cache size in human-readable format (e.g., `1.2 GiB` instead of raw bytes).
     #[arg(long = "human", short = 'H', alias = "human-readable")]
     pub(crate) human: bool,
+
+    /// Show the size of each cache bucket (repos, hooks, tools, and cache) and the total.
+    #[arg(long)]
+    pub(crate) buckets: bool,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct CachePruneArgs {
+    /// Remove entries that have not been used for this long, such as `30d`, `12h`, or `2w`.
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    pub(crate) older_than: Duration,
+
+    /// Print what would be removed, but do not delete anything.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+/// Parse an age such as `30d` into a duration.
+///
+/// Supported units are `s`, `m`, `h`, `d`, and `w`.
+fn parse_age(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| "missing unit, expected one of `s`, `m`, `h`, `d`, `w`".to_string())?;
+    let (number, unit) = value.split_at(unit_start);
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| "expected a number followed by a unit, such as `30d`".to_string())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown unit `{unit}`, expected one of `s`, `m`, `h`, `d`, `w`"
+            ));
+        }
+    };
+    number
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| "age is too large".to_string())
 }
 
 #[derive(Debug, Args)]
@@ -1101,9 +1753,22 @@ pub(crate) struct InitTemplateDirArgs {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use clap::Parser;
 
-    use super::Cli;
+    use super::{Cli, parse_age};
+
+    #[test]
+    fn parse_age_accepts_units() {
+        assert_eq!(parse_age("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_age("12h"), Ok(Duration::from_hours(12)));
+        assert_eq!(parse_age("30d"), Ok(Duration::from_hours(30 * 24)));
+        assert_eq!(parse_age("2w"), Ok(Duration::from_hours(14 * 24)));
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+    }
 
     #[test]
     fn update_rejects_invalid_repo_tag_pattern_during_cli_parsing() {
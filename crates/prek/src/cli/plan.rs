@@ -0,0 +1,121 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use owo_colors::OwoColorize;
+
+use crate::cli::ExitStatus;
+use crate::cli::reporter::HookInitReporter;
+use crate::cli::run::Selectors;
+use crate::config::Stage;
+use crate::fs::CWD;
+use crate::hook::Hook;
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::workspace::{HookInitFilters, Project, Refresh, Workspace};
+
+/// Print the order `prek run` would run hooks in, without running anything.
+pub(crate) async fn plan(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: Vec<String>,
+    skips: Vec<String>,
+    stage: Stage,
+    refresh: Refresh,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
+    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), &refresh)?;
+
+    let reporter = HookInitReporter::new(printer);
+    let lock = store.lock_async().await?;
+    let hooks = workspace
+        .init_hooks(
+            store,
+            HookInitFilters::new(Some(&selectors), None),
+            Some(&reporter),
+        )
+        .await
+        .context("Failed to init hooks")?;
+    drop(lock);
+
+    let hooks: Vec<_> = hooks
+        .into_iter()
+        .filter(|hook| selectors.matches_hook(hook))
+        .filter(|hook| hook.stages.contains(stage))
+        .collect();
+    selectors.report_unused();
+
+    if hooks.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}: No hooks found for stage `{stage}`",
+            "error".red().bold(),
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let mut first = true;
+    // Projects are run from the deepest to the shallowest, in the same order as the workspace.
+    for project in workspace.all_projects() {
+        let mut project_hooks: Vec<&Hook> = hooks
+            .iter()
+            .filter(|hook| hook.project().idx() == project.idx())
+            .collect();
+        if project_hooks.is_empty() {
+            continue;
+        }
+        project_hooks.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.idx.cmp(&b.idx)));
+
+        if !first {
+            writeln!(printer.stdout())?;
+        }
+        first = false;
+        write_project(project, &project_hooks, printer)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Render the priority groups of one project as a tree.
+fn write_project(project: &Project, hooks: &[&Hook], printer: Printer) -> anyhow::Result<()> {
+    let groups = hooks
+        .chunk_by(|a, b| a.priority == b.priority)
+        .collect::<Vec<_>>();
+
+    writeln!(printer.stdout(), "{}", project.to_string().bold())?;
+    for (group_pos, group) in groups.iter().enumerate() {
+        let last_group = group_pos + 1 == groups.len();
+        let (branch, indent) = if last_group {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        writeln!(
+            printer.stdout(),
+            "{branch}{}",
+            format!("priority {}", group[0].priority).cyan()
+        )?;
+
+        for (hook_pos, hook) in group.iter().enumerate() {
+            let branch = if hook_pos + 1 == group.len() {
+                "└── "
+            } else {
+                "├── "
+            };
+            if hook.require_serial_project {
+                writeln!(
+                    printer.stdout(),
+                    "{indent}{branch}{} {}",
+                    hook.id,
+                    "(runs alone)".dimmed()
+                )?;
+            } else {
+                writeln!(printer.stdout(), "{indent}{branch}{}", hook.id)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use prek_consts::prepend_paths;
+use prek_identify::tags_from_path;
+
+use crate::cli::ExitStatus;
+use crate::cli::reporter::HookInitReporter;
+use crate::cli::run::{HookFileFilter, InstallCache, Selectors};
+use crate::config::{Language, PassFilenames};
+use crate::fs;
+use crate::fs::CWD;
+use crate::hook::{Hook, Repo};
+use crate::printer::Printer;
+use crate::run::partition_filenames;
+use crate::store::Store;
+use crate::workspace::{HookInitFilters, Refresh, Workspace};
+
+/// Print the command lines a hook would be run with, without running it.
+pub(crate) async fn render_hook(
+    store: &Store,
+    config: Option<PathBuf>,
+    includes: Vec<String>,
+    skips: Vec<String>,
+    files: Vec<PathBuf>,
+    refresh: Refresh,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
+    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), &refresh)?;
+
+    let reporter = HookInitReporter::new(printer);
+    let lock = store.lock_async().await?;
+    let hooks = workspace
+        .init_hooks(
+            store,
+            HookInitFilters::new(Some(&selectors), None),
+            Some(&reporter),
+        )
+        .await
+        .context("Failed to init hooks")?;
+
+    let hooks: Vec<_> = hooks
+        .into_iter()
+        .filter(|hook| selectors.matches_hook(hook))
+        .collect();
+    selectors.report_unused();
+
+    if hooks.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}: No hooks found after filtering with the given selectors",
+            "error".red().bold(),
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let files = files
+        .into_iter()
+        .map(|file| std::path::absolute(&file).unwrap_or(file))
+        .collect::<Vec<_>>();
+
+    let install_cache = InstallCache::new();
+    for (idx, hook) in hooks.iter().enumerate() {
+        if idx > 0 {
+            writeln!(printer.stdout_important())?;
+        }
+        render(store, &install_cache, hook, &files, printer).await?;
+    }
+    drop(lock);
+
+    Ok(ExitStatus::Success)
+}
+
+async fn render(
+    store: &Store,
+    install_cache: &InstallCache,
+    hook: &Hook,
+    files: &[PathBuf],
+    printer: Printer,
+) -> anyhow::Result<()> {
+    let mut stdout = printer.stdout_important();
+
+    writeln!(stdout, "{}", hook.full_id().bold())?;
+    writeln!(
+        stdout,
+        "  {} {}",
+        "Language:".bold().cyan(),
+        hook.language.as_ref()
+    )?;
+    writeln!(
+        stdout,
+        "  {} {}",
+        "Working directory:".bold().cyan(),
+        hook.run_dir().display()
+    )?;
+
+    if hook.env.is_empty() {
+        writeln!(stdout, "  {} (none)", "Environment:".bold().cyan())?;
+    } else {
+        writeln!(stdout, "  {}", "Environment:".bold().cyan())?;
+        for (key, value) in hook.env.iter().sorted() {
+            writeln!(stdout, "    {key}={value}")?;
+        }
+    }
+
+    let mut filenames = matching_filenames(hook, files);
+    if !files.is_empty() {
+        writeln!(
+            stdout,
+            "  {} {} of {} files",
+            "Matched:".bold().cyan(),
+            filenames.len(),
+            files.len(),
+        )?;
+    }
+    if hook.files_from_repo_root {
+        let prefix = hook.project_path_from_repo_root();
+        for filename in &mut filenames {
+            *filename = prefix.join(&*filename);
+        }
+    }
+
+    if matches!(hook.repo(), Repo::Meta { .. } | Repo::Builtin { .. }) {
+        writeln!(stdout, "  {} (runs in-process)", "Batches:".bold().cyan())?;
+        return Ok(());
+    }
+
+    let (filenames, pass_filenames) = match hook.pass_filenames {
+        PassFilenames::None => (Vec::new(), "none".to_string()),
+        PassFilenames::All => (filenames, "all".to_string()),
+        PassFilenames::Limited(n) => (filenames, format!("at most {n} per batch")),
+    };
+    writeln!(
+        stdout,
+        "  {} {pass_filenames}",
+        "Pass filenames:".bold().cyan()
+    )?;
+    let filenames = filenames.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+
+    // Resolve the entry against the hook environment like the language backends do, or against
+    // the current `PATH` if the environment is not installed yet.
+    let env_path = match install_cache.installed_info(store, hook).await {
+        Some(info) => {
+            let paths = hook.language.setup_paths(&info);
+            let paths = paths.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+            Some(prepend_paths(&paths).context("Failed to join PATH")?)
+        }
+        None => {
+            if hook.needs_install_env() {
+                writeln!(
+                    stdout,
+                    "  {} not installed, resolving `entry` with the current `PATH`",
+                    "Hook environment:".bold().cyan()
+                )?;
+            }
+            None
+        }
+    };
+    let entry = if hook.language == Language::Script {
+        let repo_path = hook.repo_path().unwrap_or(hook.work_dir());
+        hook.entry
+            .resolve_script(repo_path, env_path.as_deref(), store)?
+    } else {
+        hook.entry.resolve(env_path.as_deref(), store)?
+    };
+    let batches = partition_filenames(hook, entry.argv(), &filenames)?;
+
+    writeln!(stdout, "  {} {}", "Batches:".bold().cyan(), batches.len())?;
+    for (idx, batch) in batches.iter().enumerate() {
+        let argv = entry
+            .argv()
+            .iter()
+            .cloned()
+            .chain(hook.args.iter().map(OsString::from))
+            .chain(batch.iter().map(|file| file.as_os_str().to_owned()))
+            .collect::<Vec<_>>();
+        writeln!(
+            stdout,
+            "    {} {}",
+            format!("[{}]", idx + 1).dimmed(),
+            render_argv(&argv)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Return the files (relative to the hook's project) that pass the hook's filters.
+fn matching_filenames(hook: &Hook, files: &[PathBuf]) -> Vec<PathBuf> {
+    let filter = HookFileFilter::new(hook);
+
+    files
+        .iter()
+        .filter_map(|file| {
+            let relative = file.strip_prefix(hook.work_dir()).ok()?;
+            let relative = fs::normalize_path(relative.to_path_buf());
+            if !filter.matches_filename(&relative) {
+                return None;
+            }
+            let tags = tags_from_path(file).ok();
+            filter.matches_tags(tags.as_ref()).then_some(relative)
+        })
+        .collect()
+}
+
+/// Render the argv as a shell-quoted command line.
+fn render_argv(argv: &[OsString]) -> String {
+    argv.iter()
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            shlex::try_quote(&arg).map_or_else(|_| arg.to_string(), |quoted| quoted.into_owned())
+        })
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_argv_quotes_arguments() {
+        let argv = [
+            OsString::from("ruff"),
+            OsString::from("--fix"),
+            OsString::from("src/my file.py"),
+        ];
+        assert_eq!(render_argv(&argv), "ruff --fix 'src/my file.py'");
+    }
+
+    #[test]
+    fn render_argv_keeps_plain_paths() {
+        let argv = [OsString::from("echo"), OsString::from("a/b.txt")];
+        assert_eq!(render_argv(&argv), "echo a/b.txt");
+    }
+}
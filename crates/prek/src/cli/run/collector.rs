@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::debug;
+
+use crate::cli::run::filter::{FileSelection, collect_files_for_selection};
+use crate::config::FileCollectorConfig;
+use crate::process::Cmd;
+use crate::{fs, jj};
+
+/// A source of the files to run hooks on.
+///
+/// The default collector asks Git for staged, changed or tracked files. Other collectors can
+/// answer the same question from a build system or another VCS, which often knows the affected
+/// files of a change more precisely than `git diff`.
+#[async_trait::async_trait(?Send)]
+pub(crate) trait FileCollector {
+    /// Return the selected file paths, relative to the git root.
+    async fn collect(
+        &self,
+        git_root: &Path,
+        workspace_root: &Path,
+        selection: FileSelection,
+    ) -> Result<Vec<PathBuf>>;
+}
+
+/// Collect files with Git.
+pub(crate) struct GitFileCollector;
+
+#[async_trait::async_trait(?Send)]
+impl FileCollector for GitFileCollector {
+    async fn collect(
+        &self,
+        git_root: &Path,
+        workspace_root: &Path,
+        selection: FileSelection,
+    ) -> Result<Vec<PathBuf>> {
+        collect_files_for_selection(git_root, workspace_root, selection).await
+    }
+}
+
+/// Collect files by running the `file_collector` command of the workspace root config.
+///
+/// The command runs in the workspace root and is passed the selection as arguments:
+/// `staged`, `all`, or `diff <from_ref> <to_ref>`. It prints one path per line, relative to
+/// the workspace root. Explicit `--files`, `--glob` and `--directory` selections still go
+/// through Git, since the user has already named the files, and so does `--since`, which
+/// needs the working tree state.
+pub(crate) struct CommandFileCollector {
+    entry: Vec<String>,
+}
+
+impl CommandFileCollector {
+    pub(crate) fn new(config: &FileCollectorConfig) -> Result<Self> {
+        let entry = shlex::split(&config.entry)
+            .filter(|entry| !entry.is_empty())
+            .with_context(|| format!("Invalid `file_collector` entry: `{}`", config.entry))?;
+        Ok(Self { entry })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileCollector for CommandFileCollector {
+    async fn collect(
+        &self,
+        git_root: &Path,
+        workspace_root: &Path,
+        selection: FileSelection,
+    ) -> Result<Vec<PathBuf>> {
+        let args = match &selection {
+            FileSelection::Since { .. } | FileSelection::Explicit { .. } => None,
+            FileSelection::Default => Some(vec!["staged".to_string()]),
+            FileSelection::All { .. } => Some(vec!["all".to_string()]),
+            FileSelection::Diff { from_ref, to_ref } => {
+                Some(vec!["diff".to_string(), from_ref.clone(), to_ref.clone()])
+            }
+        };
+        let Some(args) = args else {
+            return GitFileCollector
+                .collect(git_root, workspace_root, selection)
+                .await;
+        };
+
+        let (program, entry_args) = self.entry.split_first().expect("entry is not empty");
+        let mut cmd = Cmd::new(program);
+        let output = cmd
+            .args(entry_args)
+            .args(&args)
+            .current_dir(workspace_root)
+            .check(true)
+            .output()
+            .await
+            .with_context(|| {
+                format!("Failed to run `file_collector` `{}`", self.entry.join(" "))
+            })?;
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("`file_collector` printed paths that are not valid UTF-8")?;
+        let relative_root = workspace_root
+            .strip_prefix(git_root)
+            .unwrap_or(Path::new(""));
+        let files = stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| fs::normalize_path(relative_root.join(line)))
+            .collect::<Vec<_>>();
+        debug!(
+            "Files from `file_collector` for `{}`: {}",
+            args.join(" "),
+            files.len()
+        );
+        Ok(files)
+    }
+}
+
+/// Collect files with `jj` in a Jujutsu repo colocated with Git.
+///
+/// A plain run checks the files changed in the working-copy change, since `jj` has no staging
+/// area, and `--all-files` checks the files `jj` tracks, including new ones Git does not know
+/// about yet. Diffs between refs and explicit selections go through Git, which shares the
+/// commits of a colocated repo.
+pub(crate) struct JujutsuFileCollector;
+
+#[async_trait::async_trait(?Send)]
+impl FileCollector for JujutsuFileCollector {
+    async fn collect(
+        &self,
+        git_root: &Path,
+        workspace_root: &Path,
+        selection: FileSelection,
+    ) -> Result<Vec<PathBuf>> {
+        let files = match selection {
+            FileSelection::Default => {
+                let files = jj::get_changed_files(git_root).await?;
+                debug!("Files changed in the working-copy change: {}", files.len());
+                files
+            }
+            FileSelection::All { .. } => {
+                let files = jj::ls_files(git_root).await?;
+                debug!("All files tracked by jj: {}", files.len());
+                files
+            }
+            FileSelection::Diff { .. }
+            | FileSelection::Since { .. }
+            | FileSelection::Explicit { .. } => {
+                return GitFileCollector
+                    .collect(git_root, workspace_root, selection)
+                    .await;
+            }
+        };
+        Ok(files.into_iter().map(fs::normalize_path).collect())
+    }
+}
@@ -0,0 +1,253 @@
+//! Deduplicated runs of identical remote hooks across projects, with `--dedup-hooks`.
+//!
+//! Hooks from the same remote repository and rev, with the same id, entry, arguments, environment
+//! variables and hook environment, are run once over the union of their projects' files, from
+//! the repository root. The output is then split back by file: each project reports the lines
+//! naming its own files, plus the lines that name no file at all.
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bstr::ByteSlice;
+use rustc_hash::{FxHashMap, FxHashSet};
+use seahash::SeaHasher;
+use tokio::sync::OnceCell;
+use tracing::debug;
+
+use crate::cli::run::run::hook_key;
+use crate::config::PassFilenames;
+use crate::hook::{Hook, InstalledHook, Repo};
+
+/// Groups of identical hooks from different projects that run once.
+pub(crate) struct HookDedup {
+    /// Group of each deduplicated hook, by project and hook index.
+    groups: FxHashMap<(usize, usize), usize>,
+    runs: Vec<DedupGroup>,
+}
+
+pub(crate) struct DedupGroup {
+    /// The identical hooks, in workspace order.
+    pub(crate) hooks: Vec<InstalledHook>,
+    /// The run shared by all hooks of the group, started by the first hook to get its turn.
+    pub(crate) run: OnceCell<SharedRun>,
+}
+
+impl HookDedup {
+    /// Group identical hooks, or return `None` if no hook is configured by several projects.
+    pub(crate) fn new(hooks: &[InstalledHook]) -> Option<Self> {
+        let mut by_fingerprint: FxHashMap<u64, Vec<InstalledHook>> = FxHashMap::default();
+        let mut order = Vec::new();
+        for hook in hooks {
+            let Some(fingerprint) = fingerprint(hook) else {
+                continue;
+            };
+            let members = by_fingerprint.entry(fingerprint).or_default();
+            if members.is_empty() {
+                order.push(fingerprint);
+            }
+            // The same hook configured twice in one project still runs twice.
+            if members
+                .iter()
+                .all(|member| member.project().idx() != hook.project().idx())
+            {
+                members.push(hook.clone());
+            }
+        }
+
+        let mut groups = FxHashMap::default();
+        let mut runs = Vec::new();
+        for fingerprint in order {
+            let members = by_fingerprint
+                .remove(&fingerprint)
+                .expect("fingerprint was recorded");
+            if members.len() < 2 {
+                continue;
+            }
+            debug!(
+                "Running hook `{}` once for {} projects",
+                members[0].id,
+                members.len()
+            );
+            for member in &members {
+                groups.insert(hook_key(member), runs.len());
+            }
+            runs.push(DedupGroup {
+                hooks: members,
+                run: OnceCell::new(),
+            });
+        }
+
+        (!runs.is_empty()).then_some(Self { groups, runs })
+    }
+
+    /// The group `hook` belongs to, if it is deduplicated.
+    pub(crate) fn group(&self, hook: &Hook) -> Option<&DedupGroup> {
+        self.groups
+            .get(&hook_key(hook))
+            .map(|&group| &self.runs[group])
+    }
+}
+
+/// Identify the hooks that can share a run, `None` for hooks that cannot be deduplicated.
+fn fingerprint(hook: &InstalledHook) -> Option<u64> {
+    let Repo::Remote { path, .. } = hook.repo() else {
+        return None;
+    };
+    // Hooks without filenames, or with a limit on them, would see different arguments.
+    if hook.pass_filenames != PassFilenames::All || hook.always_run {
+        return None;
+    }
+
+    let mut hasher = SeaHasher::new();
+    path.hash(&mut hasher);
+    hook.id.hash(&mut hasher);
+    hook.entry.raw().hash(&mut hasher);
+    hook.args.hash(&mut hasher);
+    let mut env: Vec<_> = hook.env.iter().collect();
+    env.sort_unstable();
+    env.hash(&mut hasher);
+    hook.language.hash(&mut hasher);
+    hook.env_path().hash(&mut hasher);
+    hook.require_serial.hash(&mut hasher);
+    hook.isolation.hash(&mut hasher);
+    hook.output_encoding
+        .map(encoding_rs::Encoding::name)
+        .hash(&mut hasher);
+    hook.success_exit_codes.hash(&mut hasher);
+    hook.warning_exit_codes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// The outcome of a deduplicated run.
+pub(crate) struct SharedRun {
+    pub(crate) exit_status: i32,
+    pub(crate) output: Vec<u8>,
+    pub(crate) duration: Duration,
+    /// Files passed to the run by each hook of the group, relative to the repository root.
+    pub(crate) files: Vec<FxHashSet<PathBuf>>,
+    /// Whether the run changed any file of each hook of the group.
+    pub(crate) modified: Vec<bool>,
+}
+
+/// The part of a shared run attributed to one hook of its group.
+pub(crate) struct AttributedRun {
+    pub(crate) exit_status: i32,
+    pub(crate) output: Vec<u8>,
+    pub(crate) modified_files: bool,
+}
+
+impl SharedRun {
+    /// Attribute the run to the hook at `member` in its group.
+    ///
+    /// A run that did not exit with one of `success_exit_codes` is attributed to the hook if its
+    /// output names one of the hook's files, or if it names no file at all and so cannot be told
+    /// apart. Otherwise the hook gets the first success code.
+    pub(crate) fn attribute(&self, member: usize, success_exit_codes: &[i32]) -> AttributedRun {
+        let mut owners: FxHashMap<String, Vec<usize>> = FxHashMap::default();
+        for (idx, files) in self.files.iter().enumerate() {
+            for file in files {
+                owners
+                    .entry(file.to_string_lossy().into_owned())
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        let mut output = Vec::new();
+        let mut names_any = false;
+        let mut names_member = false;
+        for line in self.output.lines_with_terminator() {
+            let named = named_members(&line.to_str_lossy(), &owners);
+            names_any |= !named.is_empty();
+            if named.is_empty() || named.contains(&member) {
+                names_member |= named.contains(&member);
+                output.extend_from_slice(line);
+            }
+        }
+
+        let passed = success_exit_codes.contains(&self.exit_status);
+        let attributed = !passed && (names_member || !names_any);
+        AttributedRun {
+            exit_status: if attributed {
+                self.exit_status
+            } else {
+                success_exit_codes.first().copied().unwrap_or(0)
+            },
+            output,
+            modified_files: self.modified[member],
+        }
+    }
+}
+
+/// The hooks whose files are named in an output line.
+fn named_members(line: &str, owners: &FxHashMap<String, Vec<usize>>) -> FxHashSet<usize> {
+    line.split(|c: char| c.is_whitespace() || matches!(c, ':' | '"' | '\'' | '`' | '(' | ')' | ','))
+        .filter_map(|token| owners.get(token.trim_start_matches("./")))
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Hash the content of `files`, to tell which files a shared run modified.
+pub(crate) fn hash_files(root: &Path, files: &FxHashSet<PathBuf>) -> FxHashMap<PathBuf, u64> {
+    files
+        .iter()
+        .map(|file| {
+            let hash = fs_err::read(root.join(file)).map_or(0, |content| seahash::hash(&content));
+            (file.clone(), hash)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attribute_output_by_file() {
+        let run = SharedRun {
+            exit_status: 2,
+            output: b"error: `teh` should be `the`\n  --> app/README.md:1:1\nwarning: slow\n"
+                .to_vec(),
+            duration: Duration::ZERO,
+            files: vec![
+                [PathBuf::from("app/README.md")].into_iter().collect(),
+                [PathBuf::from("lib/README.md")].into_iter().collect(),
+            ],
+            modified: vec![false, false],
+        };
+
+        let app = run.attribute(0, &[0]);
+        assert_eq!(app.exit_status, 2);
+        assert_eq!(
+            app.output.to_str_lossy(),
+            "error: `teh` should be `the`\n  --> app/README.md:1:1\nwarning: slow\n"
+        );
+
+        let lib = run.attribute(1, &[0]);
+        assert_eq!(lib.exit_status, 0);
+        assert_eq!(
+            lib.output.to_str_lossy(),
+            "error: `teh` should be `the`\nwarning: slow\n"
+        );
+    }
+
+    #[test]
+    fn attribute_unnamed_failure_to_every_hook() {
+        let run = SharedRun {
+            exit_status: 1,
+            output: b"something went wrong\n".to_vec(),
+            duration: Duration::ZERO,
+            files: vec![
+                [PathBuf::from("a.txt")].into_iter().collect(),
+                [PathBuf::from("b/b.txt")].into_iter().collect(),
+            ],
+            modified: vec![false, true],
+        };
+
+        assert_eq!(run.attribute(0, &[0]).exit_status, 1);
+        let second = run.attribute(1, &[0]);
+        assert_eq!(second.exit_status, 1);
+        assert!(second.modified_files);
+    }
+}
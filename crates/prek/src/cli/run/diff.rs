@@ -1,8 +1,10 @@
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
 use crate::git;
+use crate::git::StatusSnapshot;
 
 pub(super) struct DiffTracker<'a> {
     path: &'a Path,
@@ -10,16 +12,27 @@ pub(super) struct DiffTracker<'a> {
 }
 
 enum DiffBaseline {
-    Clean,
+    /// No tracked changes, with the untracked files captured before the first group that may
+    /// modify files.
+    Clean(Option<BTreeSet<PathBuf>>),
     Unknown,
-    Snapshot(Vec<u8>),
+    Snapshot(StatusSnapshot),
+}
+
+/// The changes made by a priority group.
+#[derive(Debug, Default)]
+pub(super) struct GroupChanges {
+    /// Whether any tracked file changed, or an untracked file was created or removed.
+    pub(super) modified: bool,
+    /// Untracked files created by the group, relative to the repository root.
+    pub(super) new_files: Vec<PathBuf>,
 }
 
 impl<'a> DiffTracker<'a> {
     pub(super) fn clean_baseline(path: &'a Path) -> Self {
         Self {
             path,
-            baseline: DiffBaseline::Clean,
+            baseline: DiffBaseline::Clean(None),
         }
     }
 
@@ -31,8 +44,19 @@ impl<'a> DiffTracker<'a> {
     }
 
     pub(super) async fn prepare_for_group(&mut self, may_modify_files: bool) -> Result<()> {
-        if may_modify_files && let DiffBaseline::Unknown = self.baseline {
-            self.baseline = DiffBaseline::Snapshot(git::get_diff(self.path).await?);
+        if !may_modify_files {
+            return Ok(());
+        }
+        match &mut self.baseline {
+            DiffBaseline::Unknown => {
+                self.baseline = DiffBaseline::Snapshot(git::status_snapshot(self.path).await?);
+            }
+            // `WorkTreeKeeper` does not stash untracked files, remember them so files created by
+            // hooks can be told apart.
+            DiffBaseline::Clean(untracked @ None) => {
+                *untracked = Some(git::untracked_files(self.path).await?);
+            }
+            DiffBaseline::Clean(Some(_)) | DiffBaseline::Snapshot(_) => {}
         }
         Ok(())
     }
@@ -41,42 +65,46 @@ impl<'a> DiffTracker<'a> {
         &mut self,
         may_modify_files: bool,
         all_skipped: bool,
-    ) -> Result<bool> {
+    ) -> Result<GroupChanges> {
         // Read-only groups and fully skipped groups cannot change files, so avoid
         // asking git about the working tree.
         if !may_modify_files || all_skipped {
-            return Ok(false);
+            return Ok(GroupChanges::default());
         }
 
         match &mut self.baseline {
-            DiffBaseline::Clean => {
-                // `WorkTreeKeeper` already removed unstaged changes. A quiet
-                // worktree check keeps the common no-op path cheap.
-                if !git::has_worktree_diff(self.path).await? {
-                    return Ok(false);
-                }
-                // `diff-files --quiet` is stat-based, so an in-place rewrite
-                // can look dirty even when the content is unchanged. Do a full
-                // diff here to ignore stat-only changes and reuse the content
-                // diff as the baseline if the hook really modified files.
-                let curr_diff = git::get_diff(self.path).await?;
-                if curr_diff.is_empty() {
-                    return Ok(false);
+            DiffBaseline::Clean(untracked) => {
+                // `WorkTreeKeeper` already removed unstaged changes, so any
+                // change reported by `git status` was made by this group.
+                // `git status` compares content when stat data is stale, so
+                // in-place rewrites with identical content are not reported.
+                let curr_status = git::status_snapshot(self.path).await?;
+                let prev_untracked = untracked.take().unwrap_or_default();
+                if curr_status.is_clean() && *curr_status.untracked() == prev_untracked {
+                    *untracked = Some(prev_untracked);
+                    return Ok(GroupChanges::default());
                 }
 
                 // Capture the dirty state after this group so later groups can
-                // compare against the exact diff left by previous hooks.
-                self.baseline = DiffBaseline::Snapshot(curr_diff);
-                Ok(true)
+                // compare against the exact state left by previous hooks.
+                let new_files = new_files(&prev_untracked, &curr_status);
+                self.baseline = DiffBaseline::Snapshot(curr_status);
+                Ok(GroupChanges {
+                    modified: true,
+                    new_files,
+                })
             }
-            DiffBaseline::Snapshot(prev_diff) => {
+            DiffBaseline::Snapshot(prev_status) => {
                 // Unknown initial state, `--all-files`, and later dirty groups
-                // need a full before/after diff comparison to avoid confusing
+                // need a full before/after comparison to avoid confusing
                 // pre-existing user changes with hook changes.
-                let curr_diff = git::get_diff(self.path).await?;
-                let modified = curr_diff != *prev_diff;
-                *prev_diff = curr_diff;
-                Ok(modified)
+                let curr_status = git::status_snapshot(self.path).await?;
+                let changes = GroupChanges {
+                    modified: curr_status != *prev_status,
+                    new_files: new_files(prev_status.untracked(), &curr_status),
+                };
+                *prev_status = curr_status;
+                Ok(changes)
             }
             DiffBaseline::Unknown => {
                 unreachable!("diff baseline must be captured before hooks can modify files")
@@ -84,3 +112,11 @@ impl<'a> DiffTracker<'a> {
         }
     }
 }
+
+fn new_files(prev_untracked: &BTreeSet<PathBuf>, curr_status: &StatusSnapshot) -> Vec<PathBuf> {
+    curr_status
+        .untracked()
+        .difference(prev_untracked)
+        .cloned()
+        .collect()
+}
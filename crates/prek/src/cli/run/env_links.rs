@@ -0,0 +1,185 @@
+//! Links from the project to hook environments, created by `prek env link`.
+//!
+//! The links are recorded in `.prek/env-links.json` at the git root, so they can be repointed
+//! whenever the environment of a linked hook is rebuilt, and removed by `prek uninstall`.
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::fs::Simplified;
+use crate::git::GIT_ROOT;
+use crate::hook::{Hook, InstalledHook};
+use crate::warn_user;
+
+const ENV_LINKS_FILE: &str = ".prek/env-links.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct EnvLink {
+    /// Path of the link, relative to the git root.
+    pub(crate) path: PathBuf,
+    /// Path of the project the hook belongs to, relative to the workspace root.
+    pub(crate) project: PathBuf,
+    /// The hook id.
+    pub(crate) hook: String,
+}
+
+impl EnvLink {
+    pub(crate) fn new(path: PathBuf, hook: &Hook) -> Self {
+        Self {
+            path,
+            project: hook.project().relative_path().to_path_buf(),
+            hook: hook.id.clone(),
+        }
+    }
+
+    fn matches(&self, hook: &Hook) -> bool {
+        self.hook == hook.id && self.project == hook.project().relative_path()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct EnvLinks {
+    links: Vec<EnvLink>,
+}
+
+impl EnvLinks {
+    pub(crate) fn load(git_root: &Path) -> Result<Self> {
+        let path = git_root.join(ENV_LINKS_FILE);
+        match fs_err::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .with_context(|| format!("Failed to parse `{}`", path.user_display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub(crate) fn save(&self, git_root: &Path) -> Result<()> {
+        let path = git_root.join(ENV_LINKS_FILE);
+        if self.links.is_empty() {
+            match fs_err::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => return Ok(()),
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record a link, replacing any previous link at the same path.
+    pub(crate) fn insert(&mut self, link: EnvLink) {
+        self.links.retain(|existing| existing.path != link.path);
+        self.links.push(link);
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<EnvLink> {
+        std::mem::take(&mut self.links)
+    }
+}
+
+/// Repoint the recorded links of the given hooks to their current environments.
+///
+/// Failures are reported as warnings, a stale link should not fail the run.
+pub(crate) fn refresh_env_links(hooks: &[InstalledHook]) {
+    let Ok(git_root) = GIT_ROOT.as_ref() else {
+        return;
+    };
+    if !git_root.join(ENV_LINKS_FILE).is_file() {
+        return;
+    }
+
+    let result = EnvLinks::load(git_root).and_then(|links| {
+        for link in &links.links {
+            let Some(env_path) = hooks
+                .iter()
+                .filter(|hook| link.matches(hook))
+                .find_map(InstalledHook::env_path)
+            else {
+                continue;
+            };
+            let path = git_root.join(&link.path);
+            if fs_err::read_link(&path).is_ok_and(|target| target == env_path) {
+                continue;
+            }
+            debug!(
+                "Updating env link `{}` to `{}`",
+                path.display(),
+                env_path.display()
+            );
+            link_dir(env_path, &path)?;
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        warn_user!("Failed to update hook environment links: {err:#}");
+    }
+}
+
+/// Create a directory link at `link` pointing to `target`, replacing an existing link.
+///
+/// Uses a symlink, or a junction on Windows when creating symlinks is not permitted.
+pub(crate) fn link_dir(target: &Path, link: &Path) -> Result<()> {
+    match fs_err::symlink_metadata(link) {
+        Ok(metadata) if metadata.is_symlink() || is_junction(link) => remove_link(link)?,
+        Ok(_) => anyhow::bail!("`{}` already exists and is not a link", link.user_display()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    if let Some(parent) = link.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        fs_err::os::unix::fs::symlink(target, link)?;
+    }
+
+    #[cfg(windows)]
+    {
+        if let Err(err) = fs_err::os::windows::fs::symlink_dir(target, link) {
+            debug!("Failed to create symlink, falling back to a junction: {err}");
+            let status = std::process::Command::new("cmd")
+                .arg("/C")
+                .arg("mklink")
+                .arg("/J")
+                .arg(link)
+                .arg(target)
+                .stdout(std::process::Stdio::null())
+                .status()?;
+            if !status.success() {
+                anyhow::bail!("Failed to create junction `{}`", link.user_display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove a link created by [`link_dir`], without touching the environment it points to.
+pub(crate) fn remove_link(link: &Path) -> Result<()> {
+    // Directory symlinks and junctions are removed as directories on Windows.
+    #[cfg(windows)]
+    {
+        fs_err::remove_dir(link)?;
+    }
+    #[cfg(not(windows))]
+    {
+        fs_err::remove_file(link)?;
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn is_junction(path: &Path) -> bool {
+    fs_err::read_link(path).is_ok()
+}
+
+#[cfg(not(windows))]
+fn is_junction(_path: &Path) -> bool {
+    false
+}
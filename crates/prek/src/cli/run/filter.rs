@@ -6,13 +6,18 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use globset::Glob;
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use prek_consts::CONFIG_FILENAMES;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
-use prek_identify::{TagSet, tags_from_path};
+use prek_identify::{TagSet, tags, tags_from_path};
 use rustc_hash::{FxHashMap, FxHashSet};
+use tokio::io::AsyncReadExt;
 use tracing::{debug, error, instrument};
 
+use crate::cli::run::collector::{FileCollector, GitFileCollector};
 use crate::config::{FilePattern, GlobPatterns, Stage};
-use crate::fs::PathClean;
+use crate::fs::{PathClean, Simplified};
 use crate::git::GIT_ROOT;
 use crate::hook::Hook;
 use crate::workspace::Project;
@@ -71,11 +76,11 @@ impl<'a> FileTagFilter<'a> {
         }
         if self
             .any
-            .is_some_and(|s| !s.is_empty() && s.is_disjoint(file_types))
+            .is_some_and(|s| !s.is_empty() && !s.intersects(file_types))
         {
             return false;
         }
-        if self.exclude.is_some_and(|s| !s.is_disjoint(file_types)) {
+        if self.exclude.is_some_and(|s| s.intersects(file_types)) {
             return false;
         }
         true
@@ -84,6 +89,8 @@ impl<'a> FileTagFilter<'a> {
 
 pub(crate) struct HookFileFilter<'a> {
     filename: FilenameFilter<'a>,
+    /// The file patterns of the hook's repo, which files must pass as well.
+    repo_filename: FilenameFilter<'a>,
     tags: FileTagFilter<'a>,
 }
 
@@ -91,6 +98,10 @@ impl<'a> HookFileFilter<'a> {
     pub(crate) fn new(hook: &'a Hook) -> Self {
         Self {
             filename: FilenameFilter::new(hook.files.as_ref(), hook.exclude.as_ref()),
+            repo_filename: FilenameFilter::new(
+                hook.repo_files.as_ref(),
+                hook.repo_exclude.as_ref(),
+            ),
             tags: FileTagFilter::new(
                 Some(&hook.types),
                 Some(&hook.types_or),
@@ -100,7 +111,7 @@ impl<'a> HookFileFilter<'a> {
     }
 
     pub(crate) fn matches_filename(&self, filename: &Path) -> bool {
-        self.filename.matches(filename)
+        self.repo_filename.matches(filename) && self.filename.matches(filename)
     }
 
     pub(crate) fn matches_tags(&self, tags: Option<&TagSet>) -> bool {
@@ -148,6 +159,7 @@ impl<'a> ProjectFile<'a> {
 #[derive(Default)]
 pub(crate) struct FileTagCache<'a> {
     paths: &'a [PathBuf],
+    submodules: Option<&'a FxHashSet<PathBuf>>,
     tags_by_file: Vec<OnceCell<Option<TagSet>>>,
 }
 
@@ -156,14 +168,29 @@ impl<'a> FileTagCache<'a> {
         let tags_by_file = (0..paths.len()).map(|_| OnceCell::new()).collect();
         Self {
             paths,
+            submodules: None,
             tags_by_file,
         }
     }
 
+    /// Tag these paths as submodules instead of inspecting them on disk.
+    #[must_use]
+    pub(crate) fn with_submodules(mut self, submodules: &'a FxHashSet<PathBuf>) -> Self {
+        self.submodules = Some(submodules);
+        self
+    }
+
     pub(crate) fn tags(&self, file_idx: usize) -> Option<&TagSet> {
         self.tags_by_file[file_idx]
             .get_or_init(|| {
                 let path = &self.paths[file_idx];
+                // A gitlink may not be checked out, so its tags can't come from the filesystem.
+                if self
+                    .submodules
+                    .is_some_and(|submodules| submodules.contains(path))
+                {
+                    return Some(tags::TAG_SET_SUBMODULE);
+                }
                 match tags_from_path(path) {
                     Ok(tags) => Some(tags),
                     Err(err) => {
@@ -347,8 +374,14 @@ pub(crate) struct RunFileIndex<'a> {
 }
 
 impl<'a> RunFileIndex<'a> {
-    pub(crate) fn new(input: &'a RunInput, projects: &[Arc<Project>]) -> Self {
-        let RunInput::Files(filenames) = input else {
+    /// With `flatten`, the root project sees every file, including those consumed by orphan
+    /// projects.
+    pub(crate) fn new(input: &'a RunInput, projects: &[Arc<Project>], flatten: bool) -> Self {
+        let RunInput::Files {
+            files: filenames,
+            submodules,
+        } = input
+        else {
             return Self {
                 projects: Vec::new(),
                 tag_cache: FileTagCache::default(),
@@ -385,12 +418,18 @@ impl<'a> RunFileIndex<'a> {
             })
             .collect::<Vec<_>>();
 
+        let root_idx = flatten
+            .then(|| projects.iter().find(|project| project.is_root()))
+            .flatten()
+            .map(|project| project.idx());
+
         let mut matching_projects = Vec::new();
         for (file_idx, filename) in filenames.iter().enumerate() {
             project_tree.matching_projects(filename, &mut matching_projects);
 
             // The tree yields ancestors from root to leaf. Apply ownership from the most
             // specific project upwards, stopping once an orphan project consumes the file.
+            let mut consumed_by = None;
             for &project_idx in matching_projects.iter().rev() {
                 let project = &projects[project_idx];
                 let hook_path = filename
@@ -400,14 +439,22 @@ impl<'a> RunFileIndex<'a> {
                     project_files[project_idx].push(file_idx, hook_path);
                 }
                 if project.config().orphan.unwrap_or(false) {
+                    consumed_by = Some(project_idx);
                     break;
                 }
             }
+
+            if let Some(root_idx) = root_idx
+                && consumed_by.is_some_and(|idx| idx != root_idx)
+                && project_filters[root_idx].matches(filename)
+            {
+                project_files[root_idx].push(file_idx, filename);
+            }
         }
 
         Self {
             projects: project_files,
-            tag_cache: FileTagCache::from_paths(filenames),
+            tag_cache: FileTagCache::from_paths(filenames).with_submodules(submodules),
         }
     }
 
@@ -432,8 +479,15 @@ pub(crate) enum FileSelection {
         from_ref: String,
         to_ref: String,
     },
+    /// Files changed between a revision and the working tree, including uncommitted and
+    /// untracked files.
+    Since {
+        rev: String,
+    },
     Explicit {
         files: Vec<String>,
+        /// A file listing more paths, or `-` for stdin.
+        files_from: Option<PathBuf>,
         globs: Vec<Glob>,
         directories: Vec<String>,
     },
@@ -448,9 +502,77 @@ impl FileSelection {
         match self {
             Self::Diff { from_ref, to_ref } => (Some(from_ref), Some(to_ref)),
             Self::All { from_ref, to_ref } => (from_ref.as_deref(), to_ref.as_deref()),
-            Self::Default | Self::Explicit { .. } => (None, None),
+            Self::Default | Self::Since { .. } | Self::Explicit { .. } => (None, None),
         }
     }
+
+    /// Check that the diff refs resolve, and expand a `A...B` range given as `--from-ref`.
+    ///
+    /// A range diffs `B` against the merge base of `A` and `B`, which is what `--from-ref A --to-ref B`
+    /// does for plain commits. Plain refs are kept as written, so hooks see them unchanged in
+    /// `PRE_COMMIT_FROM_REF` and `PRE_COMMIT_TO_REF`.
+    ///
+    /// `--since <rev>` is resolved to the merge base of `<rev>` and `HEAD`, so a branch is only
+    /// compared with the point it forked from, not with later commits on `<rev>`.
+    pub(crate) async fn resolve_refs(self) -> Result<Self> {
+        let (from_ref, to_ref) = match self {
+            Self::Diff { from_ref, to_ref } => (from_ref, to_ref),
+            Self::Since { rev } => {
+                let rev_id = resolve_rev(&rev, "--since").await?;
+                let head = resolve_rev("HEAD", "--since").await?;
+                let merge_base = git::merge_base(&rev_id, &head)
+                    .await?
+                    .with_context(|| format!("`{rev}` and `HEAD` have no common ancestor"))?;
+                debug!("Merge base of `{rev}` and `HEAD`: {merge_base}");
+                return Ok(Self::Since { rev: merge_base });
+            }
+            _ => return Ok(self),
+        };
+
+        let Some((left, right)) = from_ref.split_once("...") else {
+            if let Some((left, right)) = from_ref.split_once("..") {
+                anyhow::bail!(
+                    "Two-dot ranges are not supported in `--from-ref`, use `--from-ref {left}...{right}` or `--from-ref {left} --to-ref {right}` instead"
+                );
+            }
+            resolve_rev(&from_ref, "--from-ref").await?;
+            resolve_rev(&to_ref, "--to-ref").await?;
+            return Ok(Self::Diff { from_ref, to_ref });
+        };
+
+        // `--to-ref` defaults to `HEAD`, so only a different value can conflict with the range.
+        if to_ref != "HEAD" {
+            anyhow::bail!(
+                "`--from-ref {from_ref}` already specifies both sides of the diff, so `--to-ref` cannot be used with it"
+            );
+        }
+
+        // Like Git, an omitted side of the range means `HEAD`.
+        let left = if left.is_empty() { "HEAD" } else { left };
+        let right = if right.is_empty() { "HEAD" } else { right };
+        let left_id = resolve_rev(left, "--from-ref").await?;
+        let right_id = resolve_rev(right, "--from-ref").await?;
+
+        let merge_base = git::merge_base(&left_id, &right_id)
+            .await?
+            .with_context(|| format!("`{left}` and `{right}` have no common ancestor"))?;
+        debug!("Merge base of `{left}` and `{right}`: {merge_base}");
+
+        Ok(Self::Diff {
+            from_ref: merge_base,
+            to_ref: right.to_string(),
+        })
+    }
+}
+
+async fn resolve_rev(rev: &str, flag: &str) -> Result<String> {
+    // Keep revisions from being parsed as `git rev-parse` options.
+    if rev.starts_with('-') {
+        anyhow::bail!("Invalid revision `{rev}` passed to `{flag}`");
+    }
+    git::resolve_rev(rev)
+        .await?
+        .with_context(|| format!("Cannot resolve revision `{rev}` passed to `{flag}`"))
 }
 
 #[derive(Default)]
@@ -458,6 +580,8 @@ pub(crate) struct CollectOptions {
     pub(crate) input_mode: RunInputMode,
     pub(crate) selection: FileSelection,
     pub(crate) commit_msg_filename: Option<String>,
+    /// Where to collect files from. Defaults to Git.
+    pub(crate) collector: Option<Box<dyn FileCollector>>,
 }
 
 impl CollectOptions {
@@ -491,6 +615,7 @@ impl From<Stage> for RunInputMode {
             | Stage::PostCommit
             | Stage::PostMerge
             | Stage::PostRewrite
+            | Stage::PreAutoGc
             | Stage::PreRebase => Self::NoFiles,
         }
     }
@@ -498,7 +623,11 @@ impl From<Stage> for RunInputMode {
 
 pub(crate) enum RunInput {
     /// File paths relative to the workspace root.
-    Files(Vec<PathBuf>),
+    Files {
+        files: Vec<PathBuf>,
+        /// The gitlink entries of `files`, tagged as `submodule`.
+        submodules: FxHashSet<PathBuf>,
+    },
     /// Absolute path to the Git message file passed by `commit-msg` and `prepare-commit-msg`.
     MessageFile(PathBuf),
 }
@@ -510,7 +639,7 @@ impl RunInput {
     /// compatibility helper discards them and returns an empty list.
     pub(crate) fn into_files(self) -> Vec<PathBuf> {
         match self {
-            Self::Files(files) => files,
+            Self::Files { files, .. } => files,
             Self::MessageFile(_) => vec![],
         }
     }
@@ -522,6 +651,7 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         input_mode,
         selection,
         commit_msg_filename,
+        collector,
     } = opts;
 
     let git_root = GIT_ROOT.as_ref()?;
@@ -532,7 +662,12 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
             let path = commit_msg_filename.expect("commit_msg_filename should be set");
             return Ok(RunInput::MessageFile(git_root.join(path)));
         }
-        RunInputMode::NoFiles => return Ok(RunInput::Files(vec![])),
+        RunInputMode::NoFiles => {
+            return Ok(RunInput::Files {
+                files: vec![],
+                submodules: FxHashSet::default(),
+            });
+        }
     }
 
     // The workspace root relative to the git root.
@@ -544,7 +679,10 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         )
     })?;
 
-    let filenames = collect_files_for_selection(git_root, root, selection).await?;
+    // Files selected with `--files`, `--glob` or `--directory` are passed to hooks as given.
+    let explicit = matches!(selection, FileSelection::Explicit { .. });
+    let collector = collector.unwrap_or_else(|| Box::new(GitFileCollector));
+    let filenames = collector.collect(git_root, root, selection).await?;
 
     // Convert filenames to be relative to the workspace root.
     let mut filenames = filenames
@@ -558,12 +696,120 @@ pub(crate) async fn collect_run_input(root: &Path, opts: CollectOptions) -> Resu
         })
         .collect::<Vec<_>>();
 
+    if !explicit {
+        let mut ignore = PrekIgnore::new(root);
+        filenames.retain(|filename| !ignore.is_ignored(filename));
+    }
+
     // Sort filenames if in tests to make the order consistent.
     if EnvVars.is_set(EnvVars::PREK_INTERNAL__SORT_FILENAMES) {
         filenames.sort_unstable();
     }
 
-    Ok(RunInput::Files(filenames))
+    let submodules = collect_submodules(git_root, relative_root, &filenames).await?;
+
+    Ok(RunInput::Files {
+        files: filenames,
+        submodules,
+    })
+}
+
+/// The nested `.prekignore` files of the workspace, applied to collected files with
+/// `.gitignore` semantics.
+///
+/// A `.prekignore` next to a config file keeps excluding directories from workspace discovery
+/// only, so excluding a nested project does not hide its files from the enclosing project.
+struct PrekIgnore<'a> {
+    root: &'a Path,
+    /// The parsed `.prekignore` of each visited workspace-relative directory, if it applies.
+    dirs: FxHashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl<'a> PrekIgnore<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            dirs: FxHashMap::default(),
+        }
+    }
+
+    /// Whether a workspace-relative file is ignored by a nested `.prekignore` in one of its
+    /// directories.
+    fn is_ignored(&mut self, filename: &Path) -> bool {
+        // Like `.gitignore`, the closest file wins, so it can re-include files with `!pattern`.
+        for dir in filename.ancestors().skip(1) {
+            if !self.dirs.contains_key(dir) {
+                let gitignore = self.load(dir);
+                self.dirs.insert(dir.to_path_buf(), gitignore);
+            }
+            let Some(gitignore) = &self.dirs[dir] else {
+                continue;
+            };
+            let relative = filename
+                .strip_prefix(dir)
+                .expect("ancestor must be a prefix");
+            match gitignore.matched_path_or_any_parents(relative, false) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn load(&self, dir: &Path) -> Option<Gitignore> {
+        let dir = self.root.join(dir);
+        let path = dir.join(".prekignore");
+        if !path.is_file() || CONFIG_FILENAMES.iter().any(|name| dir.join(name).is_file()) {
+            return None;
+        }
+
+        let mut builder = GitignoreBuilder::new(&dir);
+        if let Some(err) = builder.add(&path) {
+            warn_user!("Failed to parse `{}`: {err}", path.user_display());
+        }
+        match builder.build() {
+            Ok(gitignore) => Some(gitignore),
+            Err(err) => {
+                warn_user!("Failed to parse `{}`: {err}", path.user_display());
+                None
+            }
+        }
+    }
+}
+
+/// Find the gitlink entries among `filenames`, so hooks can match submodule pointer changes
+/// with `types: [submodule]` even when the submodule is not checked out.
+async fn collect_submodules(
+    git_root: &Path,
+    relative_root: &Path,
+    filenames: &[PathBuf],
+) -> Result<FxHashSet<PathBuf>> {
+    if filenames.is_empty() || !git_root.join(".gitmodules").try_exists()? {
+        return Ok(FxHashSet::default());
+    }
+
+    let gitlinks: FxHashSet<PathBuf> = git::ls_gitlinks(git_root)
+        .await?
+        .into_iter()
+        .filter_map(|gitlink| {
+            gitlink
+                .strip_prefix(relative_root)
+                .map(|p| fs::normalize_path(p.to_path_buf()))
+                .ok()
+        })
+        .collect();
+    if gitlinks.is_empty() {
+        return Ok(gitlinks);
+    }
+
+    let submodules: FxHashSet<PathBuf> = filenames
+        .iter()
+        .filter(|filename| gitlinks.contains(*filename))
+        .cloned()
+        .collect();
+    debug!("Submodules in the run input: {}", submodules.len());
+    Ok(submodules)
 }
 
 fn adjust_relative_path(path: &str, new_cwd: &Path) -> Result<PathBuf, std::io::Error> {
@@ -658,9 +904,43 @@ async fn collect_explicit_files(
     Ok(selected.into_iter().collect())
 }
 
-/// Collect files to run hooks on.
+/// Read the paths listed in a `--files-from` file, or in stdin for `-`.
+///
+/// Paths are separated by NUL characters if the list contains any, and by newlines otherwise.
+async fn read_files_from(path: &Path) -> Result<Vec<String>> {
+    let content = if path == Path::new("-") {
+        let mut content = Vec::new();
+        tokio::io::stdin()
+            .read_to_end(&mut content)
+            .await
+            .context("Failed to read the `--files-from` list from stdin")?;
+        content
+    } else {
+        tokio::fs::read(path).await.with_context(|| {
+            format!(
+                "Failed to read the `--files-from` list `{}`",
+                path.display()
+            )
+        })?
+    };
+    let content =
+        String::from_utf8(content).context("The `--files-from` list is not valid UTF-8")?;
+
+    let paths: Vec<&str> = if content.contains('\0') {
+        content.split('\0').collect()
+    } else {
+        content.lines().collect()
+    };
+    Ok(paths
+        .into_iter()
+        .filter(|path| !path.is_empty())
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Collect files to run hooks on with Git.
 /// Returns a list of file paths relative to the git root.
-async fn collect_files_for_selection(
+pub(super) async fn collect_files_for_selection(
     git_root: &Path,
     workspace_root: &Path,
     selection: FileSelection,
@@ -676,11 +956,22 @@ async fn collect_files_for_selection(
             );
             Ok(files)
         }
+        FileSelection::Since { rev } => {
+            let files = git::get_changed_files_since(&rev, workspace_root).await?;
+            debug!("Files changed since {rev}: {}", files.len());
+            Ok(files)
+        }
         FileSelection::Explicit {
-            files,
+            mut files,
+            files_from,
             globs,
             directories,
-        } => collect_explicit_files(git_root, files, globs, directories).await,
+        } => {
+            if let Some(files_from) = files_from {
+                files.extend(read_files_from(&files_from).await?);
+            }
+            collect_explicit_files(git_root, files, globs, directories).await
+        }
         FileSelection::All { .. } => {
             let files = git::ls_files(git_root, [workspace_root]).await?;
             debug!("All files in the workspace: {}", files.len());
@@ -0,0 +1,265 @@
+//! GitHub Actions output for `prek run --github-summary`.
+//!
+//! Hook details are wrapped in `::group::` workflow commands so they fold in the job log, and a
+//! markdown table of every hook is appended to the job summary file once the run finishes.
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use console::strip_ansi_codes;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use serde::Serialize;
+
+use crate::warn_user;
+
+/// Maximum number of output lines kept per failed hook, the job summary is limited to 1 MiB.
+const MAX_OUTPUT_LINES: usize = 200;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HookOutcome {
+    Passed,
+    /// Passed with notes, the exit code is one of the hook's `warning_exit_codes`.
+    Warning,
+    Failed,
+    Skipped,
+    DryRun,
+}
+
+impl HookOutcome {
+    fn as_markdown(self) -> &'static str {
+        match self {
+            Self::Passed => "✅ Passed",
+            Self::Warning => "⚠️ Warning",
+            Self::Failed => "❌ Failed",
+            Self::Skipped => "⏭️ Skipped",
+            Self::DryRun => "📝 Dry Run",
+        }
+    }
+}
+
+/// The result of a single hook, as shown in the job summary and run reports.
+#[derive(Debug, Clone)]
+pub(crate) struct HookSummary {
+    pub(crate) project: String,
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) outcome: HookOutcome,
+    pub(crate) duration: Duration,
+    pub(crate) exit_code: i32,
+    /// Number of files passed to the hook.
+    pub(crate) files: usize,
+    pub(crate) files_modified: bool,
+    pub(crate) output: String,
+}
+
+/// Collects hook results and writes them to `$GITHUB_STEP_SUMMARY`.
+#[derive(Debug, Default)]
+pub(crate) struct GithubSummary {
+    show_projects: bool,
+    hooks: Vec<HookSummary>,
+}
+
+impl GithubSummary {
+    pub(crate) const GROUP_END: &'static str = "::endgroup::";
+
+    pub(crate) fn new(show_projects: bool) -> Self {
+        Self {
+            show_projects,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// The workflow command that starts a collapsible group in the job log.
+    pub(crate) fn group_start(hook_id: &str) -> String {
+        format!("::group::{hook_id}")
+    }
+
+    pub(crate) fn record(&mut self, hook: HookSummary) {
+        self.hooks.push(hook);
+    }
+
+    /// Append the summary to the file named by `$GITHUB_STEP_SUMMARY`.
+    pub(crate) fn write(&self) -> Result<()> {
+        let Some(path) = EnvVars.var_os(EnvVars::GITHUB_STEP_SUMMARY) else {
+            warn_user!(
+                "`--github-summary` requires `{}` to be set, skipping the job summary",
+                EnvVars::GITHUB_STEP_SUMMARY
+            );
+            return Ok(());
+        };
+        let path = PathBuf::from(path);
+
+        let mut file = fs_err::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        file.write_all(self.render().as_bytes())
+            .with_context(|| format!("Failed to write job summary to `{}`", path.display()))?;
+
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let count = |outcome| {
+            self.hooks
+                .iter()
+                .filter(|hook| hook.outcome == outcome)
+                .count()
+        };
+
+        let _ = writeln!(out, "## prek run\n");
+        let _ = writeln!(
+            out,
+            "{} passed, {} failed, {} skipped\n",
+            count(HookOutcome::Passed) + count(HookOutcome::Warning),
+            count(HookOutcome::Failed),
+            count(HookOutcome::Skipped) + count(HookOutcome::DryRun),
+        );
+
+        if self.show_projects {
+            let _ = writeln!(out, "| Project | Hook | Status | Duration |");
+            let _ = writeln!(out, "| --- | --- | --- | --- |");
+        } else {
+            let _ = writeln!(out, "| Hook | Status | Duration |");
+            let _ = writeln!(out, "| --- | --- | --- |");
+        }
+        for hook in &self.hooks {
+            let mut status = hook.outcome.as_markdown().to_string();
+            if hook.files_modified {
+                status.push_str(" (files modified)");
+            }
+            let duration = if hook.outcome == HookOutcome::Skipped {
+                "-".to_string()
+            } else {
+                format!("{:.2}s", hook.duration.as_secs_f64())
+            };
+            let name = format!("{} (`{}`)", escape_cell(&hook.name), escape_cell(&hook.id));
+            if self.show_projects {
+                let _ = writeln!(
+                    out,
+                    "| {} | {name} | {status} | {duration} |",
+                    escape_cell(&hook.project),
+                );
+            } else {
+                let _ = writeln!(out, "| {name} | {status} | {duration} |");
+            }
+        }
+
+        let failures: Vec<_> = self
+            .hooks
+            .iter()
+            .filter(|hook| hook.outcome == HookOutcome::Failed)
+            .collect();
+        if !failures.is_empty() {
+            let _ = writeln!(out, "\n### Failures");
+        }
+        for hook in failures {
+            let title = if self.show_projects {
+                format!("<code>{}</code> in {}", hook.id, hook.project)
+            } else {
+                format!("<code>{}</code>", hook.id)
+            };
+            let _ = writeln!(out, "\n<details>\n<summary>{title}</summary>\n");
+            if hook.exit_code != 0 {
+                let _ = writeln!(out, "- exit code: {}", hook.exit_code);
+            }
+            if hook.files_modified {
+                let _ = writeln!(out, "- files were modified");
+            }
+
+            let output = strip_ansi_codes(hook.output.trim());
+            if !output.is_empty() {
+                let lines: Vec<_> = output.lines().collect();
+                let fence = code_fence(&output);
+                let _ = writeln!(out, "\n{fence}text");
+                for line in lines.iter().take(MAX_OUTPUT_LINES) {
+                    let _ = writeln!(out, "{line}");
+                }
+                if lines.len() > MAX_OUTPUT_LINES {
+                    let _ = writeln!(out, "... ({} more lines)", lines.len() - MAX_OUTPUT_LINES);
+                }
+                let _ = writeln!(out, "{fence}");
+            }
+            let _ = writeln!(out, "\n</details>");
+        }
+        out.push('\n');
+
+        out
+    }
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// A backtick fence longer than any backtick run in `text`.
+fn code_fence(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(id: &str, outcome: HookOutcome, output: &str) -> HookSummary {
+        HookSummary {
+            project: "<workspace>".to_string(),
+            id: id.to_string(),
+            name: format!("Run {id}"),
+            outcome,
+            duration: Duration::from_millis(1250),
+            exit_code: i32::from(outcome == HookOutcome::Failed),
+            files: 1,
+            files_modified: false,
+            output: output.to_string(),
+        }
+    }
+
+    #[test]
+    fn render_summary() {
+        let mut summary = GithubSummary::new(false);
+        summary.record(hook("fmt", HookOutcome::Passed, ""));
+        summary.record(hook(
+            "lint",
+            HookOutcome::Failed,
+            "\u{1b}[31merror\u{1b}[0m: bad | code",
+        ));
+        summary.record(hook("typos", HookOutcome::Skipped, ""));
+
+        insta::assert_snapshot!(summary.render(), @r"
+        ## prek run
+
+        1 passed, 1 failed, 1 skipped
+
+        | Hook | Status | Duration |
+        | --- | --- | --- |
+        | Run fmt (`fmt`) | ✅ Passed | 1.25s |
+        | Run lint (`lint`) | ❌ Failed | 1.25s |
+        | Run typos (`typos`) | ⏭️ Skipped | - |
+
+        ### Failures
+
+        <details>
+        <summary><code>lint</code></summary>
+
+        - exit code: 1
+
+        ```text
+        error: bad | code
+        ```
+
+        </details>
+        ");
+    }
+
+    #[test]
+    fn code_fence_is_longer_than_output_backticks() {
+        assert_eq!(code_fence("plain"), "```");
+        assert_eq!(code_fence("```rust\n```"), "````");
+    }
+}
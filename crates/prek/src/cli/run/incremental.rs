@@ -0,0 +1,283 @@
+//! Incremental `--all-files` runs: check only the files changed since a hook last passed.
+//!
+//! After a hook passes an `--all-files --incremental` run, the tree of the tracked files it
+//! checked is recorded. The next incremental run only passes the hook the files that changed
+//! since. The trees are written to a private object directory in the cache, never to the
+//! repository.
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use rustc_hash::{FxHashMap, FxHashSet};
+use seahash::SeaHasher;
+use tracing::debug;
+
+use crate::cli::run::run::hook_key;
+use crate::git;
+use crate::hook::{Hook, InstalledHook};
+use crate::store::{CacheBucket, Store};
+
+/// Config files of common tools, looked up in the project and the repository root.
+///
+/// They are rarely matched by the hooks reading them, but changing one may change what a hook
+/// reports for files that did not change.
+const TOOL_CONFIG_FILES: &[&str] = &[
+    ".editorconfig",
+    ".eslintrc",
+    ".eslintrc.js",
+    ".eslintrc.json",
+    ".eslintrc.yaml",
+    ".eslintrc.yml",
+    ".flake8",
+    ".golangci.yaml",
+    ".golangci.yml",
+    ".markdownlint.json",
+    ".markdownlint.yaml",
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.yaml",
+    ".prettierrc.yml",
+    ".pylintrc",
+    ".rubocop.yml",
+    ".ruff.toml",
+    ".rustfmt.toml",
+    ".typos.toml",
+    ".yamllint",
+    ".yamllint.yaml",
+    ".yamllint.yml",
+    "_typos.toml",
+    "biome.json",
+    "clippy.toml",
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "mypy.ini",
+    "package.json",
+    "prettier.config.js",
+    "pyproject.toml",
+    "ruff.toml",
+    "rustfmt.toml",
+    "setup.cfg",
+    "tox.ini",
+    "tsconfig.json",
+    "typos.toml",
+];
+
+/// Tree hashes of the last successful `--all-files` run of each hook.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct IncrementalBaselines {
+    /// Keyed by config file, hook id, and a fingerprint of the prek version, the config, the
+    /// hook environment and the files the hook reads its settings from.
+    hooks: BTreeMap<String, String>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl IncrementalBaselines {
+    fn path(store: &Store) -> PathBuf {
+        store
+            .cache_path(CacheBucket::Prek)
+            .join("incremental-baselines.json")
+    }
+
+    fn load(store: &Store) -> Self {
+        let path = Self::path(store);
+        match fs_err::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                debug!("Failed to deserialize incremental baselines: {err}");
+                Self::default()
+            }),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    debug!("Failed to read incremental baselines: {err}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self, store: &Store) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let path = Self::path(store);
+        let parent = path.parent().expect("cache file has a parent");
+        fs_err::create_dir_all(parent)?;
+
+        // Write to a temporary file first, so concurrent runs never observe a partial file.
+        let mut temp = tempfile::NamedTempFile::new_in(parent)?;
+        serde_json::to_writer(&mut temp, self)?;
+        temp.persist(&path)?;
+        Ok(())
+    }
+}
+
+/// Hashes the content of files, reading each file once.
+#[derive(Default)]
+struct FileHashes(FxHashMap<PathBuf, Option<u64>>);
+
+impl FileHashes {
+    /// Hash the content of `path`, `None` if it is not a file.
+    fn get(&mut self, path: PathBuf) -> Result<Option<u64>> {
+        if let Some(hash) = self.0.get(&path) {
+            return Ok(*hash);
+        }
+        let hash = match fs_err::read(&path) {
+            Ok(content) => Some(seahash::hash(&content)),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::NotFound | std::io::ErrorKind::IsADirectory
+                ) =>
+            {
+                None
+            }
+            Err(err) => return Err(err.into()),
+        };
+        self.0.insert(path, hash);
+        Ok(hash)
+    }
+}
+
+/// The files `hook` may read its settings from: common tool config files, and the files named
+/// in its `args`, either as a whole argument or as the value of `--option=<file>`.
+fn settings_files(hook: &Hook, git_root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for dir in [hook.work_dir(), git_root] {
+        files.extend(TOOL_CONFIG_FILES.iter().map(|name| dir.join(name)));
+    }
+    for arg in &hook.args {
+        let value = arg.split_once('=').map_or(arg.as_str(), |(_, value)| value);
+        for candidate in [arg.as_str(), value] {
+            let path = hook.run_dir().join(candidate);
+            if !candidate.is_empty() && path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// The state of an incremental `--all-files` run.
+pub(crate) struct IncrementalRun {
+    /// Tree of the tracked files in the working tree when the run started.
+    tree: String,
+    baselines: IncrementalBaselines,
+    /// Storage key of each hook, by project and hook index.
+    keys: FxHashMap<(usize, usize), String>,
+    /// Files changed since each recorded baseline tree, relative to the repository root.
+    changed: FxHashMap<String, FxHashSet<PathBuf>>,
+}
+
+impl IncrementalRun {
+    pub(crate) async fn new(
+        store: &Store,
+        git_root: &Path,
+        hooks: &[InstalledHook],
+    ) -> Result<Self> {
+        let objects = store
+            .cache_path(CacheBucket::Prek)
+            .join("incremental-objects");
+        let tree = git::write_worktree_tree(git_root, &store.scratch_path(), &objects).await?;
+        let baselines = IncrementalBaselines::load(store);
+
+        // Any change to prek, the config file, the hook environment or the files the hook
+        // reads its settings from invalidates the baseline, since the hook may now report
+        // different results for unchanged files. The hook index tells apart hooks that share
+        // an id.
+        let mut file_hashes = FileHashes::default();
+        let mut keys = FxHashMap::default();
+        for hook in hooks {
+            let config_file = hook.project().config_file();
+            let mut hasher = SeaHasher::new();
+            env!("CARGO_PKG_VERSION").hash(&mut hasher);
+            file_hashes
+                .get(config_file.to_path_buf())?
+                .hash(&mut hasher);
+            hook.idx.hash(&mut hasher);
+            hook.env_path().hash(&mut hasher);
+            hook.args.hash(&mut hasher);
+            for file in settings_files(hook, git_root) {
+                if let Some(hash) = file_hashes.get(file.clone())? {
+                    file.hash(&mut hasher);
+                    hash.hash(&mut hasher);
+                }
+            }
+            keys.insert(
+                hook_key(hook),
+                format!(
+                    "{}:{}:{:016x}",
+                    config_file.display(),
+                    hook.id,
+                    hasher.finish()
+                ),
+            );
+        }
+
+        let mut changed = FxHashMap::default();
+        for key in keys.values() {
+            let Some(old) = baselines.hooks.get(key) else {
+                continue;
+            };
+            if changed.contains_key(old) {
+                continue;
+            }
+            let files = if *old == tree {
+                FxHashSet::default()
+            } else {
+                match git::get_changed_files_between_trees(git_root, &objects, old, &tree).await {
+                    Ok(files) => files.into_iter().collect(),
+                    Err(err) => {
+                        // Blobs of the baseline tree may have been garbage collected from the
+                        // repository.
+                        debug!("Failed to diff against incremental baseline `{old}`: {err}");
+                        continue;
+                    }
+                }
+            };
+            changed.insert(old.clone(), files);
+        }
+
+        Ok(Self {
+            tree,
+            baselines,
+            keys,
+            changed,
+        })
+    }
+
+    /// The files changed since `hook` last passed, or `None` if it has to check every file.
+    pub(crate) fn changed_files(&self, hook: &Hook) -> Option<&FxHashSet<PathBuf>> {
+        let key = self.keys.get(&hook_key(hook))?;
+        let old = self.baselines.hooks.get(key)?;
+        self.changed.get(old)
+    }
+
+    /// Record that `hook` passed on every file of the tree the run started with.
+    pub(crate) fn record_success(&mut self, hook: &Hook) {
+        let Some(key) = self.keys.get(&hook_key(hook)) else {
+            return;
+        };
+        if self.baselines.hooks.get(key) == Some(&self.tree) {
+            return;
+        }
+
+        // Drop baselines recorded for older configs or environments of the same hook.
+        if let Some((prefix, _)) = key.rsplit_once(':') {
+            self.baselines.hooks.retain(|other, _| {
+                other
+                    .rsplit_once(':')
+                    .is_none_or(|(other, _)| other != prefix)
+            });
+        }
+        self.baselines.hooks.insert(key.clone(), self.tree.clone());
+        self.baselines.dirty = true;
+    }
+
+    pub(crate) fn save(&self, store: &Store) -> Result<()> {
+        self.baselines.save(store)
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::Stdio;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -5,12 +8,20 @@ use anyhow::{Context, Result};
 use futures_util::stream::{FuturesUnordered, StreamExt};
 use mea::once::OnceCell;
 use mea::semaphore::Semaphore;
+use prek_consts::env_vars::EnvVars;
+use prek_consts::prepend_paths;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
 use tracing::{debug, warn};
 
 use crate::cli::reporter::HookInstallReporter;
+use crate::cli::run::env_links::refresh_env_links;
 use crate::config::Language;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
-use crate::run::INTERNAL_CONCURRENCY;
+use crate::hook_entry::HookEntry;
+use crate::process::Cmd;
+use crate::remote_cache::RemoteCache;
+use crate::run::{INTERNAL_CONCURRENCY, LanguageLimits};
 use crate::store::Store;
 
 /// Resolve already-installed hook environments and install the missing ones.
@@ -33,19 +44,28 @@ pub(crate) async fn install_hooks(
             continue;
         }
 
-        if let Some(installed_hook) = cache.installed_hook(store, hook.clone()).await {
+        if let Some(installed_hook) = cache.reuse_installed_hook(store, hook.clone()).await {
             installed_hooks.push(installed_hook);
         } else {
+            if cache.has_unhealthy_env(store, &hook).await {
+                cache.stats.rebuilt += 1;
+            } else {
+                cache.stats.installed += 1;
+            }
             hooks_to_install.push(hook);
         }
     }
 
     let semaphore = Rc::new(Semaphore::new(*INTERNAL_CONCURRENCY));
+    let language_limits = Rc::new(LanguageLimits::from_settings());
     let mut futures = FuturesUnordered::new();
 
     for partition in partition_hooks(hooks_to_install) {
         let semaphore = Rc::clone(&semaphore);
-        futures.push(async move { install_partition(partition, store, reporter, semaphore).await });
+        let language_limits = Rc::clone(&language_limits);
+        futures.push(async move {
+            install_partition(partition, store, reporter, semaphore, language_limits).await
+        });
     }
 
     while let Some(partition_hooks) = futures.next().await {
@@ -58,16 +78,34 @@ pub(crate) async fn install_hooks(
         "Number of hooks installed should match the number of hooks provided"
     );
 
+    record_env_usage(&installed_hooks).await;
+    refresh_env_links(&installed_hooks);
+
     Ok(installed_hooks)
 }
 
+/// Record that the environments of `hooks` are used now, so `prek cache prune` keeps them.
+async fn record_env_usage(hooks: &[InstalledHook]) {
+    let mut seen = FxHashSet::default();
+    for hook in hooks {
+        if let InstalledHook::Installed { info, .. } = hook
+            && seen.insert(&info.env_path)
+            && let Err(err) = info.touch_last_used().await
+        {
+            debug!(%err, path = %info.env_path.display(), "Failed to record environment usage");
+        }
+    }
+}
+
 async fn install_partition(
     hooks: Vec<Arc<Hook>>,
     store: &Store,
     reporter: &HookInstallReporter,
     semaphore: Rc<Semaphore>,
+    language_limits: Rc<LanguageLimits>,
 ) -> Result<Vec<InstalledHook>> {
     let mut installed_hooks = Vec::with_capacity(hooks.len());
+    let remote_cache = RemoteCache::from_settings();
 
     for hook in hooks {
         debug_assert!(hook.needs_install_env());
@@ -91,19 +129,43 @@ async fn install_partition(
                 info: Arc::clone(info),
             }
         } else {
+            // Wait for the language slot first, so a capped language does not hold a global one.
+            let _language_permit = language_limits.acquire(hook.language).await;
             let _permit = semaphore.acquire(1).await;
 
+            if let Some(remote_cache) = remote_cache
+                && let Some(info) = remote_cache.restore(store, &hook).await
+            {
+                installed_hooks.push(InstalledHook::Installed {
+                    hook,
+                    info: Arc::new(info),
+                });
+                continue;
+            }
+
             let installed_hook = hook
                 .language
                 .install(store, hook.clone(), reporter)
                 .await
                 .with_context(|| format!("Failed to install hook `{hook}`"))?;
 
+            if let InstalledHook::Installed { info, .. } = &installed_hook {
+                run_setup_commands(&hook, info, store)
+                    .await
+                    .with_context(|| format!("Failed to run setup commands for hook `{hook}`"))?;
+            }
+
             installed_hook
                 .mark_as_installed(store)
                 .await
                 .with_context(|| format!("Failed to mark hook `{hook}` as installed"))?;
 
+            if let Some(remote_cache) = remote_cache
+                && let InstalledHook::Installed { info, .. } = &installed_hook
+            {
+                remote_cache.upload(store, &hook, info).await;
+            }
+
             match &installed_hook {
                 InstalledHook::Installed { info, .. } => {
                     debug!("Installed hook `{hook}` in `{}`", info.env_path.display());
@@ -121,12 +183,78 @@ async fn install_partition(
     Ok(installed_hooks)
 }
 
+/// Run the hook's `setup` commands in a freshly installed environment.
+///
+/// This runs before the environment is marked as installed, so a failed setup leaves the
+/// environment unusable and it is rebuilt on the next run.
+async fn run_setup_commands(hook: &Hook, info: &InstallInfo, store: &Store) -> Result<()> {
+    if info.setup.is_empty() {
+        return Ok(());
+    }
+
+    let paths = hook.language.setup_paths(info);
+    let paths = paths.iter().map(PathBuf::as_path).collect::<Vec<_>>();
+    let new_path = prepend_paths(&paths).context("Failed to join PATH")?;
+
+    for command in &info.setup {
+        debug!("Running setup command `{command}` for hook `{hook}`");
+        let entry = HookEntry::new(hook.id.clone(), command.clone(), hook.entry.shell())
+            .resolve(Some(&new_path), store)?;
+        let mut cmd = Cmd::new(&entry[0]);
+        cmd.current_dir(hook.work_dir())
+            .args(&entry[1..])
+            .env(EnvVars::PATH, &new_path);
+        for (key, value) in hook.language.setup_env(info) {
+            match value {
+                Some(value) => cmd.env(key, value),
+                None => cmd.env_remove(key),
+            };
+        }
+        cmd.envs(info.env(store))
+            .envs(&hook.env)
+            .stdin(Stdio::null())
+            .check(true)
+            .output()
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Group the hooks whose environments [`install_hooks`] would create, without installing anything.
+///
+/// Hooks that need no environment or can reuse a healthy one from the store are left out. Each
+/// group is expected to end up sharing one new environment.
+pub(crate) async fn plan_new_envs(
+    hooks: &[Arc<Hook>],
+    store: &Store,
+    cache: &InstallCache,
+) -> Vec<Vec<Arc<Hook>>> {
+    let mut groups: Vec<Vec<Arc<Hook>>> = Vec::new();
+    for hook in hooks {
+        if !hook.needs_install_env() || cache.installed_info(store, hook).await.is_some() {
+            continue;
+        }
+
+        if let Some(group) = groups.iter_mut().find(|group| {
+            same_install_partition(&group[0], hook)
+                && group[0].language_version == hook.language_version
+        }) {
+            group.push(Arc::clone(hook));
+        } else {
+            groups.push(vec![Arc::clone(hook)]);
+        }
+    }
+
+    groups
+}
+
 /// Group hooks so each partition can install independently.
 ///
-/// Hooks with the same install language, repository, and dependency sequence stay in one
-/// partition so later hooks can reuse an environment installed by an earlier hook. Version
-/// requirements are checked by the full environment requirement and intentionally do not split
-/// partitions.
+/// Hooks with the same install language, repository, dependency sequence, and setup commands
+/// stay in one partition so later hooks can reuse an environment installed by an earlier hook.
+/// Version requirements are checked by the full environment requirement and intentionally do not
+/// split partitions.
 fn partition_hooks(hooks: Vec<Arc<Hook>>) -> Vec<Vec<Arc<Hook>>> {
     let mut partitions: Vec<Vec<Arc<Hook>>> = Vec::new();
     for hook in hooks {
@@ -146,7 +274,8 @@ fn partition_hooks(hooks: Vec<Arc<Hook>>) -> Vec<Vec<Arc<Hook>>> {
 fn same_install_partition(left: &Hook, right: &Hook) -> bool {
     partition_language(left.language) == partition_language(right.language)
         && left.repo().identity() == right.repo().identity()
-        && left.additional_dependencies == right.additional_dependencies
+        && left.env_dependencies() == right.env_dependencies()
+        && left.setup == right.setup
 }
 
 fn partition_language(language: Language) -> Language {
@@ -207,12 +336,43 @@ impl CachedInstallInfo {
     }
 }
 
+/// How hook environments were resolved while preparing hooks, counted per hook.
+///
+/// Hooks that share an environment created earlier in the same command count as installed,
+/// since that environment did not exist in the store before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct EnvCacheStats {
+    /// Hooks that reused a healthy environment already present in the store.
+    pub(crate) reused: usize,
+    /// Hooks whose environment had to be installed.
+    pub(crate) installed: usize,
+    /// Hooks whose matching environment failed its health check and was installed again.
+    pub(crate) rebuilt: usize,
+}
+
+impl EnvCacheStats {
+    pub(crate) fn total(&self) -> usize {
+        self.reused + self.installed + self.rebuilt
+    }
+}
+
+impl Display for EnvCacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} reused, {} installed, {} rebuilt",
+            self.reused, self.installed, self.rebuilt
+        )
+    }
+}
+
 /// Lazy cache of hook environments already present in the store.
 ///
 /// This cache does not track environments created during the current command. New environments
 /// are returned by `install_hooks` directly, and same-call reuse happens inside `install_partition`.
 pub(crate) struct InstallCache {
     store_hooks: OnceCell<Vec<CachedInstallInfo>>,
+    stats: EnvCacheStats,
 }
 
 impl InstallCache {
@@ -220,9 +380,15 @@ impl InstallCache {
     pub(crate) fn new() -> Self {
         Self {
             store_hooks: OnceCell::new(),
+            stats: EnvCacheStats::default(),
         }
     }
 
+    /// Return how many hook environments were reused, installed, or rebuilt so far.
+    pub(crate) fn stats(&self) -> EnvCacheStats {
+        self.stats
+    }
+
     /// Return environments loaded from the store hooks directory.
     ///
     /// Loading is lazy and happens at most once per `InstallCache`. Callers should hold the store
@@ -245,13 +411,47 @@ impl InstallCache {
         store: &Store,
         hook: Arc<Hook>,
     ) -> Option<InstalledHook> {
+        let info = self.installed_info(store, &hook).await?;
+        Some(InstalledHook::Installed { hook, info })
+    }
+
+    /// Like [`InstallCache::installed_hook`], but records a cache hit in [`InstallCache::stats`].
+    pub(crate) async fn reuse_installed_hook(
+        &mut self,
+        store: &Store,
+        hook: Arc<Hook>,
+    ) -> Option<InstalledHook> {
+        let installed_hook = self.installed_hook(store, hook).await?;
+        self.stats.reused += 1;
+        Some(installed_hook)
+    }
+
+    /// Return whether the store has an environment for this hook that failed its health check.
+    async fn has_unhealthy_env(&self, store: &Store, hook: &Hook) -> bool {
+        let Some(requirement) = hook.environment_requirement() else {
+            return false;
+        };
+        for env in self.installed_hooks(store).await {
+            if requirement.is_satisfied_by(env.info_ref()) && !env.ensure_healthy().await {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Return the install info of a healthy environment from the store cache for this hook.
+    ///
+    /// Returns `None` if the hook does not need an environment or none is installed yet.
+    pub(crate) async fn installed_info(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> Option<Arc<InstallInfo>> {
         let requirement = hook.environment_requirement()?;
         for env in self.installed_hooks(store).await {
             if requirement.is_satisfied_by(env.info_ref()) && env.ensure_healthy().await {
-                return Some(InstalledHook::Installed {
-                    hook,
-                    info: env.info(),
-                });
+                return Some(env.info());
             }
         }
 
@@ -42,16 +42,14 @@ impl IntentToAddRestorer {
         }
 
         // TODO: xargs
-        git_cmd()?
-            .arg("rm")
-            .arg("--cached")
-            .arg("--")
-            .file_args(&files)
-            .check(true)
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .await?;
+        git::output_retrying_index_lock(
+            git_cmd()?
+                .arg("rm")
+                .arg("--cached")
+                .arg("--")
+                .file_args(&files),
+        )
+        .await?;
 
         Ok(Self(files))
     }
@@ -65,10 +63,11 @@ impl IntentToAddRestorer {
                 .arg("--intent-to-add")
                 .arg("--")
                 // TODO: xargs
-                .args(&self.0)
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::null())
-                .status()?;
+                .args(&self.0);
+            let output = git::output_retrying_index_lock_blocking(&mut cmd)?;
+            if !output.status.success() {
+                anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+            }
         }
         Ok(())
     }
@@ -156,15 +155,15 @@ impl UnstagedChangesRestorer {
 
     fn checkout_working_tree(root: &Path) -> Result<()> {
         let mut cmd = Command::new(GIT.as_ref()?);
-        let output = git::apply_git_work_tree(&mut cmd)
+        git::apply_git_work_tree(&mut cmd)
             .arg("-c")
             .arg("submodule.recurse=0")
             .arg("checkout")
             .arg("--")
             .arg(root)
             // prevent recursive post-checkout hooks
-            .env(EnvVars::PREK_INTERNAL__SKIP_POST_CHECKOUT, "1")
-            .output()?;
+            .env(EnvVars::PREK_INTERNAL__SKIP_POST_CHECKOUT, "1");
+        let output = git::output_retrying_index_lock_blocking(&mut cmd)?;
         if output.status.success() {
             Ok(())
         } else {
@@ -1,17 +1,26 @@
+pub(crate) use collector::{CommandFileCollector, FileCollector, JujutsuFileCollector};
+pub(crate) use env_links::{EnvLink, EnvLinks, link_dir, remove_link};
 pub(crate) use filter::{
     CollectOptions, FileSelection, FileTagCache, FileTagFilter, HookFileFilter, ProjectFiles,
     RunFileIndex, RunInput, collect_run_input,
 };
-pub(crate) use install::{InstallCache, install_hooks};
+pub(crate) use install::{InstallCache, install_hooks, plan_new_envs};
 pub(crate) use reporter::{HookRunReporter, project_status_marker};
-pub(crate) use run::run;
-pub(crate) use selector::{ConfiguredHook, GroupFilters, SelectorSource, Selectors};
+pub(crate) use run::{RunSettings, run};
+pub(crate) use selector::{ConfiguredHook, GroupFilters, RepoFilters, SelectorSource, Selectors};
 
+mod collector;
+mod dedup;
 mod diff;
+mod env_links;
 mod filter;
+mod github;
+mod incremental;
 mod install;
 mod keeper;
+mod report;
 mod reporter;
 #[allow(clippy::module_inception)]
 mod run;
+mod schedule;
 mod selector;
@@ -0,0 +1,322 @@
+//! Machine-readable run reports for `prek run --report`.
+//!
+//! Every hook result of the run is collected and written to the requested files once the run
+//! finishes, so CI systems can track hook durations and failures over time, or render them as
+//! test results with the JUnit XML format.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use console::strip_ansi_codes;
+use serde::Serialize;
+
+use crate::cli::run::github::{HookOutcome, HookSummary};
+use crate::cli::{RunReport, RunReportFormat};
+use crate::warn_user;
+
+/// Version of the JSON report layout, bumped on incompatible changes.
+const JSON_REPORT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    version: u32,
+    /// Whether the run passed, as reflected by the exit status.
+    success: bool,
+    /// Total wall-clock duration of the hooks, in milliseconds.
+    duration_ms: u128,
+    hooks: Vec<JsonHook<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonHook<'a> {
+    id: &'a str,
+    name: &'a str,
+    project: &'a str,
+    status: HookOutcome,
+    duration_ms: u128,
+    exit_code: i32,
+    /// Number of files passed to the hook.
+    files: usize,
+    files_modified: bool,
+}
+
+/// Collects hook results and writes them to the `--report` files.
+#[derive(Debug)]
+pub(crate) struct RunReports {
+    targets: Vec<RunReport>,
+    hooks: Vec<HookSummary>,
+}
+
+impl RunReports {
+    pub(crate) fn new(targets: Vec<RunReport>) -> Option<Self> {
+        (!targets.is_empty()).then(|| Self {
+            targets,
+            hooks: Vec::new(),
+        })
+    }
+
+    pub(crate) fn record(&mut self, hook: HookSummary) {
+        self.hooks.push(hook);
+    }
+
+    /// Write every report, warning about the ones that could not be written.
+    pub(crate) fn write(&self, success: bool, duration: Duration) {
+        for target in &self.targets {
+            let rendered = match target.format {
+                RunReportFormat::Json => self.render_json(success, duration),
+                RunReportFormat::Junit => Ok(self.render_junit(duration)),
+            };
+            let result = rendered.and_then(|content| {
+                fs_err::write(&target.path, content).with_context(|| {
+                    format!("Failed to write run report to `{}`", target.path.display())
+                })
+            });
+            if let Err(err) = result {
+                warn_user!("{err:#}");
+            }
+        }
+    }
+
+    fn render_json(&self, success: bool, duration: Duration) -> Result<String> {
+        let report = JsonReport {
+            version: JSON_REPORT_VERSION,
+            success,
+            duration_ms: duration.as_millis(),
+            hooks: self
+                .hooks
+                .iter()
+                .map(|hook| JsonHook {
+                    id: &hook.id,
+                    name: &hook.name,
+                    project: &hook.project,
+                    status: hook.outcome,
+                    duration_ms: hook.duration.as_millis(),
+                    exit_code: hook.exit_code,
+                    files: hook.files,
+                    files_modified: hook.files_modified,
+                })
+                .collect(),
+        };
+        let mut json = serde_json::to_string_pretty(&report)?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Render one `<testsuite>` per project with one `<testcase>` per hook.
+    fn render_junit(&self, duration: Duration) -> String {
+        let mut projects: Vec<(&str, Vec<&HookSummary>)> = Vec::new();
+        for hook in &self.hooks {
+            match projects.iter_mut().find(|(name, _)| *name == hook.project) {
+                Some((_, hooks)) => hooks.push(hook),
+                None => projects.push((&hook.project, vec![hook])),
+            }
+        }
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let (failures, skipped) = junit_counts(&self.hooks);
+        let _ = writeln!(
+            xml,
+            r#"<testsuites name="prek" tests="{}" failures="{failures}" skipped="{skipped}" time="{:.3}">"#,
+            self.hooks.len(),
+            duration.as_secs_f64(),
+        );
+        for (project, hooks) in projects {
+            let (failures, skipped) = junit_counts(hooks.iter().copied());
+            let _ = writeln!(
+                xml,
+                r#"  <testsuite name="{}" tests="{}" failures="{failures}" skipped="{skipped}" time="{:.3}">"#,
+                escape_xml(project),
+                hooks.len(),
+                hooks
+                    .iter()
+                    .map(|hook| hook.duration)
+                    .sum::<Duration>()
+                    .as_secs_f64(),
+            );
+            for hook in hooks {
+                write_testcase(&mut xml, hook);
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Count the failed and skipped hooks of a JUnit test suite.
+fn junit_counts<'a>(hooks: impl IntoIterator<Item = &'a HookSummary>) -> (usize, usize) {
+    hooks
+        .into_iter()
+        .fold((0, 0), |(failures, skipped), hook| match hook.outcome {
+            HookOutcome::Failed => (failures + 1, skipped),
+            HookOutcome::Skipped | HookOutcome::DryRun => (failures, skipped + 1),
+            HookOutcome::Passed | HookOutcome::Warning => (failures, skipped),
+        })
+}
+
+fn write_testcase(xml: &mut String, hook: &HookSummary) {
+    let _ = write!(
+        xml,
+        r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+        escape_xml(&hook.id),
+        escape_xml(&hook.project),
+        hook.duration.as_secs_f64(),
+    );
+    let output = strip_ansi_codes(&hook.output);
+    let output = output.trim_end();
+    match hook.outcome {
+        HookOutcome::Failed => {
+            let message = if hook.exit_code == 0 && hook.files_modified {
+                "files were modified by this hook".to_string()
+            } else {
+                format!("exit code: {}", hook.exit_code)
+            };
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                escape_xml(&message),
+                escape_xml(output),
+            );
+        }
+        HookOutcome::Skipped => {
+            xml.push_str(">\n      <skipped message=\"no files to check\"/>\n    </testcase>\n");
+        }
+        HookOutcome::DryRun => {
+            xml.push_str(">\n      <skipped message=\"dry run\"/>\n    </testcase>\n");
+        }
+        HookOutcome::Passed | HookOutcome::Warning if !output.is_empty() => {
+            let _ = writeln!(
+                xml,
+                ">\n      <system-out>{}</system-out>\n    </testcase>",
+                escape_xml(output),
+            );
+        }
+        HookOutcome::Passed | HookOutcome::Warning => xml.push_str("/>\n"),
+    }
+}
+
+/// Escape text for XML attributes and content, dropping characters XML 1.0 does not allow.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{RunReports, escape_xml};
+    use crate::cli::run::github::{HookOutcome, HookSummary};
+    use crate::cli::{RunReport, RunReportFormat};
+
+    #[test]
+    fn render_json_report() {
+        let mut reports = RunReports::new(vec![RunReport {
+            format: RunReportFormat::Json,
+            path: "report.json".into(),
+        }])
+        .unwrap();
+        reports.record(HookSummary {
+            project: ".".to_string(),
+            id: "ruff".to_string(),
+            name: "ruff check".to_string(),
+            outcome: HookOutcome::Failed,
+            duration: Duration::from_millis(1250),
+            exit_code: 1,
+            files: 3,
+            files_modified: true,
+            output: "error".to_string(),
+        });
+
+        insta::assert_snapshot!(reports.render_json(false, Duration::from_millis(1500)).unwrap(), @r#"
+        {
+          "version": 1,
+          "success": false,
+          "duration_ms": 1500,
+          "hooks": [
+            {
+              "id": "ruff",
+              "name": "ruff check",
+              "project": ".",
+              "status": "failed",
+              "duration_ms": 1250,
+              "exit_code": 1,
+              "files": 3,
+              "files_modified": true
+            }
+          ]
+        }
+        "#);
+    }
+
+    #[test]
+    fn render_junit_report() {
+        let mut reports = RunReports::new(vec![RunReport {
+            format: RunReportFormat::Junit,
+            path: "report.xml".into(),
+        }])
+        .unwrap();
+        for (project, id, outcome, exit_code, files_modified, output) in [
+            (
+                ".",
+                "ruff",
+                HookOutcome::Failed,
+                1,
+                false,
+                "\u{1b}[31merror\u{1b}[0m: a < b\n",
+            ),
+            (".", "format", HookOutcome::Failed, 0, true, ""),
+            (".", "typos", HookOutcome::Passed, 0, false, ""),
+            ("app", "mypy", HookOutcome::Skipped, 0, false, ""),
+        ] {
+            reports.record(HookSummary {
+                project: project.to_string(),
+                id: id.to_string(),
+                name: id.to_string(),
+                outcome,
+                duration: Duration::from_millis(250),
+                exit_code,
+                files: 1,
+                files_modified,
+                output: output.to_string(),
+            });
+        }
+
+        insta::assert_snapshot!(reports.render_junit(Duration::from_millis(1500)), @r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="prek" tests="4" failures="2" skipped="1" time="1.500">
+          <testsuite name="." tests="3" failures="2" skipped="0" time="0.750">
+            <testcase name="ruff" classname="." time="0.250">
+              <failure message="exit code: 1">error: a &lt; b</failure>
+            </testcase>
+            <testcase name="format" classname="." time="0.250">
+              <failure message="files were modified by this hook"></failure>
+            </testcase>
+            <testcase name="typos" classname="." time="0.250"/>
+          </testsuite>
+          <testsuite name="app" tests="1" failures="0" skipped="1" time="0.250">
+            <testcase name="mypy" classname="app" time="0.250">
+              <skipped message="no files to check"/>
+            </testcase>
+          </testsuite>
+        </testsuites>
+        "#);
+    }
+
+    #[test]
+    fn escape_xml_drops_control_characters() {
+        assert_eq!(escape_xml("a\u{7}b & \"c\"\n"), "ab &amp; &quot;c&quot;\n");
+    }
+}
@@ -9,7 +9,9 @@
 //! A hook run is represented by a `HookBar`. Its main progress row is always
 //! present while the hook is active or waiting for its final result. If the hook
 //! streams output for long enough, up to `HOOK_OUTPUT_PREVIEW_LINES` preview
-//! rows are inserted directly below the main row.
+//! rows are inserted directly below the main row. Hooks with `stream_output`
+//! skip the preview and print each complete output line above the progress
+//! rows instead, labeled with the hook id.
 //!
 //! `HookRunReporter::running` owns active `HookBar`s. `HookGroup` owns the
 //! per-project rows that can outlive an active hook: the optional project
@@ -55,17 +57,19 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::collections::hash_map::Entry;
+use std::fmt::Write as _;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use console::{Term, strip_ansi_codes};
+use encoding_rs::Encoding;
 use indicatif::{ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
 use rustc_hash::FxHashMap;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::cli::reporter::{ProgressReporter, SPINNER_TICKS, set_current_reporter};
-use crate::hook::Hook;
+use crate::hook::{Hook, decode_output};
 use crate::printer::Printer;
 use crate::process::OutputSink;
 use crate::workspace;
@@ -89,6 +93,8 @@ struct HookBar {
     output_bars: Vec<ProgressBar>,
     /// Rolling text state rendered into `output_bars`.
     output_preview: OutputPreview,
+    /// Line buffer for hooks that stream their output instead of previewing it.
+    streamed_output: Option<StreamedOutput>,
     /// Hook start time, used to avoid flashing output preview rows for fast hooks.
     started_at: Instant,
     /// Result is filled by `on_run_result`; it stays `None` between completion
@@ -103,7 +109,8 @@ impl HookBar {
             line_order,
             progress,
             output_bars: Vec::new(),
-            output_preview: OutputPreview::default(),
+            output_preview: OutputPreview::new(hook.output_encoding),
+            streamed_output: hook.stream_output.then(|| StreamedOutput::new(hook)),
             started_at: Instant::now(),
             passed: None,
         }
@@ -366,12 +373,20 @@ struct OutputPreview {
     lines: Vec<String>,
     line_open: bool,
     pending_cr: bool,
+    encoding: Option<&'static Encoding>,
 }
 
 impl OutputPreview {
+    fn new(encoding: Option<&'static Encoding>) -> Self {
+        Self {
+            encoding,
+            ..Self::default()
+        }
+    }
+
     fn push_chunk(&mut self, chunk: &[u8]) {
         // Preview text is lossy by design: the full bytes are still collected by `process`.
-        let text = String::from_utf8_lossy(chunk);
+        let text = decode_output(self.encoding, chunk);
         let text = strip_ansi_codes(&text);
         for ch in text.chars().filter(|ch| is_preview_char(*ch)) {
             if self.pending_cr {
@@ -423,6 +438,64 @@ impl OutputPreview {
     }
 }
 
+/// Output of a hook that streams its lines live instead of previewing them.
+///
+/// Output is only printed a full line at a time, so lines from concurrent hooks
+/// never interleave mid-line.
+#[derive(Debug)]
+struct StreamedOutput {
+    /// Label printed before every streamed line.
+    label: String,
+    /// Bytes of the current, unterminated line.
+    pending: Vec<u8>,
+    encoding: Option<&'static Encoding>,
+}
+
+impl StreamedOutput {
+    fn new(hook: &Hook) -> Self {
+        Self {
+            label: format!("[{}]", hook.id),
+            pending: Vec::new(),
+            encoding: hook.output_encoding,
+        }
+    }
+
+    /// Buffers one output chunk and returns the lines it completed.
+    fn push_chunk(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+        let Some(end) = self.pending.iter().rposition(|byte| *byte == b'\n') else {
+            return Vec::new();
+        };
+
+        let rest = self.pending.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        complete[..end]
+            .split(|byte| *byte == b'\n')
+            .map(|line| self.format_line(line))
+            .collect()
+    }
+
+    /// Returns the last line if the hook exited without a trailing newline.
+    fn finish(mut self) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let line = std::mem::take(&mut self.pending);
+        Some(self.format_line(&line))
+    }
+
+    fn format_line(&self, line: &[u8]) -> String {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        // Keep what a terminal would show after carriage-return overwrites, e.g. progress bars.
+        let line = line.rsplit(|byte| *byte == b'\r').next().unwrap_or(line);
+        format!(
+            "{} {}",
+            self.label.dimmed(),
+            decode_output(self.encoding, line)
+        )
+    }
+}
+
 fn is_preview_char(ch: char) -> bool {
     matches!(ch, '\n' | '\r' | '\t') || !ch.is_control()
 }
@@ -589,6 +662,12 @@ impl HookRunReporter {
             let Some(run_bar) = running.get_mut(&id) else {
                 return;
             };
+            if let Some(streamed_output) = &mut run_bar.streamed_output {
+                let lines = streamed_output.push_chunk(chunk);
+                drop(running);
+                self.print_streamed_output(&lines);
+                return;
+            }
             if !run_bar.push_output(&self.reporter, width, chunk) {
                 return;
             }
@@ -609,10 +688,14 @@ impl HookRunReporter {
             Orphan(HookBar),
         }
 
-        let placement = {
+        let (placement, last_line) = {
             let mut running = self.running.lock().unwrap();
             let mut completed = running.remove(&id).unwrap();
             self.reporter.root.inc(1);
+            let last_line = completed
+                .streamed_output
+                .take()
+                .and_then(StreamedOutput::finish);
 
             // Keep the completed line visible until the group result is rendered.
             let progress = &completed.progress;
@@ -622,15 +705,20 @@ impl HookRunReporter {
             // Move the hook into its group before releasing `running`, so layout
             // accounting never observes the main row as neither running nor completed.
             let mut groups = self.groups.lock().unwrap();
-            if let Some(group) = groups.get_mut(&completed.hook_key.project_idx) {
+            let placement = if let Some(group) = groups.get_mut(&completed.hook_key.project_idx) {
                 let output_bars = std::mem::take(&mut completed.output_bars);
                 group.completed.push(completed);
                 CompletedPlacement::Stored(output_bars)
             } else {
                 CompletedPlacement::Orphan(completed)
-            }
+            };
+            (placement, last_line)
         };
 
+        if let Some(last_line) = last_line {
+            self.print_streamed_output(&[last_line]);
+        }
+
         match placement {
             CompletedPlacement::Stored(output_bars) => {
                 for output_bar in output_bars {
@@ -641,6 +729,20 @@ impl HookRunReporter {
         }
     }
 
+    /// Print streamed hook output above the progress rows.
+    fn print_streamed_output(&self, lines: &[String]) {
+        if lines.is_empty() {
+            return;
+        }
+
+        self.suspend(|| {
+            let mut stdout = self.reporter.printer.stdout();
+            for line in lines {
+                let _ = writeln!(stdout, "{line}");
+            }
+        });
+    }
+
     pub fn on_run_result(&self, hook: &Hook, passed: bool) {
         let hook_key = HookKey::from_hook(hook);
         let progress = {
@@ -839,6 +941,7 @@ mod tests {
             progress: ProgressBar::hidden(),
             output_bars: Vec::new(),
             output_preview: OutputPreview::default(),
+            streamed_output: None,
             started_at: Instant::now(),
             passed,
         }
@@ -854,6 +957,7 @@ mod tests {
             progress: progress_bar(reporter),
             output_bars: Vec::new(),
             output_preview: OutputPreview::default(),
+            streamed_output: None,
             started_at,
             passed: None,
         }
@@ -978,6 +1082,38 @@ mod tests {
         assert_eq!(preview.visible_lines(), ["two", "three", "four"]);
     }
 
+    fn streamed_lines(lines: &[String]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| strip_ansi_codes(line).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn streamed_output_emits_complete_lines() {
+        let mut output = StreamedOutput {
+            label: "[pytest]".to_string(),
+            pending: Vec::new(),
+        };
+
+        assert!(output.push_chunk(b"collecting").is_empty());
+        assert_eq!(
+            streamed_lines(&output.push_chunk(b" 3 items\r\ntest_a PASSED\ntest_b")),
+            ["[pytest] collecting 3 items", "[pytest] test_a PASSED"]
+        );
+        assert_eq!(
+            streamed_lines(&output.push_chunk(b" 50%\rtest_b PASSED\n")),
+            ["[pytest] test_b PASSED"]
+        );
+        assert!(output.push_chunk(b"done").is_empty());
+        assert_eq!(
+            output
+                .finish()
+                .map(|line| strip_ansi_codes(&line).into_owned()),
+            Some("[pytest] done".to_string())
+        );
+    }
+
     #[test]
     fn hook_output_preview_is_buffered_before_delay() {
         let reporter = HookRunReporter::new(Printer::Silent, 80, false);
@@ -1,69 +1,142 @@
+use std::cmp::Reverse;
 use std::fmt::Write as _;
 use std::io::Write as _;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use futures_util::stream::{FuturesUnordered, StreamExt};
-use mea::semaphore::Semaphore;
 use owo_colors::OwoColorize;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use prek_consts::{PRE_COMMIT_CONFIG_YAML, PREK_TOML};
 use prek_identify::{TagSet, tags_from_path};
-use rustc_hash::{FxBuildHasher, FxHashMap};
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 use tracing::{debug, error, trace};
 use unicode_width::UnicodeWidthStr;
 
 use crate::cli::reporter::{HookInitReporter, HookInstallReporter};
+use crate::cli::run::dedup::{self, DedupGroup, HookDedup, SharedRun};
 use crate::cli::run::diff::DiffTracker;
 use crate::cli::run::filter::{RunInputMode, stage_uses_message_file_input};
-use crate::cli::run::install::{InstallCache, install_hooks};
+use crate::cli::run::github::{GithubSummary, HookOutcome, HookSummary};
+use crate::cli::run::incremental::IncrementalRun;
+use crate::cli::run::install::{EnvCacheStats, InstallCache, install_hooks};
 use crate::cli::run::keeper::WorkTreeKeeper;
+use crate::cli::run::report::RunReports;
+use crate::cli::run::schedule::{HookDurations, HookPermits, HookSchedule, conflict_waves};
 use crate::cli::run::{
-    CollectOptions, FileSelection, FileTagCache, GroupFilters, HookFileFilter, HookRunReporter,
-    ProjectFiles, RunFileIndex, RunInput, Selectors, collect_run_input, project_status_marker,
+    CollectOptions, CommandFileCollector, FileCollector, FileSelection, FileTagCache, GroupFilters,
+    HookFileFilter, HookRunReporter, JujutsuFileCollector, ProjectFiles, RepoFilters, RunFileIndex,
+    RunInput, Selectors, collect_run_input, project_status_marker,
 };
-use crate::cli::{ExitStatus, RunExtraArgs};
+use crate::cli::{ExitStatus, RunArgs, RunExtraArgs, RunOptions, RunReport, flag};
 use crate::config::{PassFilenames, Stage};
 use crate::fs::CWD;
 use crate::git::GIT_ROOT;
 use crate::hook::{Hook, InstalledHook};
 use crate::printer::Printer;
-use crate::run::{HOOK_CONCURRENCY, USE_COLOR};
+use crate::run::{HOOK_CONCURRENCY, LanguageLimits, USE_COLOR};
 use crate::store::Store;
-use crate::workspace::{HookInitFilters, Project, Workspace};
-use crate::{fs, git, hooks, warn_user};
+use crate::workspace::{HookInitFilters, Project, Refresh, Workspace};
+use crate::{fs, git, hooks, jj, warn_user};
+
+/// Everything `prek run` needs to select and run hooks, besides the store and the printer.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default)]
+pub(crate) struct RunSettings {
+    pub(crate) config: Option<PathBuf>,
+    pub(crate) includes: Vec<String>,
+    pub(crate) skips: Vec<String>,
+    pub(crate) groups: Vec<String>,
+    pub(crate) no_groups: Vec<String>,
+    pub(crate) repos: Vec<String>,
+    pub(crate) hook_stage: Option<Stage>,
+    pub(crate) flatten: bool,
+    pub(crate) dedup_hooks: bool,
+    pub(crate) incremental: bool,
+    /// Collect files with `jj` in a colocated `jj` repo.
+    pub(crate) use_jj: bool,
+    pub(crate) selection: FileSelection,
+    pub(crate) show_diff_on_failure: bool,
+    pub(crate) fail_fast: Option<bool>,
+    pub(crate) exit_zero: bool,
+    pub(crate) dry_run: bool,
+    pub(crate) github_summary: bool,
+    pub(crate) reports: Vec<RunReport>,
+    pub(crate) refresh: Refresh,
+    pub(crate) extra_args: RunExtraArgs,
+    pub(crate) verbose: bool,
+}
+
+impl RunSettings {
+    /// Settings of a `prek run` invocation, without the global options.
+    pub(crate) fn from_args(args: RunArgs) -> Self {
+        Self {
+            groups: args.groups,
+            no_groups: args.no_groups,
+            repos: args.repos,
+            hook_stage: args.stage,
+            flatten: args.flatten,
+            dedup_hooks: args.dedup_hooks,
+            incremental: args.incremental,
+            ..Self::from(args.options)
+        }
+    }
+}
+
+impl From<RunOptions> for RunSettings {
+    fn from(options: RunOptions) -> Self {
+        Self {
+            includes: options.includes,
+            skips: options.skips,
+            selection: options.file_selection.into(),
+            show_diff_on_failure: options.show_diff_on_failure,
+            fail_fast: flag(options.fail_fast, options.no_fail_fast),
+            exit_zero: options.exit_zero,
+            dry_run: options.dry_run,
+            github_summary: options.github_summary,
+            reports: options.reports,
+            extra_args: options.extra,
+            ..Self::default()
+        }
+    }
+}
 
-#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn run(
     store: &Store,
-    config: Option<PathBuf>,
-    includes: Vec<String>,
-    skips: Vec<String>,
-    groups: Vec<String>,
-    no_groups: Vec<String>,
-    hook_stage: Option<Stage>,
-    selection: FileSelection,
-    show_diff_on_failure: bool,
-    fail_fast: Option<bool>,
-    dry_run: bool,
-    refresh: bool,
-    extra_args: RunExtraArgs,
-    verbose: bool,
+    mut settings: RunSettings,
     printer: Printer,
 ) -> Result<ExitStatus> {
     // Prevent recursive post-checkout hooks.
-    if hook_stage == Some(Stage::PostCheckout)
+    if settings.hook_stage == Some(Stage::PostCheckout)
         && EnvVars.is_set(EnvVars::PREK_INTERNAL__SKIP_POST_CHECKOUT)
     {
         return Ok(ExitStatus::Success);
     }
 
     // Ensure we are in a git repository.
-    LazyLock::force(&GIT_ROOT).as_ref()?;
-
-    let should_stash = selection.requires_clean_worktree();
+    let git_root = LazyLock::force(&GIT_ROOT).as_ref()?;
+    // `jj` snapshots the working copy on every command and has no staging area, so a colocated
+    // repo needs neither a stash nor staged configs. Git hooks still run against the Git index,
+    // e.g. for a `git commit` in the colocated repo.
+    let colocated_jj = settings.use_jj && jj::is_colocated(git_root);
+
+    let selection = std::mem::take(&mut settings.selection)
+        .resolve_refs()
+        .await?;
+
+    let should_stash = selection.requires_clean_worktree() && !colocated_jj;
+    // Only plain `--all-files` runs can skip files checked by an earlier run.
+    settings.incremental &= !settings.dry_run
+        && matches!(
+            selection,
+            FileSelection::All {
+                from_ref: None,
+                to_ref: None
+            }
+        );
 
     // Check if we have unresolved merge conflict files and fail fast.
     if should_stash && git::has_unmerged_paths().await? {
@@ -72,16 +145,35 @@ pub(crate) async fn run(
         );
     }
 
-    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
-    let selectors = Selectors::load(&includes, &skips, &workspace_root)?;
-    let group_filters = GroupFilters::parse(&groups, &no_groups)?;
+    let workspace_root = Workspace::find_root(settings.config.as_deref(), &CWD)?;
+    let selectors = Selectors::load(&settings.includes, &settings.skips, &workspace_root)?;
+    let group_filters = GroupFilters::parse(&settings.groups, &settings.no_groups)?;
     let has_group_filters = group_filters.has_filters();
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), refresh)?;
+    let repo_filters = RepoFilters::parse(&settings.repos)?;
+    let workspace = Workspace::discover(
+        store,
+        workspace_root,
+        settings.config.take(),
+        Some(&selectors),
+        &settings.refresh,
+    )?;
 
     if should_stash {
         workspace.check_configs_staged().await?;
     }
 
+    let collector: Option<Box<dyn FileCollector>> = if let Some(config) = workspace
+        .root_project()
+        .and_then(|project| project.config().file_collector.as_ref())
+    {
+        Some(Box::new(CommandFileCollector::new(config)?))
+    } else if colocated_jj {
+        debug!("Found a colocated jj repo, collecting files with jj");
+        Some(Box::new(JujutsuFileCollector))
+    } else {
+        None
+    };
+
     let reporter = HookInitReporter::new(printer);
     let hooks = {
         let _lock = store.lock_async().await?;
@@ -95,7 +187,8 @@ pub(crate) async fn run(
         workspace
             .init_hooks(
                 store,
-                HookInitFilters::new(Some(&selectors), Some(&group_filters)),
+                HookInitFilters::new(Some(&selectors), Some(&group_filters))
+                    .with_repo_filters(&repo_filters),
                 Some(&reporter),
             )
             .await
@@ -105,11 +198,13 @@ pub(crate) async fn run(
         .into_iter()
         .filter(|h| selectors.matches_hook(h))
         .filter(|h| group_filters.matches_hook(h))
+        .filter(|h| repo_filters.matches_hook(h))
         .map(Arc::new)
         .collect();
 
     selectors.report_unused();
     group_filters.report_unused();
+    repo_filters.report_unused();
 
     if selected_hooks.is_empty() {
         writeln!(
@@ -130,8 +225,12 @@ pub(crate) async fn run(
         return Ok(ExitStatus::Failure);
     }
 
-    let (stage_filter, input_mode) =
-        infer_stage_and_input_mode(hook_stage, has_group_filters, &selected_hooks, &selectors);
+    let (stage_filter, input_mode) = infer_stage_and_input_mode(
+        settings.hook_stage,
+        has_group_filters,
+        &selected_hooks,
+        &selectors,
+    );
     let filtered_hooks: Vec<Arc<Hook>> = if let Some(stage_filter) = stage_filter {
         selected_hooks
             .iter()
@@ -147,6 +246,12 @@ pub(crate) async fn run(
             .collect()
     };
 
+    let filtered_hooks = if settings.flatten {
+        flatten_hooks(filtered_hooks)
+    } else {
+        filtered_hooks
+    };
+
     if filtered_hooks.is_empty() {
         if let Some(stage) = stage_filter {
             debug!("No hooks found for stage {stage} after filtering, exit early");
@@ -166,23 +271,41 @@ pub(crate) async fn run(
 
     // Clear any unstaged changes from the git working directory.
     let mut _guard = None;
+    let mut worktree_cleaned = false;
     if should_stash {
-        _guard = Some(
-            WorkTreeKeeper::clean(store, workspace.root())
-                .await
-                .context("Failed to clean work tree")?,
-        );
+        // While a rebase, cherry-pick or revert is paused, Git moves on to the next commit as soon as
+        // the hooks pass. A patch that fails to re-apply would then leave the unstaged changes
+        // behind in a file the user never sees, so leave the working tree untouched instead.
+        if let Some(operation) = git::pending_operation().await? {
+            debug!("A {operation} is in progress, not stashing unstaged changes");
+            if git::has_unstaged_changes(workspace.root()).await? {
+                warn_user!(
+                    "a {operation} is in progress; unstaged changes are not stashed and hooks will see them"
+                );
+            }
+        } else {
+            _guard = Some(
+                WorkTreeKeeper::clean(store, workspace.root())
+                    .await
+                    .context("Failed to clean work tree")?,
+            );
+            worktree_cleaned = true;
+        }
+    } else if colocated_jj && selection.requires_clean_worktree() {
+        let commit = jj::snapshot(workspace.root()).await?;
+        debug!("Snapshotted the jj working copy as commit {commit}, not stashing");
     }
 
     let (from_ref, to_ref) = selection.refs();
-    set_env_vars(from_ref, to_ref, &extra_args);
+    set_env_vars(from_ref, to_ref, &settings.extra_args);
 
     let input = collect_run_input(
         workspace.root(),
         CollectOptions {
             input_mode,
             selection,
-            commit_msg_filename: extra_args.commit_msg_filename,
+            commit_msg_filename: settings.extra_args.commit_msg_filename.take(),
+            collector,
         },
     )
     .await
@@ -196,8 +319,8 @@ pub(crate) async fn run(
         )
     })?;
 
-    let file_index = RunFileIndex::new(&input, workspace.all_projects());
-    let installed_hooks = ensure_hooks_installed(
+    let file_index = RunFileIndex::new(&input, workspace.all_projects(), settings.flatten);
+    let (installed_hooks, env_stats) = ensure_hooks_installed(
         store,
         printer,
         &workspace,
@@ -206,21 +329,76 @@ pub(crate) async fn run(
         &filtered_hooks,
     )
     .await?;
+    debug!("Hook environments: {env_stats}");
 
-    run_hooks(
+    let status = run_hooks(
         &workspace,
         &input,
         &file_index,
         &installed_hooks,
         store,
-        show_diff_on_failure,
-        fail_fast,
-        dry_run,
-        should_stash,
-        verbose,
+        &mut settings,
+        worktree_cleaned,
         printer,
     )
-    .await
+    .await?;
+
+    if settings.verbose && env_stats.total() > 0 {
+        writeln!(printer.stdout(), "Hook environments: {env_stats}")?;
+    }
+
+    Ok(status)
+}
+
+/// What makes a hook of a nested project the same as a hook of the workspace root.
+type FlattenKey = (String, String, Vec<String>, String, Option<String>);
+
+fn flatten_key(hook: &Hook) -> FlattenKey {
+    (
+        hook.repo().to_string(),
+        hook.id.clone(),
+        hook.args.clone(),
+        hook.entry.raw().to_string(),
+        hook.files.as_ref().map(ToString::to_string),
+    )
+}
+
+/// With `--flatten`, run hooks configured in the workspace root only once, from the root.
+///
+/// Hooks of nested projects identical to a root hook (same repo and rev, id, args, entry and
+/// `files`) are dropped, the root project sees the files of every project. Other hooks still run
+/// once per project.
+fn flatten_hooks(hooks: Vec<Arc<Hook>>) -> Vec<Arc<Hook>> {
+    let root_hooks: FxHashSet<FlattenKey> = hooks
+        .iter()
+        .filter(|hook| hook.project().is_root())
+        .map(|hook| flatten_key(hook))
+        .collect();
+
+    let mut warned = FxHashSet::default();
+    hooks
+        .into_iter()
+        .filter(|hook| {
+            if hook.project().is_root() {
+                return true;
+            }
+            if root_hooks.contains(&flatten_key(hook)) {
+                debug!(
+                    "Skipping hook `{}` of project `{}`, it runs from the workspace root",
+                    hook.id,
+                    hook.project()
+                );
+                return false;
+            }
+            if warned.insert(hook.id.clone()) {
+                warn_user!(
+                    "hook `{}` is not configured the same way in the workspace root, `--flatten` runs it for each project",
+                    hook.id
+                );
+            }
+            true
+        })
+        .collect()
 }
 
 fn infer_stage_and_input_mode(
@@ -308,7 +486,8 @@ fn set_env_vars(from_ref: Option<&str>, to_ref: Option<&str>, args: &RunExtraArg
 ///
 /// Hooks that do not need an environment are returned as-is. Hooks that need an
 /// environment first try the install cache; only cache misses are filtered
-/// against the run input before installation.
+/// against the run input before installation. Also returns how the environments
+/// of the hooks that will run were resolved.
 async fn ensure_hooks_installed<'paths>(
     store: &Store,
     printer: Printer,
@@ -316,7 +495,7 @@ async fn ensure_hooks_installed<'paths>(
     input: &'paths RunInput,
     file_index: &RunFileIndex<'paths>,
     hooks: &[Arc<Hook>],
-) -> Result<Vec<InstalledHook>> {
+) -> Result<(Vec<InstalledHook>, EnvCacheStats)> {
     let env_hooks = hooks
         .iter()
         .filter(|hook| hook.needs_install_env())
@@ -324,10 +503,11 @@ async fn ensure_hooks_installed<'paths>(
         .collect::<Vec<_>>();
 
     if env_hooks.is_empty() {
-        return Ok(hooks
+        let hooks = hooks
             .iter()
             .map(|hook| InstalledHook::NoNeedInstall(hook.clone()))
-            .collect());
+            .collect();
+        return Ok((hooks, EnvCacheStats::default()));
     }
 
     let _lock = store.lock_async().await?;
@@ -338,7 +518,10 @@ async fn ensure_hooks_installed<'paths>(
     // Resolve the cache before file filtering so already-installed hooks keep their exact
     // environment, while missing hooks still avoid install when they would not run.
     for hook in env_hooks {
-        if let Some(installed_hook) = install_cache.installed_hook(store, hook.clone()).await {
+        if let Some(installed_hook) = install_cache
+            .reuse_installed_hook(store, hook.clone())
+            .await
+        {
             installed_by_hook.insert(hook_key(&hook), installed_hook);
         } else {
             missing_env_hooks.push(hook.clone());
@@ -358,14 +541,15 @@ async fn ensure_hooks_installed<'paths>(
         }
     }
 
-    Ok(hooks
+    let hooks = hooks
         .iter()
         .map(|hook| {
             installed_by_hook
                 .remove(&hook_key(hook))
                 .unwrap_or_else(|| InstalledHook::NoNeedInstall(hook.clone()))
         })
-        .collect())
+        .collect();
+    Ok((hooks, install_cache.stats()))
 }
 
 /// Return the missing environment hooks that should actually be installed.
@@ -394,7 +578,7 @@ fn select_hooks_to_install<'paths>(
 
     for project in workspace.all_projects() {
         match input {
-            RunInput::Files(_) => {
+            RunInput::Files { .. } => {
                 let Some(mut hooks) = project_to_hooks.remove(project.as_ref()) else {
                     continue;
                 };
@@ -423,27 +607,44 @@ fn select_hooks_to_install<'paths>(
     Ok(hooks_to_install)
 }
 
-fn hook_key(hook: &Hook) -> (usize, usize) {
+pub(super) fn hook_key(hook: &Hook) -> (usize, usize) {
     // Hook indexes are scoped to a project config, so workspace runs need the project index too.
     (hook.project().idx(), hook.idx)
 }
 
-#[allow(clippy::fn_params_excessive_bools)]
 async fn run_hooks<'paths>(
     workspace: &Workspace,
     input: &'paths RunInput,
     file_index: &RunFileIndex<'paths>,
     hooks: &[InstalledHook],
     store: &Store,
-    show_diff_on_failure: bool,
-    fail_fast: Option<bool>,
-    dry_run: bool,
+    settings: &mut RunSettings,
     worktree_cleaned: bool,
-    verbose: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
     debug_assert!(!hooks.is_empty(), "No hooks to run");
 
+    // Commit message hooks only ever see one file, there is nothing to share.
+    let dedup =
+        if settings.dedup_hooks && !settings.dry_run && matches!(input, RunInput::Files { .. }) {
+            HookDedup::new(hooks)
+        } else {
+            None
+        };
+
+    let incremental = if settings.incremental {
+        let git_root = GIT_ROOT.as_ref()?;
+        match IncrementalRun::new(store, git_root, hooks).await {
+            Ok(incremental) => Some(incremental),
+            Err(err) => {
+                debug!("Failed to prepare an incremental run, checking all files: {err}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Group hooks by project to run them in order of their depth in the workspace.
     #[allow(clippy::mutable_key_type)]
     let mut project_to_hooks: FxHashMap<&Project, Vec<InstalledHook>> =
@@ -460,8 +661,9 @@ async fn run_hooks<'paths>(
     let mut session = HookRunSession::new(
         hooks,
         store,
-        dry_run,
-        verbose,
+        settings,
+        dedup,
+        incremental,
         show_project_headers,
         printer,
     );
@@ -479,12 +681,22 @@ async fn run_hooks<'paths>(
             // If two hooks have the same priority, preserve their original order from the config.
             hooks.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.idx.cmp(&b.idx)));
 
+            let mut groups: Vec<Vec<InstalledHook>> = PriorityGroups::new(hooks).collect();
+            if session.schedule == HookSchedule::SlowestFirst {
+                // Within a priority group every hook may start at once, so queue the slowest first.
+                for group in &mut groups {
+                    group.sort_by_key(|hook| Reverse(session.expected_duration(hook)));
+                }
+            }
+
             project_runs.push(ProjectRun {
+                idx: project_runs.len(),
                 project,
-                project_fail_fast: fail_fast
+                project_fail_fast: settings
+                    .fail_fast
                     .or_else(|| project.config().fail_fast)
                     .unwrap_or(false),
-                groups: PriorityGroups::new(hooks).collect(),
+                groups,
             });
         }
 
@@ -492,12 +704,30 @@ async fn run_hooks<'paths>(
             continue;
         }
 
+        if session.schedule == HookSchedule::SlowestFirst {
+            // Start projects whose first group holds the slowest hook first, so a single slow
+            // hook overlaps with the other projects' work instead of running on its own at the end.
+            // Results are still reported in workspace order.
+            project_runs.sort_by_cached_key(|run| {
+                Reverse(run.groups.first().map_or(Duration::ZERO, |group| {
+                    group
+                        .iter()
+                        .map(|hook| session.expected_duration(hook))
+                        .max()
+                        .unwrap_or_default()
+                }))
+            });
+        }
+
         let project_results = session
             .run_project_level(project_runs, input, file_index, clean_baseline)
             .await?;
         let mut stop_after_level = false;
 
         for project_result in project_results {
+            // Projects of the same level run concurrently, so only touch the index once the
+            // whole level finished.
+            project_result.intent_to_add_new_files().await?;
             stop_after_level |= session.finish_project_run(project_result, show_project_headers)?;
         }
 
@@ -506,7 +736,13 @@ async fn run_hooks<'paths>(
         }
     }
 
-    session.finish(workspace, show_diff_on_failure).await
+    session.save_durations();
+    session.save_incremental();
+    session.write_github_summary();
+    session.write_reports();
+    session
+        .finish(workspace, settings.show_diff_on_failure)
+        .await
 }
 
 struct ProjectDepthGroups<'a> {
@@ -541,6 +777,8 @@ impl<'a> Iterator for ProjectDepthGroups<'a> {
 }
 
 struct ProjectRun<'project> {
+    /// Position in workspace order, used to report results in a stable order.
+    idx: usize,
     project: &'project Project,
     project_fail_fast: bool,
     groups: Vec<Vec<InstalledHook>>,
@@ -556,22 +794,49 @@ impl ProjectRunResult<'_> {
     fn failed(&self) -> bool {
         self.groups.iter().any(ProjectGroupRunResult::failed)
     }
+
+    /// Mark the files created by hooks with `git add --intent-to-add` if the project asks for it.
+    async fn intent_to_add_new_files(&self) -> Result<()> {
+        if !self.project.config().intent_to_add.unwrap_or(false) {
+            return Ok(());
+        }
+
+        let new_files: Vec<PathBuf> = self
+            .groups
+            .iter()
+            .flat_map(|group| group.new_files.iter().cloned())
+            .collect();
+        if new_files.is_empty() {
+            return Ok(());
+        }
+
+        debug!(
+            "Marking {} new files with `git add --intent-to-add`",
+            new_files.len()
+        );
+        git::intent_to_add(&new_files)
+            .await
+            .context("Failed to mark new files with `git add --intent-to-add`")
+    }
 }
 
 struct ProjectGroupRunResult {
     results: Vec<RunResult>,
     modified_files: bool,
+    /// Untracked files created by the group, relative to the repository root.
+    new_files: Vec<PathBuf>,
 }
 
 impl ProjectGroupRunResult {
     fn hook_fail_fast(&self) -> bool {
+        let blocking_modified = self.blocking_modified();
         self.results.iter().any(|result| {
-            let ok = if self.modified_files {
+            let ok = if blocking_modified {
                 false
             } else {
                 result.status.as_bool()
             };
-            !ok && result.hook.fail_fast
+            !ok && result.hook.fail_fast && !result.hook.advisory
         })
     }
 
@@ -579,8 +844,30 @@ impl ProjectGroupRunResult {
         self.modified_files || self.results.iter().any(|result| !result.status.as_bool())
     }
 
+    /// Whether the group modified files and a non-advisory hook that ran may have done so.
+    ///
+    /// Hooks of a group run in parallel, so modifications cannot be attributed to a single hook.
+    /// A group whose only file writers are advisory hooks does not fail the run.
+    fn blocking_modified(&self) -> bool {
+        self.modified_files
+            && self.results.iter().any(|result| {
+                !result.hook.advisory
+                    && !result.status.is_skipped()
+                    && hooks::may_modify_files(&result.hook)
+            })
+    }
+
+    /// Whether a non-advisory hook failed, advisory hooks never stop or fail the run.
+    fn blocking_failed(&self) -> bool {
+        self.blocking_modified()
+            || self
+                .results
+                .iter()
+                .any(|result| !result.hook.advisory && !result.status.as_bool())
+    }
+
     fn should_stop_project(&self, project_fail_fast: bool) -> bool {
-        self.failed() && (project_fail_fast || self.hook_fail_fast())
+        self.blocking_failed() && (project_fail_fast || self.hook_fail_fast())
     }
 }
 
@@ -590,34 +877,102 @@ struct HookRunSession<'a> {
     reporter: HookRunReporter,
     status_printer: StatusPrinter,
     printer: Printer,
+    exit_zero: bool,
     dry_run: bool,
     verbose: bool,
     success: bool,
     file_modified: bool,
+    schedule: HookSchedule,
+    /// Hook duration history, only tracked when scheduling by it.
+    durations: Option<HookDurations>,
+    /// GitHub Actions job summary, only collected with `--github-summary`.
+    github: Option<GithubSummary>,
+    /// Machine-readable run reports, only collected with `--report`.
+    reports: Option<RunReports>,
+    /// When the first hook started, for the total duration in run reports.
+    start: Instant,
+    /// Identical hooks of several projects that run once, with `--dedup-hooks`.
+    dedup: Option<HookDedup>,
+    /// Baselines of an incremental `--all-files` run.
+    incremental: Option<IncrementalRun>,
 }
 
 impl<'a> HookRunSession<'a> {
     fn new(
         hooks: &[InstalledHook],
         store: &'a Store,
-        dry_run: bool,
-        verbose: bool,
+        settings: &mut RunSettings,
+        dedup: Option<HookDedup>,
+        incremental: Option<IncrementalRun>,
         show_project_headers: bool,
         printer: Printer,
     ) -> Self {
         let status_printer = StatusPrinter::for_hooks(hooks, printer);
         let reporter =
             HookRunReporter::new(printer, status_printer.bar_len(), show_project_headers);
+        let schedule = if settings.dry_run {
+            HookSchedule::ConfigOrder
+        } else {
+            HookSchedule::from_env(&EnvVars)
+        };
+        let durations =
+            (schedule == HookSchedule::SlowestFirst).then(|| HookDurations::load(store));
 
         Self {
             store,
             reporter,
             status_printer,
             printer,
-            dry_run,
-            verbose,
+            exit_zero: settings.exit_zero,
+            dry_run: settings.dry_run,
+            verbose: settings.verbose,
             success: true,
             file_modified: false,
+            schedule,
+            durations,
+            github: settings
+                .github_summary
+                .then(|| GithubSummary::new(show_project_headers)),
+            reports: RunReports::new(std::mem::take(&mut settings.reports)),
+            start: Instant::now(),
+            dedup,
+            incremental,
+        }
+    }
+
+    fn expected_duration(&self, hook: &Hook) -> Duration {
+        self.durations
+            .as_ref()
+            .map_or(Duration::ZERO, |durations| durations.get(hook))
+    }
+
+    fn save_durations(&self) {
+        if let Some(durations) = &self.durations
+            && let Err(err) = durations.save(self.store)
+        {
+            debug!("Failed to save hook durations: {err}");
+        }
+    }
+
+    fn save_incremental(&self) {
+        if let Some(incremental) = &self.incremental
+            && let Err(err) = incremental.save(self.store)
+        {
+            debug!("Failed to save incremental baselines: {err}");
+        }
+    }
+
+    fn write_github_summary(&self) {
+        if let Some(github) = &self.github
+            && let Err(err) = github.write()
+        {
+            warn_user!("Failed to write GitHub job summary: {err}");
+        }
+    }
+
+    fn write_reports(&self) {
+        if let Some(reports) = &self.reports {
+            reports.write(self.success || self.exit_zero, self.start.elapsed());
         }
     }
 
@@ -650,14 +1005,18 @@ impl<'a> HookRunSession<'a> {
         file_index: &RunFileIndex<'paths>,
         clean_baseline: bool,
     ) -> Result<Vec<ProjectRunResult<'project>>> {
-        let semaphore = Rc::new(Semaphore::new(*HOOK_CONCURRENCY));
+        let permits = Rc::new(
+            HookPermits::new(*HOOK_CONCURRENCY)
+                .with_language_limits(LanguageLimits::from_settings()),
+        );
         let mut runs = FuturesUnordered::new();
-        for (idx, project_run) in project_runs.into_iter().enumerate() {
-            let semaphore = Rc::clone(&semaphore);
+        for project_run in project_runs {
+            let permits = Rc::clone(&permits);
             runs.push(async move {
+                let idx = project_run.idx;
                 let project = project_run.project;
                 let result = self
-                    .run_project(project_run, input, file_index, clean_baseline, semaphore)
+                    .run_project(project_run, input, file_index, clean_baseline, permits)
                     .await;
                 if let Ok(result) = &result {
                     self.reporter.on_project_complete(project, result.failed());
@@ -681,7 +1040,7 @@ impl<'a> HookRunSession<'a> {
         input: &'paths RunInput,
         file_index: &RunFileIndex<'paths>,
         clean_baseline: bool,
-        semaphore: Rc<Semaphore>,
+        permits: Rc<HookPermits>,
     ) -> Result<ProjectRunResult<'project>> {
         let project_input = ProjectHookInput::new(input, project_run.project, file_index)?;
         trace!(
@@ -713,20 +1072,24 @@ impl<'a> HookRunSession<'a> {
                 .run_priority_group(
                     group_hooks,
                     &project_input,
-                    file_index.tag_cache(),
-                    Rc::clone(&semaphore),
+                    input,
+                    file_index,
+                    Rc::clone(&permits),
                 )
                 .await?;
             let all_skipped = group_results
                 .iter()
                 .all(|result| result.status.is_skipped());
-            let group_modified_files = diff_tracker
+            let group_changes = diff_tracker
                 .changed_after_group(group_may_modify_files, all_skipped)
                 .await?;
 
+            // A deduplicated run may change files of this project from another project.
+            let shared_modified = group_results.iter().any(|result| result.modified_files);
             let group = ProjectGroupRunResult {
                 results: group_results,
-                modified_files: group_modified_files,
+                modified_files: group_changes.modified || shared_modified,
+                new_files: group_changes.new_files,
             };
             self.update_live_priority_group(&group);
             stop_after_level = group.should_stop_project(project_run.project_fail_fast);
@@ -744,44 +1107,211 @@ impl<'a> HookRunSession<'a> {
         })
     }
 
-    async fn run_priority_group(
+    async fn run_priority_group<'paths>(
         &self,
         group_hooks: Vec<InstalledHook>,
-        project_input: &ProjectHookInput<'_, '_>,
-        tag_cache: &FileTagCache<'_>,
-        semaphore: Rc<Semaphore>,
+        project_input: &ProjectHookInput<'_, 'paths>,
+        input: &'paths RunInput,
+        file_index: &RunFileIndex<'paths>,
+        permits: Rc<HookPermits>,
     ) -> Result<Vec<RunResult>> {
+        let tag_cache = file_index.tag_cache();
         debug!(
             "Running priority group with priority {}: {:?}",
             group_hooks[0].priority,
             group_hooks.iter().map(|hook| &hook.id).collect::<Vec<_>>()
         );
 
-        let mut runs = FuturesUnordered::new();
-        for hook in group_hooks {
-            runs.push(run_hook(
-                hook,
-                project_input,
-                tag_cache,
-                self.store,
-                self.dry_run,
-                &self.reporter,
-                Rc::clone(&semaphore),
-            ));
-        }
+        let hooks: Vec<_> = group_hooks
+            .into_iter()
+            .map(|hook| {
+                let input = self.hook_input(&hook, project_input, tag_cache);
+                (hook, input)
+            })
+            .collect();
+        let waves = if self.dry_run {
+            vec![hooks]
+        } else {
+            isolate_project_serial_hooks(split_conflicting_writers(hooks, project_input, tag_cache))
+        };
 
         let mut group_results = Vec::new();
-        while let Some(result) = runs.next().await {
-            group_results.push(result?);
+        for wave in waves {
+            let mut runs = FuturesUnordered::new();
+            for (hook, hook_input) in wave {
+                let weight = self.expected_duration(&hook);
+                runs.push(self.run_hook(
+                    hook,
+                    hook_input,
+                    input,
+                    file_index,
+                    Rc::clone(&permits),
+                    weight,
+                ));
+            }
+
+            while let Some(result) = runs.next().await {
+                group_results.push(result?);
+            }
         }
         Ok(group_results)
     }
 
+    /// The files `hook` runs on, narrowed to the changed files in an incremental run.
+    fn hook_input<'paths>(
+        &self,
+        hook: &Hook,
+        project_input: &ProjectHookInput<'_, 'paths>,
+        tag_cache: &FileTagCache<'paths>,
+    ) -> HookRunInput<'paths> {
+        let input = project_input.run_input_for_hook(hook, tag_cache);
+        if let Some(changed) = self
+            .incremental
+            .as_ref()
+            .and_then(|incremental| incremental.changed_files(hook))
+        {
+            input.retain_changed(hook, changed, || {
+                project_input.candidate_files(hook, tag_cache)
+            })
+        } else {
+            input
+        }
+    }
+
+    async fn run_hook<'paths>(
+        &self,
+        hook: InstalledHook,
+        hook_input: HookRunInput<'paths>,
+        input: &'paths RunInput,
+        file_index: &RunFileIndex<'paths>,
+        permits: Rc<HookPermits>,
+        weight: Duration,
+    ) -> Result<RunResult> {
+        if let Some(group) = self.dedup.as_ref().and_then(|dedup| dedup.group(&hook)) {
+            return self
+                .run_deduplicated(group, hook, hook_input, input, file_index, permits, weight)
+                .await;
+        }
+        run_hook(
+            hook,
+            hook_input,
+            self.store,
+            self.dry_run,
+            &self.reporter,
+            permits,
+            weight,
+        )
+        .await
+    }
+
+    /// Run `hook` as part of its deduplicated group, starting the shared run if no other hook
+    /// of the group started it yet.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_deduplicated<'paths>(
+        &self,
+        group: &DedupGroup,
+        hook: InstalledHook,
+        hook_input: HookRunInput<'paths>,
+        input: &'paths RunInput,
+        file_index: &RunFileIndex<'paths>,
+        permits: Rc<HookPermits>,
+        weight: Duration,
+    ) -> Result<RunResult> {
+        if !hook_input.matched() {
+            return Ok(RunResult::from_status(hook, RunStatus::NoFiles));
+        }
+        let files = hook_input.filename_count();
+
+        let shared = group
+            .run
+            .get_or_try_init(|| self.run_shared(group, input, file_index, permits, weight))
+            .await?;
+        let member = group
+            .hooks
+            .iter()
+            .position(|member| hook_key(member) == hook_key(&hook))
+            .expect("hook belongs to its group");
+        let attributed = shared.attribute(member, &hook.success_exit_codes);
+
+        Ok(RunResult {
+            status: RunStatus::from_exit_status(&hook, attributed.exit_status),
+            hook,
+            duration: shared.duration,
+            exit_status: attributed.exit_status,
+            output: attributed.output,
+            files,
+            modified_files: attributed.modified_files,
+        })
+    }
+
+    /// Run the first hook of `group` once, from the repository root, over the files of every
+    /// hook of the group.
+    async fn run_shared<'paths>(
+        &self,
+        group: &DedupGroup,
+        input: &'paths RunInput,
+        file_index: &RunFileIndex<'paths>,
+        permits: Rc<HookPermits>,
+        weight: Duration,
+    ) -> Result<SharedRun> {
+        let mut files = Vec::with_capacity(group.hooks.len());
+        for member in &group.hooks {
+            let project_input = ProjectHookInput::new(input, member.project(), file_index)?;
+            let member_input = self.hook_input(member, &project_input, file_index.tag_cache());
+            files.push(
+                member_input
+                    .into_repo_root_filenames(member)
+                    .into_iter()
+                    .collect::<FxHashSet<_>>(),
+            );
+        }
+        let union: FxHashSet<PathBuf> = files.iter().flatten().cloned().collect();
+        let mut filenames: Vec<PathBuf> = union.iter().cloned().collect();
+        filenames.sort_unstable();
+
+        let leader = &group.hooks[0];
+        debug!(
+            "Running hook `{leader}` once on {} files of {} projects",
+            filenames.len(),
+            group.hooks.len()
+        );
+
+        let git_root = GIT_ROOT.as_ref()?;
+        let before = hooks::may_modify_files(leader).then(|| dedup::hash_files(git_root, &union));
+        let result = run_hook(
+            leader.run_from_repo_root(),
+            HookRunInput::with_repo_root_filenames(filenames),
+            self.store,
+            false,
+            &self.reporter,
+            permits,
+            weight,
+        )
+        .await?;
+        let modified = if let Some(before) = before {
+            let after = dedup::hash_files(git_root, &union);
+            files
+                .iter()
+                .map(|files| files.iter().any(|file| before.get(file) != after.get(file)))
+                .collect()
+        } else {
+            vec![false; files.len()]
+        };
+
+        Ok(SharedRun {
+            exit_status: result.exit_status,
+            output: result.output,
+            duration: result.duration,
+            files,
+            modified,
+        })
+    }
+
     fn update_live_priority_group(&self, group: &ProjectGroupRunResult) {
         let single_hook_modified_files = group.results.len() == 1 && group.modified_files;
 
         for result in &group.results {
-            let status = if single_hook_modified_files && result.status == RunStatus::Success {
+            let status = if single_hook_modified_files && result.status.ran_ok() {
                 RunStatus::Failed
             } else {
                 result.status
@@ -817,26 +1347,74 @@ impl<'a> HookRunSession<'a> {
         group: ProjectGroupRunResult,
         hook_prefix: &str,
     ) -> Result<()> {
+        let blocking_modified = group.blocking_modified();
         let ProjectGroupRunResult {
             mut results,
             modified_files,
+            ..
         } = group;
         // Print results in a stable order (same order as config within the project).
         results.sort_unstable_by_key(|a| a.hook.idx);
 
         self.file_modified |= modified_files;
 
+        if let Some(durations) = &mut self.durations {
+            for result in &results {
+                if !result.status.is_skipped() {
+                    durations.record(&result.hook, result.duration);
+                }
+            }
+        }
+
+        if let Some(incremental) = &mut self.incremental
+            && !modified_files
+        {
+            for result in &results {
+                if matches!(result.status, RunStatus::Success | RunStatus::NoFiles) {
+                    incremental.record_success(&result.hook);
+                }
+            }
+        }
+
+        if self.github.is_some() || self.reports.is_some() {
+            for result in &results {
+                let files_modified = modified_files && !result.status.is_skipped();
+                let outcome = match result.status {
+                    RunStatus::Success if files_modified => HookOutcome::Failed,
+                    RunStatus::Warning if files_modified => HookOutcome::Failed,
+                    RunStatus::Success => HookOutcome::Passed,
+                    RunStatus::Warning => HookOutcome::Warning,
+                    RunStatus::Failed => HookOutcome::Failed,
+                    RunStatus::NoFiles => HookOutcome::Skipped,
+                    RunStatus::DryRun => HookOutcome::DryRun,
+                };
+                let summary = HookSummary {
+                    project: result.hook.project().display_name(),
+                    id: result.hook.id.clone(),
+                    name: result.hook.name.clone(),
+                    outcome,
+                    duration: result.duration,
+                    exit_code: result.exit_status,
+                    files: result.files,
+                    files_modified,
+                    output: result.hook.decode_output(&result.output).into_owned(),
+                };
+                if let Some(reports) = &mut self.reports {
+                    reports.record(summary.clone());
+                }
+                if let Some(github) = &mut self.github {
+                    github.record(summary);
+                }
+            }
+        }
+
         self.reporter.clear_completed();
         self.reporter
             .suspend(|| self.render_priority_group(&results, modified_files, hook_prefix))?;
 
-        for RunResult { status, .. } in &results {
-            let ok = if modified_files {
-                false
-            } else {
-                status.as_bool()
-            };
-            self.success &= ok;
+        self.success &= !blocking_modified;
+        for RunResult { hook, status, .. } in &results {
+            self.success &= status.as_bool() || hook.advisory;
         }
 
         Ok(())
@@ -887,7 +1465,7 @@ impl<'a> HookRunSession<'a> {
             let prefix = format!("{hook_prefix}{prefix}");
 
             // If a single hook modified files, treat it as failed.
-            let status = if single_hook_modified_files && result.status == RunStatus::Success {
+            let status = if single_hook_modified_files && result.status.ran_ok() {
                 RunStatus::Failed
             } else {
                 result.status
@@ -905,7 +1483,13 @@ impl<'a> HookRunSession<'a> {
                 _ => self.printer.stdout(),
             };
 
-            if self.verbose || result.hook.verbose || status == RunStatus::Failed {
+            if self.verbose
+                || result.hook.verbose
+                || matches!(status, RunStatus::Failed | RunStatus::Warning)
+            {
+                if self.github.is_some() {
+                    writeln!(stdout, "{}", GithubSummary::group_start(&result.hook.id))?;
+                }
                 writeln!(
                     stdout,
                     "{detail_prefix}{}",
@@ -948,7 +1532,7 @@ impl<'a> HookRunSession<'a> {
                         } else {
                             writeln!(stdout)?;
                         }
-                        let text = String::from_utf8_lossy(output);
+                        let text = result.hook.decode_output(output);
                         for line in text.lines() {
                             if line.is_empty() {
                                 if show_group_ui {
@@ -964,6 +1548,9 @@ impl<'a> HookRunSession<'a> {
                         }
                     }
                 }
+                if self.github.is_some() {
+                    writeln!(stdout, "{}", GithubSummary::GROUP_END)?;
+                }
             }
         }
 
@@ -1018,6 +1605,9 @@ impl<'a> HookRunSession<'a> {
 
         if self.success {
             Ok(ExitStatus::Success)
+        } else if self.exit_zero {
+            debug!("Hooks failed, exiting with status code 0 because of `--exit-zero`");
+            Ok(ExitStatus::Success)
         } else {
             Ok(ExitStatus::Failure)
         }
@@ -1065,7 +1655,7 @@ impl<'index, 'paths> ProjectHookInput<'index, 'paths> {
         file_index: &'index RunFileIndex<'paths>,
     ) -> Result<Self> {
         match input {
-            RunInput::Files(_) => Ok(Self::Files(file_index.project_files(project))),
+            RunInput::Files { .. } => Ok(Self::Files(file_index.project_files(project))),
             RunInput::MessageFile(path) => {
                 let tags = match tags_from_path(path) {
                     Ok(tags) => Some(tags),
@@ -1101,6 +1691,15 @@ impl<'index, 'paths> ProjectHookInput<'index, 'paths> {
                 PassFilenames::None => HookRunInput::without_filenames(
                     project_files.has_matching_file(hook, tag_cache),
                 ),
+                PassFilenames::All | PassFilenames::Limited(_) if hook.files_from_repo_root => {
+                    let prefix = hook.project_path_from_repo_root();
+                    HookRunInput::with_repo_root_filenames(
+                        project_files
+                            .matching_filenames(hook, tag_cache)
+                            .into_iter()
+                            .map(|filename| prefix.join(filename)),
+                    )
+                }
                 PassFilenames::All | PassFilenames::Limited(_) => {
                     HookRunInput::with_filenames(project_files.matching_filenames(hook, tag_cache))
                 }
@@ -1109,6 +1708,13 @@ impl<'index, 'paths> ProjectHookInput<'index, 'paths> {
                 if self.matches_hook(hook, tag_cache) {
                     match hook.pass_filenames {
                         PassFilenames::None => HookRunInput::without_filenames(true),
+                        PassFilenames::All | PassFilenames::Limited(_)
+                            if hook.files_from_repo_root =>
+                        {
+                            HookRunInput::with_filename(
+                                hook.project_path_from_repo_root().join(hook_arg),
+                            )
+                        }
                         PassFilenames::All | PassFilenames::Limited(_) => {
                             HookRunInput::with_filename(hook_arg.clone())
                         }
@@ -1120,6 +1726,20 @@ impl<'index, 'paths> ProjectHookInput<'index, 'paths> {
         }
     }
 
+    /// The files a hook may write, based on its file filters.
+    fn candidate_files(&self, hook: &Hook, tag_cache: &FileTagCache<'paths>) -> Vec<&Path> {
+        match self {
+            Self::Files(project_files) => project_files.matching_filenames(hook, tag_cache),
+            Self::MessageFile { hook_arg, .. } => {
+                if self.matches_hook(hook, tag_cache) {
+                    vec![hook_arg.as_path()]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
     fn matches_hook(&self, hook: &Hook, tag_cache: &FileTagCache<'paths>) -> bool {
         match self {
             Self::Files(project_files) => project_files.has_matching_file(hook, tag_cache),
@@ -1136,8 +1756,12 @@ impl<'index, 'paths> ProjectHookInput<'index, 'paths> {
 
 enum HookRunInput<'a> {
     Filenames(Vec<&'a Path>),
+    /// Filenames relative to the git root, for hooks with `files_from_repo_root: true`.
+    RepoRootFilenames(Vec<PathBuf>),
     Filename(PathBuf),
-    WithoutFilenames { matched: bool },
+    WithoutFilenames {
+        matched: bool,
+    },
 }
 
 impl<'a> HookRunInput<'a> {
@@ -1148,6 +1772,13 @@ impl<'a> HookRunInput<'a> {
         Self::Filenames(filenames.into_iter().collect())
     }
 
+    fn with_repo_root_filenames<I>(filenames: I) -> Self
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        Self::RepoRootFilenames(filenames.into_iter().collect())
+    }
+
     fn with_filename(filename: PathBuf) -> Self {
         Self::Filename(filename)
     }
@@ -1156,9 +1787,54 @@ impl<'a> HookRunInput<'a> {
         Self::WithoutFilenames { matched }
     }
 
+    /// Keep only the files in `changed` (relative to the repository root), for an incremental
+    /// `--all-files` run.
+    fn retain_changed<'c, F>(self, hook: &Hook, changed: &FxHashSet<PathBuf>, candidates: F) -> Self
+    where
+        F: FnOnce() -> Vec<&'c Path>,
+    {
+        let prefix = hook.project_path_from_repo_root();
+        let is_changed = |filename: &Path| changed.contains(&prefix.join(filename));
+        match self {
+            Self::Filenames(filenames) => Self::Filenames(
+                filenames
+                    .into_iter()
+                    .filter(|filename| is_changed(filename))
+                    .collect(),
+            ),
+            Self::RepoRootFilenames(filenames) => Self::RepoRootFilenames(
+                filenames
+                    .into_iter()
+                    .filter(|filename| changed.contains(filename))
+                    .collect(),
+            ),
+            Self::WithoutFilenames { matched: true } if !hook.always_run => {
+                Self::WithoutFilenames {
+                    matched: candidates().into_iter().any(is_changed),
+                }
+            }
+            input @ (Self::Filename(_) | Self::WithoutFilenames { .. }) => input,
+        }
+    }
+
+    /// The filenames relative to the repository root.
+    fn into_repo_root_filenames(self, hook: &Hook) -> Vec<PathBuf> {
+        let prefix = hook.project_path_from_repo_root();
+        match self {
+            Self::Filenames(filenames) => filenames
+                .into_iter()
+                .map(|filename| prefix.join(filename))
+                .collect(),
+            Self::RepoRootFilenames(filenames) => filenames,
+            Self::Filename(filename) => vec![prefix.join(filename)],
+            Self::WithoutFilenames { .. } => Vec::new(),
+        }
+    }
+
     fn matched(&self) -> bool {
         match self {
             Self::Filenames(filenames) => !filenames.is_empty(),
+            Self::RepoRootFilenames(filenames) => !filenames.is_empty(),
             Self::Filename(_) => true,
             Self::WithoutFilenames { matched } => *matched,
         }
@@ -1167,6 +1843,7 @@ impl<'a> HookRunInput<'a> {
     fn filename_count(&self) -> usize {
         match self {
             Self::Filenames(filenames) => filenames.len(),
+            Self::RepoRootFilenames(filenames) => filenames.len(),
             Self::Filename(_) => 1,
             Self::WithoutFilenames { .. } => 0,
         }
@@ -1176,9 +1853,11 @@ impl<'a> HookRunInput<'a> {
         // Shuffle the files so that they more evenly fill out the xargs
         // partitions, but do it deterministically in case a hook cares about ordering.
         const SEED: u64 = 1_542_676_187;
-        if let Self::Filenames(filenames) = self {
-            let mut rng = fastrand::Rng::with_seed(SEED);
-            rng.shuffle(filenames);
+        let mut rng = fastrand::Rng::with_seed(SEED);
+        match self {
+            Self::Filenames(filenames) => rng.shuffle(filenames),
+            Self::RepoRootFilenames(filenames) => rng.shuffle(filenames),
+            Self::Filename(_) | Self::WithoutFilenames { .. } => {}
         }
     }
 }
@@ -1186,14 +1865,34 @@ impl<'a> HookRunInput<'a> {
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum RunStatus {
     Success,
+    /// The hook passed with notes, its exit code is one of `warning_exit_codes`.
+    Warning,
     Failed,
     DryRun,
     NoFiles,
 }
 
 impl RunStatus {
+    fn from_exit_status(hook: &Hook, exit_status: i32) -> Self {
+        if hook.success_exit_codes.contains(&exit_status) {
+            Self::Success
+        } else if hook.warning_exit_codes.contains(&exit_status) {
+            Self::Warning
+        } else {
+            Self::Failed
+        }
+    }
+
     fn as_bool(self) -> bool {
-        matches!(self, Self::Success | Self::NoFiles | Self::DryRun)
+        matches!(
+            self,
+            Self::Success | Self::Warning | Self::NoFiles | Self::DryRun
+        )
+    }
+
+    /// Whether the hook ran and passed, with or without warnings.
+    fn ran_ok(self) -> bool {
+        matches!(self, Self::Success | Self::Warning)
     }
 
     fn is_skipped(self) -> bool {
@@ -1209,6 +1908,7 @@ struct StatusPrinter {
 impl StatusPrinter {
     const PASSED: &'static str = "Passed";
     const FAILED: &'static str = "Failed";
+    const WARNING: &'static str = "Warning";
     const SKIPPED: &'static str = "Skipped";
     const DRY_RUN: &'static str = "Dry Run";
     const NO_FILES: &'static str = "(no files to check)";
@@ -1260,6 +1960,11 @@ impl StatusPrinter {
                 Self::PASSED.on_green().to_string(),
                 Self::PASSED.width(),
             ),
+            RunStatus::Warning => (
+                "",
+                Self::WARNING.black().on_yellow().to_string(),
+                Self::WARNING.width(),
+            ),
             RunStatus::Failed => ("", Self::FAILED.on_red().to_string(), Self::FAILED.width()),
         };
         let (prefix, prefix_width) = if prefix.is_empty() {
@@ -1283,9 +1988,13 @@ impl StatusPrinter {
 struct RunResult {
     hook: InstalledHook,
     status: RunStatus,
-    duration: std::time::Duration,
+    duration: Duration,
     exit_status: i32,
     output: Vec<u8>,
+    /// Number of files passed to the hook.
+    files: usize,
+    /// Files of the hook's project changed by a deduplicated run of another project.
+    modified_files: bool,
 }
 
 impl RunResult {
@@ -1293,29 +2002,100 @@ impl RunResult {
         Self {
             hook,
             status,
-            duration: std::time::Duration::ZERO,
+            duration: Duration::ZERO,
             exit_status: 0,
             output: Vec::new(),
+            files: 0,
+            modified_files: false,
+        }
+    }
+}
+
+/// Split a priority group into waves that run one after another, so that builtin and fast-path
+/// hooks which rewrite the same files never run at the same time.
+///
+/// Candidate files come from each hook's file filters. A hook is placed after every earlier hook
+/// (in config order) it shares a candidate file with; hooks within a wave keep the group order.
+/// Other hooks are never delayed, since prek cannot tell whether they write files.
+fn split_conflicting_writers<'paths>(
+    group: Vec<(InstalledHook, HookRunInput<'paths>)>,
+    project_input: &ProjectHookInput<'_, 'paths>,
+    tag_cache: &FileTagCache<'paths>,
+) -> Vec<Vec<(InstalledHook, HookRunInput<'paths>)>> {
+    let mut config_order: Vec<usize> = (0..group.len()).collect();
+    config_order.sort_by_key(|&i| group[i].0.idx);
+
+    let footprints: Vec<_> = config_order
+        .iter()
+        .map(|&i| {
+            let (hook, input) = &group[i];
+            let runs = input.matched() || hook.always_run;
+            (runs && hooks::known_to_modify_files(hook)).then(|| {
+                project_input
+                    .candidate_files(hook, tag_cache)
+                    .into_iter()
+                    .collect::<FxHashSet<_>>()
+            })
+        })
+        .collect();
+
+    let mut hook_waves = vec![0; group.len()];
+    for (wave, &i) in conflict_waves(&footprints).into_iter().zip(&config_order) {
+        hook_waves[i] = wave;
+    }
+
+    let mut waves: Vec<Vec<_>> = Vec::new();
+    for ((hook, input), wave) in group.into_iter().zip(hook_waves) {
+        if wave > 0 {
+            debug!(
+                "Hook `{hook}` may write files used by another hook in its priority group, delaying it"
+            );
+        }
+        if waves.len() <= wave {
+            waves.resize_with(wave + 1, Vec::new);
+        }
+        waves[wave].push((hook, input));
+    }
+    waves
+}
+
+/// Move every `require_serial_project` hook into a wave of its own, after the other hooks of
+/// its wave, so no other hook of the project runs alongside it.
+fn isolate_project_serial_hooks<T>(
+    waves: Vec<Vec<(InstalledHook, T)>>,
+) -> Vec<Vec<(InstalledHook, T)>> {
+    let mut isolated = Vec::with_capacity(waves.len());
+    for wave in waves {
+        let (serial, rest): (Vec<_>, Vec<_>) = wave
+            .into_iter()
+            .partition(|(hook, _)| hook.require_serial_project);
+        if !rest.is_empty() {
+            isolated.push(rest);
+        }
+        for (hook, input) in serial {
+            debug!("Hook `{hook}` requires the project to itself, running it alone");
+            isolated.push(vec![(hook, input)]);
         }
     }
+    isolated
 }
 
 async fn run_hook(
     hook: InstalledHook,
-    project_input: &ProjectHookInput<'_, '_>,
-    tag_cache: &FileTagCache<'_>,
+    mut input: HookRunInput<'_>,
     store: &Store,
     dry_run: bool,
     reporter: &HookRunReporter,
-    semaphore: Rc<Semaphore>,
+    permits: Rc<HookPermits>,
+    weight: Duration,
 ) -> Result<RunResult> {
     let _permit = if dry_run {
         None
     } else {
-        Some(semaphore.acquire(1).await)
+        let language = permits.acquire_language(hook.language).await;
+        Some((language, permits.acquire(weight).await))
     };
 
-    let mut input = project_input.run_input_for_hook(&hook, tag_cache);
     let matched = input.matched();
     let filename_count = input.filename_count();
     trace!(
@@ -1338,6 +2118,10 @@ async fn run_hook(
             HookRunInput::Filenames(filenames) => {
                 hook.language.run(store, &hook, filenames, reporter).await
             }
+            HookRunInput::RepoRootFilenames(filenames) => {
+                let filenames: Vec<_> = filenames.iter().map(PathBuf::as_path).collect();
+                hook.language.run(store, &hook, &filenames, reporter).await
+            }
             HookRunInput::Filename(filename) => {
                 let filenames = [filename.as_path()];
                 hook.language.run(store, &hook, &filenames, reporter).await
@@ -1353,10 +2137,8 @@ async fn run_hook(
 
     let run_status = if dry_run {
         RunStatus::DryRun
-    } else if exit_status == 0 {
-        RunStatus::Success
     } else {
-        RunStatus::Failed
+        RunStatus::from_exit_status(&hook, exit_status)
     };
 
     Ok(RunResult {
@@ -1365,6 +2147,8 @@ async fn run_hook(
         duration,
         exit_status,
         output: hook_output,
+        files: filename_count,
+        modified_files: false,
     })
 }
 
@@ -1381,6 +2165,11 @@ fn dry_run_hook(hook: &InstalledHook, input: &HookRunInput<'_>) -> Result<Vec<u8
                 writeln!(output, "- {}", filename.display())?;
             }
         }
+        HookRunInput::RepoRootFilenames(filenames) => {
+            for filename in filenames {
+                writeln!(output, "- {}", filename.display())?;
+            }
+        }
         HookRunInput::Filename(filename) => {
             writeln!(output, "- {}", filename.display())?;
         }
@@ -0,0 +1,361 @@
+//! Hook scheduling: which hooks get a concurrency slot first.
+//!
+//! Hooks always run within their priority group and project depth level; scheduling only
+//! decides the start order among hooks that are allowed to run at the same time.
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::Result;
+use mea::semaphore::SemaphorePermit;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use rustc_hash::FxHashSet;
+use tokio::sync::oneshot;
+use tracing::debug;
+
+use crate::config::Language;
+use crate::hook::Hook;
+use crate::run::LanguageLimits;
+use crate::store::{CacheBucket, Store};
+use crate::warn_user;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, strum::AsRefStr, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum HookSchedule {
+    /// Start hooks in config order.
+    #[default]
+    ConfigOrder,
+    /// Start the hooks that took the longest in previous runs first.
+    SlowestFirst,
+}
+
+impl HookSchedule {
+    pub(crate) fn from_env(env_vars: &impl EnvVarsRead) -> Self {
+        match env_vars.var(EnvVars::PREK_HOOK_SCHEDULE) {
+            Ok(value) => Self::from_str(&value).unwrap_or_else(|_| {
+                warn_user!(
+                    "Invalid value for {}: {:?}. Expected config-order or slowest-first; using default ({:?})",
+                    EnvVars::PREK_HOOK_SCHEDULE,
+                    value,
+                    Self::default().as_ref(),
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// Durations of previous hook runs, keyed by config file and hook id.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct HookDurations {
+    /// Smoothed duration in milliseconds.
+    hooks: BTreeMap<String, u64>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl HookDurations {
+    fn path(store: &Store) -> PathBuf {
+        store
+            .cache_path(CacheBucket::Prek)
+            .join("hook-durations.json")
+    }
+
+    fn key(hook: &Hook) -> String {
+        format!("{}:{}", hook.project().config_file().display(), hook.id)
+    }
+
+    /// Load the recorded durations, starting empty if there is no usable history.
+    pub(crate) fn load(store: &Store) -> Self {
+        let path = Self::path(store);
+        match fs_err::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                debug!("Failed to deserialize hook durations: {err}");
+                Self::default()
+            }),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    debug!("Failed to read hook durations: {err}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// The expected duration of `hook`, or zero if it has never run.
+    pub(crate) fn get(&self, hook: &Hook) -> Duration {
+        self.hooks
+            .get(&Self::key(hook))
+            .map_or(Duration::ZERO, |millis| Duration::from_millis(*millis))
+    }
+
+    /// Record a new sample, averaged with the previous value to smooth out noisy runs.
+    pub(crate) fn record(&mut self, hook: &Hook, duration: Duration) {
+        let sample = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        self.hooks
+            .entry(Self::key(hook))
+            .and_modify(|millis| *millis = millis.midpoint(sample))
+            .or_insert(sample);
+        self.dirty = true;
+    }
+
+    pub(crate) fn save(&self, store: &Store) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let path = Self::path(store);
+        let parent = path.parent().expect("cache file has a parent");
+        fs_err::create_dir_all(parent)?;
+
+        // Write to a temporary file first, so concurrent runs never observe a partial file.
+        let mut temp = tempfile::NamedTempFile::new_in(parent)?;
+        serde_json::to_writer(&mut temp, self)?;
+        temp.persist(&path)?;
+        Ok(())
+    }
+}
+
+/// Assign each hook of a priority group to a wave, so hooks that may write the same files run
+/// one after another instead of racing.
+///
+/// `footprints` holds, in config order, the candidate files of each hook that may modify files,
+/// or `None` for hooks that never write. A hook lands in the wave after the latest earlier hook
+/// it shares a file with, and in wave 0 otherwise.
+pub(crate) fn conflict_waves<T: Eq + Hash>(footprints: &[Option<FxHashSet<T>>]) -> Vec<usize> {
+    let mut waves: Vec<usize> = Vec::with_capacity(footprints.len());
+    for (i, footprint) in footprints.iter().enumerate() {
+        let wave = footprint.as_ref().map_or(0, |footprint| {
+            footprints[..i]
+                .iter()
+                .zip(&waves)
+                .filter(|(earlier, _)| {
+                    earlier
+                        .as_ref()
+                        .is_some_and(|earlier| !earlier.is_disjoint(footprint))
+                })
+                .map(|(_, wave)| wave + 1)
+                .max()
+                .unwrap_or(0)
+        });
+        waves.push(wave);
+    }
+    waves
+}
+
+/// A pool of hook concurrency permits, handed out heaviest waiter first.
+///
+/// When a permit is free it is granted immediately. Otherwise, waiters are served by
+/// descending weight, and in arrival order for equal weights, so with all weights zero this
+/// behaves like a FIFO semaphore.
+pub(crate) struct HookPermits {
+    state: RefCell<PermitState>,
+    languages: LanguageLimits,
+}
+
+struct PermitState {
+    available: usize,
+    next_seq: u64,
+    waiters: BinaryHeap<Waiter>,
+}
+
+struct Waiter {
+    weight: Duration,
+    seq: u64,
+    tx: oneshot::Sender<()>,
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .cmp(&other.weight)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl HookPermits {
+    pub(crate) fn new(permits: usize) -> Self {
+        Self {
+            state: RefCell::new(PermitState {
+                available: permits,
+                next_seq: 0,
+                waiters: BinaryHeap::new(),
+            }),
+            languages: LanguageLimits::default(),
+        }
+    }
+
+    /// Also cap the hooks of each language, as configured in the global config.
+    #[must_use]
+    pub(crate) fn with_language_limits(mut self, languages: LanguageLimits) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Wait for a slot for a hook of `language`, if that language is capped.
+    ///
+    /// Acquire it before [`Self::acquire`], so a capped hook does not hold a global permit while
+    /// it waits.
+    pub(crate) async fn acquire_language(&self, language: Language) -> Option<SemaphorePermit<'_>> {
+        self.languages.acquire(language).await
+    }
+
+    pub(crate) async fn acquire(&self, weight: Duration) -> HookPermit<'_> {
+        let rx = {
+            let mut state = self.state.borrow_mut();
+            if state.available > 0 {
+                state.available -= 1;
+                return HookPermit { permits: self };
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiters.push(Waiter { weight, seq, tx });
+            rx
+        };
+
+        // The sender is only dropped without sending if the pool itself is dropped,
+        // which cannot happen while we borrow it.
+        rx.await.expect("hook permit pool dropped");
+        HookPermit { permits: self }
+    }
+
+    fn release(&self) {
+        let mut state = self.state.borrow_mut();
+        // Hand the permit over directly; skip waiters whose futures were dropped.
+        while let Some(waiter) = state.waiters.pop() {
+            if waiter.tx.send(()).is_ok() {
+                return;
+            }
+        }
+        state.available += 1;
+    }
+}
+
+pub(crate) struct HookPermit<'a> {
+    permits: &'a HookPermits,
+}
+
+impl Drop for HookPermit<'_> {
+    fn drop(&mut self) {
+        self.permits.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use futures_util::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn hook_schedule_from_env() {
+        assert_eq!(
+            HookSchedule::from_env(&EnvVars::from_map(&[])),
+            HookSchedule::ConfigOrder
+        );
+        assert_eq!(
+            HookSchedule::from_env(&EnvVars::from_map(&[(
+                EnvVars::PREK_HOOK_SCHEDULE,
+                "slowest-first"
+            )])),
+            HookSchedule::SlowestFirst
+        );
+        assert_eq!(
+            HookSchedule::from_env(&EnvVars::from_map(&[(
+                EnvVars::PREK_HOOK_SCHEDULE,
+                "fastest"
+            )])),
+            HookSchedule::ConfigOrder
+        );
+    }
+
+    #[tokio::test]
+    async fn permits_prefer_heaviest_waiter() {
+        let permits = Rc::new(HookPermits::new(1));
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let held = permits.acquire(Duration::ZERO).await;
+
+        let waiter = |name: &'static str, weight: u64| {
+            let permits = Rc::clone(&permits);
+            let order = Rc::clone(&order);
+            async move {
+                let _permit = permits.acquire(Duration::from_secs(weight)).await;
+                order.borrow_mut().push(name);
+            }
+            .boxed_local()
+        };
+
+        let mut waiters = vec![
+            waiter("light", 1),
+            waiter("heavy", 10),
+            waiter("first-zero", 0),
+            waiter("second-zero", 0),
+        ];
+        // Register every waiter before releasing the held permit.
+        for waiter in &mut waiters {
+            assert!(waiter.now_or_never().is_none());
+        }
+
+        drop(held);
+        futures_util::future::join_all(waiters).await;
+
+        assert_eq!(
+            *order.borrow(),
+            ["heavy", "light", "first-zero", "second-zero"]
+        );
+    }
+
+    #[test]
+    fn conflict_waves_serialize_overlapping_writers() {
+        let files = |names: &[&'static str]| Some(names.iter().copied().collect::<FxHashSet<_>>());
+
+        let footprints = [
+            files(&["a.py", "b.py"]),
+            files(&["c.rs"]),
+            // Read-only hooks never conflict.
+            None,
+            files(&["b.py"]),
+            files(&["b.py", "c.rs"]),
+            files(&[]),
+        ];
+        assert_eq!(conflict_waves(&footprints), [0, 0, 0, 1, 2, 0]);
+    }
+
+    #[tokio::test]
+    async fn permits_skip_dropped_waiters() {
+        let permits = HookPermits::new(1);
+        let held = permits.acquire(Duration::ZERO).await;
+
+        let mut dropped = permits.acquire(Duration::from_secs(1)).boxed_local();
+        assert!((&mut dropped).now_or_never().is_none());
+        drop(dropped);
+
+        drop(held);
+        // The permit went back to the pool instead of the dropped waiter.
+        assert!(permits.acquire(Duration::ZERO).now_or_never().is_some());
+    }
+}
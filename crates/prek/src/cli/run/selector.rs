@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use crate::config::validate_name;
-use crate::hook::Hook;
+use crate::hook::{Hook, Repo};
 use crate::warn_user;
 
 use anyhow::anyhow;
@@ -62,6 +62,13 @@ pub(crate) enum Error {
         #[source]
         source: anyhow::Error,
     },
+
+    #[error("Invalid repo selector: `{selector}`")]
+    RepoSelector {
+        selector: String,
+        #[source]
+        source: anyhow::Error,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -531,6 +538,126 @@ impl GroupFilters {
     }
 }
 
+/// Filters hooks by the repository they come from.
+///
+/// A selector matches a remote repo by its full URL, or by a trailing path
+/// such as `pre-commit-hooks` or `pre-commit/pre-commit-hooks`. The keywords
+/// `local`, `meta` and `builtin` select hooks from those repo kinds.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RepoFilters {
+    repos: Vec<String>,
+    usage: Arc<Mutex<FilterUsage>>,
+}
+
+impl RepoFilters {
+    pub(crate) fn parse(repos: &[String]) -> Result<Self, Error> {
+        let mut seen = FxHashSet::default();
+        let mut selectors = Vec::new();
+
+        for repo in repos {
+            let trimmed = repo.trim();
+            if trimmed.is_empty() {
+                return Err(Error::RepoSelector {
+                    selector: format!("--repo={repo}"),
+                    source: anyhow!("repo selector cannot be empty"),
+                });
+            }
+            if seen.insert(trimmed) {
+                selectors.push(trimmed.to_string());
+            }
+        }
+
+        Ok(Self {
+            repos: selectors,
+            usage: Arc::default(),
+        })
+    }
+
+    pub(crate) fn has_filters(&self) -> bool {
+        !self.repos.is_empty()
+    }
+
+    pub(crate) fn matches_hook(&self, hook: &Hook) -> bool {
+        match hook.repo() {
+            Repo::Remote { url, .. } => self.matches(|selector| repo_url_matches(selector, url)),
+            Repo::Local { .. } => self.matches(|selector| selector == "local"),
+            Repo::Meta { .. } => self.matches(|selector| selector == "meta"),
+            Repo::Builtin { .. } => self.matches(|selector| selector == "builtin"),
+        }
+    }
+
+    /// Return whether a configured remote repo can be selected, before it is cloned.
+    pub(crate) fn matches_remote_repo(&self, repo: &str, source: &str) -> bool {
+        self.matches(|selector| {
+            repo_url_matches(selector, repo) || repo_url_matches(selector, source)
+        })
+    }
+
+    fn matches(&self, matches_selector: impl Fn(&str) -> bool) -> bool {
+        if self.repos.is_empty() {
+            return true;
+        }
+
+        let mut usage = self.usage.lock().unwrap();
+        let mut included = false;
+        for (idx, repo) in self.repos.iter().enumerate() {
+            if matches_selector(repo) {
+                usage.use_include(idx);
+                included = true;
+            }
+        }
+        included
+    }
+
+    pub(crate) fn report_unused(&self) {
+        let usage = self.usage.lock().unwrap();
+        let unused = usage
+            .unused_includes(&self.repos)
+            .map(|(_, repo)| format!("--repo={repo}"))
+            .collect::<Vec<_>>();
+
+        match unused.as_slice() {
+            [] => {}
+            [repo] => {
+                warn_user!("repo selector `{repo}` did not match any hooks");
+            }
+            _ => {
+                let warning = unused
+                    .iter()
+                    .map(|repo| format!("  - `{repo}`"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                warn_user!("the following repo selectors did not match any hooks:");
+                anstream::eprintln!("{warning}");
+            }
+        }
+    }
+}
+
+/// Match a `--repo` selector against a repo URL, ignoring a trailing `/` or `.git`.
+///
+/// Besides the full URL, any trailing run of path segments matches, so `pre-commit-hooks`
+/// and `pre-commit/pre-commit-hooks` both select `https://github.com/pre-commit/pre-commit-hooks`.
+fn repo_url_matches(selector: &str, url: &str) -> bool {
+    fn normalize(url: &str) -> &str {
+        let url = url.trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url)
+    }
+
+    let selector = normalize(selector);
+    let url = normalize(url);
+    if selector.is_empty() {
+        return false;
+    }
+    if selector == url {
+        return true;
+    }
+
+    url.strip_suffix(selector)
+        .is_some_and(|prefix| prefix.ends_with(['/', '\\', ':']))
+}
+
 #[derive(Default, Debug)]
 struct FilterUsage {
     used_includes: FxHashSet<usize>,
@@ -1059,6 +1186,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_repo_url_matches() {
+        let url = "https://github.com/pre-commit/pre-commit-hooks";
+        let cases = [
+            (url, true),
+            ("https://github.com/pre-commit/pre-commit-hooks.git", true),
+            ("https://github.com/pre-commit/pre-commit-hooks/", true),
+            ("pre-commit/pre-commit-hooks", true),
+            ("pre-commit-hooks", true),
+            ("commit-hooks", false),
+            ("pre-commit", false),
+            ("", false),
+        ];
+
+        for (selector, expected) in cases {
+            assert_eq!(repo_url_matches(selector, url), expected, "{selector}");
+        }
+
+        assert!(repo_url_matches(
+            "ruff-pre-commit",
+            "git@github.com:astral-sh/ruff-pre-commit.git"
+        ));
+        assert!(repo_url_matches("hooks", "../hooks"));
+    }
+
+    #[test]
+    fn test_repo_filters_parse() {
+        let filters = RepoFilters::parse(&["ruff".to_string(), " ruff ".to_string()]).unwrap();
+        assert_eq!(filters.repos, vec!["ruff".to_string()]);
+        assert!(filters.matches_remote_repo(
+            "https://github.com/astral-sh/ruff",
+            "https://github.com/astral-sh/ruff"
+        ));
+
+        let err = RepoFilters::parse(&[String::new()]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid repo selector: `--repo=`");
+    }
+
     #[test]
     fn test_selector_as_flag() {
         let selector = Selector {
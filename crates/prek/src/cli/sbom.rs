@@ -0,0 +1,703 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use aws_lc_rs::digest::{SHA256, digest};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::cli::run::InstallCache;
+use crate::cli::{ExitStatus, SbomFormat};
+use crate::fs::CWD;
+use crate::git;
+use crate::hook::{InstallInfo, Repo};
+use crate::printer::Printer;
+use crate::store::Store;
+use crate::version;
+use crate::warn_user;
+use crate::workspace::{HookInitFilters, Refresh, Workspace};
+
+/// The property that records which hook environment a package is installed in.
+const ENV_PROPERTY: &str = "prek:env";
+
+/// What kind of thing prek installed.
+#[derive(Debug, Clone, Copy)]
+enum ComponentKind {
+    /// A cloned hook repository.
+    Repo,
+    /// A language toolchain that hook environments were built with.
+    Toolchain,
+    /// A package installed into a hook environment.
+    Package,
+}
+
+/// One entry of the bill of materials.
+#[derive(Debug, Clone)]
+struct Component {
+    kind: ComponentKind,
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    /// A license as declared by the package.
+    license: Option<License>,
+    /// The source the component was obtained from, such as the repository URL.
+    source: Option<String>,
+    /// Extra facts, such as the hooks an environment serves.
+    properties: BTreeMap<String, String>,
+}
+
+impl Component {
+    fn new(kind: ComponentKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            version: None,
+            purl: None,
+            license: None,
+            source: None,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    fn bom_ref(&self) -> String {
+        let kind = match self.kind {
+            ComponentKind::Repo => "repo",
+            ComponentKind::Toolchain => "toolchain",
+            ComponentKind::Package => "package",
+        };
+        let mut bom_ref = format!("{kind}:{}", self.name);
+        if let Some(version) = &self.version {
+            bom_ref.push('@');
+            bom_ref.push_str(version);
+        }
+        // The same package can be installed into several environments.
+        if let Some(env) = self.properties.get(ENV_PROPERTY) {
+            bom_ref.push('#');
+            bom_ref.push_str(env);
+        }
+        bom_ref
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum License {
+    /// A valid SPDX license expression, such as `MIT OR Apache-2.0`.
+    Expression(String),
+    /// Free-form license text that may not be a valid SPDX expression.
+    Name(String),
+}
+
+/// Print a CycloneDX or SPDX document describing what prek installed for the workspace.
+pub(crate) async fn sbom(
+    store: &Store,
+    config: Option<PathBuf>,
+    format: SbomFormat,
+    refresh: Refresh,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
+    let workspace = Workspace::discover(store, workspace_root, config, None, &refresh)?;
+
+    // Like environments, repos are only reported once cloned; `prek sbom` never clones anything.
+    let (hooks, missing_repos) = workspace
+        .init_cached_hooks(store, HookInitFilters::new(None, None))
+        .await
+        .context("Failed to init hooks")?;
+    for repo in missing_repos {
+        warn_user!(
+            "Skipping `{}`, which is not cloned yet; run `prek prepare-hooks` to include it",
+            repo.source()
+        );
+    }
+
+    // Components keyed by their `bom-ref`, which must be unique within a document.
+    let mut components = BTreeMap::new();
+    let mut repos = BTreeMap::new();
+    for hook in &hooks {
+        if let Repo::Remote { path, url, rev, .. } = hook.repo() {
+            if !repos.contains_key(&(url, rev)) {
+                let commit = git::get_head_commit(path)
+                    .await
+                    .with_context(|| format!("Failed to resolve the commit of `{url}@{rev}`"))?;
+                repos.insert((url, rev), (commit, BTreeSet::new()));
+            }
+            if let Some((_, hook_ids)) = repos.get_mut(&(url, rev)) {
+                hook_ids.insert(hook.id.as_str());
+            }
+        }
+    }
+    for ((url, rev), (commit, hook_ids)) in repos {
+        let mut component = Component::new(ComponentKind::Repo, url.as_str());
+        component.version = Some(rev.clone());
+        component.purl = github_purl(url, &commit);
+        component.source = Some(url.clone());
+        component.properties.insert("prek:commit".into(), commit);
+        component.properties.insert(
+            "prek:hooks".into(),
+            hook_ids.into_iter().collect::<Vec<_>>().join(","),
+        );
+        components.insert(component.bom_ref(), component);
+    }
+
+    // Only report environments that are already installed; `prek sbom` never installs anything.
+    let install_cache = InstallCache::new();
+    let mut envs: BTreeMap<PathBuf, (Arc<InstallInfo>, BTreeSet<&str>)> = BTreeMap::new();
+    for hook in &hooks {
+        let Some(info) = install_cache.installed_info(store, hook).await else {
+            continue;
+        };
+        envs.entry(info.env_path.clone())
+            .or_insert_with(|| (info, BTreeSet::new()))
+            .1
+            .insert(hook.id.as_str());
+    }
+    for (env_path, (info, hook_ids)) in envs {
+        let toolchain = toolchain_component(&info);
+        components.entry(toolchain.bom_ref()).or_insert(toolchain);
+
+        let env_name = env_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let env_hooks = hook_ids.into_iter().collect::<Vec<_>>().join(",");
+        let mut packages = env_packages(&env_path);
+        if packages.is_empty() {
+            // No package metadata we understand, fall back to the declared dependencies.
+            packages = info
+                .dependencies
+                .iter()
+                .map(|dependency| Component::new(ComponentKind::Package, dependency.as_str()))
+                .collect();
+        }
+        for mut package in packages {
+            package
+                .properties
+                .insert(ENV_PROPERTY.into(), env_name.clone());
+            package
+                .properties
+                .insert("prek:hooks".into(), env_hooks.clone());
+            components.entry(package.bom_ref()).or_insert(package);
+        }
+    }
+
+    let components = components.into_values().collect::<Vec<_>>();
+    let document = match format {
+        SbomFormat::Cyclonedx => cyclonedx(&components),
+        SbomFormat::Spdx => spdx(&components, SystemTime::now()),
+    };
+    writeln!(
+        printer.stdout_important(),
+        "{}",
+        serde_json::to_string_pretty(&document)?
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+fn toolchain_component(info: &InstallInfo) -> Component {
+    let mut component = Component::new(ComponentKind::Toolchain, info.language.as_ref());
+    // Languages without a managed toolchain record version `0.0.0`.
+    if info.language_version != semver::Version::new(0, 0, 0) {
+        component.version = Some(info.language_version.to_string());
+    }
+    if !info.toolchain.as_os_str().is_empty() {
+        component.properties.insert(
+            "prek:toolchain".into(),
+            info.toolchain.display().to_string(),
+        );
+    }
+    component
+}
+
+/// The `pkg:github` package URL of a GitHub repository at `commit`.
+fn github_purl(url: &str, commit: &str) -> Option<String> {
+    let path = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("git@github.com:"))?;
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some(format!(
+        "pkg:github/{}/{}@{commit}",
+        owner.to_ascii_lowercase(),
+        repo.to_ascii_lowercase()
+    ))
+}
+
+/// Find the packages installed in a hook environment from their metadata files.
+fn env_packages(env_path: &Path) -> Vec<Component> {
+    walkdir::WalkDir::new(env_path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .flat_map(|entry| {
+            let path = entry.path();
+            let content = || fs_err::read_to_string(path).ok();
+            match path.file_name().and_then(|name| name.to_str()) {
+                Some("METADATA")
+                    if parent_name(path, 1).is_some_and(|name| name.ends_with(".dist-info")) =>
+                {
+                    content()
+                        .and_then(|content| python_package(&content))
+                        .into_iter()
+                        .collect()
+                }
+                Some("package.json") if is_node_module(path) => content()
+                    .and_then(|content| npm_package(&content))
+                    .into_iter()
+                    .collect(),
+                Some(".crates2.json") => content()
+                    .map(|content| cargo_packages(&content))
+                    .unwrap_or_default(),
+                Some(name)
+                    if name.ends_with(".gemspec")
+                        && parent_name(path, 1) == Some("specifications") =>
+                {
+                    content()
+                        .and_then(|content| gem_package(&content))
+                        .into_iter()
+                        .collect()
+                }
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// The name of the `level`-th ancestor directory of `path`, starting at 1 for the parent.
+fn parent_name(path: &Path, level: usize) -> Option<&str> {
+    path.ancestors().nth(level)?.file_name()?.to_str()
+}
+
+/// Whether `path` is the `package.json` of a package in `node_modules`, possibly scoped.
+fn is_node_module(path: &Path) -> bool {
+    match parent_name(path, 2) {
+        Some("node_modules") => true,
+        Some(scope) if scope.starts_with('@') => parent_name(path, 3) == Some("node_modules"),
+        _ => false,
+    }
+}
+
+/// Parse the headers of a Python `METADATA` file.
+fn python_package(metadata: &str) -> Option<Component> {
+    let mut name = None;
+    let mut version = None;
+    let mut expression = None;
+    let mut license = None;
+    for line in metadata.lines() {
+        // The headers end at the first blank line, the description follows.
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "Name" => name = Some(value.to_string()),
+            "Version" => version = Some(value.to_string()),
+            "License-Expression" => expression = Some(value.to_string()),
+            "License" if !value.is_empty() && !value.eq_ignore_ascii_case("UNKNOWN") => {
+                license = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let mut component = Component::new(ComponentKind::Package, name?);
+    let normalized = component.name.to_ascii_lowercase().replace(['_', '.'], "-");
+    component.purl = version
+        .as_ref()
+        .map(|version| format!("pkg:pypi/{normalized}@{version}"));
+    component.version = version;
+    component.license = expression
+        .map(License::Expression)
+        .or(license.map(License::Name));
+    Some(component)
+}
+
+/// Parse the `package.json` of an installed npm package.
+fn npm_package(content: &str) -> Option<Component> {
+    let manifest: Value = serde_json::from_str(content).ok()?;
+    let name = manifest.get("name")?.as_str()?;
+    let version = manifest.get("version").and_then(Value::as_str);
+
+    let mut component = Component::new(ComponentKind::Package, name);
+    component.version = version.map(str::to_string);
+    component.purl = version.map(|version| {
+        format!(
+            "pkg:npm/{}@{version}",
+            name.replacen('@', "%40", usize::from(name.starts_with('@')))
+        )
+    });
+    component.license = match manifest.get("license") {
+        Some(Value::String(license)) => Some(License::Expression(license.clone())),
+        Some(Value::Object(license)) => license
+            .get("type")
+            .and_then(Value::as_str)
+            .map(|license| License::Name(license.to_string())),
+        _ => None,
+    };
+    Some(component)
+}
+
+/// Parse the `.crates2.json` that `cargo install` writes to its install root.
+fn cargo_packages(content: &str) -> Vec<Component> {
+    let Ok(manifest) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    manifest
+        .get("installs")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(package_id, _)| {
+            // Package ids look like `ripgrep 14.1.0 (registry+https://...)`.
+            let mut parts = package_id.splitn(3, ' ');
+            let name = parts.next()?;
+            let version = parts.next()?;
+            let source = parts.next().unwrap_or_default();
+
+            let mut component = Component::new(ComponentKind::Package, name);
+            component.version = Some(version.to_string());
+            // Crates built from a path or a git checkout are not on crates.io.
+            if source.starts_with("(registry+") || source.starts_with("(sparse+") {
+                component.purl = Some(format!("pkg:cargo/{name}@{version}"));
+            }
+            Some(component)
+        })
+        .collect()
+}
+
+/// Parse an installed gem specification, as normalized by RubyGems.
+fn gem_package(spec: &str) -> Option<Component> {
+    let mut name = None;
+    let mut version = None;
+    let mut licenses = Vec::new();
+    for line in spec.lines() {
+        // Fields look like `s.name = "rubocop".freeze` or `s.licenses = ["MIT".freeze]`.
+        let Some((key, value)) = line
+            .trim()
+            .strip_prefix("s.")
+            .and_then(|line| line.split_once(" = "))
+        else {
+            continue;
+        };
+        let mut strings = value.split('"').skip(1).step_by(2).map(str::to_string);
+        match key {
+            "name" => name = strings.next(),
+            "version" => version = strings.next(),
+            "licenses" | "license" => licenses.extend(strings),
+            _ => {}
+        }
+    }
+
+    let mut component = Component::new(ComponentKind::Package, name?);
+    component.purl = version
+        .as_ref()
+        .map(|version| format!("pkg:gem/{}@{version}", component.name));
+    component.version = version;
+    component.license = match licenses.as_slice() {
+        [] => None,
+        [license] => Some(License::Expression(license.clone())),
+        // RubyGems does not say how several licenses combine.
+        licenses => Some(License::Name(licenses.join(", "))),
+    };
+    Some(component)
+}
+
+fn cyclonedx(components: &[Component]) -> Value {
+    let components = components
+        .iter()
+        .map(|component| {
+            let mut value = json!({
+                "type": match component.kind {
+                    ComponentKind::Repo => "application",
+                    ComponentKind::Toolchain => "platform",
+                    ComponentKind::Package => "library",
+                },
+                "bom-ref": component.bom_ref(),
+                "name": component.name,
+            });
+            if let Some(version) = &component.version {
+                value["version"] = json!(version);
+            }
+            if let Some(purl) = &component.purl {
+                value["purl"] = json!(purl);
+            }
+            match &component.license {
+                Some(License::Expression(expression)) => {
+                    value["licenses"] = json!([{ "expression": expression }]);
+                }
+                Some(License::Name(name)) => {
+                    value["licenses"] = json!([{ "license": { "name": name } }]);
+                }
+                None => {}
+            }
+            if let Some(source) = &component.source {
+                value["externalReferences"] = json!([{ "type": "vcs", "url": source }]);
+            }
+            if !component.properties.is_empty() {
+                value["properties"] = component
+                    .properties
+                    .iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect();
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "prek",
+                    "version": version::version().version,
+                }],
+            },
+        },
+        "components": components,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_info: Option<String>,
+    download_location: String,
+    files_analyzed: bool,
+    license_concluded: &'static str,
+    license_declared: String,
+    copyright_text: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<Value>,
+}
+
+fn spdx(components: &[Component], created: SystemTime) -> Value {
+    let packages = components
+        .iter()
+        .enumerate()
+        .map(|(idx, component)| SpdxPackage {
+            spdx_id: format!("SPDXRef-Package-{}", idx + 1),
+            name: component.name.clone(),
+            version_info: component.version.clone(),
+            download_location: match (&component.source, &component.version) {
+                (Some(source), Some(version)) => format!("git+{source}@{version}"),
+                _ => "NOASSERTION".to_string(),
+            },
+            files_analyzed: false,
+            license_concluded: "NOASSERTION",
+            // Free-form license names are not valid SPDX expressions.
+            license_declared: match &component.license {
+                Some(License::Expression(expression)) => expression.clone(),
+                _ => "NOASSERTION".to_string(),
+            },
+            copyright_text: "NOASSERTION",
+            comment: (!component.properties.is_empty()).then(|| {
+                component
+                    .properties
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            }),
+            external_refs: component
+                .purl
+                .iter()
+                .map(|purl| {
+                    json!({
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": purl,
+                    })
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    let relationships = packages
+        .iter()
+        .map(|package| {
+            json!({
+                "spdxElementId": "SPDXRef-DOCUMENT",
+                "relationshipType": "DESCRIBES",
+                "relatedSpdxElement": package.spdx_id,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let created = format_utc(created);
+    let refs = components
+        .iter()
+        .map(Component::bom_ref)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let namespace = hex::encode(digest(&SHA256, format!("{created}\n{refs}").as_bytes()));
+    let tool_version = version::version().version;
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "prek hook environments",
+        "documentNamespace": format!("https://prek.j178.dev/spdx/{namespace}"),
+        "creationInfo": {
+            "created": created,
+            "creators": [format!("Tool: prek-{tool_version}")],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+/// Format a time as an RFC 3339 UTC timestamp with second precision.
+fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let days = i64::try_from(secs / 86_400).unwrap_or_default();
+    let rem = secs % 86_400;
+
+    // Convert days since the epoch to a civil date, see
+    // <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        License, cargo_packages, format_utc, gem_package, github_purl, is_node_module, npm_package,
+        python_package,
+    };
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(1_709_251_199)),
+            "2024-02-29T23:59:59Z"
+        );
+    }
+
+    #[test]
+    fn parses_python_metadata() {
+        let package = python_package(indoc::indoc! {"
+            Metadata-Version: 2.4
+            Name: Typing_Extensions
+            Version: 4.12.2
+            License-Expression: PSF-2.0
+
+            License: this is the description, not a header
+        "})
+        .unwrap();
+        assert_eq!(package.name, "Typing_Extensions");
+        assert_eq!(package.version.as_deref(), Some("4.12.2"));
+        assert_eq!(
+            package.purl.as_deref(),
+            Some("pkg:pypi/typing-extensions@4.12.2")
+        );
+        assert_eq!(package.license, Some(License::Expression("PSF-2.0".into())));
+
+        let package = python_package("Name: pyyaml\nVersion: 6.0\nLicense: MIT License\n").unwrap();
+        assert_eq!(package.license, Some(License::Name("MIT License".into())));
+    }
+
+    #[test]
+    fn parses_npm_package_json() {
+        let package =
+            npm_package(r#"{"name": "@scope/pkg", "version": "1.0.0", "license": "MIT"}"#).unwrap();
+        assert_eq!(package.purl.as_deref(), Some("pkg:npm/%40scope/pkg@1.0.0"));
+        assert_eq!(package.license, Some(License::Expression("MIT".into())));
+
+        assert!(is_node_module(Path::new(
+            "lib/node_modules/pkg/package.json"
+        )));
+        assert!(is_node_module(Path::new(
+            "lib/node_modules/@scope/pkg/package.json"
+        )));
+        assert!(!is_node_module(Path::new("lib/pkg/package.json")));
+    }
+
+    #[test]
+    fn parses_cargo_installs() {
+        let packages = cargo_packages(
+            r#"{"installs": {
+                "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)": {},
+                "hook 0.1.0 (path+file:///repo)": {}
+            }}"#,
+        );
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "ripgrep");
+        assert_eq!(packages[0].version.as_deref(), Some("14.1.0"));
+        assert_eq!(
+            packages[0].purl.as_deref(),
+            Some("pkg:cargo/ripgrep@14.1.0")
+        );
+        assert_eq!(packages[1].name, "hook");
+        assert_eq!(packages[1].purl, None);
+    }
+
+    #[test]
+    fn parses_gemspec() {
+        let package = gem_package(indoc::indoc! {r#"
+            # -*- encoding: utf-8 -*-
+            # stub: rubocop 1.50.0 ruby lib
+
+            Gem::Specification.new do |s|
+              s.name = "rubocop".freeze
+              s.version = "1.50.0".freeze
+              s.licenses = ["MIT".freeze]
+            end
+        "#})
+        .unwrap();
+        assert_eq!(package.name, "rubocop");
+        assert_eq!(package.version.as_deref(), Some("1.50.0"));
+        assert_eq!(package.purl.as_deref(), Some("pkg:gem/rubocop@1.50.0"));
+        assert_eq!(package.license, Some(License::Expression("MIT".into())));
+    }
+
+    #[test]
+    fn builds_github_purls() {
+        assert_eq!(
+            github_purl("https://github.com/pre-commit/pre-commit-hooks", "abc").as_deref(),
+            Some("pkg:github/pre-commit/pre-commit-hooks@abc")
+        );
+        assert_eq!(github_purl("https://gitlab.com/a/b", "abc"), None);
+    }
+}
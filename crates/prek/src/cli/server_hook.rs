@@ -0,0 +1,344 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use prek_consts::CONFIG_FILENAMES;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use serde::Serialize;
+use tempfile::TempDir;
+use tokio::io::AsyncReadExt;
+use tracing::debug;
+
+use crate::cli::{ExitStatus, ServerHookOutputFormat};
+use crate::config::Stage;
+use crate::git::{self, GitCommandExt};
+use crate::printer::Printer;
+use crate::process::Cmd;
+use crate::store::Store;
+
+/// A ref update read from the standard input of a `pre-receive` hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RefUpdate {
+    old: String,
+    new: String,
+    name: String,
+}
+
+impl RefUpdate {
+    /// Parse a `<old-oid> <new-oid> <ref-name>` line.
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let old = parts.next()?;
+        let new = parts.next()?;
+        let name = parts.next()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            old: old.to_string(),
+            new: new.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    fn is_deletion(&self) -> bool {
+        is_zero_oid(&self.new)
+    }
+
+    fn is_creation(&self) -> bool {
+        is_zero_oid(&self.old)
+    }
+}
+
+fn is_zero_oid(oid: &str) -> bool {
+    oid.bytes().all(|b| b == b'0')
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum RefStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+struct RefReport {
+    #[serde(rename = "ref")]
+    name: String,
+    old: String,
+    new: String,
+    status: RefStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'static str>,
+    output: String,
+}
+
+impl RefReport {
+    fn skipped(update: RefUpdate, reason: &'static str) -> Self {
+        Self {
+            name: update.name,
+            old: update.old,
+            new: update.new,
+            status: RefStatus::Skipped,
+            exit_code: None,
+            reason: Some(reason),
+            output: String::new(),
+        }
+    }
+}
+
+/// Run the configured hooks against every ref pushed to a server-side repository.
+///
+/// Reads the ref updates of a `pre-receive` hook from stdin, checks out each pushed commit into a
+/// temporary work tree and runs `prek run` there. The configuration in a pushed commit could run
+/// arbitrary commands on the server, so the configuration enforced is either `config` or the one
+/// on the default branch of the receiving repository, never the pushed one.
+pub(crate) async fn server_hook(
+    store: &Store,
+    config: Option<PathBuf>,
+    stage: Stage,
+    output_format: ServerHookOutputFormat,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let mut stdin = String::new();
+    tokio::io::stdin()
+        .read_to_string(&mut stdin)
+        .await
+        .context("Failed to read ref updates from stdin")?;
+
+    let mut updates = Vec::new();
+    for line in stdin.lines().filter(|line| !line.trim().is_empty()) {
+        let Some(update) = RefUpdate::parse(line) else {
+            anyhow::bail!("Invalid ref update `{line}`, expected `<old-oid> <new-oid> <ref-name>`");
+        };
+        updates.push(update);
+    }
+
+    let config_dir = TempDir::with_prefix_in("server-hook-config-", store.scratch_path())?;
+    let config = match config {
+        Some(config) => Some(std::path::absolute(&config)?),
+        None => default_branch_config(config_dir.path()).await?,
+    };
+    let object_dirs = object_directories().await?;
+    let prek = std::env::current_exe()?;
+
+    let mut reports = Vec::with_capacity(updates.len());
+    for update in updates {
+        let report = if update.is_deletion() {
+            RefReport::skipped(update, "ref deleted")
+        } else if let Some(config) = &config {
+            check_ref(store, &prek, config, &object_dirs, stage, update).await?
+        } else {
+            RefReport::skipped(update, "no configuration file on the default branch")
+        };
+        if let ServerHookOutputFormat::Text = output_format {
+            write_text_report(printer, &report)?;
+        }
+        reports.push(report);
+    }
+
+    if let ServerHookOutputFormat::Json = output_format {
+        writeln!(
+            printer.stdout_important(),
+            "{}",
+            serde_json::to_string_pretty(&reports)?
+        )?;
+    }
+
+    if reports
+        .iter()
+        .any(|report| matches!(report.status, RefStatus::Failed))
+    {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// Copy the configuration file on the default branch of the receiving repository into `dir`.
+///
+/// Returns `None` if the default branch has no configuration file, or does not exist yet.
+async fn default_branch_config(dir: &Path) -> Result<Option<PathBuf>> {
+    for filename in CONFIG_FILENAMES {
+        let output = git::git_cmd()?
+            .arg("show")
+            .arg(format!("HEAD:{filename}"))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .check(false)
+            .output()
+            .await?;
+        if output.status.success() {
+            // Keep the file name, it decides how the configuration is parsed.
+            let path = dir.join(filename);
+            fs_err::tokio::write(&path, output.stdout).await?;
+            debug!("Using `{filename}` from the default branch");
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// The object directories of the receiving repository.
+///
+/// During `pre-receive`, pushed objects live in a quarantine directory that Git exposes through
+/// `GIT_OBJECT_DIRECTORY`, with the main object directory listed in
+/// `GIT_ALTERNATE_OBJECT_DIRECTORIES`. Both are needed to check out the pushed commits.
+async fn object_directories() -> Result<Vec<PathBuf>> {
+    let output = git::git_cmd()?
+        .arg("rev-parse")
+        .arg("--path-format=absolute")
+        .arg("--git-path")
+        .arg("objects")
+        .output()
+        .await
+        .context("Failed to locate the object directory of the repository")?;
+    let mut dirs = vec![PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    )];
+    if let Some(alternates) = EnvVars.var_os(EnvVars::GIT_ALTERNATE_OBJECT_DIRECTORIES) {
+        dirs.extend(std::env::split_paths(&alternates));
+    }
+    Ok(dirs)
+}
+
+/// Check out the pushed commit into a temporary repository that borrows the objects of the
+/// receiving repository, then run the hooks of the trusted `config` in it.
+///
+/// A `git worktree` can not be used here, as Git refuses ref updates while the pushed objects
+/// are quarantined.
+async fn check_ref(
+    store: &Store,
+    prek: &Path,
+    config: &Path,
+    object_dirs: &[PathBuf],
+    stage: Stage,
+    update: RefUpdate,
+) -> Result<RefReport> {
+    let tmp_dir = TempDir::with_prefix_in("server-hook-", store.scratch_path())?;
+    let work_tree = tmp_dir.path();
+    debug!(
+        "Checking out `{}` at `{}` into `{}`",
+        update.name,
+        update.new,
+        work_tree.display()
+    );
+
+    git::git_cmd()?
+        .isolate_from_git_env()
+        .arg("init")
+        .arg("--quiet")
+        .arg(work_tree)
+        .output()
+        .await?;
+    let alternates = object_dirs
+        .iter()
+        .map(|dir| format!("{}\n", dir.display()))
+        .collect::<String>();
+    fs_err::tokio::write(work_tree.join(".git/objects/info/alternates"), alternates).await?;
+    git::git_cmd()?
+        .isolate_from_git_env()
+        .arg("checkout")
+        .arg("--quiet")
+        .arg("--detach")
+        .arg(&update.new)
+        .current_dir(work_tree)
+        .output()
+        .await
+        .with_context(|| format!("Failed to check out `{}`", update.name))?;
+
+    let mut cmd = Cmd::new(prek);
+    cmd.arg("run")
+        .arg("--config")
+        .arg(config)
+        .arg("--color")
+        .arg("never")
+        .arg("--stage")
+        .arg(stage.to_string());
+    if update.is_creation() {
+        // There is no previous commit to diff against, check every file of the new ref.
+        cmd.arg("--all-files");
+    } else {
+        cmd.arg("--from-ref")
+            .arg(&update.old)
+            .arg("--to-ref")
+            .arg(&update.new);
+    }
+    let output = cmd
+        .isolate_from_git_env()
+        .env("PRE_COMMIT_REMOTE_BRANCH", &update.name)
+        .current_dir(work_tree)
+        .stdin(Stdio::null())
+        .check(false)
+        .output()
+        .await?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(RefReport {
+        name: update.name,
+        old: update.old,
+        new: update.new,
+        status: if output.status.success() {
+            RefStatus::Passed
+        } else {
+            RefStatus::Failed
+        },
+        exit_code: output.status.code(),
+        reason: None,
+        output: text,
+    })
+}
+
+fn write_text_report(printer: Printer, report: &RefReport) -> Result<()> {
+    let output = report.output.trim_end();
+    if !output.is_empty() {
+        writeln!(printer.stdout(), "{output}")?;
+    }
+    match report.status {
+        RefStatus::Passed => writeln!(
+            printer.stdout(),
+            "{}: {}",
+            report.name.cyan(),
+            "Passed".green()
+        )?,
+        RefStatus::Failed => writeln!(
+            printer.stdout_important(),
+            "{}: {}",
+            report.name.cyan(),
+            "Failed".red()
+        )?,
+        RefStatus::Skipped => writeln!(
+            printer.stdout(),
+            "{}: {} ({})",
+            report.name.cyan(),
+            "Skipped".yellow(),
+            report.reason.unwrap_or_default()
+        )?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ref_update() {
+        let update = RefUpdate::parse(
+            "0000000000000000000000000000000000000000 1111111111111111111111111111111111111111 refs/heads/main",
+        )
+        .unwrap();
+        assert_eq!(update.name, "refs/heads/main");
+        assert!(update.is_creation());
+        assert!(!update.is_deletion());
+
+        assert!(RefUpdate::parse("abc refs/heads/main").is_none());
+        assert!(RefUpdate::parse("a b c d").is_none());
+    }
+}
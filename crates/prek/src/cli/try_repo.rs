@@ -10,7 +10,7 @@ use tempfile::TempDir;
 use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Value};
 
 use crate::cli::run::Selectors;
-use crate::cli::{ExitStatus, RunOptions, flag};
+use crate::cli::{ExitStatus, RunOptions, RunSettings};
 use crate::config::{self, Stage};
 use crate::git;
 use crate::git::GIT_ROOT;
@@ -18,6 +18,7 @@ use crate::hooks::{BuiltinHooks, MetaHooks};
 use crate::printer::Printer;
 use crate::store::Store;
 use crate::warn_user;
+use crate::workspace::Refresh;
 
 async fn get_head_rev(repo: &Path) -> Result<String> {
     let head_rev = git::git_cmd()?
@@ -202,12 +203,13 @@ where
 }
 
 pub(crate) async fn try_repo(
+    store: &Store,
     config: Option<PathBuf>,
     repo: String,
     rev: Option<String>,
     run_args: RunOptions,
     stage: Option<Stage>,
-    refresh: bool,
+    refresh: Refresh,
     verbose: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -215,7 +217,6 @@ pub(crate) async fn try_repo(
         warn_user!("`--config` option is ignored when using `try-repo`");
     }
 
-    let store = Store::from_settings()?;
     let tmp_dir = TempDir::with_prefix_in("try-repo-", store.scratch_path())?;
 
     let store = Store::from_path(tmp_dir.path()).init()?;
@@ -273,22 +274,19 @@ pub(crate) async fn try_repo(
     )?;
     writeln!(printer.stdout(), "{}", display_config_str.dimmed())?;
 
-    let file_selection = run_args.file_selection.into();
     crate::cli::run(
         &store,
-        Some(config_file),
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        stage,
-        file_selection,
-        run_args.show_diff_on_failure,
-        flag(run_args.fail_fast, run_args.no_fail_fast),
-        run_args.dry_run,
-        refresh,
-        run_args.extra,
-        verbose,
+        RunSettings {
+            config: Some(config_file),
+            // The selectors already picked the hooks of the generated config.
+            includes: vec![],
+            skips: vec![],
+            hook_stage: stage,
+            use_jj: true,
+            refresh,
+            verbose,
+            ..RunSettings::from(run_args)
+        },
         printer,
     )
     .await
@@ -3,7 +3,7 @@ use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use futures_util::{StreamExt, TryStreamExt};
+use futures_util::StreamExt;
 use globset::Glob;
 use rustc_hash::{FxHashMap, FxHashSet};
 use semver::Version;
@@ -12,8 +12,9 @@ use crate::cli::reporter::UpdateReporter;
 use crate::cli::run::Selectors;
 use crate::cli::update::config::write_new_config;
 use crate::cli::update::display::{apply_repo_updates, warn_frozen_mismatches};
+use crate::cli::update::report::write_update_report;
 use crate::cli::update::source::{collect_repo_sources, evaluate_repo_source};
-use crate::cli::{ExitStatus, RepoTagPattern};
+use crate::cli::{ExitStatus, RepoTagPattern, UpdateOutputFormat};
 use crate::config::{GlobPatterns, Repo};
 use crate::fs::{CWD, Simplified};
 use crate::printer::Printer;
@@ -21,10 +22,11 @@ use crate::run::INTERNAL_CONCURRENCY;
 use crate::settings::{CliTagFilterOptions, FilesystemOptions, TagFilterOptions};
 use crate::store::Store;
 use crate::warn_user;
-use crate::workspace::{Project, Workspace};
+use crate::workspace::{Project, Refresh, Workspace};
 
 mod config;
 mod display;
+mod report;
 mod repository;
 mod source;
 
@@ -167,7 +169,7 @@ struct TagTimestamp {
 
 impl TagTimestamp {
     fn new(tag: String, timestamp: u64, commit: String) -> Self {
-        let version = Version::parse(tag.strip_prefix('v').unwrap_or(&tag)).ok();
+        let version = parse_tag_version(&tag);
         Self {
             tag,
             version,
@@ -177,6 +179,11 @@ impl TagTimestamp {
     }
 }
 
+/// Parse a tag name such as `v1.2.3` as a semantic version.
+fn parse_tag_version(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
 #[derive(Debug, Eq, PartialEq)]
 struct SkippedDowngrade {
     /// The configured revision that was kept.
@@ -444,13 +451,20 @@ pub(crate) async fn update(
     dry_run: bool,
     exit_code: bool,
     cooldown_days: Option<u8>,
+    output_format: UpdateOutputFormat,
     filesystem: Option<FilesystemOptions>,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let workspace_root = Workspace::find_root(config.as_deref(), &CWD)?;
     // TODO: support selectors?
     let selectors = Selectors::default();
-    let workspace = Workspace::discover(store, workspace_root, config, Some(&selectors), true)?;
+    let workspace = Workspace::discover(
+        store,
+        workspace_root,
+        config,
+        Some(&selectors),
+        &Refresh::All,
+    )?;
 
     let cli_tag_filters = CliTagFilterOptions {
         include: include_tag,
@@ -495,8 +509,8 @@ pub(crate) async fn update(
             result
         })
         .buffer_unordered(jobs)
-        .try_collect::<Vec<_>>()
-        .await?
+        .collect::<Vec<_>>()
+        .await
         .into_iter()
         .flatten()
         .collect();
@@ -505,6 +519,14 @@ pub(crate) async fn update(
 
     warn_frozen_mismatches(&outcomes, printer)?;
 
+    // Structured output never writes the config, so only the summary matters here.
+    let (dry_run, printer) = if matches!(output_format, UpdateOutputFormat::Text) {
+        (dry_run, printer)
+    } else {
+        write_update_report(&outcomes, output_format, printer)?;
+        (true, Printer::Silent)
+    };
+
     // Group results by project config file
     let mut project_updates: ProjectUpdates<'_> = FxHashMap::default();
     let apply_result =
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use semver::Version;
+use serde::Serialize;
+
+use crate::cli::UpdateOutputFormat;
+use crate::cli::update::{RepoUpdate, parse_tag_version};
+use crate::fs::Simplified;
+use crate::printer::Printer;
+
+/// The kind of version bump between the current and the latest revision.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ChangeType {
+    /// The repo is already at the latest revision.
+    None,
+    Major,
+    Minor,
+    Patch,
+    /// At least one of the revisions is not a semver tag.
+    Unknown,
+}
+
+/// One machine-readable entry per configured repo occurrence.
+#[derive(Debug, Serialize)]
+struct RepoReport {
+    /// The config file that contains this repo entry.
+    config: String,
+    /// The 1-based line number of this repo entry's `rev` setting.
+    line: usize,
+    repo: String,
+    current_rev: String,
+    current_frozen: Option<String>,
+    /// The revision `prek update` would write, or `null` when the update failed.
+    latest_rev: Option<String>,
+    latest_frozen: Option<String>,
+    /// The kind of change, or `null` when the update failed.
+    change: Option<ChangeType>,
+    error: Option<String>,
+    #[serde(skip)]
+    sort_key: (usize, usize),
+}
+
+/// Classify the change between two tag-like revisions by their semver components.
+fn change_type(current: &str, latest: &str) -> ChangeType {
+    if current == latest {
+        return ChangeType::None;
+    }
+
+    let (Some(current), Some(latest)) = (parse_tag_version(current), parse_tag_version(latest))
+    else {
+        return ChangeType::Unknown;
+    };
+    classify(&current, &latest)
+}
+
+fn classify(current: &Version, latest: &Version) -> ChangeType {
+    if current == latest {
+        ChangeType::None
+    } else if current.major != latest.major {
+        ChangeType::Major
+    } else if current.minor != latest.minor {
+        ChangeType::Minor
+    } else {
+        ChangeType::Patch
+    }
+}
+
+fn collect_reports(updates: &[RepoUpdate<'_>]) -> Vec<RepoReport> {
+    let mut reports = Vec::new();
+
+    for update in updates {
+        let target = update.target;
+        for usage in &target.usages {
+            let mut report = RepoReport {
+                config: usage.project.config_file().user_display().to_string(),
+                line: usage.rev_line_number,
+                repo: target.repo.to_string(),
+                current_rev: target.current_rev.to_string(),
+                current_frozen: usage.current_frozen.clone(),
+                latest_rev: None,
+                latest_frozen: None,
+                change: None,
+                error: None,
+                sort_key: (usage.project.idx(), usage.remote_index),
+            };
+
+            match &update.result {
+                Ok(resolved) => {
+                    let latest = &resolved.revision;
+                    // Frozen revisions are compared by the tags in their `# frozen:` comments.
+                    let current_tag = usage
+                        .current_frozen
+                        .as_deref()
+                        .unwrap_or(target.current_rev);
+                    let latest_tag = latest.frozen.as_deref().unwrap_or(&latest.rev);
+                    report.change = Some(if target.current_rev == latest.rev {
+                        ChangeType::None
+                    } else {
+                        change_type(current_tag, latest_tag)
+                    });
+                    report.latest_rev = Some(latest.rev.clone());
+                    report.latest_frozen.clone_from(&latest.frozen);
+                }
+                Err(err) => {
+                    report.error = Some(err.to_string());
+                }
+            }
+
+            reports.push(report);
+        }
+    }
+
+    reports.sort_by_key(|report| report.sort_key);
+    reports
+}
+
+/// Writes the evaluated repo updates to stdout in a machine-readable format.
+pub(super) fn write_update_report(
+    updates: &[RepoUpdate<'_>],
+    output_format: UpdateOutputFormat,
+    printer: Printer,
+) -> Result<()> {
+    let reports = collect_reports(updates);
+
+    let output = match output_format {
+        UpdateOutputFormat::Text => unreachable!("text output is rendered by `apply_repo_updates`"),
+        UpdateOutputFormat::Json => format!("{}\n", serde_json::to_string_pretty(&reports)?),
+        UpdateOutputFormat::Yaml => serde_saphyr::to_string(&reports)?,
+    };
+    write!(printer.stdout(), "{output}")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_type_from_semver_tags() {
+        assert_eq!(change_type("v1.2.3", "v2.0.0"), ChangeType::Major);
+        assert_eq!(change_type("v1.2.3", "v1.3.0"), ChangeType::Minor);
+        assert_eq!(change_type("v1.2.3", "v1.2.4"), ChangeType::Patch);
+        assert_eq!(change_type("1.2.3", "v1.2.3"), ChangeType::None);
+        assert_eq!(change_type("v1.2.3-rc1", "v1.2.3"), ChangeType::Patch);
+    }
+
+    #[test]
+    fn change_type_unknown_for_non_semver_revs() {
+        assert_eq!(change_type("v1.2.3", "nightly"), ChangeType::Unknown);
+        assert_eq!(
+            change_type("0123456789abcdef0123456789abcdef01234567", "v1.2.3"),
+            ChangeType::Unknown
+        );
+        assert_eq!(change_type("main", "main"), ChangeType::None);
+    }
+}
@@ -216,11 +216,12 @@ async fn collect_frozen_mismatches<'a>(
 pub(super) async fn evaluate_repo_source<'a>(
     repo_source: &'a RepoSource<'a>,
     bleeding_edge: bool,
-) -> Result<Vec<RepoUpdate<'a>>> {
-    let tmp_dir = tempfile::tempdir()?;
-    let repo_path = tmp_dir.path();
-
+) -> Vec<RepoUpdate<'a>> {
+    // Failing to fetch one repository is reported with its targets instead of aborting the
+    // whole update, so the other repositories still get updated.
     let result = async {
+        let tmp_dir = tempfile::tempdir()?;
+        let repo_path = tmp_dir.path();
         trace!(
             "Cloning repository `{}` to `{}`",
             repo_source.source,
@@ -229,24 +230,25 @@ pub(super) async fn evaluate_repo_source<'a>(
         setup_and_fetch_repo(repo_source.source, repo_path).await?;
         let metadata = list_tag_metadata(repo_path).await?;
 
-        anyhow::Ok(metadata)
+        anyhow::Ok((tmp_dir, metadata))
     }
     .await;
 
-    let tag_timestamps = match result {
-        Ok(metadata) => metadata,
+    let (tmp_dir, tag_timestamps) = match result {
+        Ok(result) => result,
         Err(e) => {
             let error = format!("{e:#}");
-            return Ok(repo_source
+            return repo_source
                 .targets
                 .iter()
                 .map(|target| RepoUpdate {
                     target,
                     result: Err(anyhow::anyhow!(error.clone())),
                 })
-                .collect());
+                .collect();
         }
     };
+    let repo_path = tmp_dir.path();
 
     let mut updates = Vec::with_capacity(repo_source.targets.len());
     for target in &repo_source.targets {
@@ -268,7 +270,7 @@ pub(super) async fn evaluate_repo_source<'a>(
         updates.push(RepoUpdate { target, result });
     }
 
-    Ok(updates)
+    updates
 }
 
 /// Resolves one configured repo target within an already-fetched remote repository.
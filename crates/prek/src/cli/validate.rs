@@ -8,12 +8,21 @@ use owo_colors::OwoColorize;
 
 use crate::cli::ExitStatus;
 use crate::config::{read_config, read_manifest};
+use crate::fs::CWD;
 use crate::printer::Printer;
 use crate::warn_user;
+use crate::workspace::Workspace;
 
+/// Validate the given configuration files, or every project configuration of the workspace when
+/// none are given.
 pub(crate) fn validate_configs(configs: Vec<PathBuf>, printer: Printer) -> Result<ExitStatus> {
     let mut status = ExitStatus::Success;
 
+    let configs = if configs.is_empty() {
+        workspace_configs()
+    } else {
+        configs
+    };
     if configs.is_empty() {
         warn_user!("No configs to check");
         return Ok(ExitStatus::Success);
@@ -45,6 +54,20 @@ pub(crate) fn validate_configs(configs: Vec<PathBuf>, printer: Printer) -> Resul
     Ok(status)
 }
 
+/// The configuration files of all projects in the workspace of the current directory.
+fn workspace_configs() -> Vec<PathBuf> {
+    let Ok(root) = Workspace::find_root(None, &CWD) else {
+        return Vec::new();
+    };
+    match Workspace::find_config_files(&root) {
+        Ok(configs) => configs,
+        Err(err) => {
+            warn_user!("Failed to discover workspace configs: {err}");
+            Vec::new()
+        }
+    }
+}
+
 pub(crate) fn validate_manifest(manifests: Vec<PathBuf>, printer: Printer) -> Result<ExitStatus> {
     let mut status = ExitStatus::Success;
 
@@ -1,17 +1,17 @@
 use std::collections::BTreeMap;
 use std::error::Error as _;
 use std::fmt::Display;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use fancy_regex::Regex;
 use globset::{Glob, GlobSet};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use prek_identify::TagSet;
+use prek_identify::{CustomTags, TagSet};
 use rustc_hash::FxHashMap;
 use serde::de::{DeserializeSeed, Error as DeError, MapAccess, Visitor};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use strum::EnumCount;
 
 use crate::fs::Simplified;
@@ -355,6 +355,29 @@ impl Display for FilePattern {
     }
 }
 
+impl Serialize for FilePattern {
+    /// Serialize back to the configuration syntax: a regex string or `{ glob: [...] }`.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct GlobList<'a> {
+            glob: Vec<&'a str>,
+        }
+
+        match self {
+            // No path is empty, so this regex never matches.
+            FilePattern::Never => serializer.serialize_str("^$"),
+            FilePattern::Regex(regex) => serializer.serialize_str(regex.as_str()),
+            FilePattern::Glob(globs) => GlobList {
+                glob: globs.patterns.iter().map(Glob::glob).collect(),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
 impl TryFrom<FilePatternWire> for FilePattern {
     type Error = FilePatternWireError;
 
@@ -425,6 +448,7 @@ pub(crate) enum HookType {
     PostCommit,
     PostMerge,
     PostRewrite,
+    PreAutoGc,
     #[default]
     PreCommit,
     PreMergeCommit,
@@ -461,6 +485,7 @@ pub(crate) enum Stage {
     PostCommit,
     PostMerge,
     PostRewrite,
+    PreAutoGc,
     #[default]
     #[serde(alias = "commit")]
     PreCommit,
@@ -480,6 +505,7 @@ impl From<HookType> for Stage {
             HookType::PostCommit => Self::PostCommit,
             HookType::PostMerge => Self::PostMerge,
             HookType::PostRewrite => Self::PostRewrite,
+            HookType::PreAutoGc => Self::PreAutoGc,
             HookType::PreCommit => Self::PreCommit,
             HookType::PreMergeCommit => Self::PreMergeCommit,
             HookType::PrePush => Self::PrePush,
@@ -497,6 +523,7 @@ impl Stage {
         Self::PostCommit,
         Self::PostMerge,
         Self::PostRewrite,
+        Self::PreAutoGc,
         Self::PreCommit,
         Self::PreMergeCommit,
         Self::PrePush,
@@ -646,8 +673,114 @@ impl<'de> Deserialize<'de> for PassFilenames {
     }
 }
 
+/// The `language_version` of a hook.
+///
+/// Either a single version, or versions keyed by target triple patterns such as
+/// `aarch64-apple-darwin` or `*-linux-*`.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) enum LanguageVersion {
+    Version(String),
+    /// Target triple patterns and their versions, in config order.
+    ByTarget(Vec<(Glob, String)>),
+}
+
+impl std::fmt::Debug for LanguageVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Version(version) => version.fmt(f),
+            Self::ByTarget(versions) => f
+                .debug_map()
+                .entries(
+                    versions
+                        .iter()
+                        .map(|(pattern, version)| (pattern.glob(), version)),
+                )
+                .finish(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LanguageVersion {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LanguageVersionVisitor;
+
+        impl<'de> Visitor<'de> for LanguageVersionVisitor {
+            type Value = LanguageVersion;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a version string or a map of target triple patterns to versions")
+            }
+
+            fn visit_str<E: DeError>(self, v: &str) -> Result<LanguageVersion, E> {
+                Ok(LanguageVersion::Version(v.to_string()))
+            }
+
+            // YAML reads unquoted versions such as `3.12` or `20` as numbers.
+            fn visit_u64<E: DeError>(self, v: u64) -> Result<LanguageVersion, E> {
+                Ok(LanguageVersion::Version(v.to_string()))
+            }
+
+            fn visit_i64<E: DeError>(self, v: i64) -> Result<LanguageVersion, E> {
+                Ok(LanguageVersion::Version(v.to_string()))
+            }
+
+            fn visit_f64<E: DeError>(self, v: f64) -> Result<LanguageVersion, E> {
+                Ok(LanguageVersion::Version(v.to_string()))
+            }
+
+            fn visit_map<M>(self, mut map: M) -> Result<LanguageVersion, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut versions = Vec::new();
+                while let Some((pattern, version)) = map.next_entry::<String, LanguageVersion>()? {
+                    let pattern = Glob::new(&pattern).map_err(|err| {
+                        M::Error::custom(format!("invalid target pattern `{pattern}`: {err}"))
+                    })?;
+                    let LanguageVersion::Version(version) = version else {
+                        return Err(M::Error::custom(format!(
+                            "the version for target pattern `{}` must be a string",
+                            pattern.glob()
+                        )));
+                    };
+                    versions.push((pattern, version));
+                }
+                Ok(LanguageVersion::ByTarget(versions))
+            }
+        }
+
+        deserializer.deserialize_any(LanguageVersionVisitor)
+    }
+}
+
+impl LanguageVersion {
+    /// Resolve the version for the `target` triple.
+    ///
+    /// The first matching pattern wins; returns `None` if no pattern matches.
+    pub(crate) fn resolve(&self, target: &str) -> Option<&str> {
+        match self {
+            Self::Version(version) => Some(version),
+            Self::ByTarget(versions) => versions
+                .iter()
+                .find(|(pattern, _)| pattern.compile_matcher().is_match(target))
+                .map(|(_, version)| version.as_str()),
+        }
+    }
+
+    /// The version, if it is not keyed by target.
+    pub(crate) fn as_version(&self) -> Option<&str> {
+        match self {
+            Self::Version(version) => Some(version),
+            Self::ByTarget(_) => None,
+        }
+    }
+}
+
 /// A predefined shell adapter used to run hook entries as shell source.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "schemars", schemars(rename_all = "lowercase"))]
@@ -659,6 +792,20 @@ pub(crate) enum Shell {
     Cmd,
 }
 
+/// How a hook command is isolated from the rest of the system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename_all = "snake_case"))]
+pub(crate) enum Isolation {
+    /// Run the hook directly on the host.
+    #[default]
+    None,
+    /// Run the hook in a sandbox that can only write to the repository and only read the
+    /// system directories and the hook's own repository, environment, and toolchain.
+    Container,
+}
+
 /// Common hook options.
 #[derive(Debug, Clone, Default, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -683,6 +830,11 @@ pub(crate) struct HookOptions {
     pub additional_dependencies: Option<Vec<String>>,
     /// Additional arguments to pass to the hook.
     pub args: Option<Vec<String>>,
+    /// Read additional arguments from a key in another file, as `<file>:<key>`, e.g.
+    /// `pyproject.toml:tool.prek.ruff`.
+    /// The file is relative to the project root and must be a TOML or JSON file, the key is a
+    /// dotted path to a list of arguments. They are appended after `args`.
+    pub args_from: Option<String>,
     /// Environment variables to set for the hook.
     pub env: Option<FxHashMap<String, String>>,
     /// This hook will run even if there are no matching files.
@@ -691,22 +843,38 @@ pub(crate) struct HookOptions {
     /// If this hook fails, don't run any more hooks.
     /// Default is false.
     pub fail_fast: Option<bool>,
+    /// Report failures of this hook without failing the run.
+    /// Default is false.
+    pub advisory: Option<bool>,
     /// Append filenames that would be checked to the hook entry as arguments.
     /// Default is true.
     pub pass_filenames: Option<PassFilenames>,
+    /// Pass filenames relative to the git repository root and run the hook from there,
+    /// instead of relative to the project directory.
+    /// Default is false.
+    pub files_from_repo_root: Option<bool>,
     /// A description of the hook. For metadata only.
     pub description: Option<String>,
     /// Run the hook on a specific version of the language.
+    /// Either a version, or a map of target triple patterns to versions where the first
+    /// pattern matching the host wins.
     /// Default is `default`.
     /// See <https://pre-commit.com/#overriding-language-version>.
-    pub language_version: Option<String>,
+    pub language_version: Option<LanguageVersion>,
     /// Write the output of the hook to a file when the hook fails or verbose is enabled.
     pub log_file: Option<String>,
+    /// A hint printed after the message of a `fail` hook, e.g. to explain what the forbidden
+    /// files are and how to get rid of them.
+    /// Only supported by `fail` hooks.
+    pub pattern_hint: Option<String>,
     /// Run the hook entry through a predefined shell adapter.
     pub shell: Option<Shell>,
     /// This hook will execute using a single process instead of in parallel.
     /// Default is false.
     pub require_serial: Option<bool>,
+    /// Run this hook on its own: no other hook of the same project runs while it does.
+    /// Default is false.
+    pub require_serial_project: Option<bool>,
     /// Select which Git hook stages this hook runs for.
     /// Default all stages are selected.
     /// See <https://pre-commit.com/#confining-hooks-to-run-at-certain-stages>.
@@ -714,6 +882,32 @@ pub(crate) struct HookOptions {
     /// Print the output of the hook even if it passes.
     /// Default is false.
     pub verbose: Option<bool>,
+    /// Stream the output of the hook live while it runs, instead of only showing it once the
+    /// hook finishes.
+    /// Default is false.
+    pub stream_output: Option<bool>,
+    /// The encoding used to decode the hook's output for display, e.g. `cp936` or `shift_jis`.
+    /// Also exported as `PYTHONIOENCODING` for `python` hooks.
+    /// Default is UTF-8, replacing invalid sequences.
+    pub output_encoding: Option<String>,
+    /// Exit codes that mean the hook passed.
+    /// Default is `[0]`.
+    pub success_exit_codes: Option<Vec<i32>>,
+    /// Exit codes that mean the hook passed with notes: its output is shown, but the run does
+    /// not fail.
+    /// Default is none.
+    pub warning_exit_codes: Option<Vec<i32>>,
+    /// Commands to run once in the hook environment after it is installed.
+    /// They are re-run only when the environment is rebuilt.
+    pub setup: Option<Vec<String>>,
+    /// Run the hook from the project's own `node_modules/.bin` when it exists, instead of
+    /// installing a separate environment.
+    /// Only supported by local `node` hooks.
+    /// Default is false.
+    pub use_project_node_modules: Option<bool>,
+    /// Run the hook in a sandbox (`container`) or directly on the host (`none`).
+    /// Default is the `isolation` of the global config, or `none`.
+    pub isolation: Option<Isolation>,
     /// The minimum version of prek required to run this hook.
     #[serde(deserialize_with = "deserialize_and_validate_minimum_version", default)]
     pub minimum_prek_version: Option<String>,
@@ -743,16 +937,28 @@ impl HookOptions {
             exclude_types,
             additional_dependencies,
             args,
+            args_from,
             always_run,
             fail_fast,
+            advisory,
             pass_filenames,
+            files_from_repo_root,
             description,
             language_version,
             log_file,
+            pattern_hint,
             shell,
             require_serial,
+            require_serial_project,
             stages,
             verbose,
+            stream_output,
+            output_encoding,
+            success_exit_codes,
+            warning_exit_codes,
+            setup,
+            use_project_node_modules,
+            isolation,
             minimum_prek_version,
         );
 
@@ -1008,6 +1214,12 @@ pub(crate) struct RemoteRepo {
     pub rev: String,
     #[serde(skip_serializing)]
     pub hooks: Vec<RemoteHook>,
+    /// File include pattern applied to every hook of this repo, in addition to the hook's own.
+    #[serde(default, skip_serializing)]
+    pub files: Option<FilePattern>,
+    /// File exclude pattern applied to every hook of this repo, in addition to the hook's own.
+    #[serde(default, skip_serializing)]
+    pub exclude: Option<FilePattern>,
 
     #[serde(skip_serializing, flatten)]
     _unused_keys: BTreeMap<String, serde_json::Value>,
@@ -1036,6 +1248,8 @@ impl RemoteRepo {
             resolved_source: None,
             rev,
             hooks,
+            files: None,
+            exclude: None,
             _unused_keys: BTreeMap::new(),
         }
     }
@@ -1069,11 +1283,14 @@ impl std::fmt::Debug for RemoteRepo {
         if let Some(source) = &self.resolved_source {
             debug.field("source", source);
         }
-        debug
-            .field("rev", &self.rev)
-            .field("hooks", &self.hooks)
-            .field("_unused_keys", &self._unused_keys)
-            .finish()
+        debug.field("rev", &self.rev).field("hooks", &self.hooks);
+        if let Some(files) = &self.files {
+            debug.field("files", files);
+        }
+        if let Some(exclude) = &self.exclude {
+            debug.field("exclude", exclude);
+        }
+        debug.field("_unused_keys", &self._unused_keys).finish()
     }
 }
 
@@ -1158,6 +1375,10 @@ impl<'de> Deserialize<'de> for Repo {
                 let mut repo: Option<String> = None;
                 let mut rev: Option<String> = None;
                 let mut hooks: Option<HooksValue> = None;
+                // Only remote repos accept file patterns, so keep the raw values until the repo
+                // kind is known; other repos report them as unused keys.
+                let mut files: Option<serde_json::Value> = None;
+                let mut exclude: Option<serde_json::Value> = None;
                 let mut unused = BTreeMap::new();
 
                 while let Some(key) = map.next_key::<String>()? {
@@ -1169,6 +1390,12 @@ impl<'de> Deserialize<'de> for Repo {
                         "rev" => {
                             rev = Some(map.next_value()?);
                         }
+                        "files" => {
+                            files = Some(map.next_value()?);
+                        }
+                        "exclude" => {
+                            exclude = Some(map.next_value()?);
+                        }
                         "hooks" => {
                             hooks = Some(match repo.as_deref() {
                                 Some("local") => HooksValue::Local(map.next_value()?),
@@ -1186,6 +1413,13 @@ impl<'de> Deserialize<'de> for Repo {
                 }
 
                 let repo_value = repo.ok_or_else(|| M::Error::missing_field("repo"))?;
+                if matches!(repo_value.as_str(), "local" | "meta" | "builtin") {
+                    for (key, value) in [("files", files.take()), ("exclude", exclude.take())] {
+                        if let Some(value) = value {
+                            unused.insert(key.to_string(), value);
+                        }
+                    }
+                }
                 match repo_value.as_str() {
                     "local" => {
                         if rev.is_some() {
@@ -1255,11 +1489,21 @@ impl<'de> Deserialize<'de> for Repo {
                                 return Err(M::Error::custom("invalid hooks for remote repo"));
                             }
                         };
+                        let files = files
+                            .map(FilePattern::deserialize)
+                            .transpose()
+                            .map_err(M::Error::custom)?;
+                        let exclude = exclude
+                            .map(FilePattern::deserialize)
+                            .transpose()
+                            .map_err(M::Error::custom)?;
                         Ok(Repo::Remote(RemoteRepo {
                             repo: repo_value,
                             resolved_source: None,
                             rev,
                             hooks,
+                            files,
+                            exclude,
                             _unused_keys: unused,
                         }))
                     }
@@ -1357,6 +1601,97 @@ pub(crate) struct UpdateOptions {
     pub(crate) repos: BTreeMap<String, RepoTagFilterOptions>,
 }
 
+/// How often pre-commit.ci opens autoupdate pull requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) enum AutoupdateSchedule {
+    Weekly,
+    Monthly,
+    Quarterly,
+}
+
+/// Settings read by [pre-commit.ci](https://pre-commit.ci).
+///
+/// prek does not act on these itself, but validates them and includes them in
+/// `prek export-config` so self-hosted automation can follow the same settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) struct CiOptions {
+    /// Commit message used for automatic fixes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) autofix_commit_msg: Option<String>,
+    /// Whether to push automatic fixes to pull requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) autofix_prs: Option<bool>,
+    /// Branch that autoupdate pull requests target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) autoupdate_branch: Option<String>,
+    /// Commit message used for autoupdate pull requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) autoupdate_commit_msg: Option<String>,
+    /// How often to open autoupdate pull requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) autoupdate_schedule: Option<AutoupdateSchedule>,
+    /// Hook ids to skip in CI, e.g. hooks that need network access or local tools.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) skip: Vec<String>,
+    /// Whether to check out submodules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) submodules: Option<bool>,
+
+    #[serde(skip_serializing, flatten)]
+    _unused_keys: BTreeMap<String, serde_json::Value>,
+}
+
+/// An external command that lists the files to run hooks on, in place of Git.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) struct FileCollectorConfig {
+    /// The command to run, split into arguments like a hook `entry`.
+    pub(crate) entry: String,
+}
+
+/// Custom file type identification: mappings from file extensions and file names to tags,
+/// and content-based detection.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub(crate) struct IdentifyOptions {
+    /// Tags for files with these extensions, matched case-insensitively, e.g. `snake` or
+    /// `tar.zst`.
+    #[serde(default)]
+    pub(crate) extensions: BTreeMap<String, TagSet>,
+    /// Tags for files with exactly these names, e.g. `Snakefile`.
+    #[serde(default)]
+    pub(crate) filenames: BTreeMap<String, TagSet>,
+    /// Tag extensionless text files of otherwise unknown type, such as configuration files, as
+    /// `json`, `yaml`, `toml` or `xml` based on their first KB.
+    /// Default is false.
+    #[serde(default)]
+    pub(crate) detect_content: bool,
+}
+
+impl IdentifyOptions {
+    /// Make these mappings available to file type detection for the rest of the process.
+    pub(crate) fn register(&self) {
+        prek_identify::set_content_detection(self.detect_content);
+
+        let mut custom = CustomTags::default();
+        for (extension, tags) in &self.extensions {
+            custom.add_extension(extension, *tags);
+        }
+        for (filename, tags) in &self.filenames {
+            custom.add_filename(filename.clone(), *tags);
+        }
+        if !custom.is_empty() {
+            prek_identify::set_custom_tags(custom);
+        }
+    }
+}
+
 // TODO: warn sensible regex
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1402,6 +1737,28 @@ pub(crate) struct Config {
     /// When false (default), files in subprojects are processed by both the subproject and
     /// any parent projects that contain them.
     pub orphan: Option<bool>,
+    /// Set to true to mark files created by hooks with `git add --intent-to-add`, so they show
+    /// up in `git diff` and are not left out of the commit.
+    /// Default is false.
+    pub intent_to_add: Option<bool>,
+    /// A command that lists the files to run hooks on, replacing Git for staged, `--all-files`
+    /// and `--from-ref` runs, e.g. to ask a build system which files a change affects.
+    /// Only read from the workspace root configuration.
+    pub file_collector: Option<FileCollectorConfig>,
+    /// Workspace projects whose configuration file has a non-standard name, as a mapping from
+    /// the project directory to its configuration file, e.g. `"app" = "ci/pre-commit.yaml"`.
+    /// Project directories are relative to the workspace root, configuration files are relative
+    /// to their project directory.
+    /// Only read from the workspace root configuration.
+    pub projects: Option<BTreeMap<String, PathBuf>>,
+    /// Custom file type tags for `types`, `types_or` and `exclude_types`, mapping file
+    /// extensions and file names to tags, e.g. `extensions = { snake = ["python", "text"] }`.
+    /// They take precedence over the built-in mappings.
+    /// Tags must be ones prek already knows, new tag names cannot be defined.
+    /// Only read from the workspace root configuration.
+    pub identify: Option<IdentifyOptions>,
+    /// Settings for pre-commit.ci, accepted so configurations can be shared with it.
+    pub ci: Option<CiOptions>,
 
     #[serde(skip_serializing, flatten)]
     _unused_keys: BTreeMap<String, serde_json::Value>,
@@ -1430,6 +1787,83 @@ impl Config {
         Ok(())
     }
 
+    /// The location, id and alias of every hook, ignoring aliases that are empty or equal the id.
+    fn hook_aliases(&self) -> Vec<(String, &str, Option<&str>)> {
+        let mut hooks: Vec<(String, &str, Option<&str>)> = Vec::new();
+        macro_rules! collect_hooks {
+            ($repo_idx:expr, $hooks:expr) => {
+                for (hook_idx, hook) in $hooks.iter().enumerate() {
+                    hooks.push((
+                        format!("repos[{}].hooks[{hook_idx}]", $repo_idx),
+                        hook.id.as_str(),
+                        hook.options
+                            .alias
+                            .as_deref()
+                            .filter(|alias| !alias.is_empty() && *alias != hook.id),
+                    ));
+                }
+            };
+        }
+
+        for (repo_idx, repo) in self.repos.iter().enumerate() {
+            match repo {
+                Repo::Remote(repo) => collect_hooks!(repo_idx, repo.hooks),
+                Repo::Local(repo) => collect_hooks!(repo_idx, repo.hooks),
+                Repo::Meta(repo) => collect_hooks!(repo_idx, repo.hooks),
+                Repo::Builtin(repo) => collect_hooks!(repo_idx, repo.hooks),
+            }
+        }
+        hooks
+    }
+
+    /// Reject hook aliases that collide with the id of another hook, as selecting by that id
+    /// would silently also select the aliased hook.
+    ///
+    /// Repeating the same hook id is allowed, an alias is what tells those hooks apart.
+    fn validate_aliases(&self) -> std::result::Result<(), Error> {
+        let hooks = self.hook_aliases();
+        for (location, id, alias) in &hooks {
+            let Some(alias) = alias else {
+                continue;
+            };
+            if let Some((other_location, other_id, _)) =
+                hooks.iter().find(|(_, other_id, _)| other_id == alias)
+            {
+                return Err(Error::AliasConflictsWithId {
+                    alias: (*alias).to_string(),
+                    hook: (*id).to_string(),
+                    location: location.clone(),
+                    other_hook: (*other_id).to_string(),
+                    other_location: other_location.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Describe hook aliases shared by several hooks, selecting such an alias selects all of them.
+    fn duplicate_aliases(&self) -> Vec<String> {
+        let hooks = self.hook_aliases();
+        let mut duplicates = Vec::new();
+        for (idx, (location, id, alias)) in hooks.iter().enumerate() {
+            let Some(alias) = alias else {
+                continue;
+            };
+            if let Some((other_location, other_id, _)) = hooks[..idx]
+                .iter()
+                .find(|(_, _, other_alias)| other_alias == &Some(*alias))
+            {
+                duplicates.push(format!(
+                    "Alias `{}` of hook `{}` at `{location}` is also used by hook `{}` at `{other_location}`, selecting it runs both hooks",
+                    alias.cyan(),
+                    id.cyan(),
+                    other_id.cyan(),
+                ));
+            }
+        }
+        duplicates
+    }
+
     /// Resolve local relative repository sources from the config file, not the process cwd.
     fn resolve_relative_repo_sources(&mut self, config_path: &Path) -> Result<(), Error> {
         let config_dir = config_path
@@ -1476,6 +1910,17 @@ pub(crate) enum Error {
 
     #[error("Priority alias `{alias}` referenced by hook `{hook}` is not declared in `priorities`")]
     UnknownPriorityAlias { hook: String, alias: PriorityAlias },
+
+    #[error(
+        "Alias `{alias}` of hook `{hook}` at `{location}` collides with the id of hook `{other_hook}` at `{other_location}`"
+    )]
+    AliasConflictsWithId {
+        alias: String,
+        hook: String,
+        location: String,
+        other_hook: String,
+        other_location: String,
+    },
 }
 
 impl Error {
@@ -1494,7 +1939,7 @@ impl Error {
 }
 
 /// Keys that prek does not use.
-const EXPECTED_UNUSED: &[&str] = &["minimum_pre_commit_version", "ci"];
+const EXPECTED_UNUSED: &[&str] = &["minimum_pre_commit_version"];
 
 fn push_unused_paths<'a, I>(acc: &mut Vec<String>, prefix: &str, keys: I)
 where
@@ -1521,6 +1966,9 @@ fn collect_unused_paths(config: &Config) -> Vec<String> {
             (!EXPECTED_UNUSED.contains(&key)).then_some(key)
         }),
     );
+    if let Some(ci) = &config.ci {
+        push_unused_paths(&mut paths, "ci", ci._unused_keys.keys().map(String::as_str));
+    }
 
     for (repo_idx, repo) in config.repos.iter().enumerate() {
         let (repo_unused_keys, hooks_options): (_, Box<dyn Iterator<Item = &HookOptions>>) =
@@ -1604,6 +2052,7 @@ pub(crate) fn load_config(path: &Path) -> Result<Config, Error> {
             .map_err(|e| Error::Yaml(path.user_display().to_string(), Box::new(e)))?,
     };
     config.validate_priorities()?;
+    config.validate_aliases()?;
     config.resolve_relative_repo_sources(path)?;
 
     Ok(config)
@@ -1616,16 +2065,16 @@ pub(crate) fn read_config(path: &Path) -> Result<Config, Error> {
     let unused_paths = collect_unused_paths(&config);
     warn_unused_paths(path, &unused_paths);
 
+    for duplicate in config.duplicate_aliases() {
+        warn_user!("{duplicate}");
+    }
+
     // Check for mutable revs and warn the user.
     let mutable_revs = config
         .repos
         .iter()
         .filter_map(|repo| match repo {
-            // A rev is considered mutable if it doesn't contain a '.' (like a version)
-            // and is not a hexadecimal string (like a commit SHA).
-            Repo::Remote(repo) if !repo.rev.contains('.') && !looks_like_sha(&repo.rev) => {
-                Some(repo)
-            }
+            Repo::Remote(repo) if is_mutable_rev(&repo.rev) => Some(repo),
             _ => None,
         })
         .map(|repo| format!("{}: {}", repo.repo().cyan(), repo.rev.yellow()))
@@ -1656,6 +2105,14 @@ pub(crate) fn read_manifest(path: &Path) -> Result<Manifest, Error> {
     Ok(manifest)
 }
 
+/// Check if a `rev` looks like a branch or a moving tag rather than a version or a commit.
+///
+/// A rev is considered mutable if it doesn't contain a '.' (like a version)
+/// and is not a hexadecimal string (like a commit SHA).
+pub(crate) fn is_mutable_rev(rev: &str) -> bool {
+    !rev.contains('.') && !looks_like_sha(rev)
+}
+
 /// Check if a string looks like a git SHA-1.
 pub(crate) fn looks_like_sha(s: &str) -> bool {
     !s.is_empty() && s.as_bytes().iter().all(u8::is_ascii_hexdigit)
@@ -2034,6 +2491,44 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn remote_repo_file_patterns() {
+        let yaml = indoc::indoc! {r"
+            repos:
+              - repo: https://github.com/example/hooks
+                rev: v1.0.0
+                files: ^src/
+                exclude:
+                  glob: src/vendor/**
+                hooks:
+                  - id: lint
+              - repo: local
+                files: ^src/
+                hooks:
+                  - id: fmt
+                    name: fmt
+                    entry: fmt
+                    language: system
+        "};
+        let config = serde_saphyr::from_str::<Config>(yaml).unwrap();
+
+        let Repo::Remote(remote) = &config.repos[0] else {
+            panic!("expected a remote repo");
+        };
+        let files = remote.files.as_ref().expect("files should be set");
+        let exclude = remote.exclude.as_ref().expect("exclude should be set");
+        assert!(files.is_match(Path::new("src/main.rs")));
+        assert!(!files.is_match(Path::new("docs/index.md")));
+        assert!(exclude.is_match(Path::new("src/vendor/lib.rs")));
+        assert!(remote._unused_keys.is_empty());
+
+        // Other repos keep reporting `files` as an unexpected key.
+        let Repo::Local(local) = &config.repos[1] else {
+            panic!("expected a local repo");
+        };
+        assert!(local._unused_keys.contains_key("files"));
+    }
+
     #[test]
     fn parse_hooks() {
         // Remote hook only `id` is required.
@@ -2214,6 +2709,95 @@ mod tests {
         insta::assert_debug_snapshot!(result);
     }
 
+    #[test]
+    fn language_version_by_target() {
+        let yaml = indoc::indoc! { r"
+            repos:
+              - repo: local
+                hooks:
+                  - id: hook-1
+                    name: hook 1
+                    entry: echo hello world
+                    language: node
+                    language_version:
+                      aarch64-apple-darwin: '20.11.0'
+                      '*-linux-*': '22'
+                      '*': system
+        "};
+        let config = serde_saphyr::from_str::<Config>(yaml).unwrap();
+        let Repo::Local(repo) = &config.repos[0] else {
+            panic!("expected a local repo");
+        };
+        let version = repo.hooks[0].options.language_version.as_ref().unwrap();
+        insta::assert_debug_snapshot!(version, @r#"
+        {
+            "aarch64-apple-darwin": "20.11.0",
+            "*-linux-*": "22",
+            "*": "system",
+        }
+        "#);
+
+        assert_eq!(version.resolve("aarch64-apple-darwin"), Some("20.11.0"));
+        assert_eq!(version.resolve("x86_64-unknown-linux-gnu"), Some("22"));
+        assert_eq!(version.resolve("x86_64-pc-windows-msvc"), Some("system"));
+        assert_eq!(version.as_version(), None);
+
+        let version = LanguageVersion::ByTarget(vec![(
+            Glob::new("*-apple-darwin").unwrap(),
+            "20".to_string(),
+        )]);
+        assert_eq!(version.resolve("x86_64-unknown-linux-gnu"), None);
+
+        let yaml = indoc::indoc! { r"
+            repos:
+              - repo: local
+                hooks:
+                  - id: hook-1
+                    name: hook 1
+                    entry: echo hello world
+                    language: node
+                    language_version:
+                      '[x86_64-*': '22'
+        "};
+        let err = serde_saphyr::from_str::<Config>(yaml).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid target pattern `[x86_64-*`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn language_version_number() {
+        let yaml = indoc::indoc! { r"
+            repos:
+              - repo: local
+                hooks:
+                  - id: hook-1
+                    name: hook 1
+                    entry: echo hello world
+                    language: python
+                    language_version: 3.12
+                  - id: hook-2
+                    name: hook 2
+                    entry: echo hello world
+                    language: node
+                    language_version:
+                      '*-linux-*': 22
+                      '*': 20.11
+        "};
+        let config = serde_saphyr::from_str::<Config>(yaml).unwrap();
+        let Repo::Local(repo) = &config.repos[0] else {
+            panic!("expected a local repo");
+        };
+        let version = repo.hooks[0].options.language_version.as_ref().unwrap();
+        assert_eq!(version.as_version(), Some("3.12"));
+
+        let version = repo.hooks[1].options.language_version.as_ref().unwrap();
+        assert_eq!(version.resolve("x86_64-unknown-linux-gnu"), Some("22"));
+        assert_eq!(version.resolve("aarch64-apple-darwin"), Some("20.11"));
+    }
+
     #[test]
     fn parse_update_options() {
         let yaml = indoc::indoc! {r#"
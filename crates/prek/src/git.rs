@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
 use std::str::Utf8Error;
 use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
 
 use anyhow::Result;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
@@ -136,6 +138,54 @@ pub(crate) fn git_cmd() -> Result<Cmd, Error> {
     Ok(cmd)
 }
 
+/// Delays before retrying a git command that failed because another git process, such as an
+/// editor refreshing the index, holds `index.lock`: 0.1s doubling up to 1.6s, about 3s in total.
+fn index_lock_backoff() -> impl Iterator<Item = Duration> {
+    (0..5).map(|attempt| Duration::from_millis(100 << attempt))
+}
+
+fn is_index_lock_error(stderr: &[u8]) -> bool {
+    memchr::memmem::find(stderr, b"index.lock").is_some()
+}
+
+/// Run a git command that writes the index, retrying with backoff while another git process
+/// holds `index.lock`. Fails like a checked command once the retries are exhausted.
+pub(crate) async fn output_retrying_index_lock(cmd: &mut Cmd) -> Result<Output, Error> {
+    cmd.check(false);
+    let mut backoff = index_lock_backoff();
+    loop {
+        let output = cmd.output().await?;
+        if !output.status.success()
+            && is_index_lock_error(&output.stderr)
+            && let Some(delay) = backoff.next()
+        {
+            debug!("`index.lock` is held by another git process, retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+        return Ok(cmd.check_output(output)?);
+    }
+}
+
+/// Blocking version of [`output_retrying_index_lock`], for restoring the working tree on drop.
+///
+/// The caller checks the exit status.
+pub(crate) fn output_retrying_index_lock_blocking(cmd: &mut Command) -> std::io::Result<Output> {
+    let mut backoff = index_lock_backoff();
+    loop {
+        let output = cmd.output()?;
+        if !output.status.success()
+            && is_index_lock_error(&output.stderr)
+            && let Some(delay) = backoff.next()
+        {
+            debug!("`index.lock` is held by another git process, retrying in {delay:?}");
+            std::thread::sleep(delay);
+            continue;
+        }
+        return Ok(output);
+    }
+}
+
 fn zsplit(s: &[u8]) -> Result<Vec<PathBuf>, Utf8Error> {
     s.split(|&b| b == b'\0')
         .filter(|slice| !slice.is_empty())
@@ -172,6 +222,37 @@ pub(crate) async fn intent_to_add_files(root: &Path) -> Result<Vec<PathBuf>, Err
     Ok(zsplit(&output.stdout)?)
 }
 
+/// List the untracked (and not ignored) files under `path`, relative to the repository root.
+pub(crate) async fn untracked_files(path: &Path) -> Result<BTreeSet<PathBuf>, Error> {
+    let output = git_cmd()?
+        .arg("ls-files")
+        .arg("--others")
+        .arg("--exclude-standard")
+        .arg("--full-name")
+        .arg("-z")
+        .arg("--")
+        .arg(path)
+        .check(true)
+        .output()
+        .await?;
+    Ok(zsplit(&output.stdout)?.into_iter().collect())
+}
+
+/// Mark `paths`, relative to the repository root, with `git add --intent-to-add`.
+pub(crate) async fn intent_to_add(paths: &[PathBuf]) -> Result<()> {
+    let git_root = GIT_ROOT.as_ref()?;
+    output_retrying_index_lock(
+        git_cmd()?
+            .current_dir(git_root)
+            .arg("add")
+            .arg("--intent-to-add")
+            .arg("--")
+            .file_args(paths),
+    )
+    .await?;
+    Ok(())
+}
+
 pub(crate) async fn get_added_files(root: &Path) -> Result<Vec<PathBuf>, Error> {
     let output = git_cmd()?
         .current_dir(root)
@@ -226,6 +307,28 @@ pub(crate) async fn get_changed_files(
     Ok(zsplit(&output.stdout)?)
 }
 
+/// List the files under `root` that changed between `rev` and the working tree, relative to the
+/// repository root. Staged, unstaged, and untracked files are all included.
+pub(crate) async fn get_changed_files_since(rev: &str, root: &Path) -> Result<Vec<PathBuf>, Error> {
+    let output = git_cmd()?
+        .arg("diff")
+        .arg("--name-only")
+        .arg("--diff-filter=ACMRT")
+        .hidden_args(["--no-ext-diff"])
+        .arg("-z") // Use NUL as line terminator
+        .arg(rev)
+        .arg("--")
+        .arg(root)
+        .check(true)
+        .output()
+        .await?;
+    let mut files = zsplit(&output.stdout)?;
+    files.extend(untracked_files(root).await?);
+    files.sort_unstable();
+    files.dedup();
+    Ok(files)
+}
+
 #[instrument(level = "trace", skip(paths))]
 pub(crate) async fn ls_files<P>(
     cwd: &Path,
@@ -248,6 +351,163 @@ where
     Ok(zsplit(&output.stdout)?)
 }
 
+/// Return the paths tracked in the index under `cwd`, relative to `cwd`.
+///
+/// This is equivalent to `git ls-files`, but reads the index file directly instead of
+/// streaming every path through a pipe, which matters on repositories with hundreds of
+/// thousands of tracked files. Falls back to `git ls-files` for index layouts we don't parse.
+#[instrument(level = "trace")]
+pub(crate) async fn ls_index_files(cwd: &Path) -> Result<Vec<PathBuf>, Error> {
+    let output = git_cmd()?
+        .current_dir(cwd)
+        .arg("rev-parse")
+        .arg("--show-object-format")
+        .arg("--show-prefix")
+        .arg("--git-path")
+        .arg("index")
+        .check(false)
+        .output()
+        .await?;
+
+    if output.status.success() {
+        let mut lines = output.stdout.split(|&b| b == b'\n');
+        if let (Some(object_format), Some(prefix), Some(index)) =
+            (lines.next(), lines.next(), lines.next())
+        {
+            let hash_len = match object_format {
+                b"sha1" => Some(20),
+                b"sha256" => Some(32),
+                _ => None,
+            };
+            if let Some(hash_len) = hash_len {
+                let index = cwd.join(path_from_git_bytes(index)?);
+                let data = fs_err::tokio::read(&index).await?;
+                if let Some(paths) = parse_index_paths(&data, hash_len) {
+                    return paths
+                        .iter()
+                        .filter_map(|path| path.strip_prefix(prefix))
+                        .map(|path| path_from_git_bytes(path).map_err(Error::from))
+                        .collect();
+                }
+            }
+        }
+    }
+
+    debug!("Falling back to `git ls-files` to list the index");
+    ls_files(cwd, [Path::new(".")]).await
+}
+
+/// Parse the paths of all entries in a git index file, in index order.
+///
+/// Returns `None` for malformed files and for layouts that don't list every tracked path
+/// (split index, sparse directory entries).
+fn parse_index_paths(data: &[u8], hash_len: usize) -> Option<Vec<Vec<u8>>> {
+    const SPARSE_DIR_MODE: u32 = 0o040_000;
+    const EXTENDED_FLAG: u16 = 0x4000;
+
+    let read_u32 = |pos: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+    };
+
+    if data.get(..4)? != b"DIRC" {
+        return None;
+    }
+    let version = read_u32(4)?;
+    if !(2..=4).contains(&version) {
+        return None;
+    }
+    let count = usize::try_from(read_u32(8)?).ok()?;
+
+    let mut paths: Vec<Vec<u8>> = Vec::with_capacity(count);
+    let mut pos = 12;
+    for _ in 0..count {
+        let entry_start = pos;
+        // ctime, mtime, dev, ino, mode, uid, gid, size, then the object id.
+        let mode = read_u32(pos + 24)?;
+        if mode == SPARSE_DIR_MODE {
+            return None;
+        }
+        pos += 40 + hash_len;
+        let flags = u16::from_be_bytes(data.get(pos..pos + 2)?.try_into().ok()?);
+        pos += 2;
+        if flags & EXTENDED_FLAG != 0 {
+            pos += 2;
+        }
+
+        let path = if version == 4 {
+            // Version 4 prefix-compresses each path against the previous entry.
+            let (strip, varint_len) = read_offset_varint(data.get(pos..)?)?;
+            pos += varint_len;
+            let suffix_len = data.get(pos..)?.iter().position(|&b| b == b'\0')?;
+            let previous = paths.last().map_or(&[][..], Vec::as_slice);
+            let keep = previous.len().checked_sub(strip)?;
+            let mut path = previous[..keep].to_vec();
+            path.extend_from_slice(&data[pos..pos + suffix_len]);
+            pos += suffix_len + 1;
+            path
+        } else {
+            let name_len = data.get(pos..)?.iter().position(|&b| b == b'\0')?;
+            let path = data[pos..pos + name_len].to_vec();
+            // Entries are padded with 1-8 NUL bytes to a multiple of eight bytes.
+            pos = entry_start + ((pos - entry_start + name_len + 8) & !7);
+            path
+        };
+
+        // Unmerged paths have one entry per stage.
+        if paths.last() != Some(&path) {
+            paths.push(path);
+        }
+    }
+
+    // A split index only stores the entries changed since the shared index.
+    let end = data.len().checked_sub(hash_len)?;
+    while pos + 8 <= end {
+        if data.get(pos..pos + 4)? == b"link" {
+            return None;
+        }
+        pos += 8 + usize::try_from(read_u32(pos + 4)?).ok()?;
+    }
+
+    Some(paths)
+}
+
+/// Decode the offset varint used by index version 4.
+fn read_offset_varint(data: &[u8]) -> Option<(usize, usize)> {
+    let mut byte = *data.first()?;
+    let mut value = usize::from(byte & 0x7f);
+    let mut len = 1;
+    while byte & 0x80 != 0 {
+        byte = *data.get(len)?;
+        len += 1;
+        value = ((value + 1) << 7) | usize::from(byte & 0x7f);
+    }
+    Some((value, len))
+}
+
+/// Return the gitlink (submodule) entries in the index under `cwd`, relative to `cwd`.
+pub(crate) async fn ls_gitlinks(cwd: &Path) -> Result<Vec<PathBuf>, Error> {
+    let output = git_cmd()?
+        .current_dir(cwd)
+        .arg("ls-files")
+        .arg("-z")
+        .arg("-s")
+        .check(true)
+        .output()
+        .await?;
+
+    // Each entry is `<mode> <object> <stage>\t<path>`, gitlinks have mode `160000`.
+    let gitlinks = output
+        .stdout
+        .split(|&b| b == b'\0')
+        .filter(|entry| entry.starts_with(b"160000 "))
+        .filter_map(|entry| {
+            let tab = entry.iter().position(|&b| b == b'\t')?;
+            Some(path_from_git_bytes(&entry[tab + 1..]))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(gitlinks)
+}
+
 pub(crate) async fn get_git_dir() -> Result<PathBuf, Error> {
     let output = git_cmd()?
         .arg("rev-parse")
@@ -369,8 +629,55 @@ pub(crate) async fn is_in_merge_conflict() -> Result<bool, Error> {
     Ok(git_dir.join("MERGE_HEAD").try_exists()? && git_dir.join("MERGE_MSG").try_exists()?)
 }
 
+/// A multi-step Git operation that stopped and is waiting to be continued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PendingOperation {
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl std::fmt::Display for PendingOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rebase => f.write_str("rebase"),
+            Self::CherryPick => f.write_str("cherry-pick"),
+            Self::Revert => f.write_str("revert"),
+        }
+    }
+}
+
+/// Detect a rebase, cherry-pick or revert in progress from the state files Git keeps in the git dir.
+pub(crate) async fn pending_operation() -> Result<Option<PendingOperation>, Error> {
+    let git_dir = get_git_dir().await?;
+    if git_dir.join("rebase-merge").try_exists()? || git_dir.join("rebase-apply").try_exists()? {
+        return Ok(Some(PendingOperation::Rebase));
+    }
+    if git_dir.join("CHERRY_PICK_HEAD").try_exists()? {
+        return Ok(Some(PendingOperation::CherryPick));
+    }
+    if git_dir.join("REVERT_HEAD").try_exists()? {
+        return Ok(Some(PendingOperation::Revert));
+    }
+    Ok(None)
+}
+
+/// Returns true if tracked files under `root` have changes that are not staged.
+pub(crate) async fn has_unstaged_changes(root: &Path) -> Result<bool, Error> {
+    let status = git_cmd()?
+        .arg("diff")
+        .arg("--quiet")
+        .hidden_args(["--ignore-submodules", "--no-ext-diff"])
+        .arg("--")
+        .arg(root)
+        .check(false)
+        .status()
+        .await?;
+    Ok(status.code() == Some(1))
+}
+
 pub(crate) async fn get_conflicted_files(root: &Path) -> Result<Vec<PathBuf>, Error> {
-    let tree = git_cmd()?.arg("write-tree").check(true).output().await?;
+    let tree = output_retrying_index_lock(git_cmd()?.arg("write-tree")).await?;
 
     let output = git_cmd()?
         .arg("diff")
@@ -410,43 +717,62 @@ async fn parse_merge_msg_for_conflicts() -> Result<Vec<PathBuf>, Error> {
     Ok(conflicts)
 }
 
-#[instrument(level = "trace")]
-pub(crate) async fn has_worktree_diff(path: &Path) -> Result<bool, Error> {
-    let mut cmd = git_cmd()?;
-    let status = cmd
-        .arg("diff-files")
-        .arg("--quiet")
-        .hidden_args(["--no-ext-diff", "--no-textconv", "--ignore-submodules"])
-        .arg("--")
-        .arg(path)
-        .check(false)
-        .status()
-        .await?;
+/// One changed path reported by `git status --porcelain=v2`.
+#[derive(Debug, PartialEq, Eq)]
+struct StatusEntry {
+    /// The raw status fields preceding the path: `XY`, submodule state, modes and object names.
+    fields: Vec<u8>,
+    /// The source path of a staged rename or copy.
+    orig_path: Option<PathBuf>,
+    /// A hash of the worktree content, for paths with unstaged changes.
+    worktree: Option<u64>,
+}
 
-    if status.success() {
-        return Ok(false);
-    }
-    if status.code() == Some(1) {
-        return Ok(true);
+/// A snapshot of the changed tracked paths and the untracked files under a pathspec.
+///
+/// Two snapshots compare equal when neither the index nor the worktree content of any
+/// changed path differs between them, and they list the same untracked files.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct StatusSnapshot {
+    entries: BTreeMap<PathBuf, StatusEntry>,
+    untracked: BTreeSet<PathBuf>,
+}
+
+impl StatusSnapshot {
+    /// Returns true if no tracked path under the pathspec has staged or unstaged changes.
+    ///
+    /// Untracked files are not considered.
+    pub(crate) fn is_clean(&self) -> bool {
+        self.entries.is_empty()
     }
 
-    cmd.check_status(status)?;
-    Ok(true)
+    /// The untracked (and not ignored) files, relative to the repository root.
+    pub(crate) fn untracked(&self) -> &BTreeSet<PathBuf> {
+        &self.untracked
+    }
 }
 
+/// Take a [`StatusSnapshot`] of `path` with a single `git status --porcelain=v2 -z` call.
+///
+/// `git status` refreshes stat information in memory, so in-place rewrites with identical
+/// content are not reported, and it honors the user's `core.untrackedCache` and
+/// `status.renames` settings.
 #[instrument(level = "trace")]
-pub(crate) async fn get_diff(path: &Path) -> Result<Vec<u8>, Error> {
+pub(crate) async fn status_snapshot(path: &Path) -> Result<StatusSnapshot> {
+    let git_root = GIT_ROOT.as_ref()?;
     let output = git_cmd()?
-        .arg("diff")
-        .hidden_args(["--no-ext-diff", "--no-textconv", "--ignore-submodules"])
+        // Don't take `index.lock` to write back refreshed stat information,
+        // hooks of other projects may be running concurrently.
+        .arg("--no-optional-locks")
+        .arg("status")
+        .arg("--porcelain=v2")
+        .arg("-z")
+        .arg("--untracked-files=all")
+        .arg("--ignore-submodules")
         .arg("--")
         .arg(path)
-        // This diff is only used as a best-effort before/after snapshot of
-        // hook changes. Some CI environments keep enough of `.git` for
-        // `git ls-files` but omit blob objects needed by `git diff`; Git then
-        // exits 128 on stderr with empty stdout. Keep comparing stdout in that
-        // case so `run --all-files` can still run against the files Git can
-        // enumerate.
+        // This snapshot is only a best-effort before/after comparison of hook
+        // changes, keep comparing stdout even if git reports an error.
         .check(false)
         .output()
         .await?;
@@ -454,10 +780,81 @@ pub(crate) async fn get_diff(path: &Path) -> Result<Vec<u8>, Error> {
         debug!(
             status = %output.status,
             stderr = %String::from_utf8_lossy(&output.stderr),
-            "Continuing with git diff stdout despite non-zero exit status"
+            "Continuing with git status stdout despite non-zero exit status"
         );
     }
-    Ok(output.stdout)
+
+    let (records, untracked) = parse_porcelain_v2(&output.stdout)?;
+    let mut entries = BTreeMap::new();
+    for (path, fields, orig_path) in records {
+        // `fields` starts with `XY`, `Y` is the worktree status.
+        let worktree = if fields.get(1).is_some_and(|&y| y != b'.') {
+            fs_err::tokio::read(git_root.join(&path))
+                .await
+                .ok()
+                .map(|content| seahash::hash(&content))
+        } else {
+            None
+        };
+        entries.insert(
+            path,
+            StatusEntry {
+                fields,
+                orig_path,
+                worktree,
+            },
+        );
+    }
+
+    Ok(StatusSnapshot { entries, untracked })
+}
+
+/// A changed tracked path from `git status --porcelain=v2`, as `(path, fields, orig_path)`.
+type PorcelainRecord = (PathBuf, Vec<u8>, Option<PathBuf>);
+
+/// Parse `git status --porcelain=v2 -z` output into changed tracked path records and the
+/// untracked paths.
+fn parse_porcelain_v2(
+    output: &[u8],
+) -> Result<(Vec<PorcelainRecord>, BTreeSet<PathBuf>), Utf8Error> {
+    let mut records = Vec::new();
+    let mut untracked = BTreeSet::new();
+    let mut parts = output
+        .split(|&b| b == b'\0')
+        .filter(|part| !part.is_empty());
+
+    while let Some(record) = parts.next() {
+        if let Some(path) = record.strip_prefix(b"? ") {
+            untracked.insert(path_from_git_bytes(path)?);
+            continue;
+        }
+
+        // The number of space separated fields before the path, see `git help status`.
+        let field_count = match record.first().copied() {
+            Some(b'1') => 8,
+            Some(b'2') => 9,
+            Some(b'u') => 10,
+            _ => continue,
+        };
+        let mut split = record.splitn(field_count + 1, |&b| b == b' ');
+        let fields = split
+            .by_ref()
+            .skip(1)
+            .take(field_count - 1)
+            .collect::<Vec<_>>()
+            .join(&b' ');
+        let Some(path) = split.next() else {
+            continue;
+        };
+        let orig_path = if record[0] == b'2' {
+            parts.next().map(path_from_git_bytes).transpose()?
+        } else {
+            None
+        };
+        records.push((path_from_git_bytes(path)?, fields, orig_path));
+    }
+
+    Ok((records, untracked))
 }
 
 /// Create a tree object from the current index.
@@ -465,12 +862,106 @@ pub(crate) async fn get_diff(path: &Path) -> Result<Vec<u8>, Error> {
 /// The name of the new tree object is printed to standard output.
 /// The index must be in a fully merged state.
 pub(crate) async fn write_tree() -> Result<String, Error> {
-    let output = git_cmd()?.arg("write-tree").check(true).output().await?;
+    let output = output_retrying_index_lock(git_cmd()?.arg("write-tree")).await?;
     Ok(String::from_utf8_lossy(&output.stdout)
         .trim_ascii()
         .to_string())
 }
 
+/// Resolve `git rev-parse --git-path <path>` against `root`.
+async fn git_path(root: &Path, path: &str) -> Result<PathBuf> {
+    let output = git_cmd()?
+        .current_dir(root)
+        .arg("rev-parse")
+        .arg("--git-path")
+        .arg(path)
+        .check(true)
+        .output()
+        .await?;
+    Ok(root.join(path_from_git_bytes(output.stdout.trim_ascii())?))
+}
+
+/// Environment that makes Git write new objects to the private `objects` directory, while still
+/// reading the objects of the repository at `root`.
+async fn private_objects_env(root: &Path, objects: &Path) -> Result<[(&'static str, OsString); 2]> {
+    let repo_objects = git_path(root, "objects").await?;
+    fs_err::create_dir_all(objects)?;
+    Ok([
+        ("GIT_OBJECT_DIRECTORY", objects.as_os_str().to_owned()),
+        (
+            "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+            std::path::absolute(repo_objects)?.into_os_string(),
+        ),
+    ])
+}
+
+/// Create a tree object from the tracked files as they are in the working tree.
+///
+/// Unlike [`write_tree`], unstaged changes are included. Works on a copy of the index in
+/// `scratch` and writes new objects to the private `objects` directory, so neither the real
+/// index nor the repository's objects are touched.
+pub(crate) async fn write_worktree_tree(
+    root: &Path,
+    scratch: &Path,
+    objects: &Path,
+) -> Result<String> {
+    let index = git_path(root, "index").await?;
+    let objects_env = private_objects_env(root, objects).await?;
+
+    fs_err::create_dir_all(scratch)?;
+    let temp_dir = tempfile::tempdir_in(scratch)?;
+    let temp_index = temp_dir.path().join("index");
+    if index.exists() {
+        fs_err::tokio::copy(&index, &temp_index).await?;
+    }
+
+    git_cmd()?
+        .current_dir(root)
+        .arg("add")
+        .arg("--update")
+        .env("GIT_INDEX_FILE", &temp_index)
+        .envs(objects_env.clone())
+        .check(true)
+        .output()
+        .await?;
+    let output = git_cmd()?
+        .current_dir(root)
+        .arg("write-tree")
+        .env("GIT_INDEX_FILE", &temp_index)
+        .envs(objects_env)
+        .check(true)
+        .output()
+        .await?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_ascii()
+        .to_string())
+}
+
+/// Return the files added or modified between two trees written by [`write_worktree_tree`]
+/// with the same `objects` directory, relative to the repository root.
+pub(crate) async fn get_changed_files_between_trees(
+    root: &Path,
+    objects: &Path,
+    old: &str,
+    new: &str,
+) -> Result<Vec<PathBuf>> {
+    let output = git_cmd()?
+        .current_dir(root)
+        .arg("diff-tree")
+        .arg("-r")
+        .arg("--name-only")
+        .arg("--no-renames")
+        .arg("--diff-filter=ACMRT")
+        .arg("-z")
+        .arg(old)
+        .arg(new)
+        .envs(private_objects_env(root, objects).await?)
+        .check(true)
+        .output()
+        .await?;
+    Ok(zsplit(&output.stdout)?)
+}
+
 /// Get the path of the top-level directory of the working tree.
 #[instrument(level = "trace")]
 pub(crate) fn get_root() -> Result<PathBuf, Error> {
@@ -493,6 +984,42 @@ pub(crate) fn get_root() -> Result<PathBuf, Error> {
     path_from_git_bytes(output.stdout.trim_ascii()).map_err(Error::from)
 }
 
+/// The absolute Git directory and common directory of the current repository.
+///
+/// Both are the same, except in linked worktrees.
+pub(crate) fn get_git_dirs() -> Result<[PathBuf; 2], Error> {
+    let git = GIT.as_ref().map_err(|&e| Error::GitNotFound(e))?;
+    let mut cmd = Command::new(git);
+    let output = apply_git_work_tree(&mut cmd)
+        .arg("rev-parse")
+        .arg("--absolute-git-dir")
+        .arg("--git-common-dir")
+        .output()?;
+    if !output.status.success() {
+        return Err(Error::Command(process::Error::Status {
+            command: format!(
+                "{} rev-parse --absolute-git-dir --git-common-dir",
+                git.to_string_lossy()
+            ),
+            error: StatusError {
+                status: output.status,
+                output: Some(output),
+            },
+        }));
+    }
+
+    let mut lines = output.stdout.split(|&b| b == b'\n').map(<[u8]>::trim_ascii);
+    let git_dir = path_from_git_bytes(lines.next().unwrap_or_default())?;
+    // The common directory is relative to the current directory unless it is elsewhere.
+    let common_dir = match lines.next() {
+        Some(common_dir) if !common_dir.is_empty() => {
+            std::path::absolute(path_from_git_bytes(common_dir)?)?.clean()
+        }
+        _ => git_dir.clone(),
+    };
+    Ok([git_dir, common_dir])
+}
+
 pub(crate) async fn init_repo(url: &str, path: &Path) -> Result<(), Error> {
     git_cmd()?
         // Unset `extensions.objectFormat` if set, just follow what hash the remote uses.
@@ -563,6 +1090,31 @@ pub(crate) fn is_auth_error(err: &Error) -> bool {
     .any(|needle| error.contains(needle))
 }
 
+/// List the tag names of a remote repository without cloning it.
+pub(crate) async fn ls_remote_tags(url: &str) -> Result<Vec<String>, Error> {
+    let output = git_cmd()?
+        .arg("ls-remote")
+        .arg("--tags")
+        .arg("--refs")
+        .arg(url)
+        .isolate_from_git_env()
+        .env(EnvVars::LC_ALL, "C")
+        .env(
+            EnvVars::GIT_TERMINAL_PROMPT,
+            TerminalPrompt::Disabled.env_value(),
+        )
+        .check(true)
+        .output()
+        .await?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(_, reference)| reference.strip_prefix("refs/tags/"))
+        .map(ToString::to_string)
+        .collect())
+}
+
 async fn shallow_clone(
     rev: &str,
     path: &Path,
@@ -888,6 +1440,56 @@ pub(crate) async fn rev_exists(rev: &str) -> Result<bool, Error> {
     Ok(output.status.success())
 }
 
+/// Resolve a revision expression such as `main`, `HEAD~2` or `@{upstream}` to an object id.
+///
+/// Returns `None` if Git cannot resolve it.
+pub(crate) async fn resolve_rev(rev: &str) -> Result<Option<String>, Error> {
+    let output = git_cmd()?
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(rev)
+        .check(false)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_ascii()
+            .to_string(),
+    ))
+}
+
+/// Get the best common ancestor of two commits.
+///
+/// Returns `None` if the commits share no history.
+pub(crate) async fn merge_base(left: &str, right: &str) -> Result<Option<String>, Error> {
+    let mut cmd = git_cmd()?;
+    let output = cmd
+        .arg("merge-base")
+        .arg(left)
+        .arg(right)
+        .check(false)
+        .output()
+        .await?;
+
+    if output.status.success() {
+        return Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .trim_ascii()
+                .to_string(),
+        ));
+    }
+    if output.status.code() == Some(1) {
+        return Ok(None);
+    }
+
+    cmd.check_status(output.status)?;
+    Ok(None)
+}
+
 /// Check if `ancestor` is an ancestor of `commit`.
 pub(crate) async fn is_ancestor(ancestor: &str, commit: &str) -> Result<bool, Error> {
     let mut cmd = git_cmd()?;
@@ -966,6 +1568,34 @@ pub(crate) async fn get_parent_commit(commit: &str) -> Result<Option<String>, Er
     }
 }
 
+/// Check whether `path`, relative to `root`, is ignored by git.
+pub(crate) async fn is_ignored(root: &Path, path: &Path) -> Result<bool, Error> {
+    let output = git_cmd()?
+        .current_dir(root)
+        .arg("check-ignore")
+        .arg("-q")
+        .arg("--")
+        .arg(path)
+        .check(false)
+        .output()
+        .await?;
+    Ok(output.status.success())
+}
+
+/// Get the commit SHA checked out in the repository at `path`.
+pub(crate) async fn get_head_commit(path: &Path) -> Result<String, Error> {
+    let output = git_cmd()?
+        .current_dir(path)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .check(true)
+        .output()
+        .await?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_ascii()
+        .to_string())
+}
+
 /// Return a list of absolute paths of all git submodules in the repository.
 #[instrument(level = "trace")]
 pub(crate) fn list_submodules(git_root: &Path) -> Result<Vec<PathBuf>, Error> {
@@ -997,12 +1627,16 @@ mod tests {
     #[cfg(unix)]
     use super::zsplit;
     use super::{
-        Error, GIT, TerminalPrompt, full_clone, init_repo, shared_repository_file_mode,
-        should_update_submodules, update_submodules,
+        Error, GIT, TerminalPrompt, full_clone, init_repo, ls_index_files,
+        output_retrying_index_lock, parse_index_paths, parse_porcelain_v2,
+        shared_repository_file_mode, should_update_submodules, update_submodules,
     };
+    use crate::process::Cmd;
     use assert_cmd::assert::OutputAssertExt;
+    use std::collections::BTreeSet;
     use std::path::Path;
     use std::process::Command;
+    use std::time::Duration;
 
     fn run_git(path: &Path, args: &[&str]) {
         let mut command = Command::new(GIT.as_ref().unwrap());
@@ -1011,6 +1645,24 @@ mod tests {
         command.assert().success();
     }
 
+    #[tokio::test]
+    async fn index_writes_wait_for_index_lock() {
+        let tmp = tempfile::tempdir().unwrap();
+        run_git(tmp.path(), &["init"]);
+        let lock = tmp.path().join(".git").join("index.lock");
+        fs_err::write(&lock, "").unwrap();
+        let release = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(250));
+            fs_err::remove_file(lock).unwrap();
+        });
+
+        let mut cmd = Cmd::new(GIT.as_ref().unwrap());
+        cmd.current_dir(tmp.path()).arg("write-tree");
+        let output = output_retrying_index_lock(&mut cmd).await.unwrap();
+        assert!(output.status.success());
+        release.join().unwrap();
+    }
+
     #[tokio::test]
     async fn should_update_submodules_when_gitmodules_exists() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1092,6 +1744,37 @@ mod tests {
         assert!(!message.contains("--depth=1"));
     }
 
+    #[test]
+    fn parse_porcelain_v2_records() {
+        let output = b"1 .M N... 100644 100644 100644 aaaa aaaa src/my file.py\0\
+            2 R. N... 100644 100644 100644 bbbb bbbb R100 new.py\0old.py\0\
+            u UU N... 100644 100644 100644 100644 cccc dddd eeee conflict.txt\0\
+            ? untracked.txt\0";
+
+        let (records, untracked) = parse_porcelain_v2(output).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (
+                    "src/my file.py".into(),
+                    b".M N... 100644 100644 100644 aaaa aaaa".to_vec(),
+                    None,
+                ),
+                (
+                    "new.py".into(),
+                    b"R. N... 100644 100644 100644 bbbb bbbb R100".to_vec(),
+                    Some("old.py".into()),
+                ),
+                (
+                    "conflict.txt".into(),
+                    b"UU N... 100644 100644 100644 100644 cccc dddd eeee".to_vec(),
+                    None,
+                ),
+            ]
+        );
+        assert_eq!(untracked, BTreeSet::from(["untracked.txt".into()]));
+    }
+
     #[cfg(unix)]
     #[test]
     fn zsplit_preserves_non_utf8_paths() {
@@ -1104,6 +1787,51 @@ mod tests {
         assert_eq!(paths[1].as_os_str().as_bytes(), b"bad-\xff.py");
     }
 
+    #[tokio::test]
+    async fn ls_index_files_matches_git_ls_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        run_git(tmp.path(), &["init"]);
+        for file in ["a/b/c.txt", "a/b/d.txt", "a/e.txt", "Sub/X.txt", "z.txt"] {
+            let path = tmp.path().join(file);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, "content\n").unwrap();
+        }
+        run_git(tmp.path(), &["add", "."]);
+
+        for version in ["2", "3", "4"] {
+            run_git(tmp.path(), &["update-index", "--index-version", version]);
+            let data = fs_err::read(tmp.path().join(".git/index")).unwrap();
+            assert_eq!(
+                parse_index_paths(&data, 20).unwrap(),
+                ["Sub/X.txt", "a/b/c.txt", "a/b/d.txt", "a/e.txt", "z.txt"]
+                    .map(|path| path.as_bytes().to_vec()),
+                "index version {version}"
+            );
+
+            let files = ls_index_files(&tmp.path().join("a")).await.unwrap();
+            assert_eq!(
+                files,
+                [
+                    Path::new("b/c.txt"),
+                    Path::new("b/d.txt"),
+                    Path::new("e.txt")
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn parse_index_paths_rejects_split_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        run_git(tmp.path(), &["init"]);
+        fs_err::write(tmp.path().join("file.txt"), "content\n").unwrap();
+        run_git(tmp.path(), &["add", "."]);
+        run_git(tmp.path(), &["update-index", "--split-index"]);
+
+        let data = fs_err::read(tmp.path().join(".git/index")).unwrap();
+        assert_eq!(parse_index_paths(&data, 20), None);
+    }
+
     #[test]
     fn shared_repository_group_mode_matches_git_behavior() {
         for value in ["group", "true", "yes", "on", "1"] {
@@ -1,27 +1,33 @@
 use std::borrow::Cow;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
+use encoding_rs::Encoding;
 use prek_consts::PRE_COMMIT_HOOKS_YAML;
+use prek_consts::env_vars::EnvVars;
 use prek_identify::{TagSet, tags};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use target_lexicon::HOST;
 use tempfile::TempDir;
 use thiserror::Error;
 use tracing::trace;
 
 use crate::config::{
-    self, BuiltinHook, Config, FilePattern, HookOptions, Language, LocalHook, ManifestHook,
-    MetaHook, PassFilenames, RemoteHook, Stages, read_manifest,
+    self, BuiltinHook, Config, FilePattern, HookOptions, Isolation, Language, LanguageVersion,
+    LocalHook, ManifestHook, MetaHook, PassFilenames, RemoteHook, Stages, read_manifest,
 };
+use crate::git::GIT_ROOT;
 use crate::hook_entry::HookEntry;
 use crate::languages::version::LanguageRequest;
-use crate::languages::{ShellSupport, extract_metadata};
+use crate::languages::{ShellSupport, extract_metadata, go_run_module};
 use crate::store::Store;
+use crate::warn_user;
 use crate::workspace::Project;
 
 #[derive(Error, Debug)]
@@ -82,11 +88,26 @@ impl HookSpec {
 
     pub(crate) fn apply_project_defaults(&mut self, config: &Config) {
         let language = self.language;
+        // Pick the version for this host first, so an unmatched target map falls back to
+        // the project default.
+        if let Some(version) = self.options.language_version.take() {
+            let host = HOST.to_string();
+            self.options.language_version = version
+                .resolve(&host)
+                .map(|version| LanguageVersion::Version(version.to_string()));
+            if self.options.language_version.is_none() {
+                warn_user!(
+                    "No `language_version` target of hook `{}` matches `{host}`, using the default version",
+                    self.id
+                );
+            }
+        }
         if self.options.language_version.is_none() {
             self.options.language_version = config
                 .default_language_version
                 .as_ref()
-                .and_then(|v| v.get(&language).cloned());
+                .and_then(|v| v.get(&language).cloned())
+                .map(LanguageVersion::Version);
         }
 
         if self
@@ -312,6 +333,8 @@ pub(crate) struct HookBuilder {
     hook_spec: HookSpec,
     // The index of the hook in the project configuration.
     idx: usize,
+    repo_files: Option<FilePattern>,
+    repo_exclude: Option<FilePattern>,
 }
 
 impl HookBuilder {
@@ -326,9 +349,22 @@ impl HookBuilder {
             repo,
             hook_spec,
             idx,
+            repo_files: None,
+            repo_exclude: None,
         }
     }
 
+    /// Set the repo-level file patterns, which apply on top of the hook's own `files` and `exclude`.
+    pub(crate) fn repo_file_patterns(
+        mut self,
+        files: Option<&FilePattern>,
+        exclude: Option<&FilePattern>,
+    ) -> Self {
+        self.repo_files = files.cloned();
+        self.repo_exclude = exclude.cloned();
+        self
+    }
+
     /// Check the hook configuration.
     fn check(&self) -> Result<(), Error> {
         let language = self.hook_spec.language;
@@ -336,6 +372,9 @@ impl HookBuilder {
             language_version,
             additional_dependencies,
             shell,
+            setup,
+            pattern_hint,
+            use_project_node_modules,
             ..
         } = &self.hook_spec.options;
         let additional_dependencies = additional_dependencies
@@ -366,8 +405,22 @@ impl HookBuilder {
             }
         }
 
+        if let Some(setup) = setup
+            && !setup.is_empty()
+            && (!language.supports_install_env() || language == Language::Docker)
+        {
+            return Err(Error::Hook {
+                hook: self.hook_spec.id.clone(),
+                error: anyhow::anyhow!(
+                    "Hook specified `setup` but the language `{language}` does not install a local environment",
+                ),
+            });
+        }
+
         if !language.supports_language_version() {
             if let Some(language_version) = language_version
+                .as_ref()
+                .and_then(LanguageVersion::as_version)
                 && language_version != "default"
             {
                 return Err(Error::Hook {
@@ -379,6 +432,34 @@ impl HookBuilder {
             }
         }
 
+        if pattern_hint.is_some() && language != Language::Fail {
+            return Err(Error::Hook {
+                hook: self.hook_spec.id.clone(),
+                error: anyhow::anyhow!(
+                    "Hook specified `pattern_hint` but only `fail` hooks support it, not `{language}` hooks",
+                ),
+            });
+        }
+
+        if *use_project_node_modules == Some(true) {
+            if language != Language::Node {
+                return Err(Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!(
+                        "Hook specified `use_project_node_modules` but only `node` hooks support it, not `{language}` hooks",
+                    ),
+                });
+            }
+            if !matches!(self.repo.as_ref(), Repo::Local { .. }) {
+                return Err(Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!(
+                        "Hook specified `use_project_node_modules` but only local hooks support it",
+                    ),
+                });
+            }
+        }
+
         if shell.is_some() {
             match self.repo.as_ref() {
                 Repo::Meta { .. } => {
@@ -435,26 +516,74 @@ impl HookBuilder {
             .into_iter()
             .collect::<BTreeSet<_>>();
         let options = self.hook_spec.options;
-        let language_version = options.language_version.unwrap_or_default();
+        let language_version = options
+            .language_version
+            .as_ref()
+            .and_then(LanguageVersion::as_version)
+            .unwrap_or_default();
         let alias = options.alias.unwrap_or_default();
-        let args = options.args.unwrap_or_default();
+        let mut args = options.args.unwrap_or_default();
+        if let Some(args_from) = &options.args_from {
+            let extra = read_args_from(self.project.path(), args_from)
+                .await
+                .map_err(|error| Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error,
+                })?;
+            args.extend(extra);
+        }
         let env = options.env.unwrap_or_default();
         let types = options.types.unwrap_or(tags::TAG_SET_FILE);
         let types_or = options.types_or.unwrap_or_default();
         let exclude_types = options.exclude_types.unwrap_or_default();
         let always_run = options.always_run.unwrap_or(false);
         let fail_fast = options.fail_fast.unwrap_or(false);
+        let advisory = options.advisory.unwrap_or(false);
         let pass_filenames = options.pass_filenames.unwrap_or(PassFilenames::All);
+        let files_from_repo_root = options.files_from_repo_root.unwrap_or(false);
         let require_serial = options.require_serial.unwrap_or(false);
+        let require_serial_project = options.require_serial_project.unwrap_or(false);
         let verbose = options.verbose.unwrap_or(false);
+        let stream_output = options.stream_output.unwrap_or(false);
+        let output_encoding = options
+            .output_encoding
+            .as_deref()
+            .map(|label| {
+                Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| Error::Hook {
+                    hook: self.hook_spec.id.clone(),
+                    error: anyhow::anyhow!("Unknown `output_encoding` `{label}`"),
+                })
+            })
+            .transpose()?;
+        let success_exit_codes = options.success_exit_codes.unwrap_or_else(|| vec![0]);
+        let warning_exit_codes = options.warning_exit_codes.unwrap_or_default();
+        if let Some(code) = warning_exit_codes
+            .iter()
+            .find(|code| success_exit_codes.contains(code))
+        {
+            return Err(Error::Hook {
+                hook: self.hook_spec.id.clone(),
+                error: anyhow::anyhow!(
+                    "Exit code `{code}` is in both `success_exit_codes` and `warning_exit_codes`"
+                ),
+            });
+        }
         let stages = options.stages.unwrap_or(Stages::ALL);
         let shell = options.shell;
         let additional_dependencies = options.additional_dependencies.unwrap_or_default();
-        let language_request = LanguageRequest::parse(self.hook_spec.language, &language_version)
+        let setup = options.setup.unwrap_or_default();
+        let use_project_node_modules = options.use_project_node_modules.unwrap_or(false);
+        let go_run_module = go_run_module(
+            self.hook_spec.language,
+            matches!(self.repo.as_ref(), Repo::Local { .. }),
+            &self.hook_spec.entry,
+            &additional_dependencies,
+        );
+        let language_request = LanguageRequest::parse(self.hook_spec.language, language_version)
             .map_err(|e| Error::Hook {
-            hook: self.hook_spec.id.clone(),
-            error: anyhow::anyhow!(e),
-        })?;
+                hook: self.hook_spec.id.clone(),
+                error: anyhow::anyhow!(e),
+            })?;
 
         let entry = HookEntry::new(self.hook_spec.id.clone(), self.hook_spec.entry, shell);
 
@@ -470,8 +599,13 @@ impl HookBuilder {
             groups,
             entry,
             stages,
+            language_version: language_version.to_string(),
             language_request,
             additional_dependencies,
+            setup,
+            use_project_node_modules,
+            isolation: options.isolation,
+            go_run_module,
             alias,
             types,
             types_or,
@@ -480,13 +614,23 @@ impl HookBuilder {
             env,
             always_run,
             fail_fast,
+            advisory,
             pass_filenames,
+            files_from_repo_root,
             require_serial,
+            require_serial_project,
             verbose,
+            stream_output,
+            output_encoding,
+            success_exit_codes,
+            warning_exit_codes,
             files: options.files,
             exclude: options.exclude,
+            repo_files: self.repo_files,
+            repo_exclude: self.repo_exclude,
             description: options.description,
             log_file: options.log_file,
+            pattern_hint: options.pattern_hint,
             minimum_prek_version: options.minimum_prek_version,
         };
 
@@ -503,6 +647,54 @@ impl HookBuilder {
     }
 }
 
+/// Read the arguments referenced by `args_from`, given as `<file>:<key>`.
+///
+/// The file is relative to the project root and parsed as TOML or JSON depending on its
+/// extension. The key is a dotted path to a list, whose numbers and booleans are converted to
+/// strings. The file is read every time hooks are built, so changes apply to the next run.
+async fn read_args_from(project_root: &Path, spec: &str) -> anyhow::Result<Vec<String>> {
+    let Some((file, key)) = spec
+        .rsplit_once(':')
+        .filter(|(file, key)| !file.is_empty() && !key.is_empty())
+    else {
+        anyhow::bail!("`args_from` must be in the form `<file>:<key>`, got `{spec}`");
+    };
+
+    let path = project_root.join(file);
+    let content = fs_err::tokio::read_to_string(&path)
+        .await
+        .with_context(|| format!("Failed to read `args_from` file `{file}`"))?;
+    let value: serde_json::Value = match path.extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("toml") => {
+            toml::from_str(&content).with_context(|| format!("Failed to parse `{file}`"))?
+        }
+        Some(ext) if ext.eq_ignore_ascii_case("json") => {
+            serde_json::from_str(&content).with_context(|| format!("Failed to parse `{file}`"))?
+        }
+        _ => anyhow::bail!("`args_from` file `{file}` must be a `.toml` or `.json` file"),
+    };
+
+    let value = key
+        .split('.')
+        .try_fold(&value, |value, segment| value.get(segment))
+        .with_context(|| format!("Key `{key}` not found in `{file}`"))?;
+    let Some(items) = value.as_array() else {
+        anyhow::bail!("Key `{key}` in `{file}` must be a list of arguments");
+    };
+
+    items
+        .iter()
+        .map(|item| match item {
+            serde_json::Value::String(arg) => Ok(arg.clone()),
+            serde_json::Value::Number(arg) => Ok(arg.to_string()),
+            serde_json::Value::Bool(arg) => Ok(arg.to_string()),
+            _ => Err(anyhow::anyhow!(
+                "Key `{key}` in `{file}` must only contain strings, numbers or booleans"
+            )),
+        })
+        .collect()
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct Hook {
@@ -518,26 +710,60 @@ pub(crate) struct Hook {
     pub alias: String,
     pub files: Option<FilePattern>,
     pub exclude: Option<FilePattern>,
+    /// The `files` pattern of the repo the hook comes from, which files must match as well.
+    pub repo_files: Option<FilePattern>,
+    /// The `exclude` pattern of the repo the hook comes from.
+    pub repo_exclude: Option<FilePattern>,
     pub types: TagSet,
     pub types_or: TagSet,
     pub exclude_types: TagSet,
     pub additional_dependencies: Vec<String>,
+    pub setup: Vec<String>,
+    /// Run from the project's `node_modules/.bin` instead of an installed environment.
+    pub use_project_node_modules: bool,
+    /// The hook's own `isolation`, `None` to use the global default.
+    pub isolation: Option<Isolation>,
+    /// The `<module>@<version>` of a local `go run` entry, which keys the hook environment.
+    pub go_run_module: Option<String>,
     pub args: Vec<String>,
     pub env: FxHashMap<String, String>,
     pub always_run: bool,
     pub fail_fast: bool,
+    /// Failures of this hook are reported but do not fail the run.
+    pub advisory: bool,
     pub pass_filenames: PassFilenames,
+    pub files_from_repo_root: bool,
     pub description: Option<String>,
+    /// The `language_version` after applying `default_language_version`, empty if unset.
+    pub language_version: String,
     pub language_request: LanguageRequest,
     pub log_file: Option<String>,
+    pub pattern_hint: Option<String>,
     pub require_serial: bool,
+    /// No other hook of the same project runs alongside this hook.
+    pub require_serial_project: bool,
     pub stages: Stages,
     pub verbose: bool,
+    pub stream_output: bool,
+    /// The encoding of the hook's output, UTF-8 if unset.
+    pub output_encoding: Option<&'static Encoding>,
+    /// Exit codes that mean the hook passed.
+    pub success_exit_codes: Vec<i32>,
+    /// Exit codes that mean the hook passed with notes.
+    pub warning_exit_codes: Vec<i32>,
     pub minimum_prek_version: Option<String>,
     pub priority: u32,
     pub groups: BTreeSet<String>,
 }
 
+/// Decode hook output with `encoding`, or as lossy UTF-8 when unset.
+pub(crate) fn decode_output(encoding: Option<&'static Encoding>, output: &[u8]) -> Cow<'_, str> {
+    match encoding {
+        Some(encoding) => encoding.decode_without_bom_handling(output).0,
+        None => String::from_utf8_lossy(output),
+    }
+}
+
 impl Display for Hook {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
@@ -562,6 +788,24 @@ impl Hook {
         self.repo.path()
     }
 
+    /// Decode captured output for display with the hook's `output_encoding`.
+    pub(crate) fn decode_output<'a>(&self, output: &'a [u8]) -> Cow<'a, str> {
+        decode_output(self.output_encoding, output)
+    }
+
+    /// The environment variable that asks a Python hook to print in its `output_encoding`.
+    ///
+    /// Other tools have no portable switch for this; set `LC_ALL` or similar in `env` instead.
+    pub(crate) fn output_encoding_env(&self) -> Option<(&'static str, String)> {
+        let encoding = self.output_encoding?;
+        matches!(self.language, Language::Python).then(|| {
+            (
+                EnvVars::PYTHONIOENCODING,
+                encoding.name().to_ascii_lowercase(),
+            )
+        })
+    }
+
     pub(crate) fn full_id(&self) -> String {
         let path = self.project.relative_path();
         if path.as_os_str().is_empty() {
@@ -576,9 +820,59 @@ impl Hook {
         self.project.path()
     }
 
+    /// Get the directory the hook command runs from, which the filenames passed to the hook
+    /// are relative to.
+    ///
+    /// This is the git root for hooks with `files_from_repo_root: true`, and the
+    /// [`work_dir`](Self::work_dir) otherwise.
+    pub(crate) fn run_dir(&self) -> &Path {
+        if self.files_from_repo_root
+            && let Ok(git_root) = GIT_ROOT.as_ref()
+        {
+            return git_root;
+        }
+        self.work_dir()
+    }
+
+    /// Get the [`run_dir`](Self::run_dir) as a path in-process hooks can join filenames onto.
+    ///
+    /// In-process hooks run from the workspace root, so this is the project path relative to it,
+    /// or the absolute git root for hooks with `files_from_repo_root: true`.
+    pub(crate) fn file_base(&self) -> &Path {
+        if self.files_from_repo_root
+            && let Ok(git_root) = GIT_ROOT.as_ref()
+        {
+            return git_root;
+        }
+        self.project.relative_path()
+    }
+
+    /// Get the path to the project directory relative to the git root.
+    ///
+    /// Filenames matched in the project are joined onto this for hooks with
+    /// `files_from_repo_root: true`.
+    pub(crate) fn project_path_from_repo_root(&self) -> &Path {
+        GIT_ROOT
+            .as_ref()
+            .ok()
+            .and_then(|git_root| self.project.path().strip_prefix(git_root).ok())
+            .unwrap_or(Path::new(""))
+    }
+
     pub(crate) fn needs_install_env(&self) -> bool {
         !matches!(self.repo(), Repo::Meta { .. } | Repo::Builtin { .. })
             && self.language.supports_install_env()
+            && self.project_node_modules_bin().is_none()
+    }
+
+    /// The `node_modules/.bin` directory of the project, for hooks with
+    /// `use_project_node_modules: true` whose project has one.
+    pub(crate) fn project_node_modules_bin(&self) -> Option<PathBuf> {
+        if !self.use_project_node_modules {
+            return None;
+        }
+        let bin = self.project.path().join("node_modules").join(".bin");
+        bin.is_dir().then_some(bin)
     }
 
     /// Returns a lightweight view of the hook's environment requirement.
@@ -592,11 +886,20 @@ impl Hook {
         Some(HookEnvRequirementRef {
             language: self.language,
             repo: self.repo.identity(),
-            dependencies: &self.additional_dependencies,
+            dependencies: self.env_dependencies(),
+            setup: &self.setup,
             language_request: &self.language_request,
         })
     }
 
+    /// Dependencies recorded with the hook environment.
+    pub(crate) fn env_dependencies(&self) -> &[String] {
+        match &self.go_run_module {
+            Some(module) => std::slice::from_ref(module),
+            None => &self.additional_dependencies,
+        }
+    }
+
     /// Dependencies to pass to language dependency installers.
     ///
     /// For remote hooks, this includes the local path to the cloned repository so that
@@ -618,6 +921,7 @@ pub(crate) struct HookEnvRequirement {
     pub(crate) language: Language,
     repo: Option<RepoIdentity>,
     dependencies: Vec<String>,
+    setup: Vec<String>,
     language_request: LanguageRequest,
 }
 
@@ -628,6 +932,7 @@ pub(crate) struct HookEnvRequirementRef<'a> {
     language: Language,
     repo: Option<RepoIdentityRef<'a>>,
     dependencies: &'a [String],
+    setup: &'a [String],
     language_request: &'a LanguageRequest,
 }
 
@@ -646,13 +951,17 @@ impl HookEnvRequirement {
         }
 
         hook_spec.apply_project_defaults(config);
-        hook_spec.options.language_version.get_or_insert_default();
         hook_spec
             .options
             .additional_dependencies
             .get_or_insert_default();
 
-        let request = hook_spec.options.language_version.as_deref().unwrap_or("");
+        let request = hook_spec
+            .options
+            .language_version
+            .as_ref()
+            .and_then(LanguageVersion::as_version)
+            .unwrap_or("");
         let language_request = LanguageRequest::parse(language, request).with_context(|| {
             format!(
                 "Invalid language_version `{request}` for hook `{}`",
@@ -660,17 +969,27 @@ impl HookEnvRequirement {
             )
         })?;
 
-        let dependencies = hook_spec
+        let additional_dependencies = hook_spec
             .options
             .additional_dependencies
             .as_deref()
-            .unwrap_or_default()
-            .to_vec();
+            .unwrap_or_default();
+        let dependencies = match go_run_module(
+            language,
+            repo.is_none(),
+            &hook_spec.entry,
+            additional_dependencies,
+        ) {
+            Some(module) => vec![module],
+            None => additional_dependencies.to_vec(),
+        };
+        let setup = hook_spec.options.setup.unwrap_or_default();
 
         Ok(Some(Self {
             language,
             repo: repo.map(RepoIdentity::from),
             dependencies,
+            setup,
             language_request,
         }))
     }
@@ -680,6 +999,7 @@ impl HookEnvRequirement {
             language: self.language,
             repo: self.repo.as_ref().map(RepoIdentity::as_ref),
             dependencies: &self.dependencies,
+            setup: &self.setup,
             language_request: &self.language_request,
         }
     }
@@ -692,6 +1012,7 @@ impl HookEnvRequirementRef<'_> {
             && info.language == self.language
             && info.repo.as_ref().map(RepoIdentity::as_ref) == self.repo
             && info.dependencies == self.dependencies
+            && info.setup == self.setup
             && self.language_request.satisfied_by(info)
     }
 }
@@ -724,6 +1045,8 @@ impl Display for InstalledHook {
 }
 
 pub(crate) const HOOK_MARKER: &str = ".prek-hook.json";
+/// Records when an environment was last used, as seconds since the Unix epoch.
+pub(crate) const LAST_USED_MARKER: &str = ".prek-last-used";
 pub(crate) const INSTALL_INFO_SCHEMA_VERSION: u8 = 1;
 
 impl InstalledHook {
@@ -743,6 +1066,19 @@ impl InstalledHook {
         }
     }
 
+    /// A copy of the hook that runs from the repository root, with filenames relative to it.
+    pub(crate) fn run_from_repo_root(&self) -> Self {
+        let mut hook = Hook::clone(self);
+        hook.files_from_repo_root = true;
+        match self {
+            InstalledHook::Installed { info, .. } => InstalledHook::Installed {
+                hook: Arc::new(hook),
+                info: Arc::clone(info),
+            },
+            InstalledHook::NoNeedInstall(_) => InstalledHook::NoNeedInstall(Arc::new(hook)),
+        }
+    }
+
     /// Get the install info of the hook if it is installed.
     pub(crate) fn install_info(&self) -> Option<&InstallInfo> {
         match self {
@@ -777,9 +1113,16 @@ pub(crate) struct InstallInfo {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     repo: Option<RepoIdentity>,
     pub(crate) dependencies: Vec<String>,
+    /// Setup commands that were run after the environment was installed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) setup: Vec<String>,
     pub(crate) env_path: PathBuf,
     pub(crate) toolchain: PathBuf,
     extra: FxHashMap<String, String>,
+    /// Path environment variables computed during install, for backends that set them on every
+    /// run of the hook. Paths inside the store are relative to it, so the store can be moved.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    env: BTreeMap<String, PathBuf>,
     #[serde(skip, default)]
     temp_dir: Option<TempDir>,
 }
@@ -792,9 +1135,11 @@ impl Clone for InstallInfo {
             language_version: self.language_version.clone(),
             repo: self.repo.clone(),
             dependencies: self.dependencies.clone(),
+            setup: self.setup.clone(),
             env_path: self.env_path.clone(),
             toolchain: self.toolchain.clone(),
             extra: self.extra.clone(),
+            env: self.env.clone(),
             temp_dir: None,
         }
     }
@@ -802,12 +1147,14 @@ impl Clone for InstallInfo {
 
 impl InstallInfo {
     pub(crate) fn new(hook: &Hook, hooks_dir: &Path) -> Result<Self, Error> {
-        Self::create(
+        let mut info = Self::create(
             hook.language,
             hook.repo.identity().map(RepoIdentity::from),
-            hook.additional_dependencies.clone(),
+            hook.env_dependencies().to_vec(),
             hooks_dir,
-        )
+        )?;
+        info.setup.clone_from(&hook.setup);
+        Ok(info)
     }
 
     pub(crate) fn create(
@@ -826,10 +1173,12 @@ impl InstallInfo {
             language,
             repo,
             dependencies,
+            setup: Vec::new(),
             env_path: env_path.path().to_path_buf(),
             language_version: semver::Version::new(0, 0, 0),
             toolchain: PathBuf::new(),
             extra: FxHashMap::default(),
+            env: BTreeMap::new(),
             temp_dir: Some(env_path),
         })
     }
@@ -863,6 +1212,30 @@ impl InstallInfo {
         self.language.check_health(self).await
     }
 
+    /// Record that the environment is used now.
+    pub(crate) async fn touch_last_used(&self) -> std::io::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        fs_err::tokio::write(self.env_path.join(LAST_USED_MARKER), now.to_string()).await
+    }
+
+    /// When the environment was last used.
+    ///
+    /// Environments created before usage was tracked fall back to their install time.
+    pub(crate) fn last_used(&self) -> Option<SystemTime> {
+        let recorded = fs_err::read_to_string(self.env_path.join(LAST_USED_MARKER))
+            .ok()
+            .and_then(|content| content.trim().parse::<u64>().ok())
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        recorded.or_else(|| {
+            fs_err::metadata(self.env_path.join(HOOK_MARKER))
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+    }
+
     pub(crate) fn with_language_version(&mut self, version: semver::Version) -> &mut Self {
         self.language_version = version;
         self
@@ -881,6 +1254,26 @@ impl InstallInfo {
     pub(crate) fn get_extra(&self, key: &str) -> Option<&String> {
         self.extra.get(key)
     }
+
+    pub(crate) fn with_env(&mut self, key: &str, path: &Path, store: &Store) -> &mut Self {
+        let path = path.strip_prefix(store.path()).unwrap_or(path);
+        self.env.insert(key.to_string(), path.to_path_buf());
+        self
+    }
+
+    /// The recorded environment variables, with paths resolved against `store`.
+    pub(crate) fn env<'a>(
+        &'a self,
+        store: &'a Store,
+    ) -> impl Iterator<Item = (&'a str, PathBuf)> + 'a {
+        self.env
+            .iter()
+            .map(|(key, path)| (key.as_str(), store.path().join(path)))
+    }
+
+    pub(crate) fn has_env(&self) -> bool {
+        !self.env.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -1012,6 +1405,11 @@ mod tests {
                     fail_fast: None,
                     minimum_prek_version: None,
                     orphan: None,
+                    intent_to_add: None,
+                    file_collector: None,
+                    projects: None,
+                    identify: None,
+                    ci: None,
                     _unused_keys: {},
                 },
                 ..
@@ -1033,12 +1431,18 @@ mod tests {
             alias: "alias-1",
             files: None,
             exclude: None,
+            repo_files: None,
+            repo_exclude: None,
             types: [
                 "text",
             ],
             types_or: [],
             exclude_types: [],
             additional_dependencies: [],
+            setup: [],
+            use_project_node_modules: false,
+            isolation: None,
+            go_run_module: None,
             args: [
                 "--flag",
             ],
@@ -1049,10 +1453,13 @@ mod tests {
             },
             always_run: true,
             fail_fast: false,
+            advisory: false,
             pass_filenames: None,
+            files_from_repo_root: false,
             description: Some(
                 "desc",
             ),
+            language_version: "python3.12",
             language_request: Python(
                 MajorMinor(
                     3,
@@ -1060,9 +1467,17 @@ mod tests {
                 ),
             ),
             log_file: None,
+            pattern_hint: None,
             require_serial: false,
+            require_serial_project: false,
             stages: Stages(manual),
             verbose: true,
+            stream_output: false,
+            output_encoding: None,
+            success_exit_codes: [
+                0,
+            ],
+            warning_exit_codes: [],
             minimum_prek_version: None,
             priority: 42,
             groups: {
@@ -1305,6 +1720,7 @@ mod tests {
             language: Language::Python,
             repo: None,
             dependencies: &dependencies,
+            setup: &[],
             language_request: &language_request,
         };
 
@@ -1313,6 +1729,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn decode_output_with_encoding() {
+        // "你好" in CP936.
+        let output = [0xC4, 0xE3, 0xBA, 0xC3];
+        let gbk = Encoding::for_label(b"cp936");
+        assert_eq!(decode_output(gbk, &output), "你好");
+        assert!(decode_output(None, &output).contains('\u{FFFD}'));
+        assert_eq!(decode_output(None, "你好".as_bytes()), "你好");
+    }
+
+    #[test]
+    fn install_info_env_round_trips() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let mut install_info =
+            InstallInfo::create(Language::Golang, None, Vec::new(), temp.path())?;
+        let marker = serde_json::to_value(&install_info)?;
+        assert!(marker.get("env").is_none());
+
+        let store = Store::from_path(temp.path().join("store"));
+        install_info
+            .with_env("GOPATH", &store.path().join("cache/go"), &store)
+            .with_env("GOROOT", Path::new("/usr/local/go"), &store);
+        let marker = serde_json::to_value(&install_info)?;
+        assert_eq!(
+            marker["env"],
+            json!({ "GOPATH": "cache/go", "GOROOT": "/usr/local/go" })
+        );
+
+        // Recorded paths follow the store when it moves.
+        let parsed: InstallInfo = serde_json::from_value(marker)?;
+        let moved = Store::from_path(temp.path().join("moved"));
+        assert_eq!(
+            parsed.env(&moved).collect::<Vec<_>>(),
+            [
+                ("GOPATH", moved.path().join("cache/go")),
+                ("GOROOT", PathBuf::from("/usr/local/go")),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn structured_repo_distinguishes_remote_and_local_requirements() -> Result<()> {
         let repo = RepoIdentityRef::new("https://example.invalid/hooks", "v0.1.0");
@@ -1336,6 +1793,7 @@ mod tests {
             language: Language::Python,
             repo: Some(repo),
             dependencies: &dependencies,
+            setup: &[],
             language_request: &language_request,
         };
         let local_requirement = HookEnvRequirementRef {
@@ -1348,6 +1806,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn setup_commands_are_part_of_requirement() -> Result<()> {
+        let install_info: InstallInfo = serde_json::from_value(json!({
+            "schema_version": INSTALL_INFO_SCHEMA_VERSION,
+            "language": "python",
+            "language_version": "3.12.0",
+            "dependencies": [],
+            "setup": ["playwright install chromium"],
+            "env_path": "/tmp/setup-env",
+            "toolchain": "/usr/bin/python3",
+            "extra": {},
+        }))?;
+        let setup = vec!["playwright install chromium".to_string()];
+        let language_request = LanguageRequest::parse(Language::Python, "")?;
+        let requirement = HookEnvRequirementRef {
+            language: Language::Python,
+            repo: None,
+            dependencies: &[],
+            setup: &setup,
+            language_request: &language_request,
+        };
+        let without_setup = HookEnvRequirementRef {
+            setup: &[],
+            ..requirement
+        };
+
+        assert!(requirement.is_satisfied_by(&install_info));
+        assert!(!without_setup.is_satisfied_by(&install_info));
+        Ok(())
+    }
+
     /// Set up a temporary directory with a minimal `.pre-commit-config.yaml`
     /// and a `remote-repo` subdirectory.
     fn setup_python_hook_test() -> Result<(tempfile::TempDir, Arc<Project>)> {
@@ -1387,7 +1876,8 @@ mod tests {
             priority: None,
             groups: None,
             options: HookOptions {
-                language_version: language_version.map(str::to_string),
+                language_version: language_version
+                    .map(|version| LanguageVersion::Version(version.to_string())),
                 ..Default::default()
             },
         };
@@ -1491,4 +1981,52 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn read_args_from_toml_and_json() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        fs_err::write(
+            temp.path().join("pyproject.toml"),
+            indoc::indoc! {r#"
+                [tool.prek]
+                ruff = ["--fix", "--line-length", 100]
+            "#},
+        )?;
+        fs_err::write(
+            temp.path().join("args.json"),
+            r#"{"lint": {"args": ["--strict", true]}}"#,
+        )?;
+
+        let args = super::read_args_from(temp.path(), "pyproject.toml:tool.prek.ruff").await?;
+        assert_eq!(args, ["--fix", "--line-length", "100"]);
+
+        let args = super::read_args_from(temp.path(), "args.json:lint.args").await?;
+        assert_eq!(args, ["--strict", "true"]);
+
+        let err = super::read_args_from(temp.path(), "pyproject.toml:tool.prek.mypy")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Key `tool.prek.mypy` not found in `pyproject.toml`"
+        );
+
+        let err = super::read_args_from(temp.path(), "pyproject.toml:tool.prek")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Key `tool.prek` in `pyproject.toml` must be a list of arguments"
+        );
+
+        let err = super::read_args_from(temp.path(), "pyproject.toml")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`args_from` must be in the form `<file>:<key>`, got `pyproject.toml`"
+        );
+
+        Ok(())
+    }
 }
@@ -110,6 +110,14 @@ impl HookEntry {
             Self::Shell(entry) => Some(entry.shell),
         }
     }
+
+    /// Get the original entry string.
+    pub(crate) fn raw(&self) -> &str {
+        match self {
+            Self::Direct(entry) => &entry.entry,
+            Self::Shell(entry) => &entry.entry,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
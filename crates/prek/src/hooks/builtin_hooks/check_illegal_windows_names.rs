@@ -10,14 +10,56 @@ pub(super) const ILLEGAL_WINDOWS_PATTERN: &str = r"(?i)((^|/)(CON|PRN|AUX|NUL|CO
 // Upstream implements `check-illegal-windows-names` as a `fail` hook with a
 // `files` regex. Our pre-commit-hooks fast path already handles that generic
 // `fail` language in Rust, so there is no dedicated fast-path implementation to
-// add here. This module only exists to provide the builtin-hook equivalent:
-// reuse the same regex for matching, then emit a simple fail-style message.
+// add here. The builtin keeps the same regex as its default `files` filter, but
+// checks every path natively, so overriding `files` doesn't turn every matched
+// file into a failure.
 pub(crate) fn check_illegal_windows_names(_hook: &Hook, filenames: &[&Path]) -> (i32, Vec<u8>) {
-    if filenames.is_empty() {
+    let illegal: Vec<&Path> = filenames
+        .iter()
+        .copied()
+        .filter(|filename| is_illegal_windows_name(filename))
+        .collect();
+    if illegal.is_empty() {
         return (0, Vec::new());
     }
 
-    (1, illegal_windows_names_output(filenames))
+    (1, illegal_windows_names_output(&illegal))
+}
+
+/// Whether any component of a git path (`/`-separated) cannot be created on Windows.
+fn is_illegal_windows_name(path: &Path) -> bool {
+    path.to_string_lossy().split('/').any(|component| {
+        component.chars().any(|c| {
+            matches!(
+                c,
+                '<' | '>' | ':' | '"' | '\\' | '|' | '?' | '*' | '\0'..='\x1F'
+            )
+        }) || component.ends_with(|c: char| c == '.' || c.is_whitespace())
+            || is_reserved_device_name(component)
+    })
+}
+
+/// Device names are reserved regardless of case and extension, e.g. `con.txt`.
+fn is_reserved_device_name(component: &str) -> bool {
+    let stem = component
+        .split_once('.')
+        .map_or(component, |(stem, _)| stem)
+        .to_ascii_uppercase();
+    if matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL") {
+        return true;
+    }
+
+    let Some(suffix) = stem
+        .strip_prefix("COM")
+        .or_else(|| stem.strip_prefix("LPT"))
+    else {
+        return false;
+    };
+    let mut chars = suffix.chars();
+    matches!(
+        (chars.next(), chars.next()),
+        (Some('0'..='9' | '\u{00B9}' | '\u{00B2}' | '\u{00B3}'), None)
+    )
 }
 
 fn illegal_windows_names_output(filenames: &[&Path]) -> Vec<u8> {
@@ -80,6 +122,44 @@ mod tests {
         assert!(re.is_match("dir/file./next").unwrap());
     }
 
+    #[test]
+    fn test_native_check_matches_pattern() {
+        let re = illegal_windows_re();
+        for name in [
+            "normal_file.txt",
+            "src/main.rs",
+            "CON",
+            "con.txt",
+            "dir/AUX/file",
+            "CONSOLE.txt",
+            "COM1",
+            "COM\u{00B9}.log",
+            "COM10",
+            "LPT9.txt",
+            "file:name",
+            "file\tname",
+            "file.",
+            "dir/file ",
+            "dir/file./next",
+        ] {
+            assert_eq!(
+                is_illegal_windows_name(Path::new(name)),
+                re.is_match(name).unwrap(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_only_illegal_files_fail() {
+        let filenames = [Path::new("src/ok.rs"), Path::new("src/aux.rs")];
+        let illegal: Vec<&Path> = filenames
+            .into_iter()
+            .filter(|filename| is_illegal_windows_name(filename))
+            .collect();
+        assert_eq!(illegal, [Path::new("src/aux.rs")]);
+    }
+
     #[test]
     fn test_output_lines() {
         let filenames = [Path::new("CON.txt"), Path::new("bad:name.txt")];
@@ -12,7 +12,7 @@ pub(crate) async fn check_json5(
     run_concurrent_file_checks(
         filenames.iter().copied(),
         *INTERNAL_CONCURRENCY,
-        |filename| check_file(hook.project().relative_path(), filename),
+        |filename| check_file(hook.file_base(), filename),
     )
     .await
 }
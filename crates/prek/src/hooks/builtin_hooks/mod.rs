@@ -32,6 +32,7 @@ mod pattern;
 #[strum(serialize_all = "kebab-case")]
 pub(crate) enum BuiltinHooks {
     CheckAddedLargeFiles,
+    CheckAst,
     CheckCaseConflict,
     CheckExecutablesHaveShebangs,
     CheckIllegalWindowsNames,
@@ -44,6 +45,7 @@ pub(crate) enum BuiltinHooks {
     CheckVcsPermalinks,
     CheckXml,
     CheckYaml,
+    DebugStatements,
     DenyPattern,
     DestroyedSymlinks,
     DetectPrivateKey,
@@ -69,6 +71,7 @@ impl BuiltinHooks {
             | Self::TrailingWhitespace => true,
 
             Self::CheckAddedLargeFiles
+            | Self::CheckAst
             | Self::CheckCaseConflict
             | Self::CheckExecutablesHaveShebangs
             | Self::CheckIllegalWindowsNames
@@ -81,6 +84,7 @@ impl BuiltinHooks {
             | Self::CheckVcsPermalinks
             | Self::CheckXml
             | Self::CheckYaml
+            | Self::DebugStatements
             | Self::DenyPattern
             | Self::DestroyedSymlinks
             | Self::DetectPrivateKey
@@ -102,6 +106,7 @@ impl BuiltinHooks {
             Self::CheckAddedLargeFiles => {
                 Box::pin(pre_commit_hooks::check_added_large_files(hook, filenames))
             }
+            Self::CheckAst => Box::pin(pre_commit_hooks::check_ast(hook, filenames)),
             Self::CheckCaseConflict => {
                 Box::pin(pre_commit_hooks::check_case_conflict(hook, filenames))
             }
@@ -126,6 +131,7 @@ impl BuiltinHooks {
             }
             Self::CheckXml => Box::pin(pre_commit_hooks::check_xml(hook, filenames)),
             Self::CheckYaml => Box::pin(pre_commit_hooks::check_yaml(hook, filenames)),
+            Self::DebugStatements => Box::pin(pre_commit_hooks::debug_statements(hook, filenames)),
             Self::DenyPattern => Box::pin(pattern::deny_pattern(hook, filenames)),
             Self::DestroyedSymlinks => {
                 Box::pin(pre_commit_hooks::destroyed_symlinks(hook, filenames))
@@ -175,6 +181,20 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::CheckAst => BuiltinHook {
+                id: "check-ast".to_string(),
+                name: "check python ast".to_string(),
+                entry: "check-ast".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "simply checks whether the files parse as valid python.".to_string(),
+                    ),
+                    types: Some(tags::TAG_SET_PYTHON),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::CheckCaseConflict => BuiltinHook {
                 id: "check-case-conflict".to_string(),
                 name: "check for case conflicts".to_string(),
@@ -341,6 +361,21 @@ impl BuiltinHook {
                     ..Default::default()
                 },
             },
+            BuiltinHooks::DebugStatements => BuiltinHook {
+                id: "debug-statements".to_string(),
+                name: "debug statements (python)".to_string(),
+                entry: "debug-statements".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    description: Some(
+                        "checks for debugger imports and py37+ `breakpoint()` calls in python source."
+                            .to_string(),
+                    ),
+                    types: Some(tags::TAG_SET_PYTHON),
+                    ..Default::default()
+                },
+            },
             BuiltinHooks::DenyPattern => BuiltinHook {
                 id: "deny-pattern".to_string(),
                 name: "deny patterns".to_string(),
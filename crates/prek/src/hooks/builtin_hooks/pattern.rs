@@ -88,7 +88,7 @@ pub(crate) async fn require_pattern(hook: &Hook, filenames: &[&Path]) -> Result<
 async fn run(hook: &Hook, filenames: &[&Path], policy: MatchPolicy) -> Result<(i32, Vec<u8>)> {
     let args = Args::try_parse_from(hook.entry.expect_direct().split_with_args(&hook.args)?)?;
     let matcher = Matcher::new(&args)?;
-    let file_base = hook.project().relative_path();
+    let file_base = hook.file_base();
 
     run_concurrent_file_checks(
         filenames.iter().copied(),
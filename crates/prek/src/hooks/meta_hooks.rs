@@ -1,7 +1,10 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 use std::ops::ControlFlow;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use itertools::Itertools;
@@ -14,7 +17,7 @@ use crate::cli::run::{
 use crate::config::{self, FilePattern, HookOptions, Language, MetaHook};
 use crate::hook::Hook;
 use crate::store::Store;
-use crate::workspace::{HookInitFilters, Project};
+use crate::workspace::{HookInitFilters, Project, Refresh, Workspace};
 
 // For builtin hooks (meta hooks and builtin pre-commit-hooks), they are not run
 // in the project root like other hooks. Instead, they run in the workspace root.
@@ -38,7 +41,9 @@ use crate::workspace::{HookInitFilters, Project};
 #[cfg_attr(feature = "schemars", schemars(rename_all = "kebab-case"))]
 #[strum(serialize_all = "kebab-case")]
 pub(crate) enum MetaHooks {
+    CheckDuplicateHooksAcrossProjects,
     CheckHooksApply,
+    CheckRevConsistency,
     CheckUselessExcludes,
     Identity,
 }
@@ -53,7 +58,11 @@ impl MetaHooks {
     ) -> Result<(i32, Vec<u8>)> {
         let progress = reporter.on_run_start(hook, filenames.len());
         let result = match self {
+            Self::CheckDuplicateHooksAcrossProjects => {
+                check_duplicate_hooks_across_projects(store, hook)
+            }
             Self::CheckHooksApply => check_hooks_apply(store, hook, filenames).await,
+            Self::CheckRevConsistency => check_rev_consistency(store, hook),
             Self::CheckUselessExcludes => check_useless_excludes(hook, filenames).await,
             Self::Identity => Ok(identity(hook, filenames)),
         };
@@ -69,6 +78,16 @@ impl MetaHook {
             FilePattern::glob(CONFIG_FILENAMES.iter().map(ToString::to_string).collect()).unwrap();
 
         Ok(match hook_id {
+            MetaHooks::CheckDuplicateHooksAcrossProjects => MetaHook {
+                id: "check-duplicate-hooks-across-projects".to_string(),
+                name: "Check duplicate hooks across projects".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    files: Some(config_file_glob),
+                    ..Default::default()
+                },
+            },
             MetaHooks::CheckHooksApply => MetaHook {
                 id: "check-hooks-apply".to_string(),
                 name: "Check hooks apply".to_string(),
@@ -79,6 +98,16 @@ impl MetaHook {
                     ..Default::default()
                 },
             },
+            MetaHooks::CheckRevConsistency => MetaHook {
+                id: "check-rev-consistency".to_string(),
+                name: "Check rev consistency".to_string(),
+                priority: None,
+                groups: None,
+                options: HookOptions {
+                    files: Some(config_file_glob),
+                    ..Default::default()
+                },
+            },
             MetaHooks::CheckUselessExcludes => MetaHook {
                 id: "check-useless-excludes".to_string(),
                 name: "Check useless excludes".to_string(),
@@ -131,53 +160,66 @@ pub(crate) async fn check_hooks_apply(
             .init_hooks(store, HookInitFilters::none(), None)
             .await
             .context("Failed to init hooks")?;
-        let hooks = project_hooks
-            .iter()
-            .filter(|hook| !hook.always_run && hook.language != Language::Fail)
-            .collect::<Vec<_>>();
-        if hooks.is_empty() {
-            continue;
+        for project_hook in hooks_without_files(&project_hooks, &input, &tag_cache) {
+            code = 1;
+            writeln!(
+                &mut output,
+                "{} does not apply to this repository",
+                project_hook.id
+            )?;
         }
+    }
 
-        let filters = hooks
-            .iter()
-            .map(|hook| HookFileFilter::new(hook))
-            .collect::<Vec<_>>();
-        let mut matches = vec![false; hooks.len()];
-        let mut remaining = matches.len();
+    Ok((code, output))
+}
 
-        ProjectFiles::visit_for_project(input.iter(), hooks[0].project(), None, None, |file| {
-            let tags = file.tags(&tag_cache);
-            for (matched, filter) in matches.iter_mut().zip(&filters) {
-                if *matched {
-                    continue;
-                }
-                if filter.matches_filename(file.hook_path()) && filter.matches_tags(tags) {
-                    *matched = true;
-                    remaining -= 1;
-                }
-            }
+/// Returns the hooks of a single project that match none of the `input` files.
+///
+/// `input` is relative to the workspace root. Hooks that run without files are never reported.
+pub(crate) fn hooks_without_files<'a, 'p>(
+    project_hooks: &'a [Hook],
+    input: &'p [PathBuf],
+    tag_cache: &FileTagCache<'p>,
+) -> Vec<&'a Hook> {
+    let hooks = project_hooks
+        .iter()
+        .filter(|hook| !hook.always_run && hook.language != Language::Fail)
+        .collect::<Vec<_>>();
+    if hooks.is_empty() {
+        return Vec::new();
+    }
 
-            if remaining == 0 {
-                return ControlFlow::Break(());
+    let filters = hooks
+        .iter()
+        .map(|hook| HookFileFilter::new(hook))
+        .collect::<Vec<_>>();
+    let mut matches = vec![false; hooks.len()];
+    let mut remaining = matches.len();
+
+    ProjectFiles::visit_for_project(input.iter(), hooks[0].project(), None, None, |file| {
+        let tags = file.tags(tag_cache);
+        for (matched, filter) in matches.iter_mut().zip(&filters) {
+            if *matched {
+                continue;
             }
-
-            ControlFlow::Continue(())
-        });
-
-        for (project_hook, matches) in hooks.iter().zip(matches) {
-            if !matches {
-                code = 1;
-                writeln!(
-                    &mut output,
-                    "{} does not apply to this repository",
-                    project_hook.id
-                )?;
+            if filter.matches_filename(file.hook_path()) && filter.matches_tags(tags) {
+                *matched = true;
+                remaining -= 1;
             }
         }
-    }
 
-    Ok((code, output))
+        if remaining == 0 {
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    });
+
+    hooks
+        .into_iter()
+        .zip(matches)
+        .filter_map(|(hook, matched)| (!matched).then_some(hook))
+        .collect()
 }
 
 fn load_meta_projects(hook: &Hook, filenames: &[&Path]) -> Result<Vec<Project>> {
@@ -193,22 +235,248 @@ fn load_meta_projects(hook: &Hook, filenames: &[&Path]) -> Result<Vec<Project>>
         .collect()
 }
 
+/// Returns the directories that contain a config file among `files`.
+///
+/// Directories are sorted, so parents come before children.
+pub(crate) fn config_dirs(files: &[PathBuf]) -> BTreeSet<&Path> {
+    files
+        .iter()
+        .filter(|file| {
+            file.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| CONFIG_FILENAMES.contains(&name))
+        })
+        .filter_map(|file| file.parent())
+        .collect()
+}
+
+/// Loads every project config below the hook's project, not just the changed ones,
+/// so workspace-scoped meta hooks can compare all member configs against each other.
+///
+/// Projects are returned sorted by their relative path, so parents come before children.
+fn load_workspace_projects(store: &Store, hook: &Hook) -> Result<Vec<Arc<Project>>> {
+    let project = hook.project();
+    let root = project
+        .path()
+        .ancestors()
+        .nth(project.relative_path().components().count())
+        .context("Project path should be below the workspace root")?;
+
+    let workspace = Workspace::discover(store, root.to_path_buf(), None, None, &Refresh::None)?;
+    let mut projects = workspace
+        .all_projects()
+        .iter()
+        .filter(|member| member.relative_path().starts_with(project.relative_path()))
+        .cloned()
+        .collect::<Vec<_>>();
+    projects.sort_by(|a, b| a.relative_path().cmp(b.relative_path()));
+
+    Ok(projects)
+}
+
+/// Returns the repo and hook ids of hooks that behave the same wherever they are configured.
+///
+/// Local hooks are skipped since the same id can mean different commands in different projects,
+/// and meta hooks only ever validate their own project.
+fn shared_hook_ids(config: &config::Config) -> Vec<(&str, &str)> {
+    let mut hook_ids = Vec::new();
+    for repo in &config.repos {
+        match repo {
+            config::Repo::Remote(repo) => {
+                hook_ids.extend(
+                    repo.hooks
+                        .iter()
+                        .map(|hook| (repo.repo(), hook.id.as_str())),
+                );
+            }
+            config::Repo::Builtin(repo) => {
+                hook_ids.extend(
+                    repo.hooks
+                        .iter()
+                        .map(|hook| (repo.repo.as_str(), hook.id.as_str())),
+                );
+            }
+            config::Repo::Local(_) | config::Repo::Meta(_) => {}
+        }
+    }
+    hook_ids
+}
+
+/// A hook configured in both a project and one of its parent projects.
+pub(crate) struct DuplicateHook<'a> {
+    pub(crate) project: &'a Project,
+    pub(crate) parent: &'a Project,
+    pub(crate) repo: &'a str,
+    pub(crate) hook_id: &'a str,
+}
+
+impl std::fmt::Display for DuplicateHook<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` from `{}` is also configured in parent project `{}`",
+            self.hook_id, self.repo, self.parent
+        )
+    }
+}
+
+/// Finds hooks configured in both a project and one of its parent projects.
+///
+/// `projects` must be sorted by their relative path, so parents come before children.
+pub(crate) fn find_duplicate_hooks<P: Borrow<Project>>(projects: &[P]) -> Vec<DuplicateHook<'_>> {
+    let mut duplicates = Vec::new();
+
+    for (idx, project) in projects.iter().enumerate() {
+        let project = project.borrow();
+        if project.config().orphan.unwrap_or(false) {
+            continue;
+        }
+
+        let hook_ids = shared_hook_ids(project.config());
+        if hook_ids.is_empty() {
+            continue;
+        }
+
+        // Walk up from the nearest parent, stopping at the first orphan project
+        // since it hides its files from the projects above it.
+        let parents =
+            projects[..idx]
+                .iter()
+                .rev()
+                .map(Borrow::borrow)
+                .filter(|parent: &&Project| {
+                    project.relative_path().starts_with(parent.relative_path())
+                });
+        for parent in parents {
+            let parent_hook_ids = shared_hook_ids(parent.config());
+            for (repo, hook_id) in hook_ids.iter().unique() {
+                if parent_hook_ids.contains(&(*repo, *hook_id)) {
+                    duplicates.push(DuplicateHook {
+                        project,
+                        parent,
+                        repo,
+                        hook_id,
+                    });
+                }
+            }
+
+            if parent.config().orphan.unwrap_or(false) {
+                break;
+            }
+        }
+    }
+
+    duplicates
+}
+
+/// Reports hooks configured in both a project and one of its parent projects.
+///
+/// Parent projects also see the files of non-orphan nested projects, so such hooks
+/// run twice on the same files.
+pub(crate) fn check_duplicate_hooks_across_projects(
+    store: &Store,
+    hook: &Hook,
+) -> Result<(i32, Vec<u8>)> {
+    let projects = load_workspace_projects(store, hook)?;
+
+    let mut code = 0;
+    let mut output = Vec::new();
+    for duplicate in find_duplicate_hooks(&projects) {
+        code = 1;
+        writeln!(&mut output, "{}: {duplicate}", duplicate.project)?;
+    }
+
+    Ok((code, output))
+}
+
+/// Reports remote repos that are pinned to different revisions in different projects.
+pub(crate) fn check_rev_consistency(store: &Store, hook: &Hook) -> Result<(i32, Vec<u8>)> {
+    let projects = load_workspace_projects(store, hook)?;
+
+    // repo -> rev -> projects pinning it
+    let mut revs: BTreeMap<&str, BTreeMap<&str, Vec<&Project>>> = BTreeMap::new();
+    for project in &projects {
+        let project: &Project = project;
+        for repo in &project.config().repos {
+            if let config::Repo::Remote(repo) = repo {
+                let pinned = revs
+                    .entry(repo.repo())
+                    .or_default()
+                    .entry(repo.rev.as_str())
+                    .or_default();
+                if !pinned.contains(&project) {
+                    pinned.push(project);
+                }
+            }
+        }
+    }
+
+    let mut code = 0;
+    let mut output = Vec::new();
+
+    for project in &projects {
+        let mut reported = BTreeSet::new();
+        for repo in &project.config().repos {
+            let config::Repo::Remote(repo) = repo else {
+                continue;
+            };
+            let repo_revs = &revs[repo.repo()];
+            if repo_revs.len() < 2 || !reported.insert((repo.repo(), repo.rev.as_str())) {
+                continue;
+            }
+
+            code = 1;
+            let others = repo_revs
+                .iter()
+                .filter(|(rev, _)| **rev != repo.rev)
+                .map(|(rev, projects)| format!("`{rev}` ({})", projects.iter().join(", ")))
+                .join(", ");
+            writeln!(
+                &mut output,
+                "{project}: `{}` is pinned to `{}`, while other projects use {others}",
+                repo.repo(),
+                repo.rev,
+            )?;
+        }
+    }
+
+    Ok((code, output))
+}
+
+/// Collects the id and options of every hook in `repo`, with the repo-level `files` pattern
+/// that the hook's files must match as well.
 fn extend_hook_options<'a>(
     repo: &'a config::Repo,
-    hook_options: &mut Vec<(&'a String, &'a HookOptions)>,
+    hook_options: &mut Vec<(&'a String, &'a HookOptions, Option<&'a FilePattern>)>,
 ) {
     match repo {
         config::Repo::Remote(repo) => {
-            hook_options.extend(repo.hooks.iter().map(|hook| (&hook.id, &hook.options)));
+            hook_options.extend(
+                repo.hooks
+                    .iter()
+                    .map(|hook| (&hook.id, &hook.options, repo.files.as_ref())),
+            );
         }
         config::Repo::Local(repo) => {
-            hook_options.extend(repo.hooks.iter().map(|hook| (&hook.id, &hook.options)));
+            hook_options.extend(
+                repo.hooks
+                    .iter()
+                    .map(|hook| (&hook.id, &hook.options, None)),
+            );
         }
         config::Repo::Meta(repo) => {
-            hook_options.extend(repo.hooks.iter().map(|hook| (&hook.id, &hook.options)));
+            hook_options.extend(
+                repo.hooks
+                    .iter()
+                    .map(|hook| (&hook.id, &hook.options, None)),
+            );
         }
         config::Repo::Builtin(repo) => {
-            hook_options.extend(repo.hooks.iter().map(|hook| (&hook.id, &hook.options)));
+            hook_options.extend(
+                repo.hooks
+                    .iter()
+                    .map(|hook| (&hook.id, &hook.options, None)),
+            );
         }
     }
 }
@@ -288,17 +556,39 @@ pub(crate) async fn check_useless_excludes(
             )?;
         }
 
+        for repo in &config.repos {
+            let config::Repo::Remote(repo) = repo else {
+                continue;
+            };
+            if !excludes_any(&input_project, repo.files.as_ref(), repo.exclude.as_ref()) {
+                code = 1;
+                let display = repo
+                    .exclude
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                writeln!(
+                    &mut output,
+                    "The exclude pattern `{display}` for repo `{}` does not match any files",
+                    repo.repo
+                )?;
+            }
+        }
+
         let mut hook_options = Vec::new();
         for repo in &config.repos {
             extend_hook_options(repo, &mut hook_options);
         }
-        if hook_options.iter().all(|(_, opts)| opts.exclude.is_none()) {
+        if hook_options
+            .iter()
+            .all(|(_, opts, _)| opts.exclude.is_none())
+        {
             continue;
         }
 
         let tag_filters = hook_options
             .iter()
-            .map(|(_, opts)| {
+            .map(|(_, opts, _)| {
                 FileTagFilter::new(
                     opts.types.as_ref(),
                     opts.types_or.as_ref(),
@@ -308,13 +598,13 @@ pub(crate) async fn check_useless_excludes(
             .collect::<Vec<_>>();
         let mut exclude_matches = hook_options
             .iter()
-            .map(|(_, opts)| opts.exclude.is_none())
+            .map(|(_, opts, _)| opts.exclude.is_none())
             .collect::<Vec<_>>();
         let mut remaining = exclude_matches.iter().filter(|matched| !**matched).count();
 
         ProjectFiles::visit_for_project(input_workspace.iter(), &project, None, None, |file| {
             let tags = file.tags(&tag_cache);
-            for ((matched, (_, opts)), tag_filter) in exclude_matches
+            for ((matched, (_, opts, repo_files)), tag_filter) in exclude_matches
                 .iter_mut()
                 .zip(&hook_options)
                 .zip(&tag_filters)
@@ -323,7 +613,13 @@ pub(crate) async fn check_useless_excludes(
                     continue;
                 }
 
-                if matches_patterns(file.hook_path(), opts.files.as_ref(), opts.exclude.as_ref()) {
+                if repo_files.is_none_or(|pattern| pattern.is_match(file.hook_path()))
+                    && matches_patterns(
+                        file.hook_path(),
+                        opts.files.as_ref(),
+                        opts.exclude.as_ref(),
+                    )
+                {
                     *matched = true;
                     remaining -= 1;
                 }
@@ -336,7 +632,7 @@ pub(crate) async fn check_useless_excludes(
             ControlFlow::Continue(())
         });
 
-        for ((hook_id, opts), exclude_matches) in hook_options.iter().zip(exclude_matches) {
+        for ((hook_id, opts, _), exclude_matches) in hook_options.iter().zip(exclude_matches) {
             if !exclude_matches {
                 code = 1;
                 let display = opts
@@ -411,6 +707,20 @@ mod tests {
         assert!(useless_files.is_match(Path::new(PRE_COMMIT_CONFIG_YML)));
         assert!(useless_files.is_match(Path::new(PREK_TOML)));
 
+        for id in [
+            "check-duplicate-hooks-across-projects",
+            "check-rev-consistency",
+        ] {
+            let workspace = MetaHook::from_id(id).expect("known meta hook");
+            let workspace_files = workspace
+                .options
+                .files
+                .as_ref()
+                .expect("files should be set");
+            assert!(workspace_files.is_match(Path::new(PRE_COMMIT_CONFIG_YAML)));
+            assert!(workspace_files.is_match(Path::new(PREK_TOML)));
+        }
+
         let identity = MetaHook::from_id("identity").expect("known meta hook");
         assert!(identity.options.files.is_none());
         assert_eq!(identity.options.verbose, Some(true));
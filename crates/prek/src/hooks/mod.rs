@@ -9,7 +9,9 @@ use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, Repo};
 pub(crate) use crate::hooks::builtin_hooks::BuiltinHooks;
-pub(crate) use crate::hooks::meta_hooks::MetaHooks;
+pub(crate) use crate::hooks::meta_hooks::{
+    MetaHooks, config_dirs, find_duplicate_hooks, hooks_without_files,
+};
 use crate::hooks::pre_commit_hooks::{PreCommitHooks, is_pre_commit_hooks};
 use crate::store::Store;
 
@@ -59,6 +61,21 @@ pub(crate) fn may_modify_files(hook: &Hook) -> bool {
     }
 }
 
+/// Returns true if the hook is a builtin or fast-path hook that rewrites files.
+///
+/// Unlike [`may_modify_files`], hooks whose behavior prek cannot know return false.
+pub(crate) fn known_to_modify_files(hook: &Hook) -> bool {
+    match hook.repo() {
+        Repo::Builtin { .. } => {
+            BuiltinHooks::from_str(hook.id.as_str()).is_ok_and(BuiltinHooks::may_modify_files)
+        }
+        Repo::Remote { .. } => {
+            fast_path_hook(hook).is_some_and(|implemented| implemented.may_modify_files())
+        }
+        _ => false,
+    }
+}
+
 pub async fn run_fast_path(
     _store: &Store,
     hook: &Hook,
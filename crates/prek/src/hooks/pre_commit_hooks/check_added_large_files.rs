@@ -34,7 +34,7 @@ pub(crate) async fn check_added_large_files(
     let filenames = if args.enforce_all {
         filenames
     } else {
-        let added_files = get_added_files(hook.work_dir())
+        let added_files = get_added_files(hook.run_dir())
             .await?
             .into_iter()
             .collect::<FxHashSet<_>>();
@@ -50,9 +50,9 @@ pub(crate) async fn check_added_large_files(
         return Ok((0, Vec::new()));
     }
 
-    // Builtin hooks receive project-relative filenames, so git attribute lookups need to run
-    // from the project root for nested `.gitattributes` files to apply.
-    let lfs_files = get_lfs_files(hook.work_dir(), filenames).await?;
+    // Builtin hooks receive filenames relative to their run directory, so git attribute lookups
+    // need to run from there for nested `.gitattributes` files to apply.
+    let lfs_files = get_lfs_files(hook.run_dir(), filenames).await?;
 
     let filenames = filenames
         .iter()
@@ -60,7 +60,7 @@ pub(crate) async fn check_added_large_files(
         .filter(|f| !lfs_files.contains(*f));
 
     run_concurrent_file_checks(filenames, *INTERNAL_CONCURRENCY, |filename| async move {
-        let file_path = hook.project().relative_path().join(filename);
+        let file_path = hook.file_base().join(filename);
         let size = fs_err::tokio::metadata(file_path).await?.len() / 1024;
         if size > args.max_kb {
             anyhow::Ok((
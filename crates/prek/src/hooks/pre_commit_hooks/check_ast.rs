@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use anyhow::Result;
+use rustpython_parser::ast::{self, Parse};
+
+use crate::hook::Hook;
+use crate::hooks::pre_commit_hooks::{FilenamesArgs, hook_filenames, parse_hook_args};
+use crate::hooks::run_concurrent_file_checks;
+use crate::run::INTERNAL_CONCURRENCY;
+
+pub(crate) async fn check_ast(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+    let args: FilenamesArgs = parse_hook_args(hook)?;
+    run_concurrent_file_checks(
+        hook_filenames(&args.filenames, filenames),
+        *INTERNAL_CONCURRENCY,
+        |filename| check_file(hook.file_base(), filename),
+    )
+    .await
+}
+
+async fn check_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
+    let path = file_base.join(filename);
+    let content = fs_err::tokio::read(&path).await?;
+    match parse_python(filename, &content) {
+        Ok(_) => Ok((0, Vec::new())),
+        Err(error) => Ok((
+            1,
+            format!("{}: failed parsing: {error}\n", filename.display()).into_bytes(),
+        )),
+    }
+}
+
+/// Parse the content of a Python file as a module.
+///
+/// The parser supports syntax up to Python 3.11; newer constructs are reported as errors.
+/// On failure, returns a message with the 1-based line and column of the syntax error.
+pub(crate) fn parse_python(filename: &Path, content: &[u8]) -> Result<ast::Suite, String> {
+    let source = std::str::from_utf8(content).map_err(|e| format!("invalid UTF-8 ({e})"))?;
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+
+    ast::Suite::parse(source, &filename.to_string_lossy()).map_err(|err| {
+        let (line, col) = line_col(source, usize::from(err.offset));
+        format!("{} at line {line}, column {}", err.error, col + 1)
+    })
+}
+
+/// Convert a byte offset into a 1-based line and a 0-based byte column.
+pub(crate) fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source.as_bytes()[..offset.min(source.len())];
+    let line = memchr::memchr_iter(b'\n', before).count() + 1;
+    let col = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(before.len(), |pos| before.len() - pos - 1);
+    (line, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_python() {
+        let content = b"import os\n\n\ndef f(x):\n    return x + 1\n";
+        assert!(parse_python(Path::new("ok.py"), content).is_ok());
+    }
+
+    #[test]
+    fn valid_python_with_bom() {
+        let content = b"\xef\xbb\xbfx = 1\n";
+        assert!(parse_python(Path::new("bom.py"), content).is_ok());
+    }
+
+    #[test]
+    fn invalid_python() {
+        let content = b"x = 1\ndef f(:\n    pass\n";
+        let error = parse_python(Path::new("bad.py"), content).unwrap_err();
+        assert!(error.contains("at line 2"), "{error}");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let error = parse_python(Path::new("bad.py"), b"x = '\xff'\n").unwrap_err();
+        assert!(error.starts_with("invalid UTF-8"), "{error}");
+    }
+
+    #[test]
+    fn offsets_to_line_col() {
+        let source = "a = 1\nbb = 2\n";
+        assert_eq!(line_col(source, 0), (1, 0));
+        assert_eq!(line_col(source, 4), (1, 4));
+        assert_eq!(line_col(source, 6), (2, 0));
+        assert_eq!(line_col(source, 9), (2, 3));
+    }
+}
@@ -16,10 +16,10 @@ pub(crate) async fn check_case_conflict(
 ) -> Result<(i32, Vec<u8>)> {
     let args: FilenamesArgs = parse_hook_args(hook)?;
     let filenames = hook_filenames(&args.filenames, filenames).collect::<Vec<_>>();
-    let work_dir = hook.work_dir();
+    let work_dir = hook.run_dir();
 
-    // Get all files in the repo.
-    let repo_files = git::ls_files(work_dir, [Path::new(".")]).await?;
+    // Get all files in the repo, read straight from the index.
+    let repo_files = git::ls_index_files(work_dir).await?;
     let mut repo_files_with_dirs: FxHashSet<&Path> = FxHashSet::default();
     for path in &repo_files {
         insert_path_and_parents(&mut repo_files_with_dirs, path);
@@ -32,7 +32,7 @@ pub(crate) async fn check_executables_have_shebangs(
         .stdout;
 
     let tracks_executable_bit = std::str::from_utf8(&stdout)?.trim() != "false";
-    let file_base = hook.project().relative_path();
+    let file_base = hook.file_base();
 
     let (code, output) = if tracks_executable_bit {
         // core.fileMode=true means the platform honors the executable bit, so trust the FS metadata.
@@ -22,7 +22,7 @@ pub(crate) async fn check_shebang_scripts_are_executable(
         return Ok((0, Vec::new()));
     }
 
-    let file_base = hook.project().relative_path();
+    let file_base = hook.file_base();
     let stdout = git_index_stage_output(file_base).await?;
     let filenames: FxHashSet<_> = filenames.into_iter().collect();
     let entries = matching_git_index_paths_by_executable_bit(&stdout, file_base, &filenames, false);
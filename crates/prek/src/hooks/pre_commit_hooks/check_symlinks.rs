@@ -12,7 +12,7 @@ pub(crate) async fn check_symlinks(hook: &Hook, filenames: &[&Path]) -> Result<(
     run_concurrent_file_checks(
         hook_filenames(&args.filenames, filenames),
         *INTERNAL_CONCURRENCY,
-        |filename| check_file(hook.project().relative_path(), filename),
+        |filename| check_file(hook.file_base(), filename),
     )
     .await
 }
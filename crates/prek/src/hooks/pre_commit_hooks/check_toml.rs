@@ -13,7 +13,7 @@ pub(crate) async fn check_toml(hook: &Hook, filenames: &[&Path]) -> Result<(i32,
     run_concurrent_file_checks(
         hook_filenames(&args.filenames, filenames),
         *INTERNAL_CONCURRENCY,
-        |filename| check_file(hook.project().relative_path(), filename),
+        |filename| check_file(hook.file_base(), filename),
     )
     .await
 }
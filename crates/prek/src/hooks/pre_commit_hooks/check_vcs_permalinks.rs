@@ -90,7 +90,7 @@ pub(crate) async fn check_vcs_permalinks(
         args.additional_github_domains,
     ));
 
-    let file_base = hook.project().relative_path();
+    let file_base = hook.file_base();
     run_concurrent_file_checks(
         hook_filenames(&args.filenames, filenames),
         *INTERNAL_CONCURRENCY,
@@ -29,13 +29,7 @@ pub(crate) async fn check_yaml(hook: &Hook, filenames: &[&Path]) -> Result<(i32,
     run_concurrent_file_checks(
         hook_filenames(&args.filenames, filenames),
         *INTERNAL_CONCURRENCY,
-        |filename| {
-            check_file(
-                hook.project().relative_path(),
-                filename,
-                args.allow_multiple_documents,
-            )
-        },
+        |filename| check_file(hook.file_base(), filename, args.allow_multiple_documents),
     )
     .await
 }
@@ -0,0 +1,178 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use rustpython_ast::{Expr, ExprCall, StmtImport, StmtImportFrom, Visitor};
+
+use crate::hook::Hook;
+use crate::hooks::pre_commit_hooks::check_ast::{line_col, parse_python};
+use crate::hooks::pre_commit_hooks::{FilenamesArgs, hook_filenames, parse_hook_args};
+use crate::hooks::run_concurrent_file_checks;
+use crate::run::INTERNAL_CONCURRENCY;
+
+/// Debugger modules flagged when imported, the same set as upstream `debug-statements`.
+const DEBUG_STATEMENTS: &[&str] = &[
+    "bpdb",
+    "ipdb",
+    "pdb",
+    "pdbr",
+    "pudb",
+    "pydevd_pycharm",
+    "q",
+    "rdb",
+    "rpdb",
+    "wdb",
+];
+
+pub(crate) async fn debug_statements(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
+    let args: FilenamesArgs = parse_hook_args(hook)?;
+    run_concurrent_file_checks(
+        hook_filenames(&args.filenames, filenames),
+        *INTERNAL_CONCURRENCY,
+        |filename| check_file(hook.file_base(), filename),
+    )
+    .await
+}
+
+async fn check_file(file_base: &Path, filename: &Path) -> Result<(i32, Vec<u8>)> {
+    let path = file_base.join(filename);
+    let content = fs_err::tokio::read(&path).await?;
+    let suite = match parse_python(filename, &content) {
+        Ok(suite) => suite,
+        Err(error) => {
+            let output = format!("{} - Could not parse ast: {error}\n", filename.display());
+            return Ok((1, output.into_bytes()));
+        }
+    };
+
+    let mut visitor = DebugStatementVisitor::default();
+    for stmt in suite {
+        visitor.visit_stmt(stmt);
+    }
+    if visitor.breakpoints.is_empty() {
+        return Ok((0, Vec::new()));
+    }
+
+    // Parsing succeeded, so the content is valid UTF-8.
+    let source = String::from_utf8_lossy(&content);
+    let source = source.strip_prefix('\u{feff}').unwrap_or(&source);
+    let mut output = String::new();
+    for breakpoint in visitor.breakpoints {
+        let (line, col) = line_col(source, breakpoint.offset);
+        let _ = writeln!(
+            output,
+            "{}:{line}:{col}: {} {}",
+            filename.display(),
+            breakpoint.name,
+            breakpoint.reason,
+        );
+    }
+    Ok((1, output.into_bytes()))
+}
+
+struct Breakpoint {
+    offset: usize,
+    name: String,
+    reason: &'static str,
+}
+
+#[derive(Default)]
+struct DebugStatementVisitor {
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl Visitor for DebugStatementVisitor {
+    fn visit_stmt_import(&mut self, node: StmtImport) {
+        for alias in &node.names {
+            if DEBUG_STATEMENTS.contains(&alias.name.as_str()) {
+                self.breakpoints.push(Breakpoint {
+                    offset: usize::from(node.range.start()),
+                    name: alias.name.to_string(),
+                    reason: "imported",
+                });
+            }
+        }
+    }
+
+    fn visit_stmt_import_from(&mut self, node: StmtImportFrom) {
+        if let Some(module) = &node.module
+            && DEBUG_STATEMENTS.contains(&module.as_str())
+        {
+            self.breakpoints.push(Breakpoint {
+                offset: usize::from(node.range.start()),
+                name: module.to_string(),
+                reason: "imported",
+            });
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: ExprCall) {
+        if let Expr::Name(func) = node.func.as_ref()
+            && func.id.as_str() == "breakpoint"
+        {
+            self.breakpoints.push(Breakpoint {
+                offset: usize::from(node.range.start()),
+                name: func.id.to_string(),
+                reason: "called",
+            });
+        }
+        self.generic_visit_expr_call(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    async fn check(content: &str) -> Result<(i32, String)> {
+        let dir = tempdir()?;
+        let path = dir.path().join("file.py");
+        fs_err::tokio::write(&path, content).await?;
+        let (code, output) = check_file(Path::new(""), &path).await?;
+        let output = String::from_utf8(output)?.replace(&*path.to_string_lossy(), "file.py");
+        Ok((code, output))
+    }
+
+    #[tokio::test]
+    async fn no_debug_statements() -> Result<()> {
+        let (code, output) = check("import os\n\nprint(os.getcwd())\n").await?;
+        assert_eq!(code, 0);
+        assert!(output.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn debug_imports_and_breakpoint() -> Result<()> {
+        let content = indoc::indoc! {"
+            import pdb
+            from ipdb import set_trace
+
+
+            def f():
+                import os, pudb
+                if True:
+                    breakpoint()
+        "};
+        let (code, output) = check(content).await?;
+        assert_eq!(code, 1);
+        insta::assert_snapshot!(output, @r"
+        file.py:1:0: pdb imported
+        file.py:2:0: ipdb imported
+        file.py:6:4: pudb imported
+        file.py:8:8: breakpoint called
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unparsable_file() -> Result<()> {
+        let (code, output) = check("def f(:\n").await?;
+        assert_eq!(code, 1);
+        assert!(
+            output.starts_with("file.py - Could not parse ast"),
+            "{output}"
+        );
+        Ok(())
+    }
+}
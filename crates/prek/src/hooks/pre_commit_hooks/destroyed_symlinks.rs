@@ -16,7 +16,7 @@ const PERMS_NONEXIST: u32 = 0;
 pub(crate) async fn destroyed_symlinks(hook: &Hook, filenames: &[&Path]) -> Result<(i32, Vec<u8>)> {
     let args: FilenamesArgs = parse_hook_args(hook)?;
     let filenames = hook_filenames(&args.filenames, filenames).collect::<Vec<_>>();
-    let status_output = git_status_output(hook.work_dir()).await?;
+    let status_output = git_status_output(hook.run_dir()).await?;
     let entries = status_output
         .split(|&byte| byte == b'\0')
         .filter_map(|entry| match parse_ordinary_changed_entry(entry) {
@@ -82,7 +82,11 @@ async fn find_destroyed_symlinks<'a>(
     }
 
     let filenames = filenames.iter().copied().collect::<FxHashSet<_>>();
-    let relative_prefix = hook.project().relative_path();
+    let relative_prefix = if hook.files_from_repo_root {
+        Path::new("")
+    } else {
+        hook.project().relative_path()
+    };
     let mut destroyed_links = Vec::new();
 
     for entry in entries {
@@ -106,7 +110,7 @@ async fn find_destroyed_symlinks<'a>(
             continue;
         }
 
-        if is_destroyed_symlink(hook.work_dir(), &entry).await? {
+        if is_destroyed_symlink(hook.run_dir(), &entry).await? {
             destroyed_links.push(entry_path);
         }
     }
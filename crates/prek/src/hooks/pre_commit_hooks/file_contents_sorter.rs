@@ -26,7 +26,7 @@ pub(crate) async fn file_contents_sorter(
     filenames: &[&Path],
 ) -> Result<(i32, Vec<u8>)> {
     let args: Args = parse_hook_args(hook)?;
-    let file_base = hook.project().relative_path();
+    let file_base = hook.file_base();
 
     run_file_checks(
         &args.filenames,
@@ -19,7 +19,7 @@ pub(crate) async fn fix_byte_order_marker(
         &args.filenames,
         filenames,
         *INTERNAL_CONCURRENCY,
-        |filename| fix_file(hook.project().relative_path(), filename),
+        |filename| fix_file(hook.file_base(), filename),
     )
     .await
 }
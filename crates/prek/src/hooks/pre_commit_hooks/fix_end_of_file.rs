@@ -13,7 +13,7 @@ pub(crate) async fn fix_end_of_file(hook: &Hook, filenames: &[&Path]) -> Result<
         &args.filenames,
         filenames,
         *INTERNAL_CONCURRENCY,
-        |filename| fix_file(hook.project().relative_path(), filename),
+        |filename| fix_file(hook.file_base(), filename),
     )
     .await
 }
@@ -79,7 +79,7 @@ pub(crate) async fn fix_trailing_whitespace(
         *INTERNAL_CONCURRENCY,
         |filename| {
             fix_file(
-                hook.project().relative_path(),
+                hook.file_base(),
                 filename,
                 chars,
                 force_markdown,
@@ -23,7 +23,7 @@ pub(crate) async fn forbid_new_submodules(
     };
 
     let stdout = git::git_cmd()?
-        .current_dir(hook.work_dir())
+        .current_dir(hook.run_dir())
         .arg("diff")
         .arg("--relative")
         .arg("--diff-filter=A")
@@ -68,7 +68,7 @@ pub(crate) async fn mixed_line_ending(hook: &Hook, filenames: &[&Path]) -> Resul
         &args.filenames,
         filenames,
         *INTERNAL_CONCURRENCY,
-        |filename| fix_file(hook.project().relative_path(), filename, args.fix),
+        |filename| fix_file(hook.file_base(), filename, args.fix),
     )
     .await
 }
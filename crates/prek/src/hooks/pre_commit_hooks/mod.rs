@@ -11,6 +11,7 @@ use crate::hooks::run_concurrent_file_checks;
 use super::HookFuture;
 
 mod check_added_large_files;
+mod check_ast;
 mod check_case_conflict;
 mod check_executables_have_shebangs;
 pub(crate) mod check_json;
@@ -21,6 +22,7 @@ mod check_toml;
 mod check_vcs_permalinks;
 mod check_xml;
 mod check_yaml;
+mod debug_statements;
 mod destroyed_symlinks;
 mod detect_private_key;
 mod file_contents_sorter;
@@ -34,6 +36,7 @@ mod pretty_format_json;
 mod shebangs;
 
 pub(crate) use check_added_large_files::check_added_large_files;
+pub(crate) use check_ast::check_ast;
 pub(crate) use check_case_conflict::check_case_conflict;
 pub(crate) use check_executables_have_shebangs::check_executables_have_shebangs;
 pub(crate) use check_json::check_json;
@@ -44,6 +47,7 @@ pub(crate) use check_toml::check_toml;
 pub(crate) use check_vcs_permalinks::check_vcs_permalinks;
 pub(crate) use check_xml::check_xml;
 pub(crate) use check_yaml::check_yaml;
+pub(crate) use debug_statements::debug_statements;
 pub(crate) use destroyed_symlinks::destroyed_symlinks;
 pub(crate) use detect_private_key::detect_private_key;
 pub(crate) use file_contents_sorter::file_contents_sorter;
@@ -139,6 +143,11 @@ pub(crate) enum PreCommitHooks {
     MixedLineEnding,
     DetectPrivateKey,
     NoCommitToBranch,
+    // `check-ast` and `debug-statements` are builtin-only as well: the Rust parser only
+    // understands Python up to 3.11, so newer syntax would be reported as a parse error where
+    // the upstream hook, running on the project's own interpreter, accepts it.
+    // CheckAst,
+    // DebugStatements,
     // `pretty-format-json` is intentionally builtin-only for now. Do not enable
     // automatic fast-path replacement until parity coverage against upstream
     // Python is broad enough to trust it as the default implementation.
@@ -23,7 +23,9 @@ impl Args {
         }
 
         for pattern in &self.patterns {
-            let pattern = Regex::new(pattern).context("Failed to compile regex patterns")?;
+            // Patterns only need to match at the start of the branch name, like Python's `re.match`.
+            let pattern = Regex::new(&format!(r"\A(?:{pattern})"))
+                .context("Failed to compile regex patterns")?;
             if pattern.is_match(branch).unwrap_or(false) {
                 return Ok(true);
             }
@@ -49,7 +51,8 @@ pub(crate) async fn no_commit_to_branch(hook: &Hook) -> Result<(i32, Vec<u8>)> {
 
     let ref_name = String::from_utf8_lossy(&output.stdout);
     // stdout must start with "refs/heads/"
-    let branch = ref_name.trim().trim_start_matches("refs/heads/");
+    let ref_name = ref_name.trim();
+    let branch = ref_name.strip_prefix("refs/heads/").unwrap_or(ref_name);
 
     if args.check_protected(branch)? {
         let err_msg = format!("You are not allowed to commit to branch '{branch}'\n");
@@ -58,3 +61,41 @@ pub(crate) async fn no_commit_to_branch(hook: &Hook) -> Result<(i32, Vec<u8>)> {
         Ok((0, Vec::new()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(argv: &[&str]) -> Args {
+        Args::try_parse_from(std::iter::once("no-commit-to-branch").chain(argv.iter().copied()))
+            .unwrap()
+    }
+
+    #[test]
+    fn default_branches() -> Result<()> {
+        let args = args(&[]);
+        assert!(args.check_protected("main")?);
+        assert!(args.check_protected("master")?);
+        assert!(!args.check_protected("feature/main")?);
+        Ok(())
+    }
+
+    #[test]
+    fn patterns_match_at_start() -> Result<()> {
+        let args = args(&[
+            "--branch",
+            "develop",
+            "--pattern",
+            "release/.*",
+            "-p",
+            ".*-wip$",
+        ]);
+        assert!(args.check_protected("develop")?);
+        assert!(!args.check_protected("main")?);
+        assert!(args.check_protected("release/1.0")?);
+        assert!(!args.check_protected("backport/release/1.0")?);
+        assert!(args.check_protected("my-branch-wip")?);
+        assert!(!args.check_protected("my-branch-wip2")?);
+        Ok(())
+    }
+}
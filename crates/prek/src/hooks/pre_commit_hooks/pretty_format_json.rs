@@ -78,7 +78,7 @@ pub(crate) async fn pretty_format_json(hook: &Hook, filenames: &[&Path]) -> Resu
         &args.filenames,
         filenames,
         *INTERNAL_CONCURRENCY,
-        |filename| check_file(hook.project().relative_path(), filename, &prepared),
+        |filename| check_file(hook.file_base(), filename, &prepared),
     )
     .await
 }
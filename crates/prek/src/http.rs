@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::LazyLock;
+use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
 use futures_util::TryStreamExt;
@@ -8,6 +9,8 @@ use owo_colors::OwoColorize;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use reqwest::Certificate;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
 use tokio_util::io::StreamReader;
 use tracing::debug;
 
@@ -104,6 +107,30 @@ pub(crate) async fn download_artifact_with(
     Ok(download)
 }
 
+/// The size of the file at `url` as reported by a `HEAD` request, if the host reports one.
+pub(crate) async fn content_length(url: &str) -> Option<u64> {
+    let response = match REQWEST_CLIENT
+        .head(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+    {
+        Ok(response) => response,
+        Err(err) => {
+            debug!(url = %url, "Failed to look up download size: {err}");
+            return None;
+        }
+    };
+    // `Response::content_length` reports the empty body of a `HEAD` response.
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
 async fn download_to_temp_file(
     url: &str,
     filename: &str,
@@ -118,17 +145,34 @@ async fn download_to_temp_file(
         .send()
         .await
         .and_then(reqwest::Response::error_for_status)
-        .with_context(|| format!("Failed to download file from {url}"))?;
-
-    let stream = response.bytes_stream().map_err(std::io::Error::other);
+        .map_err(|err| download_error(url, err))?;
+
+    let stream = Box::pin(
+        response
+            .bytes_stream()
+            .map_err(std::io::Error::other)
+            .and_then(|chunk| async move {
+                if let Some(limiter) = DOWNLOAD_RATE_LIMITER.as_ref() {
+                    limiter.throttle(chunk.len()).await;
+                }
+                Ok::<_, std::io::Error>(chunk)
+            }),
+    );
 
     let mut reader = HashReader::new(StreamReader::new(stream));
     let mut file = fs_err::tokio::File::create(&path)
         .await
         .with_context(|| format!("Failed to create temporary download `{}`", path.display()))?;
-    tokio::io::copy(&mut reader, &mut file)
-        .await
-        .with_context(|| format!("Failed to download file from {url} to `{}`", path.display()))?;
+    if let Err(err) = tokio::io::copy(&mut reader, &mut file).await {
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(err) => download_error(url, err),
+            Err(err) => anyhow::Error::new(err),
+        };
+        return Err(err.context(format!(
+            "Failed to download file from {url} to `{}`",
+            path.display()
+        )));
+    }
     file.flush()
         .await
         .with_context(|| format!("Failed to flush temporary download `{}`", path.display()))?;
@@ -143,6 +187,130 @@ async fn download_to_temp_file(
     ))
 }
 
+/// Turn a failed request into an error naming the URL, with a hint when it timed out.
+fn download_error(url: &str, err: reqwest::Error) -> anyhow::Error {
+    if err.is_timeout() {
+        let timeouts = HttpTimeouts::from_env(&EnvVars);
+        anyhow::Error::new(err).context(format!(
+            "Timed out downloading {url} (connect timeout {}s, read timeout {}s); \
+             set `{}` or `{}` to wait longer",
+            timeouts.connect.as_secs(),
+            timeouts.read.as_secs(),
+            EnvVars::PREK_CONNECT_TIMEOUT,
+            EnvVars::PREK_DOWNLOAD_TIMEOUT,
+        ))
+    } else {
+        anyhow::Error::new(err).context(format!("Failed to download file from {url}"))
+    }
+}
+
+/// Connect and read timeouts for all HTTP requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HttpTimeouts {
+    connect: Duration,
+    /// The longest time to wait for the next chunk of a response, not for the whole response,
+    /// so large downloads on slow connections still complete.
+    read: Duration,
+}
+
+impl HttpTimeouts {
+    const DEFAULT_CONNECT: Duration = Duration::from_secs(10);
+    const DEFAULT_READ: Duration = Duration::from_secs(30);
+
+    fn from_env(env_vars: &impl EnvVarsRead) -> Self {
+        Self {
+            connect: read_seconds(
+                env_vars,
+                EnvVars::PREK_CONNECT_TIMEOUT,
+                Self::DEFAULT_CONNECT,
+            ),
+            read: read_seconds(env_vars, EnvVars::PREK_DOWNLOAD_TIMEOUT, Self::DEFAULT_READ),
+        }
+    }
+}
+
+fn read_seconds(env_vars: &impl EnvVarsRead, name: &str, default: Duration) -> Duration {
+    let Ok(value) = env_vars.var(name) else {
+        return default;
+    };
+    match value.trim().parse::<u64>() {
+        Ok(seconds) if seconds > 0 => Duration::from_secs(seconds),
+        _ => {
+            warn_user!(
+                "Invalid value for {name}: {value:?}. Expected a positive number of seconds; using default ({})",
+                default.as_secs(),
+            );
+            default
+        }
+    }
+}
+
+/// Parse a rate such as `500K`, `2M` or `1048576` into bytes per second.
+///
+/// Suffixes are binary multiples and may be followed by `B` or `iB`.
+fn parse_rate(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let value = value
+        .strip_suffix("iB")
+        .or_else(|| value.strip_suffix('B'))
+        .unwrap_or(value);
+    let (number, multiplier) = match value.chars().last()?.to_ascii_uppercase() {
+        'K' => (&value[..value.len() - 1], 1024),
+        'M' => (&value[..value.len() - 1], 1024 * 1024),
+        'G' => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+    let bytes = number.trim().parse::<u64>().ok()?.checked_mul(multiplier)?;
+    (bytes > 0).then_some(bytes)
+}
+
+/// Limits the combined throughput of all concurrent downloads.
+struct RateLimiter {
+    bytes_per_second: u64,
+    /// The time at which the bytes received so far are paid for.
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            next: Mutex::new(None),
+        }
+    }
+
+    fn from_env(env_vars: &impl EnvVarsRead) -> Option<Self> {
+        let value = env_vars.var(EnvVars::PREK_DOWNLOAD_RATE_LIMIT).ok()?;
+        let Some(bytes_per_second) = parse_rate(&value) else {
+            warn_user!(
+                "Invalid value for {}: {:?}. Expected a rate in bytes per second, such as `500K` or `2M`; downloads are not limited",
+                EnvVars::PREK_DOWNLOAD_RATE_LIMIT,
+                value,
+            );
+            return None;
+        };
+        debug!("Limiting downloads to {bytes_per_second} bytes per second");
+        Some(Self::new(bytes_per_second))
+    }
+
+    /// Wait until `len` more bytes fit in the rate limit.
+    async fn throttle(&self, len: usize) {
+        #[allow(clippy::cast_precision_loss)]
+        let cost = Duration::from_secs_f64(len as f64 / self.bytes_per_second as f64);
+        let deadline = {
+            let mut next = self.next.lock().await;
+            let now = Instant::now();
+            let deadline = next.map_or(now, |next| next.max(now)) + cost;
+            *next = Some(deadline);
+            deadline
+        };
+        tokio::time::sleep_until(deadline).await;
+    }
+}
+
+static DOWNLOAD_RATE_LIMITER: LazyLock<Option<RateLimiter>> =
+    LazyLock::new(|| RateLimiter::from_env(&EnvVars));
+
 pub(crate) static REQWEST_CLIENT: LazyLock<reqwest::Client> =
     LazyLock::new(|| reqwest_client_from_env(&EnvVars));
 
@@ -168,7 +336,7 @@ fn reqwest_client_from_env(env_vars: &impl EnvVarsRead) -> reqwest::Client {
     };
 
     let certs = load_certs_from_paths(cert_file.as_deref(), &cert_dirs);
-    create_reqwest_client(native_tls, certs)
+    create_reqwest_client(native_tls, certs, HttpTimeouts::from_env(env_vars))
 }
 
 fn load_pem_certs_from_file(path: &Path) -> Result<Vec<Certificate>> {
@@ -239,9 +407,15 @@ fn load_certs_from_paths(file: Option<&Path>, dirs: &[impl AsRef<Path>]) -> Vec<
     certs
 }
 
-fn create_reqwest_client(native_tls: bool, custom_certs: Vec<Certificate>) -> reqwest::Client {
-    let builder =
-        reqwest::ClientBuilder::new().user_agent(format!("prek/{}", crate::version::version()));
+fn create_reqwest_client(
+    native_tls: bool,
+    custom_certs: Vec<Certificate>,
+    timeouts: HttpTimeouts,
+) -> reqwest::Client {
+    let builder = reqwest::ClientBuilder::new()
+        .user_agent(format!("prek/{}", crate::version::version()))
+        .connect_timeout(timeouts.connect)
+        .read_timeout(timeouts.read);
 
     let builder = if native_tls {
         debug!("Using native TLS for reqwest client");
@@ -266,6 +440,7 @@ fn create_reqwest_client(native_tls: bool, custom_certs: Vec<Certificate>) -> re
 mod tests {
     use std::path::Path;
     use std::str::FromStr;
+    use std::time::Duration;
 
     use anyhow::Result;
     use prek_consts::env_vars::EnvVars;
@@ -380,6 +555,49 @@ YyRIHN8wfdVoOw==
         );
     }
 
+    #[test]
+    fn http_timeouts_read_env() {
+        assert_eq!(
+            super::HttpTimeouts::from_env(&EnvVars::from_map(&[])),
+            super::HttpTimeouts {
+                connect: Duration::from_secs(10),
+                read: Duration::from_secs(30),
+            }
+        );
+        assert_eq!(
+            super::HttpTimeouts::from_env(&EnvVars::from_map(&[
+                (EnvVars::PREK_CONNECT_TIMEOUT, "5"),
+                (EnvVars::PREK_DOWNLOAD_TIMEOUT, "120"),
+            ])),
+            super::HttpTimeouts {
+                connect: Duration::from_secs(5),
+                read: Duration::from_secs(120),
+            }
+        );
+        assert_eq!(
+            super::HttpTimeouts::from_env(&EnvVars::from_map(&[(
+                EnvVars::PREK_DOWNLOAD_TIMEOUT,
+                "soon",
+            )])),
+            super::HttpTimeouts {
+                connect: Duration::from_secs(10),
+                read: Duration::from_secs(30),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_download_rate() {
+        assert_eq!(super::parse_rate("1048576"), Some(1_048_576));
+        assert_eq!(super::parse_rate("500K"), Some(500 * 1024));
+        assert_eq!(super::parse_rate("2m"), Some(2 * 1024 * 1024));
+        assert_eq!(super::parse_rate("2MiB"), Some(2 * 1024 * 1024));
+        assert_eq!(super::parse_rate("1GB"), Some(1024 * 1024 * 1024));
+        assert_eq!(super::parse_rate("0"), None);
+        assert_eq!(super::parse_rate("fast"), None);
+        assert_eq!(super::parse_rate(""), None);
+    }
+
     #[test]
     fn test_load_pem_certs_from_file() -> Result<()> {
         let temp_dir = tempfile::tempdir()?;
@@ -426,7 +644,11 @@ YyRIHN8wfdVoOw==
 
     #[tokio::test]
     async fn test_native_tls() {
-        let client = super::create_reqwest_client(true, vec![]);
+        let client = super::create_reqwest_client(
+            true,
+            vec![],
+            super::HttpTimeouts::from_env(&EnvVars::from_map(&[])),
+        );
         let resp = client.get("https://github.com").send().await;
         assert!(resp.is_ok(), "Failed to send request with native TLS");
     }
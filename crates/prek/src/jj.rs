@@ -0,0 +1,168 @@
+//! Support for Jujutsu (`jj`) repositories colocated with Git.
+//!
+//! In a colocated repo, the working copy is a `jj` change rather than staged Git changes, and
+//! `jj` snapshots it on every command. So prek asks `jj` for the files of the working-copy
+//! change instead of the Git index, and relies on the snapshot instead of stashing.
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use tracing::debug;
+
+use crate::process::Cmd;
+use crate::warn_user;
+
+pub(crate) static JJ: LazyLock<Result<PathBuf, which::Error>> =
+    LazyLock::new(|| which::which("jj"));
+
+static NO_JJ: LazyLock<bool> = LazyLock::new(|| no_jj_from_env(&EnvVars));
+
+fn no_jj_from_env(env_vars: &impl EnvVarsRead) -> bool {
+    env_vars
+        .var_as_bool(EnvVars::PREK_NO_JJ)
+        .unwrap_or_else(|value| {
+            warn_user!(
+                "Invalid value for {}: {:?}. Expected a boolean value; using default ({:?})",
+                EnvVars::PREK_NO_JJ,
+                value,
+                "false",
+            );
+            Some(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Return whether `git_root` is also a `jj` repo, and `jj` is available to query it.
+pub(crate) fn is_colocated(git_root: &Path) -> bool {
+    if !git_root.join(".jj").is_dir() {
+        return false;
+    }
+    if *NO_JJ {
+        debug!(
+            "Found `.jj` in the git root, but `{}` is set, using Git",
+            EnvVars::PREK_NO_JJ
+        );
+        return false;
+    }
+    if JJ.is_err() {
+        debug!("Found `.jj` in the git root, but `jj` is not installed, falling back to Git");
+        return false;
+    }
+    true
+}
+
+fn jj_cmd(root: &Path) -> Result<Cmd> {
+    let jj = JJ
+        .as_ref()
+        .map_err(|e| anyhow::anyhow!("`jj` not found: {e}"))?;
+    let mut cmd = Cmd::new(jj);
+    cmd.current_dir(root)
+        .args(["--no-pager", "--color", "never"])
+        .check(true);
+    Ok(cmd)
+}
+
+fn split_lines(stdout: &[u8]) -> Result<Vec<PathBuf>> {
+    let stdout = std::str::from_utf8(stdout).context("`jj` printed a path that is not UTF-8")?;
+    Ok(stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Snapshot the working copy and return the short id of the resulting commit.
+///
+/// The commit can be restored with `jj restore --from <id>` if a hook damages the working copy.
+pub(crate) async fn snapshot(root: &Path) -> Result<String> {
+    let output = jj_cmd(root)?
+        .args(["log", "--no-graph", "-r", "@", "-T", "commit_id.short()"])
+        .output()
+        .await?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Files added or modified in the working-copy change, relative to `root`.
+///
+/// Deleted files are left out, and renamed or copied files are reported by their new path.
+pub(crate) async fn get_changed_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let output = jj_cmd(root)?
+        .args(["diff", "--summary", "-r", "@"])
+        .output()
+        .await?;
+    let stdout =
+        std::str::from_utf8(&output.stdout).context("`jj` printed a path that is not UTF-8")?;
+    Ok(stdout.lines().filter_map(parse_summary_line).collect())
+}
+
+/// Parse a `jj diff --summary` line like `M src/lib.rs` or `R src/{old.rs => new.rs}`, returning
+/// `None` for deleted files.
+fn parse_summary_line(line: &str) -> Option<PathBuf> {
+    let (status, path) = line.split_once(' ')?;
+    match status {
+        "D" => None,
+        "R" | "C" => Some(PathBuf::from(renamed_path(path))),
+        _ => Some(PathBuf::from(path)),
+    }
+}
+
+/// The new path of a rename like `src/{old.rs => new.rs}` or `old.rs => new.rs`.
+fn renamed_path(path: &str) -> String {
+    if let Some((prefix, rest)) = path.split_once('{')
+        && let Some((renamed, suffix)) = rest.split_once('}')
+        && let Some((_, new)) = renamed.split_once(" => ")
+    {
+        // Moving out of a directory, like `{src => }/lib.rs`, leaves an extra separator.
+        let path = format!("{prefix}{new}{suffix}").replace("//", "/");
+        return path.trim_start_matches('/').to_string();
+    }
+    path.split_once(" => ")
+        .map_or(path, |(_, new)| new)
+        .to_string()
+}
+
+/// Files tracked in the working-copy change, relative to `root`.
+pub(crate) async fn ls_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let output = jj_cmd(root)?.args(["file", "list"]).output().await?;
+    split_lines(&output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use prek_consts::env_vars::EnvVars;
+
+    use super::{no_jj_from_env, parse_summary_line};
+
+    #[test]
+    fn summary_lines() {
+        let parse = |line| parse_summary_line(line).map(|path: PathBuf| path.display().to_string());
+
+        assert_eq!(parse("M src/lib.rs").as_deref(), Some("src/lib.rs"));
+        assert_eq!(parse("A new file.txt").as_deref(), Some("new file.txt"));
+        assert_eq!(parse("D removed.txt"), None);
+        assert_eq!(
+            parse("R src/{old.rs => new.rs}").as_deref(),
+            Some("src/new.rs")
+        );
+        assert_eq!(parse("R {src => lib}/a.rs").as_deref(), Some("lib/a.rs"));
+        assert_eq!(parse("R {src => }/a.rs").as_deref(), Some("a.rs"));
+        assert_eq!(parse("C old.txt => new.txt").as_deref(), Some("new.txt"));
+    }
+
+    #[test]
+    fn no_jj_env() {
+        assert!(!no_jj_from_env(&EnvVars::from_map(&[])));
+        assert!(no_jj_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_NO_JJ,
+            "1"
+        )])));
+        assert!(!no_jj_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_NO_JJ,
+            "false"
+        )])));
+    }
+}
@@ -1,5 +1,4 @@
 use std::path::Path;
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -11,12 +10,12 @@ use crate::cli::reporter::HookInstallReporter;
 use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
-use crate::languages::LanguageBackend;
 use crate::languages::bun::BunRequest;
 use crate::languages::bun::installer::{BunInstaller, BunResult, bin_dir, lib_dir};
 use crate::languages::version::LanguageRequest;
+use crate::languages::{LanguageBackend, ToolchainDownload};
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -113,6 +112,35 @@ impl LanguageBackend for Bun {
         Ok(())
     }
 
+    async fn toolchain_download(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> Result<Option<ToolchainDownload>> {
+        let request = match &hook.language_request {
+            LanguageRequest::Any { system_only: true } => return Ok(None),
+            LanguageRequest::Any { system_only: false } => &BunRequest::Any,
+            LanguageRequest::Bun(request) => request,
+            _ => unreachable!(),
+        };
+        BunInstaller::new(store.tools_path(ToolBucket::Bun))
+            .toolchain_download(request)
+            .await
+    }
+
+    async fn toolchain_version(&self, store: &Store, hook: &Hook) -> Result<Option<String>> {
+        let (request, allows_download) = match &hook.language_request {
+            LanguageRequest::Any { system_only } => (&BunRequest::Any, !system_only),
+            LanguageRequest::Bun(request) => (request, true),
+            _ => unreachable!(),
+        };
+        let bun = BunInstaller::new(store.tools_path(ToolBucket::Bun))
+            .install(store, request, allows_download)
+            .await
+            .context("Failed to install bun")?;
+        Ok(Some(bun.version().to_string()))
+    }
+
     async fn run(
         &self,
         store: &Store,
@@ -120,37 +148,16 @@ impl LanguageBackend for Bun {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Bun must have env path");
         let bun_bin = hook.toolchain_dir().expect("Bun binary must have parent");
         let new_path =
             prepend_paths(&[&bin_dir(env_dir), bun_bin]).context("Failed to join PATH")?;
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::BUN_INSTALL, env_dir)
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
+                .env(EnvVars::BUN_INSTALL, env_dir);
+        })
+        .await
     }
 }
@@ -15,6 +15,7 @@ use crate::checksum::{Sha256Digest, digest_from_sha256sums};
 use crate::fs::LockedFile;
 use crate::git;
 use crate::http::{REQWEST_CLIENT, download_artifact};
+use crate::languages::ToolchainDownload;
 use crate::languages::bun::BunRequest;
 use crate::languages::bun::version::BunVersion;
 use crate::process::Cmd;
@@ -109,6 +110,29 @@ impl BunInstaller {
         self.download(store, &resolved_version).await
     }
 
+    /// The bun `install` would download for `request`, or `None` if an installed or system
+    /// bun matches it.
+    pub(crate) async fn toolchain_download(
+        &self,
+        request: &BunRequest,
+    ) -> Result<Option<ToolchainDownload>> {
+        if self.find_installed(request).is_ok() || self.find_system_bun(request).await?.is_some() {
+            return Ok(None);
+        }
+
+        let download = match self.resolve_version(request).await {
+            Ok(version) => {
+                let (_, url) = Self::download_url(&version)?;
+                ToolchainDownload::resolved(&version, url).await
+            }
+            Err(err) => {
+                debug!("Failed to resolve bun version: {err:#}");
+                ToolchainDownload::unresolved()
+            }
+        };
+        Ok(Some(download))
+    }
+
     /// Get the installed version of Bun.
     fn find_installed(&self, req: &BunRequest) -> Result<BunResult> {
         let mut installed = fs_err::read_dir(&self.root)
@@ -185,8 +209,8 @@ impl BunInstaller {
         Ok(versions)
     }
 
-    /// Install a specific version of Bun.
-    async fn download(&self, store: &Store, version: &BunVersion) -> Result<BunResult> {
+    /// The archive filename and URL of a Bun release for the host platform.
+    fn download_url(version: &BunVersion) -> Result<(String, String)> {
         let arch = match HOST.architecture {
             Architecture::X86_64 => "x64",
             Architecture::Aarch64(_) => "aarch64",
@@ -202,6 +226,12 @@ impl BunInstaller {
         let filename = format!("bun-{os}-{arch}.zip");
         let url =
             format!("https://github.com/oven-sh/bun/releases/download/bun-v{version}/{filename}");
+        Ok((filename, url))
+    }
+
+    /// Install a specific version of Bun.
+    async fn download(&self, store: &Store, version: &BunVersion) -> Result<BunResult> {
+        let (filename, url) = Self::download_url(version)?;
         let checksum_url = format!(
             "https://github.com/oven-sh/bun/releases/download/bun-v{version}/SHASUMS256.txt"
         );
@@ -1,5 +1,4 @@
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -12,7 +11,7 @@ use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageBackend;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -94,38 +93,17 @@ impl LanguageBackend for Conda {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Conda must have env path");
         let new_path = conda_path(env_dir).context("Failed to join PATH")?;
         let entry = hook.entry.resolve(Some(&new_path), store)?;
 
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
                 .env(EnvVars::CONDA_PREFIX, env_dir)
                 .env_remove(EnvVars::PYTHONHOME)
-                .env_remove(EnvVars::VIRTUAL_ENV)
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+                .env_remove(EnvVars::VIRTUAL_ENV);
+        })
+        .await
     }
 }
 
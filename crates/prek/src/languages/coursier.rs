@@ -1,6 +1,5 @@
 use std::io::ErrorKind;
 use std::path::Path;
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -13,7 +12,7 @@ use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageBackend;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{CacheBucket, Store};
 
 const PRE_COMMIT_CHANNEL_DIR: &str = ".pre-commit-channel";
@@ -160,37 +159,16 @@ impl LanguageBackend for Coursier {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_path = hook.env_path().expect("Coursier must have env path");
         let coursier_cache = store.cache_path(CacheBucket::Coursier);
         let path_env = prepend_paths(&[env_path]).context("Failed to join PATH")?;
         let entry = hook.entry.resolve(Some(&path_env), store)?;
 
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .env(EnvVars::PATH, &path_env)
-                .env(EnvVars::COURSIER_CACHE, &coursier_cache)
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &path_env)
+                .env(EnvVars::COURSIER_CACHE, &coursier_cache);
+        })
+        .await
     }
 }
 
@@ -14,7 +14,6 @@ use std::collections::BTreeMap;
 use std::env::consts::EXE_EXTENSION;
 use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -28,7 +27,7 @@ use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageBackend;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -158,8 +157,6 @@ impl LanguageBackend for Dart {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Dart must have env path");
         let bin_path = bin_path(env_dir);
         let new_path = prepend_paths(&[&bin_path]).context("Failed to join PATH")?;
@@ -178,30 +175,11 @@ impl LanguageBackend for Dart {
             );
         }
 
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::PUB_CACHE, env_dir)
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
+                .env(EnvVars::PUB_CACHE, env_dir);
+        })
+        .await
     }
 }
 
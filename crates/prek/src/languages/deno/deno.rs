@@ -1,5 +1,4 @@
 use std::path::Path;
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -10,12 +9,12 @@ use tracing::debug;
 use crate::cli::reporter::HookInstallReporter;
 use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
-use crate::languages::LanguageBackend;
 use crate::languages::deno::DenoRequest;
 use crate::languages::deno::installer::{DenoInstaller, DenoResult, bin_dir};
 use crate::languages::version::LanguageRequest;
+use crate::languages::{LanguageBackend, ToolchainDownload};
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 fn is_valid_install_name(name: &str) -> bool {
@@ -174,6 +173,35 @@ impl LanguageBackend for Deno {
         Ok(())
     }
 
+    async fn toolchain_download(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> Result<Option<ToolchainDownload>> {
+        let request = match &hook.language_request {
+            LanguageRequest::Any { system_only: true } => return Ok(None),
+            LanguageRequest::Any { system_only: false } => &DenoRequest::Any,
+            LanguageRequest::Deno(request) => request,
+            _ => unreachable!(),
+        };
+        DenoInstaller::new(store.tools_path(ToolBucket::Deno))
+            .toolchain_download(request)
+            .await
+    }
+
+    async fn toolchain_version(&self, store: &Store, hook: &Hook) -> Result<Option<String>> {
+        let (request, allows_download) = match &hook.language_request {
+            LanguageRequest::Any { system_only } => (&DenoRequest::Any, !system_only),
+            LanguageRequest::Deno(request) => (request, true),
+            _ => unreachable!(),
+        };
+        let deno = DenoInstaller::new(store.tools_path(ToolBucket::Deno))
+            .install(store, request, allows_download)
+            .await
+            .context("Failed to install deno")?;
+        Ok(Some(deno.version().to_string()))
+    }
+
     async fn run(
         &self,
         store: &Store,
@@ -181,8 +209,6 @@ impl LanguageBackend for Deno {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let deno_cache_dir = store.cache_path(CacheBucket::Deno);
         let info = hook.install_info().expect("Deno must be installed");
         let env_dir = &info.env_path;
@@ -192,32 +218,12 @@ impl LanguageBackend for Deno {
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
 
-        let run = async |batch: &[&Path]| {
-            let mut cmd = Cmd::new(&entry[0]);
-            let output = cmd
-                .current_dir(hook.work_dir())
-                .env(EnvVars::PATH, &new_path)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
                 .env(EnvVars::DENO_DIR, &deno_cache_dir)
-                .env(EnvVars::DENO_NO_UPDATE_CHECK, "1")
-                .envs(&hook.env)
-                .args(&entry[1..])
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+                .env(EnvVars::DENO_NO_UPDATE_CHECK, "1");
+        })
+        .await
     }
 }
 
@@ -15,6 +15,7 @@ use crate::archive;
 use crate::checksum::{Sha256Digest, digest_from_sha256sums};
 use crate::fs::LockedFile;
 use crate::http::{REQWEST_CLIENT, download_artifact};
+use crate::languages::ToolchainDownload;
 use crate::languages::deno::DenoRequest;
 use crate::languages::deno::version::DenoVersion;
 use crate::process::Cmd;
@@ -121,6 +122,29 @@ impl DenoInstaller {
         self.download(store, &resolved_version).await
     }
 
+    /// The deno `install` would download for `request`, or `None` if an installed or system
+    /// deno matches it.
+    pub(crate) async fn toolchain_download(
+        &self,
+        request: &DenoRequest,
+    ) -> Result<Option<ToolchainDownload>> {
+        if self.find_installed(request).is_ok() || self.find_system_deno(request).await?.is_some() {
+            return Ok(None);
+        }
+
+        let download = match self.resolve_version(request).await {
+            Ok(version) => {
+                let (_, url) = Self::download_url(&version)?;
+                ToolchainDownload::resolved(&version, url).await
+            }
+            Err(err) => {
+                debug!("Failed to resolve deno version: {err:#}");
+                ToolchainDownload::unresolved()
+            }
+        };
+        Ok(Some(download))
+    }
+
     /// Get the installed version of Deno.
     fn find_installed(&self, req: &DenoRequest) -> Result<DenoResult> {
         let mut installed = fs_err::read_dir(&self.root)
@@ -194,8 +218,8 @@ impl DenoInstaller {
         Ok(versions)
     }
 
-    /// Install a specific version of Deno.
-    async fn download(&self, store: &Store, version: &DenoVersion) -> Result<DenoResult> {
+    /// The archive filename and URL of a Deno release for the host platform.
+    fn download_url(version: &DenoVersion) -> Result<(String, String)> {
         let arch = match HOST.architecture {
             Architecture::X86_64 => "x86_64",
             Architecture::Aarch64(_) => "aarch64",
@@ -211,6 +235,12 @@ impl DenoInstaller {
 
         let filename = format!("deno-{arch}-{os}.zip");
         let url = format!("https://dl.deno.land/release/v{version}/{filename}");
+        Ok((filename, url))
+    }
+
+    /// Install a specific version of Deno.
+    async fn download(&self, store: &Store, version: &DenoVersion) -> Result<DenoResult> {
+        let (filename, url) = Self::download_url(version)?;
         let checksum_url = format!("{url}.sha256sum");
         let target = self.root.join(version.to_string());
 
@@ -317,6 +317,55 @@ impl ContainerRuntimeInfo {
 static CONTAINER_RUNTIME: LazyLock<ContainerRuntimeInfo> =
     LazyLock::new(ContainerRuntimeInfo::detect_runtime);
 
+/// The path where the project is mounted inside the container.
+const CONTAINER_WORK_DIR: &str = "/src";
+/// The path where the hook repository is mounted (read-only) inside the container.
+const CONTAINER_REPO_DIR: &str = "/prek/repo";
+/// The path where the hook environment is mounted (read-only) inside the container.
+const CONTAINER_ENV_DIR: &str = "/prek/env";
+
+/// A bind mount passed to the container runtime.
+struct Volume<'a> {
+    source: Cow<'a, Path>,
+    target: &'static str,
+    read_only: bool,
+}
+
+impl Volume<'_> {
+    fn to_arg(&self) -> String {
+        let options = if self.read_only { "ro,z" } else { "rw,Z" };
+        format!("{}:{}:{options}", self.source.display(), self.target)
+    }
+}
+
+/// The build argument that receives a hook's `additional_dependencies`, space separated.
+const DEPENDENCIES_BUILD_ARG: &str = "PREK_DEPS";
+
+/// How the container user is chosen, controlled by `PREK_DOCKER_USER`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UserMapping {
+    /// Map to the current user on the host (the default).
+    Auto,
+    /// Do not pass `--user`, run as the image's default user.
+    None,
+    /// Run as the given `uid[:gid]` (or user name).
+    Explicit(String),
+}
+
+impl UserMapping {
+    fn from_env(env_vars: &impl EnvVarsRead) -> Self {
+        let Ok(value) = env_vars.var(EnvVars::PREK_DOCKER_USER) else {
+            return Self::Auto;
+        };
+        let value = value.trim();
+        match value.to_ascii_lowercase().as_str() {
+            "" | "auto" => Self::Auto,
+            "none" | "image" => Self::None,
+            _ => Self::Explicit(value.to_string()),
+        }
+    }
+}
+
 impl Docker {
     fn docker_tag(info: &InstallInfo) -> String {
         let mut hasher = DefaultHasher::new();
@@ -324,6 +373,7 @@ impl Docker {
         info.language.hash(&mut hasher);
         info.language_version.hash(&mut hasher);
         info.repo().hash(&mut hasher);
+        // Dependencies are passed as a build argument, so they must select a distinct image.
         info.dependencies.hash(&mut hasher);
 
         let digest = hex::encode(hasher.finish().to_le_bytes());
@@ -354,6 +404,13 @@ impl Docker {
             .arg("--label")
             .arg("prek.managed=true");
 
+        if !install_info.dependencies.is_empty() {
+            cmd.arg("--build-arg").arg(format!(
+                "{DEPENDENCIES_BUILD_ARG}={}",
+                install_info.dependencies.join(" ")
+            ));
+        }
+
         // Always attempt to pull all referenced images.
         if pull {
             cmd.arg("--pull");
@@ -368,11 +425,16 @@ impl Docker {
         Ok(tag)
     }
 
-    pub(crate) fn docker_run_cmd(work_dir: &Path) -> Cmd {
-        Self::docker_run_cmd_with_env(work_dir, &EnvVars)
+    /// The command of the detected container runtime, such as `docker` or `podman`.
+    pub(crate) fn runtime_cmd() -> &'static str {
+        CONTAINER_RUNTIME.cmd()
+    }
+
+    pub(crate) fn docker_run_cmd(hook: &InstalledHook) -> Cmd {
+        Self::docker_run_cmd_with_env(hook, &EnvVars)
     }
 
-    fn docker_run_cmd_with_env(work_dir: &Path, env_vars: &impl EnvVarsRead) -> Cmd {
+    fn docker_run_cmd_with_env(hook: &InstalledHook, env_vars: &impl EnvVarsRead) -> Cmd {
         let mut command = Cmd::new(CONTAINER_RUNTIME.cmd());
         command.arg("run").arg("--rm");
 
@@ -380,7 +442,30 @@ impl Docker {
             command.arg("--tty");
         }
 
-        // Run as a non-root user
+        match UserMapping::from_env(env_vars) {
+            UserMapping::Auto => Self::add_current_user_args(&mut command),
+            UserMapping::Explicit(user) => {
+                command.arg("--user").arg(user);
+            }
+            UserMapping::None => {}
+        }
+
+        if Self::should_add_init(env_vars) {
+            // Run an init inside the container that forwards signals and reaps processes
+            command.arg("--init");
+        }
+
+        for volume in Self::volumes(hook) {
+            command.arg("--volume").arg(volume.to_arg());
+        }
+        command.arg("--workdir").arg(CONTAINER_WORK_DIR);
+
+        command
+    }
+
+    /// Run as a non-root user matching the current user.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    pub(crate) fn add_current_user_args(command: &mut Cmd) {
         #[cfg(unix)]
         {
             let add_user_args = |cmd: &mut Cmd| {
@@ -391,11 +476,11 @@ impl Docker {
 
             // If runtime is rootful, set user to non-root user id matching current user id.
             if !CONTAINER_RUNTIME.is_rootless() {
-                add_user_args(&mut command);
+                add_user_args(command);
             } else if CONTAINER_RUNTIME.is_podman() {
                 // For rootless podman, set user to non-root use id matching
                 // current user id and add additional `--userns` param to map the user id correctly.
-                add_user_args(&mut command);
+                add_user_args(command);
                 command.arg("--userns").arg("keep-id");
             }
 
@@ -403,24 +488,60 @@ impl Docker {
             // problems with bind mounted files.  In this state, `root:root` inside the container is
             // the same as current `uid:gid` on the host - see subuid / subgid.
         }
+    }
 
+    /// Collect the bind mounts for running a hook.
+    ///
+    /// The hook's [run directory](Hook::run_dir), usually the project, is mounted read-write at
+    /// `/src`, while the hook repository and its environment in the store are mounted read-only.
+    fn volumes(hook: &InstalledHook) -> Vec<Volume<'_>> {
         // https://docs.docker.com/reference/cli/docker/container/run/#volumes-from
         // The `Z` option tells Docker to label the content with a private
         // unshared label. Only the current container can use a private volume.
-        let work_dir = CONTAINER_RUNTIME.map_to_host_path(work_dir);
-        let volume = format!("{}:/src:rw,Z", work_dir.display());
-
-        if Self::should_add_init(env_vars) {
-            // Run an init inside the container that forwards signals and reaps processes
-            command.arg("--init");
+        // Store paths are shared between concurrently running containers, so they
+        // use the shared `z` label instead.
+        let mut volumes = vec![Volume {
+            source: CONTAINER_RUNTIME.map_to_host_path(hook.run_dir()),
+            target: CONTAINER_WORK_DIR,
+            read_only: false,
+        }];
+        if let Some(repo_path) = hook.repo_path() {
+            volumes.push(Volume {
+                source: CONTAINER_RUNTIME.map_to_host_path(repo_path),
+                target: CONTAINER_REPO_DIR,
+                read_only: true,
+            });
         }
-        command
-            .arg("--volume")
-            .arg(volume)
-            .arg("--workdir")
-            .arg("/src");
+        if let Some(env_path) = hook.env_path() {
+            volumes.push(Volume {
+                source: CONTAINER_RUNTIME.map_to_host_path(env_path),
+                target: CONTAINER_ENV_DIR,
+                read_only: true,
+            });
+        }
+        volumes
+    }
 
-        command
+    /// Translate a filename into a path usable inside the container.
+    ///
+    /// Filenames are relative to the hook working directory, which is mounted at `/src`,
+    /// so they are passed as relative paths with `/` separators. Absolute paths under
+    /// the working directory are made relative, other paths are passed through unchanged.
+    pub(crate) fn container_path(work_dir: &Path, path: &Path) -> PathBuf {
+        let path = if path.is_absolute() {
+            match path.strip_prefix(work_dir) {
+                Ok(relative) => relative,
+                Err(_) => return path.to_path_buf(),
+            }
+        } else {
+            path
+        };
+
+        if cfg!(windows) {
+            PathBuf::from(path.to_string_lossy().replace('\\', "/"))
+        } else {
+            path.to_path_buf()
+        }
     }
 
     fn should_add_init(env_vars: &impl EnvVarsRead) -> bool {
@@ -497,16 +618,20 @@ impl LanguageBackend for Docker {
 
         let run = async |batch: &[&Path]| {
             // docker run [OPTIONS] IMAGE [COMMAND] [ARG...]
-            let mut cmd = Docker::docker_run_cmd(hook.work_dir());
+            let mut cmd = Docker::docker_run_cmd(hook);
             let output = cmd
-                .current_dir(hook.work_dir())
+                .current_dir(hook.run_dir())
                 .args(&env_args)
                 .arg("--entrypoint")
                 .arg(&entry[0])
                 .arg(&docker_tag)
                 .args(&entry[1..])
                 .args(&hook.args)
-                .file_args(batch)
+                .file_args(
+                    batch
+                        .iter()
+                        .map(|file| Docker::container_path(hook.run_dir(), file)),
+                )
                 .check(false)
                 .stdin(Stdio::null())
                 .output_with_sink(reporter.output_sink(progress))
@@ -691,6 +816,86 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_user_mapping_from_env() {
+        fn mapping(value: &str) -> UserMapping {
+            UserMapping::from_env(&EnvVars::from_map(&[(EnvVars::PREK_DOCKER_USER, value)]))
+        }
+
+        assert_eq!(
+            UserMapping::from_env(&EnvVars::from_map(&[])),
+            UserMapping::Auto
+        );
+        assert_eq!(mapping(""), UserMapping::Auto);
+        assert_eq!(mapping("AUTO"), UserMapping::Auto);
+        assert_eq!(mapping("none"), UserMapping::None);
+        assert_eq!(
+            mapping(" 1000:1000 "),
+            UserMapping::Explicit("1000:1000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_volume_to_arg() {
+        let volume = Volume {
+            source: Cow::Borrowed(Path::new("/home/user/project")),
+            target: CONTAINER_WORK_DIR,
+            read_only: false,
+        };
+        assert_eq!(volume.to_arg(), "/home/user/project:/src:rw,Z");
+
+        let volume = Volume {
+            source: Cow::Borrowed(Path::new("/home/user/.cache/prek/repos/abc")),
+            target: CONTAINER_REPO_DIR,
+            read_only: true,
+        };
+        assert_eq!(
+            volume.to_arg(),
+            "/home/user/.cache/prek/repos/abc:/prek/repo:ro,z"
+        );
+    }
+
+    #[test]
+    fn test_docker_tag_includes_dependencies() -> anyhow::Result<()> {
+        use crate::config::Language;
+
+        let hooks_dir = tempfile::tempdir()?;
+        let tag = |dependencies: &[&str]| -> anyhow::Result<String> {
+            let info = InstallInfo::create(
+                Language::Docker,
+                None,
+                dependencies.iter().map(ToString::to_string).collect(),
+                hooks_dir.path(),
+            )?;
+            Ok(Docker::docker_tag(&info))
+        };
+
+        assert_eq!(tag(&[])?, tag(&[])?);
+        assert_eq!(tag(&["ruff==0.6.0"])?, tag(&["ruff==0.6.0"])?);
+        assert_ne!(tag(&[])?, tag(&["ruff==0.6.0"])?);
+        assert_ne!(tag(&["ruff==0.6.0"])?, tag(&["ruff==0.7.0"])?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_container_path() {
+        let work_dir = Path::new("/home/user/project");
+        assert_eq!(
+            Docker::container_path(work_dir, Path::new("src/main.py")),
+            PathBuf::from("src/main.py")
+        );
+        assert_eq!(
+            Docker::container_path(work_dir, Path::new("/home/user/project/src/main.py")),
+            PathBuf::from("src/main.py")
+        );
+        assert_eq!(
+            Docker::container_path(work_dir, Path::new("/etc/hosts")),
+            PathBuf::from("/etc/hosts")
+        );
+    }
+
     #[test]
     fn test_detect_container_runtime() {
         fn runtime_with(
@@ -48,13 +48,17 @@ impl LanguageBackend for DockerImage {
 
         let entry = hook.entry.expect_direct().split()?;
         let run = async |batch: &[&Path]| {
-            let mut cmd = Docker::docker_run_cmd(hook.work_dir());
+            let mut cmd = Docker::docker_run_cmd(hook);
             let output = cmd
-                .current_dir(hook.work_dir())
+                .current_dir(hook.run_dir())
                 .args(&env_args)
                 .args(&entry[..])
                 .args(&hook.args)
-                .file_args(batch)
+                .file_args(
+                    batch
+                        .iter()
+                        .map(|file| Docker::container_path(hook.run_dir(), file)),
+                )
                 .check(false)
                 .stdin(Stdio::null())
                 .output_with_sink(reporter.output_sink(progress))
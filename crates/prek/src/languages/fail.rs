@@ -35,14 +35,59 @@ impl LanguageBackend for Fail {
         filenames: &[&Path],
         _reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
+        let entry = hook.entry.expect_direct().raw();
         let mut out = Vec::new();
-        writeln!(out, "{}\n", hook.entry.expect_direct().raw())?;
-        for f in filenames {
-            out.extend(f.to_string_lossy().as_bytes());
+        writeln!(out, "{}\n", render_message(entry, filenames))?;
+        // Files named inline by the message are not listed again.
+        if !entry.contains(FILES_PLACEHOLDER) {
+            for f in filenames {
+                out.extend(f.to_string_lossy().as_bytes());
+                out.push(b'\n');
+            }
             out.push(b'\n');
         }
-        out.push(b'\n');
+        if let Some(hint) = &hook.pattern_hint {
+            writeln!(out, "hint: {hint}")?;
+        }
 
         Ok((1, out))
     }
 }
+
+const FILES_PLACEHOLDER: &str = "{files}";
+const COUNT_PLACEHOLDER: &str = "{count}";
+
+/// Render the `entry` of a `fail` hook, replacing `{files}` with the matching files and
+/// `{count}` with their number.
+fn render_message(entry: &str, filenames: &[&Path]) -> String {
+    let mut message = entry.replace(COUNT_PLACEHOLDER, &filenames.len().to_string());
+    if message.contains(FILES_PLACEHOLDER) {
+        let files = filenames
+            .iter()
+            .map(|f| f.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(", ");
+        message = message.replace(FILES_PLACEHOLDER, &files);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::render_message;
+
+    #[test]
+    fn render_message_placeholders() {
+        let files = [Path::new("a.rej"), Path::new("dir/b.rej")];
+        assert_eq!(
+            render_message("Found {count} rejected patches: {files}", &files),
+            "Found 2 rejected patches: a.rej, dir/b.rej"
+        );
+        assert_eq!(
+            render_message("Remove these files", &files),
+            "Remove these files"
+        );
+    }
+}
@@ -1,6 +1,6 @@
+use std::ffi::{OsStr, OsString};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -11,17 +11,27 @@ use crate::cli::reporter::HookInstallReporter;
 use crate::cli::run::HookRunReporter;
 use crate::git::GitCommandExt;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
-use crate::languages::LanguageBackend;
 use crate::languages::golang::GoRequest;
 use crate::languages::golang::installer::GoInstaller;
 use crate::languages::version::LanguageRequest;
-use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::languages::{LanguageBackend, ToolchainDownload};
+use crate::run::run_hook_entry;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Golang;
 
+/// The install info key of the `<module>@<version>` a `go run` hook runs, with the version
+/// resolved at install time.
+const GO_RUN_MODULE_KEY: &str = "go_run_module";
+
+/// The part of the `go mod download -json` output prek needs.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ModuleDownload {
+    version: String,
+}
+
 #[async_trait::async_trait(?Send)]
 impl LanguageBackend for Golang {
     async fn install(
@@ -51,7 +61,9 @@ impl LanguageBackend for Golang {
             .with_language_version(go.version().deref().clone());
 
         // 2. Create environment
-        fs_err::tokio::create_dir_all(bin_dir(&info.env_path)).await?;
+        if hook.go_run_module.is_none() {
+            fs_err::tokio::create_dir_all(bin_dir(&info.env_path)).await?;
+        }
 
         // 3. Install dependencies
         // go: ~/.cache/prek/tools/go/1.24.0/bin/go
@@ -65,16 +77,16 @@ impl LanguageBackend for Golang {
             .expect("Go root should exist");
         let go_cache = store.cache_path(CacheBucket::Go);
 
-        let go_install_cmd = || {
+        let go_cmd = |subcommand: &[&str]| {
             if go.is_from_system() {
                 let mut cmd = go.cmd();
-                cmd.arg("install")
+                cmd.args(subcommand)
                     .env(EnvVars::GOTOOLCHAIN, "local")
                     .env(EnvVars::GOBIN, bin_dir(&info.env_path));
                 cmd
             } else {
                 let mut cmd = go.cmd();
-                cmd.arg("install")
+                cmd.args(subcommand)
                     .env(EnvVars::GOTOOLCHAIN, "local")
                     .env(EnvVars::GOROOT, go_root)
                     .env(EnvVars::GOBIN, bin_dir(&info.env_path))
@@ -84,9 +96,31 @@ impl LanguageBackend for Golang {
             }
         };
 
+        // `go run <module>@<version>` builds the module from the shared module cache on each run,
+        // so only download it once here instead of installing it into the environment.
+        let mut pinned_module = None;
+        if let Some(module) = &hook.go_run_module {
+            let output = go_cmd(&["mod", "download", "-json"])
+                .arg(module)
+                // Outside of any module, so the project's `go.mod` is not involved.
+                .current_dir(&info.env_path)
+                .isolate_from_git_env()
+                .check(true)
+                .output()
+                .await?;
+            let download: ModuleDownload = serde_json::from_slice(&output.stdout)
+                .context("Failed to parse the output of `go mod download`")?;
+            // Pin the version a query like `@latest` resolved to, so every run builds the
+            // downloaded version instead of resolving the query again.
+            let (path, _) = module
+                .split_once('@')
+                .expect("`go run` module must have a version");
+            pinned_module = Some(format!("{path}@{}", download.version));
+        }
+
         // GOPATH used to store downloaded source code (in $GOPATH/pkg/mod)
         if let Some(repo) = hook.repo_path() {
-            go_install_cmd()
+            go_cmd(&["install"])
                 .arg("./...")
                 .current_dir(repo)
                 .isolate_from_git_env()
@@ -95,7 +129,7 @@ impl LanguageBackend for Golang {
                 .await?;
         }
         for dep in &hook.additional_dependencies {
-            let mut cmd = go_install_cmd();
+            let mut cmd = go_cmd(&["install"]);
             if let Some(repo) = hook.repo_path() {
                 cmd.current_dir(repo);
             }
@@ -106,6 +140,21 @@ impl LanguageBackend for Golang {
                 .await?;
         }
 
+        // Recorded so `run` doesn't have to work out again whether Go is managed by prek.
+        let go_bin = bin_dir(&info.env_path);
+        info.with_env(EnvVars::GOBIN, &go_bin, store);
+        if !go.is_from_system() {
+            info.with_env(EnvVars::GOROOT, go_root, store).with_env(
+                EnvVars::GOPATH,
+                &go_cache,
+                store,
+            );
+        }
+
+        if let Some(module) = pinned_module {
+            info.with_extra(GO_RUN_MODULE_KEY, &module);
+        }
+
         info.persist_env_path();
 
         reporter.on_install_complete(progress);
@@ -120,6 +169,39 @@ impl LanguageBackend for Golang {
         Ok(())
     }
 
+    async fn toolchain_download(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> anyhow::Result<Option<ToolchainDownload>> {
+        let request = match &hook.language_request {
+            LanguageRequest::Any { system_only: true } => return Ok(None),
+            LanguageRequest::Any { system_only: false } => &GoRequest::Any,
+            LanguageRequest::Golang(request) => request,
+            _ => unreachable!(),
+        };
+        GoInstaller::new(store.tools_path(ToolBucket::Go))
+            .toolchain_download(request)
+            .await
+    }
+
+    async fn toolchain_version(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> anyhow::Result<Option<String>> {
+        let (request, allows_download) = match &hook.language_request {
+            LanguageRequest::Any { system_only } => (&GoRequest::Any, !system_only),
+            LanguageRequest::Golang(request) => (request, true),
+            _ => unreachable!(),
+        };
+        let go = GoInstaller::new(store.tools_path(ToolBucket::Go))
+            .install(store, request, allows_download)
+            .await
+            .context("Failed to install go")?;
+        Ok(Some(go.version().to_string()))
+    }
+
     async fn run(
         &self,
         store: &Store,
@@ -127,55 +209,89 @@ impl LanguageBackend for Golang {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> anyhow::Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Node hook must have env path");
 
         let go_bin = bin_dir(env_dir);
-        let go_tools = store.tools_path(ToolBucket::Go);
         let go_root_bin = hook.toolchain_dir().expect("Go root should exist");
-        let go_root = go_root_bin.parent().expect("Go root should exist");
-        let go_cache = store.cache_path(CacheBucket::Go);
+        let new_path = prepend_paths(&[&go_bin, go_root_bin]).context("Failed to join PATH")?;
 
-        // Only set GOROOT and GOPATH if using the Go installed by prek
-        let go_envs = if go_root_bin.starts_with(go_tools) {
-            vec![(EnvVars::GOROOT, go_root), (EnvVars::GOPATH, &go_cache)]
-        } else {
-            vec![]
+        // Environments installed by older versions don't record `GOBIN`, `GOROOT` and `GOPATH`.
+        let go_envs = match hook.install_info() {
+            Some(info) if info.has_env() => info.env(store).collect(),
+            _ => legacy_run_envs(store, &go_bin, go_root_bin),
         };
-        let new_path = prepend_paths(&[&go_bin, go_root_bin]).context("Failed to join PATH")?;
 
-        let entry = hook.entry.resolve(Some(&new_path), store)?;
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
+        let mut entry = hook.entry.resolve(Some(&new_path), store)?;
+        if let Some(module) = &hook.go_run_module
+            && let Some(pinned) = hook
+                .install_info()
+                .and_then(|info| info.get_extra(GO_RUN_MODULE_KEY))
+        {
+            for arg in entry.argv_mut() {
+                if arg.as_os_str() == OsStr::new(module) {
+                    *arg = OsString::from(pinned);
+                }
+            }
+        }
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
                 .env(EnvVars::GOTOOLCHAIN, "local")
-                .env(EnvVars::GOBIN, &go_bin)
                 .env(EnvVars::GOFLAGS, "-modcacherw")
-                .envs(go_envs.iter().copied())
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
+                .envs(go_envs.iter().map(|(key, value)| (*key, value)));
+        })
+        .await
+    }
+}
 
-        reporter.on_run_complete(progress);
+fn legacy_run_envs(
+    store: &Store,
+    go_bin: &Path,
+    go_root_bin: &Path,
+) -> Vec<(&'static str, PathBuf)> {
+    let mut envs = vec![(EnvVars::GOBIN, go_bin.to_path_buf())];
+    // Only set GOROOT and GOPATH if using the Go installed by prek
+    if go_root_bin.starts_with(store.tools_path(ToolBucket::Go)) {
+        let go_root = go_root_bin.parent().expect("Go root should exist");
+        envs.push((EnvVars::GOROOT, go_root.to_path_buf()));
+        envs.push((EnvVars::GOPATH, store.cache_path(CacheBucket::Go)));
+    }
+    envs
+}
 
-        Ok(output)
+/// The `<module>@<version>` run by an entry like `go run github.com/foo/bar@v1.2.3`.
+pub(crate) fn go_run_module(entry: &str) -> Option<&str> {
+    let mut args = entry.split_whitespace();
+    if args.next()? != "go" || args.next()? != "run" {
+        return None;
     }
+    // Flags taking a separate value are not recognized, the hook is then installed as usual.
+    let target = args.find(|arg| !arg.starts_with('-'))?;
+    let (module, version) = target.split_once('@')?;
+    (!module.is_empty() && !module.starts_with('.') && !version.is_empty()).then_some(target)
 }
 
 pub(crate) fn bin_dir(env_path: &Path) -> PathBuf {
     env_path.join("bin")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn go_run_module_from_entry() {
+        assert_eq!(
+            go_run_module("go run github.com/foo/bar@v1.2.3"),
+            Some("github.com/foo/bar@v1.2.3")
+        );
+        assert_eq!(
+            go_run_module("go run -mod=mod github.com/foo/bar/cmd/bar@latest --fix"),
+            Some("github.com/foo/bar/cmd/bar@latest")
+        );
+        assert_eq!(go_run_module("go run ./cmd/bar"), None);
+        assert_eq!(go_run_module("go run github.com/foo/bar"), None);
+        assert_eq!(go_run_module("go run ./cmd@v1"), None);
+        assert_eq!(go_run_module("go build github.com/foo/bar@v1.2.3"), None);
+        assert_eq!(go_run_module("gofumpt -l"), None);
+    }
+}
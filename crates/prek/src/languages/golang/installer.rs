@@ -16,9 +16,11 @@ use crate::checksum::Sha256Digest;
 use crate::fs::LockedFile;
 use crate::git;
 use crate::http::{REQWEST_CLIENT, download_artifact};
+use crate::languages::ToolchainDownload;
 use crate::languages::golang::GoRequest;
 use crate::languages::golang::golang::bin_dir;
 use crate::languages::golang::version::GoVersion;
+use crate::languages::version_manager::{self, Tool};
 use crate::process::Cmd;
 use crate::store::Store;
 
@@ -135,6 +137,13 @@ impl GoInstaller {
             return Ok(go);
         }
 
+        if version_manager::prefer_system_toolchains()
+            && let Some(go) = Self::find_version_manager_go(request).await
+        {
+            trace!(%go, "Using go from version manager");
+            return Ok(go);
+        }
+
         if !allows_download {
             anyhow::bail!("No suitable system Go version found and downloads are disabled");
         }
@@ -148,6 +157,34 @@ impl GoInstaller {
         self.download(store, &resolved_version).await
     }
 
+    /// The go `install` would download for `request`, or `None` if an installed or system go
+    /// matches it.
+    pub(crate) async fn toolchain_download(
+        &self,
+        request: &GoRequest,
+    ) -> Result<Option<ToolchainDownload>> {
+        if self.find_installed(request).is_ok() || self.find_system_go(request).await?.is_some() {
+            return Ok(None);
+        }
+        if version_manager::prefer_system_toolchains()
+            && Self::find_version_manager_go(request).await.is_some()
+        {
+            return Ok(None);
+        }
+
+        let download = match self.resolve_version(request).await {
+            Ok(version) => {
+                let (_, url) = Self::download_url(&version)?;
+                ToolchainDownload::resolved(&version, url).await
+            }
+            Err(err) => {
+                debug!("Failed to resolve go version `{request}`: {err:#}");
+                ToolchainDownload::unresolved()
+            }
+        };
+        Ok(Some(download))
+    }
+
     fn find_installed(&self, request: &GoRequest) -> Result<GoResult> {
         let mut installed = fs_err::read_dir(&self.root)
             .ok()
@@ -204,7 +241,8 @@ impl GoInstaller {
         Ok(version)
     }
 
-    async fn download(&self, store: &Store, version: &GoVersion) -> Result<GoResult> {
+    /// The archive filename and URL of a go release for the host platform.
+    fn download_url(version: &GoVersion) -> Result<(String, String)> {
         let arch = match HOST.architecture {
             Architecture::X86_32(_) => "386",
             Architecture::X86_64 => "amd64",
@@ -230,6 +268,11 @@ impl GoInstaller {
         let ext = if cfg!(windows) { "zip" } else { "tar.gz" };
         let filename = format!("go{version}.{os}-{arch}.{ext}");
         let url = format!("https://go.dev/dl/{filename}");
+        Ok((filename, url))
+    }
+
+    async fn download(&self, store: &Store, version: &GoVersion) -> Result<GoResult> {
+        let (filename, url) = Self::download_url(version)?;
         let checksum_version = version.to_string();
         let target = self.root.join(version.to_string());
 
@@ -307,6 +350,26 @@ impl GoInstaller {
         debug!(?go_request, "No system go matches the requested version");
         Ok(None)
     }
+
+    /// Find a matching Go installed by a version manager, even if it is not active.
+    async fn find_version_manager_go(go_request: &GoRequest) -> Option<GoResult> {
+        for install in version_manager::find_installs(Tool::Go).await {
+            // asdf-golang nests the toolchain in a `go` directory.
+            let go_path = [install.join("go"), install]
+                .iter()
+                .map(|root| bin_dir(root).join("go").with_extension(EXE_EXTENSION))
+                .find(|path| path.is_file());
+            let Some(go_path) = go_path else {
+                continue;
+            };
+            match GoResult::from_system_executable(go_path).await {
+                Ok(go) if go_request.matches(&go.version) => return Some(go),
+                Ok(go) => trace!(%go, "Version manager go does not match requested version"),
+                Err(e) => warn!(?e, "Failed to get version for version manager go"),
+            }
+        }
+        None
+    }
 }
 
 fn digest_from_go_releases(
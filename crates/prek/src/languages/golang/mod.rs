@@ -4,6 +4,6 @@ mod gomod;
 mod installer;
 mod version;
 
-pub(crate) use golang::Golang;
+pub(crate) use golang::{Golang, go_run_module};
 pub(crate) use gomod::extract_go_mod_metadata;
 pub(crate) use version::GoRequest;
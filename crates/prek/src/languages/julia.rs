@@ -1,5 +1,5 @@
+use std::ffi::OsString;
 use std::path::Path;
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -10,7 +10,7 @@ use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageBackend;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -102,8 +102,6 @@ impl LanguageBackend for Julia {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Julia must have env path");
 
         let mut entry = hook.entry.expect_direct().split()?;
@@ -113,30 +111,13 @@ impl LanguageBackend for Julia {
                 entry[0] = jl_path.into_os_string();
             }
         }
-
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new("julia")
-                .current_dir(hook.work_dir())
-                .arg("--startup-file=no")
-                .arg(format!("--project={}", env_dir.display()))
-                .args(&entry)
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, &entry, run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        let mut argv = vec![
+            OsString::from("julia"),
+            OsString::from("--startup-file=no"),
+            OsString::from(format!("--project={}", env_dir.display())),
+        ];
+        argv.extend(entry);
+
+        run_hook_entry(hook, &argv, filenames, reporter, |_| {}).await
     }
 }
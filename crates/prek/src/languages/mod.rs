@@ -1,6 +1,6 @@
 use std::ffi::{OsStr, OsString};
 use std::future::Future;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -23,7 +23,7 @@ mod conda;
 mod coursier;
 mod dart;
 mod deno;
-mod docker;
+pub(crate) mod docker;
 mod docker_image;
 mod dotnet;
 mod fail;
@@ -43,6 +43,7 @@ mod script;
 mod swift;
 mod system;
 pub(crate) mod version;
+mod version_manager;
 
 // Backend futures are awaited in place rather than spawned. Requiring `Send` here would impose a
 // stronger contract than callers need and rejects the borrowed async closures used by backends.
@@ -57,6 +58,27 @@ trait LanguageBackend: Sync {
 
     async fn check_health(&self, info: &InstallInfo) -> Result<()>;
 
+    /// The toolchain `install` would download for `hook`.
+    ///
+    /// Returns `None` when an installed or system toolchain matches the hook, or when the
+    /// language never downloads toolchains. Nothing is installed, but resolving the version and
+    /// the archive size may query the download host.
+    async fn toolchain_download(
+        &self,
+        _store: &Store,
+        _hook: &Hook,
+    ) -> Result<Option<ToolchainDownload>> {
+        Ok(None)
+    }
+
+    /// Find or install the toolchain `install` uses for `hook`, and return its version.
+    ///
+    /// Returns `None` for languages that do not resolve their toolchain up front; environments
+    /// of those languages are not shared through the remote cache.
+    async fn toolchain_version(&self, _store: &Store, _hook: &Hook) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     async fn run(
         &self,
         store: &Store,
@@ -68,6 +90,35 @@ trait LanguageBackend: Sync {
 
 type LanguageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + 'a>>;
 
+/// A toolchain archive that installing a hook would download.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub(crate) struct ToolchainDownload {
+    /// The version to download, `None` if the available versions could not be listed.
+    pub(crate) version: Option<String>,
+    pub(crate) url: Option<String>,
+    /// The archive size in bytes, if the download host reports it.
+    pub(crate) size: Option<u64>,
+}
+
+impl ToolchainDownload {
+    async fn resolved(version: &impl std::fmt::Display, url: String) -> Self {
+        let size = crate::http::content_length(&url).await;
+        Self {
+            version: Some(version.to_string()),
+            url: Some(url),
+            size,
+        }
+    }
+
+    fn unresolved() -> Self {
+        Self {
+            version: None,
+            url: None,
+            size: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ShellSupport {
     Supported,
@@ -245,6 +296,69 @@ impl Language {
         }
     }
 
+    /// Directories to prepend to `PATH` when running hook `setup` commands in an installed
+    /// environment: the environment's executables, then the toolchain's.
+    pub(crate) fn setup_paths(self, info: &InstallInfo) -> Vec<PathBuf> {
+        let env_bin = match self {
+            Self::Python | Self::Pygrep => python::bin_dir(&info.env_path),
+            Self::Node => node::bin_dir(&info.env_path),
+            Self::Ruby => ruby::gem_bin(&info.env_path),
+            Self::Coursier => info.env_path.clone(),
+            _ => info.env_path.join("bin"),
+        };
+
+        let mut paths = vec![env_bin];
+        if let Some(toolchain_bin) = info.toolchain.parent()
+            && !toolchain_bin.as_os_str().is_empty()
+        {
+            paths.push(toolchain_bin.to_path_buf());
+        }
+        paths
+    }
+
+    /// Environment variables to set (or unset, for `None`) when running hook `setup` commands
+    /// in an installed environment, matching what the language sets when running the hook.
+    pub(crate) fn setup_env(self, info: &InstallInfo) -> Vec<(&'static str, Option<OsString>)> {
+        let env_path = &info.env_path;
+        match self {
+            Self::Python | Self::Pygrep => vec![
+                (
+                    EnvVars::VIRTUAL_ENV,
+                    Some(env_path.clone().into_os_string()),
+                ),
+                (EnvVars::PYTHONHOME, None),
+            ],
+            Self::Node => vec![(
+                EnvVars::NODE_PATH,
+                Some(node::lib_dir(env_path).into_os_string()),
+            )],
+            Self::Ruby => vec![
+                (
+                    EnvVars::GEM_HOME,
+                    Some(ruby::gem_home(env_path).into_os_string()),
+                ),
+                (EnvVars::BUNDLE_IGNORE_CONFIG, Some("1".into())),
+                (EnvVars::GEM_PATH, None),
+                (EnvVars::BUNDLE_GEMFILE, None),
+            ],
+            // `GOPATH`, `GOBIN` and `GOROOT` are recorded in the install info.
+            Self::Golang => vec![
+                (EnvVars::GOTOOLCHAIN, Some("local".into())),
+                (EnvVars::GOFLAGS, Some("-modcacherw".into())),
+            ],
+            Self::Conda => vec![(
+                EnvVars::CONDA_PREFIX,
+                Some(env_path.clone().into_os_string()),
+            )],
+            Self::Bun => vec![(
+                EnvVars::BUN_INSTALL,
+                Some(env_path.clone().into_os_string()),
+            )],
+            Self::Dart => vec![(EnvVars::PUB_CACHE, Some(env_path.clone().into_os_string()))],
+            _ => vec![],
+        }
+    }
+
     /// Return whether the language allows specifying the version, e.g. we can install a specific
     /// requested language version.
     /// See <https://pre-commit.com/#overriding-language-version>
@@ -288,6 +402,7 @@ impl Language {
             | Self::Coursier
             | Self::Dart
             | Self::Deno
+            | Self::Docker
             | Self::Dotnet
             | Self::Golang
             | Self::Haskell
@@ -300,8 +415,7 @@ impl Language {
             | Self::R
             | Self::Ruby
             | Self::Rust => true,
-            Self::Docker
-            | Self::DockerImage
+            Self::DockerImage
             | Self::Fail
             | Self::Pygrep
             | Self::Script
@@ -323,6 +437,22 @@ impl Language {
         self.backend().check_health(info)
     }
 
+    pub(crate) fn toolchain_download<'a>(
+        &'a self,
+        store: &'a Store,
+        hook: &'a Hook,
+    ) -> LanguageFuture<'a, Option<ToolchainDownload>> {
+        self.backend().toolchain_download(store, hook)
+    }
+
+    pub(crate) fn toolchain_version<'a>(
+        &'a self,
+        store: &'a Store,
+        hook: &'a Hook,
+    ) -> LanguageFuture<'a, Option<String>> {
+        self.backend().toolchain_version(store, hook)
+    }
+
     pub(crate) fn run<'a, 'p>(
         &'a self,
         store: &'a Store,
@@ -391,6 +521,23 @@ pub(crate) async fn extract_metadata(hook: &mut Hook) -> Result<()> {
     }
 }
 
+/// The `<module>@<version>` that a local `golang` hook without `additional_dependencies` runs
+/// with `go run`.
+///
+/// Such hooks only install a Go toolchain: the module is built from the shared module cache, and
+/// keys the hook environment in place of `additional_dependencies`.
+pub(crate) fn go_run_module(
+    language: Language,
+    local: bool,
+    entry: &str,
+    additional_dependencies: &[String],
+) -> Option<String> {
+    if language != Language::Golang || !local || !additional_dependencies.is_empty() {
+        return None;
+    }
+    golang::go_run_module(entry).map(str::to_string)
+}
+
 /// Resolve the actual process invocation, honoring shebangs and PATH lookups.
 pub(crate) fn resolve_command(mut cmds: Vec<OsString>, paths: Option<&OsStr>) -> Vec<OsString> {
     let Some(candidate) = cmds.first() else {
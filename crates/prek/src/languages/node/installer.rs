@@ -8,15 +8,18 @@ use std::sync::LazyLock;
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
-use target_lexicon::{Architecture, HOST, OperatingSystem};
+use serde::Deserialize;
+use target_lexicon::{Architecture, Environment, HOST, OperatingSystem, Triple};
 use tracing::{debug, trace, warn};
 
 use crate::archive;
 use crate::checksum::{Sha256Digest, digest_from_sha256sums};
 use crate::fs::{LockedFile, is_executable};
 use crate::http::{REQWEST_CLIENT, download_artifact};
+use crate::languages::ToolchainDownload;
 use crate::languages::node::NodeRequest;
 use crate::languages::node::version::NodeVersion;
+use crate::languages::version_manager::{self, Tool};
 use crate::process::Cmd;
 use crate::store::Store;
 
@@ -82,6 +85,95 @@ pub(crate) async fn query_node_version(node: &Path) -> Result<NodeVersion> {
     serde_json::from_str(&output_str).context("Failed to parse node version")
 }
 
+/// Where the Node.js binaries for a platform are published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeDist {
+    /// Official builds from `nodejs.org`.
+    Official,
+    /// Community builds from `unofficial-builds.nodejs.org`, e.g. for musl libc.
+    Unofficial,
+}
+
+impl NodeDist {
+    fn base_url(self) -> &'static str {
+        match self {
+            Self::Official => "https://nodejs.org/dist",
+            Self::Unofficial => "https://unofficial-builds.nodejs.org/download/release",
+        }
+    }
+}
+
+/// The `{os}-{arch}` part of Node.js archive names, and where those archives are published.
+#[derive(Debug, PartialEq, Eq)]
+struct NodePlatform {
+    os: &'static str,
+    arch: &'static str,
+    dist: NodeDist,
+}
+
+impl NodePlatform {
+    fn from_triple(triple: &Triple) -> Result<Self> {
+        let os = match triple.operating_system {
+            OperatingSystem::Darwin(_) => "darwin",
+            OperatingSystem::Linux => "linux",
+            OperatingSystem::Windows => "win",
+            OperatingSystem::Aix => "aix",
+            OperatingSystem::Freebsd => anyhow::bail!(
+                "Node.js does not publish FreeBSD binaries, install Node.js with `pkg install node` instead"
+            ),
+            os => anyhow::bail!("Node.js does not publish binaries for `{os}`"),
+        };
+
+        if os == "linux" && triple.environment == Environment::Musl {
+            // Official builds link against glibc, musl builds are only published as unofficial builds.
+            let arch = match triple.architecture {
+                Architecture::X86_64 => "x64-musl",
+                Architecture::Aarch64(_) => "arm64-musl",
+                arch => anyhow::bail!("Node.js does not publish musl binaries for `{arch}`"),
+            };
+            return Ok(Self {
+                os,
+                arch,
+                dist: NodeDist::Unofficial,
+            });
+        }
+
+        let (arch, dist) = match triple.architecture {
+            Architecture::X86_32(_) => ("x86", NodeDist::Official),
+            Architecture::X86_64 => ("x64", NodeDist::Official),
+            Architecture::Aarch64(_) => ("arm64", NodeDist::Official),
+            Architecture::Arm(_) => ("armv7l", NodeDist::Official),
+            Architecture::S390x => ("s390x", NodeDist::Official),
+            Architecture::Powerpc => ("ppc64", NodeDist::Official),
+            Architecture::Powerpc64le => ("ppc64le", NodeDist::Official),
+            Architecture::Riscv64(_) if os == "linux" => ("riscv64", NodeDist::Unofficial),
+            Architecture::LoongArch64 if os == "linux" => ("loong64", NodeDist::Unofficial),
+            arch => anyhow::bail!("Node.js does not publish binaries for `{os}` on `{arch}`"),
+        };
+        Ok(Self { os, arch, dist })
+    }
+
+    /// The key used in the `files` list of the dist `index.json`.
+    fn files_key(&self) -> String {
+        format!("{}-{}", self.os, self.arch)
+    }
+}
+
+impl Display for NodePlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)
+    }
+}
+
+/// An entry of the dist `index.json`.
+#[derive(Deserialize)]
+struct DistIndexEntry {
+    #[serde(flatten)]
+    version: NodeVersion,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
 pub(crate) struct NodeInstaller {
     root: PathBuf,
 }
@@ -113,14 +205,51 @@ impl NodeInstaller {
             return Ok(node_result);
         }
 
+        if version_manager::prefer_system_toolchains()
+            && let Some(node_result) = Self::find_version_manager_node(request).await
+        {
+            trace!(%node_result, "Using node from version manager");
+            return Ok(node_result);
+        }
+
         if !allows_download {
             anyhow::bail!("No suitable system Node version found and downloads are disabled");
         }
 
-        let resolved_version = self.resolve_version(request).await?;
-        trace!(version = %resolved_version, "Downloading node");
+        let platform = NodePlatform::from_triple(&HOST)?;
+        let resolved_version = self.resolve_version(request, &platform).await?;
+        trace!(version = %resolved_version, %platform, "Downloading node");
 
-        self.download(store, &resolved_version).await
+        self.download(store, &resolved_version, &platform).await
+    }
+
+    /// The Node.js `install` would download for `request`, or `None` if an installed or system
+    /// node matches it.
+    pub(crate) async fn toolchain_download(
+        &self,
+        request: &NodeRequest,
+    ) -> Result<Option<ToolchainDownload>> {
+        if self.find_installed(request).is_ok() || self.find_system_node(request).await?.is_some() {
+            return Ok(None);
+        }
+        if version_manager::prefer_system_toolchains()
+            && Self::find_version_manager_node(request).await.is_some()
+        {
+            return Ok(None);
+        }
+
+        let platform = NodePlatform::from_triple(&HOST)?;
+        let download = match self.resolve_version(request, &platform).await {
+            Ok(version) => {
+                let (_, url) = Self::download_url(&version, &platform);
+                ToolchainDownload::resolved(version.version(), url).await
+            }
+            Err(err) => {
+                debug!("Failed to resolve node version: {err:#}");
+                ToolchainDownload::unresolved()
+            }
+        };
+        Ok(Some(download))
     }
 
     /// Get the installed version of Node.js.
@@ -156,46 +285,43 @@ impl NodeInstaller {
             .context("No installed node version matches the request")
     }
 
-    async fn resolve_version(&self, req: &NodeRequest) -> Result<NodeVersion> {
+    async fn resolve_version(
+        &self,
+        req: &NodeRequest,
+        platform: &NodePlatform,
+    ) -> Result<NodeVersion> {
         // Latest versions come first, so we can find the latest matching version.
         let versions = self
-            .list_remote_versions()
+            .list_remote_versions(platform)
             .await
             .context("Failed to list remote versions")?;
         let version = versions
             .into_iter()
             .find(|version| req.matches(version))
-            .context("Version not found on remote")?;
+            .with_context(|| {
+                format!("No Node.js build matching the request found for `{platform}`")
+            })?;
         Ok(version)
     }
 
-    /// List all versions of Node.js available on the Node.js website.
-    async fn list_remote_versions(&self) -> Result<Vec<NodeVersion>> {
-        let url = "https://nodejs.org/dist/index.json";
-        let versions: Vec<NodeVersion> = REQWEST_CLIENT.get(url).send().await?.json().await?;
+    /// List all versions of Node.js published for the platform.
+    async fn list_remote_versions(&self, platform: &NodePlatform) -> Result<Vec<NodeVersion>> {
+        let url = format!("{}/index.json", platform.dist.base_url());
+        let entries: Vec<DistIndexEntry> = REQWEST_CLIENT.get(&url).send().await?.json().await?;
+
+        // Unofficial builds are not published for every release and platform.
+        let files_key = platform.files_key();
+        let versions = entries
+            .into_iter()
+            .filter(|entry| platform.dist == NodeDist::Official || entry.files.contains(&files_key))
+            .map(|entry| entry.version)
+            .collect();
         Ok(versions)
     }
 
-    // TODO: support mirror?
-    /// Install a specific version of Node.js.
-    async fn download(&self, store: &Store, version: &NodeVersion) -> Result<NodeResult> {
-        let mut arch = match HOST.architecture {
-            Architecture::X86_32(_) => "x86",
-            Architecture::X86_64 => "x64",
-            Architecture::Aarch64(_) => "arm64",
-            Architecture::Arm(_) => "armv7l",
-            Architecture::S390x => "s390x",
-            Architecture::Powerpc => "ppc64",
-            Architecture::Powerpc64le => "ppc64le",
-            _ => anyhow::bail!("Unsupported architecture"),
-        };
-        let os = match HOST.operating_system {
-            OperatingSystem::Darwin(_) => "darwin",
-            OperatingSystem::Linux => "linux",
-            OperatingSystem::Windows => "win",
-            OperatingSystem::Aix => "aix",
-            _ => anyhow::bail!("Unsupported OS"),
-        };
+    /// The archive filename and URL of a Node.js release for `platform`.
+    fn download_url(version: &NodeVersion, platform: &NodePlatform) -> (String, String) {
+        let NodePlatform { os, mut arch, dist } = *platform;
         if os == "darwin" && arch == "arm64" && version.major() < 16 {
             // Node.js 16 and later are required for arm64 on macOS.
             arch = "x64";
@@ -203,11 +329,21 @@ impl NodeInstaller {
         let ext = if cfg!(windows) { "zip" } else { "tar.xz" };
 
         let filename = format!("node-v{}-{os}-{arch}.{ext}", version.version());
-        let url = format!("https://nodejs.org/dist/v{}/{filename}", version.version());
-        let checksum_url = format!(
-            "https://nodejs.org/dist/v{}/SHASUMS256.txt",
-            version.version()
-        );
+        let url = format!("{}/v{}/{filename}", dist.base_url(), version.version());
+        (filename, url)
+    }
+
+    // TODO: support mirror?
+    /// Install a specific version of Node.js.
+    async fn download(
+        &self,
+        store: &Store,
+        version: &NodeVersion,
+        platform: &NodePlatform,
+    ) -> Result<NodeResult> {
+        let (filename, url) = Self::download_url(version, platform);
+        let base_url = platform.dist.base_url();
+        let checksum_url = format!("{base_url}/v{}/SHASUMS256.txt", version.version());
         let target = self.root.join(version.to_string());
 
         let download = download_artifact(&url, &filename, store, async || {
@@ -295,6 +431,31 @@ impl NodeInstaller {
         Ok(None)
     }
 
+    /// Find a matching Node.js installed by a version manager, even if it is not active.
+    async fn find_version_manager_node(node_request: &NodeRequest) -> Option<NodeResult> {
+        for install in version_manager::find_installs(Tool::Node).await {
+            let node_path = bin_dir(&install).join("node").with_extension(EXE_EXTENSION);
+            let Ok(Some(npm_path)) = Self::find_npm_in_same_directory(&node_path) else {
+                continue;
+            };
+            match NodeResult::from_executables(node_path, npm_path).await {
+                Ok(node_result) if node_request.matches(&node_result.version) => {
+                    return Some(node_result);
+                }
+                Ok(node_result) => {
+                    trace!(
+                        %node_result,
+                        "Version manager node does not match requested version"
+                    );
+                }
+                Err(e) => {
+                    warn!(?e, "Failed to get version for version manager node");
+                }
+            }
+        }
+        None
+    }
+
     /// Find npm executable in the same directory as the given node executable.
     fn find_npm_in_same_directory(node_path: &Path) -> Result<Option<PathBuf>> {
         let node_dir = node_path
@@ -335,3 +496,58 @@ pub(crate) fn lib_dir(prefix: &Path) -> PathBuf {
         prefix.join("lib").join("node_modules")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(triple: &str) -> Result<NodePlatform> {
+        NodePlatform::from_triple(&Triple::from_str(triple).unwrap())
+    }
+
+    #[test]
+    fn node_platform_from_triple() {
+        let official = platform("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(official.to_string(), "linux-x64");
+        assert_eq!(official.dist, NodeDist::Official);
+        assert_eq!(
+            platform("aarch64-apple-darwin").unwrap().to_string(),
+            "darwin-arm64"
+        );
+        assert_eq!(
+            platform("x86_64-pc-windows-msvc").unwrap().to_string(),
+            "win-x64"
+        );
+
+        // musl and less common architectures fall back to unofficial builds.
+        let musl = platform("x86_64-unknown-linux-musl").unwrap();
+        assert_eq!(musl.files_key(), "linux-x64-musl");
+        assert_eq!(musl.dist, NodeDist::Unofficial);
+        assert_eq!(
+            platform("aarch64-unknown-linux-musl").unwrap().to_string(),
+            "linux-arm64-musl"
+        );
+        assert_eq!(
+            platform("riscv64gc-unknown-linux-gnu").unwrap().dist,
+            NodeDist::Unofficial
+        );
+    }
+
+    #[test]
+    fn node_platform_unsupported() {
+        let err = platform("x86_64-unknown-freebsd").unwrap_err();
+        assert!(err.to_string().contains("pkg install node"));
+        let err = platform("s390x-unknown-linux-musl").unwrap_err();
+        assert!(err.to_string().contains("musl"));
+    }
+
+    #[test]
+    fn dist_index_entry_files() {
+        let entries: Vec<DistIndexEntry> = serde_json::from_str(
+            r#"[{"version":"v22.1.0","lts":false,"files":["linux-x64-musl","linux-x64"]}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries[0].version.to_string(), "22.1.0");
+        assert_eq!(entries[0].files, ["linux-x64-musl", "linux-x64"]);
+    }
+}
@@ -3,5 +3,6 @@ mod installer;
 mod node;
 mod version;
 
+pub(crate) use installer::{bin_dir, lib_dir};
 pub(crate) use node::Node;
 pub(crate) use version::NodeRequest;
@@ -1,5 +1,4 @@
 use std::path::Path;
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -11,13 +10,13 @@ use crate::cli::reporter::HookInstallReporter;
 use crate::cli::run::HookRunReporter;
 use crate::hook::InstalledHook;
 use crate::hook::{Hook, InstallInfo};
-use crate::languages::LanguageBackend;
 use crate::languages::node::NodeRequest;
 use crate::languages::node::installer::{NodeInstaller, bin_dir, lib_dir, query_node_version};
 use crate::languages::node::version::EXTRA_KEY_LTS;
 use crate::languages::version::LanguageRequest;
+use crate::languages::{LanguageBackend, ToolchainDownload};
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -145,6 +144,35 @@ impl LanguageBackend for Node {
         Ok(())
     }
 
+    async fn toolchain_download(
+        &self,
+        store: &Store,
+        hook: &Hook,
+    ) -> Result<Option<ToolchainDownload>> {
+        let request = match &hook.language_request {
+            LanguageRequest::Any { system_only: true } => return Ok(None),
+            LanguageRequest::Any { system_only: false } => &NodeRequest::Any,
+            LanguageRequest::Node(request) => request,
+            _ => unreachable!(),
+        };
+        NodeInstaller::new(store.tools_path(ToolBucket::Node))
+            .toolchain_download(request)
+            .await
+    }
+
+    async fn toolchain_version(&self, store: &Store, hook: &Hook) -> Result<Option<String>> {
+        let (request, allows_download) = match &hook.language_request {
+            LanguageRequest::Any { system_only } => (&NodeRequest::Any, !system_only),
+            LanguageRequest::Node(request) => (request, true),
+            _ => unreachable!(),
+        };
+        let node = NodeInstaller::new(store.tools_path(ToolBucket::Node))
+            .install(store, request, allows_download)
+            .await
+            .context("Failed to install node")?;
+        Ok(Some(node.version().to_string()))
+    }
+
     async fn run(
         &self,
         store: &Store,
@@ -152,7 +180,20 @@ impl LanguageBackend for Node {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
+        // Without an environment, the hook runs scripts from the project's own `node_modules`,
+        // with the `node` found in `PATH`.
+        if hook.env_path().is_none() {
+            let bin_dir = hook
+                .project_node_modules_bin()
+                .context("The project's `node_modules/.bin` directory no longer exists")?;
+            let new_path = prepend_paths(&[&bin_dir]).context("Failed to join PATH")?;
+            let entry = hook.entry.resolve(Some(&new_path), store)?;
+
+            return run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+                cmd.env(EnvVars::PATH, &new_path);
+            })
+            .await;
+        }
 
         let env_dir = hook.env_path().expect("Node must have env path");
         let node_bin = hook.toolchain_dir().expect("Node binary must have parent");
@@ -162,32 +203,12 @@ impl LanguageBackend for Node {
         let entry = hook.entry.resolve(Some(&new_path), store)?;
         let npm_cache = store.cache_path(CacheBucket::Npm);
 
-        let run = async |batch: &[&Path]| {
-            let mut cmd = Cmd::new(&entry[0]);
-            cmd.current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
-                .env(EnvVars::NODE_PATH, lib_dir(env_dir))
-                .envs(&hook.env);
-            apply_npm_config_env(&mut cmd, env_dir, &npm_cache);
-            let output = cmd
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
+                .env(EnvVars::NODE_PATH, lib_dir(env_dir));
+            apply_npm_config_env(cmd, env_dir, &npm_cache);
+        })
+        .await
     }
 }
 
@@ -198,7 +198,7 @@ impl LanguageBackend for Pygrep {
 
         let args = Args::parse(&hook.args).context("Failed to parse `args`")?;
         let mut cmd = Cmd::new(&info.toolchain)
-            .current_dir(hook.work_dir())
+            .current_dir(hook.run_dir())
             .envs(&hook.env)
             .arg("-I") // Isolate mode.
             .arg("-B") // Don't write bytecode.
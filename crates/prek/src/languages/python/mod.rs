@@ -17,6 +17,6 @@ pub(crate) async fn extract_metadata(hook: &mut Hook) -> Result<()> {
 }
 
 pub(crate) use python::Python;
-pub(crate) use python::{python_exec, query_python_info_cached};
+pub(crate) use python::{bin_dir, python_exec, query_python_info_cached};
 pub(crate) use uv::Uv;
 pub(crate) use version::PythonRequest;
@@ -1,7 +1,6 @@
 use std::env::consts::EXE_EXTENSION;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::str;
 use std::sync::Arc;
 
@@ -14,7 +13,7 @@ use crate::cli::run::HookRunReporter;
 use crate::hook::{Hook, InstallInfo, InstalledHook};
 use crate::languages::LanguageBackend;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::Store;
 
 #[derive(Debug, Copy, Clone)]
@@ -128,39 +127,15 @@ impl LanguageBackend for R {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_path = hook.env_path().expect("R must have env path");
         let activate = env_path.join("activate.R");
         let entry = r_hook_entry(hook)?;
 
-        let run = async |batch: &[&Path]| {
-            let mut cmd = Cmd::new(&entry[0]);
-            cmd.current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env_remove(EnvVars::RENV_PROJECT)
-                .env(EnvVars::R_PROFILE_USER, &activate)
-                .stdin(Stdio::null());
-
-            cmd.envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false);
-
-            let output = cmd
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, &entry, run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+        run_hook_entry(hook, &entry, filenames, reporter, |cmd| {
+            cmd.env_remove(EnvVars::RENV_PROJECT)
+                .env(EnvVars::R_PROFILE_USER, &activate);
+        })
+        .await
     }
 }
 
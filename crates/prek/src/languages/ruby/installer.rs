@@ -14,6 +14,8 @@ use crate::checksum::{Sha256Digest, digest_from_sha256sums};
 use crate::fs::LockedFile;
 use crate::http::{DownloadChecksumPolicy, REQWEST_CLIENT, download_artifact_with};
 use crate::languages::ruby::RubyRequest;
+use crate::languages::ruby::rubyinstaller;
+use crate::languages::version_manager::{self, Tool};
 use crate::process::Cmd;
 use crate::store::Store;
 
@@ -45,7 +47,7 @@ fn rv_ruby_api_url(env_vars: &impl EnvVarsRead) -> (String, bool) {
     }
 }
 
-fn github_repo_path(url: &str) -> Option<String> {
+pub(super) fn github_repo_path(url: &str) -> Option<String> {
     let url = Url::parse(url).ok()?;
     if url.scheme() != "https"
         || url.host_str() != Some("github.com")
@@ -69,7 +71,7 @@ fn github_repo_path(url: &str) -> Option<String> {
 
 /// Conditionally add a GitHub auth token to a request builder.
 /// Only sends `GITHUB_TOKEN` when `is_github` is true.
-fn maybe_add_github_auth(
+pub(super) fn maybe_add_github_auth(
     req: reqwest::RequestBuilder,
     is_github: bool,
     env_vars: &impl EnvVarsRead,
@@ -145,7 +147,7 @@ pub(crate) struct RubyResult {
 }
 
 impl RubyResult {
-    fn from_managed_dir(dir: &Path, version: semver::Version) -> Result<Self> {
+    pub(super) fn from_managed_dir(dir: &Path, version: semver::Version) -> Result<Self> {
         let bin_dir = dir.join("bin");
         let ruby_bin = bin_dir.join("ruby").with_extension(EXE_EXTENSION);
         let gem_bin = find_gem_for_ruby(&ruby_bin)?;
@@ -231,10 +233,25 @@ impl RubyInstaller {
             ));
         }
 
+        // rv-ruby does not build for Windows, use RubyInstaller2 builds there instead.
+        if let Some(arch) = rubyinstaller::platform_arch(&HOST) {
+            return rubyinstaller::install(&self.root, store, request, arch)
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "{}\n\nCaused by:\n  {e}",
+                        ruby_not_found_error(
+                            request,
+                            "Failed to download Ruby from RubyInstaller2."
+                        )
+                    )
+                });
+        }
+
         let Some(platform) = rv_platform_string(&HOST) else {
             anyhow::bail!(ruby_not_found_error(
                 request,
-                // Windows, unknown CPU, etc. that doesn't have a matching rv-ruby
+                // Unknown CPU, libc, etc. that doesn't have a matching rv-ruby
                 // release asset (that we know about).
                 "Automatic installation is not supported on this platform."
             ));
@@ -416,11 +433,22 @@ impl RubyInstaller {
         }
 
         // If we didn't find a suitable Ruby in PATH, search version manager directories
-        #[cfg(not(target_os = "windows"))]
         if let Some(result) = search_version_managers(request).await {
             return Ok(Some(result));
         }
 
+        // Ask version managers installed elsewhere, e.g. with a custom data directory
+        if version_manager::prefer_system_toolchains() {
+            for install in version_manager::find_installs(Tool::Ruby).await {
+                let ruby_path = install.join("bin/ruby").with_extension(EXE_EXTENSION);
+                if ruby_path.exists()
+                    && let Some(result) = try_ruby_path(&ruby_path, request).await
+                {
+                    return Ok(Some(result));
+                }
+            }
+        }
+
         Ok(None)
     }
 }
@@ -480,8 +508,53 @@ async fn search_version_managers(request: &RubyRequest) -> Option<RubyResult> {
     None
 }
 
+/// Search common Windows install locations for suitable Ruby installations
+#[cfg(target_os = "windows")]
+async fn search_version_managers(request: &RubyRequest) -> Option<RubyResult> {
+    let mut search_dirs = Vec::new();
+    // scoop: ~/scoop/apps/ruby/3.4.6/bin/ruby.exe
+    if let Ok(scoop) = EnvVars.var(EnvVars::SCOOP) {
+        search_dirs.push(PathBuf::from(scoop).join("apps/ruby"));
+    }
+    if let Ok(home) = EnvVars.var(EnvVars::USERPROFILE) {
+        search_dirs.push(PathBuf::from(home).join("scoop/apps/ruby"));
+    }
+    for search_dir in &search_dirs {
+        if let Some(result) = search_ruby_installations(search_dir, request).await {
+            return Some(result);
+        }
+    }
+
+    // RubyInstaller (also used by `winget`): C:\Ruby34-x64\bin\ruby.exe
+    let system_drive = EnvVars
+        .var(EnvVars::SYSTEMDRIVE)
+        .unwrap_or_else(|_| "C:".to_string());
+    let entries = fs_err::read_dir(format!("{system_drive}\\")).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir()
+            || !entry
+                .file_name()
+                .to_string_lossy()
+                .to_ascii_lowercase()
+                .starts_with("ruby")
+        {
+            continue;
+        }
+
+        let ruby_path = path.join("bin/ruby.exe");
+        if ruby_path.exists() {
+            if let Some(result) = try_ruby_path(&ruby_path, request).await {
+                trace!("Found suitable RubyInstaller Ruby: {}", ruby_path.display());
+                return Some(result);
+            }
+        }
+    }
+
+    None
+}
+
 /// Search a version manager directory for Ruby installations
-#[cfg(not(target_os = "windows"))]
 async fn search_ruby_installations(dir: &Path, request: &RubyRequest) -> Option<RubyResult> {
     let entries = fs_err::read_dir(dir).ok()?;
 
@@ -491,7 +564,7 @@ async fn search_ruby_installations(dir: &Path, request: &RubyRequest) -> Option<
             continue;
         }
 
-        let ruby_path = path.join("bin/ruby");
+        let ruby_path = path.join("bin/ruby").with_extension(EXE_EXTENSION);
         if ruby_path.exists() {
             if let Some(result) = try_ruby_path(&ruby_path, request).await {
                 trace!(
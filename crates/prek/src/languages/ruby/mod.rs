@@ -9,7 +9,8 @@ mod gem;
 mod installer;
 #[allow(clippy::module_inception)]
 mod ruby;
+mod rubyinstaller;
 mod version;
 
-pub(crate) use ruby::Ruby;
+pub(crate) use ruby::{Ruby, gem_bin, gem_home};
 pub(crate) use version::RubyRequest;
@@ -1,6 +1,5 @@
 use std::env::consts::EXE_EXTENSION;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -16,8 +15,7 @@ use crate::languages::ruby::RubyRequest;
 use crate::languages::ruby::gem::{build_gemspecs, install_gems};
 use crate::languages::ruby::installer::{RubyInstaller, query_ruby_version};
 use crate::languages::version::LanguageRequest;
-use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{Store, ToolBucket};
 
 #[derive(Debug, Copy, Clone)]
@@ -133,8 +131,6 @@ impl LanguageBackend for Ruby {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Ruby hook must have env path");
 
         // Prepare PATH
@@ -150,41 +146,22 @@ impl LanguageBackend for Ruby {
         let entry = hook.entry.resolve(Some(&new_path), store)?;
 
         // Execute in batches
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .env(EnvVars::PATH, &new_path)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
                 .env(EnvVars::GEM_HOME, &gem_home)
                 .env(EnvVars::BUNDLE_IGNORE_CONFIG, "1")
                 .env_remove(EnvVars::GEM_PATH)
-                .env_remove(EnvVars::BUNDLE_GEMFILE)
-                .envs(&hook.env)
-                .args(&entry[1..])
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+                .env_remove(EnvVars::BUNDLE_GEMFILE);
+        })
+        .await
     }
 }
 
 /// Get the `GEM_HOME` path for this environment
-fn gem_home(env_path: &Path) -> PathBuf {
+pub(crate) fn gem_home(env_path: &Path) -> PathBuf {
     env_path.join("gems")
 }
 
-fn gem_bin(env_path: &Path) -> PathBuf {
+pub(crate) fn gem_bin(env_path: &Path) -> PathBuf {
     gem_home(env_path).join("bin")
 }
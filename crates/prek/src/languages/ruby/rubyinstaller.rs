@@ -0,0 +1,301 @@
+//! Managed Ruby downloads for Windows, using the RubyInstaller2 release builds.
+//!
+//! RubyInstaller2 publishes one GitHub release per Ruby build (e.g. `RubyInstaller-3.4.8-1`),
+//! each with self-contained archives such as `rubyinstaller-3.4.8-1-x64.7z`.
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use serde::Deserialize;
+use target_lexicon::{Architecture, OperatingSystem, Triple};
+use tracing::{debug, trace};
+
+use crate::archive;
+use crate::checksum::Sha256Digest;
+use crate::http::{DownloadChecksumPolicy, REQWEST_CLIENT, download_artifact_with};
+use crate::languages::ruby::RubyRequest;
+use crate::languages::ruby::installer::{RubyResult, github_repo_path, maybe_add_github_auth};
+use crate::process::Cmd;
+use crate::store::Store;
+
+const RUBYINSTALLER_DEFAULT_URL: &str = "https://github.com/oneclick/rubyinstaller2";
+
+fn rubyinstaller_base_url(env_vars: &impl EnvVarsRead) -> String {
+    match env_vars.var(EnvVars::PREK_RUBYINSTALLER_MIRROR) {
+        Ok(mirror) => mirror.trim_end_matches('/').to_string(),
+        Err(_) => RUBYINSTALLER_DEFAULT_URL.to_string(),
+    }
+}
+
+/// Returns the releases listing URL and whether the target host is github.com.
+fn rubyinstaller_api_url(env_vars: &impl EnvVarsRead) -> (String, bool) {
+    let base = rubyinstaller_base_url(env_vars);
+    if let Some(path) = github_repo_path(&base) {
+        (
+            format!("https://api.github.com/repos{path}/releases?per_page=100"),
+            true,
+        )
+    } else {
+        (format!("{base}/releases"), false)
+    }
+}
+
+/// Returns the RubyInstaller2 asset architecture suffix for the current target.
+pub(super) fn platform_arch(triple: &Triple) -> Option<&'static str> {
+    match (triple.operating_system, triple.architecture) {
+        (OperatingSystem::Windows, Architecture::X86_64) => Some("x64"),
+        (OperatingSystem::Windows, Architecture::X86_32(_)) => Some("x86"),
+        (OperatingSystem::Windows, Architecture::Aarch64(_)) => Some("arm"),
+        _ => None,
+    }
+}
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    /// The asset digest reported by GitHub, e.g. `sha256:<hex>`.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// A downloadable RubyInstaller2 archive.
+#[derive(Debug)]
+struct Asset {
+    version: semver::Version,
+    build: u32,
+    tag: String,
+    name: String,
+    digest: Option<Sha256Digest>,
+}
+
+/// Extract the Ruby version and package build number from a RubyInstaller2 asset name.
+///
+/// Given arch `x64` and asset `rubyinstaller-3.4.8-1-x64.7z`, returns `(3.4.8, 1)`.
+/// Both `.7z` and `.zip` archives are accepted. Pre-release versions are skipped.
+fn parse_asset_name(name: &str, arch: &str) -> Option<(semver::Version, u32)> {
+    let name = name.strip_prefix("rubyinstaller-")?;
+    let name = name
+        .strip_suffix(".7z")
+        .or_else(|| name.strip_suffix(".zip"))?;
+    let name = name.strip_suffix(arch)?.strip_suffix('-')?;
+    let (version, build) = name.rsplit_once('-')?;
+    let version = semver::Version::parse(version).ok()?;
+    if !version.pre.is_empty() {
+        return None;
+    }
+    Some((version, build.parse().ok()?))
+}
+
+fn parse_digest(digest: &str) -> Option<Sha256Digest> {
+    digest.strip_prefix("sha256:")?.parse().ok()
+}
+
+/// List the RubyInstaller2 archives available for `arch`, newest first.
+async fn list_remote_assets(arch: &str) -> Result<Vec<Asset>> {
+    let (api_url, is_github) = rubyinstaller_api_url(&EnvVars);
+
+    let req = REQWEST_CLIENT
+        .get(&api_url)
+        .header("Accept", "application/vnd.github+json");
+    let req = maybe_add_github_auth(req, is_github, &EnvVars);
+
+    let releases: Vec<Release> = req
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .with_context(|| format!("Failed to fetch RubyInstaller2 releases from {api_url}"))?
+        .json()
+        .await
+        .context("Failed to parse RubyInstaller2 release JSON")?;
+
+    let assets = releases
+        .into_iter()
+        .flat_map(|release| {
+            let tag = release.tag_name;
+            release.assets.into_iter().filter_map(move |asset| {
+                let (version, build) = parse_asset_name(&asset.name, arch)?;
+                Some(Asset {
+                    version,
+                    build,
+                    tag: tag.clone(),
+                    digest: asset.digest.as_deref().and_then(parse_digest),
+                    name: asset.name,
+                })
+            })
+        })
+        // Prefer the newest version, then the newest package build, then `.7z` over `.zip`.
+        .sorted_unstable_by(|a, b| {
+            (&b.version, b.build, b.name.ends_with(".7z")).cmp(&(
+                &a.version,
+                a.build,
+                a.name.ends_with(".7z"),
+            ))
+        })
+        .dedup_by(|a, b| a.version == b.version)
+        .collect();
+
+    Ok(assets)
+}
+
+/// Download the newest RubyInstaller2 build matching `request` into `root/<version>`.
+pub(super) async fn install(
+    root: &Path,
+    store: &Store,
+    request: &RubyRequest,
+    arch: &str,
+) -> Result<RubyResult> {
+    let assets = list_remote_assets(arch).await?;
+    let Some(asset) = assets
+        .into_iter()
+        .find(|asset| request.matches(&asset.version))
+    else {
+        anyhow::bail!("No RubyInstaller2 release found matching: {request}");
+    };
+    trace!(?asset, "Selected RubyInstaller2 build");
+
+    let base_url = rubyinstaller_base_url(&EnvVars);
+    let is_github = github_repo_path(&base_url).is_some();
+    let url = format!("{base_url}/releases/download/{}/{}", asset.tag, asset.name);
+
+    let version = &asset.version;
+    let target = root.join(version.to_string());
+    debug!(url = %url, target = %target.display(), "Downloading Ruby {version}");
+
+    let digest = asset.digest;
+    let download = download_artifact_with(
+        &url,
+        &asset.name,
+        store,
+        DownloadChecksumPolicy::from_env(&EnvVars),
+        async || Ok(digest),
+        |req| maybe_add_github_auth(req, is_github, &EnvVars),
+    )
+    .await
+    .with_context(|| format!("Failed to download Ruby {version} from {url}"))?;
+
+    // RubyInstaller2 archives contain a single `rubyinstaller-{version}-{build}-{arch}/` directory.
+    let extracted = if asset.name.ends_with(".7z") {
+        extract_7z(download.path())
+            .await
+            .with_context(|| format!("Failed to extract Ruby {version}"))?
+    } else {
+        archive::extract_archive(download.path())
+            .await
+            .with_context(|| format!("Failed to extract Ruby {version}"))?
+    };
+
+    if target.exists() {
+        debug!(target = %target.display(), "Removing existing Ruby");
+        fs_err::tokio::remove_dir_all(&target).await?;
+    }
+    fs_err::tokio::rename(&extracted, &target).await?;
+
+    RubyResult::from_managed_dir(&target, version.clone())
+}
+
+/// Extract a `.7z` archive next to it and return the top-level directory.
+///
+/// Windows 10 and later ship a libarchive-based `tar.exe`, which can read 7-Zip archives.
+async fn extract_7z(path: &Path) -> Result<std::path::PathBuf> {
+    let extract_dir = path.with_file_name("extract");
+    fs_err::tokio::create_dir_all(&extract_dir).await?;
+
+    Cmd::new("tar")
+        .arg("-xf")
+        .arg(path)
+        .arg("-C")
+        .arg(&extract_dir)
+        .check(true)
+        .output()
+        .await?;
+
+    Ok(archive::strip_component(&extract_dir)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_asset_name() {
+        assert_eq!(
+            parse_asset_name("rubyinstaller-3.4.8-1-x64.7z", "x64"),
+            Some((semver::Version::new(3, 4, 8), 1))
+        );
+        assert_eq!(
+            parse_asset_name("rubyinstaller-3.3.6-2-arm.zip", "arm"),
+            Some((semver::Version::new(3, 3, 6), 2))
+        );
+        // Other architectures, installers, devkits and pre-releases are ignored.
+        assert_eq!(
+            parse_asset_name("rubyinstaller-3.4.8-1-x86.7z", "x64"),
+            None
+        );
+        assert_eq!(
+            parse_asset_name("rubyinstaller-3.4.8-1-x64.exe", "x64"),
+            None
+        );
+        assert_eq!(
+            parse_asset_name("rubyinstaller-devkit-3.4.8-1-x64.exe", "x64"),
+            None
+        );
+        assert_eq!(
+            parse_asset_name("rubyinstaller-3.5.0-preview1-1-x64.7z", "x64"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_digest() {
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert!(parse_digest(&format!("sha256:{hex}")).is_some());
+        assert!(parse_digest(hex).is_none());
+        assert!(parse_digest("sha512:abcd").is_none());
+    }
+
+    #[test]
+    fn test_platform_arch() {
+        let arch = |triple: &str| platform_arch(&Triple::from_str(triple).unwrap());
+        assert_eq!(arch("x86_64-pc-windows-msvc"), Some("x64"));
+        assert_eq!(arch("i686-pc-windows-msvc"), Some("x86"));
+        assert_eq!(arch("aarch64-pc-windows-msvc"), Some("arm"));
+        assert_eq!(arch("x86_64-unknown-linux-gnu"), None);
+    }
+
+    #[test]
+    fn rubyinstaller_env_overrides() {
+        assert_eq!(
+            rubyinstaller_api_url(&EnvVars::from_map(&[])),
+            (
+                "https://api.github.com/repos/oneclick/rubyinstaller2/releases?per_page=100"
+                    .to_string(),
+                true
+            )
+        );
+
+        let env = EnvVars::from_map(&[(
+            EnvVars::PREK_RUBYINSTALLER_MIRROR,
+            "https://mirror.example.com/rubyinstaller2/",
+        )]);
+        assert_eq!(
+            rubyinstaller_base_url(&env),
+            "https://mirror.example.com/rubyinstaller2"
+        );
+        assert_eq!(
+            rubyinstaller_api_url(&env),
+            (
+                "https://mirror.example.com/rubyinstaller2/releases".to_string(),
+                false
+            )
+        );
+    }
+}
@@ -2,7 +2,6 @@ use std::env::consts::EXE_EXTENSION;
 use std::ffi::OsStr;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -24,7 +23,7 @@ use crate::languages::rust::rustup::Rustup;
 use crate::languages::rust::version::{Channel, EXTRA_KEY_CHANNEL};
 use crate::languages::version::LanguageRequest;
 use crate::process::Cmd;
-use crate::run::run_by_batch;
+use crate::run::run_hook_entry;
 use crate::store::{CacheBucket, Store, ToolBucket};
 
 fn format_cargo_dependency(dep: &str) -> String {
@@ -499,8 +498,6 @@ impl LanguageBackend for Rust {
         filenames: &[&Path],
         reporter: &HookRunReporter,
     ) -> anyhow::Result<(i32, Vec<u8>)> {
-        let progress = reporter.on_run_start(hook, filenames.len());
-
         let env_dir = hook.env_path().expect("Rust hook must have env path");
         let info = hook.install_info().expect("Rust hook must be installed");
 
@@ -511,31 +508,12 @@ impl LanguageBackend for Rust {
         let new_path = prepend_paths(&[&rust_bin, &rustc_bin]).context("Failed to join PATH")?;
 
         let entry = hook.entry.resolve(Some(&new_path), store)?;
-        let run = async |batch: &[&Path]| {
-            let output = Cmd::new(&entry[0])
-                .current_dir(hook.work_dir())
-                .args(&entry[1..])
-                .env(EnvVars::PATH, &new_path)
+        run_hook_entry(hook, entry.argv(), filenames, reporter, |cmd| {
+            cmd.env(EnvVars::PATH, &new_path)
                 .env(EnvVars::CARGO_HOME, &cargo_home)
-                .env(EnvVars::RUSTUP_AUTO_INSTALL, "0")
-                .envs(&hook.env)
-                .args(&hook.args)
-                .file_args(batch)
-                .check(false)
-                .stdin(Stdio::null())
-                .pty_output_with_sink(reporter.output_sink(progress))
-                .await?;
-
-            reporter.on_run_progress(progress, batch.len() as u64);
-
-            anyhow::Ok(output)
-        };
-
-        let output = run_by_batch(hook, filenames, entry.argv(), run).await?;
-
-        reporter.on_run_complete(progress);
-
-        Ok(output)
+                .env(EnvVars::RUSTUP_AUTO_INSTALL, "0");
+        })
+        .await
     }
 }
 
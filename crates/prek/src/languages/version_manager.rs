@@ -0,0 +1,208 @@
+//! Toolchains installed by system version managers (mise, asdf).
+//!
+//! A version manager usually only puts shims for the *active* version in `PATH`, so a
+//! matching version that is installed but not active is invisible to the `PATH` lookup.
+//! When `PREK_PREFER_SYSTEM_TOOLCHAINS` is enabled, installers ask the version managers for
+//! their installed versions before downloading a toolchain of their own.
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use serde::Deserialize;
+use tracing::{debug, trace};
+
+use crate::process::Cmd;
+use crate::warn_user;
+
+static PREFER_SYSTEM_TOOLCHAINS: LazyLock<bool> =
+    LazyLock::new(|| prefer_system_toolchains_from_env(&EnvVars));
+
+fn prefer_system_toolchains_from_env(env_vars: &impl EnvVarsRead) -> bool {
+    env_vars
+        .var_as_bool(EnvVars::PREK_PREFER_SYSTEM_TOOLCHAINS)
+        .unwrap_or_else(|value| {
+            warn_user!(
+                "Invalid value for {}: {:?}. Expected a boolean value; using default ({:?})",
+                EnvVars::PREK_PREFER_SYSTEM_TOOLCHAINS,
+                value,
+                "false",
+            );
+            Some(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Whether to look for toolchains installed by version managers before downloading.
+pub(crate) fn prefer_system_toolchains() -> bool {
+    *PREFER_SYSTEM_TOOLCHAINS
+}
+
+/// A toolchain that version managers can install.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Tool {
+    Node,
+    Ruby,
+    Go,
+}
+
+impl Tool {
+    fn mise_name(self) -> &'static str {
+        match self {
+            Self::Node => "node",
+            Self::Ruby => "ruby",
+            Self::Go => "go",
+        }
+    }
+
+    fn asdf_plugin(self) -> &'static str {
+        match self {
+            Self::Node => "nodejs",
+            Self::Ruby => "ruby",
+            Self::Go => "golang",
+        }
+    }
+}
+
+/// List the install directories of `tool` versions known to mise and asdf.
+///
+/// Version managers that are not installed, or fail to answer, are skipped.
+pub(crate) async fn find_installs(tool: Tool) -> Vec<PathBuf> {
+    let mut installs = Vec::new();
+
+    if let Ok(mise) = which::which("mise") {
+        match list_mise_installs(&mise, tool).await {
+            Ok(found) => installs.extend(found),
+            Err(err) => debug!(
+                "Failed to list {} versions from mise: {err}",
+                tool.mise_name()
+            ),
+        }
+    }
+    if let Ok(asdf) = which::which("asdf") {
+        match list_asdf_installs(&asdf, tool).await {
+            Ok(found) => installs.extend(found),
+            Err(err) => debug!(
+                "Failed to list {} versions from asdf: {err}",
+                tool.asdf_plugin()
+            ),
+        }
+    }
+
+    trace!(?tool, ?installs, "Found version manager installs");
+    installs
+}
+
+#[derive(Deserialize)]
+struct MiseInstall {
+    install_path: PathBuf,
+}
+
+async fn list_mise_installs(mise: &Path, tool: Tool) -> Result<Vec<PathBuf>> {
+    let output = Cmd::new(mise)
+        .arg("ls")
+        .arg("--installed")
+        .arg("--json")
+        .arg(tool.mise_name())
+        .check(true)
+        .output()
+        .await?;
+    parse_mise_installs(&output.stdout)
+}
+
+fn parse_mise_installs(stdout: &[u8]) -> Result<Vec<PathBuf>> {
+    let installs: Vec<MiseInstall> = serde_json::from_slice(stdout)?;
+    Ok(installs
+        .into_iter()
+        .map(|install| install.install_path)
+        .collect())
+}
+
+async fn list_asdf_installs(asdf: &Path, tool: Tool) -> Result<Vec<PathBuf>> {
+    let output = Cmd::new(asdf)
+        .arg("list")
+        .arg(tool.asdf_plugin())
+        .check(true)
+        .output()
+        .await?;
+
+    let mut installs = Vec::new();
+    for version in parse_asdf_versions(&String::from_utf8_lossy(&output.stdout)) {
+        let output = Cmd::new(asdf)
+            .arg("where")
+            .arg(tool.asdf_plugin())
+            .arg(version)
+            .check(true)
+            .output()
+            .await?;
+        let path = String::from_utf8_lossy(&output.stdout);
+        installs.push(PathBuf::from(path.trim()));
+    }
+    Ok(installs)
+}
+
+/// Parse `asdf list <plugin>` output, where the active version is marked with `*`.
+fn parse_asdf_versions(stdout: &str) -> impl Iterator<Item = &str> {
+    stdout
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|version| !version.is_empty() && !version.contains(char::is_whitespace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefer_system_toolchains_env() {
+        assert!(!prefer_system_toolchains_from_env(&EnvVars::from_map(&[])));
+        assert!(prefer_system_toolchains_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_PREFER_SYSTEM_TOOLCHAINS,
+            "1"
+        )])));
+        assert!(!prefer_system_toolchains_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_PREFER_SYSTEM_TOOLCHAINS,
+            "maybe"
+        )])));
+    }
+
+    #[test]
+    fn mise_installs() -> Result<()> {
+        let stdout = br#"[
+          {
+            "version": "20.11.0",
+            "install_path": "/home/user/.local/share/mise/installs/node/20.11.0",
+            "source": {"type": "mise.toml", "path": "/home/user/mise.toml"},
+            "installed": true,
+            "active": true
+          },
+          {
+            "version": "22.3.0",
+            "install_path": "/home/user/.local/share/mise/installs/node/22.3.0",
+            "installed": true,
+            "active": false
+          }
+        ]"#;
+
+        assert_eq!(
+            parse_mise_installs(stdout)?,
+            [
+                PathBuf::from("/home/user/.local/share/mise/installs/node/20.11.0"),
+                PathBuf::from("/home/user/.local/share/mise/installs/node/22.3.0"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn asdf_versions() {
+        let stdout = "  18.19.0\n *20.11.0\n  22.3.0\n";
+        assert_eq!(
+            parse_asdf_versions(stdout).collect::<Vec<_>>(),
+            ["18.19.0", "20.11.0", "22.3.0"]
+        );
+
+        let stdout = "  No versions installed\n";
+        assert_eq!(parse_asdf_versions(stdout).count(), 0);
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value, json};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Rotate the log file once it grows beyond this size.
+const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// Number of rotated log files to keep, as `prek.log.1` (newest) to `prek.log.3` (oldest).
+const MAX_LOG_FILE_BACKUPS: usize = 3;
+
+/// Formats each event as a single JSON object per line.
+///
+/// ```json
+/// {"timestamp":"...","level":"DEBUG","target":"prek::git","fields":{"message":"..."},"spans":[...]}
+/// ```
+pub(crate) struct JsonLines;
+
+impl<S, N> FormatEvent<S, N> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let mut fields = JsonVisitor::default();
+        event.record(&mut fields);
+
+        let spans: Vec<Value> = ctx
+            .event_scope()
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+            .map(|span| {
+                let extensions = span.extensions();
+                let fields = extensions
+                    .get::<FormattedFields<N>>()
+                    .map(|fields| anstream::adapter::strip_str(&fields.fields).to_string())
+                    .unwrap_or_default();
+                json!({ "name": span.name(), "fields": fields })
+            })
+            .collect();
+
+        let metadata = event.metadata();
+        let line = json!({
+            "timestamp": timestamp,
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": fields.0,
+            "spans": spans,
+        });
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Collects event fields into a JSON object, stripping ANSI escapes from strings.
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(
+            field.name().to_string(),
+            json!(anstream::adapter::strip_str(value).to_string()),
+        );
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+/// An append-only log file that rotates itself once it exceeds [`MAX_LOG_FILE_SIZE`].
+pub(crate) struct RotatingFile {
+    path: PathBuf,
+    file: Option<fs_err::File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub(crate) fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs_err::create_dir_all(parent)?;
+        }
+        let mut file = Self {
+            path,
+            file: None,
+            size: 0,
+        };
+        file.reopen()?;
+        if file.size > MAX_LOG_FILE_SIZE {
+            file.rotate(0)?;
+        }
+        Ok(file)
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let file = fs_err::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Shift `prek.log` to `prek.log.1`, `prek.log.1` to `prek.log.2`, and so on,
+    /// dropping the oldest backup, if writing `incoming` more bytes would exceed the size limit.
+    ///
+    /// Several prek processes can share a log file, so rotation happens under a lock next to it,
+    /// and is skipped if another process already rotated the file. This is not the store lock,
+    /// which is held for as long as hook environments are installed.
+    fn rotate(&mut self, incoming: u64) -> io::Result<()> {
+        // Close the file first, Windows can't rename a file that is still open.
+        self.file = None;
+        let lock = fs_err::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(lock_path(&self.path))?;
+        lock.lock()?;
+        let shifted = match fs_err::metadata(&self.path) {
+            Ok(metadata) if metadata.len() + incoming > MAX_LOG_FILE_SIZE => self.shift_backups(),
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        };
+        // Keep logging even if another process holds on to the old file.
+        let reopened = self.reopen();
+        drop(lock);
+        reopened?;
+        shifted
+    }
+
+    fn shift_backups(&self) -> io::Result<()> {
+        for index in (1..MAX_LOG_FILE_BACKUPS).rev() {
+            let from = backup_path(&self.path, index);
+            if from.exists() {
+                fs_err::rename(&from, backup_path(&self.path, index + 1))?;
+            }
+        }
+        fs_err::rename(&self.path, backup_path(&self.path, 1))
+    }
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.rotate(buf.len() as u64)?;
+        }
+        let file = self
+            .file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("log file is closed"))?;
+        let written = file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_when_exceeding_max_size() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("log").join("prek.log");
+
+        let mut file = RotatingFile::open(path.clone())?;
+        let chunk = vec![b'a'; usize::try_from(MAX_LOG_FILE_SIZE).unwrap() / 2 + 1];
+        for _ in 0..=MAX_LOG_FILE_BACKUPS * 2 {
+            file.write_all(&chunk)?;
+        }
+        file.flush()?;
+
+        // Every chunk after the first one pushed the file over the limit.
+        assert_eq!(fs_err::metadata(&path)?.len(), chunk.len() as u64);
+        for index in 1..=MAX_LOG_FILE_BACKUPS {
+            assert!(backup_path(&path, index).exists());
+        }
+        assert!(!backup_path(&path, MAX_LOG_FILE_BACKUPS + 1).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn skips_rotation_done_by_another_process() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("prek.log");
+
+        let mut first = RotatingFile::open(path.clone())?;
+        first.write_all(&vec![
+            b'a';
+            usize::try_from(MAX_LOG_FILE_SIZE).unwrap() - 10
+        ])?;
+        let mut second = RotatingFile::open(path.clone())?;
+
+        // Both processes see a full log file, only the first one rotates it.
+        first.write_all(&[b'b'; 20])?;
+        second.write_all(&[b'c'; 20])?;
+        first.flush()?;
+        second.flush()?;
+
+        assert!(backup_path(&path, 1).exists());
+        assert!(!backup_path(&path, 2).exists());
+        assert_eq!(fs_err::metadata(&path)?.len(), 40);
+
+        Ok(())
+    }
+}
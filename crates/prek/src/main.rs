@@ -4,11 +4,12 @@ use std::process::ExitCode;
 use std::str::FromStr;
 use std::sync::Mutex;
 
-use anstream::{ColorChoice, StripStream, eprintln};
+use anstream::{ColorChoice, eprintln};
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser};
 use clap_complete::CompleteEnv;
 use owo_colors::OwoColorize;
+use prek_consts::env_vars::EnvVars;
 use tracing::debug;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::filter::Directive;
@@ -19,10 +20,12 @@ use tracing_subscriber::{EnvFilter, Layer};
 
 use crate::cleanup::cleanup;
 use crate::cli::{
-    CacheCommand, CacheNamespace, Cli, Command, ExitStatus, UtilCommand, UtilNamespace, flag,
+    CacheCommand, CacheNamespace, Cli, Command, ConfigCommand, ConfigNamespace, EnvCommand,
+    EnvNamespace, ExitStatus, UtilCommand, UtilNamespace, VersionOutputFormat,
 };
 #[cfg(feature = "self-update")]
 use crate::cli::{SelfCommand, SelfNamespace, SelfUpdateArgs};
+use crate::git::GIT_ROOT;
 use crate::printer::Printer;
 use crate::run::USE_COLOR;
 use crate::settings::FilesystemOptions;
@@ -40,14 +43,19 @@ mod hook_entry;
 mod hooks;
 mod http;
 mod install_source;
+mod jj;
 mod languages;
+mod logging;
 mod printer;
 mod process;
 #[cfg(all(unix, feature = "profiler"))]
 mod profiler;
+mod recording;
+mod remote_cache;
 #[cfg(unix)]
 mod resource_limit;
 mod run;
+mod sandbox;
 #[cfg(feature = "schemars")]
 mod schema;
 mod settings;
@@ -126,22 +134,19 @@ fn setup_logging(level: Level, log_file: LogFile, store: &Store) -> Result<()> {
             LogFile::Path(path) => path,
             LogFile::Disabled => unreachable!(),
         };
-        let log_file = fs_err::OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(log_file_path)
-            .context("Failed to open log file")?;
-        let log_file = Mutex::new(StripStream::new(log_file.into_file()));
-
-        let file_format = tracing_subscriber::fmt::format()
-            .with_target(false)
-            .with_ansi(false);
+        let log_file = logging::RotatingFile::open(log_file_path.clone())
+            .with_context(|| format!("Failed to open log file `{}`", log_file_path.display()))?;
+        let file_filter = EnvFilter::builder()
+            .with_default_directive(Directive::from_str("prek=debug")?)
+            .with_env_var(EnvVars::PREK_LOG)
+            .from_env()
+            .context("Invalid PREK_LOG directive")?;
+
         let file_layer = tracing_subscriber::fmt::layer()
             .with_span_events(FmtSpan::CLOSE)
-            .event_format(file_format)
-            .with_writer(log_file)
-            .with_filter(EnvFilter::new("prek=trace"));
+            .event_format(logging::JsonLines)
+            .with_writer(Mutex::new(log_file))
+            .with_filter(file_filter);
 
         registry.with(file_layer).init();
     }
@@ -155,6 +160,43 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
 
     ColorChoice::write_global(cli.globals.color.into());
 
+    let printer = if cli.globals.quiet == 1 {
+        Printer::Quiet
+    } else if cli.globals.quiet > 1 {
+        Printer::Silent
+    } else if cli.globals.verbose > 1 {
+        Printer::Verbose
+    } else if cli.globals.no_progress {
+        Printer::NoProgress
+    } else {
+        Printer::Default
+    };
+
+    if cli.globals.version {
+        let version = version::version();
+        match cli.version_output_format.unwrap_or_default() {
+            VersionOutputFormat::Text => writeln!(printer.stdout_important(), "prek {version}")?,
+            VersionOutputFormat::Json => writeln!(
+                printer.stdout_important(),
+                "{}",
+                serde_json::to_string_pretty(&version)?
+            )?,
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    // Initialize before `--cd` so a synthetic work tree captures the directory where
+    // git launched the hook. Keep it in prek's own lazy state instead of the
+    // process environment, otherwise user hooks and their nested git commands
+    // would inherit a `GIT_WORK_TREE` that git itself did not expose.
+    // See: https://www.spinics.net/lists/git/msg374197.html
+    //      https://github.com/pre-commit/pre-commit/issues/2295
+    git::init_git_work_tree()?;
+
+    if let Some(dir) = cli.globals.cd.as_ref() {
+        std::env::set_current_dir(dir)?;
+    }
+
     let store = Store::from_settings()?;
     let log_file = LogFile::from_args(cli.globals.log_file.clone(), cli.globals.no_log_file);
     setup_logging(
@@ -169,18 +211,6 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         &store,
     )?;
 
-    let printer = if cli.globals.quiet == 1 {
-        Printer::Quiet
-    } else if cli.globals.quiet > 1 {
-        Printer::Silent
-    } else if cli.globals.verbose > 1 {
-        Printer::Verbose
-    } else if cli.globals.no_progress {
-        Printer::NoProgress
-    } else {
-        Printer::Default
-    };
-
     if cli.globals.quiet > 0 {
         warnings::disable();
     } else {
@@ -188,6 +218,10 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
     }
 
     debug!("prek: {}", version::version());
+    if let Some(dir) = cli.globals.cd.as_ref() {
+        debug!("Changed current directory to: `{}`", dir.display());
+    }
+    debug!("Using store at `{}`", store.path().display());
 
     #[cfg(unix)]
     match resource_limit::adjust_open_file_limit() {
@@ -197,19 +231,6 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         }
     }
 
-    // Initialize before `--cd` so a synthetic work tree captures the directory where
-    // git launched the hook. Keep it in prek's own lazy state instead of the
-    // process environment, otherwise user hooks and their nested git commands
-    // would inherit a `GIT_WORK_TREE` that git itself did not expose.
-    // See: https://www.spinics.net/lists/git/msg374197.html
-    //      https://github.com/pre-commit/pre-commit/issues/2295
-    git::init_git_work_tree()?;
-
-    if let Some(dir) = cli.globals.cd.as_ref() {
-        debug!("Changing current directory to: `{}`", dir.display());
-        std::env::set_current_dir(dir)?;
-    }
-
     debug!("Args: {:?}", std::env::args().collect::<Vec<_>>());
 
     macro_rules! show_settings {
@@ -243,7 +264,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.prepare_hooks,
                 args.overwrite,
                 args.allow_missing_config,
-                cli.globals.refresh,
+                cli.globals.refresh.into(),
                 printer,
                 args.git_dir.as_deref(),
             )
@@ -255,7 +276,9 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 cli.globals.config,
                 args.includes,
                 args.skips,
-                cli.globals.refresh,
+                args.output_format,
+                args.dry_run,
+                cli.globals.refresh.into(),
                 printer,
             )
             .await
@@ -275,24 +298,22 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         Command::Run(args) => {
             show_settings!(args);
             let args = *args;
-            let options = args.options;
-            let file_selection = options.file_selection.into();
+            recording::init(
+                args.record.as_deref(),
+                args.replay.as_deref(),
+                GIT_ROOT.as_deref().ok(),
+                store.path(),
+            )?;
 
             cli::run(
                 &store,
-                cli.globals.config,
-                options.includes,
-                options.skips,
-                args.groups,
-                args.no_groups,
-                args.stage,
-                file_selection,
-                options.show_diff_on_failure,
-                flag(options.fail_fast, options.no_fail_fast),
-                options.dry_run,
-                cli.globals.refresh,
-                options.extra,
-                cli.globals.verbose > 0,
+                cli::RunSettings {
+                    config: cli.globals.config,
+                    use_jj: true,
+                    refresh: cli.globals.refresh.into(),
+                    verbose: cli.globals.verbose > 0,
+                    ..cli::RunSettings::from_args(args)
+                },
                 printer,
             )
             .await
@@ -309,8 +330,10 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.no_groups,
                 args.hook_stage,
                 args.language,
+                args.filters,
+                args.tree,
                 args.output_format,
-                cli.globals.refresh,
+                cli.globals.refresh.into(),
                 cli.globals.verbose > 0,
                 printer,
             )
@@ -348,7 +371,34 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             CacheCommand::GC(args) => {
                 cli::cache_gc(&store, args.dry_run, cli.globals.verbose > 0, printer).await
             }
-            CacheCommand::Size(cli::SizeArgs { human }) => cli::cache_size(&store, human, printer),
+            CacheCommand::Size(cli::SizeArgs { human, buckets }) => {
+                cli::cache_size(&store, human, buckets, printer)
+            }
+            CacheCommand::Prune(args) => {
+                cli::cache_prune(
+                    &store,
+                    args.older_than,
+                    args.dry_run,
+                    cli.globals.verbose > 0,
+                    printer,
+                )
+                .await
+            }
+        },
+        Command::Env(EnvNamespace { command }) => match command {
+            EnvCommand::Link(args) => {
+                show_settings!(args);
+
+                cli::env_link(
+                    &store,
+                    cli.globals.config,
+                    args.hook,
+                    args.path,
+                    cli.globals.refresh.into(),
+                    printer,
+                )
+                .await
+            }
         },
         Command::Clean => cli::cache_clean(&store, printer),
         Command::GC(args) => {
@@ -364,7 +414,31 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
 
             cli::validate_manifest(args.manifests, printer)
         }
+        Command::Config(ConfigNamespace { command }) => match command {
+            ConfigCommand::Lint(args) => {
+                show_settings!(args);
+
+                cli::config_lint(&store, cli.globals.config, args.fix, printer).await
+            }
+            ConfigCommand::Schema => cli::config_schema(printer),
+        },
         Command::SampleConfig(args) => cli::sample_config(args.file.into(), args.format, printer),
+        Command::Init(args) => {
+            show_settings!(args);
+
+            cli::init(
+                &store,
+                cli.globals.config,
+                args.format,
+                args.yes,
+                args.no_install,
+                args.run,
+                cli.globals.refresh.into(),
+                cli.globals.verbose > 0,
+                printer,
+            )
+            .await
+        }
         Command::Update(args) => {
             let filesystem = FilesystemOptions::user()?;
             show_settings!(args);
@@ -385,6 +459,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.dry_run || args.check,
                 args.exit_code || args.check,
                 args.cooldown_days,
+                args.output_format,
                 filesystem,
                 printer,
             )
@@ -394,22 +469,105 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             cli::try_repo(
+                &store,
                 cli.globals.config,
                 args.repo,
                 args.rev,
                 args.run_args,
                 args.stage,
-                cli.globals.refresh,
+                cli.globals.refresh.into(),
                 cli.globals.verbose > 0,
                 printer,
             )
             .await
         }
+        Command::Compare(args) => {
+            show_settings!(args);
+
+            cli::compare(
+                &store,
+                args.pre_commit,
+                args.run_args,
+                args.per_hook,
+                cli.globals.verbose > 0,
+                printer,
+            )
+            .await
+        }
+        Command::RenderHook(args) => {
+            show_settings!(args);
+
+            cli::render_hook(
+                &store,
+                cli.globals.config,
+                args.includes,
+                args.skips,
+                args.files,
+                cli.globals.refresh.into(),
+                printer,
+            )
+            .await
+        }
+        Command::ExportConfig(args) => {
+            show_settings!(args);
+
+            cli::export_config(
+                &store,
+                cli.globals.config,
+                args.resolved,
+                cli.globals.refresh.into(),
+                printer,
+            )
+            .await
+        }
+        Command::Plan(args) => {
+            show_settings!(args);
+
+            cli::plan(
+                &store,
+                cli.globals.config,
+                args.includes,
+                args.skips,
+                args.stage,
+                cli.globals.refresh.into(),
+                printer,
+            )
+            .await
+        }
+        Command::Sbom(args) => {
+            show_settings!(args);
+
+            cli::sbom(
+                &store,
+                cli.globals.config,
+                args.format,
+                cli.globals.refresh.into(),
+                printer,
+            )
+            .await
+        }
+        Command::Help(args) => {
+            show_settings!(args);
+
+            cli::help(&args.topic, args.man, printer)
+        }
+        Command::ServerHook(args) => {
+            show_settings!(args);
+
+            cli::server_hook(
+                &store,
+                cli.globals.config,
+                args.stage.into(),
+                args.output_format,
+                printer,
+            )
+            .await
+        }
         Command::Util(UtilNamespace { command }) => match command {
             UtilCommand::Identify(args) => {
                 show_settings!(args);
 
-                cli::identify(&args.paths, args.output_format, printer)
+                cli::identify(cli.globals.config, &args.paths, args.output_format, printer)
             }
             UtilCommand::ListBuiltins(args) => {
                 show_settings!(args);
@@ -425,7 +583,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                     cli.globals.config,
                     args.hook_types,
                     args.no_allow_missing_config,
-                    cli.globals.refresh,
+                    cli.globals.refresh.into(),
                     printer,
                 )
                 .await
@@ -484,7 +642,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 cli.globals.config,
                 args.hook_types,
                 args.no_allow_missing_config,
-                cli.globals.refresh,
+                cli.globals.refresh.into(),
                 printer,
             )
             .await
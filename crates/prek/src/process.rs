@@ -32,15 +32,68 @@ use std::ops::Range;
 use std::path::Path;
 use std::process::Output;
 use std::process::{CommandArgs, CommandEnvs, ExitStatus, Stdio};
+#[cfg(not(windows))]
+use std::sync::LazyLock;
 
 use owo_colors::OwoColorize;
+#[cfg(not(windows))]
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use thiserror::Error;
 use tokio::io::AsyncReadExt;
+#[cfg(not(windows))]
+use tracing::debug;
 use tracing::{enabled, trace};
 
+use crate::recording;
 use crate::run::HookRunOutput;
 #[cfg(not(windows))]
 use crate::run::USE_COLOR;
+#[cfg(not(windows))]
+use crate::warn_user;
+
+/// Whether running commands under a PTY is disabled, forcing piped output.
+#[cfg(not(windows))]
+static NO_PTY: LazyLock<bool> = LazyLock::new(|| no_pty_from_env(&EnvVars));
+
+#[cfg(not(windows))]
+fn no_pty_from_env(env_vars: &impl EnvVarsRead) -> bool {
+    env_vars
+        .var_as_bool(EnvVars::PREK_NO_PTY)
+        .unwrap_or_else(|value| {
+            warn_user!(
+                "Invalid value for {}: {:?}. Expected a boolean value; using default ({:?})",
+                EnvVars::PREK_NO_PTY,
+                value,
+                "false",
+            );
+            Some(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Build the exit status of a process that exited with `code`.
+#[cfg(unix)]
+fn exit_status_from_code(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+
+    ExitStatus::from_raw((code & 0xff) << 8)
+}
+
+/// Build the exit status of a process that exited with `code`.
+#[cfg(windows)]
+fn exit_status_from_code(code: i32) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+
+    ExitStatus::from_raw(code.cast_unsigned())
+}
+
+/// Open a PTY and the stdout/stderr handles to give to the child process.
+#[cfg(not(windows))]
+fn open_pty() -> Result<(prek_pty::Pty, prek_pty::Pts, Stdio, Stdio), Error> {
+    let (pty, pts) = prek_pty::open()?;
+    let (_, stdout, stderr) = pts.setup_subprocess()?;
+    Ok((pty, pts, stdout, stderr))
+}
 
 /// An error from executing a command.
 #[derive(Debug, Error)]
@@ -210,6 +263,43 @@ impl Cmd {
         self.check_status = checked;
         self
     }
+
+    /// Run this command through `program`, passing `args` and then this command's program
+    /// and arguments to it, e.g. to run it inside a sandbox.
+    ///
+    /// The environment, working directory, status checking, and hidden and file-list arguments
+    /// carry over. Stdio does not, so configure it on the returned command.
+    pub fn wrapped<I, S>(&self, program: impl AsRef<OsStr>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut wrapped = Self::new(program);
+        wrapped.args(args);
+        let offset = wrapped.get_args().count() + 1;
+        wrapped.arg(self.get_program()).args(self.get_args());
+
+        for (key, value) in self.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        if let Some(dir) = self.get_current_dir() {
+            wrapped.current_dir(dir);
+        }
+
+        wrapped.hidden_arg_ranges = self
+            .hidden_arg_ranges
+            .iter()
+            .map(|range| range.start + offset..range.end + offset)
+            .collect();
+        if self.file_arg_boundary != usize::MAX {
+            wrapped.file_arg_boundary = self.file_arg_boundary + offset;
+        }
+        wrapped.check_status = self.check_status;
+        wrapped
+    }
 }
 
 /// Execution APIs
@@ -246,6 +336,10 @@ impl Cmd {
         &mut self,
         mut sink: S,
     ) -> Result<MergedOutput, Error> {
+        if let Some(output) = self.replay_captured_output(&mut sink) {
+            return output;
+        }
+
         self.log_command();
         let (mut child, mut reader) = self.spawn_with_output()?;
 
@@ -297,21 +391,39 @@ impl Cmd {
     #[cfg(not(windows))]
     pub(crate) async fn pty_output_with_sink<S: OutputSink>(
         &mut self,
-        sink: S,
+        mut sink: S,
     ) -> Result<MergedOutput, Error> {
-        // If color is not used, fallback to piped output.
-        if !*USE_COLOR {
+        if let Some(output) = self.replay_captured_output(&mut sink) {
+            return output;
+        }
+
+        // If color is not used or PTY is disabled, fallback to piped output.
+        if !*USE_COLOR || *NO_PTY {
             return self.output_with_sink(sink).await;
         }
 
-        self.run_on_pty(sink).await
+        // Some environments (containers without `/dev/pts`, restricted CI runners) can not
+        // allocate a PTY, fallback to piped output instead of failing the hook.
+        let (pty, pts, stdout, stderr) = match open_pty() {
+            Ok(handles) => handles,
+            Err(err) => {
+                debug!("Failed to allocate a PTY, falling back to pipes: {err:?}");
+                return self.output_with_sink(sink).await;
+            }
+        };
+
+        self.run_on_pty(pty, pts, stdout, stderr, sink).await
     }
 
     #[cfg(not(windows))]
-    async fn run_on_pty<S: OutputSink>(&mut self, mut sink: S) -> Result<MergedOutput, Error> {
-        let (mut pty, pts) = prek_pty::open()?;
-        let (_, stdout, stderr) = pts.setup_subprocess()?;
-
+    async fn run_on_pty<S: OutputSink>(
+        &mut self,
+        mut pty: prek_pty::Pty,
+        pts: prek_pty::Pts,
+        stdout: Stdio,
+        stderr: Stdio,
+        mut sink: S,
+    ) -> Result<MergedOutput, Error> {
         self.inner.stdin(Stdio::null());
         self.inner.stdout(stdout);
         self.inner.stderr(stderr);
@@ -378,6 +490,25 @@ impl Cmd {
         self.maybe_check_captured_output(MergedOutput { status, bytes })
     }
 
+    /// Answer the command from a `--replay` recording instead of running it.
+    fn replay_captured_output<S: OutputSink>(
+        &self,
+        sink: &mut S,
+    ) -> Option<Result<MergedOutput, Error>> {
+        let replayed = recording::replay(self)?;
+        Some(
+            replayed
+                .map_err(|cause| self.exec_error(cause))
+                .and_then(|(code, bytes)| {
+                    sink.write_chunk(&bytes);
+                    self.maybe_check_captured_output(MergedOutput {
+                        status: exit_status_from_code(code),
+                        bytes,
+                    })
+                }),
+        )
+    }
+
     /// Equivalent to [`std::process::Command::status`]
     /// but logged, with the error wrapped, and status checked (by default)
     pub async fn status(&mut self) -> Result<ExitStatus, Error> {
@@ -586,6 +717,8 @@ impl Cmd {
     }
 
     fn maybe_check_captured_output(&self, output: MergedOutput) -> Result<MergedOutput, Error> {
+        recording::record(self, output.status.code().unwrap_or(1), &output.bytes);
+
         if self.check_status && !output.status.success() {
             let status = output.status;
             let output = Output {
@@ -675,9 +808,12 @@ fn write_command_line<'a>(
 #[cfg(all(test, not(windows)))]
 mod tests {
     use std::error::Error as _;
+    use std::path::Path;
     use std::sync::{Arc, Mutex};
 
-    use super::{Cmd, OutputSink, write_command_line};
+    use prek_consts::env_vars::EnvVars;
+
+    use super::{Cmd, OutputSink, no_pty_from_env, write_command_line};
 
     #[derive(Default)]
     struct RecordingSink {
@@ -801,6 +937,38 @@ mod tests {
         assert_eq!(cmd.file_arg_boundary, usize::MAX);
     }
 
+    #[test]
+    fn wrapped_keeps_hidden_and_file_args() {
+        let mut cmd = Cmd::new("ruff");
+        cmd.arg("check")
+            .hidden_args(["--quiet"])
+            .env("RUFF_CACHE_DIR", "/tmp/ruff")
+            .current_dir("/repo")
+            .file_args(["a.py", "b.py"]);
+
+        let wrapped = cmd.wrapped("bwrap", ["--die-with-parent", "--"]);
+        assert_eq!(wrapped.to_string(), "bwrap --die-with-parent -- ruff check");
+        assert_eq!(
+            wrapped
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>(),
+            [
+                "--die-with-parent",
+                "--",
+                "ruff",
+                "check",
+                "--quiet",
+                "a.py",
+                "b.py"
+            ]
+        );
+        assert_eq!(wrapped.hidden_arg_ranges, vec![4..5]);
+        assert_eq!(wrapped.file_arg_boundary, 5);
+        assert_eq!(wrapped.get_current_dir(), Some(Path::new("/repo")));
+        assert_eq!(wrapped.get_envs().count(), 1);
+    }
+
     #[tokio::test]
     async fn pty_output_captures_trailing_output_after_fast_exit() {
         for _ in 0..20 {
@@ -817,4 +985,21 @@ mod tests {
             assert_eq!(output, "FINAL\n");
         }
     }
+
+    #[test]
+    fn no_pty_env() {
+        assert!(!no_pty_from_env(&EnvVars::from_map(&[])));
+        assert!(no_pty_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_NO_PTY,
+            "true"
+        )])));
+        assert!(!no_pty_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_NO_PTY,
+            "0"
+        )])));
+        assert!(!no_pty_from_env(&EnvVars::from_map(&[(
+            EnvVars::PREK_NO_PTY,
+            "maybe"
+        )])));
+    }
 }
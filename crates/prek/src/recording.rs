@@ -0,0 +1,200 @@
+//! Record the commands hooks run and replay them later without executing anything.
+//!
+//! `prek run --record <dir>` appends every hook command to `<dir>/invocations.jsonl`, with its
+//! argv, working directory, environment changes, exit code and output. `prek run --replay <dir>`
+//! then answers the same commands from the recording, so the orchestration around hooks (file
+//! selection, batching, reporting) can be tested deterministically or reproduced from a user's
+//! recording. Paths under the git root and the prek home are stored as `$ROOT` and `$PREK_HOME`,
+//! so recordings can be replayed on another machine.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::fs::Simplified;
+use crate::process::Cmd;
+use crate::warn_user_once;
+
+const INVOCATIONS_FILE: &str = "invocations.jsonl";
+
+static RECORDING: OnceLock<Recording> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Invocation {
+    argv: Vec<String>,
+    cwd: Option<String>,
+    /// Environment variables set (or removed, as `null`) on top of the inherited environment.
+    env: BTreeMap<String, Option<String>>,
+    exit_code: i32,
+    output: String,
+}
+
+/// The argv and working directory that identify a command across a recording and its replay.
+type InvocationKey = (Vec<String>, Option<String>);
+
+enum Mode {
+    Record(Mutex<fs_err::File>),
+    /// Recorded invocations by command, consumed in the order they were recorded.
+    Replay(Mutex<FxHashMap<InvocationKey, VecDeque<Invocation>>>),
+}
+
+struct Recording {
+    dir: PathBuf,
+    mode: Mode,
+    /// Path prefixes replaced with placeholders, longest first.
+    placeholders: Vec<(String, &'static str)>,
+}
+
+impl Recording {
+    fn normalize(&self, value: &str) -> String {
+        let mut value = value.to_string();
+        for (prefix, placeholder) in &self.placeholders {
+            value = value.replace(prefix.as_str(), placeholder);
+        }
+        value
+    }
+
+    fn key(&self, cmd: &Cmd) -> InvocationKey {
+        let argv = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| self.normalize(&arg.to_string_lossy()))
+            .collect();
+        let cwd = cmd
+            .get_current_dir()
+            .map(|cwd| self.normalize(&cwd.to_string_lossy()));
+        (argv, cwd)
+    }
+}
+
+/// Start recording hook commands into `record`, or replaying them from `replay`.
+pub(crate) fn init(
+    record: Option<&Path>,
+    replay: Option<&Path>,
+    git_root: Option<&Path>,
+    store: &Path,
+) -> Result<()> {
+    let (dir, mode) = match (record, replay) {
+        (Some(dir), _) => {
+            fs_err::create_dir_all(dir)?;
+            let file = fs_err::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dir.join(INVOCATIONS_FILE))?;
+            (dir, Mode::Record(Mutex::new(file)))
+        }
+        (None, Some(dir)) => {
+            let path = dir.join(INVOCATIONS_FILE);
+            let content = fs_err::read_to_string(&path)?;
+            let mut invocations: FxHashMap<InvocationKey, VecDeque<Invocation>> =
+                FxHashMap::default();
+            for (idx, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let invocation: Invocation = serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Invalid invocation on line {} of `{}`",
+                        idx + 1,
+                        path.user_display()
+                    )
+                })?;
+                invocations
+                    .entry((invocation.argv.clone(), invocation.cwd.clone()))
+                    .or_default()
+                    .push_back(invocation);
+            }
+            (dir, Mode::Replay(Mutex::new(invocations)))
+        }
+        (None, None) => return Ok(()),
+    };
+
+    let mut placeholders = vec![(store.to_string_lossy().into_owned(), "$PREK_HOME")];
+    if let Some(git_root) = git_root {
+        placeholders.push((git_root.to_string_lossy().into_owned(), "$ROOT"));
+    }
+    placeholders.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+    let _ = RECORDING.set(Recording {
+        dir: dir.to_path_buf(),
+        mode,
+        placeholders,
+    });
+    Ok(())
+}
+
+/// Answer `cmd` from the recording being replayed.
+///
+/// Returns `None` when not replaying, otherwise the recorded exit code and output. A command
+/// that was recorded several times is answered in recording order, repeating the last answer
+/// once they run out.
+pub(crate) fn replay(cmd: &Cmd) -> Option<std::io::Result<(i32, Vec<u8>)>> {
+    let recording = RECORDING.get()?;
+    let Mode::Replay(invocations) = &recording.mode else {
+        return None;
+    };
+
+    let key = recording.key(cmd);
+    let mut invocations = invocations.lock().unwrap();
+    let Some(recorded) = invocations.get_mut(&key) else {
+        return Some(Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "No recorded invocation of this command in `{}`",
+                recording.dir.user_display()
+            ),
+        )));
+    };
+    let invocation = if recorded.len() > 1 {
+        recorded.pop_front()
+    } else {
+        recorded.front().cloned()
+    }
+    .expect("recorded invocations are never empty");
+
+    trace!(argv = ?invocation.argv, "Replaying recorded invocation");
+    Some(Ok((invocation.exit_code, invocation.output.into_bytes())))
+}
+
+/// Append `cmd` and its outcome to the recording, if recording.
+pub(crate) fn record(cmd: &Cmd, exit_code: i32, output: &[u8]) {
+    let Some(recording) = RECORDING.get() else {
+        return;
+    };
+    let Mode::Record(file) = &recording.mode else {
+        return;
+    };
+
+    let (argv, cwd) = recording.key(cmd);
+    let env = cmd
+        .get_envs()
+        .map(|(key, value)| {
+            (
+                key.to_string_lossy().into_owned(),
+                value.map(|value| recording.normalize(&value.to_string_lossy())),
+            )
+        })
+        .collect();
+    let invocation = Invocation {
+        argv,
+        cwd,
+        env,
+        exit_code,
+        output: String::from_utf8_lossy(output).into_owned(),
+    };
+
+    let result = serde_json::to_string(&invocation)
+        .map_err(std::io::Error::other)
+        .and_then(|line| writeln!(file.lock().unwrap(), "{line}"));
+    if let Err(err) = result {
+        warn_user_once!(
+            "Failed to record invocation into `{}`: {err}",
+            recording.dir.user_display()
+        );
+    }
+}
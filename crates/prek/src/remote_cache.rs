@@ -0,0 +1,378 @@
+//! Remote cache of hook environments shared between machines.
+//!
+//! After a hook environment is installed, it is archived and uploaded under a key derived from
+//! everything that determines its contents, next to the SHA256 digest of the archive. Before
+//! installing an environment, prek first tries to download one with the same key, so a cold CI
+//! runner can skip most installs. Archives that do not match their digest are discarded.
+//!
+//! Only environments are cached, not toolchains, and only over plain HTTP `GET` and `PUT`;
+//! uploading is opt-in, meant for trusted CI runners.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result, bail};
+use async_compression::tokio::write::GzipEncoder;
+use aws_lc_rs::digest::{SHA256, digest};
+use futures_util::TryStreamExt;
+use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use reqwest::StatusCode;
+use target_lexicon::HOST;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio_util::io::{ReaderStream, StreamReader};
+use tracing::{debug, warn};
+
+use crate::archive;
+use crate::checksum::{HashReader, Sha256Digest};
+use crate::hook::{Hook, InstallInfo};
+use crate::http::REQWEST_CLIENT;
+use crate::settings::{FilesystemOptions, RemoteCacheOptions};
+use crate::store::Store;
+use crate::warn_user;
+
+/// Directory that holds the environment inside a cache archive.
+const ARCHIVE_ROOT: &str = "env";
+
+static REMOTE_CACHE: LazyLock<Option<RemoteCache>> =
+    LazyLock::new(|| match FilesystemOptions::user() {
+        Ok(options) => options.and_then(|options| {
+            options
+                .remote_cache()
+                .map(|remote_cache| RemoteCache::new(remote_cache, &EnvVars))
+        }),
+        Err(err) => {
+            warn_user!("{err:#}; ignoring the remote cache");
+            None
+        }
+    });
+
+/// An HTTP server that stores environment archives with `GET` and `PUT` requests.
+#[derive(Debug)]
+pub(crate) struct RemoteCache {
+    url: String,
+    token: Option<String>,
+    upload: bool,
+}
+
+impl RemoteCache {
+    /// The remote cache configured in the global config, if any.
+    pub(crate) fn from_settings() -> Option<&'static Self> {
+        REMOTE_CACHE.as_ref()
+    }
+
+    fn new(options: &RemoteCacheOptions, env_vars: &impl EnvVarsRead) -> Self {
+        Self {
+            url: options.url.trim_end_matches('/').to_string(),
+            token: env_vars
+                .var(EnvVars::PREK_REMOTE_CACHE_TOKEN)
+                .ok()
+                .filter(|token| !token.is_empty()),
+            upload: options.upload(),
+        }
+    }
+
+    /// Compute the cache key of the environment for `hook`.
+    ///
+    /// Only hooks from remote repositories are cached, because a pinned `rev` is the only thing
+    /// that guarantees two machines install the same code. The store path is part of the key,
+    /// since environments contain absolute paths and only work where they were created.
+    /// Environments also link to their toolchain, so the key includes the toolchain version and
+    /// libc; languages that cannot resolve their toolchain before installing are not cached.
+    async fn key(hook: &Hook, store: &Store) -> Option<String> {
+        let repo = hook.repo().identity()?;
+        let toolchain = match hook.language.toolchain_version(store, hook).await {
+            Ok(toolchain) => toolchain?,
+            Err(err) => {
+                debug!("Failed to resolve the toolchain of hook `{hook}`: {err:#}");
+                return None;
+            }
+        };
+
+        let mut input = String::new();
+        let _ = writeln!(input, "prek {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(
+            input,
+            "platform {}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        let _ = writeln!(input, "libc {}", HOST.environment);
+        let _ = writeln!(input, "store {}", store.hooks_dir().display());
+        let _ = writeln!(input, "language {}", hook.language);
+        let _ = writeln!(input, "language_version {}", hook.language_version);
+        let _ = writeln!(input, "toolchain {toolchain}");
+        let _ = writeln!(input, "repo {repo}");
+        for dependency in hook.env_dependencies() {
+            let _ = writeln!(input, "dependency {dependency}");
+        }
+        for command in &hook.setup {
+            let _ = writeln!(input, "setup {command}");
+        }
+
+        Some(hex::encode(digest(&SHA256, input.as_bytes())))
+    }
+
+    fn archive_url(&self, key: &str) -> String {
+        format!("{}/{key}.tar.gz", self.url)
+    }
+
+    fn digest_url(&self, key: &str) -> String {
+        format!("{}/{key}.tar.gz.sha256", self.url)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Download and unpack the environment for `hook` into the store.
+    ///
+    /// Returns `None` on a cache miss. Failures are logged and also treated as a miss, so the
+    /// environment is installed normally.
+    pub(crate) async fn restore(&self, store: &Store, hook: &Hook) -> Option<InstallInfo> {
+        let key = Self::key(hook, store).await?;
+        match self.try_restore(store, hook, &key).await {
+            Ok(info) => info,
+            Err(err) => {
+                warn!(
+                    "Failed to restore environment for hook `{hook}` from the remote cache: {err:#}"
+                );
+                None
+            }
+        }
+    }
+
+    async fn try_restore(
+        &self,
+        store: &Store,
+        hook: &Hook,
+        key: &str,
+    ) -> Result<Option<InstallInfo>> {
+        // The digest is uploaded after the archive, so an entry without one is incomplete.
+        let Some(expected) = self.fetch_digest(key).await? else {
+            debug!("Remote cache miss for hook `{hook}`");
+            return Ok(None);
+        };
+
+        let url = self.archive_url(key);
+        debug!(url = %url, "Downloading hook environment from the remote cache");
+        let response = self
+            .authorize(REQWEST_CLIENT.get(&url))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to download {url}"))?;
+
+        fs_err::tokio::create_dir_all(store.scratch_path()).await?;
+        let temp_dir = tempfile::tempdir_in(store.scratch_path())?;
+        let archive = temp_dir.path().join(format!("{key}.tar.gz"));
+        let stream = response.bytes_stream().map_err(std::io::Error::other);
+        let mut reader = HashReader::new(StreamReader::new(stream));
+        let mut file = fs_err::tokio::File::create(&archive).await?;
+        tokio::io::copy(&mut reader, &mut file)
+            .await
+            .with_context(|| format!("Failed to download {url}"))?;
+        file.flush().await?;
+        expected.verify(reader.finish(), &url)?;
+
+        let file = fs_err::tokio::File::open(&archive).await?;
+        let env_dir = unpack_env(file, temp_dir.path()).await?;
+
+        let info = InstallInfo::from_env_path(&env_dir)
+            .await
+            .context("Failed to read install info from the cached environment")?;
+        if !hook
+            .environment_requirement()
+            .is_some_and(|requirement| requirement.is_satisfied_by(&info))
+        {
+            bail!("Cached environment does not match the hook");
+        }
+        if info.env_path.parent() != Some(store.hooks_dir().as_path()) {
+            bail!(
+                "Cached environment belongs to another store: `{}`",
+                info.env_path.display()
+            );
+        }
+        if info.env_path.exists() {
+            bail!(
+                "Cached environment path `{}` already exists",
+                info.env_path.display()
+            );
+        }
+
+        fs_err::tokio::rename(&env_dir, &info.env_path).await?;
+        if let Err(err) = info.check_health().await {
+            fs_err::tokio::remove_dir_all(&info.env_path).await?;
+            return Err(err.context("Cached environment is unhealthy"));
+        }
+
+        debug!(
+            "Restored environment for hook `{hook}` from the remote cache to `{}`",
+            info.env_path.display()
+        );
+        Ok(Some(info))
+    }
+
+    /// Download the digest of the archive stored under `key`, or `None` if there is none.
+    async fn fetch_digest(&self, key: &str) -> Result<Option<Sha256Digest>> {
+        let url = self.digest_url(key);
+        debug!(url = %url, "Looking up hook environment in the remote cache");
+
+        let response = self
+            .authorize(REQWEST_CLIENT.get(&url))
+            .send()
+            .await
+            .with_context(|| format!("Failed to request {url}"))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let digest = response
+            .error_for_status()
+            .with_context(|| format!("Failed to download {url}"))?
+            .text()
+            .await
+            .with_context(|| format!("Failed to download {url}"))?;
+        digest
+            .parse::<Sha256Digest>()
+            .with_context(|| format!("Invalid digest at {url}"))
+            .map(Some)
+    }
+
+    /// Archive a newly installed environment for `hook` and upload it.
+    ///
+    /// Failures are logged and otherwise ignored, since the environment itself is usable.
+    pub(crate) async fn upload(&self, store: &Store, hook: &Hook, info: &InstallInfo) {
+        if !self.upload {
+            return;
+        }
+        let Some(key) = Self::key(hook, store).await else {
+            return;
+        };
+        if let Err(err) = self.try_upload(store, info, &key).await {
+            warn!("Failed to upload environment for hook `{hook}` to the remote cache: {err:#}");
+        }
+    }
+
+    async fn try_upload(&self, store: &Store, info: &InstallInfo, key: &str) -> Result<()> {
+        fs_err::tokio::create_dir_all(store.scratch_path()).await?;
+        let temp_dir = tempfile::tempdir_in(store.scratch_path())?;
+        let path = temp_dir.path().join(format!("{key}.tar.gz"));
+        archive_env(&info.env_path, &path).await?;
+
+        let mut reader = HashReader::new(fs_err::tokio::File::open(&path).await?);
+        tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+        let digest = reader.finish();
+
+        let file = fs_err::tokio::File::open(&path).await?;
+        let len = file.metadata().await?.len();
+        let url = self.archive_url(key);
+        debug!(url = %url, size = len, "Uploading hook environment to the remote cache");
+        self.authorize(REQWEST_CLIENT.put(&url))
+            .header(reqwest::header::CONTENT_LENGTH, len)
+            .body(reqwest::Body::wrap_stream(ReaderStream::new(file)))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to upload {url}"))?;
+
+        // Upload the digest last, since its presence marks the entry as complete.
+        let url = self.digest_url(key);
+        self.authorize(REQWEST_CLIENT.put(&url))
+            .body(digest.to_string())
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to upload {url}"))?;
+
+        Ok(())
+    }
+}
+
+/// Write the environment at `env_path` to a `.tar.gz` archive at `dest`.
+async fn archive_env(env_path: &Path, dest: &Path) -> Result<()> {
+    let file = fs_err::tokio::File::create(dest).await?;
+    let mut builder = tokio_tar::Builder::new_non_terminated(GzipEncoder::new(file));
+    // Environments link to their toolchains; keep the links instead of copying the toolchains.
+    builder.follow_symlinks(false);
+    builder
+        .append_dir_all(ARCHIVE_ROOT, env_path)
+        .await
+        .with_context(|| format!("Failed to archive `{}`", env_path.display()))?;
+    let mut encoder = builder.into_inner().await?;
+    encoder.shutdown().await?;
+    Ok(())
+}
+
+/// Unpack an archive written by [`archive_env`] into `target`, returning the environment path.
+async fn unpack_env(reader: impl AsyncRead + Unpin, target: &Path) -> Result<PathBuf> {
+    archive::untar_gz(reader, target)
+        .await
+        .context("Failed to unpack cached environment")?;
+    let env_dir = target.join(ARCHIVE_ROOT);
+    if !env_dir.is_dir() {
+        bail!("Cached archive does not contain an environment");
+    }
+    Ok(env_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use prek_consts::env_vars::EnvVars;
+
+    use super::{RemoteCache, archive_env, unpack_env};
+    use crate::settings::RemoteCacheOptions;
+
+    #[tokio::test]
+    async fn archive_round_trip() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let env = temp.path().join("python-abc");
+        fs_err::create_dir_all(env.join("bin"))?;
+        fs_err::write(env.join("bin").join("tool"), "#!/bin/sh\n")?;
+        fs_err::write(env.join(".prek-hook.json"), "{}")?;
+
+        let archive = temp.path().join("env.tar.gz");
+        archive_env(&env, &archive).await?;
+
+        let target = temp.path().join("unpacked");
+        fs_err::create_dir_all(&target)?;
+        let file = fs_err::tokio::File::open(&archive).await?;
+        let env_dir = unpack_env(file, &target).await?;
+
+        assert_eq!(
+            fs_err::read_to_string(env_dir.join("bin").join("tool"))?,
+            "#!/bin/sh\n"
+        );
+        assert_eq!(
+            fs_err::read_to_string(env_dir.join(".prek-hook.json"))?,
+            "{}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn archive_url_joins_base_url() {
+        let options: RemoteCacheOptions =
+            toml::from_str(r#"url = "https://cache.example.com/prek/""#).unwrap();
+        let cache = RemoteCache::new(&options, &EnvVars::from_map(&[]));
+        assert_eq!(
+            cache.archive_url("abc"),
+            "https://cache.example.com/prek/abc.tar.gz"
+        );
+        assert_eq!(
+            cache.digest_url("abc"),
+            "https://cache.example.com/prek/abc.tar.gz.sha256"
+        );
+        assert!(cache.token.is_none());
+        assert!(!cache.upload);
+
+        let cache = RemoteCache::new(
+            &options,
+            &EnvVars::from_map(&[(EnvVars::PREK_REMOTE_CACHE_TOKEN, "secret")]),
+        );
+        assert_eq!(cache.token.as_deref(), Some("secret"));
+    }
+}
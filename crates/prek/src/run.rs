@@ -1,16 +1,22 @@
 use std::cmp::max;
 use std::ffi::{OsStr, OsString};
 use std::path::Path;
+use std::process::Stdio;
 use std::sync::LazyLock;
 
 use anstream::ColorChoice;
 use futures_util::{StreamExt, TryStreamExt};
+use mea::semaphore::{Semaphore, SemaphorePermit};
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use rustc_hash::FxHashMap;
 use tracing::trace;
 
-use crate::config::PassFilenames;
-use crate::hook::Hook;
+use crate::cli::run::HookRunReporter;
+use crate::config::{Language, PassFilenames};
+use crate::hook::{Hook, InstalledHook};
+use crate::process::Cmd;
+use crate::sandbox::Sandbox;
+use crate::settings::FilesystemOptions;
 use crate::warn_user;
 
 pub(crate) static USE_COLOR: LazyLock<bool> =
@@ -61,6 +67,51 @@ pub(crate) static HOOK_CONCURRENCY: LazyLock<usize> =
 pub(crate) static BATCH_CONCURRENCY: LazyLock<usize> =
     LazyLock::new(|| resolve_concurrency(&EnvVars, EnvVars::PREK_CONCURRENT_BATCHES));
 
+/// Per-language concurrency caps from the `concurrency` table of the global config.
+static LANGUAGE_CONCURRENCY: LazyLock<FxHashMap<Language, usize>> =
+    LazyLock::new(|| match FilesystemOptions::user() {
+        Ok(options) => options
+            .map(|options| {
+                options
+                    .language_concurrency()
+                    .iter()
+                    .map(|(language, cap)| (*language, cap.get()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Err(err) => {
+            warn_user!("{err:#}; ignoring per-language concurrency limits");
+            FxHashMap::default()
+        }
+    });
+
+/// Semaphores enforcing the per-language concurrency caps, on top of the global limits.
+#[derive(Default)]
+pub(crate) struct LanguageLimits(FxHashMap<Language, Semaphore>);
+
+impl LanguageLimits {
+    /// Limits configured in the global config.
+    pub(crate) fn from_settings() -> Self {
+        Self::new(&LANGUAGE_CONCURRENCY)
+    }
+
+    fn new(caps: &FxHashMap<Language, usize>) -> Self {
+        Self(
+            caps.iter()
+                .map(|(language, cap)| (*language, Semaphore::new(*cap)))
+                .collect(),
+        )
+    }
+
+    /// Wait for a slot for `language`, or return `None` if the language is not limited.
+    pub(crate) async fn acquire(&self, language: Language) -> Option<SemaphorePermit<'_>> {
+        match self.0.get(&language) {
+            Some(semaphore) => Some(semaphore.acquire(1).await),
+            None => None,
+        }
+    }
+}
+
 /// Iterator that yields partitions of filenames that fit within the maximum command line length.
 struct Partitions<'a> {
     filenames: &'a [&'a Path],
@@ -254,6 +305,7 @@ impl<'a> Iterator for Partitions<'a> {
     }
 }
 
+/// The combined exit code and output of all batches of a hook run.
 #[derive(Debug, Default)]
 pub(crate) struct HookRunOutput {
     exit_code: i32,
@@ -264,13 +316,139 @@ impl HookRunOutput {
     pub(crate) fn new(exit_code: i32, output: Vec<u8>) -> Self {
         Self { exit_code, output }
     }
+}
+
+/// Combines the outputs of all batches of a hook run.
+///
+/// A header or footer block that a later batch prints exactly like the first batch, such as a
+/// deprecation warning emitted by every invocation, is dropped from the later batch and listed
+/// once with a count at the end instead. A batch whose whole output repeats the first batch is
+/// kept as is, since it may be the same real error reported for different files.
+#[derive(Debug, Default)]
+struct BatchOutputs {
+    combined: HookRunOutput,
+    /// Lines of the first batch, without trailing whitespace.
+    first: Option<Vec<Vec<u8>>>,
+    /// Lines dropped from later batches, with the number of batches that printed them, in the
+    /// order they were first dropped.
+    repeated: Vec<(Vec<u8>, usize)>,
+}
+
+impl BatchOutputs {
+    fn append(&mut self, output: HookRunOutput) {
+        self.combined.exit_code |= output.exit_code;
+
+        let lines = output
+            .output
+            .split_inclusive(|&b| b == b'\n')
+            .collect::<Vec<_>>();
+        let Some(first) = &self.first else {
+            self.first = Some(
+                lines
+                    .iter()
+                    .map(|line| line.trim_ascii_end().to_vec())
+                    .collect(),
+            );
+            self.combined.output.extend_from_slice(&output.output);
+            return;
+        };
+
+        let same = |(line, first): (&&[u8], &Vec<u8>)| line.trim_ascii_end() == first.as_slice();
+        let header = lines.iter().zip(first).take_while(same).count();
+        let footer = lines
+            .iter()
+            .rev()
+            .zip(first.iter().rev())
+            .take(lines.len().min(first.len()) - header)
+            .take_while(same)
+            .count();
+        if header + footer == lines.len() {
+            self.combined.output.extend_from_slice(&output.output);
+            return;
+        }
+
+        let (head, rest) = lines.split_at(header);
+        let (body, tail) = rest.split_at(rest.len() - footer);
+        // Blank lines separate the output of batches, keep them.
+        let mut drop_repeated = |line: &[u8], combined: &mut Vec<u8>| {
+            let key = line.trim_ascii_end();
+            if key.is_empty() {
+                combined.extend_from_slice(line);
+                return;
+            }
+            match self
+                .repeated
+                .iter_mut()
+                .find(|(repeated, _)| repeated == key)
+            {
+                Some((_, count)) => *count += 1,
+                None => self.repeated.push((key.to_vec(), 2)),
+            }
+        };
+        for line in head {
+            drop_repeated(line, &mut self.combined.output);
+        }
+        for line in body {
+            self.combined.output.extend_from_slice(line);
+        }
+        for line in tail {
+            drop_repeated(line, &mut self.combined.output);
+        }
+    }
+
+    fn finish(self) -> HookRunOutput {
+        let mut combined = self.combined;
+        if self.repeated.is_empty() {
+            return combined;
+        }
+
+        if !combined.output.ends_with(b"\n") {
+            combined.output.push(b'\n');
+        }
+        combined
+            .output
+            .extend_from_slice(b"\nLines repeated across batches (shown once above):\n");
+        for (line, count) in &self.repeated {
+            combined
+                .output
+                .extend_from_slice(format!("  ({count} batches) ").as_bytes());
+            combined.output.extend_from_slice(line);
+            combined.output.push(b'\n');
+        }
+        combined
+    }
+}
 
-    fn append(&mut self, output: Self) {
-        self.exit_code |= output.exit_code;
-        self.output.extend(output.output);
+/// The number of batches of a hook that may run concurrently.
+pub(crate) fn batch_concurrency(hook: &Hook) -> usize {
+    if hook.require_serial {
+        1
+    } else {
+        *BATCH_CONCURRENCY
     }
 }
 
+/// Split filenames into the batches [`run_by_batch`] would run the hook with.
+///
+/// This only computes the command lines, so callers can inspect them without executing anything.
+pub(crate) fn partition_filenames<'a>(
+    hook: &'a Hook,
+    entry: &[OsString],
+    filenames: &'a [&'a Path],
+) -> anyhow::Result<Vec<&'a [&'a Path]>> {
+    let partitions = Partitions::split(hook, entry, filenames, batch_concurrency(hook))?;
+    Ok(partitions.collect())
+}
+
+/// Run `run` once per batch of `filenames`, with up to [`batch_concurrency`] batches in flight.
+///
+/// Batches are sized to fit the platform command-line limit for `entry`, the hook's `args`, and
+/// its `env`. The outputs are concatenated in batch order and the exit codes are bitwise ORed,
+/// so the hook fails if any batch fails. A header or footer that later batches repeat from the
+/// first batch is only kept the first time and summarized at the end. Without filenames, `run`
+/// is called once with an empty batch.
+///
+/// Most languages should use [`run_hook_entry`], which builds the batch command as well.
 pub(crate) async fn run_by_batch<T, F>(
     hook: &Hook,
     filenames: &[&Path],
@@ -281,11 +459,7 @@ where
     F: for<'a> AsyncFn(&'a [&'a Path]) -> anyhow::Result<T>,
     T: Into<HookRunOutput> + Send + 'static,
 {
-    let concurrency = if hook.require_serial {
-        1
-    } else {
-        *BATCH_CONCURRENCY
-    };
+    let concurrency = batch_concurrency(hook);
 
     // Split files into batches
     let partitions = Partitions::split(hook, entry, filenames, concurrency)?;
@@ -300,22 +474,215 @@ where
     let output = futures_util::stream::iter(partitions)
         .map(|batch| run(batch))
         .buffered(concurrency)
-        .try_fold(
-            HookRunOutput::default(),
-            |mut combined, output| async move {
-                combined.append(output.into());
-                anyhow::Ok(combined)
-            },
-        )
-        .await?;
+        .try_fold(BatchOutputs::default(), |mut combined, output| async move {
+            combined.append(output.into());
+            anyhow::Ok(combined)
+        })
+        .await?
+        .finish();
 
     Ok((output.exit_code, output.output))
 }
 
+/// Run a hook's resolved `entry` over `filenames` in batches, reporting progress to `reporter`.
+///
+/// Each batch runs `entry` followed by the hook's `args` and the batch filenames, from the hook's
+/// [run directory](Hook::run_dir) with stdin closed, and streams its output through a PTY when available.
+/// `configure` sets up the language environment of each batch command, such as `PATH`. The hook's
+/// `env` is applied after it, so users can override anything the language sets. Hooks with
+/// `isolation: container` run inside a [`Sandbox`].
+pub(crate) async fn run_hook_entry(
+    hook: &InstalledHook,
+    entry: &[OsString],
+    filenames: &[&Path],
+    reporter: &HookRunReporter,
+    configure: impl Fn(&mut Cmd),
+) -> anyhow::Result<(i32, Vec<u8>)> {
+    let sandbox = Sandbox::for_hook(hook)?;
+    let progress = reporter.on_run_start(hook, filenames.len());
+
+    let run = async |batch: &[&Path]| {
+        let mut cmd = Cmd::new(&entry[0]);
+        cmd.current_dir(hook.run_dir()).args(&entry[1..]);
+        configure(&mut cmd);
+        cmd.envs(hook.output_encoding_env());
+        cmd.envs(&hook.env).args(&hook.args).file_args(batch);
+        if let Some(sandbox) = &sandbox {
+            cmd = sandbox.wrap(&cmd);
+        }
+        let output = cmd
+            .check(false)
+            .stdin(Stdio::null())
+            .pty_output_with_sink(reporter.output_sink(progress))
+            .await?;
+
+        reporter.on_run_progress(progress, batch.len() as u64);
+
+        anyhow::Ok(output)
+    };
+
+    let output = run_by_batch(hook, filenames, entry, run).await?;
+
+    reporter.on_run_complete(progress);
+
+    Ok(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::borrow::Cow;
+    use std::num::NonZeroUsize;
     use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use prek_consts::PRE_COMMIT_CONFIG_YAML;
+
+    use crate::config::{HookOptions, Language};
+    use crate::hook::{HookBuilder, HookSpec, Repo};
+    use crate::workspace::Project;
+
+    async fn build_test_hook(options: HookOptions) -> anyhow::Result<(tempfile::TempDir, Hook)> {
+        let temp = tempfile::tempdir()?;
+        let config_path = temp.path().join(PRE_COMMIT_CONFIG_YAML);
+        fs_err::write(&config_path, "repos: []\n")?;
+
+        let project = Arc::new(Project::from_config_file(
+            Cow::Borrowed(&config_path),
+            None,
+        )?);
+        let repo = Arc::new(Repo::Local { hooks: vec![] });
+        let hook_spec = HookSpec {
+            id: "batched".to_string(),
+            name: "batched".to_string(),
+            entry: "echo".to_string(),
+            language: Language::System,
+            priority: None,
+            groups: None,
+            options,
+        };
+        let hook = HookBuilder::new(project, repo, hook_spec, 0)
+            .build()
+            .await?;
+
+        Ok((temp, hook))
+    }
+
+    #[tokio::test]
+    async fn run_by_batch_combines_batches_in_order() -> anyhow::Result<()> {
+        let (_temp, hook) = build_test_hook(HookOptions {
+            pass_filenames: Some(PassFilenames::Limited(NonZeroUsize::new(2).unwrap())),
+            ..Default::default()
+        })
+        .await?;
+
+        let files: Vec<PathBuf> = (0..5).map(|i| PathBuf::from(format!("f{i}"))).collect();
+        let filenames: Vec<&Path> = files.iter().map(PathBuf::as_path).collect();
+        let entry = [OsString::from("echo")];
+
+        let (exit_code, output) =
+            run_by_batch(&hook, &filenames, &entry, async |batch: &[&Path]| {
+                let names = batch
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<_>>();
+                // Only the last batch fails.
+                let exit_code = i32::from(batch.contains(&Path::new("f4")));
+                anyhow::Ok(HookRunOutput::new(
+                    exit_code,
+                    format!("[{}]", names.join(",")).into_bytes(),
+                ))
+            })
+            .await?;
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(output, b"[f0,f1][f2,f3][f4]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn run_by_batch_collapses_lines_repeated_across_batches() -> anyhow::Result<()> {
+        let (_temp, hook) = build_test_hook(HookOptions {
+            pass_filenames: Some(PassFilenames::Limited(NonZeroUsize::new(1).unwrap())),
+            ..Default::default()
+        })
+        .await?;
+
+        let filenames = [Path::new("a.py"), Path::new("b.py"), Path::new("c.py")];
+        let entry = [OsString::from("echo")];
+
+        let (_, output) = run_by_batch(&hook, &filenames, &entry, async |batch: &[&Path]| {
+            let file = batch[0].display();
+            anyhow::Ok(HookRunOutput::new(
+                0,
+                format!("warning: `foo` is deprecated\n{file}: ok\n\n").into_bytes(),
+            ))
+        })
+        .await?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            indoc::indoc! {"
+                warning: `foo` is deprecated
+                a.py: ok
+
+                b.py: ok
+
+                c.py: ok
+
+
+                Lines repeated across batches (shown once above):
+                  (3 batches) warning: `foo` is deprecated
+            "}
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn run_by_batch_keeps_the_same_error_of_every_batch() -> anyhow::Result<()> {
+        let (_temp, hook) = build_test_hook(HookOptions {
+            pass_filenames: Some(PassFilenames::Limited(NonZeroUsize::new(1).unwrap())),
+            ..Default::default()
+        })
+        .await?;
+
+        let filenames = [Path::new("a.py"), Path::new("b.py")];
+        let entry = [OsString::from("echo")];
+
+        let (exit_code, output) = run_by_batch(&hook, &filenames, &entry, async |_: &[&Path]| {
+            anyhow::Ok(HookRunOutput::new(
+                1,
+                b"error: missing license header\n".to_vec(),
+            ))
+        })
+        .await?;
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(
+            String::from_utf8(output)?,
+            indoc::indoc! {"
+                error: missing license header
+                error: missing license header
+            "}
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn run_by_batch_runs_once_without_filenames() -> anyhow::Result<()> {
+        let (_temp, hook) = build_test_hook(HookOptions::default()).await?;
+        let entry = [OsString::from("echo")];
+
+        let (exit_code, output) = run_by_batch(&hook, &[], &entry, async |batch: &[&Path]| {
+            assert!(batch.is_empty());
+            anyhow::Ok(HookRunOutput::new(0, b"ran".to_vec()))
+        })
+        .await?;
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(output, b"ran");
+        Ok(())
+    }
 
     #[test]
     fn hook_run_output_append_bitwise_ors_exit_codes() {
@@ -536,4 +903,19 @@ mod tests {
         let total_files: usize = all_batches.iter().sum();
         assert_eq!(total_files, 10);
     }
+
+    #[tokio::test]
+    async fn language_limits_cap_only_configured_languages() {
+        use futures_util::FutureExt;
+
+        let limits = LanguageLimits::new(&[(Language::Docker, 1)].into_iter().collect());
+
+        let held = limits.acquire(Language::Docker).await;
+        assert!(held.is_some());
+        assert!(limits.acquire(Language::Docker).now_or_never().is_none());
+        assert!(limits.acquire(Language::Python).await.is_none());
+
+        drop(held);
+        assert!(limits.acquire(Language::Docker).now_or_never().is_some());
+    }
 }
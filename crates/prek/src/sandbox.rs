@@ -0,0 +1,361 @@
+//! Sandboxing of hooks with `isolation: container`.
+//!
+//! The hook command runs in a new mount namespace where the directory it runs from (its project,
+//! or the Git repository for `files_from_repo_root` hooks), except for the Git directories, is the
+//! only writable directory, and only the system directories, the Git directories, and the hook's
+//! own repository, environment, and toolchain are readable. Paths are the same inside and outside
+//! the sandbox, so environments that refer to absolute paths keep working.
+//!
+//! Bubblewrap (`bwrap`) is used when it is on `PATH`. Otherwise the command runs in a throwaway
+//! `busybox:stable` container with the same directories bind-mounted, which needs no
+//! unprivileged user namespaces.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Result, bail};
+use tracing::trace;
+
+use crate::config::Isolation;
+use crate::git;
+use crate::hook::InstalledHook;
+use crate::languages::docker::Docker;
+use crate::process::Cmd;
+use crate::settings::FilesystemOptions;
+use crate::warn_user;
+
+/// Host directories that hooks need to run system binaries, mounted read-only.
+const SYSTEM_DIRS: &[&str] = &[
+    "/usr", "/bin", "/sbin", "/lib", "/lib32", "/lib64", "/etc", "/opt",
+];
+
+/// The image of the container fallback, pulled by the container runtime on first use. Its own
+/// files are hidden by the system directories mounted over them, so any small image works.
+const CONTAINER_IMAGE: &str = "busybox:stable";
+
+/// Scratch directory inside the sandbox, also used as `HOME`.
+const SANDBOX_TMP: &str = "/tmp";
+
+static DEFAULT_ISOLATION: LazyLock<Isolation> = LazyLock::new(|| match FilesystemOptions::user() {
+    Ok(options) => options
+        .and_then(|options| options.isolation())
+        .unwrap_or_default(),
+    Err(err) => {
+        warn_user!("{err:#}; ignoring the default isolation");
+        Isolation::default()
+    }
+});
+
+static BACKEND: LazyLock<Option<Backend>> = LazyLock::new(Backend::detect);
+
+static GIT_DIRS: LazyLock<Result<[PathBuf; 2], git::Error>> = LazyLock::new(git::get_git_dirs);
+
+#[derive(Debug)]
+enum Backend {
+    Bubblewrap(PathBuf),
+    Container(&'static str),
+}
+
+impl Backend {
+    fn detect() -> Option<Self> {
+        if let Ok(bwrap) = which::which("bwrap") {
+            trace!(path = %bwrap.display(), "Sandboxing hooks with bubblewrap");
+            return Some(Self::Bubblewrap(bwrap));
+        }
+        let runtime = Docker::runtime_cmd();
+        if which::which(runtime).is_ok() {
+            trace!("Sandboxing hooks with `{runtime}`");
+            return Some(Self::Container(runtime));
+        }
+        None
+    }
+}
+
+/// A directory that is visible inside the sandbox at the same path.
+#[derive(Debug, PartialEq, Eq)]
+struct Mount {
+    path: PathBuf,
+    writable: bool,
+}
+
+/// The sandbox a hook with `isolation: container` runs in.
+#[derive(Debug)]
+pub(crate) struct Sandbox {
+    backend: &'static Backend,
+    mounts: Vec<Mount>,
+}
+
+impl Sandbox {
+    /// The sandbox to run `hook` in, or `None` if the hook is not isolated.
+    pub(crate) fn for_hook(hook: &InstalledHook) -> Result<Option<Self>> {
+        if hook.isolation.unwrap_or(*DEFAULT_ISOLATION) != Isolation::Container {
+            return Ok(None);
+        }
+        if !cfg!(target_os = "linux") {
+            bail!("Hook `{hook}` has `isolation: container`, which is only supported on Linux");
+        }
+        let Some(backend) = BACKEND.as_ref() else {
+            bail!(
+                "Hook `{hook}` has `isolation: container`, which requires `bwrap` (bubblewrap) or a container runtime on PATH"
+            );
+        };
+
+        Ok(Some(Self {
+            backend,
+            mounts: Self::mounts(hook)?,
+        }))
+    }
+
+    /// Collect the directories of `hook` to expose: the directory it runs from read-write, and
+    /// the Git directories, the hook repository, environment, and toolchain read-only.
+    fn mounts(hook: &InstalledHook) -> Result<Vec<Mount>> {
+        let mut mounts = vec![Mount {
+            path: hook.run_dir().to_path_buf(),
+            writable: true,
+        }];
+
+        // Mounted over the repository, so a hook cannot install Git hooks or rewrite the config
+        // that runs outside the sandbox. Without them, the sandbox would not protect anything.
+        let git_dirs = match GIT_DIRS.as_ref() {
+            Ok(git_dirs) => git_dirs,
+            Err(err) => bail!(
+                "Hook `{hook}` has `isolation: container`, but the Git directories to protect could not be found: {err}"
+            ),
+        };
+        for path in git_dirs {
+            if !mounts.iter().any(|mount| &mount.path == path) {
+                mounts.push(Mount {
+                    path: path.clone(),
+                    writable: false,
+                });
+            }
+        }
+
+        let toolchain_prefix = hook
+            .install_info()
+            .and_then(|info| toolchain_prefix(&info.toolchain));
+        let read_only = [hook.repo_path(), hook.env_path(), toolchain_prefix]
+            .into_iter()
+            .flatten();
+        for path in read_only {
+            let covered = mounts.iter().any(|mount| path.starts_with(&mount.path))
+                || SYSTEM_DIRS.iter().any(|dir| path.starts_with(dir));
+            if !covered {
+                mounts.push(Mount {
+                    path: path.to_path_buf(),
+                    writable: false,
+                });
+            }
+        }
+        Ok(mounts)
+    }
+
+    /// Wrap `cmd` to run inside the sandbox.
+    pub(crate) fn wrap(&self, cmd: &Cmd) -> Cmd {
+        let work_dir = cmd
+            .get_current_dir()
+            .map_or_else(|| self.mounts[0].path.clone(), Path::to_path_buf);
+        match self.backend {
+            Backend::Bubblewrap(bwrap) => cmd.wrapped(bwrap, self.bubblewrap_args(&work_dir)),
+            Backend::Container(runtime) => {
+                // `HOME` is set inside the container, not taken from the hook's environment.
+                let env_keys = cmd
+                    .get_envs()
+                    .filter(|(key, value)| value.is_some() && *key != "HOME")
+                    .map(|(key, _)| key.to_os_string())
+                    .collect::<Vec<_>>();
+                cmd.wrapped(
+                    runtime,
+                    self.container_args(&work_dir, &env_keys, user_args()),
+                )
+            }
+        }
+    }
+
+    fn bubblewrap_args(&self, work_dir: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = [
+            "--die-with-parent",
+            "--unshare-all",
+            "--share-net",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--tmpfs",
+            SANDBOX_TMP,
+            "--setenv",
+            "HOME",
+            SANDBOX_TMP,
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+
+        for dir in SYSTEM_DIRS {
+            // Merged-`/usr` systems link `/bin` and friends into `/usr`.
+            if let Ok(target) = fs_err::read_link(dir) {
+                args.extend(["--symlink".into(), target.into(), (*dir).into()]);
+            } else {
+                args.extend(["--ro-bind-try".into(), (*dir).into(), (*dir).into()]);
+            }
+        }
+        for mount in &self.mounts {
+            let flag = if mount.writable {
+                "--bind"
+            } else {
+                "--ro-bind"
+            };
+            args.extend([
+                flag.into(),
+                mount.path.clone().into(),
+                mount.path.clone().into(),
+            ]);
+        }
+        args.extend(["--chdir".into(), work_dir.into(), "--".into()]);
+        args
+    }
+
+    fn container_args(
+        &self,
+        work_dir: &Path,
+        env_keys: &[OsString],
+        user_args: Vec<OsString>,
+    ) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec!["run".into(), "--rm".into(), "--init".into()];
+        args.extend(user_args);
+        args.extend(["--tmpfs".into(), SANDBOX_TMP.into()]);
+        args.extend(["--env".into(), format!("HOME={SANDBOX_TMP}").into()]);
+        // The runtime passes `--env KEY` through from its own environment.
+        for key in env_keys {
+            args.extend(["--env".into(), key.clone()]);
+        }
+
+        let volumes = SYSTEM_DIRS
+            .iter()
+            .map(Path::new)
+            .filter(|dir| dir.exists())
+            .map(|dir| (dir, false))
+            .chain(
+                self.mounts
+                    .iter()
+                    .map(|mount| (mount.path.as_path(), mount.writable)),
+            );
+        for (path, writable) in volumes {
+            let mut volume = OsString::from(path);
+            volume.push(":");
+            volume.push(path);
+            if !writable {
+                volume.push(":ro");
+            }
+            args.extend(["--volume".into(), volume]);
+        }
+
+        args.extend(["--workdir".into(), work_dir.into(), CONTAINER_IMAGE.into()]);
+        args
+    }
+}
+
+/// The arguments that run the container as the current user.
+fn user_args() -> Vec<OsString> {
+    let mut cmd = Cmd::new("");
+    Docker::add_current_user_args(&mut cmd);
+    cmd.get_args().map(OsString::from).collect()
+}
+
+/// The installation prefix of a toolchain executable, such as `/opt/python3.12` for
+/// `/opt/python3.12/bin/python3`.
+fn toolchain_prefix(toolchain: &Path) -> Option<&Path> {
+    let prefix = toolchain.parent()?.parent()?;
+    // Never expose the whole file system for toolchains such as `/bin/python3`.
+    (prefix.parent().is_some()).then_some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+    use std::path::{Path, PathBuf};
+
+    use super::{Backend, Mount, Sandbox, toolchain_prefix};
+    use crate::process::Cmd;
+
+    fn sandbox(backend: Backend) -> Sandbox {
+        Sandbox {
+            backend: Box::leak(Box::new(backend)),
+            mounts: vec![
+                Mount {
+                    path: PathBuf::from("/work/repo"),
+                    writable: true,
+                },
+                Mount {
+                    path: PathBuf::from("/store/hooks/python-abc"),
+                    writable: false,
+                },
+            ],
+        }
+    }
+
+    fn args(cmd: &Cmd) -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn bubblewrap_binds_mounts_at_the_same_path() {
+        let mut cmd = Cmd::new("ruff");
+        cmd.arg("check").current_dir("/work/repo/app");
+
+        let wrapped = sandbox(Backend::Bubblewrap(PathBuf::from("bwrap"))).wrap(&cmd);
+        let args = args(&wrapped).join(" ");
+        assert!(args.contains("--bind /work/repo /work/repo"), "{args}");
+        assert!(
+            args.contains("--ro-bind /store/hooks/python-abc /store/hooks/python-abc"),
+            "{args}"
+        );
+        assert!(
+            args.ends_with("--chdir /work/repo/app -- ruff check"),
+            "{args}"
+        );
+    }
+
+    #[test]
+    fn container_passes_env_by_name() {
+        let container_args = sandbox(Backend::Container("docker")).container_args(
+            Path::new("/work/repo"),
+            &[OsString::from("PATH")],
+            vec![],
+        );
+        let container_args = container_args
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(container_args.contains("--env PATH"), "{container_args}");
+        assert!(
+            container_args.contains("--env HOME=/tmp "),
+            "{container_args}"
+        );
+        assert!(
+            container_args.contains("--volume /work/repo:/work/repo "),
+            "{container_args}"
+        );
+        assert!(
+            container_args.contains("--volume /store/hooks/python-abc:/store/hooks/python-abc:ro"),
+            "{container_args}"
+        );
+        assert!(
+            container_args.ends_with("--workdir /work/repo busybox:stable"),
+            "{container_args}"
+        );
+    }
+
+    #[test]
+    fn toolchain_prefix_skips_root() {
+        assert_eq!(
+            toolchain_prefix(Path::new("/opt/python3.12/bin/python3")),
+            Some(Path::new("/opt/python3.12"))
+        );
+        assert_eq!(toolchain_prefix(Path::new("/bin/python3")), None);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::{
-    BuiltinHook, BuiltinRepo, FilePattern, LocalHook, LocalRepo, MetaHook, MetaRepo, PassFilenames,
-    RemoteHook, RemoteRepo, Repo, Stage, Stages, StringOrList,
+    BuiltinHook, BuiltinRepo, FilePattern, LanguageVersion, LocalHook, LocalRepo, MetaHook,
+    MetaRepo, PassFilenames, RemoteHook, RemoteRepo, Repo, Stage, Stages, StringOrList,
 };
 use std::borrow::Cow;
 
@@ -223,6 +223,27 @@ impl schemars::JsonSchema for StringOrList {
     }
 }
 
+impl schemars::JsonSchema for LanguageVersion {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("LanguageVersion")
+    }
+
+    fn json_schema(_gen: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "The version of the language to run the hook with. \
+            Either a version, or a map of target triple patterns (such as `aarch64-apple-darwin` \
+            or `*-linux-*`) to versions, where the first pattern matching the host wins.",
+            "oneOf": [
+                {"type": ["string", "number"]},
+                {
+                    "type": "object",
+                    "additionalProperties": {"type": ["string", "number"]}
+                }
+            ]
+        })
+    }
+}
+
 impl schemars::JsonSchema for PassFilenames {
     fn schema_name() -> std::borrow::Cow<'static, str> {
         std::borrow::Cow::Borrowed("PassFilenames")
@@ -287,6 +308,10 @@ impl schemars::JsonSchema for RemoteRepo {
 
     fn json_schema(schema_gen: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
         let hook_schema = schema_gen.subschema_for::<RemoteHook>();
+        let file_pattern_ref = schema_gen
+            .subschema_for::<FilePattern>()
+            .get("$ref")
+            .cloned();
         schemars::json_schema!({
             "type": "object",
             "properties": {
@@ -305,6 +330,14 @@ impl schemars::JsonSchema for RemoteRepo {
                     "items": hook_schema,
                     "writeOnly": true,
                 },
+                "files": {
+                    "description": "File include pattern applied to every hook of this repo, in addition to the hook's own `files`.",
+                    "$ref": file_pattern_ref,
+                },
+                "exclude": {
+                    "description": "File exclude pattern applied to every hook of this repo, in addition to the hook's own `exclude`.",
+                    "$ref": file_pattern_ref,
+                },
             },
             "required": ["repo", "rev", "hooks"],
             "additionalProperties": true,
@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::io::ErrorKind;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
@@ -7,9 +8,10 @@ use anyhow::{Context, Result};
 use etcetera::BaseStrategy;
 use globset::Glob;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
+use rustc_hash::FxHashMap;
 use serde::Deserialize;
 
-use crate::config::{StringOrList, UpdateOptions as ProjectUpdateOptions};
+use crate::config::{Isolation, Language, StringOrList, UpdateOptions as ProjectUpdateOptions};
 
 fn user_config_path() -> Option<PathBuf> {
     if let Some(path) = EnvVars.var_os(EnvVars::PREK_INTERNAL__USER_CONFIG_PATH) {
@@ -84,6 +86,52 @@ impl Deref for FilesystemOptions {
 pub(crate) struct Options {
     #[serde(alias = "auto_update")]
     update: Option<GlobalUpdateOptions>,
+    /// Maximum number of hooks of each language installed or run at the same time.
+    concurrency: FxHashMap<Language, NonZeroUsize>,
+    /// Remote cache of hook environments shared between machines.
+    remote_cache: Option<RemoteCacheOptions>,
+    /// Default `isolation` of hooks that do not set their own.
+    isolation: Option<Isolation>,
+    /// Directory of the store, in place of the platform cache directory.
+    store_path: Option<PathBuf>,
+}
+
+impl Options {
+    /// The per-language concurrency caps.
+    pub(crate) fn language_concurrency(&self) -> &FxHashMap<Language, NonZeroUsize> {
+        &self.concurrency
+    }
+
+    /// The remote cache settings, if a remote cache is configured.
+    pub(crate) fn remote_cache(&self) -> Option<&RemoteCacheOptions> {
+        self.remote_cache.as_ref()
+    }
+
+    /// The default `isolation` of hooks, if configured.
+    pub(crate) fn isolation(&self) -> Option<Isolation> {
+        self.isolation
+    }
+
+    /// The configured store directory, if any.
+    pub(crate) fn store_path(&self) -> Option<&Path> {
+        self.store_path.as_deref()
+    }
+}
+
+/// Remote cache options represented in the global `prek.toml` file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct RemoteCacheOptions {
+    /// Base URL that environment archives are downloaded from and uploaded to.
+    pub(crate) url: String,
+    /// Whether to upload newly installed environments, `false` by default.
+    upload: Option<bool>,
+}
+
+impl RemoteCacheOptions {
+    pub(crate) fn upload(&self) -> bool {
+        self.upload.unwrap_or(false)
+    }
 }
 
 /// Default update options represented in the global `prek.toml` file.
@@ -205,7 +253,7 @@ mod tests {
     use super::{CliTagFilterOptions, FilesystemOptions, Options, UpdateSettings};
     use globset::Glob;
 
-    use crate::config::UpdateOptions as ProjectUpdateOptions;
+    use crate::config::{Isolation, Language, UpdateOptions as ProjectUpdateOptions};
 
     fn glob_pattern(pattern: &str) -> Glob {
         pattern.parse().unwrap()
@@ -260,6 +308,70 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn options_deserializes_language_concurrency() {
+        let options: Options = toml::from_str(
+            r"
+            [concurrency]
+            docker = 1
+            python = 2
+            ",
+        )
+        .unwrap();
+
+        let concurrency = options.language_concurrency();
+        assert_eq!(concurrency.len(), 2);
+        assert_eq!(concurrency[&Language::Docker].get(), 1);
+        assert_eq!(concurrency[&Language::Python].get(), 2);
+
+        let err = toml::from_str::<Options>(
+            r"
+            [concurrency]
+            docker = 0
+            ",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("nonzero"), "{err}");
+    }
+
+    #[test]
+    fn options_deserializes_remote_cache() {
+        let options: Options = toml::from_str(
+            r#"
+            [remote_cache]
+            url = "https://cache.example.com/prek"
+            "#,
+        )
+        .unwrap();
+
+        let remote_cache = options.remote_cache().unwrap();
+        assert_eq!(remote_cache.url, "https://cache.example.com/prek");
+        assert!(!remote_cache.upload());
+
+        let options: Options = toml::from_str(
+            r#"
+            [remote_cache]
+            url = "https://cache.example.com/prek"
+            upload = true
+            "#,
+        )
+        .unwrap();
+        assert!(options.remote_cache().unwrap().upload());
+
+        assert!(toml::from_str::<Options>("[remote_cache]\nupload = true\n").is_err());
+    }
+
+    #[test]
+    fn options_deserializes_isolation() {
+        let options: Options = toml::from_str(r#"isolation = "container""#).unwrap();
+        assert_eq!(options.isolation(), Some(Isolation::Container));
+
+        let options: Options = toml::from_str("").unwrap();
+        assert_eq!(options.isolation(), None);
+
+        assert!(toml::from_str::<Options>(r#"isolation = "vm""#).is_err());
+    }
+
     #[test]
     fn options_deserializes_legacy_update_key_alias() {
         let options: Options = toml::from_str(
@@ -1,10 +1,12 @@
 use std::hash::{Hash, Hasher};
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
 use anyhow::Result;
 use etcetera::BaseStrategy;
 use futures_util::StreamExt;
+use owo_colors::OwoColorize;
 use prek_consts::env_vars::{EnvVars, EnvVarsRead};
 use rustc_hash::{FxHashMap, FxHashSet};
 use seahash::SeaHasher;
@@ -15,6 +17,7 @@ use crate::config::{RemoteRepo, RemoteRepoKey};
 use crate::fs::{LockedFile, expand_tilde};
 use crate::git::{self, TerminalPrompt};
 use crate::run::INTERNAL_CONCURRENCY;
+use crate::settings::FilesystemOptions;
 use crate::warn_user;
 use crate::workspace::{HookInitReporter, WorkspaceCache};
 
@@ -55,10 +58,92 @@ pub enum Error {
     },
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
+    #[error(
+        "Refusing to delete from `{}`, which is not a prek store (no `README` file)",
+        .0.display()
+    )]
+    NotAStore(PathBuf),
+    #[error(
+        "Repository `{repo}` at rev `{rev}` is not trusted\n\n{} Add `{repo} {rev}` as a line to `{}` to trust it, or rerun in an interactive terminal to be prompted.",
+        "hint:".yellow().bold(),
+        .trust_file.display(),
+    )]
+    UntrustedRepo {
+        repo: String,
+        rev: String,
+        trust_file: PathBuf,
+    },
 }
 
 pub(crate) const REPO_MARKER: &str = ".prek-repo.json";
 
+static REQUIRE_TRUST: LazyLock<bool> = LazyLock::new(|| require_trust_from_env(&EnvVars));
+
+fn require_trust_from_env(env_vars: &impl EnvVarsRead) -> bool {
+    env_vars
+        .var_as_bool(EnvVars::PREK_REQUIRE_TRUST)
+        .unwrap_or_else(|value| {
+            warn_user!(
+                "Invalid value for {}: {:?}. Expected a boolean value; using default ({:?})",
+                EnvVars::PREK_REQUIRE_TRUST,
+                value,
+                "false",
+            );
+            Some(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Parse the trust file: one `<repo> <rev>` pair per line, blank lines and `#` comments ignored.
+fn parse_trusted_repos(content: &str) -> FxHashSet<(&str, &str)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let repo = parts.next()?;
+            let rev = parts.next()?;
+            Some((repo, rev))
+        })
+        .collect()
+}
+
+/// The marker file that every store has, checked before deleting anything from a store.
+const STORE_MARKER: &str = "README";
+
+/// The default store directory: `PREK_HOME`, or the platform cache directory.
+fn default_store_path() -> Option<PathBuf> {
+    if let Some(path) = EnvVars.var_os(EnvVars::PREK_HOME) {
+        return Some(expand_tilde(PathBuf::from(path)));
+    }
+    etcetera::choose_base_strategy()
+        .map(|path| path.cache_dir().join("prek"))
+        .ok()
+}
+
+/// The `store_path` of the user-level `prek.toml`.
+///
+/// Project configurations cannot move the store, as `prek cache clean` deletes it.
+fn user_store_path() -> Option<PathBuf> {
+    let options = match FilesystemOptions::user() {
+        Ok(options) => options?,
+        Err(err) => {
+            warn_user!("{err:#}; ignoring `store_path`");
+            return None;
+        }
+    };
+    let path = expand_tilde(options.store_path()?.to_path_buf());
+    if path.is_relative() {
+        warn_user!(
+            "Ignoring `store_path = \"{}\"` in the global config, it must be an absolute path",
+            path.display()
+        );
+        return None;
+    }
+    Some(path)
+}
+
 /// A store for managing repos.
 #[derive(Debug)]
 pub struct Store {
@@ -70,14 +155,17 @@ impl Store {
         Self { path: path.into() }
     }
 
-    /// Create a store from environment variables or default paths.
+    /// Create a store from environment variables, the global configuration or default paths.
+    ///
+    /// The store path is resolved from, in order:
+    /// 1. The `PREK_HOME` environment variable.
+    /// 2. `store_path` in the user-level `prek.toml`.
+    /// 3. The platform cache directory, e.g. `$XDG_CACHE_HOME/prek` on Linux.
     pub(crate) fn from_settings() -> Result<Self, Error> {
-        let path = if let Some(path) = EnvVars.var_os(EnvVars::PREK_HOME) {
-            Some(expand_tilde(PathBuf::from(path)))
+        let path = if EnvVars.var_os(EnvVars::PREK_HOME).is_some() {
+            default_store_path()
         } else {
-            etcetera::choose_base_strategy()
-                .map(|path| path.cache_dir().join("prek"))
-                .ok()
+            user_store_path().or_else(default_store_path)
         };
 
         let Some(path) = path else {
@@ -94,15 +182,26 @@ impl Store {
 
     /// Initialize the store.
     pub(crate) fn init(self) -> Result<Self, Error> {
+        // Only mark new or empty directories as a store, so pointing the store at an existing
+        // directory does not let `prek cache clean` delete it.
+        let is_store = match fs_err::read_dir(&self.path) {
+            Ok(mut entries) => entries.next().is_none(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => true,
+            Err(err) => return Err(err.into()),
+        };
+
         fs_err::create_dir_all(&self.path)?;
         fs_err::create_dir_all(self.repos_dir())?;
         fs_err::create_dir_all(self.hooks_dir())?;
         fs_err::create_dir_all(self.scratch_path())?;
 
+        if !is_store {
+            return Ok(self);
+        }
         match fs_err::OpenOptions::new()
             .write(true)
             .create_new(true)
-            .open(self.path.join("README")) {
+            .open(self.path.join(STORE_MARKER)) {
             Ok(mut f) => f.write_all(b"This directory is maintained by the prek project.\nLearn more: https://github.com/j178/prek\n")?,
             Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => (),
             Err(err) => return Err(err.into()),
@@ -110,6 +209,16 @@ impl Store {
         Ok(self)
     }
 
+    /// Fail unless the store directory has the marker file written by [`Store::init`], before
+    /// deleting anything from it.
+    pub(crate) fn ensure_marked(&self) -> Result<(), Error> {
+        if self.path.join(STORE_MARKER).is_file() {
+            Ok(())
+        } else {
+            Err(Error::NotAStore(self.path.clone()))
+        }
+    }
+
     async fn clone_repo_to_temp(
         &self,
         repo: &RemoteRepo,
@@ -157,12 +266,16 @@ impl Store {
         repos: impl IntoIterator<Item = &'a RemoteRepo>,
         reporter: Option<&dyn HookInitReporter>,
     ) -> Result<FxHashMap<RemoteRepoKey<'a>, PathBuf>, Error> {
+        let repos = repos.into_iter().collect::<Vec<_>>();
+        if *REQUIRE_TRUST {
+            self.ensure_trusted(&repos)?;
+        }
+
         let mut cloned = FxHashMap::default();
         let mut pending = Vec::new();
 
         for repo in repos {
-            let target = self.repo_path(repo);
-            if target.join(REPO_MARKER).try_exists()? {
+            if let Some(target) = self.cloned_repo_path(repo)? {
                 cloned.insert(repo.key(), target);
                 continue;
             }
@@ -268,6 +381,72 @@ impl Store {
         Ok(cloned)
     }
 
+    /// Make sure every repository is listed in the trust file, as required by `PREK_REQUIRE_TRUST`.
+    ///
+    /// Untrusted repositories are confirmed one at a time in an interactive terminal and added
+    /// to the trust file; otherwise the first untrusted repository is reported as an error.
+    fn ensure_trusted(&self, repos: &[&RemoteRepo]) -> Result<(), Error> {
+        let trust_file = self.trusted_repos_file();
+        let content = match fs_err::read_to_string(&trust_file) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let trusted = parse_trusted_repos(&content);
+
+        let mut seen = FxHashSet::default();
+        let untrusted = repos
+            .iter()
+            .map(|repo| (repo.repo(), repo.rev.as_str()))
+            .filter(|key| !trusted.contains(key) && seen.insert(*key))
+            .collect::<Vec<_>>();
+        if untrusted.is_empty() {
+            return Ok(());
+        }
+
+        let interactive = std::io::stdin().is_terminal() && !EnvVars::is_under_ci();
+        for (repo, rev) in untrusted {
+            if !interactive || !Self::confirm_trust(repo, rev)? {
+                return Err(Error::UntrustedRepo {
+                    repo: repo.to_string(),
+                    rev: rev.to_string(),
+                    trust_file,
+                });
+            }
+
+            if let Some(parent) = trust_file.parent() {
+                fs_err::create_dir_all(parent)?;
+            }
+            let mut file = fs_err::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&trust_file)?;
+            writeln!(file, "{repo} {rev}")?;
+            debug!(repo, rev, "Trusted repo");
+        }
+
+        Ok(())
+    }
+
+    fn confirm_trust(repo: &str, rev: &str) -> Result<bool, Error> {
+        let mut stderr = anstream::stderr();
+        write!(
+            stderr,
+            "Repository `{}` at rev `{}` has not been trusted before. Trust it and allow its hooks to run? {} ",
+            repo.cyan(),
+            rev.cyan(),
+            "[y/N]".dimmed()
+        )?;
+        stderr.flush()?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        Ok(matches!(
+            answer.trim().to_ascii_lowercase().as_str(),
+            "y" | "yes"
+        ))
+    }
+
     /// Clone a single remote repository into the store.
     pub(crate) async fn clone_repo(
         &self,
@@ -294,6 +473,16 @@ impl Store {
         self.repos_dir().join(Self::repo_key(repo))
     }
 
+    /// Returns the path of a remote repo if it has already been cloned into the store.
+    pub(crate) fn cloned_repo_path(&self, repo: &RemoteRepo) -> Result<Option<PathBuf>, Error> {
+        let target = self.repo_path(repo);
+        if target.join(REPO_MARKER).try_exists()? {
+            Ok(Some(target))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Returns the store key (directory name) for a remote repo.
     pub(crate) fn repo_key(repo: &RemoteRepo) -> String {
         let mut hasher = SeaHasher::new();
@@ -336,8 +525,18 @@ impl Store {
         self.path.join("scratch")
     }
 
+    /// The default log file, rotated copies are kept next to it.
     pub(crate) fn log_file(&self) -> PathBuf {
-        self.path.join("prek.log")
+        self.path.join("log").join("prek.log")
+    }
+
+    /// The file listing remote repositories trusted under `PREK_REQUIRE_TRUST`.
+    ///
+    /// Always kept in the default store, not in a configured `store_path` or a temporary store.
+    pub(crate) fn trusted_repos_file(&self) -> PathBuf {
+        default_store_path()
+            .unwrap_or_else(|| self.path.clone())
+            .join("trusted-repos.txt")
     }
 
     pub(crate) fn config_tracking_file(&self) -> PathBuf {
@@ -45,6 +45,33 @@ pub(crate) struct VersionInfo {
     ///
     /// `None` if not built from a git repo or if retrieval failed.
     pub(crate) commit_info: Option<CommitInfo>,
+    /// Information about the build itself.
+    pub(crate) build_info: BuildInfo,
+}
+
+/// Information about how prek was built.
+#[derive(Serialize)]
+pub(crate) struct BuildInfo {
+    /// The target triple prek was compiled for, such as "x86_64-unknown-linux-gnu".
+    pub(crate) target: String,
+    /// The date prek was built, honoring `SOURCE_DATE_EPOCH`.
+    pub(crate) date: String,
+    /// The optional Cargo features enabled in this build.
+    pub(crate) features: Vec<&'static str>,
+}
+
+/// The optional Cargo features enabled at compile time.
+fn enabled_features() -> Vec<&'static str> {
+    let features = [
+        ("docker", cfg!(feature = "docker")),
+        ("profiler", cfg!(feature = "profiler")),
+        ("schemars", cfg!(feature = "schemars")),
+        ("self-update", cfg!(feature = "self-update")),
+    ];
+    features
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
 }
 
 impl fmt::Display for VersionInfo {
@@ -63,12 +90,6 @@ impl fmt::Display for VersionInfo {
     }
 }
 
-impl From<VersionInfo> for clap::builder::Str {
-    fn from(val: VersionInfo) -> Self {
-        val.to_string().into()
-    }
-}
-
 /// Returns information about prek's version.
 pub fn version() -> VersionInfo {
     // Environment variables are only read at compile-time
@@ -92,8 +113,16 @@ pub fn version() -> VersionInfo {
             .map_or(0, |value| value.parse::<u32>().unwrap_or(0)),
     });
 
+    // Build info is set by `build.rs`
+    let build_info = BuildInfo {
+        target: env!("PREK_BUILD_TARGET").to_string(),
+        date: env!("PREK_BUILD_DATE").to_string(),
+        features: enabled_features(),
+    };
+
     VersionInfo {
         version,
         commit_info,
+        build_info,
     }
 }
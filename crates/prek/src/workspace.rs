@@ -15,9 +15,9 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, error, instrument, trace};
 
-use crate::cli::run::{ConfiguredHook, GroupFilters, Selectors};
-use crate::config::{self, Config, read_config};
-use crate::fs::Simplified;
+use crate::cli::run::{ConfiguredHook, GroupFilters, RepoFilters, Selectors};
+use crate::config::{self, Config, load_config, read_config};
+use crate::fs::{CWD, Simplified};
 use crate::git::GIT_ROOT;
 use crate::hook::HookSpec;
 use crate::hook::{self, Hook, HookBuilder, Repo};
@@ -60,6 +60,7 @@ pub(crate) trait HookInitReporter {
 pub(crate) struct HookInitFilters<'a> {
     selectors: Option<&'a Selectors>,
     group_filters: Option<&'a GroupFilters>,
+    repo_filters: Option<&'a RepoFilters>,
 }
 
 impl<'a> HookInitFilters<'a> {
@@ -70,6 +71,7 @@ impl<'a> HookInitFilters<'a> {
         Self {
             selectors,
             group_filters,
+            repo_filters: None,
         }
     }
 
@@ -77,7 +79,21 @@ impl<'a> HookInitFilters<'a> {
         Self::default()
     }
 
+    /// Skip cloning remote repos that the `--repo` selectors rule out.
+    #[must_use]
+    pub(crate) fn with_repo_filters(mut self, repo_filters: &'a RepoFilters) -> Self {
+        self.repo_filters = Some(repo_filters);
+        self
+    }
+
     fn keeps_remote_repo(self, project: &Project, repo: &config::RemoteRepo) -> bool {
+        if self
+            .repo_filters
+            .is_some_and(|filters| !filters.matches_remote_repo(repo.repo(), repo.source()))
+        {
+            return false;
+        }
+
         repo.hooks.iter().any(|hook| {
             let hook = ConfiguredHook::new(
                 project.relative_path(),
@@ -190,6 +206,43 @@ async fn init_remote_repos<'a>(
         .collect()
 }
 
+/// Look up remote repos in the store without cloning, returning the ones not cloned yet.
+fn cached_remote_repos<'a>(
+    store: &Store,
+    remote_configs: Vec<&'a config::RemoteRepo>,
+) -> Result<
+    (
+        FxHashMap<config::RemoteRepoKey<'a>, Arc<Repo>>,
+        Vec<&'a config::RemoteRepo>,
+    ),
+    Error,
+> {
+    let mut remote_repos = FxHashMap::default();
+    let mut missing_repos = Vec::new();
+    for repo in remote_configs {
+        if let Some(path) = store.cloned_repo_path(repo)? {
+            let cloned = Repo::remote(repo.source().to_string(), repo.rev.clone(), path)?;
+            remote_repos.insert(repo.key(), Arc::new(cloned));
+        } else {
+            missing_repos.push(repo);
+        }
+    }
+    Ok((remote_repos, missing_repos))
+}
+
+/// Skip the remote repo entries of a project that are not in the store.
+fn keep_cached_repo_entries(
+    project: &Project,
+    repo_entries_to_init: &mut [bool],
+    remote_repos: &FxHashMap<config::RemoteRepoKey<'_>, Arc<Repo>>,
+) {
+    for (keep, repo) in repo_entries_to_init.iter_mut().zip(&project.config.repos) {
+        if let config::Repo::Remote(repo) = repo {
+            *keep &= remote_repos.contains_key(&repo.key());
+        }
+    }
+}
+
 /// Initialized repo slots aligned with the configured repo entries in a project.
 ///
 /// A `None` slot means the corresponding remote repo entry was fully excluded by
@@ -267,22 +320,28 @@ impl Project {
 
         let config = read_config(&config_path)?;
 
-        let config_dir = config_path
-            .parent()
-            .expect("config file must have a parent");
+        Ok(Self::from_config(config_path.into_owned(), config, root))
+    }
 
-        let root = root.unwrap_or_else(|| config_dir.to_path_buf());
+    /// Initialize a new project from an already loaded configuration.
+    pub(crate) fn from_config(config_path: PathBuf, config: Config, root: Option<PathBuf>) -> Self {
+        let root = root.unwrap_or_else(|| {
+            config_path
+                .parent()
+                .expect("config file must have a parent")
+                .to_path_buf()
+        });
 
-        Ok(Self {
+        Self {
             root,
             config,
-            config_path: config_path.into_owned(),
+            config_path,
             idx: 0,
             relative_path: PathBuf::new(),
-        })
+        }
     }
 
-    fn find_config(path: &Path) -> Option<PathBuf> {
+    pub(crate) fn find_config(path: &Path) -> Option<PathBuf> {
         for name in CONFIG_FILENAMES {
             let file = path.join(name);
             if file.is_file() {
@@ -407,6 +466,41 @@ impl Project {
         Ok(hooks)
     }
 
+    /// Load hooks like [`Project::init_hooks`], but without cloning anything.
+    ///
+    /// Hooks from remote repos that are not in the store yet are skipped, and the sources of
+    /// those repos are returned instead.
+    pub(crate) async fn init_cached_hooks(
+        self,
+        store: &Store,
+        filters: HookInitFilters<'_>,
+    ) -> Result<(Vec<Hook>, Vec<String>), Error> {
+        let (repos, missing_repos) = {
+            let mut remote_keys_to_clone = FxHashSet::default();
+            let mut remote_configs = Vec::new();
+            let mut repo_entries_to_init = plan_project_repo_init(
+                &self,
+                filters,
+                &mut remote_keys_to_clone,
+                &mut remote_configs,
+            );
+
+            let (remote_repos, missing_repos) = cached_remote_repos(store, remote_configs)?;
+            keep_cached_repo_entries(&self, &mut repo_entries_to_init, &remote_repos);
+            let repos = build_project_repo_slots(&self, repo_entries_to_init, &remote_repos);
+            let missing_repos = missing_repos
+                .into_iter()
+                .map(|repo| repo.source().to_string())
+                .collect();
+            (repos, missing_repos)
+        };
+
+        let project = Arc::new(self);
+        let hooks = Project::build_hooks(project, repos).await?;
+
+        Ok((hooks, missing_repos))
+    }
+
     /// Initialize remote repositories for the project.
     async fn init_repos(
         &self,
@@ -438,17 +532,24 @@ impl Project {
         repos: ProjectRepoSlots,
     ) -> Result<Vec<Hook>, Error> {
         let mut hooks = Vec::new();
-        let mut push_hook = async |repo: &Arc<Repo>, hook_spec: HookSpec| {
-            let builder = HookBuilder::new(
-                Arc::clone(&project),
-                Arc::clone(repo),
-                hook_spec,
-                hooks.len(),
-            );
-            let hook = builder.build().await?;
-            hooks.push(hook);
-            Ok::<_, Error>(())
-        };
+        let mut push_hook =
+            async |repo: &Arc<Repo>,
+                   hook_spec: HookSpec,
+                   repo_config: Option<&config::RemoteRepo>| {
+                let builder = HookBuilder::new(
+                    Arc::clone(&project),
+                    Arc::clone(repo),
+                    hook_spec,
+                    hooks.len(),
+                )
+                .repo_file_patterns(
+                    repo_config.and_then(|config| config.files.as_ref()),
+                    repo_config.and_then(|config| config.exclude.as_ref()),
+                );
+                let hook = builder.build().await?;
+                hooks.push(hook);
+                Ok::<_, Error>(())
+            };
 
         for (repo_config, repo) in zip_eq(project.config.repos.iter(), repos) {
             let Some(repo) = repo else {
@@ -468,25 +569,25 @@ impl Project {
                         let mut hook_spec = manifest_hook.clone();
                         hook_spec.apply_remote_hook_overrides(hook_config);
 
-                        push_hook(&repo, hook_spec).await?;
+                        push_hook(&repo, hook_spec, Some(repo_config)).await?;
                     }
                 }
                 config::Repo::Local(repo_config) => {
                     for hook_config in &repo_config.hooks {
                         let hook_spec = HookSpec::from(hook_config.clone());
-                        push_hook(&repo, hook_spec).await?;
+                        push_hook(&repo, hook_spec, None).await?;
                     }
                 }
                 config::Repo::Meta(repo_config) => {
                     for hook_config in &repo_config.hooks {
                         let hook_spec = HookSpec::from(hook_config.clone());
-                        push_hook(&repo, hook_spec).await?;
+                        push_hook(&repo, hook_spec, None).await?;
                     }
                 }
                 config::Repo::Builtin(repo_config) => {
                     for hook_config in &repo_config.hooks {
                         let hook_spec = HookSpec::from(hook_config.clone());
-                        push_hook(&repo, hook_spec).await?;
+                        push_hook(&repo, hook_spec, None).await?;
                     }
                 }
             }
@@ -496,11 +597,37 @@ impl Project {
     }
 }
 
+/// Which cached workspace discovery state to discard, controlled by `--refresh`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum Refresh {
+    /// Reuse the cached workspace discovery while it is still valid.
+    #[default]
+    None,
+    /// Rediscover the whole workspace.
+    All,
+    /// Rediscover only the projects at or below these absolute paths, keeping the cached
+    /// state of the other projects.
+    Paths(Vec<PathBuf>),
+}
+
+impl From<Option<Vec<PathBuf>>> for Refresh {
+    fn from(paths: Option<Vec<PathBuf>>) -> Self {
+        match paths {
+            None => Self::None,
+            Some(paths) if paths.is_empty() => Self::All,
+            Some(paths) => Self::Paths(paths.into_iter().map(|path| CWD.join(path)).collect()),
+        }
+    }
+}
+
 /// Cache entry for a project configuration file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CachedConfigFile {
     /// Absolute path to the config file
     path: PathBuf,
+    /// Absolute path to the project directory, when it is not the directory of the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project_root: Option<PathBuf>,
     /// Last modification time
     modified: SystemTime,
     /// File size for quick change detection
@@ -533,6 +660,8 @@ impl WorkspaceCache {
             if let Ok(metadata) = fs_err::metadata(&project.config_path) {
                 config_files.push(CachedConfigFile {
                     path: project.config_path.clone(),
+                    project_root: (project.config_path.parent() != Some(project.root.as_path()))
+                        .then(|| project.root.clone()),
                     modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
                     size: metadata.len(),
                 });
@@ -610,15 +739,25 @@ impl WorkspaceCache {
     }
 
     /// Load cache from file
-    fn load(store: &Store, workspace_root: &Path, refresh: bool) -> Option<Self> {
-        if refresh {
-            return None;
-        }
+    ///
+    /// With a partial refresh, the cached projects under the refreshed paths are dropped before
+    /// validation, so editing their configs does not invalidate the rest of the cache.
+    fn load(store: &Store, workspace_root: &Path, refresh: &Refresh) -> Option<Self> {
+        let refreshed: &[PathBuf] = match refresh {
+            Refresh::None => &[],
+            Refresh::All => return None,
+            Refresh::Paths(paths) => paths,
+        };
         let cache_path = Self::cache_path(store, workspace_root);
 
         match fs_err::read_to_string(&cache_path) {
             Ok(content) => match serde_json::from_str::<Self>(&content) {
-                Ok(cache) => {
+                Ok(mut cache) => {
+                    cache.config_files.retain(|file| {
+                        !refreshed
+                            .iter()
+                            .any(|refreshed| file.path.starts_with(refreshed))
+                    });
                     if cache.version == Self::CURRENT_VERSION && cache.is_valid() {
                         Some(cache)
                     } else {
@@ -752,18 +891,20 @@ impl Workspace {
         root: PathBuf,
         config: Option<PathBuf>,
         selectors: Option<&Selectors>,
-        refresh: bool,
+        refresh: &Refresh,
     ) -> Result<Self, Error> {
         if let Some(config) = config {
             let project = Arc::new(Project::from_config_file(
                 config.into(),
                 Some(root.clone()),
             )?);
-            return Ok(Self {
+            let workspace = Self {
                 root,
                 projects: vec![Arc::clone(&project)],
                 all_projects: vec![project],
-            });
+            };
+            workspace.register_custom_tags();
+            return Ok(workspace);
         }
 
         // Try to load from cache first
@@ -772,13 +913,15 @@ impl Workspace {
             let projects: Result<Vec<_>, _> = cache
                 .config_files
                 .into_iter()
-                .map(
-                    |config_file| match Project::from_config_file(config_file.path.into(), None) {
+                .map(|config_file| {
+                    match Project::from_config_file(
+                        config_file.path.into(),
+                        config_file.project_root,
+                    ) {
                         Ok(mut project) => {
                             let relative_path = project
-                                .config_file()
-                                .parent()
-                                .and_then(|p| p.strip_prefix(&root).ok())
+                                .path()
+                                .strip_prefix(&root)
                                 .expect("Entry path should be relative to the root")
                                 .to_path_buf();
                             project.with_relative_path(relative_path);
@@ -788,12 +931,16 @@ impl Workspace {
                             debug!("Failed to load cached project config: {}", e);
                             Err(e)
                         }
-                    },
-                )
+                    }
+                })
                 .collect();
 
             match projects {
-                Ok(projects) if !projects.is_empty() => Some(projects),
+                // A partial refresh may have dropped every cached project, the refreshed paths
+                // are rediscovered below.
+                Ok(projects) if !projects.is_empty() || matches!(refresh, Refresh::Paths(_)) => {
+                    Some(projects)
+                }
                 _ => {
                     debug!("Cache invalid or empty, performing fresh discovery");
                     None
@@ -803,7 +950,32 @@ impl Workspace {
             None
         };
 
-        let mut all_projects = if let Some(projects) = projects {
+        let mut all_projects = if let Some(mut projects) = projects {
+            if let Refresh::Paths(paths) = refresh {
+                for path in paths {
+                    if !path.starts_with(&root) {
+                        warn_user!(
+                            "Ignoring `--refresh` path `{}` outside of the workspace root `{}`",
+                            path.user_display(),
+                            root.user_display(),
+                        );
+                        continue;
+                    }
+                    debug!(path = %path.user_display(), "Rediscovering workspace projects");
+                    projects.extend(Self::discover_projects(&root, path, selectors)?);
+                }
+                // Overlapping paths can find the same project twice.
+                let mut seen = FxHashSet::default();
+                projects.retain(|project| seen.insert(project.config_path.clone()));
+                if projects.is_empty() {
+                    return Err(Error::MissingConfigFile);
+                }
+
+                let cache = WorkspaceCache::new(root.clone(), &projects);
+                if let Err(e) = cache.save(store) {
+                    debug!("Failed to save workspace cache: {}", e);
+                }
+            }
             projects
         } else {
             // Cache miss or invalid, perform fresh discovery
@@ -839,24 +1011,144 @@ impl Workspace {
             return Err(Error::MissingConfigFile);
         }
 
-        Ok(Self {
+        let workspace = Self {
             root,
             projects,
             all_projects,
-        })
+        };
+        workspace.register_custom_tags();
+        Ok(workspace)
+    }
+
+    /// Apply the `identify` mappings of the workspace root configuration to file type detection.
+    fn register_custom_tags(&self) {
+        for project in &self.all_projects {
+            if !project.relative_path().as_os_str().is_empty()
+                && project.config().identify.is_some()
+            {
+                warn_user!(
+                    "Ignoring `identify` in `{}`, it is only read from the workspace root configuration",
+                    project.config_file().user_display(),
+                );
+            }
+        }
+
+        if let Some(identify) = self
+            .root_project()
+            .and_then(|project| project.config().identify.as_ref())
+        {
+            identify.register();
+        }
     }
 
     /// Perform fresh workspace discovery without cache
     fn discover_fresh(root: &Path, selectors: Option<&Selectors>) -> Result<Vec<Project>, Error> {
+        let projects = Self::discover_projects(root, root, selectors)?;
+        if projects.is_empty() {
+            return Err(Error::MissingConfigFile);
+        }
+
+        Ok(projects)
+    }
+
+    /// Find the projects at or below `dir`, with paths relative to the workspace `root`.
+    fn discover_projects(
+        root: &Path,
+        dir: &Path,
+        selectors: Option<&Selectors>,
+    ) -> Result<Vec<Project>, Error> {
         let projects = Mutex::new(Ok(Vec::new()));
+        let configured_projects = Self::configured_projects(root)?;
+
+        Self::walk_project_dirs(dir, |entry| {
+            let project = match configured_projects.get(entry.path()) {
+                Some(config_file) => Project::from_config_file(
+                    Cow::Borrowed(config_file),
+                    Some(entry.path().to_path_buf()),
+                ),
+                None => Project::from_directory(entry.path()),
+            };
+            match project {
+                Ok(mut project) => {
+                    let relative_path = entry
+                        .into_path()
+                        .strip_prefix(root)
+                        .expect("Entry path should be relative to the root")
+                        .to_path_buf();
+                    project.with_relative_path(relative_path);
+
+                    if let Ok(projects) = projects.lock().unwrap().as_mut() {
+                        projects.push(project);
+                    }
+                }
+                Err(Error::MissingConfigFile) => {}
+                Err(e) => {
+                    // Exit early if the path is selected
+                    if let Some(selectors) = selectors {
+                        let relative_path = entry
+                            .path()
+                            .strip_prefix(root)
+                            .expect("Entry path should be relative to the root");
+                        if selectors.matches_path(relative_path) {
+                            *projects.lock().unwrap() = Err(e);
+                            return WalkState::Quit;
+                        }
+                    }
+                    // Otherwise, just log the error and continue
+                    error!(
+                        path = %entry.path().user_display(),
+                        "Skipping project due to error: {e}"
+                    );
+                    return WalkState::Skip;
+                }
+            }
 
+            WalkState::Continue
+        })?;
+
+        projects.into_inner().unwrap()
+    }
+
+    /// Find the configuration files of all projects in the workspace at `root` without loading
+    /// them, so invalid configurations can be reported instead of skipped.
+    pub(crate) fn find_config_files(root: &Path) -> Result<Vec<PathBuf>, Error> {
+        let config_files = Mutex::new(Vec::new());
+        // The root configuration is still validated on its own, only the projects it maps to
+        // other configuration files are missed.
+        let configured_projects = Self::configured_projects(root).unwrap_or_else(|err| {
+            warn_user!("Failed to read the `projects` of the workspace root configuration: {err}");
+            FxHashMap::default()
+        });
+
+        Self::walk_project_dirs(root, |entry| {
+            let config_file = configured_projects
+                .get(entry.path())
+                .cloned()
+                .or_else(|| Project::find_config(entry.path()));
+            if let Some(config_file) = config_file {
+                config_files.lock().unwrap().push(config_file);
+            }
+            WalkState::Continue
+        })?;
+
+        let mut config_files = config_files.into_inner().unwrap();
+        config_files.sort();
+        Ok(config_files)
+    }
+
+    /// Walk the directories at or below `dir` that can contain projects, skipping ignored
+    /// directories, cookiecutter templates and git submodules.
+    fn walk_project_dirs(
+        dir: &Path,
+        visit: impl Fn(ignore::DirEntry) -> WalkState + Sync,
+    ) -> Result<(), Error> {
         let git_root = GIT_ROOT.as_ref().map_err(|e| Error::Git(e.into()))?;
         let submodules = git::list_submodules(git_root).unwrap_or_else(|e| {
             error!("Failed to list git submodules: {e}");
             Vec::new()
         });
 
-        ignore::WalkBuilder::new(root)
+        ignore::WalkBuilder::new(dir)
             .follow_links(false)
             .add_custom_ignore_filename(".prekignore")
             .build_parallel()
@@ -897,51 +1189,34 @@ impl Workspace {
                         return WalkState::Skip;
                     }
 
-                    match Project::from_directory(entry.path()) {
-                        Ok(mut project) => {
-                            let relative_path = entry
-                                .into_path()
-                                .strip_prefix(root)
-                                .expect("Entry path should be relative to the root")
-                                .to_path_buf();
-                            project.with_relative_path(relative_path);
-
-                            if let Ok(projects) = projects.lock().unwrap().as_mut() {
-                                projects.push(project);
-                            }
-                        }
-                        Err(Error::MissingConfigFile) => {}
-                        Err(e) => {
-                            // Exit early if the path is selected
-                            if let Some(selectors) = selectors {
-                                let relative_path = entry
-                                    .path()
-                                    .strip_prefix(root)
-                                    .expect("Entry path should be relative to the root");
-                                if selectors.matches_path(relative_path) {
-                                    *projects.lock().unwrap() = Err(e);
-                                    return WalkState::Quit;
-                                }
-                            }
-                            // Otherwise, just log the error and continue
-                            error!(
-                                path = %entry.path().user_display(),
-                                "Skipping project due to error: {e}"
-                            );
-                            return WalkState::Skip;
-                        }
-                    }
-
-                    WalkState::Continue
+                    visit(entry)
                 })
             });
 
-        let projects = projects.into_inner().unwrap()?;
-        if projects.is_empty() {
-            return Err(Error::MissingConfigFile);
-        }
+        Ok(())
+    }
 
-        Ok(projects)
+    /// Read the `projects` mapping of the workspace root configuration, as absolute project
+    /// directories to absolute configuration files.
+    ///
+    /// An invalid root configuration is an error, as the mapping decides where the other
+    /// projects load their configuration from.
+    fn configured_projects(root: &Path) -> Result<FxHashMap<PathBuf, PathBuf>, Error> {
+        let Some(path) = Project::find_config(root) else {
+            return Ok(FxHashMap::default());
+        };
+        let config = load_config(&path)?;
+        Ok(config
+            .projects
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(dir, config_file)| {
+                // Normalize trailing and inner `.` components so lookups by walked path match.
+                let dir = root.join(dir).components().collect::<PathBuf>();
+                let config_file = dir.join(config_file);
+                (dir, config_file)
+            })
+            .collect())
     }
 
     /// Sort projects by depth and assign indices
@@ -974,6 +1249,13 @@ impl Workspace {
         &self.all_projects
     }
 
+    /// The project whose config sits at the workspace root.
+    pub(crate) fn root_project(&self) -> Option<&Arc<Project>> {
+        self.all_projects
+            .iter()
+            .find(|project| project.relative_path().as_os_str().is_empty())
+    }
+
     /// Initialize remote repositories for all projects.
     async fn init_repos(
         &self,
@@ -1028,6 +1310,44 @@ impl Workspace {
         Ok(hooks)
     }
 
+    /// Load hooks like [`Workspace::init_hooks`], but without cloning anything.
+    ///
+    /// Hooks from remote repos that are not in the store yet are skipped, and those repos are
+    /// returned instead, so callers can report what would be cloned.
+    pub(crate) async fn init_cached_hooks(
+        &self,
+        store: &Store,
+        filters: HookInitFilters<'_>,
+    ) -> Result<(Vec<Hook>, Vec<&config::RemoteRepo>), Error> {
+        let mut remote_keys_to_clone = FxHashSet::default();
+        let mut remote_configs = Vec::new();
+        let project_repo_entries_to_init = self
+            .projects
+            .iter()
+            .map(|project| {
+                plan_project_repo_init(
+                    project,
+                    filters,
+                    &mut remote_keys_to_clone,
+                    &mut remote_configs,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (remote_repos, missing_repos) = cached_remote_repos(store, remote_configs)?;
+
+        let mut hooks = Vec::new();
+        for (project, mut repo_entries_to_init) in
+            zip_eq(&self.projects, project_repo_entries_to_init)
+        {
+            keep_cached_repo_entries(project, &mut repo_entries_to_init, &remote_repos);
+            let repos = build_project_repo_slots(project, repo_entries_to_init, &remote_repos);
+            hooks.extend(Project::build_hooks(Arc::clone(project), repos).await?);
+        }
+
+        Ok((hooks, missing_repos))
+    }
+
     /// Check if all configuration files are staged in git.
     pub(crate) async fn check_configs_staged(&self) -> Result<()> {
         let config_files = self
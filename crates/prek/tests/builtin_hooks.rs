@@ -3410,6 +3410,42 @@ fn check_illegal_windows_names() -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+#[test]
+fn check_illegal_windows_names_with_files_override() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: check-illegal-windows-names
+                files: ^src/
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("src/main.rs").write_str("ok")?;
+    cwd.child("src/aux.rs").write_str("bad")?;
+    cwd.child("NUL.txt").write_str("ignored")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check illegal windows names..............................................Failed
+    - hook id: check-illegal-windows-names
+    - exit code: 1
+
+      src/aux.rs: Illegal Windows filename
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test that builtin hooks work correctly even when a system-wide binary with the
 /// same name exists on PATH (regression test for <https://github.com/j178/prek/issues/1412>).
 ///
@@ -25,6 +25,87 @@ fn cache_dir() {
     ");
 }
 
+#[test]
+fn cache_dir_from_global_store_path() {
+    let context = TestContext::new();
+    context.init_project();
+    let store = context.work_dir().child("store");
+    context.write_user_config(&format!(
+        "store_path = '{}'\n",
+        store.to_string_lossy().replace('\\', "/")
+    ));
+
+    cmd_snapshot!(context.filters(), context.command().arg("cache").arg("dir").env_remove("PREK_HOME"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/store
+
+    ----- stderr -----
+    ");
+
+    // `PREK_HOME` takes precedence over `store_path`.
+    let home = context.work_dir().child("home");
+    cmd_snapshot!(context.filters(), context.command().arg("cache").arg("dir").env("PREK_HOME", &*home), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/home
+
+    ----- stderr -----
+    ");
+
+    // A project configuration cannot move the store.
+    context.write_pre_commit_config("store_path: .cache/prek\nrepos: []\n");
+    cmd_snapshot!(context.filters(), context.command().arg("cache").arg("dir").env("PREK_HOME", &*home), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/home
+
+    ----- stderr -----
+    ");
+}
+
+/// `cache clean`, `gc` and `prune` leave directories that prek did not create as a store alone.
+#[test]
+fn cache_clean_refuses_unmarked_directory() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    let home = context.work_dir().child("not-a-store");
+    home.child("important.txt").write_str("keep me")?;
+
+    cmd_snapshot!(context.filters(), context.command().arg("cache").arg("clean").env("PREK_HOME", &*home), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to delete from `[TEMP_DIR]/not-a-store`, which is not a prek store (no `README` file)
+    ");
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "gc"]).env("PREK_HOME", &*home), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to delete from `[TEMP_DIR]/not-a-store`, which is not a prek store (no `README` file)
+    ");
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "prune", "--older-than", "30d"]).env("PREK_HOME", &*home), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to delete from `[TEMP_DIR]/not-a-store`, which is not a prek store (no `README` file)
+    ");
+
+    home.child("important.txt").assert("keep me");
+
+    Ok(())
+}
+
 #[test]
 fn cache_gc_verbose_shows_removed_entries() {
     let context = TestContext::new();
@@ -85,6 +166,11 @@ fn cache_gc_verbose_shows_removed_entries() {
     ----- stdout -----
     Removed 1 repo, 1 hook env, 1 cache entry ([SIZE])
 
+    Reclaimed space by bucket:
+    - cache/go: [SIZE]
+    - hooks: [SIZE]
+    - repos: [SIZE]
+
     Removed 1 repo:
     - https://github.com/pre-commit/pre-commit-hooks@v1.0.0
       path: [HOME]/repos/deadbeef
@@ -168,6 +254,7 @@ fn cache_clean() -> anyhow::Result<()> {
 
     let home = context.work_dir().child("home");
     home.create_dir_all()?;
+    home.child("README").write_str("")?;
     home.child("cache/nested").create_dir_all()?;
     home.child("cache/data.bin").write_str("hello")?;
     home.child("cache/nested/data.bin").write_str("world!")?;
@@ -185,6 +272,7 @@ fn cache_clean() -> anyhow::Result<()> {
 
     // Test `prek clean` works for backward compatibility
     home.create_dir_all()?;
+    home.child("README").write_str("")?;
     home.child("cache").create_dir_all()?;
     home.child("cache/one.txt").write_str("abc")?;
     cmd_snapshot!(context.filters(), context.command().arg("clean").env("PREK_HOME", &*home), @"
@@ -498,6 +586,12 @@ fn cache_gc_prunes_unused_tool_versions() -> anyhow::Result<()> {
     ----- stdout -----
     Would remove 2 hook envs, 3 tools ([SIZE])
 
+    Reclaimed space by bucket:
+    - hooks: [SIZE]
+    - tools/go: [SIZE]
+    - tools/node: [SIZE]
+    - tools/python: [SIZE]
+
     Would remove 2 hook envs:
     - ruby-remove
       path: [HOME]/hooks/ruby-remove
@@ -521,6 +615,12 @@ fn cache_gc_prunes_unused_tool_versions() -> anyhow::Result<()> {
     ----- stdout -----
     Removed 2 hook envs, 3 tools ([SIZE])
 
+    Reclaimed space by bucket:
+    - hooks: [SIZE]
+    - tools/go: [SIZE]
+    - tools/node: [SIZE]
+    - tools/python: [SIZE]
+
     Removed 2 hook envs:
     - ruby-remove
       path: [HOME]/hooks/ruby-remove
@@ -598,6 +698,9 @@ fn cache_gc_prunes_tool_versions_without_positive_identification() -> anyhow::Re
     ----- stdout -----
     Would remove 2 tools ([SIZE])
 
+    Reclaimed space by bucket:
+    - tools/python: [SIZE]
+
     Would remove 2 tools:
     - python/3.11.0
       path: [HOME]/tools/python/3.11.0
@@ -729,6 +832,9 @@ fn cache_gc_removes_stale_patch_files() -> anyhow::Result<()> {
     ----- stdout -----
     Would remove 1 patch file ([SIZE])
 
+    Reclaimed space by bucket:
+    - patches: [SIZE]
+
     Would remove 1 patch file:
     - old.patch
       path: [HOME]/patches/old.patch
@@ -744,6 +850,9 @@ fn cache_gc_removes_stale_patch_files() -> anyhow::Result<()> {
     ----- stdout -----
     Removed 1 patch file ([SIZE])
 
+    Reclaimed space by bucket:
+    - patches: [SIZE]
+
     Removed 1 patch file:
     - old.patch
       path: [HOME]/patches/old.patch
@@ -757,6 +866,58 @@ fn cache_gc_removes_stale_patch_files() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn cache_gc_removes_scratch_and_partial_installs() -> anyhow::Result<()> {
+    let context = TestContext::new();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: local-node
+                name: Local Node Hook
+                entry: "node -e \"console.log(1)\""
+                language: node
+    "#});
+
+    let home = context.home_dir();
+    let config_path = context.work_dir().child(PRE_COMMIT_CONFIG_YAML);
+    write_config_tracking_file(home, &[config_path.path()])?;
+
+    // A download interrupted before it was moved into place.
+    let partial = home.child("tools/node/.install-abc");
+    partial.child("bin/node").write_str("partial")?;
+    let scratch = home.child("scratch/download-abc");
+    scratch.child("node.tar.gz").write_str("partial")?;
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "gc", "-v"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Removed 1 partial install, 1 scratch entry ([SIZE])
+
+    Reclaimed space by bucket:
+    - scratch: [SIZE]
+    - tools/node: [SIZE]
+
+    Removed 1 partial install:
+    - node/.install-abc
+      path: [HOME]/tools/node/.install-abc
+
+    Removed 1 scratch entry:
+    - download-abc
+      path: [HOME]/scratch/download-abc
+
+    ----- stderr -----
+    ");
+
+    partial.assert(predicates::path::missing());
+    scratch.assert(predicates::path::missing());
+    home.child("tools/node").assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
 fn write_config_tracking_file(
     home: &ChildPath,
     configs: &[&std::path::Path],
@@ -973,3 +1134,69 @@ fn cache_gc_dry_run_does_not_remove_entries() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn cache_prune_removes_envs_not_used_recently() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let home = context.home_dir();
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    let stale_used = now.saturating_sub(Duration::from_hours(60 * 24)).as_secs();
+
+    for (name, last_used) in [
+        ("python-stale", stale_used),
+        ("python-recent", now.as_secs()),
+    ] {
+        let env = home.child(format!("hooks/{name}"));
+        env.create_dir_all()?;
+        let marker = json!({
+            "schema_version": 1,
+            "language": "python",
+            "language_version": "3.12.0",
+            "dependencies": [],
+            "env_path": env.path(),
+            "toolchain": "",
+            "extra": {},
+        });
+        env.child(".prek-hook.json")
+            .write_str(&serde_json::to_string_pretty(&marker)?)?;
+        env.child(".prek-last-used")
+            .write_str(&last_used.to_string())?;
+    }
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "prune", "--older-than", "30d", "--dry-run"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Would remove 1 hook env ([SIZE])
+
+    ----- stderr -----
+    ");
+    home.child("hooks/python-stale")
+        .assert(predicates::path::is_dir());
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "prune", "--older-than", "30d"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Removed 1 hook env ([SIZE])
+
+    ----- stderr -----
+    ");
+    home.child("hooks/python-stale")
+        .assert(predicates::path::missing());
+    home.child("hooks/python-recent")
+        .assert(predicates::path::is_dir());
+
+    cmd_snapshot!(context.filters(), context.command().args(["cache", "prune", "--older-than", "30d"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Nothing to prune
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
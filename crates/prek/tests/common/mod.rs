@@ -82,6 +82,11 @@ impl TestContext {
     fn from_root(root: tempfile::TempDir, temp_dir: ChildPath) -> Self {
         let home_dir = ChildPath::new(root.path()).child("home");
         fs_err::create_dir_all(&home_dir).expect("Failed to create test home directory");
+        // Mark the home as a store up front, as tests write into it before prek first runs.
+        home_dir
+            .child("README")
+            .write_str("")
+            .expect("Failed to create test store marker");
 
         let mut filters = Vec::new();
 
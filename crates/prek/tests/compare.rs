@@ -0,0 +1,156 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// Compare against a stand-in `pre-commit` that reports every hook as passed.
+#[cfg(unix)]
+#[test]
+fn compare_reports_differences() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: pass
+                name: Pass hook
+                language: system
+                entry: "true"
+                pass_filenames: false
+                always_run: true
+              - id: fail
+                name: Fail hook
+                language: system
+                entry: "false"
+                pass_filenames: false
+                always_run: true
+    "#});
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    let pre_commit = context.home_dir().child("pre-commit");
+    pre_commit.write_str(indoc::indoc! {r#"
+        #!/bin/sh
+        if [ "$1" = "--version" ]; then
+            echo "pre-commit 4.0.1"
+            exit 0
+        fi
+        echo "Pass hook................................................................Passed"
+        echo "Fail hook................................................................Passed"
+        echo "generated" > generated.txt
+    "#})?;
+    common::make_executable(&pre_commit)?;
+
+    cmd_snapshot!(context.filters(), context.command()
+        .arg("compare")
+        .arg("--pre-commit")
+        .arg(&*pre_commit)
+        .arg("--")
+        .arg("--all-files"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Comparing `prek run` with `pre-commit run` (pre-commit 4.0.1)
+    Found 3 differences:
+      - Hook `Fail hook`: prek reported `Failed (exit code 1)`, pre-commit reported `Passed`
+      - File `generated.txt` was only modified by pre-commit
+      - Exit code: prek exited with 1, pre-commit exited with 0
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `--per-hook` runs each hook on its own and compares the files it modified.
+#[cfg(unix)]
+#[test]
+fn compare_per_hook_modified_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: fmt
+                name: Format
+                language: system
+                entry: sh -c 'echo formatted > fmt.txt'
+                pass_filenames: false
+                always_run: true
+    "#});
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    // Arguments: `run --color never --verbose [HOOK] RUN_ARGS...`.
+    let pre_commit = context.home_dir().child("pre-commit");
+    pre_commit.write_str(indoc::indoc! {r#"
+        #!/bin/sh
+        if [ "$1" = "--version" ]; then
+            echo "pre-commit 4.0.1"
+            exit 0
+        fi
+        echo "Format.................................................................Failed"
+        echo "- hook id: fmt"
+        echo "- files were modified by this hook"
+        if [ "$5" = "fmt" ]; then
+            echo "formatted" > other.txt
+        else
+            echo "formatted" > fmt.txt
+        fi
+        exit 1
+    "#})?;
+    common::make_executable(&pre_commit)?;
+
+    cmd_snapshot!(context.filters(), context.command()
+        .arg("compare")
+        .arg("--per-hook")
+        .arg("--pre-commit")
+        .arg(&*pre_commit)
+        .arg("--")
+        .arg("--all-files"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Comparing `prek run` with `pre-commit run` (pre-commit 4.0.1)
+    Found 2 differences:
+      - Hook `fmt`: file `fmt.txt` was only modified by prek
+      - Hook `fmt`: file `other.txt` was only modified by pre-commit
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn compare_without_pre_commit_config() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child("prek.toml")
+        .write_str("repos = []\n")?;
+    let pre_commit = context.home_dir().child("pre-commit");
+    pre_commit.touch()?;
+
+    cmd_snapshot!(context.filters(), context.command()
+        .arg("compare")
+        .arg("--pre-commit")
+        .arg(&*pre_commit), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `pre-commit` only reads `.pre-commit-config.yaml`, which does not exist in `[TEMP_DIR]/`
+    ");
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+use insta::assert_snapshot;
+use prek_consts::PRE_COMMIT_CONFIG_YAML;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn config_lint_reports_issues() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        auto_update:
+          cooldown_days: 7
+        default_stages: [commit]
+        repos:
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+          - repo: local
+            hooks:
+              - id: rust-check
+                name: rust-check
+                entry: cargo check
+                language: system
+                files: \.rs$
+    "});
+
+    let cwd = context.work_dir();
+    cwd.child("app")
+        .child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+    "})?;
+    cwd.child("lib")
+        .child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/example/hooks
+            rev: main
+            hooks:
+              - id: format
+                language: cobol
+    "})?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().args(["config", "lint"]), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    .pre-commit-config.yaml: [deprecated] `auto_update` is deprecated, use `update` instead
+    .pre-commit-config.yaml: [deprecated] `default_stages`: stage `commit` is deprecated, use `pre-commit` instead
+    .pre-commit-config.yaml: [no-matching-files] hook `rust-check` does not match any file
+    app/.pre-commit-config.yaml: [duplicate-hook] `trailing-whitespace` from `builtin` is also configured in parent project `.`
+    lib/.pre-commit-config.yaml: [unpinned-rev] `https://github.com/example/hooks` uses the mutable rev `main`, pin it to a tag or a commit SHA
+    lib/.pre-commit-config.yaml: [unknown-language] hook `format`: unknown language `cobol`
+
+    ----- stderr -----
+    Found 6 issues, 2 fixable with `prek config lint --fix`
+    ");
+
+    Ok(())
+}
+
+/// Hooks of remote repos that are not cloned yet are not checked, so linting works offline.
+#[test]
+fn config_lint_does_not_clone() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/prek-test-repos/does-not-exist
+            rev: v1.0.0
+            hooks:
+              - id: format
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().args(["config", "lint"]), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: No issues found
+    ");
+}
+
+#[test]
+fn config_lint_fix() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        # Shared settings
+        auto_update:
+          cooldown_days: 7
+        repos:
+          - repo: local
+            hooks:
+              - id: check
+                name: check
+                entry: echo
+                language: system
+                stages: [commit, push]  # run on commit and push
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().args(["config", "lint", "--fix"]), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Fixed 3 issues
+    success: No issues found
+    ");
+
+    assert_snapshot!(context.read(PRE_COMMIT_CONFIG_YAML), @"
+    # Shared settings
+    update:
+      cooldown_days: 7
+    repos:
+      - repo: local
+        hooks:
+          - id: check
+            name: check
+            entry: echo
+            language: system
+            stages: [pre-commit, pre-push]  # run on commit and push
+    ");
+}
+
+#[test]
+fn config_schema() -> Result<()> {
+    let context = TestContext::new();
+
+    let output = context.command().arg("config").arg("schema").output()?;
+    assert!(output.status.success());
+
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(schema["$id"], "https://www.schemastore.org/prek.json");
+    assert!(schema["properties"]["minimum_prek_version"].is_object());
+
+    // The printed schema is the one checked into the repository.
+    let checked_in = fs_err::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../prek.schema.json"),
+    )?;
+    assert_eq!(String::from_utf8(output.stdout)?, checked_in);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn env_link_requires_environment() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: Echo
+                entry: echo
+                language: system
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("env").arg("link").arg("echo"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Hook `echo` does not use an environment, there is nothing to link
+    ");
+}
+
+#[test]
+fn env_link_unknown_hook() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: Echo
+                entry: echo
+                language: system
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("env").arg("link").arg("missing"), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No hook found for `missing`
+    ");
+}
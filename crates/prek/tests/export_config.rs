@@ -0,0 +1,494 @@
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot, git_cmd};
+
+mod common;
+
+#[test]
+fn export_config_applies_defaults() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        default_stages: [pre-commit, pre-push]
+        default_language_version:
+          python: '3.12'
+        default_env:
+          FOO: bar
+        exclude: ^vendor/
+        repos:
+          - repo: local
+            hooks:
+              - id: lint
+                name: Lint
+                entry: python -m lint
+                language: python
+                types: [python]
+                env:
+                  BAZ: qux
+              - id: check-manual
+                name: Check Manual
+                entry: echo
+                language: system
+                stages: [manual]
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("export-config"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # Project: .
+    exclude: ^vendor/
+    fail_fast: false
+    repos:
+      - repo: local
+        hooks:
+          - id: lint
+            name: Lint
+            entry: python -m lint
+            language: python
+            language_version: '3.12'
+            types:
+              - python
+            types_or: []
+            exclude_types: []
+            additional_dependencies: []
+            setup: []
+            args: []
+            env:
+              BAZ: qux
+              FOO: bar
+            stages:
+              - pre-commit
+              - pre-push
+            groups: []
+            always_run: false
+            pass_filenames: true
+            files_from_repo_root: false
+            require_serial: false
+            require_serial_project: false
+            fail_fast: false
+            advisory: false
+            verbose: false
+            stream_output: false
+            success_exit_codes:
+              - 0
+            warning_exit_codes: []
+            use_project_node_modules: false
+            priority: 0
+          - id: check-manual
+            name: Check Manual
+            entry: echo
+            language: system
+            types:
+              - file
+            types_or: []
+            exclude_types: []
+            additional_dependencies: []
+            setup: []
+            args: []
+            env:
+              FOO: bar
+            stages:
+              - manual
+            groups: []
+            always_run: false
+            pass_filenames: false
+            files_from_repo_root: false
+            require_serial: false
+            require_serial_project: false
+            fail_fast: false
+            advisory: false
+            verbose: false
+            stream_output: false
+            success_exit_codes:
+              - 0
+            warning_exit_codes: []
+            use_project_node_modules: false
+            priority: 1
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn export_config_includes_ci_settings() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        ci:
+          autofix_commit_msg: 'style: auto fixes'
+          autoupdate_schedule: monthly
+          skip: [check]
+          submodules: false
+          unknown_ci_key: true
+        repos:
+          - repo: local
+            hooks:
+              - id: check
+                name: Check
+                entry: echo
+                language: system
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("export-config"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # Project: .
+    fail_fast: false
+    ci:
+      autofix_commit_msg: 'style: auto fixes'
+      autoupdate_schedule: monthly
+      skip:
+        - check
+      submodules: false
+    repos:
+      - repo: local
+        hooks:
+          - id: check
+            name: Check
+            entry: echo
+            language: system
+            types:
+              - file
+            types_or: []
+            exclude_types: []
+            additional_dependencies: []
+            setup: []
+            args: []
+            env: {}
+            stages:
+              - manual
+              - commit-msg
+              - post-checkout
+              - post-commit
+              - post-merge
+              - post-rewrite
+              - pre-auto-gc
+              - pre-commit
+              - pre-merge-commit
+              - pre-push
+              - pre-rebase
+              - prepare-commit-msg
+            groups: []
+            always_run: false
+            pass_filenames: false
+            files_from_repo_root: false
+            require_serial: false
+            require_serial_project: false
+            fail_fast: false
+            advisory: false
+            verbose: false
+            stream_output: false
+            success_exit_codes:
+              - 0
+            warning_exit_codes: []
+            use_project_node_modules: false
+            priority: 0
+
+    ----- stderr -----
+    warning: Ignored unexpected keys in `.pre-commit-config.yaml`: `ci.unknown_ci_key`
+    ");
+}
+
+#[test]
+fn export_config_resolved_freezes_revs() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/hooks");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+    repo_dir
+        .child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+        - id: test-hook
+          name: Test Hook
+          entry: echo
+          language: system
+    "})?;
+    git_cmd(&repo_dir).arg("add").arg(".").assert().success();
+    git_cmd(&repo_dir)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&repo_dir)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+    let repo_path = repo_dir.to_string_lossy().replace('\\', "/");
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+    ", repo_path});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"[a-f0-9]{40}", r"[COMMIT_SHA]")])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters.clone(), context.command().arg("export-config"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # Project: .
+    fail_fast: false
+    repos:
+      - repo: [HOME]/test-repos/hooks
+        rev: v1.0.0
+        hooks:
+          - id: test-hook
+            name: Test Hook
+            entry: echo
+            language: system
+            types:
+              - file
+            types_or: []
+            exclude_types: []
+            additional_dependencies: []
+            setup: []
+            args: []
+            env: {}
+            stages:
+              - manual
+              - commit-msg
+              - post-checkout
+              - post-commit
+              - post-merge
+              - post-rewrite
+              - pre-auto-gc
+              - pre-commit
+              - pre-merge-commit
+              - pre-push
+              - pre-rebase
+              - prepare-commit-msg
+            groups: []
+            always_run: false
+            pass_filenames: true
+            files_from_repo_root: false
+            require_serial: false
+            require_serial_project: false
+            fail_fast: false
+            advisory: false
+            verbose: false
+            stream_output: false
+            success_exit_codes:
+              - 0
+            warning_exit_codes: []
+            use_project_node_modules: false
+            priority: 0
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(filters, context.command().arg("export-config").arg("--resolved"), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # Project: .
+    fail_fast: false
+    repos:
+      - repo: [HOME]/test-repos/hooks
+        rev: [COMMIT_SHA]
+        hooks:
+          - id: test-hook
+            name: Test Hook
+            entry: echo
+            language: system
+            types:
+              - file
+            types_or: []
+            exclude_types: []
+            additional_dependencies: []
+            setup: []
+            args: []
+            env: {}
+            stages:
+              - manual
+              - commit-msg
+              - post-checkout
+              - post-commit
+              - post-merge
+              - post-rewrite
+              - pre-auto-gc
+              - pre-commit
+              - pre-merge-commit
+              - pre-push
+              - pre-rebase
+              - prepare-commit-msg
+            groups: []
+            always_run: false
+            pass_filenames: true
+            files_from_repo_root: false
+            require_serial: false
+            require_serial_project: false
+            fail_fast: false
+            advisory: false
+            verbose: false
+            stream_output: false
+            success_exit_codes:
+              - 0
+            warning_exit_codes: []
+            use_project_node_modules: false
+            priority: 0
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// The exported configuration loads back and exports the same hooks.
+#[test]
+fn export_config_round_trip() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/hooks");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+    repo_dir
+        .child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+        - id: test-hook
+          name: Test Hook
+          entry: echo
+          language: system
+    "})?;
+    git_cmd(&repo_dir).arg("add").arg(".").assert().success();
+    git_cmd(&repo_dir)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&repo_dir)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+    let repo_path = repo_dir.to_string_lossy().replace('\\', "/");
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        minimum_prek_version: 0.1.0
+        default_stages: [pre-commit]
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            files: ^src/
+            exclude: ^src/vendor/
+            hooks:
+              - id: test-hook
+                alias: test
+                groups: [ci]
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+          - repo: local
+            hooks:
+              - id: lint
+                name: Lint
+                entry: python -m lint
+                language: python
+                description: Lint the code
+                additional_dependencies: [ruff]
+                setup: [python -m lint --init]
+                stages: [pre-push]
+                files_from_repo_root: true
+                require_serial_project: true
+                advisory: true
+                stream_output: true
+                log_file: lint.log
+                output_encoding: shift_jis
+                success_exit_codes: [0, 3]
+                warning_exit_codes: [4]
+                isolation: container
+                minimum_prek_version: 0.1.0
+              - id: forbidden
+                name: Forbidden
+                entry: forbidden files
+                language: fail
+                files: \.orig$
+                pattern_hint: Remove the backup files
+    ", repo_path});
+    context.git_add(".");
+
+    let output = context.command().arg("export-config").output()?;
+    assert!(output.status.success());
+    let exported = String::from_utf8(output.stdout)?;
+    context
+        .work_dir()
+        .child("exported.yaml")
+        .write_str(&exported)?;
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg("exported.yaml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: All configs are valid
+    ");
+
+    let output = context
+        .command()
+        .args(["--config", "exported.yaml", "export-config"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, exported);
+    for expected in [
+        "minimum_prek_version: 0.1.0",
+        "- ruff",
+        "- pre-push",
+        "files: ^src/",
+        "exclude: ^src/vendor/",
+        "alias: test",
+        "- ci",
+        "description: Lint the code",
+        "- python -m lint --init",
+        "files_from_repo_root: true",
+        "require_serial_project: true",
+        "advisory: true",
+        "stream_output: true",
+        "log_file: lint.log",
+        "output_encoding: Shift_JIS",
+        "- 3",
+        "- 4",
+        "isolation: container",
+        "pattern_hint: Remove the backup files",
+    ] {
+        assert!(
+            exported.contains(expected),
+            "missing `{expected}`:\n{exported}"
+        );
+    }
+
+    Ok(())
+}
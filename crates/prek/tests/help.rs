@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn help_lists_topics() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.command().arg("help"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Help topics:
+
+      selectors     Select or skip hooks and projects
+      workspace     Run the hooks of nested projects in one repository
+      environment   Environment variables read by prek
+      languages     Supported hook languages and what they support
+
+    Use `prek help <TOPIC>` to read a topic, or `prek help <COMMAND>` for the full help of a command.
+    Add `--man` to print a man page instead, e.g. `prek help --man run > prek-run.1`.
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn help_topic() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.command().args(["help", "selectors"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prek-selectors - Select or skip hooks and projects
+
+        Commands such as `prek run`, `prek install` and `prek list` accept selectors
+        to pick hooks and projects, and `--skip` accepts the same syntax to leave
+        them out.
+
+    Syntax
+
+        <hook-id>
+            All hooks with the given id, in every project.
+
+        <project-path>/
+            All hooks of the project and its subprojects. The trailing slash is
+            required: a selector without a slash is a hook id.
+
+        <project-path>:<hook-id>
+            Only the given hook of the given project.
+
+        :<hook-id>
+            A hook whose id contains `:`, such as `:lint:ruff` for the id
+            `lint:ruff`.
+
+        Project paths are relative to the current directory. Selectors can be
+        repeated, and `PREK_SKIP` or `SKIP` hold comma-separated selectors to skip.
+
+    Examples
+
+            prek run black                    # every `black` hook
+            prek run frontend/                # every hook of `frontend`
+            prek run frontend:lint            # `lint` of `frontend` only
+            prek run --skip src/backend/      # everything but `src/backend`
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn help_man_page() -> Result<()> {
+    let context = TestContext::new();
+
+    let output = context
+        .command()
+        .args(["help", "--man", "cache", "clean"])
+        .output()?;
+    assert!(output.status.success());
+
+    let page = String::from_utf8(output.stdout)?;
+    assert!(page.starts_with(".TH \"PREK-CACHE-CLEAN\" \"1\""), "{page}");
+    assert!(
+        page.contains(".SH NAME\nprek\\-cache\\-clean \\- "),
+        "{page}"
+    );
+    assert!(page.contains(".SH SYNOPSIS\n"), "{page}");
+
+    Ok(())
+}
+
+#[test]
+fn help_unknown_topic() {
+    let context = TestContext::new();
+
+    cmd_snapshot!(context.filters(), context.command().args(["help", "nope"]), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unknown help topic or command `nope`; run `prek help` for the list of topics
+    ");
+}
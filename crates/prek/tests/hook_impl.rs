@@ -1,4 +1,5 @@
 use assert_cmd::assert::OutputAssertExt;
+use assert_fs::assert::PathAssert;
 use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
 use indoc::indoc;
 use prek_consts::PRE_COMMIT_CONFIG_YAML;
@@ -103,6 +104,118 @@ fn hook_impl_allows_missing_hook_dir() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn hook_impl_pre_auto_gc() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc! { r"
+        repos:
+        - repo: local
+          hooks:
+           - id: gc-check
+             name: gc-check
+             language: system
+             entry: echo
+             stages: [pre-auto-gc]
+             always_run: true
+           - id: commit-check
+             name: commit-check
+             language: fail
+             entry: should not run
+             stages: [pre-commit]
+             always_run: true
+    "});
+
+    cmd_snapshot!(context.filters(), context.install().arg("--hook-type").arg("pre-auto-gc"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    prek installed at `.git/hooks/pre-auto-gc`
+
+    ----- stderr -----
+    ");
+
+    let mut hook_impl = context.command();
+    hook_impl
+        .arg("hook-impl")
+        .arg("--hook-type")
+        .arg("pre-auto-gc")
+        .arg("--hook-dir")
+        .arg(context.work_dir().join(".git/hooks"))
+        .arg("--script-version")
+        .arg("4");
+
+    cmd_snapshot!(context.filters(), hook_impl, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    gc-check.................................................................Passed
+
+    ----- stderr -----
+    ");
+}
+
+/// Scripts generated by `pre-commit install` keep working when they invoke prek instead.
+#[test]
+fn hook_impl_from_pre_commit_generated_script() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    // The repository has moved to `prek.toml`, but the shim still passes the old config.
+    context.work_dir().child("prek.toml").write_str(indoc! {r#"
+        [[repos]]
+        repo = "local"
+        hooks = [
+          { id = "success", name = "success", language = "system", entry = "echo ok", always_run = true },
+        ]
+    "#})?;
+    context.git_add(".");
+
+    let hook_dir = context.work_dir().child(".git/hooks");
+    hook_dir.child("pre-commit").write_str(indoc! {r#"
+        #!/usr/bin/env bash
+        # File generated by pre-commit: https://pre-commit.com
+        # ID: 138fd403232d2ddd5efb44317e38bf03
+
+        # start templated
+        INSTALL_PYTHON=/usr/bin/python3
+        ARGS=(hook-impl --config=.pre-commit-config.yaml --hook-type=pre-commit)
+        # end templated
+
+        HERE="$(cd "$(dirname "$0")" && pwd)"
+        ARGS+=(--hook-dir "$HERE" -- "$@")
+
+        if [ -x "$INSTALL_PYTHON" ]; then
+            exec "$INSTALL_PYTHON" -mpre_commit "${ARGS[@]}"
+        elif command -v pre-commit > /dev/null; then
+            exec pre-commit "${ARGS[@]}"
+        else
+            echo '`pre-commit` not found.  Did you forget to activate your virtualenv?' 1>&2
+            exit 1
+        fi
+    "#})?;
+
+    let mut hook_impl = context.command();
+    hook_impl
+        .arg("hook-impl")
+        .arg("--config=.pre-commit-config.yaml")
+        .arg("--hook-type=pre-commit")
+        .arg("--hook-dir")
+        .arg(hook_dir.path())
+        .arg("--");
+
+    cmd_snapshot!(context.filters(), hook_impl, @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    success..................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn hook_impl_pre_push() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -356,6 +469,131 @@ fn hook_impl_pre_push_force_push_after_rebase() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Set up `feature` and `master` branches that both rewrite `file.txt`, so replaying
+/// the feature commit onto master stops with a conflict.
+fn setup_conflicting_branches(context: &TestContext) -> anyhow::Result<()> {
+    context.init_project();
+    context.write_pre_commit_config(indoc! { r"
+        repos:
+        - repo: builtin
+          hooks:
+           - id: check-merge-conflict
+             args: [--assume-in-merge]
+    "});
+    context.work_dir().child("file.txt").write_str("base\n")?;
+    context.work_dir().child("other.txt").write_str("other\n")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    context.git_branch("feature");
+    context.git_checkout("feature");
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("feature\n")?;
+    context.git_add(".");
+    context.git_commit("Feature change");
+
+    context.git_checkout("master");
+    context.work_dir().child("file.txt").write_str("master\n")?;
+    context.git_add(".");
+    context.git_commit("Master change");
+
+    Ok(())
+}
+
+/// `git rebase --continue` commits the resolved pick through the installed hook shim.
+#[test]
+fn hook_impl_rebase_continue() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    setup_conflicting_branches(&context)?;
+    context.git_checkout("feature");
+    context.install().assert().success();
+
+    git_cmd(context.work_dir())
+        .arg("rebase")
+        .arg("master")
+        .assert()
+        .failure();
+    let rebase_dir = context.work_dir().child(".git/rebase-merge");
+    rebase_dir.assert(predicates::path::is_dir());
+
+    let rebase_continue = || {
+        let mut cmd = git_cmd(context.work_dir());
+        cmd.args(["-c", "core.editor=:"])
+            .arg("rebase")
+            .arg("--continue")
+            .env(EnvVars::PREK_HOME, &**context.home_dir());
+        cmd
+    };
+
+    // Marking the file as resolved with the conflict markers still in it is caught by the hook,
+    // and the rebase stays paused.
+    context.git_add("file.txt");
+    let output = rebase_continue().output()?;
+    output.assert().failure();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stdout.contains("check for merge conflicts")
+            || stderr.contains("check for merge conflicts"),
+        "hook did not run:\n{stdout}\n{stderr}"
+    );
+    rebase_dir.assert(predicates::path::is_dir());
+
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("resolved\n")?;
+    context.git_add("file.txt");
+    rebase_continue().assert().success();
+
+    rebase_dir.assert(predicates::path::missing());
+    assert_eq!(context.read("file.txt"), "resolved\n");
+
+    Ok(())
+}
+
+/// Unstaged changes are left in place while a cherry-pick is paused.
+#[test]
+fn run_during_cherry_pick_does_not_stash() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    setup_conflicting_branches(&context)?;
+
+    git_cmd(context.work_dir())
+        .arg("cherry-pick")
+        .arg("feature")
+        .assert()
+        .failure();
+    context
+        .work_dir()
+        .child("file.txt")
+        .write_str("resolved\n")?;
+    context.git_add("file.txt");
+    context
+        .work_dir()
+        .child("other.txt")
+        .write_str("unstaged\n")?;
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    check for merge conflicts................................................Passed
+
+    ----- stderr -----
+    warning: a cherry-pick is in progress; unstaged changes are not stashed and hooks will see them
+    ");
+
+    assert_eq!(context.read("other.txt"), "unstaged\n");
+    context
+        .work_dir()
+        .child(".git/CHERRY_PICK_HEAD")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
+
 #[test]
 fn hook_impl_runs_legacy_hook() -> anyhow::Result<()> {
     let context = TestContext::new();
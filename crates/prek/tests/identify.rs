@@ -86,3 +86,170 @@ fn identify_json_with_missing_paths() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(unix)] // "executable" tag is different on Windows
+#[test]
+fn identify_custom_tags() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        identify:
+          extensions:
+            snake: [python, text]
+          filenames:
+            Snakefile: [python, text]
+        repos:
+          - repo: local
+            hooks:
+              - id: python-files
+                name: python-files
+                entry: echo
+                language: system
+                types: [python]
+                verbose: true
+    "});
+    context
+        .work_dir()
+        .child("rules.snake")
+        .write_str("rule all:\n")?;
+    context
+        .work_dir()
+        .child("Snakefile")
+        .write_str("rule all:\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(
+        context.filters(),
+        context
+            .command()
+            .arg("util")
+            .arg("identify")
+            .arg("rules.snake")
+            .arg("Snakefile"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    rules.snake: file, non-executable, python, text
+    Snakefile: file, non-executable, python, text
+
+    ----- stderr -----
+    "
+    );
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    python-files.............................................................Passed
+    - hook id: python-files
+    - duration: [TIME]
+
+      Snakefile rules.snake
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `identify` is only read from the workspace root, nested projects get a warning.
+#[cfg(unix)]
+#[test]
+fn identify_in_nested_project() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config("repos: []");
+    let sub = context.work_dir().child("sub");
+    sub.child(".pre-commit-config.yaml")
+        .write_str(indoc::indoc! {r"
+        identify:
+          extensions:
+            snake: [python, text]
+        repos:
+          - repo: local
+            hooks:
+              - id: python-files
+                name: python-files
+                entry: echo
+                language: system
+                types: [python]
+    "})?;
+    sub.child("rules.snake").write_str("rule all:\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ sub
+      python-files.......................................(no files to check)Skipped
+
+    ----- stderr -----
+    warning: Ignoring `identify` in `sub/.pre-commit-config.yaml`, it is only read from the workspace root configuration
+    ");
+
+    Ok(())
+}
+
+#[cfg(unix)] // "executable" tag is different on Windows
+#[test]
+fn identify_detect_content() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        identify:
+          detect_content: true
+        repos: []
+    "});
+    context
+        .work_dir()
+        .child("config")
+        .write_str("{\n  \"name\": \"prek\"\n}\n")?;
+    context
+        .work_dir()
+        .child("settings")
+        .write_str("# Settings\nname: prek\nversion: 1\n")?;
+    context
+        .work_dir()
+        .child("NOTES")
+        .write_str("Remember to run prek.\n")?;
+    context.work_dir().child("TODO").write_str("TODO: x\n")?;
+    context
+        .work_dir()
+        .child("defaults")
+        .write_str("FOO=bar\n")?;
+    context
+        .work_dir()
+        .child("notes.draft")
+        .write_str("name: prek\nversion: 1\n")?;
+
+    cmd_snapshot!(
+        context.filters(),
+        context
+            .command()
+            .arg("util")
+            .arg("identify")
+            .arg("config")
+            .arg("settings")
+            .arg("NOTES")
+            .arg("TODO")
+            .arg("defaults")
+            .arg("notes.draft"),
+        @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    config: file, json, non-executable, text
+    settings: file, non-executable, text, yaml
+    NOTES: file, non-executable, text
+    TODO: file, non-executable, text
+    defaults: file, non-executable, text
+    notes.draft: file, non-executable, text
+
+    ----- stderr -----
+    "
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use prek_consts::{PRE_COMMIT_CONFIG_YAML, PREK_TOML};
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn init_yes_writes_builtin_hooks() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("README.md").write_str("# Hello\n")?;
+    cwd.child("data.json").write_str("{}\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("init").arg("--yes").arg("--no-install"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `.pre-commit-config.yaml`
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read(PRE_COMMIT_CONFIG_YAML), @r"
+    # See https://prek.j178.dev for more information.
+    repos:
+      - repo: builtin
+        hooks:
+          - id: trailing-whitespace
+          - id: end-of-file-fixer
+          - id: check-added-large-files
+          - id: check-json
+    ");
+
+    // An existing config is never overwritten.
+    cmd_snapshot!(context.filters(), context.command().arg("init").arg("--yes").arg("--format").arg("toml"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Found existing config `.pre-commit-config.yaml`, use `prek update` to update it
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn init_yes_toml() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child("README.md")
+        .write_str("# Hello\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("init").arg("--yes").arg("--no-install").arg("--format").arg("toml"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `prek.toml`
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read(PREK_TOML), @r#"
+    # Configuration file for `prek`, a git hook framework written in Rust.
+    # See https://prek.j178.dev for more information.
+    #:schema https://www.schemastore.org/prek.json
+
+    [[repos]]
+    repo = "builtin"
+    hooks = [
+        { id = "trailing-whitespace" },
+        { id = "end-of-file-fixer" },
+        { id = "check-added-large-files" },
+    ]
+    "#);
+
+    Ok(())
+}
+
+#[test]
+fn init_suggests_local_rust_hooks_from_subdirectory() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("src/main.rs").write_str("fn main() {}\n")?;
+    context.git_add(".");
+
+    // The config is written to the repository root, not the current directory.
+    cmd_snapshot!(context.filters(), context.command().current_dir(cwd.child("src")).arg("init").arg("--yes").arg("--no-install"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Written to `[TEMP_DIR]/.pre-commit-config.yaml`
+
+    ----- stderr -----
+    ");
+
+    insta::assert_snapshot!(context.read(PRE_COMMIT_CONFIG_YAML), @r"
+    # See https://prek.j178.dev for more information.
+    repos:
+      - repo: builtin
+        hooks:
+          - id: trailing-whitespace
+          - id: end-of-file-fixer
+          - id: check-added-large-files
+      - repo: local
+        hooks:
+          - id: cargo-fmt
+            name: cargo fmt
+            entry: cargo fmt --
+            language: system
+            types: [rust]
+          - id: cargo-clippy
+            name: cargo clippy
+            entry: cargo clippy --all-targets -- -D warnings
+            language: system
+            types: [rust]
+            pass_filenames: false
+    ");
+
+    // The existing config at the root is found from the subdirectory as well.
+    cmd_snapshot!(context.filters(), context.command().current_dir(cwd.child("src")).arg("init").arg("--yes"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Found existing config `[TEMP_DIR]/.pre-commit-config.yaml`, use `prek update` to update it
+    ");
+
+    Ok(())
+}
@@ -679,6 +679,80 @@ fn install_with_existing_legacy_hook() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A hook not managed by prek is never moved over an existing legacy backup.
+#[test]
+fn install_refuses_to_clobber_legacy_hook() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hooks = context.work_dir().child(".git/hooks");
+    hooks
+        .child("pre-commit.legacy")
+        .write_str("#!/bin/sh\necho 'legacy'\n")?;
+    hooks
+        .child("pre-commit")
+        .write_str("#!/bin/sh\necho 'custom'\n")?;
+
+    cmd_snapshot!(context.filters(), context.install(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to overwrite `.git/hooks/pre-commit`, which is not managed by prek, because `.git/hooks/pre-commit.legacy` already exists.
+
+    hint: Move one of them out of the way, or use `--overwrite` to replace both.
+    ");
+    assert_snapshot!(context.read(".git/hooks/pre-commit"), @r"
+    #!/bin/sh
+    echo 'custom'
+    ");
+    assert_snapshot!(context.read(".git/hooks/pre-commit.legacy"), @r"
+    #!/bin/sh
+    echo 'legacy'
+    ");
+
+    context.install().arg("--force").assert().success();
+    hooks
+        .child("pre-commit.legacy")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Git hooks generated by `pre-commit install` are replaced instead of kept as legacy hooks.
+#[test]
+fn install_replaces_pre_commit_script() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child(".git/hooks/pre-commit")
+        .write_str(indoc::indoc! {r#"
+            #!/usr/bin/env bash
+            # File generated by pre-commit: https://pre-commit.com
+            # ID: 138fd403232d2ddd5efb44317e38bf03
+            exec pre-commit hook-impl --config=.pre-commit-config.yaml --hook-type=pre-commit -- "$@"
+        "#})?;
+
+    cmd_snapshot!(context.filters(), context.install(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Replacing `pre-commit` hook at `.git/hooks/pre-commit`
+    prek installed at `.git/hooks/pre-commit`
+
+    ----- stderr -----
+    ");
+    context
+        .work_dir()
+        .child(".git/hooks/pre-commit.legacy")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
 /// Run `prek prepare-hooks` to prepare prek hook environments without installing the git hook.
 #[test]
 fn install_hooks_only() -> anyhow::Result<()> {
@@ -726,6 +800,136 @@ fn install_hooks_only() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `prek prepare-hooks --output-format json` reports how hook environments were resolved.
+#[test]
+fn prepare_hooks_json_output() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: first
+                name: First
+                language: python
+                entry: python -c 'print("first")'
+              - id: second
+                name: Second
+                language: python
+                entry: python -c 'print("second")'
+              - id: system
+                name: System
+                language: system
+                entry: echo
+        "#});
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--output-format").arg("json"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "reused": 0,
+      "installed": 2,
+      "rebuilt": 0
+    }
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--output-format").arg("json"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "reused": 2,
+      "installed": 0,
+      "rebuilt": 0
+    }
+
+    ----- stderr -----
+    "#);
+}
+
+/// `prek prepare-hooks --dry-run` lists what would be cloned and installed without doing it.
+#[test]
+fn prepare_hooks_dry_run() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+            rev: v5.0.0
+            hooks:
+              - id: trailing-whitespace
+          - repo: local
+            hooks:
+              - id: first
+                name: First
+                language: python
+                entry: python -c 'print("first")'
+              - id: second
+                name: Second
+                language: python
+                entry: python -c 'print("second")'
+              - id: system
+                name: System
+                language: system
+                entry: echo
+        "#});
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--dry-run"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Repositories to clone:
+      https://github.com/pre-commit/pre-commit-hooks@v5.0.0
+    Environments to install:
+      python (language_version: default) for local:first, local:second
+    Environments for hooks from repositories that are not cloned yet are not listed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.prepare_hooks().arg("--dry-run").arg("--output-format").arg("json"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "clone": [
+        {
+          "repo": "https://github.com/pre-commit/pre-commit-hooks",
+          "rev": "v5.0.0"
+        }
+      ],
+      "download": [],
+      "install": [
+        {
+          "language": "python",
+          "language_version": "default",
+          "dependencies": [],
+          "hooks": [
+            "local:first",
+            "local:second"
+          ]
+        }
+      ],
+      "reused": 0
+    }
+
+    ----- stderr -----
+    "#);
+
+    // Nothing was cloned or installed.
+    context
+        .home_dir()
+        .child("repos")
+        .assert(predicates::path::missing());
+    context
+        .home_dir()
+        .child("hooks")
+        .assert(predicates::path::missing());
+}
+
 #[test]
 fn install_with_legacy_install_hooks_subcommand_alias() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -838,6 +1042,18 @@ fn uninstall() -> anyhow::Result<()> {
     ----- stderr -----
     ");
 
+    // The legacy hook is restored even if the shim was already deleted.
+    context.install().assert().success();
+    fs_err::remove_file(context.work_dir().join(".git/hooks/pre-commit"))?;
+    cmd_snapshot!(context.filters(), context.uninstall(), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Restored `.git/hooks/pre-commit.legacy` to `.git/hooks/pre-commit`
+
+    ----- stderr -----
+    ");
+
     Ok(())
 }
 
@@ -44,3 +44,78 @@ fn fail() -> Result<()> {
 
     Ok(())
 }
+
+/// `{files}` and `{count}` in `entry` are replaced, and `pattern_hint` is printed last.
+#[test]
+fn fail_message_template() -> Result<()> {
+    let context = TestContext::new();
+
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("changelog").create_dir_all()?;
+    cwd.child("changelog/a.md").touch()?;
+    cwd.child("changelog/b.txt").touch()?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+            - id: changelogs-rst
+              name: changelogs must be rst
+              entry: 'Found {count} changelogs that are not rst: {files}'
+              language: fail
+              files: 'changelog/.*(?<!\.rst)$'
+              pattern_hint: Rename them to `.rst`, changelogs are rendered by Sphinx.
+    "});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    changelogs must be rst...................................................Failed
+    - hook id: changelogs-rst
+    - exit code: 1
+
+      Found 2 changelogs that are not rst: changelog/a.md, changelog/b.txt
+
+      hint: Rename them to `.rst`, changelogs are rendered by Sphinx.
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn pattern_hint_requires_fail_language() {
+    let context = TestContext::new();
+
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+            - id: echo
+              name: echo
+              entry: echo
+              language: system
+              pattern_hint: Not used.
+    "});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `echo`
+      caused by: Hook specified `pattern_hint` but only `fail` hooks support it, not `system` hooks
+    ");
+}
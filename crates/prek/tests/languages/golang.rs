@@ -110,6 +110,7 @@ fn language_version() -> anyhow::Result<()> {
     - duration: [TIME]
 
       go version go1.24.X [OS]/[ARCH]
+    Hook environments: 0 reused, 6 installed, 0 rebuilt
 
     ----- stderr -----
     "#);
@@ -237,6 +238,101 @@ fn remote_hook() {
     ");
 }
 
+/// Test that `go run <module>@<version>` entries only install the toolchain.
+#[test]
+fn go_run_module() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: golang
+                name: golang
+                language: golang
+                entry: go run mvdan.cc/gofumpt@v0.8.0 -h
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    golang...................................................................Passed
+    - hook id: golang
+    - duration: [TIME]
+
+      usage: gofumpt [flags] [path ...]
+      	-version  show version and exit
+
+      	-d        display diffs instead of rewriting files
+      	-e        report all errors (not just the first 10 on different lines)
+      	-l        list files whose formatting differs from gofumpt's
+      	-w        write result to (source) file instead of stdout
+      	-extra    enable extra rules which should be vetted by a human
+
+      	-lang       str    target Go version in the form "go1.X" (default from go.mod)
+      	-modpath    str    Go module path containing the source file (default from go.mod)
+
+    ----- stderr -----
+    "#);
+
+    // The environment is keyed by the module, and nothing is installed into it.
+    let envs = fs_err::read_dir(context.home_dir().child("hooks"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(envs.len(), 1);
+    let info = fs_err::read_to_string(envs[0].join(".prek-hook.json"))?;
+    assert!(info.contains("mvdan.cc/gofumpt@v0.8.0"));
+    assert!(!envs[0].join("bin").exists());
+
+    Ok(())
+}
+
+/// Paths computed at install time are recorded relative to the store and set on every run.
+#[test]
+fn recorded_env() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: golang
+                name: golang
+                language: golang
+                entry: go env GOBIN
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    let output = context.run().output()?;
+    assert!(output.status.success());
+
+    let envs = fs_err::read_dir(context.home_dir().child("hooks"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(envs.len(), 1);
+    let info: serde_json::Value =
+        serde_json::from_str(&fs_err::read_to_string(envs[0].join(".prek-hook.json"))?)?;
+    let go_bin = info["env"][EnvVars::GOBIN]
+        .as_str()
+        .expect("`GOBIN` should be recorded");
+    assert!(std::path::Path::new(go_bin).is_relative());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains(&*envs[0].join("bin").to_string_lossy()));
+
+    Ok(())
+}
+
 /// Fix <https://github.com/j178/prek/issues/901>
 #[test]
 fn local_additional_deps() -> anyhow::Result<()> {
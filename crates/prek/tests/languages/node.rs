@@ -101,6 +101,7 @@ fn language_version() -> anyhow::Result<()> {
     - duration: [TIME]
 
       v20.X.X
+    Hook environments: 0 reused, 6 installed, 0 rebuilt
 
     ----- stderr -----
     "#);
@@ -351,3 +352,86 @@ fn npm_version() {
     ----- stderr -----
     ");
 }
+
+/// Test that `use_project_node_modules` runs the entry from the project's `node_modules/.bin`.
+#[cfg(unix)]
+#[test]
+fn use_project_node_modules() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let bin_dir = context.work_dir().child("node_modules/.bin");
+    bin_dir.create_dir_all()?;
+    let cli = bin_dir.child("project-tool");
+    cli.write_str(indoc::indoc! {r#"
+        #!/usr/bin/env node
+        console.log("project tool ok")
+    "#})?;
+    make_executable(cli.path())?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: node
+                name: node
+                language: node
+                entry: project-tool
+                use_project_node_modules: true
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "});
+    context.git_add(".pre-commit-config.yaml");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    node.....................................................................Passed
+    - hook id: node
+    - duration: [TIME]
+
+      project tool ok
+
+    ----- stderr -----
+    ");
+
+    // No environment was installed for the hook.
+    context
+        .home_dir()
+        .child("hooks")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// `use_project_node_modules` is only supported by `node` hooks.
+#[test]
+fn use_project_node_modules_requires_node() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                use_project_node_modules: true
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `echo`
+      caused by: Hook specified `use_project_node_modules` but only `node` hooks support it, not `system` hooks
+    ");
+}
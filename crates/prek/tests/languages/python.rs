@@ -107,6 +107,7 @@ fn language_version() -> anyhow::Result<()> {
     - duration: [TIME]
 
       (3, 11)
+    Hook environments: 0 reused, 7 installed, 0 rebuilt
 
     ----- stderr -----
     "#);
@@ -373,6 +374,81 @@ fn hook_stderr() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that `setup` commands run once in the environment after it is installed.
+#[test]
+fn setup_commands() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: setup-commands
+                name: setup-commands
+                language: python
+                entry: python ./count.py
+                setup: ['python ./record.py']
+                always_run: true
+                verbose: true
+                pass_filenames: false
+    "});
+
+    context
+        .work_dir()
+        .child("record.py")
+        .write_str(indoc::indoc! {r#"
+        import os
+        import sys
+
+        with open("setup.log", "a") as f:
+            f.write(sys.prefix + " " + os.environ.get("VIRTUAL_ENV", "") + "\n")
+    "#})?;
+    context
+        .work_dir()
+        .child("count.py")
+        .write_str(indoc::indoc! {r#"
+        import os
+        import sys
+
+        with open("setup.log") as f:
+            prefixes = f.read().splitlines()
+        print(len(prefixes), prefixes[0] == sys.prefix + " " + os.environ["VIRTUAL_ENV"])
+    "#})?;
+
+    context.git_add(".");
+
+    // The setup command runs with the hook environment's Python and `VIRTUAL_ENV`.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    setup-commands...........................................................Passed
+    - hook id: setup-commands
+    - duration: [TIME]
+
+      1 True
+
+    ----- stderr -----
+    ");
+
+    // The environment is reused, so the setup command does not run again.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    setup-commands...........................................................Passed
+    - hook id: setup-commands
+    - duration: [TIME]
+
+      1 True
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test that pep723 script for local hook is installed correctly.
 /// Only if no additional dependencies are specified.
 #[test]
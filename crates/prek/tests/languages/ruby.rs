@@ -51,6 +51,7 @@ fn system_ruby() {
     - duration: [TIME]
 
       ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    Hook environments: 0 reused, 2 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -93,6 +94,7 @@ fn language_version_default() {
     - duration: [TIME]
 
       ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -182,6 +184,7 @@ fn specific_ruby_available() {
     - duration: [TIME]
 
       ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    Hook environments: 0 reused, 5 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -329,6 +332,7 @@ fn additional_gem_dependencies() -> anyhow::Result<()> {
       <internal:[RUBY_LIB]>:[X]:in 'Kernel#require': cannot load such file -- rspec (LoadError)
       	from <internal:[RUBY_LIB]>:[X]:in 'Kernel#require'
       	from test_script.rb:1:in '<main>'
+    Hook environments: 0 reused, 3 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -403,6 +407,7 @@ fn gemspec_workflow() -> anyhow::Result<()> {
     - duration: [TIME]
 
       Hello from TestGem
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -685,6 +690,7 @@ fn local_hook_with_gemspec() -> anyhow::Result<()> {
     - duration: [TIME]
 
       Hook executed from gem!
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -739,6 +745,7 @@ fn native_gem_dependency() -> anyhow::Result<()> {
 
       MessagePack native extension working!
       Packed size: 21 bytes
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -870,6 +877,7 @@ fn auto_download() -> anyhow::Result<()> {
     - duration: [TIME]
 
       ruby 3.4.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    Hook environments: 0 reused, 2 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -932,6 +940,7 @@ fn auto_download() -> anyhow::Result<()> {
     - duration: [TIME]
 
       ruby 3.2.X ([DATE] revision [HASH]) [FLAGS] [PLATFORM]
+    Hook environments: 1 reused, 0 installed, 0 rebuilt
 
     ----- stderr -----
     ");
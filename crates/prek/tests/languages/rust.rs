@@ -73,6 +73,7 @@ fn language_version() -> Result<()> {
     - duration: [TIME]
 
       rustc 1.70.X
+    Hook environments: 0 reused, 3 installed, 0 rebuilt
 
     ----- stderr -----
     "#);
@@ -133,6 +134,7 @@ fn rustup_installer() {
     - duration: [TIME]
 
       rustc 1.X.X
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     "#);
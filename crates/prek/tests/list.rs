@@ -69,6 +69,7 @@ fn list_verbose() {
       Name: Check YAML
       Language: system
       Stages: all
+      Priority: 0
 
     .:check-json
       ID: check-json
@@ -76,6 +77,7 @@ fn list_verbose() {
       Description: Validate JSON files
       Language: system
       Stages: all
+      Priority: 1
 
 
     ----- stderr -----
@@ -116,6 +118,7 @@ fn list_verbose() {
       Description: Custom formatting tool with specific requirements
       Language: script
       Stages: pre-commit, pre-push
+      Priority: 0
 
 
     ----- stderr -----
@@ -384,6 +387,72 @@ fn list_group_excluded_remote_repo_is_not_cloned() {
     );
 }
 
+#[test]
+fn list_tree() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: check-yaml
+                name: Check YAML
+                entry: check-yaml
+                language: system
+                types: [yaml]
+              - id: format-python
+                name: Format Python
+                entry: black
+                language: python
+                types: [python]
+                stages: [pre-push]
+    "});
+
+    cmd_snapshot!(context.filters(), context.list().arg("--tree"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .
+    └── local
+        ├── · check-yaml (system)
+        └── ○ format-python (python)
+
+    ✔ installed  ○ not installed  · no environment needed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.list().arg("--filter").arg("language=python"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:format-python
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.list().arg("--filter").arg("repo=local").arg("--filter").arg("stage=pre-commit"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:check-yaml
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.list().arg("--filter").arg("color=red"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'color=red' for '--filter <KEY=VALUE>': unknown filter key `color`, expected one of `language`, `stage`, `repo`
+
+    For more information, try '--help'.
+    ");
+}
+
 #[test]
 fn list_with_aliases() {
     let context = TestContext::new();
@@ -427,6 +496,7 @@ fn list_with_aliases() {
       Name: Check YAML
       Language: system
       Stages: all
+      Priority: 0
 
 
     ----- stderr -----
@@ -495,6 +565,7 @@ fn list_json_output() {
                 entry: check-json
                 language: system
                 types: [json]
+                exclude: ^vendor/
                 description: Validate JSON files
     "});
 
@@ -507,10 +578,12 @@ fn list_json_output() {
       {
         "id": "check-yaml",
         "full_id": ".:check-yaml",
+        "project": ".",
         "name": "Check YAML",
         "alias": "yaml-check",
         "language": "system",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -518,20 +591,26 @@ fn list_json_output() {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": null
       },
       {
         "id": "check-json",
         "full_id": ".:check-json",
+        "project": ".",
         "name": "Check JSON",
         "alias": "",
         "language": "system",
         "description": "Validate JSON files",
+        "priority": 1,
         "stages": [
           "manual",
           "commit-msg",
@@ -539,12 +618,16 @@ fn list_json_output() {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": "^vendor/",
+        "installed": null
       }
     ]
 
@@ -560,10 +643,12 @@ fn list_json_output() {
       {
         "id": "check-json",
         "full_id": ".:check-json",
+        "project": ".",
         "name": "Check JSON",
         "alias": "",
         "language": "system",
         "description": "Validate JSON files",
+        "priority": 1,
         "stages": [
           "manual",
           "commit-msg",
@@ -571,12 +656,16 @@ fn list_json_output() {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": "^vendor/",
+        "installed": null
       }
     ]
 
@@ -635,10 +724,12 @@ fn workspace_list() -> anyhow::Result<()> {
       {
         "id": "show-cwd",
         "full_id": "nested/project4:show-cwd",
+        "project": "nested/project4",
         "name": "Show CWD",
         "alias": "",
         "language": "python",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -646,20 +737,26 @@ fn workspace_list() -> anyhow::Result<()> {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": false
       },
       {
         "id": "show-cwd",
         "full_id": "project3/project5:show-cwd",
+        "project": "project3/project5",
         "name": "Show CWD",
         "alias": "",
         "language": "python",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -667,20 +764,26 @@ fn workspace_list() -> anyhow::Result<()> {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": false
       },
       {
         "id": "show-cwd",
         "full_id": "project2:show-cwd",
+        "project": "project2",
         "name": "Show CWD",
         "alias": "",
         "language": "python",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -688,20 +791,26 @@ fn workspace_list() -> anyhow::Result<()> {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": false
       },
       {
         "id": "show-cwd",
         "full_id": "project3:show-cwd",
+        "project": "project3",
         "name": "Show CWD",
         "alias": "",
         "language": "python",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -709,20 +818,26 @@ fn workspace_list() -> anyhow::Result<()> {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": false
       },
       {
         "id": "show-cwd",
         "full_id": ".:show-cwd",
+        "project": ".",
         "name": "Show CWD",
         "alias": "",
         "language": "python",
         "description": null,
+        "priority": 0,
         "stages": [
           "manual",
           "commit-msg",
@@ -730,12 +845,16 @@ fn workspace_list() -> anyhow::Result<()> {
           "post-commit",
           "post-merge",
           "post-rewrite",
+          "pre-auto-gc",
           "pre-commit",
           "pre-merge-commit",
           "pre-push",
           "pre-rebase",
           "prepare-commit-msg"
-        ]
+        ],
+        "files": null,
+        "exclude": null,
+        "installed": false
       }
     ]
 
@@ -761,12 +880,14 @@ fn workspace_list() -> anyhow::Result<()> {
       Name: Show CWD
       Language: python
       Stages: all
+      Priority: 0
 
     .:show-cwd
       ID: show-cwd
       Name: Show CWD
       Language: python
       Stages: all
+      Priority: 0
 
 
     ----- stderr -----
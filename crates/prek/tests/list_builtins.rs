@@ -11,6 +11,7 @@ fn list_builtins_basic() {
     exit_code: 0
     ----- stdout -----
     check-added-large-files
+    check-ast
     check-case-conflict
     check-executables-have-shebangs
     check-illegal-windows-names
@@ -23,6 +24,7 @@ fn list_builtins_basic() {
     check-vcs-permalinks
     check-xml
     check-yaml
+    debug-statements
     deny-pattern
     destroyed-symlinks
     detect-private-key
@@ -51,6 +53,9 @@ fn list_builtins_verbose() {
     check-added-large-files
       prevents giant files from being committed.
 
+    check-ast
+      simply checks whether the files parse as valid python.
+
     check-case-conflict
       checks for files that would conflict in case-insensitive filesystems
 
@@ -87,6 +92,9 @@ fn list_builtins_verbose() {
     check-yaml
       checks yaml files for parseable syntax.
 
+    debug-statements
+      checks for debugger imports and py37+ `breakpoint()` calls in python source.
+
     deny-pattern
       fails if any file contains a matching regular expression.
 
@@ -142,6 +150,11 @@ fn list_builtins_json() {
         "name": "check for added large files",
         "description": "prevents giant files from being committed."
       },
+      {
+        "id": "check-ast",
+        "name": "check python ast",
+        "description": "simply checks whether the files parse as valid python."
+      },
       {
         "id": "check-case-conflict",
         "name": "check for case conflicts",
@@ -202,6 +215,11 @@ fn list_builtins_json() {
         "name": "check yaml",
         "description": "checks yaml files for parseable syntax."
       },
+      {
+        "id": "debug-statements",
+        "name": "debug statements (python)",
+        "description": "checks for debugger imports and py37+ `breakpoint()` calls in python source."
+      },
       {
         "id": "deny-pattern",
         "name": "deny patterns",
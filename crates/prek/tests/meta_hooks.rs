@@ -152,6 +152,55 @@ fn check_useless_excludes_remote() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `check-useless-excludes` reports a repo-level `exclude` that matches no files, and checks
+/// hook excludes only against the files the repo-level `files` pattern lets through.
+#[test]
+fn check_useless_excludes_repo_patterns() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let pre_commit_config = indoc::indoc! {r"
+    repos:
+      - repo: https://github.com/psf/black-pre-commit-mirror
+        rev: 25.1.0
+        files: ^src/
+        exclude: ^src/generated/
+        hooks:
+          - id: black
+            exclude: '^html/'
+      - repo: meta
+        hooks:
+            - id: check-useless-excludes
+    "};
+    context.work_dir().child("html").create_dir_all()?;
+    context
+        .work_dir()
+        .child("html/file1.html")
+        .write_str("<!DOCTYPE html>")?;
+    context
+        .work_dir()
+        .child("src/main.py")
+        .write_str("print('hello')\n")?;
+
+    context.write_pre_commit_config(pre_commit_config);
+    context.git_add(".");
+    cmd_snapshot!(context.filters(), context.run().arg("check-useless-excludes"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Check useless excludes...................................................Failed
+    - hook id: check-useless-excludes
+    - exit code: 1
+
+      The exclude pattern `regex: ^src/generated/` for repo `https://github.com/psf/black-pre-commit-mirror` does not match any files
+      The exclude pattern `regex: ^html/` for `black` does not match any files
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn meta_hooks_workspace() -> anyhow::Result<()> {
     let context = TestContext::new();
@@ -271,3 +320,97 @@ fn check_useless_excludes_workspace_paths_are_project_relative() -> anyhow::Resu
 
     Ok(())
 }
+
+#[test]
+fn workspace_meta_hooks() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: meta
+            hooks:
+              - id: check-duplicate-hooks-across-projects
+              - id: check-rev-consistency
+          - repo: https://github.com/astral-sh/ruff-pre-commit
+            rev: v0.1.0
+            hooks:
+              - id: ruff
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+    "})?;
+
+    let app = context.work_dir().child("app");
+    app.create_dir_all()?;
+    app.child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/astral-sh/ruff-pre-commit
+            rev: v0.2.0
+            hooks:
+              - id: ruff
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+    "})?;
+
+    // Orphan projects hide their files from parent projects, so their hooks never run twice.
+    let lib = context.work_dir().child("lib");
+    lib.create_dir_all()?;
+    lib.child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(indoc::indoc! {r"
+        orphan: true
+        repos:
+          - repo: https://github.com/astral-sh/ruff-pre-commit
+            rev: v0.1.0
+            hooks:
+              - id: ruff
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+    "})?;
+
+    // Projects excluded by `.prekignore` are not workspace members.
+    context
+        .work_dir()
+        .child(".prekignore")
+        .write_str("vendor/\n")?;
+    let vendor = context.work_dir().child("vendor");
+    vendor.create_dir_all()?;
+    vendor
+        .child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/astral-sh/ruff-pre-commit
+            rev: v0.3.0
+            hooks:
+              - id: ruff
+    "})?;
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("check-duplicate-hooks-across-projects").arg("check-rev-consistency"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    × <workspace>
+      Check duplicate hooks across projects..................................Failed
+      - hook id: check-duplicate-hooks-across-projects
+      - exit code: 1
+
+        app: `ruff` from `https://github.com/astral-sh/ruff-pre-commit` is also configured in parent project `.`
+        app: `trailing-whitespace` from `builtin` is also configured in parent project `.`
+      Check rev consistency..................................................Failed
+      - hook id: check-rev-consistency
+      - exit code: 1
+
+        .: `https://github.com/astral-sh/ruff-pre-commit` is pinned to `v0.1.0`, while other projects use `v0.2.0` (app)
+        app: `https://github.com/astral-sh/ruff-pre-commit` is pinned to `v0.2.0`, while other projects use `v0.1.0` (., lib)
+        lib: `https://github.com/astral-sh/ruff-pre-commit` is pinned to `v0.1.0`, while other projects use `v0.2.0` (app)
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+#[test]
+fn plan_groups_hooks_by_priority() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: lint
+                name: Lint
+                entry: echo
+                language: system
+                priority: 10
+              - id: format
+                name: Format
+                entry: echo
+                language: system
+                priority: 0
+              - id: typecheck
+                name: Typecheck
+                entry: echo
+                language: system
+                priority: 10
+                require_serial_project: true
+              - id: push-only
+                name: Push Only
+                entry: echo
+                language: system
+                stages: [pre-push]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("plan"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .
+    ├── priority 0
+    │   └── format
+    └── priority 10
+        ├── lint
+        └── typecheck (runs alone)
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.command().arg("plan").arg("--stage").arg("pre-push"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .
+    ├── priority 0
+    │   └── format
+    ├── priority 3
+    │   └── push-only
+    └── priority 10
+        ├── lint
+        └── typecheck (runs alone)
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.command().arg("plan").arg("push-only"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No hooks found for stage `pre-commit`
+    ");
+}
+
+#[test]
+fn plan_lists_projects_in_run_order() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: show
+                name: Show
+                entry: echo
+                language: system
+    "};
+    context.write_pre_commit_config(config);
+    context
+        .work_dir()
+        .child("app/.pre-commit-config.yaml")
+        .write_str(config)?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().arg("plan"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    app
+    └── priority 0
+        └── show
+
+    .
+    └── priority 0
+        └── show
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
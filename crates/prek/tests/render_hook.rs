@@ -0,0 +1,50 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot};
+
+mod common;
+
+/// `pass_filenames: <n>` limits the number of files in each rendered batch.
+#[cfg(unix)]
+#[test]
+fn render_hook_limited_pass_filenames() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: check
+                name: Check
+                entry: check.sh
+                language: script
+                pass_filenames: 2
+    "});
+    let cwd = context.work_dir();
+    cwd.child("check.sh").write_str("exit 0\n")?;
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    cwd.child("c.txt").write_str("c")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.command().args(["render-hook", "check", "--files", "a.txt", "b.txt", "c.txt"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .:check
+      Language: script
+      Working directory: [TEMP_DIR]/
+      Environment: (none)
+      Matched: 3 of 3 files
+      Pass filenames: at most 2 per batch
+      Batches: 2
+        [1] [TEMP_DIR]/check.sh a.txt b.txt
+        [2] [TEMP_DIR]/check.sh c.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
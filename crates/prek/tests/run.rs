@@ -1,4 +1,6 @@
+use std::io::Write;
 use std::path::Path;
+use std::process::Stdio;
 use std::time::SystemTime;
 
 use anyhow::Result;
@@ -160,6 +162,53 @@ fn run_preserves_stdout_stderr_order() -> Result<()> {
     Ok(())
 }
 
+/// Streamed output is printed live with a hook id label, and still shown in the summary.
+#[test]
+fn run_streams_hook_output() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: streamed
+                name: streamed
+                language: system
+                entry: python3 -c 'print("first"); print("second", end="")'
+                always_run: true
+                pass_filenames: false
+                stream_output: true
+              - id: failing
+                name: failing
+                language: system
+                entry: python3 -c 'import sys; print("broken"); sys.exit(1)'
+                always_run: true
+                pass_filenames: false
+                stream_output: true
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "--color=never"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    [streamed] first
+    [streamed] second
+    streamed.................................................................Passed
+    [failing] broken
+    failing..................................................................Failed
+    - hook id: failing
+    - exit code: 1
+
+      broken
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn run_does_not_rewrite_unchanged_config_tracking_file() -> Result<()> {
     let context = TestContext::new();
@@ -401,6 +450,106 @@ fn invalid_config() {
     ");
 }
 
+/// A `language_version` target map without a pattern matching the host warns and falls back
+/// to the default version.
+#[test]
+fn language_version_no_matching_target() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo
+                name: echo
+                language: system
+                entry: echo
+                language_version:
+                  no-such-target: '1'
+                always_run: true
+                pass_filenames: false
+    "});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"matches `[^`]+`", "matches `[HOST]`")])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters, context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo.....................................................................Passed
+
+    ----- stderr -----
+    warning: No `language_version` target of hook `echo` matches `[HOST]`, using the default version
+    ");
+}
+
+/// Repo-level `files` and `exclude` of a remote repo apply to each of its hooks, on top of the
+/// hook's own patterns.
+#[test]
+fn remote_repo_file_patterns() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/hooks");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir).arg("init").assert().success();
+    repo_dir
+        .child(PRE_COMMIT_HOOKS_YAML)
+        .write_str(indoc::indoc! {r"
+        - id: show-files
+          name: Show Files
+          entry: echo
+          language: system
+          files: \.txt$
+          verbose: true
+    "})?;
+    git_cmd(&repo_dir).args(["add", "."]).assert().success();
+    git_cmd(&repo_dir)
+        .args(["commit", "-m", "Initial commit"])
+        .assert()
+        .success();
+    let output = git_cmd(&repo_dir).args(["rev-parse", "HEAD"]).output()?;
+    let commit_sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: {commit_sha}
+            files: ^src/
+            exclude: ^src/vendor/
+            hooks:
+              - id: show-files
+    ", repo_dir.display()});
+
+    let cwd = context.work_dir();
+    cwd.child("src/a.txt").write_str("a")?;
+    cwd.child("src/b.md").write_str("b")?;
+    cwd.child("src/vendor/c.txt").write_str("c")?;
+    cwd.child("d.txt").write_str("d")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      src/a.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Use same repo multiple times, with same or different revisions.
 #[test]
 fn same_repo() -> Result<()> {
@@ -923,6 +1072,59 @@ fn run_group_selectors_reject_whitespace() {
     "#);
 }
 
+/// `--repo` selects hooks by source repo, and unselected remote repos are never cloned.
+#[test]
+fn run_repo_selector_filters_hooks_by_source_repo() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: https://github.com/prek-test/does-not-exist.git
+            rev: v1.0.0
+            hooks:
+              - id: remote-hook
+          - repo: local
+            hooks:
+              - id: lint
+                name: Lint
+                language: system
+                entry: python3 -c "print('lint')"
+                always_run: true
+    "#});
+
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--repo").arg("local"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Lint.....................................................................Passed
+
+    ----- stderr -----
+    "#);
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--repo").arg("local").arg("--repo").arg("pre-commit-hooks"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Lint.....................................................................Passed
+
+    ----- stderr -----
+    warning: repo selector `--repo=pre-commit-hooks` did not match any hooks
+    "#);
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--repo").arg(""), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Invalid repo selector: `--repo=`
+      caused by: repo selector cannot be empty
+    "#);
+}
+
 #[test]
 fn run_group_and_stage_filters_intersect() {
     let context = TestContext::new();
@@ -1081,6 +1283,104 @@ fn priority_group_modified_files_is_group_failure_and_output_is_indented() -> Re
     Ok(())
 }
 
+/// Builtin fixers in one priority group that match the same file run one after another, so
+/// neither overwrites the other's fix.
+#[test]
+fn priority_group_serializes_overlapping_writers() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("file.txt").write_str("line  \nlast  ")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: trailing-whitespace
+                priority: 0
+              - id: end-of-file-fixer
+                priority: 0
+              - id: mixed-line-ending
+                priority: 0
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Files were modified by following hooks...................................Failed
+      ┌ trim trailing whitespace.............................................Failed
+      │ - hook id: trailing-whitespace
+      │ - exit code: 1
+      │
+      │ Fixing file.txt
+      │ fix end of files.....................................................Failed
+      │ - hook id: end-of-file-fixer
+      │ - exit code: 1
+      │
+      │ Fixing file.txt
+      └ mixed line ending....................................................Passed
+
+    ----- stderr -----
+    ");
+
+    assert_snapshot!(context.read("file.txt"), @r"
+    line
+    last
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn require_serial_project_runs_alone() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    // Without `require_serial_project`, `migrate` would finish while `slow` is still sleeping.
+    // Both write to a scratch file outside the worktree.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: migrate
+                name: Migrate
+                language: system
+                entry: python3 -c "open('.git/order.txt', 'a').write('migrate\n')"
+                always_run: true
+                pass_filenames: false
+                require_serial_project: true
+                priority: 0
+              - id: slow
+                name: Slow
+                language: system
+                entry: python3 -c "import time; time.sleep(0.5); open('.git/order.txt', 'a').write('slow\n')"
+                always_run: true
+                pass_filenames: false
+                priority: 0
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Migrate..................................................................Passed
+    Slow.....................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    assert_snapshot!(context.read(".git/order.txt"), @r"
+    slow
+    migrate
+    ");
+
+    Ok(())
+}
+
 /// `.pre-commit-config.yaml` is not staged.
 #[test]
 fn config_not_staged() -> Result<()> {
@@ -1181,6 +1481,52 @@ fn cjk_hook_name() {
     "#);
 }
 
+/// Output of a hook with `output_encoding` is decoded from that encoding for display.
+#[test]
+fn output_encoding_cp936() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context
+        .work_dir()
+        .child("output.py")
+        .write_str(indoc::indoc! {r#"
+            import os
+
+            # "你好" encoded in GBK.
+            os.write(1, b"\xc4\xe3\xba\xc3\n")
+        "#})?;
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: localized
+                name: localized
+                language: system
+                entry: python3 output.py
+                always_run: true
+                pass_filenames: false
+                verbose: true
+                output_encoding: cp936
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "--color=never"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    localized................................................................Passed
+    - hook id: localized
+    - duration: [TIME]
+
+      你好
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Skips hooks based on the `SKIP` environment variable.
 #[test]
 fn skips() {
@@ -1705,27 +2051,217 @@ fn no_fail_fast_cli_flag() {
     ");
 }
 
-/// Run from a subdirectory. File arguments should be fixed to be relative to the root.
+/// Test --exit-zero CLI flag reports failures but exits successfully.
 #[test]
-fn subdirectory() -> Result<()> {
+fn exit_zero_cli_flag() {
     let context = TestContext::new();
     context.init_project();
 
-    let cwd = context.work_dir();
-    let child = cwd.child("foo/bar/baz");
-    child.create_dir_all()?;
-    child.child("file.txt").write_str("Hello, world!\n")?;
-
-    context.write_pre_commit_config(indoc::indoc! {r"
+    context.write_pre_commit_config(indoc::indoc! {r#"
         repos:
           - repo: local
             hooks:
-              - id: trailing-whitespace
-                name: trailing-whitespace
+              - id: failing-hook
+                name: failing-hook
                 language: system
-                entry: python3 -c 'import sys; print(sys.argv[1]); exit(1)'
+                entry: python3 -c 'print("Failed"); exit(1)'
                 always_run: true
-    "});
+              - id: passing-hook
+                name: passing-hook
+                language: system
+                entry: python3 -c 'print("Passed")'
+                always_run: true
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--exit-zero"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    failing-hook.............................................................Failed
+    - hook id: failing-hook
+    - exit code: 1
+
+      Failed
+    passing-hook.............................................................Passed
+
+    ----- stderr -----
+    ");
+}
+
+/// Failures of advisory hooks are reported but do not fail the run or trigger `fail_fast`.
+#[test]
+fn advisory_hook() {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        fail_fast: true
+        repos:
+          - repo: local
+            hooks:
+              - id: advisory-hook
+                name: advisory-hook
+                language: system
+                entry: python3 -c 'print("Failed"); exit(1)'
+                always_run: true
+                advisory: true
+              - id: passing-hook
+                name: passing-hook
+                language: system
+                entry: python3 -c 'print("Passed")'
+                always_run: true
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    advisory-hook............................................................Failed
+    - hook id: advisory-hook
+    - exit code: 1
+
+      Failed
+    passing-hook.............................................................Passed
+
+    ----- stderr -----
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: advisory-hook
+                name: advisory-hook
+                language: system
+                entry: python3 -c 'print("Failed"); exit(1)'
+                always_run: true
+                advisory: true
+              - id: failing-hook
+                name: failing-hook
+                language: system
+                entry: python3 -c 'print("Failed"); exit(1)'
+                always_run: true
+    "#});
+    context.git_add(".");
+
+    // Other hooks still fail the run.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    advisory-hook............................................................Failed
+    - hook id: advisory-hook
+    - exit code: 1
+
+      Failed
+    failing-hook.............................................................Failed
+    - hook id: failing-hook
+    - exit code: 1
+
+      Failed
+
+    ----- stderr -----
+    ");
+}
+
+/// Files modified by an advisory hook do not fail the other hooks of its priority group.
+#[test]
+fn advisory_hook_modifies_files_in_priority_group() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("file.txt").write_str("Hello\n")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: advisory-fixer
+                name: advisory-fixer
+                language: system
+                entry: python3 -c "from pathlib import Path; p = Path('file.txt'); p.write_text(p.read_text() + 'x')"
+                always_run: true
+                pass_filenames: false
+                advisory: true
+                priority: 0
+          - repo: builtin
+            hooks:
+              - id: check-added-large-files
+                priority: 0
+    "#});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Files were modified by following hooks...................................Failed
+      ┌ advisory-fixer.......................................................Passed
+      └ check for added large files..........................................Passed
+
+    ----- stderr -----
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: advisory-fixer
+                name: advisory-fixer
+                language: system
+                entry: python3 -c "from pathlib import Path; p = Path('file.txt'); p.write_text(p.read_text() + 'x')"
+                always_run: true
+                pass_filenames: false
+                advisory: true
+                priority: 0
+              - id: passing-hook
+                name: passing-hook
+                language: system
+                entry: python3 -c 'print("Passed")'
+                always_run: true
+                priority: 0
+    "#});
+    context.git_add(".");
+
+    // A non-advisory hook that may write files in the same group is still blamed.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Files were modified by following hooks...................................Failed
+      ┌ advisory-fixer.......................................................Passed
+      └ passing-hook.........................................................Passed
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Run from a subdirectory. File arguments should be fixed to be relative to the root.
+#[test]
+fn subdirectory() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    let child = cwd.child("foo/bar/baz");
+    child.create_dir_all()?;
+    child.child("file.txt").write_str("Hello, world!\n")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: trailing-whitespace
+                name: trailing-whitespace
+                language: system
+                entry: python3 -c 'import sys; print(sys.argv[1]); exit(1)'
+                always_run: true
+    "});
 
     context.git_add(".");
 
@@ -1782,14 +2318,14 @@ fn global_path_options_expand_tilde() -> Result<()> {
             "[HOME]/project",
         ),
         color: Auto,
-        refresh: false,
+        refresh: None,
         help: (),
         no_progress: false,
         quiet: 0,
         verbose: 0,
         log_file: None,
         no_log_file: false,
-        version: (),
+        version: false,
         show_settings: true,
     }
     RunArgs {
@@ -1809,6 +2345,7 @@ fn global_path_options_expand_tilde() -> Result<()> {
             fail_fast: false,
             no_fail_fast: false,
             dry_run: false,
+            github_summary: false,
             extra: RunExtraArgs {
                 remote_branch: None,
                 local_branch: None,
@@ -2022,6 +2559,88 @@ fn intent_to_add_file_survives_conflicted_stash_restore() -> Result<()> {
     Ok(())
 }
 
+/// Files created by a hook count as modifications, and `intent_to_add` marks them in the index.
+#[test]
+fn hook_created_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    cwd.child("existing.txt")
+        .write_str("untracked before the run\n")?;
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: generate
+                name: generate
+                language: system
+                entry: python3 -c 'open("generated.txt", "w").write("generated\n")'
+                pass_filenames: false
+                always_run: true
+    "#});
+    context.git_add(PRE_COMMIT_CONFIG_YAML);
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    generate.................................................................Failed
+    - hook id: generate
+    - files were modified by this hook
+
+    ----- stderr -----
+    ");
+
+    // Writing the same content again creates no new file.
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    generate.................................................................Passed
+
+    ----- stderr -----
+    ");
+
+    fs_err::remove_file(cwd.join("generated.txt"))?;
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        intent_to_add: true
+        repos:
+          - repo: local
+            hooks:
+              - id: generate
+                name: generate
+                language: system
+                entry: python3 -c 'open("generated.txt", "w").write("generated\n")'
+                pass_filenames: false
+                always_run: true
+    "#});
+    context.git_add(PRE_COMMIT_CONFIG_YAML);
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    generate.................................................................Failed
+    - hook id: generate
+    - files were modified by this hook
+
+    ----- stderr -----
+    ");
+
+    let output = git_cmd(cwd)
+        .arg("diff")
+        .arg("--diff-filter=A")
+        .arg("--name-only")
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    // Untracked files that existed before the run are left alone.
+    assert_eq!(String::from_utf8(output.stdout)?, "generated.txt\n");
+
+    Ok(())
+}
+
 #[cfg(unix)]
 #[test]
 fn restore_on_interrupt() -> Result<()> {
@@ -2261,43 +2880,92 @@ fn init_nonexistent_repo() {
     "#);
 }
 
+/// With `PREK_REQUIRE_TRUST`, remote repos must be listed in the trust file before they are cloned.
 #[test]
-fn skipped_remote_repo_is_not_cloned() {
+fn require_trust_for_remote_repo() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
     context.write_pre_commit_config(indoc::indoc! {r"
         repos:
-          - repo: builtin
-            hooks:
-              - id: end-of-file-fixer
-
           - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
             rev: v1.0.0
             hooks:
-              - id: ruff-check
+              - id: nonexistent
+                name: nonexistent
         "});
     context.git_add(".");
 
-    cmd_snapshot!(
-        context.filters(),
-        context.run().arg("--all-files").arg("--skip").arg("ruff-check"),
-        @r"
-    success: true
-    exit_code: 0
+    cmd_snapshot!(context.filters(), context.run().env(EnvVars::PREK_REQUIRE_TRUST, "1"), @r"
+    success: false
+    exit_code: 2
     ----- stdout -----
-    fix end of files.........................................................Passed
 
     ----- stderr -----
-    "
-    );
-}
+    error: Failed to init hooks
+      caused by: Repository `https://notexistentatallnevergonnahappen.com/nonexistent/repo` at rev `v1.0.0` is not trusted
 
-#[test]
-fn skipped_same_key_remote_repo_entry_is_not_initialized() -> Result<()> {
-    let context = TestContext::new();
-    context.init_project();
+    hint: Add `https://notexistentatallnevergonnahappen.com/nonexistent/repo v1.0.0` as a line to `[HOME]/trusted-repos.txt` to trust it, or rerun in an interactive terminal to be prompted.
+    ");
+    // Nothing is cloned before the repo is trusted.
+    assert_eq!(context.home_dir().child("repos").read_dir()?.count(), 0);
 
-    let hook_repo = context.home_dir().child("duplicate-key-hook-repo");
+    // Once trusted, the repo is cloned as usual.
+    context
+        .home_dir()
+        .child("trusted-repos.txt")
+        .write_str("# Trusted hook repos\nhttps://notexistentatallnevergonnahappen.com/nonexistent/repo v1.0.0\n")?;
+
+    let output = context
+        .run()
+        .env(EnvVars::PREK_REQUIRE_TRUST, "1")
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Failed to clone repo"),
+        "expected a clone failure, got:\n{stderr}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn skipped_remote_repo_is_not_cloned() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: builtin
+            hooks:
+              - id: end-of-file-fixer
+
+          - repo: https://notexistentatallnevergonnahappen.com/nonexistent/repo
+            rev: v1.0.0
+            hooks:
+              - id: ruff-check
+        "});
+    context.git_add(".");
+
+    cmd_snapshot!(
+        context.filters(),
+        context.run().arg("--all-files").arg("--skip").arg("ruff-check"),
+        @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    fix end of files.........................................................Passed
+
+    ----- stderr -----
+    "
+    );
+}
+
+#[test]
+fn skipped_same_key_remote_repo_entry_is_not_initialized() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook_repo = context.home_dir().child("duplicate-key-hook-repo");
     hook_repo.create_dir_all()?;
 
     git_cmd(&hook_repo)
@@ -2586,6 +3254,134 @@ fn run_last_commit() -> Result<()> {
     Ok(())
 }
 
+/// Repeated `--all-files --incremental` runs only check the files changed since a hook last passed.
+#[test]
+fn run_all_files_incremental() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    let config = |extra: &str| {
+        indoc::formatdoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: show-files
+                name: Show Files
+                language: system
+                entry: python3 -c 'import sys; print(sorted(f for f in sys.argv[1:] if f.endswith(".txt")))'
+                args: [--settings=settings.cfg]
+                files: \.txt$
+                verbose: true
+                {extra}
+        "#}
+    };
+    context.write_pre_commit_config(&config(""));
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    cwd.child("settings.cfg").write_str("strict = false")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    // Nothing changed since the hook passed.
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...........................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    // Only the modified file is checked, whether or not it is staged.
+    cwd.child("a.txt").write_str("changed")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt']
+
+    ----- stderr -----
+    ");
+
+    // Without `--incremental`, every file is checked.
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    // Changing a file named in `args` invalidates the baseline.
+    cwd.child("settings.cfg").write_str("strict = true")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    // So does changing a common tool config file.
+    cwd.child("pyproject.toml").write_str("[tool.ruff]\n")?;
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    // And changing the config.
+    context.write_pre_commit_config(&config("require_serial: true"));
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--incremental"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      ['a.txt', 'b.txt']
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 /// Test `prek run --files` with multiple files.
 #[test]
 fn run_multiple_files() -> Result<()> {
@@ -2622,6 +3418,90 @@ fn run_multiple_files() -> Result<()> {
     Ok(())
 }
 
+/// Test `prek run --files-from` with file lists from a file and from stdin.
+#[test]
+fn run_files_from() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: files-from
+                name: files-from
+                language: system
+                entry: echo
+                verbose: true
+                types: [text]
+    "});
+    let cwd = context.work_dir();
+    cwd.child("file1.txt").write_str("Hello, world!")?;
+    cwd.child("file 2.txt").write_str("Hello, world!")?;
+    cwd.child("src/file3.txt").write_str("Hello, world!")?;
+    cwd.child("newline.list")
+        .write_str("file1.txt\r\n\nsrc/file3.txt\n")?;
+    cwd.child("nul.list").write_str("file 2.txt\0file1.txt\0")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from").arg("newline.list"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    files-from...............................................................Passed
+    - hook id: files-from
+    - duration: [TIME]
+
+      file1.txt src/file3.txt
+
+    ----- stderr -----
+    ");
+
+    // NUL-separated lists can contain any path, and combine with `--files`.
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from").arg("nul.list").arg("--files").arg("src/file3.txt"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    files-from...............................................................Passed
+    - hook id: files-from
+    - duration: [TIME]
+
+      file 2.txt file1.txt src/file3.txt
+
+    ----- stderr -----
+    ");
+
+    let mut child = context
+        .run()
+        .arg("--files-from")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(b"src/file3.txt\n")?;
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stdout)?.contains("  src/file3.txt\n"),
+        "hook should only run on the file listed on stdin"
+    );
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files-from").arg("missing.list"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to read the `--files-from` list `missing.list`
+      caused by: No such file or directory (os error 2)
+    ");
+
+    Ok(())
+}
+
 /// Test `prek run --glob` and its interaction with other explicit file selectors.
 #[test]
 fn run_glob() -> Result<()> {
@@ -3263,12 +4143,22 @@ fn selectors_completion() -> Result<()> {
     --from-ref	The original ref in a `<from_ref>...<to_ref>` diff expression. Files changed in this diff will be run through the hooks
     --to-ref	The destination ref in a `from_ref...to_ref` diff expression. Defaults to `HEAD` if `from_ref` is specified
     --last-commit	Run hooks against the last commit. Equivalent to `--from-ref HEAD~1 --to-ref HEAD`
+    --since	Run hooks on files changed since the specified revision, including uncommitted changes
     --show-diff-on-failure	When hooks fail, run `git diff` directly afterward
     --fail-fast	Stop running hooks after the first failure
+    --exit-zero	Report hook failures as usual, but always exit with status code 0
     --dry-run	Do not run the hooks, but print the hooks that would have been run
+    --github-summary	Write a GitHub Actions job summary and fold hook output into log groups
+    --report	Write a machine-readable report of the run to a file, as `<format>=<path>`
     --stage	The stage during which the hook is fired
     --group	Run hooks belonging to the specified group
     --no-group	Do not run hooks belonging to the specified group
+    --repo	Run only hooks that come from the specified repository
+    --flatten	Treat the whole workspace as a single project
+    --dedup-hooks	Run identical remote hooks of several projects only once
+    --incremental	With `--all-files`, only check the files changed since each hook last passed
+    --record	Record the commands hooks run into a directory, with their output and exit code
+    --replay	Answer hook commands from a recording made with `--record` instead of running them
     --config	Path to alternate config file
     --cd	Change to directory before running
     --color	Whether to use color in output
@@ -3277,7 +4167,7 @@ fn selectors_completion() -> Result<()> {
     --no-progress	Hide all progress outputs
     --quiet	Use quiet output
     --verbose	Use verbose output
-    --log-file	Write trace logs to the specified file. If not specified, trace logs will be written to `$PREK_HOME/prek.log`
+    --log-file	Write debug logs to the specified file, as JSON lines. If not specified, logs will be written to `$PREK_HOME/log/prek.log`
     --version	Display the prek version
 
     ----- stderr -----
@@ -3521,6 +4411,7 @@ fn alternate_config_file() -> Result<()> {
     - duration: [TIME]
 
       Hello, world!
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -3548,6 +4439,7 @@ fn alternate_config_file() -> Result<()> {
     - duration: [TIME]
 
       Hello, world!
+    Hook environments: 1 reused, 0 installed, 0 rebuilt
 
     ----- stderr -----
     warning: Multiple configuration files found (`.pre-commit-config.yaml`, `.pre-commit-config.yml`); using `[TEMP_DIR]/.pre-commit-config.yaml`
@@ -3579,6 +4471,7 @@ fn alternate_config_file() -> Result<()> {
     - duration: [TIME]
 
       Hello, world!
+    Hook environments: 1 reused, 0 installed, 0 rebuilt
 
     ----- stderr -----
     warning: Multiple configuration files found (`prek.toml`, `.pre-commit-config.yaml`, `.pre-commit-config.yml`); using `[TEMP_DIR]/prek.toml`
@@ -3619,6 +4512,7 @@ fn prek_toml() -> Result<()> {
     - duration: [TIME]
 
       Hello, world!
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -3722,6 +4616,7 @@ fn show_diff_on_failure() -> Result<()> {
     @@ -1 +1,2 @@
      Original line
     +Added line
+    Hook environments: 0 reused, 1 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -3748,6 +4643,7 @@ fn show_diff_on_failure() -> Result<()> {
     @@ -1 +1,2 @@
      Original line
     +Added line
+    Hook environments: 1 reused, 0 installed, 0 rebuilt
 
     ----- stderr -----
     ");
@@ -3943,7 +4839,7 @@ fn run_log_file() {
     ");
     context
         .home_dir()
-        .child("prek.log")
+        .child("log/prek.log")
         .assert(predicate::path::missing());
 
     // Write log to `log`.
@@ -3965,6 +4861,15 @@ fn run_log_file() {
         .work_dir()
         .child("log")
         .assert(predicate::path::exists());
+
+    // Each line is a structured JSON record.
+    let log = context.read("log");
+    assert!(!log.is_empty());
+    for line in log.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["level"].is_string());
+        assert!(record["fields"].is_object());
+    }
 }
 
 /// Test `language_version: system` works and disables downloading.
@@ -4171,6 +5076,51 @@ fn version_info() {
     ");
 }
 
+/// Test `prek --version --output-format json` includes build metadata.
+#[test]
+fn version_info_json() -> Result<()> {
+    let context = TestContext::new();
+    let output = context
+        .command()
+        .arg("--version")
+        .arg("--output-format")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+    let build_info = &info["build_info"];
+    assert!(
+        build_info["target"]
+            .as_str()
+            .is_some_and(|target| !target.is_empty())
+    );
+    assert!(
+        build_info["date"]
+            .as_str()
+            .is_some_and(|date| date.len() == 10)
+    );
+    let features = build_info["features"]
+        .as_array()
+        .expect("features is an array");
+    assert_eq!(
+        features.iter().any(|feature| feature == "docker"),
+        cfg!(feature = "docker")
+    );
+
+    // `--output-format` is only accepted alongside `--version`.
+    context
+        .command()
+        .arg("--output-format")
+        .arg("json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--version"));
+
+    Ok(())
+}
+
 #[test]
 fn expands_tilde_in_prek_home() -> Result<()> {
     let context = TestContext::new();
@@ -4268,77 +5218,361 @@ fn run_with_tree_object_as_ref() -> Result<()> {
     Ok(())
 }
 
-/// `pass_filenames: n` limits each invocation to at most n files.
-/// With n=1, each matched file gets its own invocation.
+/// A `file_collector` command replaces Git for file selection, except for explicit files.
 #[test]
-fn pass_filenames_1_limits_batch_size() -> Result<()> {
+fn run_with_file_collector() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
 
     let cwd = context.work_dir();
-    // Use a script that errors if it receives more than one filename argument.
     context.write_pre_commit_config(indoc::indoc! {r#"
+        file_collector:
+          entry: python3 -c "import sys; print(sys.argv[1] + '.txt')"
         repos:
           - repo: local
             hooks:
-              - id: one-at-a-time
-                name: one at a time
-                entry: python -c "import sys; args = sys.argv[1:]; sys.exit(0 if len(args) <= 1 else 1)"
+              - id: echo-files
+                name: echo files
+                entry: echo
                 language: system
-                pass_filenames: 1
-                require_serial: true
                 verbose: true
     "#});
 
-    cwd.child("a.txt").write_str("a")?;
-    cwd.child("b.txt").write_str("b")?;
-    cwd.child("c.txt").write_str("c")?;
+    cwd.child("all.txt").write_str("all")?;
+    cwd.child("staged.txt").write_str("staged")?;
+    cwd.child("other.txt").write_str("other")?;
     context.git_add(".");
 
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      staged.txt
+
+    ----- stderr -----
+    ");
+
     cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
     success: true
     exit_code: 0
     ----- stdout -----
-    one at a time............................................................Passed
-    - hook id: one-at-a-time
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      all.txt
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("other.txt"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
     - duration: [TIME]
 
+      other.txt
+
     ----- stderr -----
     ");
 
     Ok(())
 }
 
-/// `pass_filenames: n` limits each invocation to at most n files.
-/// With n=2 and more than 2 matching files, multiple batches are spawned.
+/// In a colocated Jujutsu repo, files come from `jj` and unstaged changes are not stashed.
+#[cfg(unix)]
 #[test]
-fn pass_filenames_2_limits_batch_size() -> Result<()> {
+fn run_in_colocated_jj_repo() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
     let context = TestContext::new();
     context.init_project();
 
     let cwd = context.work_dir();
-    // Use a script that errors if it receives more than two filename arguments.
-    context.write_pre_commit_config(indoc::indoc! {r#"
+    context.write_pre_commit_config(indoc::indoc! {r"
         repos:
           - repo: local
             hooks:
-              - id: two-at-a-time
-                name: two at a time
-                entry: python -c "import sys; args = sys.argv[1:]; sys.exit(0 if len(args) <= 2 else 1)"
+              - id: echo-files
+                name: echo files
+                entry: echo
                 language: system
-                pass_filenames: 2
-                require_serial: true
                 verbose: true
-    "#});
-
-    cwd.child("a.txt").write_str("a")?;
-    cwd.child("b.txt").write_str("b")?;
-    cwd.child("c.txt").write_str("c")?;
-    cwd.child("d.txt").write_str("d")?;
-    cwd.child("e.txt").write_str("e")?;
+    "});
     context.git_add(".");
 
-    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    cwd.child(".jj").create_dir_all()?;
+    cwd.child("changed.txt").write_str("changed")?;
+    cwd.child("renamed.txt").write_str("renamed")?;
+    cwd.child("tracked.txt").write_str("tracked")?;
+
+    // A fake `jj` that answers the queries prek makes after its global options.
+    let fake_bin_dir = context.home_dir().child("fake-bin");
+    fake_bin_dir.create_dir_all()?;
+    let fake_jj = fake_bin_dir.child("jj");
+    fake_jj.write_str(indoc::indoc! {r#"
+        #!/bin/sh
+        while [ "${1#--}" != "$1" ] || [ "$1" = never ]; do shift; done
+        case "$1" in
+          diff) printf 'M changed.txt\nD removed.txt\nR {old => renamed}.txt\n' ;;
+          file) printf 'changed.txt\nrenamed.txt\ntracked.txt\n' ;;
+          log) echo 0123abcd ;;
+        esac
+    "#})?;
+    fs_err::set_permissions(fake_jj.path(), std::fs::Permissions::from_mode(0o755))?;
+
+    let original_path = EnvVars.var_os(EnvVars::PATH).unwrap_or_default();
+    let mut new_path = std::ffi::OsString::from(fake_bin_dir.path());
+    new_path.push(":");
+    new_path.push(&original_path);
+
+    cmd_snapshot!(context.filters(), context.run().env("PATH", &new_path), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      changed.txt renamed.txt
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").env("PATH", &new_path), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      changed.txt renamed.txt tracked.txt
+
+    ----- stderr -----
+    ");
+
+    // With `PREK_NO_JJ`, files come from the Git index again.
+    cmd_snapshot!(context.filters(), context.run().env("PATH", &new_path).env(EnvVars::PREK_NO_JJ, "1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      .pre-commit-config.yaml
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn run_with_revision_range_as_from_ref() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo-files
+                name: echo files
+                entry: echo
+                language: system
+                verbose: true
+    "});
+
+    cwd.child("file1.txt").write_str("hello")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+    context.git_tag("base");
+
+    cwd.child("file2.txt").write_str("world")?;
+    context.git_add("file2.txt");
+    context.git_commit("Add file2");
+
+    // `A...B` diffs against the merge base, and an omitted side means `HEAD`.
+    cmd_snapshot!(context.filters(), context.run().arg("--from-ref").arg("base..."), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      file2.txt
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--from-ref").arg("base...HEAD").arg("--to-ref").arg("base"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--from-ref base...HEAD` already specifies both sides of the diff, so `--to-ref` cannot be used with it
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--from-ref").arg("base..HEAD"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Two-dot ranges are not supported in `--from-ref`, use `--from-ref base...HEAD` or `--from-ref base --to-ref HEAD` instead
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--from-ref").arg("@{upstream}"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Cannot resolve revision `@{upstream}` passed to `--from-ref`
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn run_since() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: echo-files
+                name: echo files
+                entry: python3 -c "import sys; print(sorted(sys.argv[1:]))"
+                language: system
+                verbose: true
+    "#});
+
+    cwd.child("file1.txt").write_str("hello")?;
+    cwd.child("file2.txt").write_str("hello")?;
+    context.git_add(".");
+    context.git_commit("Initial commit");
+    context.git_tag("base");
+
+    cwd.child("file3.txt").write_str("committed")?;
+    context.git_add("file3.txt");
+    context.git_commit("Add file3");
+
+    // Unstaged and untracked changes are selected too, and left in place.
+    cwd.child("file1.txt").write_str("unstaged")?;
+    cwd.child("file4.txt").write_str("untracked")?;
+
+    cmd_snapshot!(context.filters(), context.run().arg("--since").arg("base"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    echo files...............................................................Passed
+    - hook id: echo-files
+    - duration: [TIME]
+
+      ['file1.txt', 'file3.txt', 'file4.txt']
+
+    ----- stderr -----
+    ");
+    assert_eq!(fs_err::read_to_string(cwd.join("file1.txt"))?, "unstaged");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--since").arg("missing"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Cannot resolve revision `missing` passed to `--since`
+    ");
+
+    Ok(())
+}
+
+/// `pass_filenames: n` limits each invocation to at most n files.
+/// With n=1, each matched file gets its own invocation.
+#[test]
+fn pass_filenames_1_limits_batch_size() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    // Use a script that errors if it receives more than one filename argument.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: one-at-a-time
+                name: one at a time
+                entry: python -c "import sys; args = sys.argv[1:]; sys.exit(0 if len(args) <= 1 else 1)"
+                language: system
+                pass_filenames: 1
+                require_serial: true
+                verbose: true
+    "#});
+
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    cwd.child("c.txt").write_str("c")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    one at a time............................................................Passed
+    - hook id: one-at-a-time
+    - duration: [TIME]
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// `pass_filenames: n` limits each invocation to at most n files.
+/// With n=2 and more than 2 matching files, multiple batches are spawned.
+#[test]
+fn pass_filenames_2_limits_batch_size() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    // Use a script that errors if it receives more than two filename arguments.
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: two-at-a-time
+                name: two at a time
+                entry: python -c "import sys; args = sys.argv[1:]; sys.exit(0 if len(args) <= 2 else 1)"
+                language: system
+                pass_filenames: 2
+                require_serial: true
+                verbose: true
+    "#});
+
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    cwd.child("c.txt").write_str("c")?;
+    cwd.child("d.txt").write_str("d")?;
+    cwd.child("e.txt").write_str("e")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -4351,3 +5585,488 @@ fn pass_filenames_2_limits_batch_size() -> Result<()> {
 
     Ok(())
 }
+
+/// A header printed by every batch, like a deprecation warning, is shown once with a count.
+#[test]
+fn repeated_batch_output_is_collapsed() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let cwd = context.work_dir();
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: warn-per-batch
+                name: warn per batch
+                entry: python -c "import sys; print('DeprecationWarning: old option'); print(sys.argv[1], 'checked')"
+                language: system
+                files: \.txt$
+                pass_filenames: 1
+                require_serial: true
+                verbose: true
+    "#});
+
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("b.txt").write_str("b")?;
+    cwd.child("c.txt").write_str("c")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    warn per batch...........................................................Passed
+    - hook id: warn-per-batch
+    - duration: [TIME]
+
+      DeprecationWarning: old option
+      a.txt checked
+      b.txt checked
+      c.txt checked
+
+      Lines repeated across batches (shown once above):
+        (3 batches) DeprecationWarning: old option
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn github_summary() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: pass
+                name: Pass
+                language: system
+                entry: python3 -c "print('ok')"
+                always_run: true
+                pass_filenames: false
+              - id: fail
+                name: Fail
+                language: system
+                entry: python3 -c "print('broken'); exit(1)"
+                always_run: true
+                pass_filenames: false
+              - id: no-files
+                name: No Files
+                language: system
+                entry: python3 -c "exit(1)"
+                files: \.rs$
+    "#});
+    context.git_add(".");
+
+    let summary = context.home_dir().child("summary.md");
+    cmd_snapshot!(context.filters(), context.run()
+        .arg("--github-summary")
+        .env(EnvVars::GITHUB_STEP_SUMMARY, &*summary), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Pass.....................................................................Passed
+    Fail.....................................................................Failed
+    ::group::fail
+    - hook id: fail
+    - exit code: 1
+
+      broken
+    ::endgroup::
+    No Files.................................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    insta::with_settings!({ filters => context.filters() }, {
+        assert_snapshot!(fs_err::read_to_string(&*summary)?, @r"
+        ## prek run
+
+        1 passed, 1 failed, 1 skipped
+
+        | Hook | Status | Duration |
+        | --- | --- | --- |
+        | Pass (`pass`) | ✅ Passed | [TIME] |
+        | Fail (`fail`) | ❌ Failed | [TIME] |
+        | No Files (`no-files`) | ⏭️ Skipped | - |
+
+        ### Failures
+
+        <details>
+        <summary><code>fail</code></summary>
+
+        - exit code: 1
+
+        ```text
+        broken
+        ```
+
+        </details>
+        ");
+    });
+
+    Ok(())
+}
+
+#[test]
+fn run_report_json() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config(indoc::indoc! {r#"
+        repos:
+          - repo: local
+            hooks:
+              - id: pass
+                name: Pass
+                language: system
+                entry: python3 -c "print('ok')"
+                always_run: true
+                pass_filenames: false
+              - id: fail
+                name: Fail
+                language: system
+                entry: python3 -c "import sys; sys.exit(1)"
+                files: \.txt$
+              - id: no-files
+                name: No Files
+                language: system
+                entry: python3 -c "exit(1)"
+                files: \.rs$
+    "#});
+    context.work_dir().child("a.txt").write_str("a")?;
+    context.work_dir().child("b.txt").write_str("b")?;
+    context.git_add(".");
+
+    let report = context.home_dir().child("report.json");
+    context
+        .run()
+        .arg("--report")
+        .arg(format!("json={}", report.display()))
+        .assert()
+        .code(1);
+
+    let report: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(&*report)?)?;
+    assert_eq!(report["version"], 1);
+    assert_eq!(report["success"], false);
+    let hooks = report["hooks"].as_array().unwrap();
+    let summary = hooks
+        .iter()
+        .map(|hook| {
+            format!(
+                "{} {} exit_code={} files={}",
+                hook["id"].as_str().unwrap(),
+                hook["status"].as_str().unwrap(),
+                hook["exit_code"],
+                hook["files"],
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            "pass passed exit_code=0 files=0",
+            "fail failed exit_code=1 files=2",
+            "no-files skipped exit_code=0 files=0",
+        ]
+    );
+    assert!(hooks.iter().all(|hook| hook["duration_ms"].is_u64()));
+
+    Ok(())
+}
+
+#[test]
+fn run_report_invalid_format() {
+    let context = TestContext::new();
+    context.init_project();
+
+    cmd_snapshot!(context.filters(), context.run().arg("--report").arg("xml=report.xml"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'xml=report.xml' for '--report <FORMAT=PATH>': unknown report format `xml`, expected `json` or `junit`
+
+    For more information, try '--help'.
+    ");
+}
+
+#[test]
+fn submodule_pointer_changes_are_tagged() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    let cwd = context.work_dir();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: submodules
+                name: Check submodules
+                language: system
+                entry: echo
+                types: [submodule]
+                verbose: true
+    "});
+    context.git_add(".");
+    context.git_commit("Initial commit");
+
+    let submodule = cwd.child("sub");
+    submodule.create_dir_all()?;
+    git_cmd(&submodule)
+        .args(["-c", "init.defaultBranch=master", "init"])
+        .assert()
+        .success();
+    submodule.child("README.md").write_str("v1\n")?;
+    git_cmd(&submodule)
+        .args(["add", "README.md"])
+        .assert()
+        .success();
+    git_cmd(&submodule)
+        .args(["commit", "-m", "Initial commit"])
+        .assert()
+        .success();
+    git_cmd(cwd)
+        .args(["submodule", "add", "./sub", "sub"])
+        .assert()
+        .success();
+    context.git_commit("Add submodule");
+
+    // A commit that only bumps the submodule pointer.
+    submodule.child("README.md").write_str("v2\n")?;
+    git_cmd(&submodule)
+        .args(["commit", "-am", "Bump"])
+        .assert()
+        .success();
+    context.git_add("sub");
+
+    cmd_snapshot!(context.filters(), context.run(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Check submodules.........................................................Passed
+    - hook id: submodules
+    - duration: [TIME]
+
+      sub
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// Hook commands recorded with `--record` are answered from the recording with `--replay`.
+#[cfg(unix)]
+#[test]
+fn run_record_and_replay() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: check
+                name: check
+                entry: sh check.sh
+                language: system
+                pass_filenames: false
+    "});
+    let cwd = context.work_dir();
+    cwd.child("check.sh")
+        .write_str("echo recorded output\nexit 1\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "--record", "recording"]), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check....................................................................Failed
+    - hook id: check
+    - exit code: 1
+
+      recorded output
+
+    ----- stderr -----
+    ");
+
+    assert!(cwd.child("recording/invocations.jsonl").exists());
+
+    // The hook now passes, but the replay still answers with the recorded failure.
+    cwd.child("check.sh").write_str("echo live output\n")?;
+
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "--replay", "recording"]), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    check....................................................................Failed
+    - hook id: check
+    - exit code: 1
+
+      recorded output
+
+    ----- stderr -----
+    ");
+
+    // A replayed run must not record hooks as passed for later incremental runs.
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "--incremental", "--replay", "recording"]), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--incremental' cannot be used with '--replay <DIR>'
+
+    Usage: prek run --all-files
+
+    For more information, try '--help'.
+    ");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn run_exit_code_mapping() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: lenient
+                name: lenient
+                entry: sh -c 'exit 1'
+                language: system
+                pass_filenames: false
+                success_exit_codes: [0, 1]
+              - id: audit
+                name: audit
+                entry: sh -c 'echo one advisory found; exit 2'
+                language: system
+                pass_filenames: false
+                warning_exit_codes: [2]
+              - id: broken
+                name: broken
+                entry: sh -c 'echo crashed; exit 3'
+                language: system
+                pass_filenames: false
+                warning_exit_codes: [2]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    lenient..................................................................Passed
+    audit...................................................................Warning
+    - hook id: audit
+    - exit code: 2
+
+      one advisory found
+    broken...................................................................Failed
+    - hook id: broken
+    - exit code: 3
+
+      crashed
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().args(["--all-files", "lenient", "audit"]), @"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    lenient..................................................................Passed
+    audit...................................................................Warning
+    - hook id: audit
+    - exit code: 2
+
+      one advisory found
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+#[test]
+fn run_exit_code_mapping_overlap() {
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: audit
+                name: audit
+                entry: audit
+                language: system
+                warning_exit_codes: [0]
+    "});
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to init hooks
+      caused by: Invalid hook `audit`
+      caused by: Exit code `0` is in both `success_exit_codes` and `warning_exit_codes`
+    ");
+}
+
+/// Hooks with `isolation: container` cannot write to the Git directory of the repository.
+#[cfg(target_os = "linux")]
+#[test]
+fn isolation_container_protects_git_dir() -> Result<()> {
+    if std::process::Command::new("bwrap")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        // Bubblewrap is not installed, and the container fallback needs to pull an image.
+        return Ok(());
+    }
+
+    let context = TestContext::new();
+    context.init_project();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: write-git-hooks
+                name: write-git-hooks
+                language: system
+                entry: sh -c 'touch .git/hooks/pre-commit 2>/dev/null'
+                pass_filenames: false
+                always_run: true
+                isolation: container
+    "});
+    context.work_dir().child(".git/hooks").create_dir_all()?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    write-git-hooks..........................................................Failed
+    - hook id: write-git-hooks
+    - exit code: 1
+
+    ----- stderr -----
+    ");
+
+    assert!(!context.work_dir().child(".git/hooks/pre-commit").exists());
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot, git_cmd};
+
+mod common;
+
+/// Repositories are only reported once cloned, `prek sbom` never clones them itself.
+#[test]
+fn sbom_reports_cloned_repos() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/hooks");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir)
+        .arg("-c")
+        .arg("init.defaultBranch=master")
+        .arg("init")
+        .assert()
+        .success();
+    repo_dir
+        .child(".pre-commit-hooks.yaml")
+        .write_str(indoc::indoc! {r"
+        - id: test-hook
+          name: Test Hook
+          entry: echo
+          language: system
+    "})?;
+    git_cmd(&repo_dir).arg("add").arg(".").assert().success();
+    git_cmd(&repo_dir)
+        .arg("commit")
+        .arg("-m")
+        .arg("Initial commit")
+        .assert()
+        .success();
+    git_cmd(&repo_dir)
+        .arg("tag")
+        .arg("v1.0.0")
+        .assert()
+        .success();
+    let repo_path = repo_dir.to_string_lossy().replace('\\', "/");
+
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: {}
+            rev: v1.0.0
+            hooks:
+              - id: test-hook
+    ", repo_path});
+    context.git_add(".");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([
+            (r"[a-f0-9]{40}", "[COMMIT_SHA]"),
+            (
+                r#""name": "prek",(\s*)"version": "[^"]+""#,
+                r#""name": "prek",$1"version": "[VERSION]""#,
+            ),
+        ])
+        .collect::<Vec<_>>();
+
+    cmd_snapshot!(filters.clone(), context.command().arg("sbom"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "bomFormat": "CycloneDX",
+      "specVersion": "1.5",
+      "version": 1,
+      "metadata": {
+        "tools": {
+          "components": [
+            {
+              "type": "application",
+              "name": "prek",
+              "version": "[VERSION]"
+            }
+          ]
+        }
+      },
+      "components": []
+    }
+
+    ----- stderr -----
+    warning: Skipping `[HOME]/test-repos/hooks`, which is not cloned yet; run `prek prepare-hooks` to include it
+    "#);
+
+    context.prepare_hooks().assert().success();
+
+    cmd_snapshot!(filters, context.command().arg("sbom"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "bomFormat": "CycloneDX",
+      "specVersion": "1.5",
+      "version": 1,
+      "metadata": {
+        "tools": {
+          "components": [
+            {
+              "type": "application",
+              "name": "prek",
+              "version": "[VERSION]"
+            }
+          ]
+        }
+      },
+      "components": [
+        {
+          "type": "application",
+          "bom-ref": "repo:[HOME]/test-repos/hooks@v1.0.0",
+          "name": "[HOME]/test-repos/hooks",
+          "version": "v1.0.0",
+          "externalReferences": [
+            {
+              "type": "vcs",
+              "url": "[HOME]/test-repos/hooks"
+            }
+          ],
+          "properties": [
+            {
+              "name": "prek:commit",
+              "value": "[COMMIT_SHA]"
+            },
+            {
+              "name": "prek:hooks",
+              "value": "test-hook"
+            }
+          ]
+        }
+      ]
+    }
+
+    ----- stderr -----
+    "#);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::process::Stdio;
+
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+
+use crate::common::{TestContext, cmd_snapshot, git_cmd};
+
+mod common;
+
+/// Hooks come from the default branch of the receiving repository, a config in the pushed
+/// commit must not run.
+#[cfg(unix)]
+#[test]
+fn server_hook_ignores_pushed_config() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let server = context.home_dir().child("server.git");
+    git_cmd(context.home_dir())
+        .args([
+            "-c",
+            "init.defaultBranch=master",
+            "init",
+            "--bare",
+            "server.git",
+        ])
+        .assert()
+        .success();
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: trusted
+                name: Trusted
+                entry: echo
+                language: system
+                pass_filenames: false
+                always_run: true
+    "});
+    context.git_add(".");
+    context.git_commit("Trusted config");
+    git_cmd(context.work_dir())
+        .arg("push")
+        .arg(&*server)
+        .arg("master")
+        .assert()
+        .success();
+
+    let marker = context.home_dir().child("pwned");
+    context.write_pre_commit_config(&indoc::formatdoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: malicious
+                name: Malicious
+                entry: touch {}
+                language: system
+                pass_filenames: false
+                always_run: true
+    ", marker.display()});
+    context.git_add(".");
+    context.git_commit("Malicious config");
+    git_cmd(context.work_dir())
+        .arg("push")
+        .arg(&*server)
+        .arg("HEAD:refs/heads/feature")
+        .assert()
+        .success();
+
+    let output = git_cmd(context.work_dir())
+        .args(["rev-parse", "HEAD"])
+        .output()?;
+    let new = String::from_utf8(output.stdout)?;
+    context.home_dir().child("ref-updates").write_str(&format!(
+        "0000000000000000000000000000000000000000 {} refs/heads/feature\n",
+        new.trim()
+    ))?;
+    let stdin = std::fs::File::open(context.home_dir().child("ref-updates"))?;
+
+    cmd_snapshot!(context.filters(), context.command().arg("server-hook").current_dir(&*server).stdin(Stdio::from(stdin)), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Trusted..................................................................Passed
+    refs/heads/feature: Passed
+
+    ----- stderr -----
+    ");
+
+    assert!(
+        !marker.exists(),
+        "the config of the pushed commit must not run"
+    );
+
+    Ok(())
+}
@@ -5,7 +5,7 @@
 //! execution across priority groups.
 //!
 //! Includes regression tests for #1335: when all hooks in a group are skipped,
-//! prek should not call `git diff` to check for file modifications.
+//! prek should not call `git status` to check for file modifications.
 
 use std::time::{Duration, SystemTime};
 
@@ -408,10 +408,10 @@ fn orphan_project_early_match_still_hides_child_files_from_parent_install() -> R
 /// Hooks with different `priority` values form separate priority groups. Each
 /// group is processed sequentially. This test verifies:
 /// 1. Skip behavior works correctly across group boundaries
-/// 2. `git diff` is not called when every hook is skipped
+/// 2. `git status` is not called when every hook is skipped
 ///
 /// Note: This test uses manual output capture instead of `cmd_snapshot!` because
-/// we need to count `get_diff` occurrences in trace-level stderr. Trace output
+/// we need to count `status_snapshot` occurrences in trace-level stderr. Trace output
 /// contains non-deterministic timestamps and timing data unsuitable for snapshots.
 #[test]
 fn all_hooks_skipped_multiple_priority_groups() -> Result<()> {
@@ -460,10 +460,10 @@ fn all_hooks_skipped_multiple_priority_groups() -> Result<()> {
 
     // Regression test for #1335: skipped hooks do not need modification checks.
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let get_diff_calls = stderr.matches("get_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        get_diff_calls, 0,
-        "Expected no get_diff calls when all hooks skip, found {get_diff_calls}.\n\
+        status_calls, 0,
+        "Expected no status_snapshot calls when all hooks skip, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -471,7 +471,7 @@ fn all_hooks_skipped_multiple_priority_groups() -> Result<()> {
 }
 
 #[test]
-fn may_modify_hook_without_changes_uses_quiet_diff_check() -> Result<()> {
+fn may_modify_hook_without_changes_uses_single_status_check() -> Result<()> {
     let context = TestContext::new();
     context.init_project();
 
@@ -495,17 +495,10 @@ fn may_modify_hook_without_changes_uses_quiet_diff_check() -> Result<()> {
     assert!(output.status.success(), "noop hook should pass");
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let has_worktree_diff_calls = stderr.matches("has_worktree_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        has_worktree_diff_calls, 1,
-        "Expected one cheap worktree diff check, found {has_worktree_diff_calls}.\n\
-         Trace output:\n{stderr}"
-    );
-
-    let get_diff_calls = stderr.matches("get_diff").count();
-    assert_eq!(
-        get_diff_calls, 0,
-        "Expected no full get_diff calls when the hook leaves files unchanged, found {get_diff_calls}.\n\
+        status_calls, 1,
+        "Expected one git status check when the hook leaves files unchanged, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -557,17 +550,10 @@ fn identical_rewrite_with_stat_change_is_not_modified() -> Result<()> {
     assert!(!stdout.contains("files were modified by this hook"));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let has_worktree_diff_calls = stderr.matches("has_worktree_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        has_worktree_diff_calls, 1,
-        "Expected one cheap worktree diff check, found {has_worktree_diff_calls}.\n\
-         Trace output:\n{stderr}"
-    );
-
-    let get_diff_calls = stderr.matches("get_diff").count();
-    assert_eq!(
-        get_diff_calls, 1,
-        "Expected one content diff to filter out stat-only changes, found {get_diff_calls}.\n\
+        status_calls, 1,
+        "Expected one git status check to filter out stat-only changes, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -605,17 +591,10 @@ fn modifying_hook_uses_clean_baseline_diff_detection() -> Result<()> {
     assert!(stdout.contains("files were modified by this hook"));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let has_worktree_diff_calls = stderr.matches("has_worktree_diff").count();
-    assert_eq!(
-        has_worktree_diff_calls, 1,
-        "Expected one cheap worktree diff check, found {has_worktree_diff_calls}.\n\
-         Trace output:\n{stderr}"
-    );
-
-    let get_diff_calls = stderr.matches("get_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        get_diff_calls, 1,
-        "Expected one full get_diff call after detecting modifications, found {get_diff_calls}.\n\
+        status_calls, 1,
+        "Expected one git status check to detect modifications, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -659,17 +638,10 @@ fn all_files_with_existing_unstaged_changes_uses_snapshot_baseline() -> Result<(
     assert!(stdout.contains("files were modified by this hook"));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let has_worktree_diff_calls = stderr.matches("has_worktree_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        has_worktree_diff_calls, 0,
-        "`--all-files` should not use the clean-baseline diff check.\n\
-         Trace output:\n{stderr}"
-    );
-
-    let get_diff_calls = stderr.matches("get_diff").count();
-    assert_eq!(
-        get_diff_calls, 2,
-        "Expected a full before/after diff comparison for dirty `--all-files`, found {get_diff_calls}.\n\
+        status_calls, 2,
+        "Expected a before/after git status comparison for dirty `--all-files`, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -699,9 +671,9 @@ fn all_files_clean_missing_blob_ignores_diff_snapshot_errors() -> Result<()> {
 
     remove_loose_blob(cwd, "file.txt")?;
 
-    // Make the index stat data stale while keeping file content unchanged. A
-    // full `git diff` now exits non-zero because the blob is missing, but its
-    // stdout is still a usable best-effort before/after snapshot.
+    // Make the index stat data stale while keeping file content unchanged.
+    // `git status` hashes the worktree content to compare it with the index,
+    // so it does not need the missing blob.
     fs_err::OpenOptions::new()
         .write(true)
         .open(cwd.child("file.txt").path())?
@@ -723,17 +695,14 @@ fn all_files_clean_missing_blob_ignores_diff_snapshot_errors() -> Result<()> {
     );
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let ignored_diff_errors = stderr
-        .matches("Continuing with git diff stdout despite non-zero exit status")
-        .count();
-    assert_eq!(
-        ignored_diff_errors, 2,
-        "Expected before/after git diff errors to be logged and ignored, found {ignored_diff_errors}.\n\
+    assert!(
+        !stderr.contains("Continuing with git status stdout despite non-zero exit status"),
+        "missing blobs should not make git status fail.\n\
          Trace output:\n{stderr}"
     );
     assert!(
-        !stderr.contains("Command `git diff` exited with an error"),
-        "missing blobs should not turn hook modification detection into a fatal git diff error.\n\
+        !stderr.contains("Command `git status` exited with an error"),
+        "missing blobs should not turn hook modification detection into a fatal git status error.\n\
          stderr:\n{stderr}"
     );
 
@@ -793,10 +762,11 @@ fn later_project_snapshots_diff_left_by_previous_project() -> Result<()> {
     );
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let has_worktree_diff_calls = stderr.matches("has_worktree_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        has_worktree_diff_calls, 1,
-        "Only the first project should use the clean-baseline check.\n\
+        status_calls, 3,
+        "Expected one clean-baseline check for the first project and a before/after \
+         snapshot for the second, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
@@ -829,10 +799,10 @@ fn read_only_builtin_hook_does_not_run_diff_detection() -> Result<()> {
     assert!(output.status.success(), "prek should succeed");
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let get_diff_calls = stderr.matches("get_diff").count();
+    let status_calls = stderr.matches("status_snapshot").count();
     assert_eq!(
-        get_diff_calls, 0,
-        "Expected no get_diff calls for read-only builtin hooks, found {get_diff_calls}.\n\
+        status_calls, 0,
+        "Expected no status_snapshot calls for read-only builtin hooks, found {status_calls}.\n\
          Trace output:\n{stderr}"
     );
 
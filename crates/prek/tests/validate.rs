@@ -65,6 +65,56 @@ fn validate_config() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn validate_workspace_configs() -> anyhow::Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let invalid = indoc::indoc! {r"
+        repos:
+          - repo: https://github.com/pre-commit/pre-commit-hooks
+    "};
+    context.write_pre_commit_config("repos: []\n");
+    context
+        .work_dir()
+        .child("app/.pre-commit-config.yaml")
+        .write_str(invalid)?;
+    context
+        .work_dir()
+        .child("app/lib/prek.toml")
+        .write_str("repos = []\n")?;
+    context
+        .work_dir()
+        .child("web/.pre-commit-config.yaml")
+        .write_str(invalid)?;
+
+    // Without arguments, every project config in the workspace is validated and each invalid
+    // one is reported.
+    cmd_snapshot!(context.filters(), context.validate_config(), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `app/.pre-commit-config.yaml`
+      caused by: error: line 2 column 5: missing field `rev`
+     --> <input>:2:5
+      |
+    1 | repos:
+    2 |   - repo: https://github.com/pre-commit/pre-commit-hooks
+      |     ^ missing field `rev`
+    error: Failed to parse `web/.pre-commit-config.yaml`
+      caused by: error: line 2 column 5: missing field `rev`
+     --> <input>:2:5
+      |
+    1 | repos:
+    2 |   - repo: https://github.com/pre-commit/pre-commit-hooks
+      |     ^ missing field `rev`
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn mutable_revision_warning_has_actionable_guidance() {
     let context = TestContext::new();
@@ -167,6 +217,92 @@ fn unknown_priority_alias_is_invalid() {
     ");
 }
 
+#[test]
+fn duplicate_hook_aliases_warn() {
+    let context = TestContext::new();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: ruff
+                name: Ruff
+                entry: ruff check
+                language: system
+              - id: ruff
+                alias: ruff-fix
+                name: Ruff (fix)
+                entry: ruff check --fix
+                language: system
+    "});
+
+    // Repeating a hook id is fine as long as the aliases tell them apart.
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    success: All configs are valid
+    ");
+
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: ruff
+                alias: lint
+                name: Ruff
+                entry: ruff check
+                language: system
+          - repo: local
+            hooks:
+              - id: mypy
+                alias: lint
+                name: Mypy
+                entry: mypy
+                language: system
+    "});
+
+    // A shared alias selects every hook using it.
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Alias `lint` of hook `mypy` at `repos[1].hooks[0]` is also used by hook `ruff` at `repos[0].hooks[0]`, selecting it runs both hooks
+    success: All configs are valid
+    ");
+}
+
+#[test]
+fn hook_alias_cannot_shadow_another_hook_id() {
+    let context = TestContext::new();
+    context.write_pre_commit_config(indoc::indoc! {r"
+        repos:
+          - repo: local
+            hooks:
+              - id: ruff
+                alias: mypy
+                name: Ruff
+                entry: ruff check
+                language: system
+              - id: mypy
+                name: Mypy
+                entry: mypy
+                language: system
+    "});
+
+    cmd_snapshot!(context.filters(), context.validate_config().arg(PRE_COMMIT_CONFIG_YAML), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Alias `mypy` of hook `ruff` at `repos[0].hooks[0]` collides with the id of hook `mypy` at `repos[0].hooks[1]`
+    ");
+}
+
 #[test]
 fn priority_aliases_cannot_contain_whitespace() {
     let context = TestContext::new();
@@ -2,6 +2,7 @@ mod common;
 
 use anyhow::Result;
 use assert_cmd::assert::OutputAssertExt;
+use assert_fs::assert::PathAssert;
 use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
 use indoc::indoc;
 use prek_consts::env_vars::EnvVars;
@@ -200,6 +201,67 @@ fn basic_discovery() -> Result<()> {
     Ok(())
 }
 
+/// `.prekignore` files nested inside a project hide matching files from hooks, unless they are
+/// passed explicitly. A `.prekignore` next to a config file only affects discovery.
+#[test]
+fn nested_prekignore_filters_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+    let cwd = context.work_dir();
+
+    context.write_pre_commit_config(indoc! {r"
+    repos:
+      - repo: local
+        hooks:
+        - id: show-files
+          name: Show Files
+          language: system
+          entry: echo
+          verbose: true
+          types: [text]
+    "});
+    cwd.child(".prekignore").write_str("*.gen.txt\n")?;
+    cwd.child("a.txt").write_str("a")?;
+    cwd.child("a.gen.txt").write_str("a")?;
+    cwd.child("src/b.txt").write_str("b")?;
+    cwd.child("src/b.gen.txt").write_str("b")?;
+    cwd.child("src/generated/c.txt").write_str("c")?;
+    cwd.child("src/.prekignore")
+        .write_str("*.gen.txt\ngenerated/\n")?;
+    cwd.child("src/keep/d.gen.txt").write_str("d")?;
+    cwd.child("src/keep/.prekignore")
+        .write_str("!*.gen.txt\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      .pre-commit-config.yaml .prekignore a.gen.txt a.txt src/.prekignore src/b.txt src/keep/.prekignore src/keep/d.gen.txt
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--files").arg("src/b.gen.txt").arg("src/generated/c.txt"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      src/b.gen.txt src/generated/c.txt
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn same_depth_project_concurrency_has_stable_output() -> Result<()> {
     let context = TestContext::new();
@@ -1185,6 +1247,98 @@ fn nested_project_exclude_is_relative() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn nested_project_files_from_repo_root() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc! {r#"
+    exclude: \.pre-commit-config\.yaml$
+    repos:
+      - repo: local
+        hooks:
+        - id: show-files
+          name: Show Files
+          language: python
+          entry: python -c 'import os, sys; [print("  - {} {}".format(f, os.path.isfile(f))) for f in sys.argv[1:]]'
+          files: ^include$
+          files_from_repo_root: true
+          verbose: true
+    "#};
+
+    context.setup_workspace(&["nested"], config)?;
+    context.work_dir().child("nested/include").write_str("")?;
+    context.git_add(".");
+
+    // `files` still matches project-relative paths, but the hook receives
+    // repository-relative paths and runs from the repository root.
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ nested
+      Show Files.............................................................Passed
+      - hook id: show-files
+      - duration: [TIME]
+
+          - nested/include True
+    ✓ <workspace>
+      Show Files.........................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// In-process builtin hooks with `files_from_repo_root` read the repository-relative paths
+/// from the repository root instead of prefixing them with the project path again.
+#[test]
+fn nested_project_builtin_files_from_repo_root() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc! {r"
+    exclude: \.pre-commit-config\.yaml$
+    repos:
+      - repo: builtin
+        hooks:
+        - id: end-of-file-fixer
+          files_from_repo_root: true
+    "};
+
+    context.setup_workspace(&["nested"], config)?;
+    context
+        .work_dir()
+        .child("nested/file.txt")
+        .write_str("content")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    × nested
+      fix end of files.........................................................Failed
+      - hook id: end-of-file-fixer
+      - exit code: 1
+      - files were modified by this hook
+
+        Fixing nested/file.txt
+    ✓ <workspace>
+      fix end of files.....................................(no files to check)Skipped
+
+    ----- stderr -----
+    ");
+
+    context
+        .work_dir()
+        .child("nested/file.txt")
+        .assert("content\n");
+
+    Ok(())
+}
+
 /// Tests that `--files` arguments references files in other projects, should be filtered out properly.
 #[test]
 fn reference_files_across_projects() -> Result<()> {
@@ -1364,6 +1518,101 @@ fn cookiecutter_template_directories_are_skipped() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn configured_project_config_files() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let hook = indoc! {r"
+      - repo: local
+        hooks:
+        - id: show-cwd
+          name: Show CWD
+          language: python
+          entry: python -c 'import sys, os; print(os.getcwd()); print(sys.argv[1:])'
+          verbose: true
+    "};
+    context.write_pre_commit_config(&format!(
+        "projects:\n  api: ci/pre-commit.yaml\nrepos:\n{hook}"
+    ));
+    context
+        .work_dir()
+        .child("api/ci/pre-commit.yaml")
+        .write_str(&format!("repos:\n{hook}"))?;
+    context.git_add(".");
+
+    // The project runs from its own directory, not from the directory of its config file.
+    cmd_snapshot!(context.filters(), context.run().arg("--refresh").arg("--all-files"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ api
+      Show CWD...............................................................Passed
+      - hook id: show-cwd
+      - duration: [TIME]
+
+        [TEMP_DIR]/api
+        ['ci/pre-commit.yaml']
+    ✓ <workspace>
+      Show CWD...............................................................Passed
+      - hook id: show-cwd
+      - duration: [TIME]
+
+        [TEMP_DIR]/
+        ['.pre-commit-config.yaml', 'api/ci/pre-commit.yaml']
+
+    ----- stderr -----
+    "#);
+
+    // Projects loaded from the workspace cache keep their project directory.
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("api/"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ api
+      Show CWD...............................................................Passed
+      - hook id: show-cwd
+      - duration: [TIME]
+
+        [TEMP_DIR]/api
+        ['ci/pre-commit.yaml']
+
+    ----- stderr -----
+    "#);
+
+    Ok(())
+}
+
+/// An invalid root configuration is reported, instead of silently losing its `projects` mapping.
+#[test]
+fn configured_projects_invalid_root_config() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    context.write_pre_commit_config("invalid: config\nprojects:\n  api: ci/pre-commit.yaml\n");
+    context
+        .work_dir()
+        .child("api/ci/pre-commit.yaml")
+        .write_str("repos: []\n")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--refresh").arg("--all-files").arg("api/"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `.pre-commit-config.yaml`
+      caused by: error: line 1 column 1: missing field `repos`
+     --> <input>:1:1
+      |
+    1 | invalid: config
+      | ^ missing field `repos`
+    ");
+
+    Ok(())
+}
+
 #[test]
 fn orphan_projects() -> Result<()> {
     let context = TestContext::new();
@@ -1536,6 +1785,260 @@ fn orphan_projects() -> Result<()> {
     Ok(())
 }
 
+/// `--flatten` runs root hooks once over the files of every project, including orphan projects.
+#[test]
+fn flatten_workspace() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc! {r#"
+    exclude: \.pre-commit-config\.yaml$
+    repos:
+      - repo: local
+        hooks:
+        - id: show-files
+          name: Show Files
+          language: system
+          entry: python3 -c 'import sys; print("Processing {} files".format(len(sys.argv[1:]))); [print("  - {}".format(f)) for f in sys.argv[1:]]'
+          verbose: true
+    "#};
+
+    context
+        .work_dir()
+        .child("src/backend/.pre-commit-config.yaml")
+        .write_str(&format!("orphan: true\n{config}"))?;
+    context
+        .work_dir()
+        .child("src/.pre-commit-config.yaml")
+        .write_str(config)?;
+    context
+        .work_dir()
+        .child(".pre-commit-config.yaml")
+        .write_str(config)?;
+
+    context
+        .work_dir()
+        .child("src/backend/test.py")
+        .write_str("")?;
+    context.work_dir().child("src/test.py").write_str("")?;
+    context.work_dir().child("test.py").write_str("")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("show-files").arg("--all-files").arg("--flatten"), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Show Files...............................................................Passed
+    - hook id: show-files
+    - duration: [TIME]
+
+      Processing 3 files
+        - src/backend/test.py
+        - src/test.py
+        - test.py
+
+    ----- stderr -----
+    "#);
+
+    Ok(())
+}
+
+/// `--flatten` only drops a nested hook that is configured exactly like a root hook, a hook
+/// with the same id but different args still runs for its project.
+#[test]
+fn flatten_workspace_keeps_differently_configured_hooks() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = |arg: &str| {
+        indoc::formatdoc! {r"
+        repos:
+          - repo: local
+            hooks:
+            - id: greet
+              name: Greet
+              language: system
+              entry: echo
+              args: [{arg}]
+              pass_filenames: false
+              verbose: true
+        "}
+    };
+    context
+        .work_dir()
+        .child("src/.pre-commit-config.yaml")
+        .write_str(&config("nested"))?;
+    context
+        .work_dir()
+        .child(".pre-commit-config.yaml")
+        .write_str(&config("root"))?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--flatten"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ✓ src
+      Greet..................................................................Passed
+      - hook id: greet
+      - duration: [TIME]
+
+        nested
+    ✓ <workspace>
+      Greet..................................................................Passed
+      - hook id: greet
+      - duration: [TIME]
+
+        root
+
+    ----- stderr -----
+    warning: hook `greet` is not configured the same way in the workspace root, `--flatten` runs it for each project
+    ");
+
+    Ok(())
+}
+
+/// `--dedup-hooks` runs an identical remote hook of several projects once, and reports the
+/// output lines naming each project's files under that project.
+#[cfg(unix)]
+#[test]
+fn dedup_hooks_workspace() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let context = TestContext::new();
+    context.init_project();
+
+    let repo_dir = context.home_dir().child("test-repos/hooks");
+    repo_dir.create_dir_all()?;
+    git_cmd(&repo_dir).arg("init").assert().success();
+    repo_dir.child(PRE_COMMIT_HOOKS_YAML).write_str(indoc! {r"
+        - id: check
+          name: Check
+          entry: check.sh
+          language: script
+    "})?;
+    repo_dir.child("check.sh").write_str(indoc! {r#"
+        #!/bin/sh
+        echo "checked $# files"
+        status=0
+        for f in "$@"; do
+          case "$f" in *bad*) echo "misspelling in $f"; status=1;; esac
+        done
+        exit $status
+    "#})?;
+    fs_err::set_permissions(
+        repo_dir.child("check.sh").path(),
+        std::fs::Permissions::from_mode(0o755),
+    )?;
+    git_cmd(&repo_dir).args(["add", "."]).assert().success();
+    git_cmd(&repo_dir)
+        .args(["commit", "-m", "Initial commit"])
+        .assert()
+        .success();
+    let output = git_cmd(&repo_dir).args(["rev-parse", "HEAD"]).output()?;
+    let commit_sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let config = indoc::formatdoc! {r"
+        exclude: \.pre-commit-config\.yaml$
+        repos:
+          - repo: {}
+            rev: {commit_sha}
+            hooks:
+              - id: check
+                verbose: true
+    ", repo_dir.display()};
+    context
+        .work_dir()
+        .child("app/.pre-commit-config.yaml")
+        .write_str(&format!("orphan: true\n{config}"))?;
+    context
+        .work_dir()
+        .child(PRE_COMMIT_CONFIG_YAML)
+        .write_str(&config)?;
+    context.work_dir().child("app/bad.txt").write_str("")?;
+    context.work_dir().child("ok.txt").write_str("")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--dedup-hooks"), @"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    × app
+      Check..................................................................Failed
+      - hook id: check
+      - duration: [TIME]
+      - exit code: 1
+
+        checked 2 files
+        misspelling in app/bad.txt
+    ✓ <workspace>
+      Check..................................................................Passed
+      - hook id: check
+      - duration: [TIME]
+
+        checked 2 files
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}
+
+/// A deduplicated fast-path hook runs from the repository root with repository-relative
+/// paths, so it fixes the files of a nested project in place.
+#[test]
+fn dedup_hooks_nested_fast_path() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc! {r"
+    exclude: \.pre-commit-config\.yaml$
+    repos:
+      - repo: https://github.com/pre-commit/pre-commit-hooks
+        rev: v5.0.0
+        hooks:
+          - id: end-of-file-fixer
+    "};
+
+    context.setup_workspace(&["nested"], config)?;
+    context
+        .work_dir()
+        .child("nested/file.txt")
+        .write_str("nested")?;
+    context.work_dir().child("root.txt").write_str("root")?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.run().arg("--all-files").arg("--dedup-hooks"), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    × nested
+      fix end of files.........................................................Failed
+      - hook id: end-of-file-fixer
+      - exit code: 1
+      - files were modified by this hook
+
+        Fixing nested/file.txt
+    × <workspace>
+      fix end of files.........................................................Failed
+      - hook id: end-of-file-fixer
+      - exit code: 1
+      - files were modified by this hook
+
+        Fixing root.txt
+
+    ----- stderr -----
+    ");
+
+    context
+        .work_dir()
+        .child("nested/file.txt")
+        .assert("nested\n");
+    context.work_dir().child("root.txt").assert("root\n");
+
+    Ok(())
+}
+
 fn setup_relative_repo_path_project() -> Result<TestContext> {
     let context = TestContext::new();
     context.init_project();
@@ -1642,3 +2145,75 @@ fn relative_repo_path_resolution_with_explicit_relative_config() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn refresh_selected_projects() -> Result<()> {
+    let context = TestContext::new();
+    context.init_project();
+
+    let config = indoc! {r"
+    repos:
+      - repo: local
+        hooks:
+        - id: show
+          name: Show
+          language: system
+          entry: echo
+    "};
+    context.setup_workspace(&["project1", "project2"], config)?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.list(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project1:show
+    project2:show
+    .:show
+
+    ----- stderr -----
+    ");
+
+    // A new project is not picked up while the cached workspace is still valid.
+    let nested = context.work_dir().child("project2/nested");
+    nested.create_dir_all()?;
+    nested.child(PRE_COMMIT_CONFIG_YAML).write_str(config)?;
+    context.git_add(".");
+
+    cmd_snapshot!(context.filters(), context.list(), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project1:show
+    project2:show
+    .:show
+
+    ----- stderr -----
+    ");
+
+    // Refreshing an unrelated project keeps the cached state of the others.
+    cmd_snapshot!(context.filters(), context.list().arg("--refresh=project1"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project1:show
+    project2:show
+    .:show
+
+    ----- stderr -----
+    ");
+
+    cmd_snapshot!(context.filters(), context.list().arg("--refresh=project2"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project2/nested:show
+    project1:show
+    project2:show
+    .:show
+
+    ----- stderr -----
+    ");
+
+    Ok(())
+}